@@ -5,6 +5,7 @@ use std::process::Command;
 fn main() {
     println!("cargo:rerun-if-changed=msc-shim/src/main.rs");
     println!("cargo:rerun-if-changed=msc-shim/Cargo.toml");
+    println!("cargo:rerun-if-changed=src/core/alias_schema.rs");
 
     // Get the output directory
     let out_dir = env::var("OUT_DIR").unwrap();
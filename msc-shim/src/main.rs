@@ -2,19 +2,14 @@ use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::process::{Command, exit};
-use serde::Deserialize;
-use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Alias data structure matching aliases.json
-#[derive(Debug, Deserialize)]
-struct AliasConfig {
-    aliases: HashMap<String, AliasData>,
-}
+/// Alias data structures shared with msc (layout of aliases.json)
+#[allow(dead_code)]
+#[path = "../../src/core/alias_schema.rs"]
+mod alias_schema;
 
-#[derive(Debug, Deserialize)]
-struct AliasData {
-    command: String,
-}
+use alias_schema::{expand_command, AliasEntry, AliasFile, AliasStats, STATS_FILE_NAME};
 
 fn main() {
     // Get the name by which this executable was invoked
@@ -35,26 +30,27 @@ fn main() {
         }
     };
 
-    let command = match load_command(&config_path, &alias_name) {
-        Ok(cmd) => cmd,
+    let (alias, track_usage) = match load_alias(&config_path, &alias_name) {
+        Ok(loaded) => loaded,
         Err(e) => {
             eprintln!("Error: {}", e);
             exit(1);
         }
     };
 
+    // Usage tracking is opt-in and must never block the alias itself
+    if track_usage {
+        record_usage(&config_path.with_file_name(STATS_FILE_NAME), &alias_name);
+    }
+
     // Get arguments passed to the alias
     let args: Vec<String> = env::args().skip(1).collect();
 
-    // Build the full command
-    let full_command = if args.is_empty() {
-        command
-    } else {
-        format!("{} {}", command, args.join(" "))
-    };
+    // Build the full command, filling {1}/{@} placeholders
+    let full_command = expand_command(&alias.command, &args);
 
     // Execute the command
-    let exit_code = execute_command(&full_command);
+    let exit_code = execute_command(&full_command, &alias);
     exit(exit_code);
 }
 
@@ -82,8 +78,8 @@ fn get_config_path() -> Result<PathBuf, String> {
         .join("aliases.json"))
 }
 
-/// Load the command for a specific alias
-fn load_command(config_path: &PathBuf, alias_name: &str) -> Result<String, String> {
+/// Load a specific alias, plus whether usage tracking is enabled
+fn load_alias(config_path: &PathBuf, alias_name: &str) -> Result<(AliasEntry, bool), String> {
     // Check if config file exists
     if !config_path.exists() {
         return Err(
@@ -96,14 +92,15 @@ fn load_command(config_path: &PathBuf, alias_name: &str) -> Result<String, Strin
         .map_err(|e| format!("Failed to read config: {}", e))?;
 
     // Parse JSON
-    let config: AliasConfig = serde_json::from_str(&content)
+    let mut config: AliasFile = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse config: {}", e))?;
 
     // Find alias
+    let track_usage = config.track_usage;
     config
         .aliases
-        .get(alias_name)
-        .map(|a| a.command.clone())
+        .remove(alias_name)
+        .map(|a| (a, track_usage))
         .ok_or_else(|| {
             format!(
                 "Alias '{}' not found. Run 'msc alias list' to see available aliases.",
@@ -112,17 +109,46 @@ fn load_command(config_path: &PathBuf, alias_name: &str) -> Result<String, Strin
         })
 }
 
-/// Execute the command and return exit code
-fn execute_command(command: &str) -> i32 {
+/// Increment the run counter for an alias (best effort, errors are ignored)
+fn record_usage(stats_path: &PathBuf, alias_name: &str) {
+    let mut stats: AliasStats = fs::read_to_string(stats_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    stats.record(alias_name, now);
+
+    // Write through a temp file so a concurrent run never sees a partial file
+    if let Ok(content) = serde_json::to_string_pretty(&stats) {
+        let tmp_path = stats_path.with_extension(format!("json.{}.tmp", std::process::id()));
+        if fs::write(&tmp_path, content).is_ok() && fs::rename(&tmp_path, stats_path).is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+    }
+}
+
+/// Execute the command in the alias' directory and environment, returning the exit code
+fn execute_command(command: &str, alias: &AliasEntry) -> i32 {
     #[cfg(target_os = "windows")]
-    let status = Command::new("cmd")
-        .args(["/C", command])
-        .status();
+    let mut process = Command::new("cmd");
+    #[cfg(target_os = "windows")]
+    process.args(["/C", command]);
 
     #[cfg(not(target_os = "windows"))]
-    let status = Command::new("sh")
-        .args(["-c", command])
-        .status();
+    let mut process = Command::new("sh");
+    #[cfg(not(target_os = "windows"))]
+    process.args(["-c", command]);
+
+    if let Some(cwd) = &alias.cwd {
+        process.current_dir(cwd);
+    }
+    process.envs(&alias.env);
+
+    let status = process.status();
 
     match status {
         Ok(exit_status) => exit_status.code().unwrap_or(1),
@@ -1,16 +1,31 @@
 use anyhow::{Context, Result};
 use clap::ArgMatches;
-use colored::Colorize;
-
+use colored::{Color, Colorize};
+use std::io::Write;
+
+use crate::commands::config::ALLOW_SHELL_OPERATORS;
+use crate::core::alias::{
+    prune_candidates, run_alias, AliasEntry, AliasFilter, AliasListing, AliasStats, ShellRunner,
+};
+use crate::core::alias_doctor::{fix_shim_issues, inspect_shims, ShimIssue};
+use crate::core::path_manager::PathChange;
 use crate::core::{get_generator, Alias, AliasConfig, Config, PathManager};
+use crate::error::CommandFailed;
+use crate::ui::{Cell, Table};
+use crate::utils::path::expand_path;
 
 /// Main handler for alias commands
 pub fn handle_alias(matches: &ArgMatches) -> Result<()> {
     match matches.subcommand() {
         Some(("add", sub_matches)) => handle_add(sub_matches),
+        Some(("edit", sub_matches)) => handle_edit(sub_matches),
         Some(("remove", sub_matches)) => handle_remove(sub_matches),
+        Some(("run", sub_matches)) => handle_run(sub_matches),
         Some(("list", sub_matches)) => handle_list(sub_matches),
+        Some(("stats", sub_matches)) => handle_stats(sub_matches),
+        Some(("prune", sub_matches)) => handle_prune(sub_matches),
         Some(("init", sub_matches)) => handle_init(sub_matches),
+        Some(("doctor", sub_matches)) => handle_doctor(sub_matches),
         Some(("nuke", sub_matches)) => handle_nuke(sub_matches),
         _ => {
             println!("Use 'msc alias --help' for more information.");
@@ -28,6 +43,7 @@ fn handle_add(matches: &ArgMatches) -> Result<()> {
         .get_one::<String>("command")
         .context("Command is required")?;
     let description = matches.get_one::<String>("description");
+    let allow_unsafe = matches.get_flag("unsafe");
 
     // Validate alias name
     if !is_valid_alias_name(name) {
@@ -49,13 +65,32 @@ fn handle_add(matches: &ArgMatches) -> Result<()> {
         );
     }
 
+    if allow_unsafe {
+        ensure_shell_operators_allowed()?;
+    }
+
     // Create alias (this validates the command for security)
-    let alias = if let Some(desc) = description {
+    let mut alias = if allow_unsafe {
+        let mut alias = Alias::new_unsafe(name.clone(), command.clone())?;
+        alias.description = description.cloned();
+        alias
+    } else if let Some(desc) = description {
         Alias::with_description(name.clone(), command.clone(), desc.clone())?
     } else {
         Alias::new(name.clone(), command.clone())?
     };
 
+    if let Some(cwd) = matches.get_one::<String>("cwd") {
+        alias.set_cwd(&expand_path(cwd)?)?;
+    }
+    for assignment in matches.get_many::<String>("env").unwrap_or_default() {
+        alias.set_env(assignment)?;
+    }
+    alias.set_category(matches.get_one::<String>("category").map(String::as_str))?;
+    for tag in matches.get_many::<String>("tag").unwrap_or_default() {
+        alias.add_tag(tag)?;
+    }
+
     // Add to config
     config.add_alias(alias.clone());
 
@@ -87,6 +122,22 @@ fn handle_add(matches: &ArgMatches) -> Result<()> {
         );
     }
     println!("  Command: {}", command.cyan());
+    if alias.unsafe_shell {
+        println!(
+            "  {}",
+            "⚠️  Unsafe: the command runs in the shell exactly as written.".red()
+        );
+    }
+    if let Some(cwd) = &alias.cwd {
+        println!("  Directory: {}", cwd.cyan());
+    }
+    for (key, value) in &alias.env {
+        println!("  Env: {}={}", key, value.cyan());
+    }
+    let labels = format_labels(&alias);
+    if !labels.is_empty() {
+        println!("  {}", labels);
+    }
 
     // Check if bin dir is in PATH
     if !PathManager::is_in_path().unwrap_or(false) {
@@ -104,6 +155,139 @@ fn handle_add(matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Handle alias edit command
+///
+/// Without any flag the command is opened in the editor; flags edit the
+/// command and metadata non-interactively.
+fn handle_edit(matches: &ArgMatches) -> Result<()> {
+    let name = matches
+        .get_one::<String>("name")
+        .context("Alias name is required")?;
+
+    let mut config = AliasConfig::load().context("Failed to load alias configuration")?;
+    let Some(alias) = config.aliases.get_mut(name) else {
+        anyhow::bail!(
+            "Alias '{}' does not exist. Create it with 'msc alias add {} <command>'.",
+            name,
+            name
+        );
+    };
+
+    let interactive = ["description", "command", "category", "tag", "untag"]
+        .iter()
+        .all(|id| matches.get_one::<String>(id).is_none())
+        && !matches.get_flag("clear-category");
+    let new_command = if interactive {
+        Some(edit_command_in_editor(alias)?)
+    } else {
+        matches.get_one::<String>("command").cloned()
+    };
+    if new_command.is_some() && alias.unsafe_shell {
+        ensure_shell_operators_allowed()?;
+    }
+    let command_changed = match new_command {
+        Some(command) => alias.set_command(&command)?,
+        None => false,
+    };
+
+    if let Some(description) = matches.get_one::<String>("description") {
+        alias.description = Some(description.clone());
+    }
+    if let Some(category) = matches.get_one::<String>("category") {
+        alias.set_category(Some(category))?;
+    }
+    if matches.get_flag("clear-category") {
+        alias.set_category(None)?;
+    }
+    for tag in matches.get_many::<String>("tag").unwrap_or_default() {
+        alias.add_tag(tag)?;
+    }
+    for tag in matches.get_many::<String>("untag").unwrap_or_default() {
+        if !alias.remove_tag(tag) {
+            println!(
+                "{}",
+                format!("⚠️  Alias '{}' has no tag '{}'.", name, tag).yellow()
+            );
+        }
+    }
+
+    let summary = format_labels(alias);
+    let alias = alias.clone();
+    config
+        .save()
+        .context("Failed to save alias configuration")?;
+
+    // The executable must pick up the new command right away
+    if command_changed {
+        let bin_dir =
+            PathManager::ensure_bin_dir().context("Failed to ensure bin directory exists")?;
+        get_generator()
+            .context("Failed to get alias generator for current platform")?
+            .generate(&alias, &bin_dir)
+            .context("Failed to regenerate alias executable")?;
+    }
+
+    println!(
+        "{}",
+        format!("✓ Alias '{}' updated successfully!", name).green()
+    );
+    if command_changed {
+        println!("  Command: {}", alias.command.cyan());
+    } else if interactive {
+        println!("  {}", "Command unchanged.".dimmed());
+    }
+    if !summary.is_empty() {
+        println!("  {}", summary);
+    }
+
+    Ok(())
+}
+
+/// Open the alias command in the editor and return the edited command
+///
+/// Lines starting with `#` are ignored and the rest is joined into one line.
+fn edit_command_in_editor(alias: &Alias) -> Result<String> {
+    let config = Config::load()?;
+    let editor = config.resolve_editor(std::env::var("EDITOR").ok());
+
+    let template = format!(
+        "# Command for alias '{}'. Save and close the editor to apply.\n\
+         # Lines starting with # are ignored; {{1}} and {{@}} are argument placeholders.\n{}\n",
+        alias.name, alias.command
+    );
+    // A fresh, exclusively created file: a predictable name in a shared temp
+    // directory could be pre-created or symlinked by another user
+    let mut file = tempfile::Builder::new()
+        .prefix("msc-alias-")
+        .suffix(".txt")
+        .tempfile()
+        .context("Failed to create a temporary file for the editor")?;
+    file.write_all(template.as_bytes())
+        .with_context(|| format!("Failed to write {}", file.path().display()))?;
+    // Close the handle so the editor can replace the file; removed on drop
+    let path = file.into_temp_path();
+
+    println!("{}", format!("Waiting for {} to close...", editor).dimmed());
+    let status = crate::commands::workspace::editor_command(&editor, &path.to_string_lossy())?
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", editor));
+    let edited = std::fs::read_to_string(&path);
+    drop(path);
+
+    let status = status?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+
+    let command = edited?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(command)
+}
+
 /// Handle alias remove command
 fn handle_remove(matches: &ArgMatches) -> Result<()> {
     let name = matches
@@ -145,14 +329,66 @@ fn handle_remove(matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Handle alias run command (execute an alias without its shim)
+fn handle_run(matches: &ArgMatches) -> Result<()> {
+    let name = matches
+        .get_one::<String>("name")
+        .context("Alias name is required")?;
+    let args: Vec<String> = matches
+        .get_many::<String>("args")
+        .unwrap_or_default()
+        .cloned()
+        .collect();
+
+    let config = AliasConfig::load().context("Failed to load alias configuration")?;
+    let alias = config
+        .get_alias(name)
+        .with_context(|| format!("Alias '{}' does not exist.", name))?;
+
+    if config.track_usage {
+        // Stats are best effort and must never prevent the alias from running
+        if let Ok(mut stats) = AliasStats::load() {
+            stats.record(name, unix_now());
+            let _ = stats.save();
+        }
+    }
+
+    let code = run_alias(alias, &args, &ShellRunner)?;
+    if code != 0 {
+        return Err(CommandFailed::new(
+            code,
+            format!("alias '{}' exited with status {}", name, code),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
 /// Handle alias list command
-fn handle_list(_matches: &ArgMatches) -> Result<()> {
+fn handle_list(matches: &ArgMatches) -> Result<()> {
     // Load config
     let config = AliasConfig::load().context("Failed to load alias configuration")?;
 
-    let aliases = config.list_aliases();
+    let filter = AliasFilter {
+        category: matches.get_one::<String>("category").cloned(),
+        tag: matches.get_one::<String>("tag").cloned(),
+    };
+    let aliases = config.filter_aliases(&filter);
+
+    if matches.get_flag("json") {
+        let listing = AliasListing::new(aliases.into_iter().map(AliasEntry::from).collect());
+        let json =
+            serde_json::to_string_pretty(&listing).context("Failed to serialize alias list")?;
+        println!("{}", json);
+        return Ok(());
+    }
 
     if aliases.is_empty() {
+        if filter.category.is_some() || filter.tag.is_some() {
+            println!("No aliases match the given category or tag.");
+            return Ok(());
+        }
         println!("No aliases configured.");
         println!(
             "Use {} to create a new alias.",
@@ -164,14 +400,45 @@ fn handle_list(_matches: &ArgMatches) -> Result<()> {
     println!("{}", "Configured Aliases:".bold());
     println!();
 
-    for alias in aliases {
-        println!("  {} {}", "•".cyan(), alias.name.bold());
-        println!("    Command: {}", alias.command.cyan());
-        if let Some(desc) = &alias.description {
-            println!("    Description: {}", desc);
+    let groups = AliasConfig::group_by_category(&aliases);
+    let show_categories = groups.iter().any(|(category, _)| category.is_some());
+
+    let mut headers = vec!["Alias"];
+    if show_categories {
+        headers.push("Category");
+    }
+    headers.extend(["Command", "Description", "Tags"]);
+    let command_column = headers.len() - 3;
+
+    let mut table = Table::new(&headers)
+        .borderless()
+        .indent(2)
+        .color(command_column, Color::Cyan)
+        .min_width(command_column, 20)
+        .fit_terminal();
+    if show_categories {
+        table = table.color(1, Color::Yellow);
+    }
+
+    for (category, group) in groups {
+        for alias in group {
+            let mut row = vec![if alias.unsafe_shell {
+                Cell::new(format!("{} (unsafe)", alias.name))
+                    .styled(|text| text.red().bold().to_string())
+            } else {
+                Cell::new(alias.name.as_str()).styled(|text| text.bold().to_string())
+            }];
+            if show_categories {
+                row.push(Cell::new(category.unwrap_or("uncategorized")));
+            }
+            row.push(Cell::new(alias.command.as_str()));
+            row.push(Cell::new(alias.description.clone().unwrap_or_default()));
+            row.push(Cell::new(alias.tags.join(", ")).styled(|text| text.dimmed().to_string()));
+            table.add_row(row);
         }
-        println!();
     }
+    table.print();
+    println!();
 
     // Show PATH status
     let in_path = PathManager::is_in_path().unwrap_or(false);
@@ -191,8 +458,244 @@ fn handle_list(_matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Handle alias stats command
+fn handle_stats(matches: &ArgMatches) -> Result<()> {
+    let mut config = AliasConfig::load().context("Failed to load alias configuration")?;
+
+    if matches.get_flag("enable") || matches.get_flag("disable") {
+        let was_tracking = config.track_usage;
+        config.track_usage = matches.get_flag("enable");
+
+        if config.track_usage && !was_tracking {
+            // prune judges aliases without runs from this point on
+            let mut stats = AliasStats::load().context("Failed to load alias stats")?;
+            stats.tracking_since = Some(unix_now());
+            stats.save().context("Failed to save alias stats")?;
+        }
+
+        config
+            .save()
+            .context("Failed to save alias configuration")?;
+
+        if config.track_usage {
+            println!("{}", "✓ Alias usage tracking enabled.".green());
+            println!(
+                "  {}",
+                "Runs are counted locally in the aliases config directory.".dimmed()
+            );
+        } else {
+            println!("{}", "✓ Alias usage tracking disabled.".green());
+        }
+        return Ok(());
+    }
+
+    if !config.track_usage {
+        println!("{}", "Alias usage tracking is disabled.".yellow());
+        println!(
+            "Use {} to start recording which aliases you run.",
+            "'msc alias stats --enable'".cyan()
+        );
+        return Ok(());
+    }
+
+    let stats = AliasStats::load().context("Failed to load alias stats")?;
+    let ranked: Vec<_> = stats
+        .ranked()
+        .into_iter()
+        .filter(|(name, _)| config.exists(name))
+        .collect();
+
+    if ranked.is_empty() {
+        println!("No alias runs recorded yet.");
+        return Ok(());
+    }
+
+    let now = unix_now();
+    println!("{}", "Alias Usage:".bold());
+    println!();
+    println!(
+        "  {:>4}  {:<20} {:>8}  {}",
+        "#".dimmed(),
+        "ALIAS".dimmed(),
+        "RUNS".dimmed(),
+        "LAST USED".dimmed()
+    );
+
+    for (rank, (name, usage)) in ranked.iter().enumerate() {
+        println!(
+            "  {:>4}  {:<20} {:>8}  {}",
+            rank + 1,
+            name.bold(),
+            usage.count.to_string().cyan(),
+            format_days_ago(usage.last_used, now)
+        );
+    }
+
+    let unused = config
+        .list_aliases()
+        .iter()
+        .filter(|alias| !stats.aliases.contains_key(&alias.name))
+        .count();
+    if unused > 0 {
+        println!();
+        println!(
+            "{}",
+            format!("{} alias(es) have no recorded runs.", unused).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Handle alias prune command (suggest stale aliases)
+fn handle_prune(matches: &ArgMatches) -> Result<()> {
+    let days = *matches
+        .get_one::<u64>("unused-for")
+        .context("--unused-for is required")?;
+
+    let config = AliasConfig::load().context("Failed to load alias configuration")?;
+
+    if !config.track_usage {
+        println!(
+            "{}",
+            "⚠️  Alias usage tracking is disabled, so every alias looks unused.".yellow()
+        );
+        println!(
+            "   Run {} and check back later.",
+            "'msc alias stats --enable'".cyan()
+        );
+        return Ok(());
+    }
+
+    let stats = AliasStats::load().context("Failed to load alias stats")?;
+    let now = unix_now();
+    let candidates = prune_candidates(&config, &stats, now, days);
+
+    if candidates.is_empty() {
+        println!(
+            "{}",
+            format!("✓ Every alias was used in the last {} days.", days).green()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Aliases unused for {} days or more:", days).bold()
+    );
+    println!();
+    for candidate in &candidates {
+        let last_used = match candidate.last_used {
+            Some(last_used) => format_days_ago(last_used, now),
+            None => "never".to_string(),
+        };
+        println!(
+            "  {} {} {}",
+            "•".cyan(),
+            candidate.name.bold(),
+            format!("(last used: {})", last_used).dimmed()
+        );
+    }
+    println!();
+    println!("Remove with {}", "'msc alias remove <name>'".cyan());
+
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
+fn format_days_ago(timestamp: u64, now: u64) -> String {
+    match now.saturating_sub(timestamp) / 86_400 {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
+
+/// Handle alias doctor command (verify shims and PATH)
+fn handle_doctor(matches: &ArgMatches) -> Result<()> {
+    let fix = matches.get_flag("fix");
+
+    println!("{}", "Checking alias system...".bold());
+    println!();
+
+    let config = AliasConfig::load().context("Failed to load alias configuration")?;
+    let bin_dir = PathManager::get_aliases_bin_dir().context("Failed to get bin directory")?;
+    let generator =
+        get_generator().context("Failed to get alias generator for current platform")?;
+
+    let mut problems = 0;
+
+    // 1. PATH
+    if PathManager::is_in_path().unwrap_or(false) {
+        println!("  {} Aliases directory is in PATH", "✓".green());
+    } else if fix {
+        PathManager::add_to_path().context("Failed to add bin directory to PATH")?;
+        println!(
+            "  {} Added aliases directory to PATH (restart your terminal)",
+            "✓".green()
+        );
+    } else {
+        problems += 1;
+        println!("  {} Aliases directory is not in PATH", "✗".red());
+    }
+
+    // 2. Shims
+    let issues = inspect_shims(&config, &bin_dir, generator.as_ref())?;
+    if issues.is_empty() {
+        println!(
+            "  {} {} alias executable(s) present and up to date",
+            "✓".green(),
+            config.aliases.len()
+        );
+    }
+
+    for issue in &issues {
+        let description = match issue {
+            ShimIssue::Missing { alias } => format!("'{}' has no executable", alias),
+            ShimIssue::Stale { alias, .. } => {
+                format!("'{}' executable is outdated or modified", alias)
+            }
+            ShimIssue::Orphan { alias, path } => {
+                format!("'{}' executable has no alias ({})", alias, path.display())
+            }
+        };
+        println!("  {} {}", "✗".red(), description);
+    }
+
+    if fix && !issues.is_empty() {
+        let fixed = fix_shim_issues(&issues, &config, &bin_dir, generator.as_ref())?;
+        println!();
+        println!(
+            "{}",
+            format!("✓ Fixed {} alias executable issue(s).", fixed).green()
+        );
+    } else {
+        problems += issues.len();
+    }
+
+    println!();
+    if problems == 0 {
+        println!("{}", "✓ No problems found.".green().bold());
+    } else {
+        println!(
+            "{}",
+            format!("{} problem(s) found.", problems).yellow().bold()
+        );
+        println!("Run {} to repair them.", "'msc alias doctor --fix'".cyan());
+    }
+
+    Ok(())
+}
+
 /// Handle alias init command (setup PATH)
-fn handle_init(_matches: &ArgMatches) -> Result<()> {
+fn handle_init(matches: &ArgMatches) -> Result<()> {
+    if matches.get_flag("dry-run") {
+        return preview_init();
+    }
+
     println!("{}", "Initializing MSC alias system...".bold());
 
     // Ensure bin directory exists
@@ -246,6 +749,56 @@ fn handle_init(_matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Print what `alias init` would change without writing anything
+fn preview_init() -> Result<()> {
+    println!("{}", "Dry run: alias init would make these changes".bold());
+    println!();
+
+    let bin_dir = PathManager::get_aliases_bin_dir()?;
+    if bin_dir.exists() {
+        println!("  Bin directory: {}", bin_dir.display().to_string().cyan());
+    } else {
+        println!(
+            "  {} {}",
+            "Create directory:".yellow(),
+            bin_dir.display().to_string().cyan()
+        );
+    }
+
+    let change = if PathManager::is_in_path()? {
+        None
+    } else {
+        PathManager::plan_add_to_path()?
+    };
+
+    match change {
+        None => println!("{}", "✓ Aliases directory is already in your PATH.".green()),
+        Some(PathChange::AppendToFile { file, content }) => {
+            println!(
+                "  {} {}",
+                "Append to".yellow(),
+                file.display().to_string().cyan()
+            );
+            for line in content.lines().filter(|line| !line.is_empty()) {
+                println!("    {}", line.green());
+            }
+        }
+        Some(PathChange::SetRegistryValue { key, name, value }) => {
+            println!(
+                "  {} {}\\{}",
+                "Set registry value".yellow(),
+                key.cyan(),
+                name.cyan()
+            );
+            println!("    {}", value.green());
+        }
+    }
+
+    println!();
+    println!("{}", "Nothing was written.".dimmed());
+    Ok(())
+}
+
 /// Handle alias nuke command (clean everything)
 fn handle_nuke(_matches: &ArgMatches) -> Result<()> {
     println!(
@@ -407,6 +960,29 @@ fn cleanup_installed_tools() -> Result<()> {
     Ok(())
 }
 
+/// Fail unless `alias.allow_shell_operators` is enabled
+fn ensure_shell_operators_allowed() -> Result<()> {
+    if !Config::load()?.alias_allow_shell_operators() {
+        anyhow::bail!(
+            "Unsafe aliases are disabled. Enable them with 'msc set {} true'.",
+            ALLOW_SHELL_OPERATORS
+        );
+    }
+    Ok(())
+}
+
+/// One-line summary of an alias' category and tags
+fn format_labels(alias: &Alias) -> String {
+    let mut parts = Vec::new();
+    if let Some(category) = &alias.category {
+        parts.push(format!("Category: {}", category.cyan()));
+    }
+    if !alias.tags.is_empty() {
+        parts.push(format!("Tags: {}", alias.tags.join(", ").cyan()));
+    }
+    parts.join("  ")
+}
+
 /// Validate alias name (only alphanumeric, hyphens, and underscores)
 fn is_valid_alias_name(name: &str) -> bool {
     !name.is_empty()
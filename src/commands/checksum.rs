@@ -0,0 +1,213 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::core::checksum_manager::{parse_checksum_line, Algorithm};
+use crate::core::ChecksumManager;
+use crate::error::CommandFailed;
+use crate::ui::glyphs::adapt;
+use crate::ui::{clear_line, CliProgressBar, ProgressTracker, ProgressUnit};
+
+/// Files smaller than this are hashed without a progress bar
+const PROGRESS_MIN_SIZE: u64 = 16 * 1024 * 1024;
+
+pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("compute", sub_matches)) => compute(sub_matches),
+        Some(("verify", sub_matches)) => verify(sub_matches),
+        _ => {
+            // Short form: `msc checksum <file> [--verify <hash>]`
+            let file = matches
+                .get_one::<String>("file")
+                .context("File argument is required")?;
+            let algorithm = parse_algorithm(matches)?;
+            match matches.get_one::<String>("verify") {
+                Some(expected) => verify_one(file, expected, algorithm),
+                None => print_hash(file, algorithm),
+            }
+        }
+    }
+}
+
+/// `msc checksum compute <file>`
+fn compute(matches: &clap::ArgMatches) -> Result<()> {
+    let file = matches
+        .get_one::<String>("file")
+        .context("File argument is required")?;
+    let algorithm = parse_algorithm(matches)?;
+
+    // Keep piped output in plain sha256sum format
+    if std::io::stdout().is_terminal() {
+        println!("{}", format!("# {}", algorithm).dimmed());
+    }
+    print_hash(file, algorithm)
+}
+
+/// `msc checksum verify <file> <expected>` or `msc checksum verify --from <list>`
+fn verify(matches: &clap::ArgMatches) -> Result<()> {
+    let algorithm = parse_algorithm(matches)?;
+
+    if let Some(list) = matches.get_one::<String>("from") {
+        return verify_list(list, algorithm);
+    }
+
+    let file = matches
+        .get_one::<String>("file")
+        .context("File argument is required")?;
+    let expected = matches
+        .get_one::<String>("expected")
+        .context("Expected hash is required")?;
+    verify_one(file, expected, algorithm)
+}
+
+fn parse_algorithm(matches: &clap::ArgMatches) -> Result<Algorithm> {
+    match matches.get_one::<String>("algorithm") {
+        Some(name) => name.parse::<Algorithm>(),
+        None => Ok(Algorithm::default()),
+    }
+}
+
+fn print_hash(file: &str, algorithm: Algorithm) -> Result<()> {
+    let path = Path::new(file);
+    if !path.is_file() {
+        println!("{} {}", "Error: Not a file:".red(), file);
+        return Err(CommandFailed::new(1, format!("'{}' is not a file", file)).into());
+    }
+
+    let hash = hash_file(path, algorithm)?;
+    println!("{}  {}", hash, file);
+    Ok(())
+}
+
+fn verify_one(file: &str, expected: &str, algorithm: Algorithm) -> Result<()> {
+    let path = Path::new(file);
+    if !path.is_file() {
+        println!("{} {}", "Error: Not a file:".red(), file);
+        return Err(CommandFailed::new(1, format!("'{}' is not a file", file)).into());
+    }
+
+    let hash = hash_file(path, algorithm)?;
+    if hash.eq_ignore_ascii_case(expected.trim()) {
+        println!(
+            "{} {} ({})",
+            format!("{} Checksum matches:", adapt("✓")).green(),
+            file,
+            algorithm
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} ({})",
+        format!("{} Checksum mismatch:", adapt("✗")).red().bold(),
+        file,
+        algorithm
+    );
+    println!("  {} {}", "Expected:".dimmed(), expected.trim());
+    println!("  {} {}", "Got:     ".dimmed(), hash.yellow());
+    Err(CommandFailed::new(1, format!("checksum mismatch for '{}'", file)).into())
+}
+
+/// Verify every `hash  filename` line of a checksum list
+///
+/// Relative filenames are resolved against the directory of the list.
+fn verify_list(list: &str, algorithm: Algorithm) -> Result<()> {
+    let content = fs::read_to_string(list)
+        .with_context(|| format!("Failed to read checksum list '{}'", list))?;
+    let base_dir = Path::new(list).parent().unwrap_or(Path::new(""));
+
+    println!(
+        "{} {} {}",
+        "Verifying".white(),
+        list.cyan().bold(),
+        format!("({})", algorithm).dimmed()
+    );
+    println!();
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut malformed = 0;
+
+    for line in content.lines() {
+        let Some((expected, file)) = parse_checksum_line(line) else {
+            if !line.trim().is_empty() && !line.trim_start().starts_with('#') {
+                malformed += 1;
+            }
+            continue;
+        };
+
+        let path = base_dir.join(file);
+        let result = if path.is_file() {
+            hash_file(&path, algorithm).map_err(|e| e.to_string())
+        } else {
+            Err("not found".to_string())
+        };
+
+        match result {
+            Ok(hash) if hash.eq_ignore_ascii_case(expected) => {
+                passed += 1;
+                println!("  {}    {}", "OK".green().bold(), file);
+            }
+            Ok(_) => {
+                failed += 1;
+                println!("  {}  {}", "FAIL".red().bold(), file);
+            }
+            Err(reason) => {
+                failed += 1;
+                println!(
+                    "  {}  {} {}",
+                    "FAIL".red().bold(),
+                    file,
+                    format!("({})", reason).dimmed()
+                );
+            }
+        }
+    }
+
+    println!();
+    let summary = format!("{} OK, {} failed", passed, failed);
+    if failed == 0 {
+        println!("{}", summary.green().bold());
+    } else {
+        println!("{}", summary.red().bold());
+    }
+    if malformed > 0 {
+        println!(
+            "{}",
+            format!("{} line(s) were not in 'hash  filename' format", malformed).yellow()
+        );
+    }
+
+    if failed > 0 || passed == 0 {
+        return Err(CommandFailed::new(1, format!("verification failed: {}", summary)).into());
+    }
+    Ok(())
+}
+
+/// Hash a file, showing a progress bar for large files on a terminal
+fn hash_file(path: &Path, algorithm: Algorithm) -> Result<String> {
+    let show_progress = std::io::stdout().is_terminal()
+        && path.metadata().map(|m| m.len()).unwrap_or(0) >= PROGRESS_MIN_SIZE;
+
+    let mut progress =
+        ProgressTracker::new(0, ProgressUnit::Bytes, CliProgressBar::new("Hashing (KB):"));
+    let hash = ChecksumManager::calculate_hash_with_progress(
+        path,
+        algorithm,
+        |hashed, total| {
+            if show_progress {
+                progress.set(hashed, total);
+            }
+        },
+        None,
+    )
+    .with_context(|| format!("Failed to hash '{}'", path.display()))?;
+
+    if show_progress {
+        clear_line();
+    }
+
+    Ok(hash)
+}
@@ -6,17 +6,32 @@
 // - list: List all clean paths (default + custom)
 // - remove: Remove custom clean paths
 // - clear: Clear all custom clean paths
+// - explain: Show how a path is categorized (user vs system phase)
+// - export: Write custom clean paths to a file
+// - restore: Move the files of a --trash session back
 
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use anyhow::Result;
 use colored::Colorize;
 
+use crate::core::clean_privilege::{
+    explain as explain_privilege, is_user_directory, PrivilegeCategory, PrivilegeRule,
+};
+use crate::core::clean_report::{CleanReport, PhaseReport};
+use crate::core::clean_trash::{self, TrashSession};
 use crate::core::{Config, TempCleaner};
 use crate::platform::get_recycle_bin_directory;
 #[cfg(windows)]
 use crate::platform::{elevate_and_rerun, is_elevated};
-use crate::ui::{format_size, read_confirmation, read_exact_confirmation, select_from_list};
+use crate::ui::{
+    format_size, interrupt, read_confirmation, read_confirmation_cancellable,
+    read_exact_confirmation, select_from_list, CliProgressBar, Confirmation, ProgressTracker,
+    ProgressUnit,
+};
+use crate::utils::path::{expand_path, needs_expansion};
 
 /// Categorizes directories by whether they require admin privileges
 #[derive(Debug)]
@@ -25,49 +40,6 @@ struct DirectoriesByPrivilege {
     system_directories: Vec<String>,
 }
 
-/// Check if a directory is a user directory (doesn't require admin privileges)
-fn is_user_directory(path: &str) -> bool {
-    let path_lower = path.to_lowercase();
-
-    // Get current user's username to check for user-specific paths
-    let current_user = std::env::var("USERNAME")
-        .ok()
-        .or_else(|| std::env::var("USER").ok())
-        .unwrap_or_default()
-        .to_lowercase();
-
-    // User directories that don't require admin
-    let user_indicators = [
-        &format!("\\users\\{}\\appdata", current_user),
-        &format!("\\users\\{}\\temp", current_user),
-        &format!("/home/{}/", current_user),
-        "\\appdata\\local\\temp",
-        "\\appdata\\roaming\\temp",
-    ];
-
-    // Check if path contains user directory indicators
-    for indicator in &user_indicators {
-        if path_lower.contains(indicator) {
-            return true;
-        }
-    }
-
-    // Also check environment-based temp directories
-    if let Ok(user_temp) = std::env::var("TEMP") {
-        if path.starts_with(&user_temp) {
-            return true;
-        }
-    }
-
-    if let Ok(user_tmp) = std::env::var("TMP") {
-        if path.starts_with(&user_tmp) {
-            return true;
-        }
-    }
-
-    false
-}
-
 /// Categorize directories by privilege requirements
 fn categorize_by_privilege(directories: &[String]) -> DirectoriesByPrivilege {
     let mut user_directories = Vec::new();
@@ -87,21 +59,70 @@ fn categorize_by_privilege(directories: &[String]) -> DirectoriesByPrivilege {
     }
 }
 
-/// Helper function to perform cleanup on a specific set of directories
-fn perform_cleanup(
-    directories: Vec<String>,
+/// Options shared by both cleanup phases of `clean start`
+struct CleanupSettings<'a> {
     dry_run: bool,
     min_age: Option<std::time::Duration>,
     max_age: Option<std::time::Duration>,
-    min_age_hours: Option<&u64>,
+    min_age_hours: Option<&'a u64>,
+    max_age_hours: Option<&'a u64>,
     include_recent: bool,
-    cancel_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// Only delete files with these extensions (`--ext`)
+    extensions: Option<Vec<String>>,
+    /// Collect every visited file for `--report`
+    record_files: bool,
+    /// Move files into this session instead of deleting them (`--trash`)
+    trash: Option<Arc<TrashSession>>,
+}
+
+/// Which privilege categories `clean start` processes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CleanScope {
+    /// User directories first, then system directories
+    All,
+    /// `--only-user`: never asks for administrator privileges
+    UserOnly,
+    /// `--only-system`: skips straight to the admin phase
+    SystemOnly,
+}
+
+impl CleanScope {
+    fn from_matches(matches: &clap::ArgMatches) -> Self {
+        if matches.get_flag("only-user") {
+            CleanScope::UserOnly
+        } else if matches.get_flag("only-system") {
+            CleanScope::SystemOnly
+        } else {
+            CleanScope::All
+        }
+    }
+
+    /// Drop the directories of the category left out of this scope
+    fn apply(self, mut categorized: DirectoriesByPrivilege) -> DirectoriesByPrivilege {
+        match self {
+            CleanScope::All => {}
+            CleanScope::UserOnly => categorized.system_directories.clear(),
+            CleanScope::SystemOnly => categorized.user_directories.clear(),
+        }
+        categorized
+    }
+}
+
+/// Helper function to perform cleanup on a specific set of directories
+fn perform_cleanup(
+    directories: Vec<String>,
+    settings: &CleanupSettings,
+    cancel_flag: Option<Arc<AtomicBool>>,
 ) -> Result<crate::core::CleanupStats> {
+    let dry_run = settings.dry_run;
+
     // Create a temporary cleaner with only these directories
     let mut cleaner = TempCleaner::new()?;
     cleaner.directories = directories;
-    cleaner.min_age = min_age;
-    cleaner.max_age = max_age;
+    cleaner.min_age = settings.min_age;
+    cleaner.max_age = settings.max_age;
+    cleaner.extensions = settings.extensions.clone();
+    cleaner.trash = settings.trash.clone();
 
     // Use provided cancel flag if available
     if let Some(flag) = cancel_flag {
@@ -160,37 +181,41 @@ fn perform_cleanup(
     }
 
     // Clean with progress callback
-    let stats = cleaner.clean(dry_run, |processed, total| {
-        let percentage = (processed as f64 / total as f64 * 100.0) as usize;
-        let bar_length: usize = 30;
-        let filled = (percentage as f64 / 100.0 * bar_length as f64) as usize;
-        let empty = bar_length.saturating_sub(filled);
-
-        print!(
-            "\r{} [{}{}] {}% ({}/{}) ",
-            "Progress:".white(),
-            "=".repeat(filled).green(),
-            " ".repeat(empty),
-            percentage,
-            processed,
-            total
-        );
-
-        use std::io::Write;
-        std::io::stdout().flush().ok();
-    })?;
+    let mut progress = ProgressTracker::new(
+        scan_stats.total_files as u64,
+        ProgressUnit::Items,
+        CliProgressBar::new("Progress:"),
+    );
+    let on_progress = |processed: usize, total: usize| {
+        progress.set(processed as u64, total as u64);
+    };
+    let stats = if settings.record_files {
+        cleaner.clean_with_records(dry_run, on_progress)?
+    } else {
+        cleaner.clean(dry_run, on_progress)?
+    };
 
-    println!();
+    progress.finish();
     println!();
 
     // Display summary
-    display_cleanup_summary(&stats, dry_run, min_age_hours, include_recent);
+    display_cleanup_summary(
+        &stats,
+        dry_run,
+        settings.min_age_hours,
+        settings.max_age_hours,
+        settings.include_recent,
+    );
 
     Ok(stats)
 }
 
-/// Clean work cache directories (target, dist, node_modules) in work directory projects
-fn clean_work_cache(config: &crate::core::Config, dry_run: bool) -> Result<()> {
+/// Clean the configured cache folders (target, dist, node_modules by default) in work directory projects
+fn clean_work_cache(
+    config: &crate::core::Config,
+    dry_run: bool,
+    trash: Option<&TrashSession>,
+) -> Result<()> {
     use std::fs;
     use std::path::PathBuf;
 
@@ -241,7 +266,19 @@ fn clean_work_cache(config: &crate::core::Config, dry_run: bool) -> Result<()> {
     }
 
     // Cache folders to clean
-    let cache_folders = ["target", "dist", "node_modules"];
+    let cache_folders = config.get_work_cache_folders();
+    if cache_folders.is_empty() {
+        println!("{}", "No cache folders configured.".yellow());
+        println!(
+            "{}",
+            "Use 'msc clean cache-folders add <name>' to add one.".dimmed()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Cache folders:".dimmed());
+    println!("  {}", cache_folders.join(", ").dimmed());
+    println!();
 
     println!(
         "{}",
@@ -305,12 +342,20 @@ fn clean_work_cache(config: &crate::core::Config, dry_run: bool) -> Result<()> {
                 } else {
                     print!(
                         "{} {}",
-                        "Deleting:".cyan(),
+                        if trash.is_some() {
+                            "Moving to trash:".cyan()
+                        } else {
+                            "Deleting:".cyan()
+                        },
                         format!("{}/{}", folder_name, cache_folder).cyan()
                     );
 
-                    // Delete the folder
-                    match fs::remove_dir_all(&cache_path) {
+                    // Delete the folder (or move it to the trash)
+                    let result = match trash {
+                        Some(trash) => trash.move_in(&cache_path, folder_size),
+                        None => fs::remove_dir_all(&cache_path),
+                    };
+                    match result {
                         Ok(_) => {
                             println!(" {} ({})", "✓".green(), format_size(folder_size).dimmed());
                             cleaned_count += 1;
@@ -381,11 +426,53 @@ fn calculate_dir_size(path: &std::path::Path) -> (u64, usize) {
     (total_size, file_count)
 }
 
+/// Minimum file age for `clean start` when neither `--min-age` nor `--include-recent` is given
+const DEFAULT_MIN_AGE_HOURS: u64 = 8;
+
+/// Age window for `clean start`: `--min-age` (or the default) up to `--max-age`
+fn age_window(
+    min_age_hours: Option<u64>,
+    max_age_hours: Option<u64>,
+    include_recent: bool,
+) -> Result<(Option<std::time::Duration>, Option<std::time::Duration>)> {
+    let hours = |h: u64| std::time::Duration::from_secs(h * 3600);
+    let min_age = if let Some(h) = min_age_hours {
+        Some(hours(h))
+    } else if include_recent {
+        None
+    } else {
+        Some(hours(DEFAULT_MIN_AGE_HOURS))
+    };
+
+    // Compare against the effective minimum, so a lone --max-age below the
+    // default does not select an empty window
+    if let (Some(min), Some(max)) = (min_age, max_age_hours) {
+        if min > hours(max) {
+            match min_age_hours {
+                Some(min) => anyhow::bail!(
+                    "--min-age ({} hours) must not be greater than --max-age ({} hours)",
+                    min,
+                    max
+                ),
+                None => anyhow::bail!(
+                    "--max-age ({} hours) is below the default minimum age of {} hours; \
+                     pass --min-age or --include-recent as well",
+                    max,
+                    DEFAULT_MIN_AGE_HOURS
+                ),
+            }
+        }
+    }
+
+    Ok((min_age, max_age_hours.map(hours)))
+}
+
 /// Display cleanup summary
 fn display_cleanup_summary(
     stats: &crate::core::CleanupStats,
     dry_run: bool,
     min_age_hours: Option<&u64>,
+    max_age_hours: Option<&u64>,
     include_recent: bool,
 ) {
     // Show cancellation status if cancelled
@@ -433,6 +520,14 @@ fn display_cleanup_summary(
             format_size(stats.deleted_size).yellow().bold()
         );
 
+        if stats.trashed_files > 0 {
+            println!(
+                "{} {}",
+                "Moved to trash:".green(),
+                format!("{} files (restorable)", stats.trashed_files).yellow()
+            );
+        }
+
         if stats.failed_files > 0 {
             println!(
                 "{} {} (files in use or protected)",
@@ -449,7 +544,9 @@ fn display_cleanup_summary(
 
         if stats.skipped_files > 0 {
             // Determine the reason for skipping
-            let reason = if min_age_hours.is_some() || !include_recent {
+            let reason = if max_age_hours.is_some() {
+                "(outside the age window)".to_string()
+            } else if min_age_hours.is_some() || !include_recent {
                 "(too recent - younger than age filter)".to_string()
             } else {
                 "(errors or other reasons)".to_string()
@@ -506,36 +603,201 @@ fn display_cleanup_summary(
     }
 }
 
+/// Run phase 1 (user directories) and phase 2 (system directories), recording
+/// each phase into `report` when one is being written
+///
+/// Returns `false` when cleanup stopped early: cancelled, system phase
+/// declined, or relaunched with administrator privileges.
+fn run_cleanup_phases(
+    categorized: &DirectoriesByPrivilege,
+    settings: &CleanupSettings,
+    cancel_flag: &Arc<AtomicBool>,
+    report: &mut Option<CleanReport>,
+) -> Result<bool> {
+    // PHASE 1: Clean user directories WITHOUT admin privileges
+    if !categorized.user_directories.is_empty() {
+        println!("{}", "═".repeat(50).green());
+        println!("{}", "PHASE 1: Cleaning User Directories".green().bold());
+        println!(
+            "{}",
+            "(No administrator privileges needed)".green().dimmed()
+        );
+        println!("{}", "═".repeat(50).green());
+        println!();
+
+        let user_stats = perform_cleanup(
+            categorized.user_directories.clone(),
+            settings,
+            Some(cancel_flag.clone()),
+        )?;
+        if let Some(report) = report.as_mut() {
+            report.user = PhaseReport::executed(&categorized.user_directories, user_stats);
+        }
+
+        println!();
+
+        // Check if cancelled during user phase
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+    }
+
+    // PHASE 2: Clean system directories WITH admin privileges (after confirmation)
+    if !categorized.system_directories.is_empty() {
+        println!("{}", "═".repeat(50).yellow());
+        println!("{}", "PHASE 2: Cleaning System Directories".yellow().bold());
+        println!(
+            "{}",
+            "(Administrator privileges required)".yellow().dimmed()
+        );
+        println!("{}", "═".repeat(50).yellow());
+        println!();
+
+        // Check if already elevated
+        #[cfg(windows)]
+        let is_admin = is_elevated();
+        #[cfg(not(windows))]
+        let is_admin = false;
+
+        if !is_admin {
+            // Not elevated - ask for confirmation before requesting admin
+            if !settings.dry_run {
+                println!(
+                    "{}",
+                    "System directories require administrator privileges.".yellow()
+                );
+                println!();
+
+                match read_confirmation_cancellable(
+                    "Do you want to clean system directories? This will request admin access. (y/n): ",
+                    cancel_flag,
+                ) {
+                    Ok(Confirmation::Confirmed) => {
+                        // User wants to clean system directories
+                    }
+                    Ok(Confirmation::Cancelled) => {
+                        println!("{}", "Skipping system directories cleanup.".yellow());
+                        return Ok(false);
+                    }
+                    Ok(Confirmation::Declined) => {
+                        println!();
+                        println!(
+                            "{}",
+                            "Skipping system directories cleanup.".yellow()
+                        );
+                        println!();
+                        print_user_phase_done(categorized);
+                        return Ok(false);
+                    }
+                    Err(e) => {
+                        println!();
+                        println!("{}", format!("Error: {}", e).red());
+                        println!("{}", "Skipping system directories for safety.".yellow());
+                        println!();
+                        print_user_phase_done(categorized);
+                        return Ok(false);
+                    }
+                }
+
+                println!();
+
+                // Request elevation
+                #[cfg(windows)]
+                {
+                    println!("{}", "Requesting administrator privileges...".cyan());
+                    if elevate_and_rerun()? {
+                        println!("{}", "Relaunching with administrator privileges...".green());
+                        return Ok(false);
+                    } else {
+                        println!(
+                            "{}",
+                            "Warning: Could not elevate privileges. Skipping system directories."
+                                .yellow()
+                        );
+                        println!();
+                        print_user_phase_done(categorized);
+                        return Ok(false);
+                    }
+                }
+
+                #[cfg(not(windows))]
+                {
+                    println!(
+                        "{}",
+                        "Privilege elevation is only supported on Windows.".yellow()
+                    );
+                    println!("{}", "Try running this command with sudo/root.".yellow());
+                    println!();
+                    print_user_phase_done(categorized);
+                    return Ok(false);
+                }
+            }
+        }
+
+        // If we're here, we have admin privileges (or it's a dry run)
+        let system_stats = perform_cleanup(
+            categorized.system_directories.clone(),
+            settings,
+            Some(cancel_flag.clone()),
+        )?;
+        if let Some(report) = report.as_mut() {
+            report.system = PhaseReport::executed(&categorized.system_directories, system_stats);
+        }
+
+        println!();
+    }
+
+    Ok(true)
+}
+
+/// Confirm phase 1 when stopping before phase 2 (nothing to say with `--only-system`)
+fn print_user_phase_done(categorized: &DirectoriesByPrivilege) {
+    if !categorized.user_directories.is_empty() {
+        println!(
+            "{}",
+            "User directories have been cleaned successfully.".green()
+        );
+    }
+}
+
 /// Handle 'clean start' command - Execute cleanup of temporary files
 ///
 /// This implements the Principle of Least Privilege by separating cleanup into two phases:
 /// 1. User directories: Cleaned WITHOUT admin privileges
 /// 2. System directories: Cleaned WITH admin privileges (after user confirmation)
 pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
-    use std::sync::atomic::{AtomicBool, Ordering};
-    use std::sync::Arc;
-
     let dry_run = matches.get_flag("dry-run");
     let min_age_hours = matches.get_one::<u64>("min-age");
+    let max_age_hours = matches.get_one::<u64>("max-age");
     let include_recent = matches.get_flag("include-recent");
     let include_recycle = matches.get_flag("include-recycle");
     let work_cache = matches.get_flag("work-cache");
+    let use_trash = matches.get_flag("trash");
+    let report_path = matches.get_one::<String>("report");
+    let scope = CleanScope::from_matches(matches);
+    let extensions: Option<Vec<String>> = matches.get_many::<String>("ext").map(|values| {
+        values
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect()
+    });
 
-    // Create shared cancellation flag
-    let cancel_flag = Arc::new(AtomicBool::new(false));
-    let cancel_flag_clone = cancel_flag.clone();
-
-    // Setup Ctrl+C handler
-    ctrlc::set_handler(move || {
+    // Determine age filters
+    let (min_age, max_age) = age_window(
+        min_age_hours.copied(),
+        max_age_hours.copied(),
+        include_recent,
+    )?;
+
+    // Shared cancellation flag, set by Ctrl+C
+    let cancel_flag = interrupt::cancel_flag_with(|| {
         println!();
         println!("{}", "Cancellation requested...".yellow().bold());
         println!(
             "{}",
             "Stopping cleanup safely (this may take a moment)...".dimmed()
         );
-        cancel_flag_clone.store(true, Ordering::Relaxed);
-    })
-    .map_err(|e| anyhow::anyhow!("Failed to set Ctrl+C handler: {}", e))?;
+    })?;
 
     if dry_run {
         println!(
@@ -549,17 +811,22 @@ pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
     println!("{}", "Press Ctrl+C at any time to cancel safely".dimmed());
     println!();
 
-    // Determine age filters
-    let (min_age, max_age) = if let Some(&hours) = min_age_hours {
-        (Some(std::time::Duration::from_secs(hours * 3600)), None)
-    } else if include_recent {
-        (None, None)
-    } else {
-        (Some(std::time::Duration::from_secs(8 * 3600)), None) // Default: 8 hours
-    };
-
     // Display age policy
-    if include_recent {
+    if let Some(max_hours) = max_age_hours {
+        let policy = match min_age {
+            Some(min) => format!(
+                "Only deleting files between {} and {} hours old",
+                min.as_secs() / 3600,
+                max_hours
+            ),
+            None => format!("Only deleting files younger than {} hours", max_hours),
+        };
+        println!("{}", policy.cyan());
+        println!(
+            "{}",
+            "Files outside this window are kept and counted as skipped".dimmed()
+        );
+    } else if include_recent {
         println!(
             "{}",
             "⚠️  Warning: Deleting files of ALL ages (including recent files)"
@@ -585,6 +852,13 @@ pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
             "Use --min-age <HOURS> to customize or --include-recent to delete all".dimmed()
         );
     }
+    if let Some(ref extensions) = extensions {
+        let list: Vec<String> = extensions.iter().map(|ext| format!(".{}", ext)).collect();
+        println!(
+            "{}",
+            format!("Only deleting files with extensions: {}", list.join(", ")).cyan()
+        );
+    }
     println!();
 
     // Get all directories from config
@@ -615,7 +889,38 @@ pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
     }
 
     // Categorize directories by privilege requirements
-    let categorized = categorize_by_privilege(&all_directories);
+    let categorized = scope.apply(categorize_by_privilege(&all_directories));
+
+    match scope {
+        CleanScope::All => {}
+        CleanScope::UserOnly => {
+            println!("{}", "Scope: user directories only (--only-user)".cyan());
+            println!();
+        }
+        CleanScope::SystemOnly => {
+            println!(
+                "{}",
+                "Scope: system directories only (--only-system)".cyan()
+            );
+            println!();
+        }
+    }
+
+    if categorized.user_directories.is_empty() && categorized.system_directories.is_empty() {
+        println!("{}", "No temp directories found in this scope.".yellow());
+        return Ok(());
+    }
+    let mut settings = CleanupSettings {
+        dry_run,
+        min_age,
+        max_age,
+        min_age_hours,
+        max_age_hours,
+        include_recent,
+        extensions,
+        record_files: report_path.is_some(),
+        trash: None,
+    };
 
     println!(
         "{}",
@@ -641,24 +946,37 @@ pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
 
     // Ask for confirmation unless it's a dry run
     if !dry_run {
-        println!(
-            "{}",
-            "⚠️  Warning: This will delete files in the directories listed above."
-                .yellow()
-                .bold()
-        );
+        if use_trash {
+            println!(
+                "{}",
+                "Files in the directories listed above will be moved to the msc trash."
+                    .cyan()
+                    .bold()
+            );
+        } else {
+            println!(
+                "{}",
+                "⚠️  Warning: This will delete files in the directories listed above."
+                    .yellow()
+                    .bold()
+            );
+        }
         println!();
 
-        // Use robust confirmation with retry logic
-        match read_confirmation("Do you want to continue? (y/n): ", 3) {
-            Ok(true) => {
+        // Ctrl+C while waiting here aborts the prompt instead of blocking on stdin
+        match read_confirmation_cancellable("Do you want to continue? (y/n): ", &cancel_flag) {
+            Ok(Confirmation::Confirmed) => {
                 // User confirmed, continue
             }
-            Ok(false) => {
+            Ok(Confirmation::Declined) => {
                 println!();
                 println!("{}", "Operation cancelled by user.".yellow());
                 return Ok(());
             }
+            Ok(Confirmation::Cancelled) => {
+                println!("{}", "Operation cancelled.".yellow());
+                return Ok(());
+            }
             Err(e) => {
                 println!();
                 println!("{}", format!("Error: {}", e).red());
@@ -670,153 +988,143 @@ pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
         println!();
     }
 
-    // PHASE 1: Clean user directories WITHOUT admin privileges
-    if !categorized.user_directories.is_empty() {
-        println!("{}", "═".repeat(50).green());
-        println!("{}", "PHASE 1: Cleaning User Directories".green().bold());
-        println!(
-            "{}",
-            "(No administrator privileges needed)".green().dimmed()
-        );
-        println!("{}", "═".repeat(50).green());
-        println!();
+    // Created after confirmation so a declined run leaves no empty session behind
+    if use_trash && !dry_run {
+        settings.trash = Some(Arc::new(TrashSession::create()?));
+    }
 
-        let _user_stats = perform_cleanup(
-            categorized.user_directories.clone(),
+    let mut report = report_path.map(|_| {
+        CleanReport::new(
             dry_run,
-            min_age,
-            max_age,
-            min_age_hours,
-            include_recent,
-            Some(cancel_flag.clone()),
-        )?;
+            &categorized.user_directories,
+            &categorized.system_directories,
+        )
+    });
+    let completed = run_cleanup_phases(&categorized, &settings, &cancel_flag, &mut report);
+
+    // The report is written even when cleanup stopped early or failed. A failed
+    // write is returned only after the trash manifest below is saved.
+    let report_written = match (report_path, &report) {
+        (Some(path), Some(report)) => report.write(Path::new(path)).map(|()| {
+            println!("{} {}", "Report written to:".green(), path.cyan());
+            println!();
+        }),
+        _ => Ok(()),
+    };
 
-        println!();
+    let result = match completed {
+        Ok(true) => {
+            if !categorized.user_directories.is_empty()
+                || !categorized.system_directories.is_empty()
+            {
+                println!("{}", "═".repeat(50).green());
+                println!("{}", "✓ Cleanup completed successfully".green().bold());
+                println!("{}", "═".repeat(50).green());
+                println!();
+            }
 
-        // Check if cancelled during user phase
-        if cancel_flag.load(Ordering::Relaxed) {
-            return Ok(());
+            // WORK CACHE CLEANUP: Clean cache folders in work directory projects if requested
+            if work_cache {
+                clean_work_cache(&config, dry_run, settings.trash.as_deref())
+            } else {
+                Ok(())
+            }
         }
+        Ok(false) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    // The manifest is written even when cleanup stopped early, so trashed files stay restorable
+    if let Some(trash) = &settings.trash {
+        finish_trash_session(trash)?;
     }
 
-    // PHASE 2: Clean system directories WITH admin privileges (after confirmation)
-    if !categorized.system_directories.is_empty() {
-        println!("{}", "═".repeat(50).yellow());
-        println!("{}", "PHASE 2: Cleaning System Directories".yellow().bold());
-        println!(
-            "{}",
-            "(Administrator privileges required)".yellow().dimmed()
-        );
-        println!("{}", "═".repeat(50).yellow());
-        println!();
-
-        // Check if already elevated
-        #[cfg(windows)]
-        let is_admin = is_elevated();
-        #[cfg(not(windows))]
-        let is_admin = false;
-
-        if !is_admin {
-            // Not elevated - ask for confirmation before requesting admin
-            if !dry_run {
-                println!(
-                    "{}",
-                    "System directories require administrator privileges.".yellow()
-                );
-                println!();
+    report_written?;
+    result
+}
 
-                match read_confirmation(
-                    "Do you want to clean system directories? This will request admin access. (y/n): ",
-                    3,
-                ) {
-                    Ok(true) => {
-                        // User wants to clean system directories
-                    }
-                    Ok(false) => {
-                        println!();
-                        println!(
-                            "{}",
-                            "Skipping system directories cleanup.".yellow()
-                        );
-                        println!();
-                        println!("{}", "User directories have been cleaned successfully.".green());
-                        return Ok(());
-                    }
-                    Err(e) => {
-                        println!();
-                        println!("{}", format!("Error: {}", e).red());
-                        println!("{}", "Skipping system directories for safety.".yellow());
-                        println!();
-                        println!("{}", "User directories have been cleaned successfully.".green());
-                        return Ok(());
-                    }
-                }
+/// Write the manifest of a `--trash` run and tell the user how to undo it
+fn finish_trash_session(trash: &TrashSession) -> Result<()> {
+    trash.save()?;
+    if trash.is_empty() {
+        return Ok(());
+    }
 
-                println!();
+    println!(
+        "{} {} {}",
+        "Trash session:".green().bold(),
+        trash.id().yellow().bold(),
+        format!("({} items in {})", trash.len(), trash.dir().display()).dimmed()
+    );
+    println!(
+        "{}",
+        format!("Undo with: msc clean restore {}", trash.id()).dimmed()
+    );
+    println!();
+    Ok(())
+}
 
-                // Request elevation
-                #[cfg(windows)]
-                {
-                    println!("{}", "Requesting administrator privileges...".cyan());
-                    if elevate_and_rerun()? {
-                        println!("{}", "Relaunching with administrator privileges...".green());
-                        return Ok(());
-                    } else {
-                        println!(
-                            "{}",
-                            "Warning: Could not elevate privileges. Skipping system directories."
-                                .yellow()
-                        );
-                        println!();
-                        println!(
-                            "{}",
-                            "User directories have been cleaned successfully.".green()
-                        );
-                        return Ok(());
-                    }
-                }
+/// Handle 'clean restore' command - Move the files of a trash session back
+///
+/// Without a session id, lists the available sessions.
+pub fn handle_restore(matches: &clap::ArgMatches) -> Result<()> {
+    let trash_root = clean_trash::default_trash_root()?;
+
+    let Some(session_id) = matches.get_one::<String>("session") else {
+        let sessions = clean_trash::list_sessions(&trash_root);
+        if sessions.is_empty() {
+            println!("{}", "No trash sessions found.".yellow());
+            println!(
+                "{}",
+                "Use 'msc clean start --trash' to clean without deleting permanently.".dimmed()
+            );
+            return Ok(());
+        }
 
-                #[cfg(not(windows))]
-                {
-                    println!(
-                        "{}",
-                        "Privilege elevation is only supported on Windows.".yellow()
-                    );
-                    println!("{}", "Try running this command with sudo/root.".yellow());
-                    println!();
-                    println!(
-                        "{}",
-                        "User directories have been cleaned successfully.".green()
-                    );
-                    return Ok(());
-                }
-            }
+        println!("{}", "Trash sessions:".white().bold());
+        for session in &sessions {
+            println!(
+                "  {}  {} items, {}  {}",
+                session.id.cyan(),
+                session.entries.len(),
+                format_size(session.total_size()),
+                session.created_at.dimmed()
+            );
         }
+        println!();
+        println!(
+            "{}",
+            "Restore one with: msc clean restore <session-id>".dimmed()
+        );
+        return Ok(());
+    };
 
-        // If we're here, we have admin privileges (or it's a dry run)
-        let _system_stats = perform_cleanup(
-            categorized.system_directories.clone(),
-            dry_run,
-            min_age,
-            max_age,
-            min_age_hours,
-            include_recent,
-            Some(cancel_flag.clone()),
-        )?;
+    let stats = clean_trash::restore_session(&trash_root, session_id)?;
 
-        println!();
+    println!(
+        "{} {}",
+        "✓ Restored:".green().bold(),
+        format!("{} items", stats.restored).yellow().bold()
+    );
+    if stats.conflicts > 0 {
+        println!(
+            "{} {} (the original path exists again; left in the trash)",
+            "Conflicts:".yellow(),
+            stats.conflicts
+        );
     }
-
-    if !categorized.user_directories.is_empty() || !categorized.system_directories.is_empty() {
-        println!("{}", "═".repeat(50).green());
-        println!("{}", "✓ Cleanup completed successfully".green().bold());
-        println!("{}", "═".repeat(50).green());
-        println!();
+    if stats.failed > 0 {
+        println!("{} {}", "Failed:".red().bold(), stats.failed);
     }
-
-    // WORK CACHE CLEANUP: Clean cache folders in work directory projects if requested
-    if work_cache {
-        clean_work_cache(&config, dry_run)?;
+    if stats.conflicts > 0 || stats.failed > 0 {
+        println!(
+            "{}",
+            format!(
+                "Remaining items are still in {}",
+                trash_root.join(session_id).display()
+            )
+            .dimmed()
+        );
     }
 
     Ok(())
@@ -826,11 +1134,33 @@ pub fn handle_start(matches: &clap::ArgMatches) -> Result<()> {
 pub fn handle_add(matches: &clap::ArgMatches) -> Result<()> {
     use crate::core::{PathValidator, ValidationResult};
 
-    let path = matches
+    if let Some(file) = matches.get_one::<String>("from-file") {
+        return add_from_file(file, matches.get_flag("force"));
+    }
+
+    let raw_path = matches
         .get_one::<String>("path")
         .ok_or_else(|| anyhow::anyhow!("Path is required"))?;
     let force = matches.get_flag("force");
 
+    // Expand ~ and environment variables BEFORE validation so the
+    // resolved location is what PathValidator checks
+    let path = &expand_path(raw_path)?;
+    if needs_expansion(raw_path) {
+        println!(
+            "{} {} {} {}",
+            "Expanded:".dimmed(),
+            raw_path.cyan(),
+            "→".dimmed(),
+            path.cyan()
+        );
+        println!(
+            "{}",
+            "Note: the expanded path is stored, not the original form.".dimmed()
+        );
+        println!();
+    }
+
     let path_buf = Path::new(path);
 
     // Validate path using PathValidator
@@ -915,6 +1245,117 @@ pub fn handle_add(matches: &clap::ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Add clean paths listed in a file (one per line), skipping unsafe ones
+fn add_from_file(file: &str, force: bool) -> Result<()> {
+    use crate::core::path_validator::parse_path_list;
+    use crate::core::PathValidator;
+
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", file, e))?;
+    let paths = parse_path_list(&content);
+
+    if paths.is_empty() {
+        println!("{}", "No paths found in file.".yellow());
+        return Ok(());
+    }
+
+    let validator = PathValidator::new();
+    let batch = validator.validate_batch(&paths, force);
+
+    let mut config = Config::load()?;
+    let mut added = Vec::new();
+    let mut already_present = Vec::new();
+
+    for path in &batch.accepted {
+        let path_str = path.to_string_lossy().to_string();
+        if config.add_clean_path(path_str.clone()) {
+            added.push(path_str);
+        } else {
+            already_present.push(path_str);
+        }
+    }
+
+    if !added.is_empty() {
+        config.save()?;
+    }
+
+    println!(
+        "{} {}",
+        "✓ Added:".green().bold(),
+        format!("{} path(s)", added.len()).green()
+    );
+    for path in &added {
+        let cleaned_path = path.strip_prefix("\\\\?\\").unwrap_or(path);
+        println!("  {} {}", "+".green(), cleaned_path.cyan());
+    }
+
+    if !already_present.is_empty() {
+        println!();
+        println!(
+            "{} {}",
+            "Already present:".yellow(),
+            already_present.len().to_string().yellow()
+        );
+        for path in &already_present {
+            let cleaned_path = path.strip_prefix("\\\\?\\").unwrap_or(path);
+            println!("  {} {}", "=".dimmed(), cleaned_path.dimmed());
+        }
+    }
+
+    if !batch.skipped.is_empty() {
+        println!();
+        println!(
+            "{} {}",
+            "✗ Skipped:".red().bold(),
+            format!("{} path(s)", batch.skipped.len()).red()
+        );
+        for (path, reason) in &batch.skipped {
+            println!("  {} {}", "-".red(), path.red());
+            for line in reason.lines() {
+                println!("      {}", line.dimmed());
+            }
+        }
+        if !force {
+            println!();
+            println!(
+                "{}",
+                "Paths with warnings can be added with --force (⚠️  dangerous!)".dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle 'clean export' command - Write custom clean paths to a file
+pub fn handle_export(matches: &clap::ArgMatches) -> Result<()> {
+    let file = matches
+        .get_one::<String>("file")
+        .ok_or_else(|| anyhow::anyhow!("File is required"))?;
+
+    let config = Config::load()?;
+    let custom_paths = config.get_custom_paths();
+
+    let mut content = String::from("# msc clean paths\n");
+    content.push_str("# Import on another machine with: msc clean add --from-file <FILE>\n");
+    for path in custom_paths {
+        content.push_str(path.strip_prefix("\\\\?\\").unwrap_or(path));
+        content.push('\n');
+    }
+
+    std::fs::write(file, content)
+        .map_err(|e| anyhow::anyhow!("Failed to write '{}': {}", file, e))?;
+
+    println!(
+        "{} {} {}",
+        "✓ Exported".green().bold(),
+        format!("{} custom path(s) to", custom_paths.len()).green(),
+        file.cyan()
+    );
+
+    Ok(())
+}
+
 /// Handle 'clean list' command - List all active clean paths
 pub fn handle_list(_matches: &clap::ArgMatches) -> Result<()> {
     // Load config - this automatically syncs default paths
@@ -974,7 +1415,7 @@ pub fn handle_list(_matches: &clap::ArgMatches) -> Result<()> {
     if let Some(work_path) = config.get_work_path() {
         // Clean the Windows long path prefix
         let cleaned_work_path = work_path.strip_prefix("\\\\?\\").unwrap_or(work_path);
-        let cache_folders = ["target", "dist", "node_modules"];
+        let cache_folders = config.get_work_cache_folders();
 
         for cache_folder in &cache_folders {
             println!(
@@ -1080,22 +1521,56 @@ pub fn handle_remove(_matches: &clap::ArgMatches) -> Result<()> {
 }
 
 /// Handle 'clean reset' command - Reset to default configuration
-pub fn handle_clear(_matches: &clap::ArgMatches) -> Result<()> {
+pub fn handle_clear(matches: &clap::ArgMatches) -> Result<()> {
     // Load config - this automatically syncs default paths
     let mut config = Config::load()?;
     let current_count = config.get_clean_paths().len();
+    let skip_confirmation = matches.get_flag("yes");
 
     // Check if already at defaults (no custom paths and no exclusions)
-    let is_at_defaults =
-        config.get_custom_paths().is_empty() && config.excluded_default_paths.is_empty();
+    let preview = config.reset_preview();
 
-    if is_at_defaults {
+    if preview.is_empty() {
         println!("{}", "Already using default configuration.".yellow());
         println!();
         println!("{}", "All paths are set to system defaults.".dimmed());
         return Ok(());
     }
 
+    println!(
+        "{}",
+        "The following changes will be applied:".white().bold()
+    );
+    println!();
+
+    if !preview.removed_custom_paths.is_empty() {
+        println!("{}", "Custom paths to be removed:".red().bold());
+        for path in &preview.removed_custom_paths {
+            let cleaned_path = path.strip_prefix("\\\\?\\").unwrap_or(path);
+            println!("  {} {}", "-".red(), cleaned_path.red());
+        }
+        println!();
+    }
+
+    if !preview.restored_default_paths.is_empty() {
+        println!("{}", "Default paths to be restored:".green().bold());
+        for path in &preview.restored_default_paths {
+            let cleaned_path = path.strip_prefix("\\\\?\\").unwrap_or(path);
+            println!("  {} {}", "+".green(), cleaned_path.green());
+        }
+        println!();
+    }
+
+    if !skip_confirmation {
+        let confirmed = read_confirmation("Apply these changes? (y/N): ", 3)?;
+        if !confirmed {
+            println!();
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+        println!();
+    }
+
     println!(
         "{}",
         "Resetting clean paths to default configuration..."
@@ -1122,6 +1597,48 @@ pub fn handle_clear(_matches: &clap::ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Handle 'clean explain' command - Show why a path is a user or system directory
+pub fn handle_explain(matches: &clap::ArgMatches) -> Result<()> {
+    let path = matches
+        .get_one::<String>("path")
+        .ok_or_else(|| anyhow::anyhow!("Path is required"))?;
+
+    let path = &expand_path(path)?;
+    let explanation = explain_privilege(path);
+
+    println!("{} {}", "Path:".white().bold(), path.cyan());
+
+    match explanation.category {
+        PrivilegeCategory::User => println!(
+            "{} {} {}",
+            "Category:".white().bold(),
+            "user".green().bold(),
+            "(phase 1, no elevation required)".dimmed()
+        ),
+        PrivilegeCategory::System => println!(
+            "{} {} {}",
+            "Category:".white().bold(),
+            "system".yellow().bold(),
+            "(phase 2, requires administrator privileges)".dimmed()
+        ),
+    }
+
+    let rule = match &explanation.rule {
+        PrivilegeRule::UserIndicator(indicator) => {
+            format!("path contains user directory fragment '{}'", indicator)
+        }
+        PrivilegeRule::TempEnvVar { var, value } => {
+            format!("path is inside %{}% ({})", var, value)
+        }
+        PrivilegeRule::NoUserRuleMatched => {
+            "no user directory rule matched (default: system)".to_string()
+        }
+    };
+    println!("{} {}", "Rule:".white().bold(), rule);
+
+    Ok(())
+}
+
 /// Handle 'clean ignore add' command - Add a folder to the ignore list
 pub fn handle_ignore_add(matches: &clap::ArgMatches) -> Result<()> {
     let folder = matches
@@ -1249,3 +1766,202 @@ pub fn handle_ignore_remove(matches: &clap::ArgMatches) -> Result<()> {
 
     Ok(())
 }
+
+/// Folder names that hold project sources rather than build output, so
+/// `--work-cache` must never delete them
+const PROTECTED_FOLDER_NAMES: &[&str] = &[
+    ".git", "src", "source", "sources", "lib", "app", "include", "test", "tests", "docs", "doc",
+];
+
+/// Check that `name` is a plain folder name (no separators or `.`/`..`) that
+/// is safe to delete from every work project
+fn validate_cache_folder_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        anyhow::bail!(
+            "Invalid cache folder name '{}': use a plain folder name such as '.next' or '__pycache__'",
+            name
+        );
+    }
+    if PROTECTED_FOLDER_NAMES
+        .iter()
+        .any(|protected| protected.eq_ignore_ascii_case(name))
+    {
+        anyhow::bail!(
+            "Refusing to add '{}': it usually holds project sources, not build output",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Handle 'clean cache-folders add' command - Add a folder name to work cache cleanup
+pub fn handle_cache_folders_add(matches: &clap::ArgMatches) -> Result<()> {
+    let folder = matches
+        .get_one::<String>("folder")
+        .ok_or_else(|| anyhow::anyhow!("Folder is required"))?;
+
+    validate_cache_folder_name(folder)?;
+
+    // Load config
+    let mut config = Config::load()?;
+
+    // Try to add the folder
+    if config.add_work_cache_folder(folder.clone()) {
+        config.save()?;
+        println!();
+        println!("{}", "✓ Folder added to cache folders:".green().bold());
+        println!("  {}", folder.cyan());
+        println!();
+        println!(
+            "{}",
+            "Folders with this name inside work projects will be deleted by --work-cache.".dimmed()
+        );
+        println!();
+    } else {
+        println!("{}", "Folder already in cache folders.".yellow());
+        println!("  {}", folder.cyan());
+    }
+
+    Ok(())
+}
+
+/// Handle 'clean cache-folders list' command - List the work cache folder names
+pub fn handle_cache_folders_list(_matches: &clap::ArgMatches) -> Result<()> {
+    let config = Config::load()?;
+    let cache_folders = config.get_work_cache_folders();
+
+    println!("{}", "═".repeat(50).white());
+    println!("{}", "  WORK CACHE FOLDERS  ".white().bold());
+    println!("{}", "═".repeat(50).white());
+    println!();
+
+    if cache_folders.is_empty() {
+        println!("  {}", "No cache folders configured.".dimmed());
+    } else {
+        println!(
+            "{}",
+            "Folders deleted inside each project during work cache cleanup:".white()
+        );
+        println!();
+
+        for (index, folder) in cache_folders.iter().enumerate() {
+            println!("  {}. {}", (index + 1).to_string().dimmed(), folder.cyan());
+        }
+    }
+
+    if config.work_cache_folders.is_none() {
+        println!();
+        println!("{}", "Using the default list.".dimmed());
+    }
+
+    println!();
+    println!("{}", "═".repeat(50).white());
+    println!();
+
+    Ok(())
+}
+
+/// Handle 'clean cache-folders remove' command - Stop cleaning a folder name
+pub fn handle_cache_folders_remove(matches: &clap::ArgMatches) -> Result<()> {
+    let folder = matches
+        .get_one::<String>("folder")
+        .ok_or_else(|| anyhow::anyhow!("Folder is required"))?;
+
+    // Load config
+    let mut config = Config::load()?;
+
+    // Try to remove the folder
+    if config.remove_work_cache_folder(folder) {
+        config.save()?;
+        println!();
+        println!("{}", "✓ Folder removed from cache folders:".green().bold());
+        println!("  {}", folder.cyan());
+        println!();
+        println!(
+            "{}",
+            "Folders with this name will no longer be deleted by --work-cache.".dimmed()
+        );
+        println!();
+    } else {
+        println!("{}", "Folder not found in cache folders.".yellow());
+        println!("  {}", folder.cyan());
+        println!();
+        println!(
+            "{}",
+            "Use 'msc clean cache-folders list' to see all cache folders.".dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categorized() -> DirectoriesByPrivilege {
+        DirectoriesByPrivilege {
+            user_directories: vec!["/home/u/.cache/tmp".to_string()],
+            system_directories: vec!["/tmp".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_cache_folder_names_must_be_plain() {
+        for name in [".next", "__pycache__", "build", "vendor"] {
+            assert!(validate_cache_folder_name(name).is_ok(), "{}", name);
+        }
+        for name in ["", ".", "..", "a/b", "..\\x"] {
+            assert!(validate_cache_folder_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_cache_folder_names_refuse_source_folders() {
+        for name in ["src", "SRC", "lib", "tests", "docs", ".git"] {
+            assert!(validate_cache_folder_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_age_window_validates_bounds() {
+        let hours = |h: u64| Some(std::time::Duration::from_secs(h * 3600));
+
+        assert_eq!(
+            age_window(Some(24), Some(720), false).unwrap(),
+            (hours(24), hours(720))
+        );
+        assert_eq!(
+            age_window(Some(24), Some(24), false).unwrap(),
+            (hours(24), hours(24))
+        );
+        assert_eq!(age_window(None, Some(48), true).unwrap(), (None, hours(48)));
+        assert_eq!(age_window(None, None, false).unwrap(), (hours(8), None));
+
+        let err = age_window(Some(48), Some(24), false).unwrap_err();
+        assert!(err.to_string().contains("--max-age"));
+
+        // A lone --max-age is checked against the default minimum
+        assert_eq!(
+            age_window(None, Some(8), false).unwrap(),
+            (hours(8), hours(8))
+        );
+        let err = age_window(None, Some(4), false).unwrap_err();
+        assert!(err.to_string().contains("default minimum"));
+    }
+
+    #[test]
+    fn test_clean_scope_keeps_one_category() {
+        let all = CleanScope::All.apply(categorized());
+        assert_eq!(all.user_directories.len(), 1);
+        assert_eq!(all.system_directories.len(), 1);
+
+        let user = CleanScope::UserOnly.apply(categorized());
+        assert_eq!(user.user_directories, ["/home/u/.cache/tmp"]);
+        assert!(user.system_directories.is_empty());
+
+        let system = CleanScope::SystemOnly.apply(categorized());
+        assert!(system.user_directories.is_empty());
+        assert_eq!(system.system_directories, ["/tmp"]);
+    }
+}
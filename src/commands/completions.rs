@@ -1,31 +1,92 @@
-use anyhow::Result;
-use clap::{ArgMatches, Command};
+use crate::cli::build_cli;
+use crate::ui::glyphs::adapt;
+use crate::ui::output::stdout_writer;
+use anyhow::{Context, Result};
+use clap::ArgMatches;
 use clap_complete::{generate, Shell};
-use std::io;
+use colored::Colorize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 /// Generate shell completions for the specified shell
-pub fn execute(matches: &ArgMatches, cli: &mut Command) -> Result<()> {
-    if let Some(shell_str) = matches.get_one::<String>("shell") {
-        let shell = match shell_str.to_lowercase().as_str() {
-            "bash" => Shell::Bash,
-            "zsh" => Shell::Zsh,
-            "fish" => Shell::Fish,
-            "powershell" => Shell::PowerShell,
-            "elvish" => Shell::Elvish,
-            _ => {
-                eprintln!("Unsupported shell: {}", shell_str);
-                eprintln!("Supported shells: bash, zsh, fish, powershell, elvish");
-                std::process::exit(1);
-            }
-        };
-
-        generate(shell, cli, "msc", &mut io::stdout());
-        Ok(())
-    } else {
-        eprintln!("Error: shell argument is required");
-        eprintln!("Usage: msc completions <SHELL>");
-        eprintln!("Supported shells: bash, zsh, fish, powershell, elvish");
-        std::process::exit(1);
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let shell = *matches
+        .get_one::<Shell>("shell")
+        .context("Shell argument is required")?;
+    let mut cli = build_cli();
+
+    if matches.value_source("output").is_none() {
+        let mut out = stdout_writer();
+        generate(shell, &mut cli, "msc", &mut out);
+        out.flush()?;
+        return Ok(());
+    }
+
+    // `--output` without a directory means the shell's usual location
+    let dir = match matches.get_one::<String>("output") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let home = dirs::home_dir().context("Could not determine home directory")?;
+            default_completion_dir(shell, &home).with_context(|| {
+                format!(
+                    "{} has no conventional completions directory; pass one to --output",
+                    shell
+                )
+            })?
+        }
+    };
+
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    let path = dir.join(completion_file_name(shell));
+    let mut file =
+        fs::File::create(&path).with_context(|| format!("Failed to create {:?}", path))?;
+    generate(shell, &mut cli, "msc", &mut file);
+
+    println!(
+        "{} {}",
+        format!("{} Wrote {} completions to", adapt("✓"), shell).green(),
+        path.display()
+    );
+    if let Some(hint) = setup_hint(shell, &dir) {
+        println!("  {}", hint.dimmed());
+    }
+    Ok(())
+}
+
+/// File name each shell looks for in its completions directory
+fn completion_file_name(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => "msc",
+        Shell::Zsh => "_msc",
+        Shell::Fish => "msc.fish",
+        Shell::Elvish => "msc.elv",
+        _ => "msc.ps1",
+    }
+}
+
+/// Per-user directory each shell loads completions from, if it has one
+fn default_completion_dir(shell: Shell, home: &Path) -> Option<PathBuf> {
+    match shell {
+        Shell::Bash => Some(home.join(".local/share/bash-completion/completions")),
+        Shell::Zsh => Some(home.join(".zfunc")),
+        Shell::Fish => Some(home.join(".config/fish/completions")),
+        Shell::Elvish => Some(home.join(".config/elvish/lib")),
+        _ => None,
+    }
+}
+
+/// What the user still has to do for the shell to pick the file up
+fn setup_hint(shell: Shell, dir: &Path) -> Option<String> {
+    match shell {
+        Shell::Zsh => Some(format!(
+            "Make sure {} is in your fpath before compinit (fpath+=({}))",
+            dir.display(),
+            dir.display()
+        )),
+        Shell::Elvish => Some("Add 'use msc' to your rc.elv".to_string()),
+        Shell::PowerShell => Some("Dot-source the file from your $PROFILE".to_string()),
+        _ => None,
     }
 }
 
@@ -42,4 +103,36 @@ mod tests {
         let _powershell = Shell::PowerShell;
         let _elvish = Shell::Elvish;
     }
+
+    #[test]
+    fn test_completions_cover_real_command_tree() {
+        for shell in [
+            Shell::Bash,
+            Shell::Zsh,
+            Shell::Fish,
+            Shell::PowerShell,
+            Shell::Elvish,
+        ] {
+            let mut out = Vec::new();
+            generate(shell, &mut build_cli(), "msc", &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("checksum"), "{} misses subcommands", shell);
+        }
+    }
+
+    #[test]
+    fn test_default_completion_dirs() {
+        let home = Path::new("/home/user");
+        assert_eq!(
+            default_completion_dir(Shell::Fish, home)
+                .unwrap()
+                .join(completion_file_name(Shell::Fish)),
+            home.join(".config/fish/completions/msc.fish")
+        );
+        assert_eq!(
+            default_completion_dir(Shell::Zsh, home),
+            Some(home.join(".zfunc"))
+        );
+        assert_eq!(default_completion_dir(Shell::PowerShell, home), None);
+    }
 }
@@ -1,7 +1,11 @@
-use crate::core::{validation, Config};
+use crate::core::{validation, Config, ImportMode};
+use crate::ui::glyphs::adapt;
+use crate::ui::read_confirmation;
 use anyhow::{Context, Result};
 use colored::Colorize;
-use std::path::Path;
+use std::fs;
+
+pub use crate::core::config::ALLOW_SHELL_OPERATORS;
 
 /// Configuration type enum for DRY code
 enum ConfigType {
@@ -27,6 +31,14 @@ impl ConfigType {
         }
     }
 
+    fn clear_path(&self, config: &mut Config) -> Option<String> {
+        match self {
+            ConfigType::Work => config.clear_work_path(),
+            ConfigType::Video => config.clear_video_path(),
+            ConfigType::Web => config.clear_web_path(),
+        }
+    }
+
     fn get_path<'a>(&self, config: &'a Config) -> Option<&'a String> {
         match self {
             ConfigType::Work => config.get_work_path(),
@@ -49,6 +61,8 @@ pub fn handle_set(matches: &clap::ArgMatches) -> Result<()> {
         Some(("work", sub_matches)) => set_path_for_type(sub_matches, ConfigType::Work),
         Some(("video", sub_matches)) => set_path_for_type(sub_matches, ConfigType::Video),
         Some(("web", sub_matches)) => set_path_for_type(sub_matches, ConfigType::Web),
+        Some(("editor", sub_matches)) => set_editor(sub_matches),
+        Some((ALLOW_SHELL_OPERATORS, sub_matches)) => set_allow_shell_operators(sub_matches),
         _ => {
             println!("Use 'msc set --help' for more information.");
             Ok(())
@@ -61,50 +75,89 @@ fn set_path_for_type(matches: &clap::ArgMatches, config_type: ConfigType) -> Res
     let path = matches
         .get_one::<String>("path")
         .context("Path argument is required")?;
+    let no_create = matches.get_flag("no-create");
 
-    // Validar el path antes de usarlo
-    validation::validate_directory_path(path)?;
-
-    let path_buf = Path::new(path);
-    if !path_buf.exists() {
-        println!(
-            "{}",
-            format!("⚠️  Warning: Path '{}' does not exist", path).yellow()
-        );
+    let resolved = validation::resolve_config_directory(path, |missing| {
+        if no_create {
+            return Ok(false);
+        }
         println!(
             "{}",
-            "The path will be saved but may not be usable until created.".dimmed()
+            format!(
+                "{} Directory '{}' does not exist.",
+                adapt("⚠️ "),
+                missing.display()
+            )
+            .yellow()
         );
-    }
+        read_confirmation("Create it? (y/N): ", 3)
+    })?;
 
-    let canonical_path = if path_buf.exists() {
-        path_buf
-            .canonicalize()
-            .map_err(|e| anyhow::anyhow!("Failed to resolve path: {}", e))?
-            .to_string_lossy()
-            .to_string()
-    } else {
-        path.to_string()
-    };
+    let canonical_path = resolved.to_string_lossy().to_string();
 
     let mut config = Config::load()?;
     config_type.set_path(&mut config, canonical_path.clone());
     config.save()?;
 
+    let cleaned_path = canonical_path
+        .strip_prefix("\\\\?\\")
+        .unwrap_or(&canonical_path);
     println!(
         "{} {}",
-        format!("✓ {} path set to:", config_type.name()).green(),
-        canonical_path
+        format!("{} {} path set to:", adapt("✓"), config_type.name()).green(),
+        cleaned_path
     );
 
     Ok(())
 }
 
+pub fn handle_unset(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("work", _)) => unset_path_for_type(ConfigType::Work),
+        Some(("video", _)) => unset_path_for_type(ConfigType::Video),
+        Some(("web", _)) => unset_path_for_type(ConfigType::Web),
+        Some(("editor", _)) => unset_editor(),
+        Some((ALLOW_SHELL_OPERATORS, _)) => unset_allow_shell_operators(),
+        _ => {
+            println!("Use 'msc unset --help' for more information.");
+            Ok(())
+        }
+    }
+}
+
+/// Shared logic for clearing a path configuration
+fn unset_path_for_type(config_type: ConfigType) -> Result<()> {
+    let mut config = Config::load()?;
+    let type_lower = config_type.name().to_lowercase();
+
+    match config_type.clear_path(&mut config) {
+        Some(previous) => {
+            config.save()?;
+            let cleaned_path = previous.strip_prefix("\\\\?\\").unwrap_or(&previous);
+            println!(
+                "{} {}",
+                format!("{} {} path cleared (was:", adapt("✓"), config_type.name()).green(),
+                format!("{})", cleaned_path).dimmed()
+            );
+        }
+        None => {
+            println!(
+                "{}",
+                format!("No {} directory configured.", type_lower).yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 pub fn handle_get(matches: &clap::ArgMatches) -> Result<()> {
     match matches.subcommand() {
         Some(("work", _)) => get_path_for_type(ConfigType::Work),
         Some(("video", _)) => get_path_for_type(ConfigType::Video),
         Some(("web", _)) => get_path_for_type(ConfigType::Web),
+        Some(("editor", _)) => get_editor(),
+        Some((ALLOW_SHELL_OPERATORS, _)) => get_allow_shell_operators(),
         _ => {
             println!("Use 'msc get --help' for more information.");
             Ok(())
@@ -149,3 +202,219 @@ fn get_path_for_type(config_type: ConfigType) -> Result<()> {
 
     Ok(())
 }
+
+fn set_editor(matches: &clap::ArgMatches) -> Result<()> {
+    let command = matches
+        .get_one::<String>("command")
+        .context("Editor command is required")?
+        .trim()
+        .to_string();
+    if command.is_empty() {
+        return Err(anyhow::anyhow!("Editor command cannot be empty"));
+    }
+
+    let mut config = Config::load()?;
+    config.set_editor(command.clone());
+    config.save()?;
+
+    println!(
+        "{} {}",
+        format!("{} Editor set to:", adapt("✓")).green(),
+        command
+    );
+    Ok(())
+}
+
+fn unset_editor() -> Result<()> {
+    let mut config = Config::load()?;
+
+    match config.clear_editor() {
+        Some(previous) => {
+            config.save()?;
+            println!(
+                "{} {}",
+                format!("{} Editor cleared (was:", adapt("✓")).green(),
+                format!("{})", previous).dimmed()
+            );
+        }
+        None => println!("{}", "No editor configured.".yellow()),
+    }
+
+    Ok(())
+}
+
+fn get_editor() -> Result<()> {
+    let config = Config::load()?;
+
+    match config.get_editor() {
+        Some(editor) => {
+            println!("{}", "Editor command:".white());
+            println!("{}", editor.cyan().bold());
+        }
+        None => {
+            let fallback = config.resolve_editor(std::env::var("EDITOR").ok());
+            println!("{}", "No editor configured.".yellow());
+            println!(
+                "{}",
+                format!("'msc work open' will use: {}", fallback).dimmed()
+            );
+            println!();
+            println!("{}", "To set an editor, run:".white());
+            println!("  {}", "msc set editor <command>".cyan().bold());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn handle_config(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("export", sub_matches)) => export_config(sub_matches),
+        Some(("import", sub_matches)) => import_config(sub_matches),
+        _ => {
+            println!("Use 'msc config --help' for more information.");
+            Ok(())
+        }
+    }
+}
+
+fn export_config(matches: &clap::ArgMatches) -> Result<()> {
+    let file = matches
+        .get_one::<String>("file")
+        .context("File argument is required")?;
+
+    let config = Config::load()?;
+    fs::write(file, config.to_json()?)
+        .with_context(|| format!("Failed to write config to '{}'", file))?;
+
+    println!(
+        "{} {}",
+        format!("{} Configuration exported to:", adapt("✓")).green(),
+        file
+    );
+    Ok(())
+}
+
+fn import_config(matches: &clap::ArgMatches) -> Result<()> {
+    let file = matches
+        .get_one::<String>("file")
+        .context("File argument is required")?;
+    let mode = if matches.get_flag("replace") {
+        ImportMode::Replace
+    } else {
+        ImportMode::Merge
+    };
+
+    let data = fs::read_to_string(file).with_context(|| format!("Failed to read '{}'", file))?;
+    let imported = Config::from_json(&data)
+        .with_context(|| format!("'{}' is not an exported msc config", file))?;
+
+    let mut config = Config::load()?;
+    let not_applied = config.import(imported, mode);
+    config.save()?;
+
+    let verb = match mode {
+        ImportMode::Merge => "merged from",
+        ImportMode::Replace => "replaced from",
+    };
+    println!(
+        "{} {}",
+        format!("{} Configuration {}:", adapt("✓"), verb).green(),
+        file
+    );
+
+    for setting in &not_applied {
+        println!(
+            "{}",
+            format!(
+                "{} '{}' enables {}; kept the local value (enable it with 'msc set {} true')",
+                adapt("⚠️ "),
+                file,
+                setting,
+                setting
+            )
+            .yellow()
+        );
+    }
+
+    let missing = config.missing_paths();
+    if !missing.is_empty() {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "{} {} configured path(s) don't exist on this machine:",
+                adapt("⚠️ "),
+                missing.len()
+            )
+            .yellow()
+        );
+        for (setting, path) in &missing {
+            println!("  {} {}", format!("{}:", setting).dimmed(), path);
+        }
+    }
+
+    Ok(())
+}
+
+fn set_allow_shell_operators(matches: &clap::ArgMatches) -> Result<()> {
+    let allow = *matches
+        .get_one::<bool>("value")
+        .context("Value is required")?;
+
+    let mut config = Config::load()?;
+    config.set_alias_allow_shell_operators(allow);
+    config.save()?;
+
+    println!(
+        "{} {}",
+        format!("{} {} set to:", adapt("✓"), ALLOW_SHELL_OPERATORS).green(),
+        allow
+    );
+    if allow {
+        println!(
+            "  {}",
+            "Aliases created with 'msc alias add --unsafe' may now use shell operators.".dimmed()
+        );
+    }
+    Ok(())
+}
+
+fn unset_allow_shell_operators() -> Result<()> {
+    let mut config = Config::load()?;
+
+    if config.alias_allow_shell_operators() {
+        config.set_alias_allow_shell_operators(false);
+        config.save()?;
+        println!(
+            "{}",
+            format!(
+                "{} {} reset to false (was: true)",
+                adapt("✓"),
+                ALLOW_SHELL_OPERATORS
+            )
+            .green()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("{} is already false.", ALLOW_SHELL_OPERATORS).yellow()
+        );
+    }
+
+    Ok(())
+}
+
+fn get_allow_shell_operators() -> Result<()> {
+    let config = Config::load()?;
+    println!(
+        "{} {}",
+        format!("{}:", ALLOW_SHELL_OPERATORS).white(),
+        config
+            .alias_allow_shell_operators()
+            .to_string()
+            .cyan()
+            .bold()
+    );
+    Ok(())
+}
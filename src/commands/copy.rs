@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use crate::core::checksum_manager::Algorithm;
+use crate::core::file_copy::{copy_file, part_path, resolve_destination, verify_copy, CopyStats};
+use crate::ui::formatters::format_size;
+use crate::ui::glyphs::adapt;
+use crate::ui::{interrupt, CliProgressBar, ProgressTracker, ProgressUnit};
+
+/// `msc cp <source> <destination>`
+pub fn execute_cp(matches: &clap::ArgMatches) -> Result<()> {
+    let (source, destination) = paths(matches)?;
+    let verify = verify_algorithm(matches)?;
+
+    copy_with_progress(&source, &destination, verify)?;
+    println!(
+        "{} {} → {}",
+        format!("{} Copied", adapt("✓")).green(),
+        source.display(),
+        destination.display()
+    );
+    Ok(())
+}
+
+/// `msc mv <source> <destination>`
+///
+/// A plain rename when both paths are on the same file system; otherwise a
+/// copy (verified if requested) followed by deleting the source.
+pub fn execute_mv(matches: &clap::ArgMatches) -> Result<()> {
+    let (source, destination) = paths(matches)?;
+    let verify = verify_algorithm(matches)?;
+
+    if !part_path(&destination).exists() && fs::rename(&source, &destination).is_ok() {
+        println!(
+            "{} {} → {}",
+            format!("{} Moved", adapt("✓")).green(),
+            source.display(),
+            destination.display()
+        );
+        return Ok(());
+    }
+
+    copy_with_progress(&source, &destination, verify)?;
+    fs::remove_file(&source)
+        .with_context(|| format!("Copied, but failed to remove '{}'", source.display()))?;
+    println!(
+        "{} {} → {}",
+        format!("{} Moved", adapt("✓")).green(),
+        source.display(),
+        destination.display()
+    );
+    Ok(())
+}
+
+fn paths(matches: &clap::ArgMatches) -> Result<(PathBuf, PathBuf)> {
+    let source = PathBuf::from(
+        matches
+            .get_one::<String>("source")
+            .context("Source argument is required")?,
+    );
+    let destination = matches
+        .get_one::<String>("destination")
+        .context("Destination argument is required")?;
+
+    if !source.is_file() {
+        anyhow::bail!("'{}' is not a file", source.display());
+    }
+    let destination = resolve_destination(&source, Path::new(destination))?;
+    Ok((source, destination))
+}
+
+fn verify_algorithm(matches: &clap::ArgMatches) -> Result<Option<Algorithm>> {
+    if !matches.get_flag("verify") {
+        return Ok(None);
+    }
+    match matches.get_one::<String>("algorithm") {
+        Some(name) => name.parse::<Algorithm>().map(Some),
+        None => Ok(Some(Algorithm::default())),
+    }
+}
+
+/// Copy with a progress bar; Ctrl+C stops cleanly and keeps the `.part` file
+fn copy_with_progress(
+    source: &Path,
+    destination: &Path,
+    verify: Option<Algorithm>,
+) -> Result<CopyStats> {
+    let cancel_flag = interrupt::cancel_flag()?;
+
+    let show_progress = std::io::stdout().is_terminal();
+    let mut progress =
+        ProgressTracker::new(0, ProgressUnit::Bytes, CliProgressBar::new("Copying:"));
+
+    let result = copy_file(
+        source,
+        destination,
+        |copied, total| {
+            if show_progress {
+                progress.set(copied, total);
+            }
+        },
+        Some(&cancel_flag),
+    );
+    if show_progress {
+        progress.finish();
+    }
+    let stats = result?;
+
+    if stats.resumed_from > 0 {
+        println!(
+            "{}",
+            format!(
+                "Resumed an interrupted copy at {} of {}",
+                format_size(stats.resumed_from),
+                format_size(stats.total)
+            )
+            .dimmed()
+        );
+    }
+
+    if let Some(algorithm) = verify {
+        verify_copy(source, destination, algorithm)?;
+        println!(
+            "{}",
+            format!("{} Checksums match ({})", adapt("✓"), algorithm).green()
+        );
+    }
+
+    Ok(stats)
+}
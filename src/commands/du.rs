@@ -0,0 +1,56 @@
+use anyhow::Result;
+use colored::{Color, Colorize};
+use std::path::Path;
+
+use crate::core::disk_usage::{disk_usage, SizeMode};
+use crate::ui::formatters::format_size;
+use crate::ui::{Align, Cell, Table};
+
+pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
+    let path = matches
+        .get_one::<String>("path")
+        .map(|s| s.as_str())
+        .unwrap_or(".");
+    let mode = if matches.get_flag("apparent-size") {
+        SizeMode::Apparent
+    } else {
+        SizeMode::Allocated
+    };
+
+    let dir_path = Path::new(path);
+    if !dir_path.is_dir() {
+        println!("{}", format!("Error: '{}' is not a directory", path).red());
+        return Ok(());
+    }
+
+    let usage = disk_usage(dir_path, matches.get_flag("all"), mode)?;
+    if usage.is_empty() {
+        println!("{}", "The directory is empty.".yellow());
+        return Ok(());
+    }
+
+    let mut table = Table::new(&["Size", "Name"])
+        .align(0, Align::Right)
+        .color(0, Color::Green)
+        .borderless();
+    for entry in &usage {
+        let name = if entry.is_dir {
+            Cell::new(format!("{}/", entry.name)).color(Color::Blue)
+        } else {
+            Cell::new(entry.name.as_str())
+        };
+        table.add_row([Cell::new(format_size(entry.size)), name]);
+    }
+    table.print();
+
+    let total: u64 = usage.iter().map(|entry| entry.size).sum();
+    println!();
+    println!(
+        "{} {} {}",
+        "Total:".white().bold(),
+        format_size(total).green().bold(),
+        format!("({} size)", mode.label()).dimmed()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use regex::RegexBuilder;
+use std::path::{Path, PathBuf};
+
+use crate::core::batch_exec::filter_by_glob;
+use crate::core::grep::{search_files, GrepMatch};
+use crate::core::{FileScanner, ScanEntry};
+
+pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
+    let pattern = matches
+        .get_one::<String>("pattern")
+        .context("Pattern argument is required")?;
+    let dir = matches
+        .get_one::<String>("path")
+        .map(|s| s.as_str())
+        .unwrap_or(".");
+
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(matches.get_flag("ignore-case"))
+        .build()
+        .with_context(|| format!("Invalid pattern '{}'", pattern))?;
+
+    let dir_path = Path::new(dir);
+    if !dir_path.is_dir() {
+        println!("{}", format!("Error: '{}' is not a directory", dir).red());
+        return Ok(());
+    }
+
+    let scanner = FileScanner::new(dir_path)?;
+    let entries: Vec<ScanEntry> = scanner
+        .scan_recursive(
+            matches.get_flag("all"),
+            matches.get_flag("respect-gitignore"),
+        )?
+        .into_iter()
+        .filter(|entry| !entry.is_dir && !entry.is_symlink)
+        .collect();
+
+    let files: Vec<PathBuf> = match matches.get_one::<String>("glob") {
+        Some(glob) => filter_by_glob(dir_path, &entries, glob)?,
+        None => entries.into_iter().map(|entry| entry.path).collect(),
+    };
+
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let results = search_files(&files, &regex, workers);
+
+    if results.is_empty() {
+        println!("{}", "No matches found.".yellow());
+        return Ok(());
+    }
+
+    for result in &results {
+        print_match(dir_path, result);
+    }
+
+    Ok(())
+}
+
+/// `path:line:text` with the matched parts highlighted
+fn print_match(root: &Path, result: &GrepMatch) {
+    let path = result.path.strip_prefix(root).unwrap_or(&result.path);
+
+    let mut line = String::new();
+    let mut last = 0;
+    for &(start, end) in &result.ranges {
+        line.push_str(&result.line[last..start]);
+        line.push_str(&result.line[start..end].red().bold().to_string());
+        last = end;
+    }
+    line.push_str(&result.line[last..]);
+
+    println!(
+        "{}:{}:{}",
+        path.display().to_string().magenta(),
+        result.line_number.to_string().green(),
+        line
+    );
+}
@@ -1,15 +1,30 @@
+use crate::core::batch_exec::{filter_by_glob, run_for_each, ExecTemplate, SystemRunner};
+use crate::core::{
+    find_duplicates, DirectorySummary, DuplicateGroup, FileScanner, FileStat, ScanEntry,
+};
+use crate::error::CommandFailed;
 use crate::git::{
-    apply_git_colors, get_git_status_for_file, is_gitignored, load_git_status, load_gitignore,
+    apply_git_colors, get_git_status_for_file, is_gitignored, load_branch_info, load_git_status,
+    load_gitignore, BranchInfo, GitStatus,
 };
 use crate::platform::is_hidden;
-use crate::ui::{format_permissions, format_size, format_time};
+use crate::ui::glyphs::adapt;
+use crate::ui::output::stdout_writer;
+use crate::ui::{
+    colorize_permissions, confirm, format_permissions, format_size, format_time, Cell, Table,
+};
 use crate::utils::icons::get_file_icon;
+use crate::utils::mime::{classify_file, ContentClass};
+use crate::utils::path::PathStyle;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Local, SecondsFormat};
 use colored::*;
+use serde::Serialize;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use unicode_width::UnicodeWidthStr;
 
 pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     match matches.subcommand() {
@@ -23,7 +38,7 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
                 .get_one::<u32>("depth")
                 .context("Depth argument is required")?;
 
-            list_deep(path, show_all, depth)
+            list_deep(path, show_all, None, depth, &PathStyle::Name)
         }
         _ => {
             let path = matches
@@ -33,11 +48,79 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
             let show_all = matches.get_flag("all");
             let is_deep = matches.get_flag("deep");
             let is_long = matches.get_flag("long");
-
-            if is_long {
+            let only = match matches.get_one::<String>("only").map(|s| s.as_str()) {
+                Some("text") => Some(ContentClass::Text),
+                Some("binary") => Some(ContentClass::Binary),
+                _ => None,
+            };
+            let path_style = if matches.get_flag("absolute") {
+                PathStyle::Absolute
+            } else if let Some(base) = matches.get_one::<String>("relative-to") {
+                PathStyle::relative_to(base)?
+            } else {
+                PathStyle::Name
+            };
+
+            if matches.get_flag("stat") {
+                stat_path(path)
+            } else if matches.get_flag("print0") {
+                let max_depth = if is_deep {
+                    *matches
+                        .get_one::<u32>("depth")
+                        .context("Depth argument is required")?
+                } else {
+                    0
+                };
+                list_print0(path, show_all, only, max_depth, &path_style)
+            } else if matches.get_flag("json") {
+                let max_depth = if is_deep {
+                    *matches
+                        .get_one::<u32>("depth")
+                        .context("Depth argument is required")?
+                } else {
+                    0
+                };
+                list_json(path, show_all, only, max_depth, &path_style)
+            } else if matches.get_flag("count") {
+                count_path(path, show_all, matches.get_flag("respect-gitignore"))
+            } else if matches.get_flag("duplicates") {
+                list_duplicates(
+                    path,
+                    show_all,
+                    matches.get_flag("respect-gitignore"),
+                    matches.get_flag("delete-extra"),
+                )
+            } else if let Some(command) = matches.get_one::<String>("exec") {
+                let max_depth = if is_deep {
+                    *matches
+                        .get_one::<u32>("depth")
+                        .context("Depth argument is required")?
+                        + 1
+                } else {
+                    1
+                };
+                let filter = ExecFilter {
+                    show_all,
+                    only,
+                    respect_gitignore: matches.get_flag("respect-gitignore"),
+                    max_depth: max_depth as usize,
+                    glob: matches.get_one::<String>("glob").map(|s| s.as_str()),
+                };
+                exec_matching(
+                    path,
+                    &filter,
+                    command,
+                    *matches.get_one::<usize>("exec-parallel").unwrap_or(&1),
+                )
+            } else if is_long {
+                let columns = match matches.get_one::<String>("columns") {
+                    Some(spec) => parse_long_columns(spec)?,
+                    None => LongColumn::defaults(),
+                };
                 list_long(
                     path,
                     show_all,
+                    only,
                     is_deep,
                     if is_deep {
                         *matches
@@ -46,20 +129,402 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
                     } else {
                         0
                     },
+                    &columns,
                 )
             } else if is_deep {
                 let depth = *matches
                     .get_one::<u32>("depth")
                     .context("Depth argument is required")?;
-                list_deep(path, show_all, depth)
+                list_deep(path, show_all, only, depth, &path_style)
+            } else {
+                list_simple(path, show_all, only, &path_style)
+            }
+        }
+    }
+}
+
+fn stat_path(path: &str) -> Result<()> {
+    let target = Path::new(path);
+
+    if fs::symlink_metadata(target).is_err() {
+        println!("{}", format!("Error: Path '{}' does not exist", path).red());
+        return Ok(());
+    }
+
+    let stat = FileStat::from_path(target)?;
+
+    let path_buf = target
+        .canonicalize()
+        .unwrap_or_else(|_| target.to_path_buf());
+    let canonical_path = path_buf.to_string_lossy();
+    let clean_path = canonical_path
+        .strip_prefix("\\\\?\\")
+        .unwrap_or(&canonical_path);
+
+    let file_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+    let icon = if stat.is_dir {
+        "📂"
+    } else {
+        get_file_icon(&file_name).trim_end()
+    };
+
+    let kind = if stat.is_symlink {
+        "symlink"
+    } else if stat.is_dir {
+        "directory"
+    } else {
+        "file"
+    };
+    let format_optional_time = |time: Option<SystemTime>| {
+        time.map(format_time)
+            .unwrap_or_else(|| "unavailable".to_string())
+    };
+
+    println!("{} {}", icon, file_name.white().bold());
+    println!();
+    println!("  {:<12} {}", "Path:".white(), clean_path.cyan());
+    println!("  {:<12} {}", "Type:".white(), kind);
+    println!(
+        "  {:<12} {} {}",
+        "Size:".white(),
+        format_size(stat.size).yellow(),
+        format!("({} bytes)", stat.size).dimmed()
+    );
+    println!(
+        "  {:<12} {}",
+        "Permissions:".white(),
+        format_permissions(&stat.metadata).magenta()
+    );
+    println!("  {:<12} {}", "MIME type:".white(), stat.mime_type);
+    println!(
+        "  {:<12} {}",
+        "Created:".white(),
+        format_optional_time(stat.created).cyan()
+    );
+    println!(
+        "  {:<12} {}",
+        "Modified:".white(),
+        format_optional_time(stat.modified).green()
+    );
+    println!(
+        "  {:<12} {}",
+        "Accessed:".white(),
+        format_optional_time(stat.accessed)
+    );
+    if let Some(inode) = stat.inode {
+        println!("  {:<12} {}", "Inode:".white(), inode);
+    }
+    if let Some(links) = stat.hard_links {
+        println!("  {:<12} {}", "Hard links:".white(), links);
+    }
+
+    let git_label = match &stat.git_status {
+        None => "not in a repository".dimmed(),
+        Some(GitStatus::Clean) => "clean".normal(),
+        Some(GitStatus::Untracked) => "untracked".bright_green(),
+        Some(GitStatus::Modified) => "modified".bright_green(),
+        Some(GitStatus::Added) => "added".bright_green(),
+        Some(GitStatus::Renamed) => "renamed".bright_green(),
+        Some(GitStatus::Deleted) => "deleted".red(),
+    };
+    println!("  {:<12} {}", "Git status:".white(), git_label);
+
+    Ok(())
+}
+
+/// Number of extensions shown in the `--count` breakdown
+const TOP_EXTENSIONS: usize = 10;
+
+fn count_path(path: &str, show_all: bool, respect_gitignore: bool) -> Result<()> {
+    let dir_path = Path::new(path);
+
+    if !dir_path.exists() {
+        println!(
+            "{}",
+            format!("Error: Directory '{}' does not exist", path).red()
+        );
+        return Ok(());
+    }
+
+    if !dir_path.is_dir() {
+        println!("{}", format!("Error: '{}' is not a directory", path).red());
+        return Ok(());
+    }
+
+    let path_buf = dir_path
+        .canonicalize()
+        .unwrap_or_else(|_| dir_path.to_path_buf());
+    let canonical_path = path_buf.to_string_lossy();
+    let clean_path = canonical_path
+        .strip_prefix("\\\\?\\")
+        .unwrap_or(&canonical_path);
+
+    println!(
+        "{} {} {}",
+        "Directory:".white(),
+        clean_path.cyan().bold(),
+        "(summary)".dimmed()
+    );
+    println!();
+
+    let scanner = FileScanner::new(dir_path)?;
+    let entries = scanner.scan_recursive(show_all, respect_gitignore)?;
+    let summary = DirectorySummary::from_entries(&entries);
+
+    println!(
+        "  {:<12} {}",
+        "Files:".white(),
+        summary.files.to_string().yellow()
+    );
+    println!(
+        "  {:<12} {}",
+        "Directories:".white(),
+        summary.directories.to_string().blue()
+    );
+    println!(
+        "  {:<12} {}",
+        "Symlinks:".white(),
+        summary.symlinks.to_string().cyan()
+    );
+    println!(
+        "  {:<12} {}",
+        "Total size:".white(),
+        format_size(summary.total_size).green()
+    );
+
+    let top = summary.top_extensions(TOP_EXTENSIONS);
+    if !top.is_empty() {
+        println!();
+        println!("{}", "Top extensions:".white().bold());
+        for (ext, count, size) in top {
+            let label = if ext == crate::core::file_scanner::NO_EXTENSION {
+                ext
+            } else {
+                format!(".{}", ext)
+            };
+            println!(
+                "  {:<12} {:>8} {}",
+                label,
+                count.to_string().yellow(),
+                format!("({})", format_size(size)).dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn list_duplicates(
+    path: &str,
+    show_all: bool,
+    respect_gitignore: bool,
+    delete_extra: bool,
+) -> Result<()> {
+    let dir_path = Path::new(path);
+
+    if !dir_path.is_dir() {
+        println!("{}", format!("Error: '{}' is not a directory", path).red());
+        return Ok(());
+    }
+
+    let path_buf = dir_path
+        .canonicalize()
+        .unwrap_or_else(|_| dir_path.to_path_buf());
+    let canonical_path = path_buf.to_string_lossy();
+    let clean_path = canonical_path
+        .strip_prefix("\\\\?\\")
+        .unwrap_or(&canonical_path);
+
+    println!(
+        "{} {} {}",
+        "Directory:".white(),
+        clean_path.cyan().bold(),
+        "(duplicates)".dimmed()
+    );
+    println!();
+
+    let scanner = FileScanner::new(dir_path)?;
+    let entries = scanner.scan_recursive(show_all, respect_gitignore)?;
+    let groups = find_duplicates(&entries);
+
+    if groups.is_empty() {
+        println!("{}", "No duplicate files found.".green());
+        return Ok(());
+    }
+
+    let display = |file: &Path| {
+        file.strip_prefix(dir_path)
+            .unwrap_or(file)
+            .display()
+            .to_string()
+    };
+
+    for group in &groups {
+        println!(
+            "{} {}",
+            format!(
+                "{} copies of {}",
+                group.paths.len(),
+                format_size(group.size)
+            )
+            .yellow(),
+            format!("({} wasted)", format_size(group.wasted_size())).dimmed()
+        );
+        for (i, file) in group.paths.iter().enumerate() {
+            if i == 0 {
+                println!("  {}", display(file).white());
             } else {
-                list_simple(path, show_all)
+                println!("  {}", display(file).bright_black());
             }
         }
+        println!();
+    }
+
+    let extra: usize = groups.iter().map(|group| group.paths.len() - 1).sum();
+    let wasted: u64 = groups.iter().map(DuplicateGroup::wasted_size).sum();
+    println!(
+        "{} {} {}",
+        "Total:".white().bold(),
+        format!("{} group(s), {} extra file(s)", groups.len(), extra).yellow(),
+        format!("({} wasted)", format_size(wasted)).green()
+    );
+
+    if !delete_extra {
+        return Ok(());
+    }
+
+    println!();
+    let prompt = format!(
+        "Delete {} extra file(s), keeping the first of each group? [y/N]",
+        extra
+    );
+    if !confirm(&prompt)? {
+        println!("{}", "Operation cancelled.".yellow());
+        return Ok(());
     }
+
+    let mut deleted = 0;
+    let mut freed = 0;
+    for group in &groups {
+        for file in group.paths.iter().skip(1) {
+            match fs::remove_file(file) {
+                Ok(()) => {
+                    deleted += 1;
+                    freed += group.size;
+                }
+                Err(e) => println!(
+                    "{}",
+                    format!("Failed to delete {}: {}", display(file), e).red()
+                ),
+            }
+        }
+    }
+
+    println!(
+        "{} {}",
+        format!("{} Deleted {} file(s)", adapt("✓"), deleted).green(),
+        format!("({} freed)", format_size(freed)).dimmed()
+    );
+
+    Ok(())
+}
+
+/// Which entries `--exec` runs on
+struct ExecFilter<'a> {
+    show_all: bool,
+    only: Option<ContentClass>,
+    respect_gitignore: bool,
+    max_depth: usize,
+    glob: Option<&'a str>,
 }
 
-fn list_simple(path: &str, show_all: bool) -> Result<()> {
+fn exec_matching(path: &str, filter: &ExecFilter, command: &str, parallel: usize) -> Result<()> {
+    let dir_path = Path::new(path);
+
+    if !dir_path.is_dir() {
+        println!("{}", format!("Error: '{}' is not a directory", path).red());
+        return Ok(());
+    }
+
+    let template = ExecTemplate::parse(command).context("Invalid --exec command")?;
+
+    let scanner = FileScanner::new(dir_path)?;
+    let mut entries = scanner.scan_recursive_with_depth(
+        filter.show_all,
+        filter.respect_gitignore,
+        Some(filter.max_depth),
+    )?;
+    entries.retain(|entry| matches_only(&entry.path, entry.is_dir, filter.only));
+    let targets = match filter.glob {
+        Some(pattern) => filter_by_glob(dir_path, &entries, pattern)?,
+        None => entries
+            .iter()
+            .filter(|e| !e.is_dir)
+            .map(|e| e.path.clone())
+            .collect(),
+    };
+
+    if targets.is_empty() {
+        println!("{}", "No matching entries found".yellow().italic());
+        return Ok(());
+    }
+
+    println!(
+        "{} {} {}",
+        "Running".white(),
+        command.cyan().bold(),
+        format!("on {} entries", targets.len()).dimmed()
+    );
+    println!();
+
+    let summary = run_for_each(&template, &targets, parallel, &SystemRunner);
+
+    println!();
+    println!(
+        "{} {}",
+        "✓".green(),
+        format!("{} succeeded", summary.succeeded).green()
+    );
+    if !summary.failed.is_empty() {
+        println!(
+            "{} {}",
+            "✗".red(),
+            format!("{} failed", summary.failed.len()).red()
+        );
+        for (path, reason) in &summary.failed {
+            println!("  {} {}", path.display().to_string().dimmed(), reason.red());
+        }
+        return Err(CommandFailed::new(
+            1,
+            format!(
+                "--exec failed for {} of {} entries",
+                summary.failed.len(),
+                targets.len()
+            ),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Whether an entry passes `--only` (directories always do, to keep the tree)
+fn matches_only(path: &Path, is_dir: bool, only: Option<ContentClass>) -> bool {
+    match only {
+        Some(class) if !is_dir => classify_file(path).is_ok_and(|found| found == class),
+        _ => true,
+    }
+}
+
+fn list_simple(
+    path: &str,
+    show_all: bool,
+    only: Option<ContentClass>,
+    path_style: &PathStyle,
+) -> Result<()> {
     let dir_path = Path::new(path);
 
     if !dir_path.exists() {
@@ -89,11 +554,22 @@ fn list_simple(path: &str, show_all: bool) -> Result<()> {
         }
 
         let is_dir = entry.file_type()?.is_dir();
+        if !matches_only(&entry.path(), is_dir, only) {
+            continue;
+        }
         let is_hidden = file_name.starts_with('.') || is_hidden(&entry);
         let is_ignored = is_gitignored(&gitignore, &entry.path(), is_dir);
         let git_status = get_git_status_for_file(&git_status_map, &entry.path(), dir_path);
-
-        items.push((file_name, is_dir, is_hidden, is_ignored, git_status));
+        let display_name = path_style.render(&file_name, &entry.path());
+
+        items.push((
+            file_name,
+            display_name,
+            is_dir,
+            is_hidden,
+            is_ignored,
+            git_status,
+        ));
     }
 
     items.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
@@ -106,30 +582,205 @@ fn list_simple(path: &str, show_all: bool) -> Result<()> {
         .strip_prefix("\\\\?\\")
         .unwrap_or(&canonical_path);
 
-    println!("{} {}", "Directory:".white(), clean_path.cyan().bold());
-    println!();
+    let mut out = stdout_writer();
+    writeln!(out, "{} {}", "Directory:".white(), clean_path.cyan().bold())?;
+    writeln!(out)?;
 
     if items.is_empty() {
-        println!("{}", "Directory is empty".yellow().italic());
+        writeln!(out, "{}", "Directory is empty".yellow().italic())?;
     } else {
-        for (name, is_dir, is_hidden, is_ignored, git_status) in &items {
+        for (name, display_name, is_dir, is_hidden, is_ignored, git_status) in &items {
             let is_dimmed = *is_hidden || *is_ignored;
 
             if *is_dir {
-                let colored_name = apply_git_colors(name.clone(), git_status, true, is_dimmed);
-                println!("📂 {}", colored_name);
+                let colored_name =
+                    apply_git_colors(display_name.clone(), git_status, true, is_dimmed);
+                writeln!(out, "📂 {}", colored_name)?;
             } else {
                 let icon = get_file_icon(name);
-                let colored_name = apply_git_colors(name.clone(), git_status, false, is_dimmed);
-                println!("{} {}", icon, colored_name);
+                let colored_name =
+                    apply_git_colors(display_name.clone(), git_status, false, is_dimmed);
+                writeln!(out, "{} {}", icon, colored_name)?;
             }
         }
     }
 
+    out.flush()?;
     Ok(())
 }
 
-fn list_deep(path: &str, show_all: bool, max_depth: u32) -> Result<()> {
+/// Print entry paths separated by NUL bytes (for `xargs -0`)
+///
+/// No decoration is printed. With the default name style, paths are joined
+/// onto the listed directory so they stay usable from the current directory.
+fn list_print0(
+    path: &str,
+    show_all: bool,
+    only: Option<ContentClass>,
+    max_depth: u32,
+    path_style: &PathStyle,
+) -> Result<()> {
+    let dir_path = Path::new(path);
+
+    if !dir_path.is_dir() {
+        anyhow::bail!("'{}' is not a directory", path);
+    }
+
+    let mut entries = Vec::new();
+    collect_entry_paths(dir_path, show_all, only, 0, max_depth, &mut entries)?;
+
+    let mut out = stdout_writer();
+    for entry in &entries {
+        // Raw bytes, so names that are not valid UTF-8 reach `xargs -0` intact
+        let rendered = match path_style {
+            PathStyle::Name => entry.clone(),
+            style => style.render_path(entry),
+        };
+        out.write_all(rendered.as_os_str().as_encoded_bytes())?;
+        out.write_all(b"\0")?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// One entry of `msc list --json`
+#[derive(Debug, Serialize)]
+struct JsonEntry {
+    name: String,
+    /// Path rendered with the selected style (relative to the listed directory by default)
+    path: String,
+    /// 0 for direct children of the listed directory
+    depth: u32,
+    is_dir: bool,
+    is_symlink: bool,
+    size: u64,
+    permissions: String,
+    /// ISO-8601 modification time
+    modified: Option<String>,
+    /// `null` outside a Git repository
+    git_status: Option<&'static str>,
+}
+
+/// Print entries as a flat JSON array, without colors or headers
+fn list_json(
+    path: &str,
+    show_all: bool,
+    only: Option<ContentClass>,
+    max_depth: u32,
+    path_style: &PathStyle,
+) -> Result<()> {
+    let dir_path = Path::new(path);
+
+    if !dir_path.is_dir() {
+        anyhow::bail!("'{}' is not a directory", path);
+    }
+
+    let scanner = FileScanner::new(dir_path)?;
+    let mut entries =
+        scanner.scan_recursive_with_depth(show_all, false, Some(max_depth as usize + 1))?;
+    entries.retain(|entry| matches_only(&entry.path, entry.is_dir, only));
+
+    // Same order as the decorated listing: case-insensitive, children after their directory
+    let sort_key = |entry: &ScanEntry| -> Vec<String> {
+        entry
+            .path
+            .strip_prefix(dir_path)
+            .unwrap_or(&entry.path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+            .collect()
+    };
+    entries.sort_by_cached_key(sort_key);
+
+    let json_entries: Vec<JsonEntry> = entries
+        .iter()
+        .map(|entry| {
+            let relative = entry.path.strip_prefix(dir_path).unwrap_or(&entry.path);
+            let rendered = match path_style {
+                PathStyle::Name => relative.to_string_lossy().replace('\\', "/"),
+                style => style.render(&entry.name, &entry.path),
+            };
+            let git_status = scanner.git_status().as_ref().map(|_| {
+                get_git_status_for_file(scanner.git_status(), &entry.path, dir_path).label()
+            });
+
+            JsonEntry {
+                name: entry.name.clone(),
+                path: rendered,
+                depth: relative.components().count().saturating_sub(1) as u32,
+                is_dir: entry.is_dir,
+                is_symlink: entry.is_symlink,
+                size: entry.size,
+                permissions: format_permissions(&entry.metadata),
+                modified: entry.metadata.modified().ok().map(|time| {
+                    DateTime::<Local>::from(time).to_rfc3339_opts(SecondsFormat::Secs, false)
+                }),
+                git_status,
+            }
+        })
+        .collect();
+
+    let mut out = stdout_writer();
+    serde_json::to_writer_pretty(&mut out, &json_entries)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Collect entry paths in listing order (sorted, directories followed by their children)
+fn collect_entry_paths(
+    dir_path: &Path,
+    show_all: bool,
+    only: Option<ContentClass>,
+    current_depth: u32,
+    max_depth: u32,
+    entries: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let mut items = Vec::new();
+
+    for entry in fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        if !show_all && (file_name.starts_with('.') || is_hidden(&entry)) {
+            continue;
+        }
+
+        let is_dir = entry.file_type()?.is_dir();
+        if !matches_only(&entry.path(), is_dir, only) {
+            continue;
+        }
+        items.push((file_name, is_dir, entry.path()));
+    }
+
+    items.sort_by_key(|item| item.0.to_lowercase());
+
+    for (_, is_dir, full_path) in items {
+        entries.push(full_path.clone());
+        if is_dir && current_depth < max_depth {
+            // Unreadable subdirectories are skipped, like in the decorated listing
+            let _ = collect_entry_paths(
+                &full_path,
+                show_all,
+                only,
+                current_depth + 1,
+                max_depth,
+                entries,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn list_deep(
+    path: &str,
+    show_all: bool,
+    only: Option<ContentClass>,
+    max_depth: u32,
+    path_style: &PathStyle,
+) -> Result<()> {
     let dir_path = Path::new(path);
 
     if !dir_path.exists() {
@@ -153,24 +804,30 @@ fn list_deep(path: &str, show_all: bool, max_depth: u32) -> Result<()> {
         .strip_prefix("\\\\?\\")
         .unwrap_or(&canonical_path);
 
-    println!(
+    let mut out = stdout_writer();
+    writeln!(
+        out,
         "{} {} {}",
         "Directory:".white(),
         clean_path.cyan().bold(),
         format!("(depth: {})", max_depth).dimmed()
-    );
-    println!();
+    )?;
+    writeln!(out)?;
 
-    list_recursive(dir_path, show_all, 0, max_depth)?;
+    list_recursive(&mut out, dir_path, show_all, only, 0, max_depth, path_style)?;
 
+    out.flush()?;
     Ok(())
 }
 
 fn list_recursive(
+    out: &mut dyn Write,
     dir_path: &Path,
     show_all: bool,
+    only: Option<ContentClass>,
     current_depth: u32,
     max_depth: u32,
+    path_style: &PathStyle,
 ) -> Result<()> {
     if current_depth > max_depth {
         return Ok(());
@@ -190,6 +847,9 @@ fn list_recursive(
         }
 
         let is_dir = entry.file_type()?.is_dir();
+        if !matches_only(&entry.path(), is_dir, only) {
+            continue;
+        }
         let is_hidden = file_name.starts_with('.') || is_hidden(&entry);
         let is_ignored = is_gitignored(&gitignore, &entry.path(), is_dir);
 
@@ -202,30 +862,48 @@ fn list_recursive(
         let indent = "  ".repeat(current_depth as usize);
         let is_dimmed = *is_hidden || *is_ignored;
         let git_status = get_git_status_for_file(&git_status_map, full_path, dir_path);
+        let display_name = path_style.render(name, full_path);
 
         if *is_dir {
-            let colored_name = apply_git_colors(name.clone(), &git_status, true, is_dimmed);
-            println!("{}📂 {}", indent, colored_name);
+            let colored_name = apply_git_colors(display_name, &git_status, true, is_dimmed);
+            writeln!(out, "{}📂 {}", indent, colored_name)?;
             if current_depth < max_depth
-                && list_recursive(full_path, show_all, current_depth + 1, max_depth).is_err()
+                && list_recursive(
+                    out,
+                    full_path,
+                    show_all,
+                    only,
+                    current_depth + 1,
+                    max_depth,
+                    path_style,
+                )
+                .is_err()
             {
-                println!(
+                writeln!(
+                    out,
                     "{}  {}",
                     indent,
                     format!("Error reading directory: {}", name).red().dimmed()
-                );
+                )?;
             }
         } else {
             let icon = get_file_icon(name);
-            let colored_name = apply_git_colors(name.clone(), &git_status, false, is_dimmed);
-            println!("{}{} {}", indent, icon, colored_name);
+            let colored_name = apply_git_colors(display_name, &git_status, false, is_dimmed);
+            writeln!(out, "{}{} {}", indent, icon, colored_name)?;
         }
     }
 
     Ok(())
 }
 
-fn list_long(path: &str, show_all: bool, is_deep: bool, max_depth: u32) -> Result<()> {
+fn list_long(
+    path: &str,
+    show_all: bool,
+    only: Option<ContentClass>,
+    is_deep: bool,
+    max_depth: u32,
+    columns: &[LongColumn],
+) -> Result<()> {
     let dir_path = Path::new(path);
 
     if !dir_path.exists() {
@@ -264,171 +942,253 @@ fn list_long(path: &str, show_all: bool, is_deep: bool, max_depth: u32) -> Resul
             "(long format)".dimmed()
         );
     }
+    if let Some(branch) = load_branch_info(dir_path) {
+        println!("{} {}", "Branch:".white(), format_branch_info(&branch));
+    }
     println!();
 
-    // Header (Name column: 38 total = icon(2) + space(1) + name text(35))
-    println!(
-        "{:<38} │ {:<9} │ {:<18} │ {:<18} │ {:<12}",
-        "Name".white().bold(),
-        "Size".white().bold(),
-        "Created".white().bold(),
-        "Modified".white().bold(),
-        "Permissions".white().bold()
-    );
-    println!(
-        "{}─┼─{}─┼─{}─┼─{}─┼─{}",
-        "─".repeat(38),
-        "─".repeat(9),
-        "─".repeat(18),
-        "─".repeat(18),
-        "─".repeat(12)
-    );
-
-    if is_deep {
-        list_long_recursive(dir_path, show_all, 0, max_depth)?;
-    } else {
-        list_long_simple(dir_path, show_all, 0)?;
-    }
+    let depth = if is_deep { max_depth } else { 0 };
+    let mut table = long_table(columns);
+    add_long_rows(&mut table, columns, dir_path, show_all, only, 0, depth)?;
+    table.print();
 
     Ok(())
 }
 
-fn list_long_simple(dir_path: &Path, show_all: bool, indent_level: u32) -> Result<()> {
-    let gitignore = load_gitignore(dir_path);
-    let git_status_map = load_git_status(dir_path);
-    let entries = fs::read_dir(dir_path)?;
-    let mut items = Vec::new();
-
-    for entry in entries {
-        let entry = entry?;
-        let file_name = entry.file_name().to_string_lossy().to_string();
+/// `main (origin/main, 2 ahead, 1 behind)`, or `HEAD detached at 1a2b3c4`
+fn format_branch_info(branch: &BranchInfo) -> String {
+    if branch.detached {
+        return format!("HEAD detached at {}", branch.name.yellow());
+    }
 
-        if !show_all && (file_name.starts_with('.') || is_hidden(&entry)) {
-            continue;
+    let tracking = match &branch.upstream {
+        None => "no upstream".to_string(),
+        Some(upstream) => {
+            let mut counts = Vec::new();
+            if branch.ahead > 0 {
+                counts.push(format!("{} ahead", branch.ahead).green().to_string());
+            }
+            if branch.behind > 0 {
+                counts.push(format!("{} behind", branch.behind).red().to_string());
+            }
+            if counts.is_empty() {
+                counts.push("up to date".to_string());
+            }
+            format!("{}, {}", upstream, counts.join(", "))
         }
+    };
+    format!(
+        "{} {}",
+        branch.name.magenta().bold(),
+        format!("({})", tracking).dimmed()
+    )
+}
 
-        let is_dir = entry.file_type()?.is_dir();
-        let is_hidden = file_name.starts_with('.') || is_hidden(&entry);
-        let is_ignored = is_gitignored(&gitignore, &entry.path(), is_dir);
+/// Width cap of the Owner and Group columns in the long format
+const OWNERSHIP_COLUMN_WIDTH: usize = 10;
+
+/// Width of `format_time` output, which is never truncated
+const TIMESTAMP_WIDTH: usize = 16;
+
+/// Column of the long format, selectable with `--columns`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LongColumn {
+    Name,
+    Size,
+    Created,
+    Modified,
+    Perms,
+    Owner,
+    Group,
+    Git,
+}
 
-        items.push((file_name, is_dir, entry.path(), is_hidden, is_ignored));
+impl LongColumn {
+    const ALL: [LongColumn; 8] = [
+        LongColumn::Name,
+        LongColumn::Size,
+        LongColumn::Created,
+        LongColumn::Modified,
+        LongColumn::Perms,
+        LongColumn::Owner,
+        LongColumn::Group,
+        LongColumn::Git,
+    ];
+
+    /// Name used in `--columns`
+    fn key(self) -> &'static str {
+        match self {
+            LongColumn::Name => "name",
+            LongColumn::Size => "size",
+            LongColumn::Created => "created",
+            LongColumn::Modified => "modified",
+            LongColumn::Perms => "perms",
+            LongColumn::Owner => "owner",
+            LongColumn::Group => "group",
+            LongColumn::Git => "git",
+        }
     }
 
-    items.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
-
-    for (name, is_dir, full_path, is_hidden, is_ignored) in &items {
-        let indent = "  ".repeat(indent_level as usize);
-        let metadata = fs::metadata(full_path)?;
-        let is_dimmed = *is_hidden || *is_ignored;
-        let git_status = get_git_status_for_file(&git_status_map, full_path, dir_path);
+    fn header(self) -> &'static str {
+        match self {
+            LongColumn::Name => "Name",
+            LongColumn::Size => "Size",
+            LongColumn::Created => "Created",
+            LongColumn::Modified => "Modified",
+            LongColumn::Perms => "Permissions",
+            LongColumn::Owner => "Owner",
+            LongColumn::Group => "Group",
+            LongColumn::Git => "Git",
+        }
+    }
 
-        // Ensure we never keep stray spaces in the icon
-        let icon = if *is_dir {
-            "📂"
-        } else {
-            get_file_icon(name).trim_end()
-        };
-        let size = if *is_dir {
-            "-".to_string()
-        } else {
-            format_size(metadata.len())
-        };
-        let created = format_time(metadata.created().unwrap_or(SystemTime::UNIX_EPOCH));
-        let modified = format_time(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
-        let permissions = format_permissions(&metadata);
-
-        // Name column: reserve exactly 35 chars for the name text (excluding icon and colors).
-        // Truncate to 32 + "..." when it exceeds the limit. Then pad so that
-        // indent + icon(2) + space(1) + name_text(<=35, padded) = 38 + indent_width total before the separator.
-        const NAME_TEXT_WIDTH: usize = 35;
-        let icon_width = icon.width();
-        // Determine available width for the name text (no colors), excluding the indent.
-        let indent_width = indent.width();
-        let name_available = NAME_TEXT_WIDTH.saturating_sub(indent_width);
-
-        // Truncate name respecting unicode display width
-        let truncated_name = if name.width() > name_available {
-            let mut out = String::new();
-            let mut w = 0usize;
-            for ch in name.chars() {
-                let cw = ch.width().unwrap_or(0);
-                if w + cw > name_available.saturating_sub(3) {
-                    // keep room for "..."
-                    break;
-                }
-                out.push(ch);
-                w += cw;
-            }
-            out.push_str("...");
-            out
-        } else {
-            name.clone()
-        };
+    /// Columns shown without `--columns` (Owner and Group on Unix only)
+    fn defaults() -> Vec<LongColumn> {
+        let mut columns = vec![
+            LongColumn::Name,
+            LongColumn::Size,
+            LongColumn::Created,
+            LongColumn::Modified,
+            LongColumn::Perms,
+        ];
+        if cfg!(unix) {
+            columns.extend([LongColumn::Owner, LongColumn::Group]);
+        }
+        columns
+    }
+}
 
-        // Pad the name text area to the available width
-        let name_text_width = truncated_name.width();
-        let pad_spaces = name_available.saturating_sub(name_text_width);
-        let padded_name_text = format!("{}{}", truncated_name, " ".repeat(pad_spaces));
+/// Parse a `--columns` list such as `name,size,modified`
+fn parse_long_columns(spec: &str) -> Result<Vec<LongColumn>> {
+    let columns = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            LongColumn::ALL
+                .into_iter()
+                .find(|column| column.key().eq_ignore_ascii_case(name))
+                .with_context(|| {
+                    let known: Vec<&str> = LongColumn::ALL.iter().map(|c| c.key()).collect();
+                    format!(
+                        "Unknown column '{}' (available: {})",
+                        name,
+                        known.join(", ")
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if columns.is_empty() {
+        anyhow::bail!("--columns needs at least one column name");
+    }
+    Ok(columns)
+}
 
-        // Build full cell: indent + icon + spacer(s) + padded name text
-        // Add one normal space plus a compensation space if icon renders as width 1
-        let extra_icon_pad = 2usize.saturating_sub(icon_width);
-        let spacer = format!("{}{}", " ", " ".repeat(extra_icon_pad));
-        let padded_name = format!("{}{}{}{}", indent, icon, spacer, padded_name_text);
+/// Everything the long format can show about one entry
+struct LongEntry {
+    /// Indent, icon and file name
+    name: String,
+    is_dir: bool,
+    is_dimmed: bool,
+    git_status: GitStatus,
+    size: String,
+    created: String,
+    modified: String,
+    permissions: String,
+    owner: String,
+    group: String,
+}
 
-        let colored_name = apply_git_colors(padded_name, &git_status, *is_dir, is_dimmed);
+fn long_table(columns: &[LongColumn]) -> Table {
+    let headers: Vec<&str> = columns.iter().map(|column| column.header()).collect();
+    let mut table = Table::new(&headers);
+
+    for (i, column) in columns.iter().enumerate() {
+        table = match column {
+            LongColumn::Name => table.min_width(i, 20),
+            LongColumn::Size => table.min_width(i, 8).color(i, Color::Yellow),
+            LongColumn::Created => table.min_width(i, TIMESTAMP_WIDTH).color(i, Color::Cyan),
+            LongColumn::Modified => table.min_width(i, TIMESTAMP_WIDTH).color(i, Color::Green),
+            LongColumn::Perms => table,
+            LongColumn::Owner => table
+                .color(i, Color::BrightBlue)
+                .max_column_width(i, OWNERSHIP_COLUMN_WIDTH),
+            LongColumn::Group => table
+                .color(i, Color::Blue)
+                .max_column_width(i, OWNERSHIP_COLUMN_WIDTH),
+            LongColumn::Git => table,
+        };
+    }
 
-        // Pad other columns to fixed widths
-        let size_padded = format!("{:<9}", size);
-        let created_padded = format!("{:<18}", created);
-        let modified_padded = format!("{:<18}", modified);
-        let permissions_padded = format!("{:<12}", permissions);
+    table.fit_terminal()
+}
 
-        let size_color = if is_dimmed {
-            size_padded.bright_black()
-        } else {
-            size_padded.yellow()
-        };
-        let created_color = if is_dimmed {
-            created_padded.bright_black()
-        } else {
-            created_padded.cyan()
-        };
-        let modified_color = if is_dimmed {
-            modified_padded.bright_black()
+/// Cells of `entry` for the selected columns, in order
+fn long_row(columns: &[LongColumn], entry: &LongEntry) -> Vec<Cell> {
+    let is_dimmed = entry.is_dimmed;
+    let plain = |text: &str| {
+        if is_dimmed {
+            Cell::new(text).color(Color::BrightBlack)
         } else {
-            modified_padded.green()
-        };
-        let permissions_color = if is_dimmed {
-            permissions_padded.bright_black()
-        } else {
-            permissions_padded.magenta()
-        };
+            Cell::new(text)
+        }
+    };
+
+    columns
+        .iter()
+        .map(|column| match column {
+            LongColumn::Name => {
+                let (git_status, is_dir) = (entry.git_status.clone(), entry.is_dir);
+                Cell::new(entry.name.as_str()).styled(move |text| {
+                    apply_git_colors(text.to_string(), &git_status, is_dir, is_dimmed).to_string()
+                })
+            }
+            LongColumn::Size => plain(&entry.size),
+            LongColumn::Created => plain(&entry.created),
+            LongColumn::Modified => plain(&entry.modified),
+            LongColumn::Perms if is_dimmed => plain(&entry.permissions),
+            LongColumn::Perms => Cell::new(entry.permissions.as_str()).styled(colorize_permissions),
+            LongColumn::Owner => plain(&entry.owner),
+            LongColumn::Group => plain(&entry.group),
+            LongColumn::Git => match entry.git_status {
+                GitStatus::Clean => Cell::new(""),
+                GitStatus::Deleted => Cell::new(entry.git_status.label()).color(Color::Red),
+                _ => Cell::new(entry.git_status.label()).color(Color::BrightGreen),
+            },
+        })
+        .collect()
+}
 
-        println!(
-            "{} │ {} │ {} │ {} │ {}",
-            colored_name, size_color, created_color, modified_color, permissions_color
-        );
-    }
+/// Owner and group names for the long format (`-` where not supported)
+#[cfg(unix)]
+fn ownership(metadata: &fs::Metadata) -> (String, String) {
+    use crate::platform::{group_name, owner_name};
+    use std::os::unix::fs::MetadataExt;
 
-    Ok(())
+    (owner_name(metadata.uid()), group_name(metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn ownership(_metadata: &fs::Metadata) -> (String, String) {
+    ("-".to_string(), "-".to_string())
 }
 
-fn list_long_recursive(
+/// Add the entries of `dir_path` to the long format table, descending into
+/// subdirectories while `current_depth < max_depth`
+fn add_long_rows(
+    table: &mut Table,
+    columns: &[LongColumn],
     dir_path: &Path,
     show_all: bool,
+    only: Option<ContentClass>,
     current_depth: u32,
     max_depth: u32,
 ) -> Result<()> {
-    if current_depth > max_depth {
-        return Ok(());
-    }
-
     let gitignore = load_gitignore(dir_path);
     let git_status_map = load_git_status(dir_path);
     let entries = fs::read_dir(dir_path)?;
     let mut items = Vec::new();
-    const NAME_TEXT_WIDTH: usize = 35;
 
     for entry in entries {
         let entry = entry?;
@@ -439,6 +1199,9 @@ fn list_long_recursive(
         }
 
         let is_dir = entry.file_type()?.is_dir();
+        if !matches_only(&entry.path(), is_dir, only) {
+            continue;
+        }
         let is_hidden = file_name.starts_with('.') || is_hidden(&entry);
         let is_ignored = is_gitignored(&gitignore, &entry.path(), is_dir);
 
@@ -448,102 +1211,140 @@ fn list_long_recursive(
     items.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
 
     for (name, is_dir, full_path, is_hidden, is_ignored) in &items {
+        let is_dir = *is_dir;
         let indent = "  ".repeat(current_depth as usize);
         let metadata = fs::metadata(full_path)?;
-        let is_dimmed = *is_hidden || *is_ignored;
-        let git_status = get_git_status_for_file(&git_status_map, full_path, dir_path);
 
-        // Normalize icon and compute other columns
-        let icon = if *is_dir {
+        // Ensure we never keep stray spaces in the icon
+        let icon = if is_dir {
             "📂"
         } else {
             get_file_icon(name).trim_end()
         };
-        let size = if *is_dir {
-            "-".to_string()
-        } else {
-            format_size(metadata.len())
-        };
-        let created = format_time(metadata.created().unwrap_or(SystemTime::UNIX_EPOCH));
-        let modified = format_time(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
-        let permissions = format_permissions(&metadata);
-
-        // First column width logic identical to simple long listing
-
-        let indent_width = indent.width();
-        let name_available = NAME_TEXT_WIDTH.saturating_sub(indent_width);
-
-        let truncated_name = if name.width() > name_available {
-            let mut out = String::new();
-            let mut w = 0usize;
-            for ch in name.chars() {
-                let cw = ch.width().unwrap_or(0);
-                if w + cw > name_available.saturating_sub(3) {
-                    break;
-                }
-                out.push(ch);
-                w += cw;
-            }
-            out.push_str("...");
-            out
-        } else {
-            name.clone()
+        // One space after the icon, plus one more when it renders a single column wide
+        let spacer = " ".repeat(1 + 2usize.saturating_sub(icon.width()));
+        let (owner, group) = ownership(&metadata);
+
+        let entry = LongEntry {
+            name: format!("{}{}{}{}", indent, icon, spacer, name),
+            is_dir,
+            is_dimmed: *is_hidden || *is_ignored,
+            git_status: get_git_status_for_file(&git_status_map, full_path, dir_path),
+            size: if is_dir {
+                "-".to_string()
+            } else {
+                format_size(metadata.len())
+            },
+            created: format_time(metadata.created().unwrap_or(SystemTime::UNIX_EPOCH)),
+            modified: format_time(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH)),
+            permissions: format_permissions(&metadata),
+            owner,
+            group,
         };
+        table.add_row(long_row(columns, &entry));
 
-        let name_text_width = truncated_name.width();
-        let pad_spaces = name_available.saturating_sub(name_text_width);
-        let padded_name_text = format!("{}{}", truncated_name, " ".repeat(pad_spaces));
-        // Add one normal space plus compensation so (icon width + extra) = 2
-        let icon_width = icon.width();
-        let extra_icon_pad = 2usize.saturating_sub(icon_width);
-        let spacer = format!("{}{}", " ", " ".repeat(extra_icon_pad));
-        let padded_name = format!("{}{}{}{}", indent, icon, spacer, padded_name_text);
+        if is_dir
+            && current_depth < max_depth
+            && add_long_rows(
+                table,
+                columns,
+                full_path,
+                show_all,
+                only,
+                current_depth + 1,
+                max_depth,
+            )
+            .is_err()
+        {
+            let indent_error = "  ".repeat((current_depth + 1) as usize);
+            table.add_row([Cell::new(format!(
+                "{}  Error reading directory: {}",
+                indent_error, name
+            ))
+            .styled(|text| text.red().dimmed().to_string())]);
+        }
+    }
 
-        let colored_name = apply_git_colors(padded_name, &git_status, *is_dir, is_dimmed);
+    Ok(())
+}
 
-        let size_padded = format!("{:<9}", size);
-        let created_padded = format!("{:<18}", created);
-        let modified_padded = format!("{:<18}", modified);
-        let permissions_padded = format!("{:<12}", permissions);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_entry() -> LongEntry {
+        LongEntry {
+            name: "notes.txt".to_string(),
+            is_dir: false,
+            is_dimmed: false,
+            git_status: GitStatus::Modified,
+            size: "1.2KB".to_string(),
+            created: "2024-05-01 09:00".to_string(),
+            modified: "2024-05-01 12:30".to_string(),
+            permissions: "rw-r--r--".to_string(),
+            owner: "marco".to_string(),
+            group: "staff".to_string(),
+        }
+    }
 
-        let size_color = if is_dimmed {
-            size_padded.bright_black()
-        } else {
-            size_padded.yellow()
-        };
-        let created_color = if is_dimmed {
-            created_padded.bright_black()
-        } else {
-            created_padded.cyan()
-        };
-        let modified_color = if is_dimmed {
-            modified_padded.bright_black()
-        } else {
-            modified_padded.green()
-        };
-        let permissions_color = if is_dimmed {
-            permissions_padded.bright_black()
-        } else {
-            permissions_padded.magenta()
+    /// Header and first row of a rendered long table, split into fields
+    fn rendered_fields(columns: &[LongColumn]) -> (Vec<String>, Vec<String>) {
+        let mut table = long_table(columns);
+        table.add_row(long_row(columns, &sample_entry()));
+
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let rendered = ansi.replace_all(&table.render(), "").to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+        let split = |line: &str| -> Vec<String> {
+            line.split(" │ ").map(|f| f.trim().to_string()).collect()
         };
+        (split(lines[0]), split(lines[2]))
+    }
 
-        println!(
-            "{} │ {} │ {} │ {} │ {}",
-            colored_name, size_color, created_color, modified_color, permissions_color
+    #[test]
+    fn test_long_columns_selection_keeps_order() {
+        let columns = parse_long_columns("modified, name,GIT,size").unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                LongColumn::Modified,
+                LongColumn::Name,
+                LongColumn::Git,
+                LongColumn::Size
+            ]
         );
 
-        if *is_dir
-            && current_depth < max_depth
-            && list_long_recursive(full_path, show_all, current_depth + 1, max_depth).is_err()
-        {
-            let indent_error = "  ".repeat((current_depth + 1) as usize);
-            println!(
-                "{}  {}",
-                indent_error,
-                format!("Error reading directory: {}", name).red().dimmed()
-            );
-        }
+        let (headers, row) = rendered_fields(&columns);
+        assert_eq!(headers, ["Modified", "Name", "Git", "Size"]);
+        assert_eq!(row, ["2024-05-01 12:30", "notes.txt", "modified", "1.2KB"]);
     }
 
-    Ok(())
+    #[test]
+    fn test_long_columns_single_column() {
+        let (headers, row) = rendered_fields(&[LongColumn::Perms]);
+        assert_eq!(headers, ["Permissions"]);
+        assert_eq!(row, ["rw-r--r--"]);
+    }
+
+    #[test]
+    fn test_long_columns_rejects_unknown_names() {
+        let err = parse_long_columns("name,colour").unwrap_err();
+        assert!(err.to_string().contains("Unknown column 'colour'"));
+        assert!(parse_long_columns(" , ").is_err());
+    }
+
+    #[test]
+    fn test_only_filters_files_but_keeps_directories() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("logo.png"), b"\x89PNG\r\n").unwrap();
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::create_dir(root.join("assets")).unwrap();
+
+        let text = Some(ContentClass::Text);
+        assert!(matches_only(&root.join("main.rs"), false, text));
+        assert!(!matches_only(&root.join("logo.png"), false, text));
+        assert!(matches_only(&root.join("assets"), true, text));
+        assert!(matches_only(&root.join("logo.png"), false, None));
+    }
 }
@@ -1,10 +1,17 @@
 // Command handlers module
 pub mod alias;
+pub mod checksum;
 pub mod clean;
 pub mod completions;
 pub mod config;
+pub mod copy;
+pub mod du;
+pub mod grep;
 pub mod hello;
 pub mod list;
+pub mod rename;
+pub mod rm;
+pub mod shell;
 pub mod sys;
 pub mod update;
 pub mod vedit;
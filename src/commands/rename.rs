@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use crate::core::batch_exec::filter_by_glob;
+use crate::core::batch_rename::{apply_renames, display_name, plan_renames};
+use crate::core::{FileScanner, ScanEntry};
+use crate::error::CommandFailed;
+use crate::ui::glyphs::adapt;
+
+pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
+    let pattern = matches
+        .get_one::<String>("pattern")
+        .context("Pattern argument is required")?;
+    let replacement = matches
+        .get_one::<String>("replacement")
+        .context("Replacement argument is required")?;
+    let dir = matches
+        .get_one::<String>("path")
+        .map(|s| s.as_str())
+        .unwrap_or(".");
+    let apply = matches.get_flag("apply");
+
+    let regex = Regex::new(pattern).with_context(|| format!("Invalid pattern '{}'", pattern))?;
+
+    let dir_path = Path::new(dir);
+    if !dir_path.is_dir() {
+        println!("{}", format!("Error: '{}' is not a directory", dir).red());
+        return Ok(());
+    }
+
+    let scanner = FileScanner::new(dir_path)?;
+    let show_hidden = matches.get_flag("all");
+    let entries = if matches.get_flag("recursive") {
+        scanner.scan_recursive(show_hidden, false)?
+    } else {
+        scanner.scan(show_hidden)?
+    };
+    let entries: Vec<ScanEntry> = entries
+        .into_iter()
+        .filter(|entry| !entry.is_dir && !entry.is_symlink)
+        .collect();
+
+    let mut files: Vec<PathBuf> = match matches.get_one::<String>("glob") {
+        Some(glob) => filter_by_glob(dir_path, &entries, glob)?,
+        None => entries.into_iter().map(|entry| entry.path).collect(),
+    };
+    files.sort();
+
+    let plan = plan_renames(&files, &regex, replacement);
+
+    for (path, reason) in &plan.skipped {
+        println!(
+            "{} {}: {}",
+            "Skipping".yellow(),
+            relative(dir_path, path),
+            reason
+        );
+    }
+
+    if plan.renames.is_empty() {
+        println!("{}", "Nothing to rename.".yellow());
+        return Ok(());
+    }
+
+    for rename in &plan.renames {
+        println!(
+            "  {} → {}",
+            relative(dir_path, &rename.from),
+            display_name(&rename.to).green()
+        );
+    }
+
+    if !apply {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "{} file(s) would be renamed. Run again with --apply to rename them.",
+                plan.renames.len()
+            )
+            .dimmed()
+        );
+        return Ok(());
+    }
+
+    let failed = apply_renames(&plan.renames);
+    for (path, error) in &failed {
+        println!("{} {}: {}", "Failed".red(), relative(dir_path, path), error);
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "{} Renamed {} file(s)",
+            adapt("✓"),
+            plan.renames.len() - failed.len()
+        )
+        .green()
+    );
+
+    if !failed.is_empty() {
+        return Err(CommandFailed::new(1, format!("{} rename(s) failed", failed.len())).into());
+    }
+    Ok(())
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
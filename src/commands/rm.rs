@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::core::remove::{protected_paths, remove_paths, RemoveMode, SystemRemover};
+use crate::core::PathValidator;
+use crate::error::CommandFailed;
+use crate::ui::confirm;
+use crate::ui::glyphs::adapt;
+
+pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
+    let paths: Vec<PathBuf> = matches
+        .get_many::<String>("paths")
+        .context("At least one path is required")?
+        .map(PathBuf::from)
+        .collect();
+    let mode = if matches.get_flag("permanent") {
+        RemoveMode::Permanent
+    } else {
+        RemoveMode::Trash
+    };
+
+    let validator = PathValidator::new();
+    let refused = protected_paths(&paths, &validator);
+    for (path, reason) in &refused {
+        println!(
+            "{} {} {}",
+            format!("{} Refusing to remove", adapt("✗")).red().bold(),
+            path.display(),
+            format!("({})", reason).dimmed()
+        );
+    }
+
+    let allowed: Vec<PathBuf> = paths
+        .into_iter()
+        .filter(|path| !refused.iter().any(|(refused, _)| refused == path))
+        .collect();
+    if allowed.is_empty() {
+        return Err(
+            CommandFailed::new(1, format!("refused to remove {} path(s)", refused.len())).into(),
+        );
+    }
+
+    if mode == RemoveMode::Permanent {
+        println!(
+            "{}",
+            format!(
+                "{} {} path(s) will be permanently deleted:",
+                adapt("⚠️ "),
+                allowed.len()
+            )
+            .yellow()
+        );
+        for path in &allowed {
+            println!("  {}", path.display());
+        }
+        if !confirm("This cannot be undone. Continue? [y/N]")? {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let summary = remove_paths(&allowed, mode, &validator, &SystemRemover);
+
+    let verb = match mode {
+        RemoveMode::Trash => "Moved to trash:",
+        RemoveMode::Permanent => "Deleted:",
+    };
+    for path in &summary.removed {
+        println!(
+            "{} {}",
+            format!("{} {}", adapt("✓"), verb).green(),
+            path.display()
+        );
+    }
+    for (path, reason) in summary.refused.iter().chain(&summary.failed) {
+        println!(
+            "{} {} {}",
+            format!("{} Could not remove", adapt("✗")).red().bold(),
+            path.display(),
+            format!("({})", reason).dimmed()
+        );
+    }
+
+    if !refused.is_empty() || !summary.is_success() {
+        return Err(CommandFailed::new(
+            1,
+            format!(
+                "{} path(s) refused, {} failed",
+                refused.len() + summary.refused.len(),
+                summary.failed.len()
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,360 @@
+// Interactive shell: run msc subcommands repeatedly from one prompt
+//
+// Each line is split into arguments, parsed with the same clap `Command` as
+// the real command line and handed to a `Dispatcher`, so the shell runs the
+// exact handlers `msc <args>` would. The binary provides the dispatcher and
+// the parser; tests use scripted input and a recording dispatcher.
+
+use anyhow::{anyhow, Result};
+use clap::{ArgMatches, Command};
+use colored::Colorize;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::path::PathBuf;
+
+const PROMPT: &str = "msc> ";
+
+/// Runs the command handler for one parsed line
+pub trait Dispatcher {
+    fn dispatch(&mut self, matches: &ArgMatches) -> Result<()>;
+}
+
+/// What the user typed at the prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellInput {
+    Line(String),
+    /// Ctrl+C: discard the current line
+    Interrupted,
+    /// Ctrl+D: leave the shell
+    Eof,
+}
+
+/// Source of input lines, with optional history
+pub trait LineReader {
+    fn read_line(&mut self, prompt: &str) -> Result<ShellInput>;
+
+    fn add_history(&mut self, _line: &str) {}
+}
+
+/// Terminal line editor with history persisted to `<config_dir>/msc/shell_history`
+struct EditorReader {
+    editor: DefaultEditor,
+    history_path: Option<PathBuf>,
+}
+
+impl EditorReader {
+    fn new() -> Result<Self> {
+        let mut editor = DefaultEditor::new()?;
+        let history_path = dirs::config_dir().map(|dir| dir.join("msc").join("shell_history"));
+        if let Some(path) = &history_path {
+            // No history yet on the first run
+            let _ = editor.load_history(path);
+        }
+        Ok(Self {
+            editor,
+            history_path,
+        })
+    }
+
+    fn save_history(&mut self) {
+        if let Some(path) = &self.history_path {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Err(e) = self.editor.save_history(path) {
+                log::warn!("Failed to save shell history: {}", e);
+            }
+        }
+    }
+}
+
+impl LineReader for EditorReader {
+    fn read_line(&mut self, prompt: &str) -> Result<ShellInput> {
+        match self.editor.readline(prompt) {
+            Ok(line) => Ok(ShellInput::Line(line)),
+            Err(ReadlineError::Interrupted) => Ok(ShellInput::Interrupted),
+            Err(ReadlineError::Eof) => Ok(ShellInput::Eof),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn add_history(&mut self, line: &str) {
+        let _ = self.editor.add_history_entry(line);
+    }
+}
+
+/// Start the interactive shell
+///
+/// `cli` builds the parser used for every line (the same one as `msc` itself).
+pub fn execute(cli: fn() -> Command, dispatcher: &mut dyn Dispatcher) -> Result<()> {
+    println!("{}", "MSC interactive shell".cyan().bold());
+    println!(
+        "{}",
+        "Type a command without 'msc' (e.g. 'list -a'), 'help' for commands, 'exit' or Ctrl+D to quit."
+            .dimmed()
+    );
+
+    let mut reader = EditorReader::new()?;
+    let result = run(cli, &mut reader, dispatcher);
+    reader.save_history();
+    result
+}
+
+/// Read, parse and dispatch lines until `exit`, `quit` or end of input
+pub fn run(
+    cli: fn() -> Command,
+    reader: &mut dyn LineReader,
+    dispatcher: &mut dyn Dispatcher,
+) -> Result<()> {
+    loop {
+        let line = match reader.read_line(PROMPT)? {
+            ShellInput::Line(line) => line,
+            ShellInput::Interrupted => continue,
+            ShellInput::Eof => break,
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        reader.add_history(line);
+
+        if matches!(line, "exit" | "quit") {
+            break;
+        }
+
+        let args = match split_line(line) {
+            Ok(args) => args,
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red(), e);
+                continue;
+            }
+        };
+
+        if args.first().is_some_and(|arg| arg == "shell") {
+            println!("{}", "Already in the msc shell.".yellow());
+            continue;
+        }
+
+        // Parse errors, --help and --version are printed by clap without leaving the shell
+        let matches =
+            match cli().try_get_matches_from(std::iter::once("msc".to_string()).chain(args)) {
+                Ok(matches) => matches,
+                Err(e) => {
+                    let _ = e.print();
+                    continue;
+                }
+            };
+
+        if let Err(e) = dispatcher.dispatch(&matches) {
+            eprintln!("{} {:#}", "Error:".red(), e);
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Split a line into arguments, honoring single and double quotes and `\` escapes
+pub fn split_line(line: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                // Windows paths keep their backslashes unless escaping a quote or space
+                match chars.clone().next() {
+                    Some(next @ ('"' | '\'' | ' ' | '\\')) => {
+                        current.push(next);
+                        chars.next();
+                    }
+                    _ => current.push('\\'),
+                }
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(anyhow!("Unclosed {} quote", q));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+    use std::collections::VecDeque;
+    use std::sync::atomic::Ordering;
+
+    struct ScriptedReader {
+        inputs: VecDeque<ShellInput>,
+        history: Vec<String>,
+    }
+
+    impl LineReader for ScriptedReader {
+        fn read_line(&mut self, _prompt: &str) -> Result<ShellInput> {
+            Ok(self.inputs.pop_front().unwrap_or(ShellInput::Eof))
+        }
+
+        fn add_history(&mut self, line: &str) {
+            self.history.push(line.to_string());
+        }
+    }
+
+    /// Records `<subcommand> <name arg>` for every dispatched line
+    #[derive(Default)]
+    struct RecordingDispatcher {
+        calls: Vec<String>,
+    }
+
+    impl Dispatcher for RecordingDispatcher {
+        fn dispatch(&mut self, matches: &ArgMatches) -> Result<()> {
+            let (name, sub) = matches.subcommand().expect("a subcommand");
+            let value = sub
+                .try_get_one::<String>("name")
+                .ok()
+                .flatten()
+                .cloned()
+                .unwrap_or_default();
+            self.calls
+                .push(format!("{} {}", name, value).trim().to_string());
+            if name == "fail" {
+                return Err(anyhow!("handler failed"));
+            }
+            Ok(())
+        }
+    }
+
+    fn test_cli() -> Command {
+        Command::new("msc")
+            .subcommand(Command::new("hello").arg(Arg::new("name").short('n').long("name")))
+            .subcommand(Command::new("list").arg(Arg::new("name").index(1)))
+            .subcommand(Command::new("fail"))
+            .subcommand(Command::new("shell"))
+    }
+
+    #[test]
+    fn test_scripted_lines_dispatch_to_handlers() {
+        let lines = [
+            "hello -n \"Ada Lovelace\"",
+            "",
+            "unknown-command",
+            "list ./src",
+            "fail",
+            "shell",
+            "list 'C:\\My Videos'",
+            "exit",
+            "hello -n never",
+        ];
+        let mut reader = ScriptedReader {
+            inputs: lines
+                .iter()
+                .map(|line| ShellInput::Line(line.to_string()))
+                .collect(),
+            history: Vec::new(),
+        };
+        reader.inputs.insert(2, ShellInput::Interrupted);
+        let mut dispatcher = RecordingDispatcher::default();
+
+        run(test_cli, &mut reader, &mut dispatcher).unwrap();
+
+        // Unknown commands and nested shells are not dispatched; a failing
+        // handler doesn't end the session; nothing runs after `exit`
+        assert_eq!(
+            dispatcher.calls,
+            [
+                "hello Ada Lovelace",
+                "list ./src",
+                "fail",
+                "list C:\\My Videos"
+            ]
+        );
+        assert_eq!(reader.history.len(), 7);
+        assert!(!reader.inputs.is_empty());
+    }
+
+    #[test]
+    fn test_eof_ends_the_session() {
+        let mut reader = ScriptedReader {
+            inputs: VecDeque::from([ShellInput::Line("list a".to_string()), ShellInput::Eof]),
+            history: Vec::new(),
+        };
+        let mut dispatcher = RecordingDispatcher::default();
+
+        run(test_cli, &mut reader, &mut dispatcher).unwrap();
+        assert_eq!(dispatcher.calls, ["list a"]);
+    }
+
+    /// Takes the Ctrl+C cancel flag like `msc cp` or `msc clean` do, then
+    /// simulates the user interrupting the command
+    #[derive(Default)]
+    struct InterruptibleDispatcher {
+        started_uncancelled: Vec<bool>,
+    }
+
+    impl Dispatcher for InterruptibleDispatcher {
+        fn dispatch(&mut self, _matches: &ArgMatches) -> Result<()> {
+            let cancel_flag = crate::ui::interrupt::cancel_flag()?;
+            self.started_uncancelled
+                .push(!cancel_flag.load(Ordering::Relaxed));
+            cancel_flag.store(true, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_same_handler_runs_twice() {
+        let mut reader = ScriptedReader {
+            inputs: VecDeque::from([
+                ShellInput::Line("list a".to_string()),
+                ShellInput::Line("list a".to_string()),
+                ShellInput::Eof,
+            ]),
+            history: Vec::new(),
+        };
+        let mut dispatcher = InterruptibleDispatcher::default();
+
+        run(test_cli, &mut reader, &mut dispatcher).unwrap();
+
+        // The Ctrl+C handler is installed once and the flag reset per command
+        assert_eq!(dispatcher.started_uncancelled, [true, true]);
+    }
+
+    #[test]
+    fn test_split_line_quotes_and_escapes() {
+        assert_eq!(
+            split_line(r#"wget "https://a.com/?q=1 2" --all"#).unwrap(),
+            ["wget", "https://a.com/?q=1 2", "--all"]
+        );
+        assert_eq!(
+            split_line(r"list C:\Users\me  it\'s ''").unwrap(),
+            ["list", r"C:\Users\me", "it's", ""]
+        );
+        assert!(split_line("hello -n \"unterminated").is_err());
+    }
+}
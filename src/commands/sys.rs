@@ -1,14 +1,26 @@
-use crate::core::system_info::collector;
+use crate::core::http::HttpConfig;
+use crate::core::system_info::redact::redact_system_info;
+use crate::core::system_info::{collector, public_ip};
+use crate::ui::output::stdout_writer;
 use crate::ui::system_formatters::{self, DisplayFilter};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::ArgMatches;
+use std::io::{IsTerminal, Write};
 
+pub mod disks;
+pub mod export;
 pub mod monitor;
+pub mod processes;
+pub mod top;
 
 pub fn execute(matches: &ArgMatches) -> Result<()> {
     match matches.subcommand() {
         Some(("monitor", sub_matches)) => monitor::execute(sub_matches),
         Some(("info", sub_matches)) => execute_info(sub_matches),
+        Some(("export", sub_matches)) => export::execute(sub_matches),
+        Some(("top", sub_matches)) => top::execute(sub_matches),
+        Some(("processes", sub_matches)) => processes::execute(sub_matches),
+        Some(("disks", sub_matches)) => disks::execute(sub_matches),
         _ => {
             println!("Use 'msc sys --help' for more information.");
             Ok(())
@@ -17,9 +29,35 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
 }
 
 fn execute_info(matches: &ArgMatches) -> Result<()> {
-    println!("Collecting system information...\n");
+    let format = matches
+        .get_one::<String>("format")
+        .map(|s| s.as_str())
+        .unwrap_or("text");
 
-    let system_info = collector::collect_system_info()?;
+    // Keep stdout clean for structured formats
+    if format == "text" {
+        println!("Collecting system information...\n");
+    }
+
+    let mut system_info = collector::collect_system_info()?;
+
+    // Opt-in: contacts an external service
+    if matches.get_flag("public-ip") {
+        if format == "text" {
+            println!("Looking up public IP (requires network access)...\n");
+        }
+        match public_ip::lookup_cached(&HttpConfig::from_env()) {
+            Ok(info) => system_info.network.public_ip = Some(info),
+            Err(e) => eprintln!("Warning: public IP lookup failed: {:#}", e),
+        }
+    }
+
+    // Output redirected to a file or another program is likely to be shared
+    let redact = matches.get_flag("redact")
+        || (!matches.get_flag("no-redact") && !std::io::stdout().is_terminal());
+    if redact {
+        redact_system_info(&mut system_info);
+    }
 
     // Parse filter flags
     let show_cpu = matches.get_flag("cpu");
@@ -54,7 +92,21 @@ fn execute_info(matches: &ArgMatches) -> Result<()> {
         }
     };
 
-    system_formatters::format_system_info(&system_info, &filter);
+    match format {
+        "json" | "yaml" => {
+            let value = system_formatters::system_info_value(&system_info, &filter)
+                .context("Failed to serialize system information")?;
+            let mut out = stdout_writer();
+            if format == "json" {
+                serde_json::to_writer_pretty(&mut out, &value)?;
+                writeln!(out)?;
+            } else {
+                serde_yaml::to_writer(&mut out, &value)?;
+            }
+            out.flush()?;
+        }
+        _ => system_formatters::format_system_info(&system_info, &filter),
+    }
 
     Ok(())
 }
@@ -0,0 +1,64 @@
+//! Disks command handler.
+//!
+//! One table row per mounted disk: mount point, device, type, file system,
+//! capacity and usage. A compact alternative to the Storage section of
+//! `msc sys info`.
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use colored::{Color, Colorize};
+
+use crate::core::system_info::storage;
+use crate::ui::formatters::format_size;
+use crate::ui::{Align, Cell, Table};
+
+/// Execute the disks command
+pub fn execute(_matches: &ArgMatches) -> Result<()> {
+    let disks = storage::collect().context("Failed to collect disk information")?;
+
+    if disks.is_empty() {
+        println!("{}", "No disks found".yellow());
+        return Ok(());
+    }
+
+    let mut table = Table::new(&[
+        "Mount", "Disk", "Type", "FS", "Size", "Used", "Free", "Use%",
+    ])
+    .color(0, Color::Cyan)
+    .align(4, Align::Right)
+    .align(5, Align::Right)
+    .align(6, Align::Right)
+    .align(7, Align::Right)
+    .min_width(4, 8)
+    .min_width(5, 8)
+    .min_width(6, 8)
+    .fit_terminal();
+
+    for disk in &disks {
+        let usage = disk.usage_percent;
+        table.add_row([
+            Cell::new(disk.mount_point.as_str()),
+            Cell::new(disk.name.as_str()),
+            Cell::new(disk.disk_type.to_string()),
+            Cell::new(disk.file_system.as_str()),
+            Cell::new(format_size(disk.total_bytes)),
+            Cell::new(format_size(disk.used_bytes)),
+            Cell::new(format_size(disk.available_bytes)),
+            Cell::new(format!("{:.1}%", usage)).color(usage_color(usage)),
+        ]);
+    }
+
+    table.print();
+    Ok(())
+}
+
+/// Same thresholds as the usage bars of `msc sys info`
+fn usage_color(percent: f32) -> Color {
+    if percent >= 85.0 {
+        Color::Red
+    } else if percent >= 70.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
@@ -0,0 +1,159 @@
+//! System report export command handler.
+//!
+//! Bundles the system information into a zip that can be attached to a
+//! support request: the full inventory as JSON, a plain-text summary and,
+//! optionally, a few seconds of live metrics.
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use colored::Colorize;
+use std::fs::File;
+use std::io::{Seek, Write};
+use std::path::Path;
+use std::time::Duration;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::core::system_info::redact::{redact_metrics, redact_system_info};
+use crate::core::system_info::{collector, SystemInfo};
+use crate::core::system_monitor::{MetricsCollector, SystemMetrics};
+use crate::ui::system_formatters;
+
+pub const SYSTEM_INFO_ENTRY: &str = "system_info.json";
+pub const SUMMARY_ENTRY: &str = "summary.txt";
+pub const METRICS_ENTRY: &str = "metrics.json";
+
+/// Execute the export command
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let output = matches
+        .get_one::<String>("file")
+        .context("Output file is required")?;
+    let metrics_secs = matches.get_one::<u64>("metrics").copied().unwrap_or(0);
+    let redact = !matches.get_flag("no-redact");
+
+    println!("Collecting system information...");
+    let mut info = collector::collect_system_info()?;
+    if redact {
+        redact_system_info(&mut info);
+    }
+
+    let mut metrics = if metrics_secs > 0 {
+        println!("Sampling metrics for {} seconds...", metrics_secs);
+        sample_metrics(metrics_secs)?
+    } else {
+        Vec::new()
+    };
+    if redact {
+        metrics.iter_mut().for_each(redact_metrics);
+    }
+
+    let file = File::create(output).with_context(|| format!("Failed to create {}", output))?;
+    write_report(file, &info, &metrics)
+        .with_context(|| format!("Failed to write report to {}", output))?;
+
+    println!(
+        "{} {}",
+        "✓ Report saved to".green(),
+        Path::new(output).display()
+    );
+    if !redact {
+        println!(
+            "{}",
+            "  Serial numbers and addresses are included; review the report before sharing it."
+                .yellow()
+        );
+    }
+    Ok(())
+}
+
+/// One metrics snapshot per second, after letting CPU usage settle
+fn sample_metrics(seconds: u64) -> Result<Vec<SystemMetrics>> {
+    let mut collector = MetricsCollector::new();
+    collector.collect()?;
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+    let mut samples = Vec::new();
+    for i in 0..seconds {
+        if i > 0 {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        samples.push(collector.collect()?);
+    }
+    Ok(samples)
+}
+
+/// Write the report zip; the metrics entry is left out when `metrics` is empty
+pub fn write_report<W: Write + Seek>(
+    writer: W,
+    info: &SystemInfo,
+    metrics: &[SystemMetrics],
+) -> Result<W> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file(SYSTEM_INFO_ENTRY, options)?;
+    serde_json::to_writer_pretty(&mut zip, info)?;
+
+    zip.start_file(SUMMARY_ENTRY, options)?;
+    zip.write_all(system_formatters::system_info_summary(info).as_bytes())?;
+
+    if !metrics.is_empty() {
+        zip.start_file(METRICS_ENTRY, options)?;
+        serde_json::to_writer_pretty(&mut zip, metrics)?;
+    }
+
+    Ok(zip.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::system_info::redact::tests::{sample_info, BATTERY_SERIAL, DISK_SERIAL};
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    fn read_entries(bytes: Vec<u8>) -> Vec<(String, String)> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        (0..archive.len())
+            .map(|i| {
+                let mut file = archive.by_index(i).unwrap();
+                let mut content = String::new();
+                file.read_to_string(&mut content).unwrap();
+                (file.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_report_contains_expected_entries() {
+        let info = sample_info();
+
+        let without_metrics = write_report(Cursor::new(Vec::new()), &info, &[]).unwrap();
+        let entries = read_entries(without_metrics.into_inner());
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, [SYSTEM_INFO_ENTRY, SUMMARY_ENTRY]);
+
+        let parsed: SystemInfo = serde_json::from_str(&entries[0].1).unwrap();
+        assert_eq!(parsed.storage[0].model.as_deref(), Some("980 PRO"));
+        assert!(entries[1].1.contains("980 PRO [NVMe SSD]"));
+
+        let metrics = vec![SystemMetrics::default(); 2];
+        let with_metrics = write_report(Cursor::new(Vec::new()), &info, &metrics).unwrap();
+        let entries = read_entries(with_metrics.into_inner());
+        assert_eq!(entries[2].0, METRICS_ENTRY);
+        let samples: Vec<SystemMetrics> = serde_json::from_str(&entries[2].1).unwrap();
+        assert_eq!(samples.len(), 2);
+    }
+
+    #[test]
+    fn test_redacted_report_has_no_serials() {
+        let mut info = sample_info();
+        redact_system_info(&mut info);
+
+        let report = write_report(Cursor::new(Vec::new()), &info, &[]).unwrap();
+        for (name, content) in read_entries(report.into_inner()) {
+            assert!(!content.contains(DISK_SERIAL), "{} leaks a serial", name);
+            assert!(!content.contains(BATTERY_SERIAL), "{} leaks a serial", name);
+        }
+    }
+}
@@ -5,7 +5,16 @@
 use anyhow::{Context, Result};
 use clap::ArgMatches;
 
+use crate::core::system_monitor::runtime::unavailable_subsystems;
+use crate::core::system_monitor::{prometheus, CsvLogger, MetricsRuntime};
 use crate::ui::monitor_tui::{run_monitor_app, MonitorAppConfig};
+use crate::ui::system_formatters;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long `--once --json` waits for slow or absent subsystems (e.g. no GPU)
+const COMPLETE_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Execute the monitor command
 pub fn execute(matches: &ArgMatches) -> Result<()> {
@@ -25,6 +34,33 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         .unwrap_or(10);
 
     let json_output = matches.get_flag("json");
+    let log_csv = matches.get_one::<String>("log-csv").map(PathBuf::from);
+
+    if matches.get_flag("prometheus-dump") {
+        return run_prometheus_dump();
+    }
+    if let Some(port) = matches.get_one::<u16>("prometheus-port").copied() {
+        let bind = matches
+            .get_one::<IpAddr>("prometheus-bind")
+            .copied()
+            .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        return run_prometheus_server(SocketAddr::new(bind, port));
+    }
+
+    // Full snapshot for scripts: waits for every subsystem instead of the first data
+    if matches.get_flag("once") && json_output {
+        return run_complete_snapshot(log_csv.as_deref());
+    }
+
+    // One-shot/limited snapshots for headless machines and logging
+    let count = if matches.get_flag("once") {
+        Some(1)
+    } else {
+        matches.get_one::<u64>("count").copied()
+    };
+    if let Some(count) = count {
+        return run_snapshots(count, interval, json_output, log_csv.as_deref());
+    }
 
     // Handle JSON output mode (non-TUI)
     if json_output {
@@ -49,16 +85,120 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         show_temperatures: true,
         show_processes: !(cpu_only || gpu_only || memory_only),
         top_processes,
+        log_csv,
     };
 
     // Run TUI
     run_monitor_app(config).context("Failed to run system monitor")
 }
 
+/// Print `count` snapshots `interval_ms` apart, then exit without starting the TUI
+fn run_snapshots(count: u64, interval_ms: u64, json: bool, log_csv: Option<&Path>) -> Result<()> {
+    let mut csv_log = log_csv
+        .map(|path| {
+            CsvLogger::open(path)
+                .with_context(|| format!("Failed to open CSV log {}", path.display()))
+        })
+        .transpose()?;
+    let runtime = MetricsRuntime::new().context("Failed to start metrics collection")?;
+    let mut snapshot = runtime.wait_for_snapshot(Duration::from_secs(5));
+
+    for i in 0..count {
+        if i > 0 {
+            std::thread::sleep(Duration::from_millis(interval_ms));
+            snapshot = runtime.latest_snapshot();
+        }
+        if json {
+            println!("{}", serde_json::to_string(&*snapshot)?);
+        } else {
+            system_formatters::format_metrics_snapshot(&snapshot);
+            system_formatters::format_unavailable_subsystems(&unavailable_subsystems(
+                &runtime.subsystem_statuses(),
+            ));
+        }
+        if let Some(logger) = csv_log.as_mut() {
+            logger
+                .append(&snapshot)
+                .context("Failed to write CSV log")?;
+        }
+    }
+
+    runtime.shutdown();
+    Ok(())
+}
+
+/// Print one snapshot as JSON once every subsystem has reported (missing ones are null)
+fn run_complete_snapshot(log_csv: Option<&Path>) -> Result<()> {
+    let mut csv_log = log_csv
+        .map(|path| {
+            CsvLogger::open(path)
+                .with_context(|| format!("Failed to open CSV log {}", path.display()))
+        })
+        .transpose()?;
+    let runtime = MetricsRuntime::new().context("Failed to start metrics collection")?;
+    let snapshot = runtime.wait_for_all_subsystems(COMPLETE_SNAPSHOT_TIMEOUT);
+    for subsystem in &snapshot.missing {
+        log::warn!("No data from {} before the timeout", subsystem.label());
+    }
+
+    println!("{}", serde_json::to_string(&snapshot.to_json()?)?);
+    if let Some(logger) = csv_log.as_mut() {
+        logger
+            .append(&snapshot.metrics)
+            .context("Failed to write CSV log")?;
+    }
+
+    runtime.shutdown();
+    Ok(())
+}
+
+/// Print one snapshot in Prometheus text format
+fn run_prometheus_dump() -> Result<()> {
+    let runtime = MetricsRuntime::new().context("Failed to start metrics collection")?;
+    let snapshot = runtime.wait_for_snapshot(Duration::from_secs(5));
+    print!("{}", prometheus::render(&snapshot));
+    runtime.shutdown();
+    Ok(())
+}
+
+/// Serve the latest snapshot at `http://<addr>/metrics` until interrupted
+///
+/// Only loopback is used unless `--prometheus-bind` asks for another address,
+/// since the metrics expose the hostname, processes, disks and network.
+fn run_prometheus_server(addr: SocketAddr) -> Result<()> {
+    let runtime = MetricsRuntime::new().context("Failed to start metrics collection")?;
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("Failed to listen on {}", addr))?;
+    runtime.wait_for_snapshot(Duration::from_secs(5));
+
+    println!(
+        "Serving Prometheus metrics at http://{}/metrics (Ctrl+C to stop)",
+        addr
+    );
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("Failed to accept metrics connection: {}", e);
+                continue;
+            }
+        };
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+        if let Err(e) = prometheus::respond(&mut stream, || {
+            prometheus::render(&runtime.latest_snapshot())
+        }) {
+            log::warn!("Failed to answer metrics request: {}", e);
+        }
+    }
+
+    runtime.shutdown();
+    Ok(())
+}
+
 /// Run in JSON output mode (for scripting)
 fn run_json_output(interval_ms: u64) -> Result<()> {
     use crate::core::system_monitor::{CollectorConfig, MetricsCollector};
-    use std::time::Duration;
 
     let mut collector = MetricsCollector::with_config(CollectorConfig::default());
 
@@ -0,0 +1,86 @@
+//! Process tree command handler.
+//!
+//! Prints the process hierarchy, or exports it as Graphviz DOT for
+//! visualization (`--dot`). `--filter`, `--pid` and `--min-cpu` prune the
+//! tree, keeping the ancestors of matching processes.
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use colored::Colorize;
+use std::fs;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::core::system_monitor::{
+    build_process_tree, filter_process_tree, flatten_tree, format_tree_indent,
+    sort_and_truncate_processes, to_dot, ProcessMetrics, ProcessTreeFilter,
+};
+use crate::ui::glyphs::adapt;
+
+/// Execute the processes command
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let top = matches
+        .get_one::<usize>("top")
+        .copied()
+        .unwrap_or(usize::MAX);
+    let filter = ProcessTreeFilter {
+        name: matches.get_one::<String>("filter").cloned(),
+        pid: matches.get_one::<u32>("pid").copied(),
+        min_cpu: matches.get_one::<f32>("min-cpu").copied(),
+    };
+
+    let processes = collect_processes(top);
+    let mut tree = build_process_tree(&processes);
+    if !filter.is_empty() {
+        tree = filter_process_tree(&tree, &filter);
+        if tree.is_empty() {
+            println!("{}", "No processes match the filter".yellow());
+            return Ok(());
+        }
+    }
+    let flattened = flatten_tree(&tree);
+
+    if let Some(path) = matches.get_one::<String>("dot") {
+        fs::write(path, to_dot(&tree))
+            .with_context(|| format!("Failed to write DOT file {}", path))?;
+        println!(
+            "{} {}",
+            format!(
+                "{} Process tree ({} processes) saved to",
+                adapt("✓"),
+                flattened.len()
+            )
+            .green(),
+            path
+        );
+        println!(
+            "{}",
+            format!("  Render it with: dot -Tsvg {} -o processes.svg", path).dimmed()
+        );
+        return Ok(());
+    }
+
+    for flat in flattened {
+        let process = &flat.process;
+        println!(
+            "{}{} {} {}",
+            format_tree_indent(&flat).dimmed(),
+            process.name.bold(),
+            format!("({})", process.pid).dimmed(),
+            format!("{:.1}%", process.cpu_usage_percent).cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Snapshot of all processes (or the `top` busiest by CPU)
+fn collect_processes(top: usize) -> Vec<ProcessMetrics> {
+    let mut system = System::new();
+    let refresh_kind = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    system.refresh_memory();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+    // CPU usage is a delta between two refreshes
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+
+    sort_and_truncate_processes(system.processes(), system.total_memory(), top)
+}
@@ -0,0 +1,208 @@
+//! Top processes command handler.
+//!
+//! A lightweight alternative to `msc sys monitor`: only the process table,
+//! redrawn in place every interval until Ctrl+C, without the TUI.
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use colored::Colorize;
+use crossterm::{cursor, execute, terminal};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::core::system_monitor::{
+    sort_and_truncate_processes, sort_processes, ProcessMetrics, ProcessSortKey,
+};
+use crate::ui::formatters::format_size;
+use crate::ui::interrupt;
+
+/// How often the loop checks for cancellation while waiting for the next refresh
+const CANCEL_POLL: Duration = Duration::from_millis(50);
+
+/// Execute the top command
+pub fn execute(matches: &ArgMatches) -> Result<()> {
+    let sort_key = parse_sort_key(
+        matches
+            .get_one::<String>("sort")
+            .map(String::as_str)
+            .unwrap_or("cpu"),
+    );
+    let count = matches.get_one::<usize>("count").copied().unwrap_or(15);
+    let interval =
+        Duration::from_millis(matches.get_one::<u64>("interval").copied().unwrap_or(1000));
+
+    let cancel_flag = interrupt::cancel_flag()?;
+
+    let mut system = System::new();
+    let refresh_kind = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    system.refresh_memory();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+    // CPU usage is a delta between two refreshes
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+    let mut stdout = io::stdout();
+    run_refresh_loop(&cancel_flag, interval, || {
+        system.refresh_memory();
+        system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+        let processes = top_processes(
+            sort_and_truncate_processes(system.processes(), system.total_memory(), usize::MAX),
+            sort_key,
+            count,
+        );
+
+        execute!(
+            stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+        print_table(&mut stdout, &processes, sort_key)?;
+        stdout.flush()?;
+        Ok(())
+    })
+    .context("Failed to refresh process list")?;
+
+    println!();
+    Ok(())
+}
+
+fn parse_sort_key(value: &str) -> ProcessSortKey {
+    match value {
+        "memory" => ProcessSortKey::Memory,
+        "pid" => ProcessSortKey::Pid,
+        "name" => ProcessSortKey::Name,
+        _ => ProcessSortKey::Cpu,
+    }
+}
+
+/// Order `processes` by `sort_key` and keep the first `count`
+fn top_processes(
+    mut processes: Vec<ProcessMetrics>,
+    sort_key: ProcessSortKey,
+    count: usize,
+) -> Vec<ProcessMetrics> {
+    sort_processes(&mut processes, sort_key);
+    processes.truncate(count);
+    processes
+}
+
+/// Call `refresh` every `interval` until `cancel_flag` is set; returns the number of refreshes
+///
+/// The flag is checked while waiting, so Ctrl+C is honored within
+/// [`CANCEL_POLL`] instead of after a full interval.
+fn run_refresh_loop<F>(cancel_flag: &AtomicBool, interval: Duration, mut refresh: F) -> Result<u64>
+where
+    F: FnMut() -> Result<()>,
+{
+    let mut refreshes = 0;
+
+    while !cancel_flag.load(Ordering::Relaxed) {
+        let started = Instant::now();
+        refresh()?;
+        refreshes += 1;
+
+        while started.elapsed() < interval && !cancel_flag.load(Ordering::Relaxed) {
+            std::thread::sleep(CANCEL_POLL.min(interval.saturating_sub(started.elapsed())));
+        }
+    }
+
+    Ok(refreshes)
+}
+
+fn print_table<W: Write>(
+    out: &mut W,
+    processes: &[ProcessMetrics],
+    sort_key: ProcessSortKey,
+) -> io::Result<()> {
+    let sort_name = match sort_key {
+        ProcessSortKey::Cpu => "CPU",
+        ProcessSortKey::Memory => "memory",
+        ProcessSortKey::Pid => "PID",
+        ProcessSortKey::Name => "name",
+    };
+    writeln!(
+        out,
+        "{} {}",
+        "Top processes".bold(),
+        format!("(sorted by {}, Ctrl+C to quit)", sort_name).dimmed()
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{}",
+        format!(
+            "{:>8}  {:<32} {:>7} {:>10}",
+            "PID", "NAME", "CPU %", "MEMORY"
+        )
+        .bold()
+    )?;
+
+    for process in processes {
+        let name: String = process.name.chars().take(32).collect();
+        writeln!(
+            out,
+            "{:>8}  {:<32} {:>7.1} {:>10}",
+            process.pid,
+            name,
+            process.cpu_usage_percent,
+            format_size(process.memory_bytes)
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, cpu: f32, memory: u64) -> ProcessMetrics {
+        ProcessMetrics {
+            pid,
+            name: name.to_string(),
+            cpu_usage_percent: cpu,
+            memory_bytes: memory,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_refresh_loop_stops_on_cancel() {
+        let cancel_flag = AtomicBool::new(false);
+        let mut calls = 0;
+        let refreshes = run_refresh_loop(&cancel_flag, Duration::from_millis(1), || {
+            calls += 1;
+            if calls == 3 {
+                cancel_flag.store(true, Ordering::Relaxed);
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(refreshes, 3);
+
+        // Already cancelled: never refreshes
+        let refreshes = run_refresh_loop(&cancel_flag, Duration::ZERO, || Ok(())).unwrap();
+        assert_eq!(refreshes, 0);
+    }
+
+    #[test]
+    fn test_top_processes_applies_sort_key() {
+        let processes = vec![
+            process(300, "bash", 1.0, 50),
+            process(100, "chrome", 40.0, 900),
+            process(200, "Xorg", 5.0, 300),
+        ];
+        let pids = |sort_key: &str, count: usize| -> Vec<u32> {
+            top_processes(processes.clone(), parse_sort_key(sort_key), count)
+                .iter()
+                .map(|p| p.pid)
+                .collect()
+        };
+
+        assert_eq!(pids("cpu", 2), [100, 200]);
+        assert_eq!(pids("memory", 3), [100, 200, 300]);
+        assert_eq!(pids("pid", 1), [100]);
+        assert_eq!(pids("name", 3), [300, 100, 200]);
+    }
+}
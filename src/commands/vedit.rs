@@ -1,6 +1,7 @@
 use crate::core::FFmpegManager;
 use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
+use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -11,8 +12,10 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
         Some(("comp", sub_matches)) | Some(("compress", sub_matches)) => {
             execute_compress(sub_matches)
         }
+        Some(("trim", sub_matches)) => execute_trim(sub_matches),
+        Some(("extract-audio", sub_matches)) => execute_extract_audio(sub_matches),
         _ => Err(anyhow!(
-            "Subcomando no reconocido. Usa 'msc vedit comp <quality> <video>'"
+            "Subcomando no reconocido. Usa 'msc vedit comp <quality> <video>', 'msc vedit trim <inicio> <fin> <video>' o 'msc vedit extract-audio <video>'"
         )),
     }
 }
@@ -42,7 +45,7 @@ fn execute_compress(matches: &clap::ArgMatches) -> Result<()> {
     let ffmpeg_path = manager.ensure_ffmpeg()?;
 
     // 5. Construir nombre de salida
-    let output_path = build_output_path(&input_path)?;
+    let output_path = build_output_path(&input_path, "_compress")?;
 
     // 6. Ejecutar compresión
     execute_compression(&ffmpeg_path, &input_path, &output_path, quality)?;
@@ -50,6 +53,555 @@ fn execute_compress(matches: &clap::ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Execute video trimming
+fn execute_trim(matches: &clap::ArgMatches) -> Result<()> {
+    // 1. Extraer argumentos
+    let start_arg = matches
+        .get_one::<String>("start")
+        .context("Marca de inicio es requerida")?;
+    let second = matches.get_one::<String>("end").map(|s| s.as_str());
+    let third = matches.get_one::<String>("video").map(|s| s.as_str());
+    let duration_arg = matches.get_one::<String>("duration").map(|s| s.as_str());
+    let reencode = matches.get_flag("reencode");
+
+    // Con --duration el segundo argumento es el video: trim <inicio> <video> --duration <d>
+    let (end_arg, video_path) = match (duration_arg, second, third) {
+        (None, Some(end), Some(video)) => (Some(end), video),
+        (Some(_), Some(video), None) => (None, video),
+        (Some(_), Some(_), Some(_)) => {
+            return Err(anyhow!("Usa una marca de fin o --duration, no ambas"))
+        }
+        _ => {
+            return Err(anyhow!(
+                "Uso: msc vedit trim <inicio> <fin> <video> o msc vedit trim <inicio> <video> --duration <duración>"
+            ))
+        }
+    };
+
+    // 2. Validar marcas de tiempo
+    let range = TrimRange::parse(start_arg, end_arg, duration_arg)?;
+
+    // 3. Validar que el archivo existe y es un video
+    let input_path = PathBuf::from(video_path);
+    if !input_path.exists() {
+        return Err(anyhow!("El archivo '{}' no existe", video_path));
+    }
+    validate_video_file(&input_path)?;
+
+    // 4. Asegurar que FFmpeg está disponible
+    let mut manager = FFmpegManager::new()?;
+    let ffmpeg_path = manager.ensure_ffmpeg()?;
+
+    // 5. Construir nombre de salida
+    let output_path = build_output_path(&input_path, "_trim")?;
+
+    // 6. Ejecutar recorte
+    execute_trimming(&ffmpeg_path, &input_path, &output_path, range, reencode)
+}
+
+/// Segmento a extraer, en segundos desde el inicio del video
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TrimRange {
+    start: f64,
+    end: f64,
+}
+
+impl TrimRange {
+    /// Construye el segmento a partir de un fin absoluto o de una duración
+    fn parse(start: &str, end: Option<&str>, duration: Option<&str>) -> Result<Self> {
+        let start_secs = parse_timestamp(start)?;
+        let end_secs = match (end, duration) {
+            (Some(end), None) => parse_timestamp(end)?,
+            (None, Some(duration)) => start_secs + parse_timestamp(duration)?,
+            _ => return Err(anyhow!("Indica una marca de fin o --duration")),
+        };
+
+        if start_secs >= end_secs {
+            return Err(anyhow!(
+                "El inicio ({}) debe ser anterior al fin ({})",
+                format_timestamp(start_secs),
+                format_timestamp(end_secs)
+            ));
+        }
+
+        Ok(Self {
+            start: start_secs,
+            end: end_secs,
+        })
+    }
+
+    fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Convierte `SS`, `MM:SS` o `HH:MM:SS` (con decimales opcionales) a segundos
+fn parse_timestamp(value: &str) -> Result<f64> {
+    let invalid = || {
+        anyhow!(
+            "Marca de tiempo inválida: '{}'. Usa SS, MM:SS o HH:MM:SS (ej. 00:01:30.5)",
+            value
+        )
+    };
+
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+
+    let mut seconds = 0.0;
+    for (index, part) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        let number: f64 = if is_last {
+            part.parse().map_err(|_| invalid())?
+        } else {
+            // Solo los segundos admiten decimales
+            part.parse::<u64>().map_err(|_| invalid())? as f64
+        };
+
+        // Minutos y segundos por debajo de 60 cuando hay una unidad mayor
+        if !number.is_finite() || number < 0.0 || (index > 0 && number >= 60.0) {
+            return Err(invalid());
+        }
+        seconds = seconds * 60.0 + number;
+    }
+
+    Ok(seconds)
+}
+
+/// Formatea segundos como `HH:MM:SS.mmm`, el formato que acepta FFmpeg
+fn format_timestamp(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        (millis / 60_000) % 60,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
+
+/// Argumentos de FFmpeg para extraer `range` de `input_path`
+///
+/// Por defecto copia los streams sin recodificar (rápido, pero el corte cae
+/// en el keyframe más cercano); con `reencode` el corte es exacto al frame.
+fn build_trim_args(
+    input_path: &Path,
+    output_path: &Path,
+    range: TrimRange,
+    reencode: bool,
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec![
+        "-i".into(),
+        input_path.into(),
+        "-ss".into(),
+        format_timestamp(range.start).into(),
+        "-to".into(),
+        format_timestamp(range.end).into(),
+    ];
+
+    if reencode {
+        args.extend(["-c:v", "libx264", "-c:a", "aac"].map(OsString::from));
+    } else {
+        args.extend(["-c", "copy", "-avoid_negative_ts", "make_zero"].map(OsString::from));
+    }
+
+    args.push("-y".into()); // Sobrescribir sin preguntar
+    args.push(output_path.into());
+    args
+}
+
+/// Ejecuta el recorte con FFmpeg
+fn execute_trimming(
+    ffmpeg_path: &PathBuf,
+    input_path: &Path,
+    output_path: &Path,
+    range: TrimRange,
+    reencode: bool,
+) -> Result<()> {
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  Recorte de Video".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+    println!("{} {}", "📹 Entrada:".cyan(), input_path.display());
+    println!("{} {}", "💾 Salida:".cyan(), output_path.display());
+    println!(
+        "{} {} → {} ({})",
+        "✂️  Segmento:".cyan(),
+        format_timestamp(range.start),
+        format_timestamp(range.end),
+        format_timestamp(range.duration())
+    );
+    println!(
+        "{} {}",
+        "⚙️  Modo:".cyan(),
+        if reencode {
+            "Recodificar (corte exacto)"
+        } else {
+            "Copia de streams (rápido, corte en keyframe)"
+        }
+    );
+    println!();
+
+    // Construir comando
+    let mut cmd = Command::new(ffmpeg_path);
+    cmd.args(build_trim_args(input_path, output_path, range, reencode));
+
+    println!("{} {:?}", "Ejecutando:".dimmed(), cmd);
+    println!();
+
+    // Ejecutar comando
+    let status = cmd.status().context("Error al ejecutar ffmpeg")?;
+
+    println!();
+
+    if !status.success() {
+        return Err(anyhow!("El recorte falló con código de salida: {}", status));
+    }
+
+    println!("{}", "✓ Recorte completado exitosamente".green().bold());
+    println!();
+    println!(
+        "{} {}",
+        "💾 Archivo guardado:".green().bold(),
+        output_path.display()
+    );
+
+    Ok(())
+}
+
+/// Execute audio extraction
+fn execute_extract_audio(matches: &clap::ArgMatches) -> Result<()> {
+    // 1. Extraer argumentos
+    let video_path = matches
+        .get_one::<String>("video")
+        .context("Archivo de video es requerido")?;
+    let format = AudioFormat::parse(
+        matches
+            .get_one::<String>("format")
+            .map(|s| s.as_str())
+            .unwrap_or("mp3"),
+    )?;
+    let bitrate = matches.get_one::<String>("bitrate").map(|s| s.as_str());
+    if let Some(bitrate) = bitrate {
+        validate_bitrate(bitrate)?;
+        if format.is_lossless() {
+            println!(
+                "{}",
+                format!("⚠ {} no usa bitrate; se ignorará --bitrate", format.name()).yellow()
+            );
+        }
+    }
+
+    // 2. Validar que el archivo existe y es un video
+    let input_path = PathBuf::from(video_path);
+    if !input_path.exists() {
+        return Err(anyhow!("El archivo '{}' no existe", video_path));
+    }
+    validate_video_file(&input_path)?;
+
+    // 3. Asegurar que FFmpeg está disponible
+    let mut manager = FFmpegManager::new()?;
+    let ffmpeg_path = manager.ensure_ffmpeg()?;
+
+    // 4. Si el audio ya está en el formato pedido, ofrecer copiarlo sin recodificar
+    let source_codec = detect_audio_codec(&ffmpeg_path, &input_path);
+    let copy = match source_codec.as_deref() {
+        Some(codec) if format.matches_codec(codec) => {
+            if bitrate.is_some() {
+                println!(
+                    "{}",
+                    "ℹ El audio ya está en ese formato, pero se recodificará para aplicar --bitrate"
+                        .dimmed()
+                );
+                false
+            } else {
+                dialoguer::Confirm::new()
+                    .with_prompt(format!(
+                        "El audio ya está en {} ({}). ¿Copiarlo sin recodificar? (más rápido, sin pérdida)",
+                        format.name(),
+                        codec
+                    ))
+                    .default(true)
+                    .interact()?
+            }
+        }
+        _ => false,
+    };
+
+    // 5. Construir nombre de salida: <nombre>.<ext> junto al video
+    let output_path = build_audio_output_path(&input_path, format)?;
+
+    // 6. Ejecutar extracción
+    execute_audio_extraction(
+        &ffmpeg_path,
+        &input_path,
+        &output_path,
+        AudioEncoding {
+            format,
+            bitrate,
+            copy,
+            source_codec: source_codec.as_deref(),
+        },
+    )
+}
+
+/// Formatos de salida de `extract-audio`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AudioFormat {
+    Mp3,
+    Aac,
+    Flac,
+    Wav,
+}
+
+impl AudioFormat {
+    fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "mp3" => Ok(AudioFormat::Mp3),
+            "aac" => Ok(AudioFormat::Aac),
+            "flac" => Ok(AudioFormat::Flac),
+            "wav" => Ok(AudioFormat::Wav),
+            _ => Err(anyhow!(
+                "Formato inválido: '{}'. Usa: mp3, aac, flac o wav",
+                name
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "MP3",
+            AudioFormat::Aac => "AAC",
+            AudioFormat::Flac => "FLAC",
+            AudioFormat::Wav => "WAV",
+        }
+    }
+
+    /// Extensión del archivo de salida (AAC se guarda en contenedor M4A)
+    fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Aac => "m4a",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Wav => "wav",
+        }
+    }
+
+    /// Codificador de FFmpeg
+    fn encoder(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "libmp3lame",
+            AudioFormat::Aac => "aac",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Wav => "pcm_s16le",
+        }
+    }
+
+    fn is_lossless(self) -> bool {
+        matches!(self, AudioFormat::Flac | AudioFormat::Wav)
+    }
+
+    /// Bitrate usado cuando no se indica `--bitrate`
+    fn default_bitrate(self) -> Option<&'static str> {
+        match self {
+            AudioFormat::Mp3 | AudioFormat::Aac => Some("192k"),
+            AudioFormat::Flac | AudioFormat::Wav => None,
+        }
+    }
+
+    /// Si un stream de audio con `codec` puede copiarse tal cual a este formato
+    fn matches_codec(self, codec: &str) -> bool {
+        match self {
+            AudioFormat::Mp3 => codec == "mp3",
+            AudioFormat::Aac => codec == "aac",
+            AudioFormat::Flac => codec == "flac",
+            AudioFormat::Wav => codec.starts_with("pcm_"),
+        }
+    }
+}
+
+/// Valida un bitrate como `128k`, `320K` o `1M`
+fn validate_bitrate(bitrate: &str) -> Result<()> {
+    let digits = bitrate.trim_end_matches(['k', 'K', 'm', 'M']);
+    let valid = !digits.is_empty()
+        && bitrate.len() - digits.len() <= 1
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && digits.parse::<u32>().is_ok_and(|n| n > 0);
+
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Bitrate inválido: '{}'. Usa un valor como 128k, 192k o 320k",
+            bitrate
+        ))
+    }
+}
+
+/// Códec del primer stream de audio, leído de la información que FFmpeg
+/// imprime al abrir el archivo; `None` si no se pudo determinar
+fn detect_audio_codec(ffmpeg_path: &Path, input_path: &Path) -> Option<String> {
+    let output = Command::new(ffmpeg_path)
+        .arg("-hide_banner")
+        .arg("-i")
+        .arg(input_path)
+        .output()
+        .ok()?;
+
+    // Sin archivo de salida ffmpeg termina con error, pero ya listó los streams
+    parse_audio_codec(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Extrae el códec de una línea como `Stream #0:1(und): Audio: aac (LC) (mp4a / ...)`
+fn parse_audio_codec(ffmpeg_info: &str) -> Option<String> {
+    ffmpeg_info
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| line.starts_with("Stream #"))
+        .find_map(|line| {
+            let (_, rest) = line.split_once(": Audio: ")?;
+            let codec: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            (!codec.is_empty()).then_some(codec)
+        })
+}
+
+/// Parámetros de codificación de `extract-audio`
+#[derive(Debug, Clone, Copy)]
+struct AudioEncoding<'a> {
+    format: AudioFormat,
+    bitrate: Option<&'a str>,
+    /// Copiar el stream de audio sin recodificar
+    copy: bool,
+    source_codec: Option<&'a str>,
+}
+
+/// Argumentos de FFmpeg para extraer el audio de `input_path`
+fn build_extract_audio_args(
+    input_path: &Path,
+    output_path: &Path,
+    encoding: &AudioEncoding,
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec!["-i".into(), input_path.into(), "-vn".into()];
+
+    if encoding.copy {
+        args.extend(["-c:a", "copy"].map(OsString::from));
+    } else {
+        args.extend(["-c:a", encoding.format.encoder()].map(OsString::from));
+        if !encoding.format.is_lossless() {
+            if let Some(bitrate) = encoding.bitrate.or(encoding.format.default_bitrate()) {
+                args.extend(["-b:a", bitrate].map(OsString::from));
+            }
+        }
+    }
+
+    args.push("-y".into()); // Sobrescribir sin preguntar
+    args.push(output_path.into());
+    args
+}
+
+/// Construye el path de salida `<nombre>.<ext>` junto al video
+fn build_audio_output_path(input_path: &Path, format: AudioFormat) -> Result<PathBuf> {
+    if input_path.file_stem().is_none() {
+        return Err(anyhow!("No se pudo obtener el nombre del archivo"));
+    }
+    let output_path = input_path.with_extension(format.extension());
+
+    // Verificar si el archivo de salida ya existe
+    if output_path.exists() {
+        println!(
+            "{}",
+            format!(
+                "⚠ El archivo '{}' ya existe y será sobrescrito",
+                output_path.display()
+            )
+            .yellow()
+        );
+    }
+
+    Ok(output_path)
+}
+
+/// Ejecuta la extracción de audio con FFmpeg
+fn execute_audio_extraction(
+    ffmpeg_path: &PathBuf,
+    input_path: &Path,
+    output_path: &PathBuf,
+    encoding: AudioEncoding,
+) -> Result<()> {
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  Extracción de Audio".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+    println!("{} {}", "📹 Entrada:".cyan(), input_path.display());
+    println!("{} {}", "💾 Salida:".cyan(), output_path.display());
+    println!(
+        "{} {} (origen: {})",
+        "🎵 Formato:".cyan(),
+        encoding.format.name(),
+        encoding.source_codec.unwrap_or("desconocido")
+    );
+    println!(
+        "{} {}",
+        "⚙️  Modo:".cyan(),
+        if encoding.copy {
+            "Copia del stream (sin recodificar)".to_string()
+        } else {
+            match encoding.bitrate.or(encoding.format.default_bitrate()) {
+                Some(bitrate) if !encoding.format.is_lossless() => {
+                    format!(
+                        "Recodificar con {} a {}",
+                        encoding.format.encoder(),
+                        bitrate
+                    )
+                }
+                _ => format!("Recodificar con {}", encoding.format.encoder()),
+            }
+        }
+    );
+    println!();
+
+    // Construir comando
+    let mut cmd = Command::new(ffmpeg_path);
+    cmd.args(build_extract_audio_args(input_path, output_path, &encoding));
+
+    println!("{} {:?}", "Ejecutando:".dimmed(), cmd);
+    println!();
+
+    // Ejecutar comando
+    let status = cmd.status().context("Error al ejecutar ffmpeg")?;
+
+    println!();
+
+    if !status.success() {
+        return Err(anyhow!(
+            "La extracción falló con código de salida: {}",
+            status
+        ));
+    }
+
+    let audio_size = fs::metadata(output_path)?.len();
+    println!("{}", "✓ Extracción completada exitosamente".green().bold());
+    println!();
+    println!(
+        "{} {:.1} MB",
+        "📦 Tamaño del audio:".cyan(),
+        audio_size as f64 / 1_048_576.0
+    );
+    println!(
+        "{} {}",
+        "💾 Archivo guardado:".green().bold(),
+        output_path.display()
+    );
+
+    Ok(())
+}
+
 /// Valida que el archivo tenga una extensión de video válida
 fn validate_video_file(path: &Path) -> Result<()> {
     let valid_extensions = ["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v"];
@@ -71,8 +623,8 @@ fn validate_video_file(path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Construye el path de salida agregando "_compress" antes de la extensión
-fn build_output_path(input_path: &Path) -> Result<PathBuf> {
+/// Construye el path de salida agregando `suffix` (p. ej. "_compress") antes de la extensión
+fn build_output_path(input_path: &Path, suffix: &str) -> Result<PathBuf> {
     let parent = input_path
         .parent()
         .ok_or_else(|| anyhow!("No se pudo obtener el directorio padre"))?;
@@ -87,7 +639,7 @@ fn build_output_path(input_path: &Path) -> Result<PathBuf> {
         .and_then(|e| e.to_str())
         .ok_or_else(|| anyhow!("No se pudo obtener la extensión"))?;
 
-    let output_name = format!("{}_compress.{}", stem, extension);
+    let output_name = format!("{}{}.{}", stem, suffix, extension);
     let output_path = parent.join(output_name);
 
     // Verificar si el archivo de salida ya existe
@@ -218,11 +770,150 @@ mod tests {
     #[test]
     fn test_build_output_path() {
         let input = PathBuf::from("C:\\videos\\test.mp4");
-        let output = build_output_path(&input).unwrap();
+        let output = build_output_path(&input, "_compress").unwrap();
         assert_eq!(output, PathBuf::from("C:\\videos\\test_compress.mp4"));
 
         let input2 = PathBuf::from("/home/user/video.avi");
-        let output2 = build_output_path(&input2).unwrap();
+        let output2 = build_output_path(&input2, "_compress").unwrap();
         assert_eq!(output2, PathBuf::from("/home/user/video_compress.avi"));
+
+        let trimmed = build_output_path(&PathBuf::from("/home/user/clip.mkv"), "_trim").unwrap();
+        assert_eq!(trimmed, PathBuf::from("/home/user/clip_trim.mkv"));
+    }
+
+    #[test]
+    fn test_audio_format_and_bitrate() {
+        let aac = AudioFormat::parse("AAC").unwrap();
+        assert_eq!(aac.extension(), "m4a");
+        assert!(aac.matches_codec("aac"));
+        assert!(!aac.matches_codec("opus"));
+        assert!(AudioFormat::Wav.matches_codec("pcm_s24le"));
+        assert!(AudioFormat::parse("ogg").is_err());
+
+        assert!(validate_bitrate("192k").is_ok());
+        assert!(validate_bitrate("1M").is_ok());
+        assert!(validate_bitrate("320").is_ok());
+        assert!(validate_bitrate("0k").is_err());
+        assert!(validate_bitrate("fast").is_err());
+        assert!(validate_bitrate("12kk").is_err());
+
+        assert_eq!(
+            build_audio_output_path(Path::new("/home/user/talk.mp4"), aac).unwrap(),
+            PathBuf::from("/home/user/talk.m4a")
+        );
+    }
+
+    #[test]
+    fn test_parse_audio_codec_from_ffmpeg_info() {
+        let info = "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'talk.mp4':\n\
+            \x20 Duration: 00:03:12.05, start: 0.000000, bitrate: 1205 kb/s\n\
+            \x20 Stream #0:0[0x1](und): Video: h264 (High) (avc1 / 0x31637661), yuv420p, 1280x720\n\
+            \x20 Stream #0:1[0x2](eng): Audio: aac (LC) (mp4a / 0x6134706D), 44100 Hz, stereo\n\
+            At least one output file must be specified";
+        assert_eq!(parse_audio_codec(info).as_deref(), Some("aac"));
+
+        let video_only = "  Stream #0:0: Video: vp9, yuv420p, 1920x1080";
+        assert_eq!(parse_audio_codec(video_only), None);
+    }
+
+    #[test]
+    fn test_extract_audio_args_copy_or_encode() {
+        let args = |encoding: AudioEncoding| -> Vec<String> {
+            build_extract_audio_args(Path::new("in.mp4"), Path::new("in.mp3"), &encoding)
+                .iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect()
+        };
+        let mp3 = AudioEncoding {
+            format: AudioFormat::Mp3,
+            bitrate: None,
+            copy: false,
+            source_codec: Some("aac"),
+        };
+
+        assert_eq!(
+            args(mp3),
+            [
+                "-i",
+                "in.mp4",
+                "-vn",
+                "-c:a",
+                "libmp3lame",
+                "-b:a",
+                "192k",
+                "-y",
+                "in.mp3"
+            ]
+        );
+        assert!(args(AudioEncoding {
+            bitrate: Some("320k"),
+            ..mp3
+        })
+        .contains(&"320k".to_string()));
+        assert_eq!(
+            args(AudioEncoding { copy: true, ..mp3 })[3..5],
+            ["-c:a", "copy"]
+        );
+        assert!(!args(AudioEncoding {
+            format: AudioFormat::Flac,
+            bitrate: Some("320k"),
+            ..mp3
+        })
+        .contains(&"-b:a".to_string()));
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("00:01:30").unwrap(), 90.0);
+        assert_eq!(parse_timestamp("2:45").unwrap(), 165.0);
+        assert_eq!(parse_timestamp("75").unwrap(), 75.0);
+        assert_eq!(parse_timestamp("01:00:00.5").unwrap(), 3600.5);
+
+        assert!(parse_timestamp("00:61:00").is_err());
+        assert!(parse_timestamp("1:2:3:4").is_err());
+        assert!(parse_timestamp("1.5:00").is_err());
+        assert!(parse_timestamp("abc").is_err());
+        assert!(parse_timestamp("").is_err());
+    }
+
+    #[test]
+    fn test_trim_range_requires_start_before_end() {
+        let range = TrimRange::parse("00:01:30", Some("00:02:45"), None).unwrap();
+        assert_eq!(range.duration(), 75.0);
+
+        let by_duration = TrimRange::parse("1:30", None, Some("75")).unwrap();
+        assert_eq!(by_duration, range);
+
+        assert!(TrimRange::parse("00:02:45", Some("00:01:30"), None).is_err());
+        assert!(TrimRange::parse("10", Some("10"), None).is_err());
+        assert!(TrimRange::parse("10", None, Some("0")).is_err());
+    }
+
+    #[test]
+    fn test_trim_args_copy_streams_unless_reencoding() {
+        let range = TrimRange::parse("00:01:30", Some("00:02:45"), None).unwrap();
+        let args = |reencode| -> Vec<String> {
+            build_trim_args(
+                Path::new("in.mp4"),
+                Path::new("in_trim.mp4"),
+                range,
+                reencode,
+            )
+            .iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+        };
+
+        let copy = args(false);
+        assert_eq!(
+            copy[..6],
+            ["-i", "in.mp4", "-ss", "00:01:30.000", "-to", "00:02:45.000"]
+        );
+        assert!(copy.windows(2).any(|pair| pair == ["-c", "copy"]));
+        assert_eq!(copy.last().unwrap(), "in_trim.mp4");
+
+        let reencoded = args(true);
+        assert!(!reencoded.contains(&"copy".to_string()));
+        assert!(reencoded.windows(2).any(|pair| pair == ["-c:v", "libx264"]));
     }
 }
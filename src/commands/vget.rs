@@ -1,10 +1,15 @@
+use crate::core::http::HttpConfig;
+use crate::core::yt_dlp_progress::{parse_progress_line, YtDlpProgress, PROGRESS_TEMPLATE};
 use crate::core::{validation, Config, YtDlpManager};
+use crate::ui::progress::format_duration_short;
+use crate::ui::{clear_line, format_size, show_progress_bar};
 use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
 use dialoguer::Input;
 use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 /// Configuration for video download
 struct DownloadConfig<'a> {
@@ -20,13 +25,24 @@ struct DownloadConfig<'a> {
     no_continue: bool,
     cookies_browser: Option<&'a str>,
     cookies_file: Option<&'a str>,
+    proxy: Option<&'a str>,
 }
 
 pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     // 1. Extraer argumentos
     let get = |k| matches.get_one::<String>(k).map(|s| s.as_str());
 
-    let url = get("url").context("URL es requerida")?;
+    let mut urls: Vec<String> = matches
+        .get_many::<String>("url")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+    if let Some(batch_file) = get("batch-file") {
+        urls.extend(read_batch_file(Path::new(batch_file))?);
+    }
+    if urls.is_empty() {
+        return Err(anyhow!("No se proporcionó ninguna URL"));
+    }
+
     let output = get("output");
     let quality = get("quality");
     let format = get("format");
@@ -37,9 +53,22 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     let clean_parts = matches.get_flag("clean-parts");
     let cookies_browser = get("cb");
     let cookies_file = get("cookies");
+    // --proxy, o HTTPS_PROXY/HTTP_PROXY del entorno
+    let proxy_settings = HttpConfig::from_env().with_proxy(get("proxy")).proxy;
+    let proxy = proxy_settings.preferred();
+
+    // Un nombre fijo haría que cada descarga sobrescriba a la anterior
+    if output.is_some() && urls.len() > 1 {
+        return Err(anyhow!(
+            "--output solo puede usarse con una única URL ({} recibidas)",
+            urls.len()
+        ));
+    }
 
-    // 2. Validar URL con validación mejorada
-    validation::validate_url(url).with_context(|| format!("URL inválida: {}", url))?;
+    // 2. Validar URL con validación mejorada (en lote, cada URL se valida al descargarla)
+    if let [url] = urls.as_slice() {
+        validation::validate_url(url).with_context(|| format!("URL inválida: {}", url))?;
+    }
 
     // 3. Asegurar yt-dlp instalado
     let mut manager = YtDlpManager::new()?;
@@ -59,8 +88,8 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
             .with_context(|| format!("Output path inválido: {}", output_name))?;
     }
 
-    // 7. Construir y ejecutar comando
-    let config = DownloadConfig {
+    // 7. Construir y ejecutar comando para cada URL
+    let config_for = |url| DownloadConfig {
         yt_dlp_path: &yt_dlp_path,
         url,
         download_dir: &download_dir,
@@ -73,12 +102,86 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
         no_continue,
         cookies_browser,
         cookies_file,
+        proxy,
     };
-    execute_download(&config)?;
+
+    if let [url] = urls.as_slice() {
+        return execute_download(&config_for(url.as_str()));
+    }
+
+    // Lote: las descargas son secuenciales y un fallo no detiene las siguientes
+    let total = urls.len();
+    let mut failures: Vec<(&str, anyhow::Error)> = Vec::new();
+
+    for (index, url) in urls.iter().enumerate() {
+        println!();
+        println!(
+            "{} {}",
+            format!("[{}/{}]", index + 1, total).cyan().bold(),
+            url
+        );
+
+        let result = validation::validate_url(url)
+            .with_context(|| format!("URL inválida: {}", url))
+            .and_then(|_| execute_download(&config_for(url.as_str())));
+        if let Err(e) = result {
+            println!("{} {}", "✗".red(), format!("{:#}", e).red());
+            failures.push((url, e));
+        }
+    }
+
+    print_batch_summary(total, &failures);
+
+    if !failures.is_empty() {
+        return Err(anyhow!(
+            "{} de {} descargas fallaron",
+            failures.len(),
+            total
+        ));
+    }
 
     Ok(())
 }
 
+/// Lee un archivo de lote: una URL por línea, ignorando líneas vacías y comentarios `#`
+fn read_batch_file(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("No se pudo leer el archivo de lote: {}", path.display()))?;
+    Ok(parse_batch_urls(&content))
+}
+
+fn parse_batch_urls(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+/// Muestra el resultado de una descarga en lote
+fn print_batch_summary(total: usize, failures: &[(&str, anyhow::Error)]) {
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  Resumen".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!(
+        "{} {}",
+        "✓".green(),
+        format!("{} completadas", total - failures.len()).green()
+    );
+    if !failures.is_empty() {
+        println!(
+            "{} {}",
+            "✗".red(),
+            format!("{} fallidas", failures.len()).red()
+        );
+        for (url, error) in failures {
+            println!("  {} {}", url.dimmed(), format!("{:#}", error).red());
+        }
+    }
+}
+
 /// Limpia archivos .part huérfanos del directorio de descarga
 fn clean_part_files(download_dir: &PathBuf) -> Result<()> {
     println!("{}", "Buscando archivos .part huérfanos...".yellow());
@@ -236,6 +339,108 @@ fn execute_download(config: &DownloadConfig) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
     println!();
 
+    let mut cmd = build_command(config);
+
+    println!("{} {:?}", "Ejecutando:".dimmed(), cmd);
+    println!();
+
+    // Ejecutar comando, mostrando su progreso con la barra de MSC
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Error al ejecutar yt-dlp")?;
+
+    if let Some(stdout) = child.stdout.take() {
+        let mut progress_bar = DownloadProgressBar::default();
+        for line in BufReader::new(stdout).split(b'\n').map_while(Result::ok) {
+            progress_bar.handle_line(&String::from_utf8_lossy(&line));
+        }
+        progress_bar.finish();
+    }
+
+    let status = child.wait().context("Error al ejecutar yt-dlp")?;
+
+    println!();
+
+    if status.success() {
+        println!("{}", "✓ Descarga completada exitosamente".green().bold());
+    } else {
+        return Err(anyhow!("La descarga falló con código: {}", status));
+    }
+
+    Ok(())
+}
+
+/// Muestra el progreso de yt-dlp con la barra de progreso de MSC
+///
+/// Las líneas que no son de progreso (o que no se pueden interpretar) se
+/// muestran tal cual. Cada entrada de una playlist empieza una barra nueva.
+#[derive(Default)]
+struct DownloadProgressBar {
+    /// Video/formato de la barra que está en pantalla
+    current: Option<String>,
+}
+
+impl DownloadProgressBar {
+    fn handle_line(&mut self, line: &str) {
+        match parse_progress_line(line) {
+            Some(progress) => self.render(&progress),
+            None => {
+                self.finish();
+                println!("{}", line.trim_end_matches('\r'));
+            }
+        }
+    }
+
+    fn render(&mut self, progress: &YtDlpProgress) {
+        if self.current.as_deref() != Some(progress.item.as_str()) {
+            self.finish();
+            self.current = Some(progress.item.clone());
+        }
+
+        let prefix = match (progress.playlist_index, progress.playlist_count) {
+            (Some(index), Some(count)) => format!("Descargando [{}/{}]", index, count),
+            _ => "Descargando".to_string(),
+        };
+
+        clear_line();
+        show_progress_bar(progress.percentage(), 100, &prefix);
+        print!("{}", progress_details(progress).dimmed());
+        io::stdout().flush().ok();
+    }
+
+    /// Termina la línea de la barra actual, si hay una
+    fn finish(&mut self) {
+        if self.current.take().is_some() {
+            println!();
+        }
+    }
+}
+
+/// Tamaño, velocidad y tiempo restante, p. ej. `12.0 MB / 48.0 MB, 2.0 MB/s, ETA 18s`
+fn progress_details(progress: &YtDlpProgress) -> String {
+    let size = match progress.total {
+        Some(total) => format!(
+            "{} / {}",
+            format_size(progress.downloaded),
+            format_size(total)
+        ),
+        None => format_size(progress.downloaded),
+    };
+    let speed = progress
+        .speed
+        .map(|speed| format!("{}/s", format_size(speed as u64)))
+        .unwrap_or_else(|| "--".to_string());
+    let eta = progress
+        .eta
+        .map(|secs| format_duration_short(std::time::Duration::from_secs(secs)))
+        .unwrap_or_else(|| "--".to_string());
+
+    format!("{}, {}, ETA {}", size, speed, eta)
+}
+
+/// Construye el comando de yt-dlp para una descarga
+fn build_command(config: &DownloadConfig) -> Command {
     let mut cmd = Command::new(config.yt_dlp_path);
 
     // Configurar directorio de salida
@@ -306,22 +511,83 @@ fn execute_download(config: &DownloadConfig) -> Result<()> {
         println!("{} {}", "🍪 Usando archivo de cookies:".cyan(), file);
     }
 
-    // Agregar URL
-    cmd.arg(config.url);
+    // Configurar proxy
+    if let Some(proxy) = config.proxy {
+        cmd.arg("--proxy").arg(proxy);
+        println!("{} {}", "🔀 Usando proxy:".cyan(), proxy);
+    }
 
-    println!("{} {:?}", "Ejecutando:".dimmed(), cmd);
-    println!();
+    // Progreso en líneas con formato propio, ver DownloadProgressBar
+    cmd.arg("--newline")
+        .arg("--progress-template")
+        .arg(PROGRESS_TEMPLATE);
 
-    // Ejecutar comando
-    let status = cmd.status().context("Error al ejecutar yt-dlp")?;
+    // Agregar URL
+    cmd.arg(config.url);
 
-    println!();
+    cmd
+}
 
-    if status.success() {
-        println!("{}", "✓ Descarga completada exitosamente".green().bold());
-    } else {
-        return Err(anyhow!("La descarga falló con código: {}", status));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch_urls_skips_blank_lines_and_comments() {
+        let content = "# videos pendientes\n\
+            https://youtube.com/watch?v=a\n\
+            \n\
+            \t  https://vimeo.com/123  \n\
+            # https://youtube.com/watch?v=skipped\n";
+
+        assert_eq!(
+            parse_batch_urls(content),
+            ["https://youtube.com/watch?v=a", "https://vimeo.com/123"]
+        );
+        assert!(parse_batch_urls("\n# nada\n").is_empty());
     }
 
-    Ok(())
+    #[test]
+    fn test_proxy_is_passed_to_yt_dlp() {
+        let proxy = HttpConfig::default()
+            .with_proxy(Some("http://proxy.corp:3128"))
+            .proxy;
+        let mut config = DownloadConfig {
+            yt_dlp_path: Path::new("yt-dlp"),
+            url: "https://youtube.com/watch?v=a",
+            download_dir: Path::new("videos"),
+            output: None,
+            quality: None,
+            format: None,
+            audio_only: false,
+            no_playlist: false,
+            playlist: false,
+            no_continue: false,
+            cookies_browser: None,
+            cookies_file: None,
+            proxy: proxy.preferred(),
+        };
+        let args = |config: &DownloadConfig| -> Vec<String> {
+            build_command(config)
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect()
+        };
+
+        let with_proxy = args(&config);
+        let pos = with_proxy.iter().position(|arg| arg == "--proxy").unwrap();
+        assert_eq!(with_proxy[pos + 1], "http://proxy.corp:3128");
+        assert_eq!(with_proxy.last().unwrap(), "https://youtube.com/watch?v=a");
+
+        config.proxy = None;
+        assert!(!args(&config).contains(&"--proxy".to_string()));
+
+        let with_progress = args(&config);
+        let pos = with_progress
+            .iter()
+            .position(|arg| arg == "--progress-template")
+            .unwrap();
+        assert_eq!(with_progress[pos + 1], PROGRESS_TEMPLATE);
+        assert!(with_progress.contains(&"--newline".to_string()));
+    }
 }
@@ -1,17 +1,27 @@
+use crate::core::http::{HttpConfig, ProxySettings, RateLimiter};
+use crate::core::validation::{load_default_blacklist, validate_url_not_blacklisted};
 use crate::core::wget::{
-    calculate_local_path_for_url, create_cookie_file, debug_database_info, extract_cookies_from_db,
-    extract_cookies_with_cdp, find_browser_cookie_db, format_cookies, process_html_file_complete,
-    resolve_cookie_path, WgetManager,
+    calculate_flat_local_path, calculate_local_path_for_url, create_cookie_file,
+    debug_database_info, document_base_url, download_resource_via, extract_cookies_from_db,
+    extract_cookies_with_cdp, extract_filename_from_url, find_browser_cookie_db, format_cookies,
+    head_check_via, is_firefox_browser, meta_refresh_target, normalize_extension,
+    process_html_file_with_options, resolve_cookie_path, ExtensionPolicy, PostProcessOptions,
+    SiteProfile, SiteProfiles, WgetManager, DEFAULT_PROFILE,
 };
 use crate::core::{validation, Config};
+use crate::ui::format_size;
+use crate::ui::verbosity::{self, Verbosity};
 use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
 use dialoguer::Input;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashSet, VecDeque};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 use url::Url;
 
 /// Execute post-processing on already downloaded files
@@ -64,11 +74,18 @@ pub fn execute_postprocessing(matches: &clap::ArgMatches) -> Result<()> {
         Url::parse(&format!("https://{}", domain))?
     };
 
+    let profile = resolve_profile(matches)?;
+    let options = resolve_post_process_options(matches);
+    let max_depth = matches
+        .get_one::<usize>("max-depth")
+        .copied()
+        .unwrap_or(DEFAULT_POST_PROCESS_DEPTH);
+
     println!();
 
     // 4. Run post-processing
     println!("{}", "⟳ Procesando archivos HTML...".cyan());
-    post_process_directory(&target_dir, &target_dir, &base_url)?;
+    post_process_directory(&target_dir, &base_url, &profile, &options, max_depth)?;
 
     println!();
     println!(
@@ -82,6 +99,73 @@ pub fn execute_postprocessing(matches: &clap::ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Download a single asset (image, CSS, PDF...) without crawling a page
+pub fn execute_asset(matches: &clap::ArgMatches) -> Result<()> {
+    let url_str = matches
+        .get_one::<String>("url")
+        .context("URL es requerida. Usa: msc wget asset <URL> [DESTINO]")?;
+
+    validation::validate_web_url(url_str).with_context(|| format!("URL inválida: {}", url_str))?;
+
+    match load_default_blacklist() {
+        Ok(blacklist) => validate_url_not_blacklisted(url_str, &blacklist)?,
+        Err(e) => println!(
+            "{} {}",
+            "⚠️  No se pudo cargar blacklist:".yellow(),
+            e.to_string().dimmed()
+        ),
+    }
+
+    let file_name = extract_filename_from_url(url_str);
+    let target_path = match matches.get_one::<String>("dest") {
+        // An existing directory (or a trailing separator) receives the derived name
+        Some(dest) if Path::new(dest).is_dir() || dest.ends_with(['/', '\\']) => {
+            let dir = PathBuf::from(dest);
+            ensure_directory_exists(&dir)?;
+            dir.join(&file_name)
+        }
+        Some(dest) => {
+            let path = PathBuf::from(dest);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                ensure_directory_exists(&parent.to_path_buf())?;
+            }
+            path
+        }
+        None => get_download_directory()?.join(&file_name),
+    };
+
+    if target_path.exists() {
+        println!(
+            "{} {}",
+            "⚠️  El archivo ya existe, no se descarga de nuevo:".yellow(),
+            target_path.display()
+        );
+        return Ok(());
+    }
+
+    println!("{} {}", "🌐 Recurso:".cyan(), url_str);
+    let proxy = matches.get_one::<String>("proxy").map(|s| s.as_str());
+    let saved_path =
+        download_resource_via(url_str, &target_path, proxy, Some("Descargando:"), None)
+            .with_context(|| format!("No se pudo descargar {}", url_str))?;
+
+    let size = fs::metadata(&saved_path).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "{} {} ({})",
+        "✓ Guardado en:".green(),
+        saved_path.display(),
+        format_size(size)
+    );
+    if saved_path != target_path {
+        println!(
+            "{}",
+            "   Extensión corregida según el tipo de contenido".dimmed()
+        );
+    }
+
+    Ok(())
+}
+
 /// Extract cookies from browser for a given URL
 pub fn execute_cookies(matches: &clap::ArgMatches) -> Result<()> {
     println!();
@@ -141,7 +225,17 @@ pub fn execute_cookies(matches: &clap::ArgMatches) -> Result<()> {
     // 4. Extract cookies (with CDP support for Chrome 127+)
     println!("{}", "⟳ Extrayendo cookies...".cyan());
 
-    let cookies = if use_cdp || auto_launch {
+    let cookies = if is_firefox_browser(browser) {
+        // Firefox stores unencrypted cookies in cookies.sqlite; CDP is Chromium-only
+        if use_cdp || auto_launch {
+            println!(
+                "{}",
+                "⚠️  --cdp y --auto-launch solo aplican a navegadores Chromium, se ignoran"
+                    .yellow()
+            );
+        }
+        extract_cookies_from_db(&cookie_db_path, domain)?
+    } else if use_cdp || auto_launch {
         // Use async runtime for CDP extraction
         tokio::runtime::Runtime::new()?.block_on(extract_cookies_with_cdp(
             domain,
@@ -223,6 +317,103 @@ pub fn execute_cookies(matches: &clap::ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Handle 'wget reject-types add' - Never download an extension
+pub fn execute_reject_types_add(matches: &clap::ArgMatches) -> Result<()> {
+    let extension = matches
+        .get_one::<String>("extension")
+        .context("La extensión es requerida")?;
+    let extension = normalize_extension(extension);
+    if extension.is_empty() {
+        return Err(anyhow!("Extensión inválida"));
+    }
+
+    let mut config = Config::load()?;
+    if config.add_rejected_extension(&extension) {
+        config.save()?;
+        println!(
+            "{}",
+            "✓ Extensión añadida a la lista de rechazo:".green().bold()
+        );
+        println!("  .{}", extension.cyan());
+        println!(
+            "{}",
+            "No se descargarán archivos con esta extensión (usa --accept para permitirla puntualmente)."
+                .dimmed()
+        );
+    } else {
+        println!(
+            "{}",
+            "La extensión ya está en la lista de rechazo.".yellow()
+        );
+        println!("  .{}", extension.cyan());
+    }
+
+    Ok(())
+}
+
+/// Handle 'wget reject-types list' - Show the rejected extensions
+pub fn execute_reject_types_list(_matches: &clap::ArgMatches) -> Result<()> {
+    let config = Config::load()?;
+    let extensions = config.get_rejected_extensions();
+
+    println!("{}", "═".repeat(50).white());
+    println!("{}", "  TIPOS DE ARCHIVO RECHAZADOS  ".white().bold());
+    println!("{}", "═".repeat(50).white());
+    println!();
+
+    if extensions.is_empty() {
+        println!("  {}", "Ninguna extensión rechazada.".dimmed());
+    } else {
+        println!(
+            "{}",
+            "Extensiones que wget nunca descarga (páginas ni recursos):".white()
+        );
+        println!();
+        for (index, extension) in extensions.iter().enumerate() {
+            println!(
+                "  {}. .{}",
+                (index + 1).to_string().dimmed(),
+                extension.cyan()
+            );
+        }
+    }
+
+    if config.rejected_extensions.is_none() {
+        println!();
+        println!("{}", "Lista por defecto (aún no modificada).".dimmed());
+    }
+
+    Ok(())
+}
+
+/// Handle 'wget reject-types remove' - Allow an extension again
+pub fn execute_reject_types_remove(matches: &clap::ArgMatches) -> Result<()> {
+    let extension = matches
+        .get_one::<String>("extension")
+        .context("La extensión es requerida")?;
+    let extension = normalize_extension(extension);
+
+    let mut config = Config::load()?;
+    if config.remove_rejected_extension(&extension) {
+        config.save()?;
+        println!(
+            "{}",
+            "✓ Extensión eliminada de la lista de rechazo:"
+                .green()
+                .bold()
+        );
+        println!("  .{}", extension.cyan());
+    } else {
+        println!(
+            "{}",
+            "La extensión no está en la lista de rechazo.".yellow()
+        );
+        println!("  .{}", extension.cyan());
+    }
+
+    Ok(())
+}
+
 /// Execute the wget command to download web pages
 pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     // 1. Extract arguments
@@ -234,12 +425,24 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     let mirror_all = matches.get_flag("all");
     let pattern = matches.get_one::<String>("pattern").map(|s| s.as_str());
     let exclude = matches.get_one::<String>("exclude").map(|s| s.as_str());
+    let base_path = matches.get_one::<String>("base-path").map(|s| s.as_str());
+    let jobs = matches.get_one::<usize>("jobs").copied().unwrap_or(1);
+    let resume = matches.get_flag("resume");
     let limit = matches.get_one::<usize>("limit").copied();
     let cookies = matches.get_one::<String>("cookies").map(|s| s.as_str());
+    let do_head_check = matches.get_flag("head-check");
 
     // 2. Validate URL
     validation::validate_web_url(url_str).with_context(|| format!("URL inválida: {}", url_str))?;
 
+    let profile = resolve_profile(matches)?;
+    let post_process = resolve_post_process_options(matches);
+    let proxy = resolve_proxy(matches);
+
+    if do_head_check {
+        run_head_check(url_str, post_process.proxy.as_deref());
+    }
+
     // 3. Ensure wget is installed
     let mut manager = WgetManager::new()?;
     let wget_path = manager.ensure_wget()?;
@@ -260,13 +463,27 @@ pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     if mirror_all {
         let mut crawler = Crawler::new(
             url_str, target_dir, wget_path, pattern, exclude, limit, cookies,
-        )?;
-        crawler.run()?;
+        )?
+        .with_profile(profile)
+        .with_post_process_options(post_process)
+        .with_base_path(base_path)
+        .with_jobs(jobs)
+        .with_resume(resume)
+        .with_proxy(proxy);
+        crawler.run()?.print_summary();
     } else {
-        execute_download(&wget_path, url_str, &target_dir, false, cookies)?;
+        execute_download(
+            &wget_path,
+            url_str,
+            &target_dir,
+            false,
+            cookies,
+            &proxy,
+            &post_process.extension_policy,
+        )?;
         // Post-processing for single page
         println!("{}", "⟳ Procesando HTML para uso offline...".cyan());
-        if let Err(e) = process_downloaded_page(url_str, &target_dir) {
+        if let Err(e) = process_downloaded_page(url_str, &target_dir, &profile, &post_process) {
             println!(
                 "{}",
                 format!("⚠️  Error durante el post-procesamiento: {}", e).yellow()
@@ -285,9 +502,53 @@ struct Crawler {
     queue: VecDeque<String>,
     pattern_regex: Option<regex::Regex>,
     exclude_regex: Option<regex::Regex>,
+    /// Only crawl URLs whose path starts with this prefix
+    base_path: Option<String>,
     limit: Option<usize>,
     downloaded_count: usize,
     cookie_file: Option<PathBuf>,
+    profile: SiteProfile,
+    post_process: PostProcessOptions,
+    /// Number of pages downloaded concurrently
+    jobs: usize,
+    /// Continue from the state saved by an interrupted crawl
+    resume: bool,
+    /// Proxies passed to wget
+    proxy: ProxySettings,
+    /// Size of the HTML pages downloaded by wget
+    page_bytes: AtomicU64,
+}
+
+/// What a `--all` crawl did, for the final summary and tests
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct CrawlReport {
+    /// Pages downloaded in this run (resumed pages are not counted again)
+    pages_downloaded: usize,
+    pages_failed: usize,
+    /// New URLs queued from the downloaded pages
+    links_discovered: usize,
+    /// Resources saved by post-processing (images, CDN files...)
+    assets_downloaded: usize,
+    /// Size of the downloaded pages plus the post-processing resources
+    bytes: u64,
+}
+
+impl CrawlReport {
+    fn print_summary(&self) {
+        println!();
+        println!("{}", "📊 Resumen del rastreo".cyan().bold());
+        println!("   Páginas descargadas:  {}", self.pages_downloaded);
+        if self.pages_failed > 0 {
+            println!(
+                "   Páginas fallidas:     {}",
+                self.pages_failed.to_string().yellow()
+            );
+        }
+        println!("   Enlaces descubiertos: {}", self.links_discovered);
+        println!("   Recursos descargados: {}", self.assets_downloaded);
+        println!("   Datos descargados:    {}", format_size(self.bytes));
+        println!();
+    }
 }
 
 impl Crawler {
@@ -361,13 +622,70 @@ impl Crawler {
             queue,
             pattern_regex,
             exclude_regex,
+            base_path: None,
             limit,
             downloaded_count: 0,
             cookie_file,
+            profile: SiteProfile::default(),
+            post_process: PostProcessOptions::default(),
+            jobs: 1,
+            resume: false,
+            proxy: ProxySettings::default(),
+            page_bytes: AtomicU64::new(0),
         })
     }
 
-    fn run(&mut self) -> Result<()> {
+    /// Use a site profile for post-processing instead of the default one
+    fn with_profile(mut self, profile: SiteProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Asset options used when post-processing the downloaded pages
+    fn with_post_process_options(mut self, options: PostProcessOptions) -> Self {
+        self.post_process = options;
+        self
+    }
+
+    /// Restrict the crawl to URLs under a path prefix (e.g. `/docs/`)
+    fn with_base_path(mut self, base_path: Option<&str>) -> Self {
+        self.base_path = base_path.map(|prefix| {
+            let prefix = prefix.trim();
+            if prefix.starts_with('/') {
+                prefix.to_string()
+            } else {
+                format!("/{}", prefix)
+            }
+        });
+
+        if let Some(ref prefix) = self.base_path {
+            println!("{} {}", "📂 Ruta base:".cyan(), prefix);
+        }
+        self
+    }
+
+    /// Download up to `jobs` pages at the same time
+    fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        if self.jobs > 1 {
+            println!("{} {}", "⚙️  Descargas paralelas:".cyan(), self.jobs);
+        }
+        self
+    }
+
+    /// Load the saved crawl state instead of starting from the start URL
+    fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Route wget's page downloads through these proxies
+    fn with_proxy(mut self, proxy: ProxySettings) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    fn run(&mut self) -> Result<CrawlReport> {
         println!();
         println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
         println!("{}", "  Iniciando Crawler Inteligente".cyan().bold());
@@ -377,102 +695,41 @@ impl Crawler {
         println!();
 
         // FASE 1: Crawling - Descargar todo sin modificar hrefs todavía
-        while let Some(url) = self.queue.pop_front() {
-            // Check limit before processing
-            if let Some(limit) = self.limit {
-                if self.downloaded_count >= limit {
-                    println!();
-                    println!(
-                        "{}",
-                        format!("🛑 Límite alcanzado: {} páginas descargadas", limit)
-                            .yellow()
-                            .bold()
-                    );
-                    println!(
-                        "{}",
-                        format!(
-                            "   {} páginas restantes en cola no serán procesadas",
-                            self.queue.len()
-                        )
-                        .dimmed()
-                    );
-                    break;
-                }
-            }
-
-            if self.visited.contains(&url) {
-                continue;
-            }
-
-            // Display progress with limit if applicable
-            if let Some(limit) = self.limit {
+        let state_path = CrawlState::path(&self.target_dir);
+        let frontier = match self.load_saved_state(&state_path)? {
+            Some(state) => Frontier::resume(state, self.limit),
+            None => Frontier::new(std::mem::take(&mut self.queue), self.limit),
+        }
+        .with_checkpoint(Checkpoint {
+            path: state_path.clone(),
+            start_url: self.base_url.to_string(),
+            every: CRAWL_STATE_INTERVAL,
+        });
+        crawl_concurrently(&frontier, self.jobs, |url, slot| self.visit_page(url, slot));
+
+        let mut report = frontier.report();
+        let (visited, remaining) = frontier.finish();
+        self.visited = visited;
+        self.downloaded_count = self.visited.len();
+
+        if let Some(limit) = self.limit {
+            if self.downloaded_count >= limit && remaining > 0 {
                 println!(
-                    "{} {} [{}/{}]",
-                    "⬇️  Descargando:".green(),
-                    url,
-                    self.downloaded_count + 1,
-                    limit
+                    "{}",
+                    format!("🛑 Límite alcanzado: {} páginas descargadas", limit)
+                        .yellow()
+                        .bold()
                 );
-            } else {
-                println!("{} {}", "⬇️  Descargando:".green(), url);
-            }
-
-            // Download the page
-            if let Err(e) = self.download_page(&url) {
-                log::warn!("Failed to download {}: {}", url, e);
                 println!(
                     "{}",
-                    format!("⚠️  Falló la descarga de {}: {}", url, e).yellow()
+                    format!(
+                        "   {} páginas restantes en cola no serán procesadas",
+                        remaining
+                    )
+                    .dimmed()
                 );
-                continue; // Skip processing if download failed
-            }
-
-            self.visited.insert(url.clone());
-            self.downloaded_count += 1;
-
-            // Extract links only (don't modify hrefs yet)
-            println!("   {}", "⟳ Extrayendo enlaces...".dimmed());
-            match self.extract_links(&url) {
-                Ok(new_links) => {
-                    let mut matched_count = 0;
-                    let mut filtered_count = 0;
-
-                    for link in new_links {
-                        if !self.visited.contains(&link) && !self.queue.contains(&link) {
-                            // Apply pattern filter if specified
-                            if self.should_crawl_url(&link) {
-                                self.queue.push_back(link);
-                                matched_count += 1;
-                            } else {
-                                filtered_count += 1;
-                            }
-                        }
-                    }
-
-                    if matched_count > 0 {
-                        println!(
-                            "   {}",
-                            format!("✓ {} enlaces agregados a la cola", matched_count)
-                                .green()
-                                .dimmed()
-                        );
-                    }
-                    if filtered_count > 0 && self.pattern_regex.is_some() {
-                        println!(
-                            "   {}",
-                            format!("⊘ {} enlaces filtrados por patrón", filtered_count).dimmed()
-                        );
-                    }
-                }
-                Err(e) => {
-                    log::warn!("Failed to extract links from {}: {}", url, e);
-                    println!(
-                        "{}",
-                        format!("⚠️  Error extrayendo enlaces de {}: {}", url, e).yellow()
-                    );
-                }
+                println!();
             }
-            println!();
         }
 
         println!("{}", "✓ Crawling completado".green().bold());
@@ -489,11 +746,147 @@ impl Crawler {
         );
         self.post_process_all_files()?;
 
+        // The crawl finished, nothing left to resume
+        if let Err(e) = fs::remove_file(&state_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("Failed to remove crawl state {:?}: {}", state_path, e);
+            }
+        }
+
         println!("{}", "✓ Post-procesamiento finalizado".green().bold());
-        Ok(())
+
+        let assets = &self.post_process.download_stats;
+        report.assets_downloaded = assets.files();
+        report.bytes = self.page_bytes.load(Ordering::Relaxed) + assets.bytes();
+        Ok(report)
+    }
+
+    /// Saved state to resume from, if `--resume` was given and a state file exists
+    fn load_saved_state(&self, path: &Path) -> Result<Option<CrawlState>> {
+        if !self.resume {
+            if path.exists() {
+                println!(
+                    "{}",
+                    "💡 Hay un rastreo anterior sin terminar; usa --resume para continuarlo"
+                        .dimmed()
+                );
+            }
+            return Ok(None);
+        }
+
+        let Some(state) = CrawlState::load(path)? else {
+            println!(
+                "{}",
+                "⚠️  No hay un rastreo guardado, se inicia desde el principio".yellow()
+            );
+            return Ok(None);
+        };
+
+        if state.start_url != self.base_url.as_str() {
+            return Err(anyhow!(
+                "El rastreo guardado en {} corresponde a {}, no a {}",
+                path.display(),
+                state.start_url,
+                self.base_url
+            ));
+        }
+
+        println!(
+            "{} {} páginas descargadas, {} en cola",
+            "⏯️  Reanudando rastreo:".cyan(),
+            state.downloaded_count,
+            state.queue.len()
+        );
+        println!();
+        Ok(Some(state))
+    }
+
+    /// Download one page and return the links to crawl next (`None` if the download failed)
+    ///
+    /// `slot` is the page's position within the download budget, for display.
+    fn visit_page(&self, url: &str, slot: usize) -> Option<Vec<String>> {
+        // Display progress with limit if applicable
+        if let Some(limit) = self.limit {
+            println!(
+                "{} {} [{}/{}]",
+                "⬇️  Descargando:".green(),
+                url,
+                slot,
+                limit
+            );
+        } else {
+            println!("{} {}", "⬇️  Descargando:".green(), url);
+        }
+
+        if let Err(e) = self.download_page(url) {
+            log::warn!("Failed to download {}: {}", url, e);
+            println!(
+                "{}",
+                format!("⚠️  Falló la descarga de {}: {}", url, e).yellow()
+            );
+            return None;
+        }
+        self.page_bytes
+            .fetch_add(self.local_page_size(url), Ordering::Relaxed);
+
+        // Extract links only (don't modify hrefs yet)
+        println!("   {}", "⟳ Extrayendo enlaces...".dimmed());
+        let links = match self.extract_links(url) {
+            Ok(links) => links,
+            Err(e) => {
+                log::warn!("Failed to extract links from {}: {}", url, e);
+                println!(
+                    "{}",
+                    format!("⚠️  Error extrayendo enlaces de {}: {}", url, e).yellow()
+                );
+                return Some(Vec::new());
+            }
+        };
+
+        // Apply pattern filter if specified
+        let (accepted, filtered): (Vec<String>, Vec<String>) = links
+            .into_iter()
+            .partition(|link| self.should_crawl_url(link));
+
+        if !filtered.is_empty() && (self.pattern_regex.is_some() || self.base_path.is_some()) {
+            println!(
+                "   {}",
+                format!("⊘ {} enlaces filtrados por patrón", filtered.len()).dimmed()
+            );
+        }
+
+        Some(accepted)
+    }
+
+    /// Size of the file wget saved for `url` (0 if it cannot be found)
+    fn local_page_size(&self, url: &str) -> u64 {
+        Url::parse(url)
+            .ok()
+            .and_then(|url| calculate_local_path_for_url(&url, &self.target_dir))
+            .and_then(|path| fs::metadata(path).ok())
+            .map_or(0, |metadata| metadata.len())
     }
 
     fn download_page(&self, url: &str) -> Result<()> {
+        let status = self
+            .page_command(url)
+            .status()
+            .context("Error al ejecutar wget")?;
+
+        if !status.success() {
+            // Check for 404/403 but allow continuation if assets failed
+            // wget exit code 8 is server error
+            if let Some(code) = status.code() {
+                if code != 8 {
+                    return Err(anyhow!("wget exited with code {}", code));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// wget invocation that downloads one page with its requisites
+    fn page_command(&self, url: &str) -> Command {
         let mut cmd = Command::new(&self.wget_path);
 
         cmd.arg("--page-requisites") // Download assets
@@ -507,46 +900,21 @@ impl Crawler {
             cmd.arg("--load-cookies").arg(cookie_file);
         }
 
+        cmd.args(wget_proxy_args(&self.proxy));
+        cmd.args(wget_reject_args(&self.post_process.extension_policy));
         cmd.arg(url);
 
         // Note: We do NOT use --convert-links here because we want to rewrite them ourselves
         // and wget's conversion might conflict with our logic or be incomplete for future pages.
         // We do NOT use -nd (no-directories) because we want to preserve structure for the crawler.
-
-        let status = cmd.status().context("Error al ejecutar wget")?;
-
-        if !status.success() {
-            // Check for 404/403 but allow continuation if assets failed
-            // wget exit code 8 is server error
-            if let Some(code) = status.code() {
-                if code != 8 {
-                    return Err(anyhow!("wget exited with code {}", code));
-                }
-            }
-        }
-        Ok(())
+        cmd
     }
 
     fn extract_links(&self, url: &str) -> Result<Vec<String>> {
         // Determine the local file path for this URL
         let url_parsed = Url::parse(url)?;
-
-        let domain = url_parsed.domain().unwrap_or("unknown");
-        let path = url_parsed.path();
-
-        let mut local_path = self.target_dir.join(domain);
-        if path == "/" || path.is_empty() {
-            local_path.push("index.html");
-        } else {
-            let rel_path = path.trim_start_matches('/');
-            local_path.push(rel_path);
-
-            if path.ends_with('/') {
-                local_path.push("index.html");
-            } else if !path.ends_with(".html") && !path.ends_with(".htm") {
-                local_path.set_extension("html");
-            }
-        }
+        let local_path = calculate_local_path_for_url(&url_parsed, &self.target_dir)
+            .with_context(|| format!("URL sin dominio: {}", url))?;
 
         if !local_path.exists() {
             return Err(anyhow!("Local file not found: {}", local_path.display()));
@@ -580,14 +948,18 @@ impl Crawler {
                             .extension()
                             .is_some_and(|ext| ext == "html" || ext == "htm")
                         {
-                            println!(
-                                "   {}",
-                                format!("⟳ Procesando {}", local_path.display()).dimmed()
-                            );
-                            if let Err(e) = process_html_file_complete(
+                            if verbosity::enabled(Verbosity::Normal) {
+                                println!(
+                                    "   {}",
+                                    format!("⟳ Procesando {}", local_path.display()).dimmed()
+                                );
+                            }
+                            if let Err(e) = process_html_file_with_options(
                                 &local_path,
                                 &self.target_dir,
                                 &self.base_url,
+                                &self.profile,
+                                &self.post_process,
                             ) {
                                 println!(
                                     "   {}",
@@ -603,7 +975,7 @@ impl Crawler {
         Ok(())
     }
 
-    /// Check if a URL should be crawled based on the pattern filter and exclusion rules
+    /// Check if a URL should be crawled based on the base path, pattern filter and exclusion rules
     fn should_crawl_url(&self, url: &str) -> bool {
         // First, check if URL matches exclusion pattern (if provided)
         if let Some(ref exclude) = self.exclude_regex {
@@ -612,24 +984,460 @@ impl Crawler {
             }
         }
 
+        // Never crawl rejected file types (.exe, .iso...)
+        if !self.post_process.extension_policy.allows(url) {
+            return false;
+        }
+
         // Parse URL to get the path
         let Ok(parsed_url) = Url::parse(url) else {
             return false;
         };
 
+        // Get the path (e.g., "/posts/test-article")
+        let path = parsed_url.path();
+
+        // Stay under --base-path, independently of --pattern. Compared by
+        // segment so that /docs does not also match /docsify
+        if let Some(ref prefix) = self.base_path {
+            if !Path::new(path).starts_with(prefix) {
+                return false;
+            }
+        }
+
         // If no inclusion pattern specified, accept all URLs (that weren't excluded)
         let Some(ref pattern) = self.pattern_regex else {
             return true;
         };
 
-        // Get the path (e.g., "/posts/test-article")
-        let path = parsed_url.path();
-
         // Check if path matches the inclusion regex pattern
         pattern.is_match(path)
     }
 }
 
+/// File in the target directory holding the state of an unfinished crawl
+const CRAWL_STATE_FILE: &str = ".msc-crawl-state.json";
+
+/// Completed pages between two saves of the crawl state
+const CRAWL_STATE_INTERVAL: usize = 10;
+
+/// Progress of a crawl, saved periodically so `--resume` can continue it
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct CrawlState {
+    start_url: String,
+    /// URLs downloaded successfully
+    visited: Vec<String>,
+    /// URLs still to download, including those in flight when saved
+    queue: Vec<String>,
+    downloaded_count: usize,
+}
+
+impl CrawlState {
+    fn path(target_dir: &Path) -> PathBuf {
+        target_dir.join(CRAWL_STATE_FILE)
+    }
+
+    /// Read the state file, `None` if it does not exist
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read crawl state from {:?}", path))?;
+        let state = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid crawl state in {:?}", path))?;
+        Ok(Some(state))
+    }
+
+    /// Write the state through a temporary file so an interruption never leaves it half-written
+    fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content)
+            .with_context(|| format!("Failed to write crawl state to {:?}", temp_path))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write crawl state to {:?}", path))?;
+        Ok(())
+    }
+}
+
+/// Where and how often the frontier saves its [`CrawlState`]
+struct Checkpoint {
+    path: PathBuf,
+    start_url: String,
+    every: usize,
+}
+
+/// Crawl queue shared by the download workers
+///
+/// URLs are claimed before being downloaded, so concurrent workers never
+/// fetch the same page twice. With a limit, each claim also reserves one of
+/// the remaining page slots; a failed download gives its slot back.
+struct Frontier {
+    state: Mutex<FrontierState>,
+    changed: Condvar,
+    limit: Option<usize>,
+    checkpoint: Option<Checkpoint>,
+}
+
+#[derive(Default)]
+struct FrontierState {
+    queue: VecDeque<String>,
+    /// Every URL ever queued (pending, in flight or attempted)
+    seen: HashSet<String>,
+    /// URLs downloaded successfully
+    visited: HashSet<String>,
+    /// URLs claimed by a worker and not completed yet
+    in_flight: HashSet<String>,
+    /// Pages downloaded since this frontier was created
+    downloaded: usize,
+    failed: usize,
+    /// Links queued from downloaded pages
+    discovered: usize,
+    /// A worker panicked: the others stop claiming URLs
+    aborted: bool,
+}
+
+impl Frontier {
+    fn new(start: impl IntoIterator<Item = String>, limit: Option<usize>) -> Self {
+        let mut state = FrontierState::default();
+        for url in start {
+            if state.seen.insert(url.clone()) {
+                state.queue.push_back(url);
+            }
+        }
+
+        Self {
+            state: Mutex::new(state),
+            changed: Condvar::new(),
+            limit,
+            checkpoint: None,
+        }
+    }
+
+    /// Continue a saved crawl: its visited URLs are not downloaded again
+    fn resume(saved: CrawlState, limit: Option<usize>) -> Self {
+        let frontier = Self::new(saved.queue, limit);
+        {
+            let mut state = frontier.lock();
+            for url in saved.visited {
+                state.seen.insert(url.clone());
+                state.visited.insert(url);
+            }
+        }
+        frontier
+    }
+
+    /// Save the crawl state every `checkpoint.every` downloaded pages
+    fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FrontierState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Claim the next URL and its slot number (1-based), waiting while other
+    /// workers may still queue links or release slots
+    ///
+    /// Returns `None` once the queue is exhausted or the limit is reached.
+    fn claim(&self) -> Option<(String, usize)> {
+        let mut state = self.lock();
+        loop {
+            if state.aborted {
+                return None;
+            }
+
+            let reserved = state.visited.len() + state.in_flight.len();
+            let has_budget = self.limit.is_none_or(|limit| reserved < limit);
+
+            if has_budget {
+                if let Some(url) = state.queue.pop_front() {
+                    state.in_flight.insert(url.clone());
+                    return Some((url, reserved + 1));
+                }
+            }
+
+            if state.in_flight.is_empty() {
+                return None;
+            }
+
+            state = self.changed.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Record a finished download and queue its unseen links, returning how many were queued
+    fn complete(&self, url: &str, links: Option<Vec<String>>) -> usize {
+        let mut state = self.lock();
+        state.in_flight.remove(url);
+
+        let mut queued = 0;
+        if let Some(links) = links {
+            state.visited.insert(url.to_string());
+            state.downloaded += 1;
+            for link in links {
+                if state.seen.insert(link.clone()) {
+                    state.queue.push_back(link);
+                    queued += 1;
+                }
+            }
+            state.discovered += queued;
+
+            if let Some(ref checkpoint) = self.checkpoint {
+                if state.visited.len().is_multiple_of(checkpoint.every.max(1)) {
+                    let saved = state.snapshot(&checkpoint.start_url);
+                    if let Err(e) = saved.save(&checkpoint.path) {
+                        log::warn!("Failed to save crawl state: {}", e);
+                    }
+                }
+            }
+        } else {
+            state.failed += 1;
+        }
+
+        self.changed.notify_all();
+        queued
+    }
+
+    /// Stop every worker; the URL a panicking worker held is never completed
+    fn abort(&self) {
+        self.lock().aborted = true;
+        self.changed.notify_all();
+    }
+
+    /// Page and link counts so far (asset counts are filled in by the crawler)
+    fn report(&self) -> CrawlReport {
+        let state = self.lock();
+        CrawlReport {
+            pages_downloaded: state.downloaded,
+            pages_failed: state.failed,
+            links_discovered: state.discovered,
+            ..CrawlReport::default()
+        }
+    }
+
+    /// Downloaded URLs and the number of URLs left in the queue
+    fn finish(self) -> (HashSet<String>, usize) {
+        let state = self.state.into_inner().unwrap_or_else(|e| e.into_inner());
+        (state.visited, state.queue.len())
+    }
+}
+
+impl FrontierState {
+    /// Current progress; in-flight URLs go back to the front of the queue
+    fn snapshot(&self, start_url: &str) -> CrawlState {
+        let mut visited: Vec<String> = self.visited.iter().cloned().collect();
+        visited.sort();
+        let mut queue: Vec<String> = self.in_flight.iter().cloned().collect();
+        queue.sort();
+        queue.extend(self.queue.iter().cloned());
+
+        CrawlState {
+            start_url: start_url.to_string(),
+            downloaded_count: visited.len(),
+            visited,
+            queue,
+        }
+    }
+}
+
+/// Aborts the frontier when a worker unwinds, so the others don't wait forever
+struct AbortOnPanic<'a>(&'a Frontier);
+
+impl Drop for AbortOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.abort();
+        }
+    }
+}
+
+/// Run `visit` on queued URLs with up to `jobs` worker threads until the frontier is exhausted
+///
+/// `visit` returns the links to queue, or `None` when the page could not be
+/// downloaded. A panic in `visit` stops the other workers and is propagated.
+fn crawl_concurrently<F>(frontier: &Frontier, jobs: usize, visit: F)
+where
+    F: Fn(&str, usize) -> Option<Vec<String>> + Sync,
+{
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| {
+                let _abort = AbortOnPanic(frontier);
+                while let Some((url, slot)) = frontier.claim() {
+                    let queued = frontier.complete(&url, visit(&url, slot));
+                    if queued > 0 {
+                        println!(
+                            "   {}",
+                            format!("✓ {} enlaces agregados a la cola", queued)
+                                .green()
+                                .dimmed()
+                        );
+                    }
+                    println!();
+                }
+            });
+        }
+    });
+}
+
+/// Resolve the `--profile` argument (built-in or from wget_profiles.json)
+fn resolve_profile(matches: &clap::ArgMatches) -> Result<SiteProfile> {
+    let name = matches
+        .get_one::<String>("profile")
+        .map(|s| s.as_str())
+        .unwrap_or(DEFAULT_PROFILE);
+
+    let profiles = SiteProfiles::load()?;
+    let profile = profiles.get(name)?.clone();
+
+    if name != DEFAULT_PROFILE {
+        println!(
+            "{} {} {}",
+            "🧩 Perfil:".cyan(),
+            name,
+            format!("({})", profile.description).dimmed()
+        );
+    }
+
+    Ok(profile)
+}
+
+/// Asset options shared by the download and `postprocessing` commands
+fn resolve_post_process_options(matches: &clap::ArgMatches) -> PostProcessOptions {
+    let defaults = PostProcessOptions::default();
+    PostProcessOptions {
+        keep_query_strings: matches.get_flag("keep-query-strings"),
+        concurrency: matches
+            .get_one::<usize>("concurrency")
+            .copied()
+            .unwrap_or(defaults.concurrency)
+            .max(1),
+        proxy: matches.get_one::<String>("proxy").cloned(),
+        rate_limit: matches.get_one::<u64>("rate-limit").map(|&bytes_per_sec| {
+            println!(
+                "{} {}/s",
+                "🐢 Límite de velocidad:".cyan(),
+                format_size(bytes_per_sec)
+            );
+            Arc::new(RateLimiter::new(bytes_per_sec))
+        }),
+        extension_policy: resolve_extension_policy(matches),
+        ..defaults
+    }
+}
+
+/// Rejected extensions from the config, adjusted by `--accept`/`--reject`
+fn resolve_extension_policy(matches: &clap::ArgMatches) -> ExtensionPolicy {
+    let values = |id: &str| -> Vec<String> {
+        matches
+            .get_many::<String>(id)
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default()
+    };
+    let (accept, reject) = (values("accept"), values("reject"));
+
+    let rejected = Config::load()
+        .map(|config| config.get_rejected_extensions())
+        .unwrap_or_else(|_| Config::default().get_rejected_extensions());
+    let policy = ExtensionPolicy::new(rejected).with_overrides(&accept, &reject);
+
+    if !accept.is_empty() || !reject.is_empty() {
+        let rejected: Vec<&str> = policy.rejected().collect();
+        println!(
+            "{} {}",
+            "🚫 Tipos rechazados:".cyan(),
+            if rejected.is_empty() {
+                "ninguno".to_string()
+            } else {
+                rejected.join(", ")
+            }
+        );
+    }
+    policy
+}
+
+/// Proxies for wget: `--proxy` if given, otherwise `HTTP(S)_PROXY`
+fn resolve_proxy(matches: &clap::ArgMatches) -> ProxySettings {
+    let explicit = matches.get_one::<String>("proxy").map(|s| s.as_str());
+    let proxy = HttpConfig::from_env().with_proxy(explicit).proxy;
+
+    if let Some(url) = proxy.preferred() {
+        println!("{} {}", "🔀 Proxy:".cyan(), url);
+    }
+    proxy
+}
+
+/// wget `-e` options that route its requests through `proxy` (none without a proxy)
+fn wget_proxy_args(proxy: &ProxySettings) -> Vec<String> {
+    if proxy.is_empty() {
+        return Vec::new();
+    }
+
+    let mut settings = vec!["use_proxy=yes".to_string()];
+    if let Some(ref url) = proxy.http {
+        settings.push(format!("http_proxy={}", url));
+    }
+    if let Some(ref url) = proxy.https {
+        settings.push(format!("https_proxy={}", url));
+    }
+    if let Some(ref hosts) = proxy.no_proxy {
+        settings.push(format!("no_proxy={}", hosts));
+    }
+
+    settings
+        .into_iter()
+        .flat_map(|setting| ["-e".to_string(), setting])
+        .collect()
+}
+
+/// wget `--reject` option for the rejected extensions (none when nothing is rejected)
+fn wget_reject_args(policy: &ExtensionPolicy) -> Vec<String> {
+    if policy.is_empty() {
+        return Vec::new();
+    }
+
+    vec![
+        "--reject".to_string(),
+        policy.rejected().collect::<Vec<_>>().join(","),
+    ]
+}
+
+/// Pre-validate the target with a HEAD request, printing warnings without aborting
+fn run_head_check(url: &str, proxy: Option<&str>) {
+    println!("{}", "⟳ Verificando URL (HEAD)...".cyan());
+
+    match head_check_via(url, proxy) {
+        Ok(report) => {
+            let warnings = report.warnings();
+            if warnings.is_empty() {
+                println!(
+                    "{}",
+                    format!(
+                        "✓ URL accesible ({} {})",
+                        report.status,
+                        report.content_type.as_deref().unwrap_or("")
+                    )
+                    .green()
+                );
+            } else {
+                for warning in warnings {
+                    println!("{}", format!("⚠️  {}", warning).yellow());
+                }
+            }
+        }
+        Err(e) => {
+            println!(
+                "{}",
+                format!("⚠️  No se pudo verificar la URL: {}", e).yellow()
+            );
+        }
+    }
+    println!();
+}
+
 /// Get the download directory (interactive if not configured)
 fn get_download_directory() -> Result<PathBuf> {
     let mut config = Config::load()?;
@@ -770,6 +1578,8 @@ fn execute_download(
     target_dir: &Path,
     _mirror_all: bool,
     cookies: Option<&str>,
+    proxy: &ProxySettings,
+    extension_policy: &ExtensionPolicy,
 ) -> Result<()> {
     print_header(url, target_dir, cookies.is_some());
 
@@ -794,6 +1604,12 @@ fn execute_download(
         cmd.arg("--load-cookies").arg(path);
     }
 
+    // Proxy
+    cmd.args(wget_proxy_args(proxy));
+
+    // Tipos de archivo rechazados
+    cmd.args(wget_reject_args(extension_policy));
+
     cmd.arg(url);
 
     println!("{} {:?}", "Ejecutando:".dimmed(), cmd);
@@ -891,6 +1707,7 @@ fn handle_download_failure(url: &str, target_dir: &Path, code: i32) -> Result<()
 fn extract_links_from_html(file_path: &PathBuf, base_url: &Url) -> Result<Vec<String>> {
     let content = fs::read_to_string(file_path)?;
     let document = scraper::Html::parse_document(&content);
+    let document_base = document_base_url(&document, base_url);
     let mut extracted_links = Vec::new();
 
     // 1. Extract links from <a> tags
@@ -900,7 +1717,7 @@ fn extract_links_from_html(file_path: &PathBuf, base_url: &Url) -> Result<Vec<St
     for element in document.select(&selector) {
         if let Some(url_str) = element.value().attr("href") {
             // Resolve URL
-            if let Ok(resolved_url) = base_url.join(url_str) {
+            if let Ok(resolved_url) = document_base.join(url_str) {
                 // Check if it's in scope (same domain)
                 if resolved_url.domain() == base_url.domain() {
                     extracted_links.push(resolved_url.to_string());
@@ -909,7 +1726,19 @@ fn extract_links_from_html(file_path: &PathBuf, base_url: &Url) -> Result<Vec<St
         }
     }
 
-    // 2. Extract URLs from JSON patterns in scripts (e.g., "nextUrl":"...", "prevUrl":"...")
+    // 2. Follow <meta http-equiv="refresh"> redirects
+    if let Some(target) = meta_refresh_target(&document) {
+        if let Ok(resolved_url) = document_base.join(&target) {
+            if resolved_url.domain() == base_url.domain() {
+                let url_string = resolved_url.to_string();
+                if !extracted_links.contains(&url_string) {
+                    extracted_links.push(url_string);
+                }
+            }
+        }
+    }
+
+    // 3. Extract URLs from JSON patterns in scripts (e.g., "nextUrl":"...", "prevUrl":"...")
     // Common patterns in manga/manhwa readers and similar sites
     let json_url_patterns = vec![
         r#""nextUrl"\s*:\s*"([^"]+)""#,
@@ -927,7 +1756,7 @@ fn extract_links_from_html(file_path: &PathBuf, base_url: &Url) -> Result<Vec<St
                     let url_str = url_match.as_str();
 
                     // Try to resolve the URL (might be relative or absolute)
-                    if let Ok(resolved_url) = base_url.join(url_str) {
+                    if let Ok(resolved_url) = document_base.join(url_str) {
                         // Check if it's in scope (same domain)
                         if resolved_url.domain() == base_url.domain() {
                             let url_string = resolved_url.to_string();
@@ -945,33 +1774,91 @@ fn extract_links_from_html(file_path: &PathBuf, base_url: &Url) -> Result<Vec<St
     Ok(extracted_links)
 }
 
-/// Post-process directory recursively (for crawler mode after all downloads complete)
-fn post_process_directory(current_dir: &PathBuf, root_dir: &PathBuf, base_url: &Url) -> Result<()> {
-    let entries = fs::read_dir(current_dir)?;
+/// Subdirectory levels walked by `wget postprocessing` when `--max-depth` is not given
+const DEFAULT_POST_PROCESS_DEPTH: usize = 32;
+
+/// Post-process every HTML file under `root_dir`, at most `max_depth` directories deep
+fn post_process_directory(
+    root_dir: &PathBuf,
+    base_url: &Url,
+    profile: &SiteProfile,
+    options: &PostProcessOptions,
+    max_depth: usize,
+) -> Result<()> {
+    for path in collect_html_files(root_dir, max_depth)? {
+        if verbosity::enabled(Verbosity::Normal) {
+            let msg = format!("⟳ Procesando {}", path.display());
+            println!("   {}", msg.dimmed());
+        }
+        process_html_file_with_options(&path, root_dir, base_url, profile, options)?;
+    }
+    Ok(())
+}
+
+/// HTML files under `root_dir`, descending at most `max_depth` directory levels
+///
+/// `assets` and symlinked directories are skipped, and every directory is
+/// entered once by canonical path, so loops in the mirror cannot recurse forever.
+fn collect_html_files(root_dir: &Path, max_depth: usize) -> Result<Vec<PathBuf>> {
+    let mut visited = HashSet::new();
+    let mut html_files = Vec::new();
+    walk_html_files(root_dir, 0, max_depth, &mut visited, &mut html_files)?;
+    Ok(html_files)
+}
+
+fn walk_html_files(
+    dir: &Path,
+    depth: usize,
+    max_depth: usize,
+    visited: &mut HashSet<PathBuf>,
+    html_files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let canonical =
+        fs::canonicalize(dir).with_context(|| format!("No se pudo resolver {}", dir.display()))?;
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
     for entry in entries {
-        let entry = entry?;
         let path = entry.path();
 
         if path.is_dir() {
-            // Skip assets directory
-            if path.file_name().is_some_and(|n| n == "assets") {
+            // Skip assets and symlinked directories
+            if path.file_name().is_some_and(|n| n == "assets") || entry.file_type()?.is_symlink() {
                 continue;
             }
-            post_process_directory(&path, root_dir, base_url)?;
+            if depth >= max_depth {
+                println!(
+                    "   {}",
+                    format!(
+                        "⚠️  Profundidad máxima alcanzada, se omite {}",
+                        path.display()
+                    )
+                    .yellow()
+                );
+                continue;
+            }
+            walk_html_files(&path, depth + 1, max_depth, visited, html_files)?;
         } else if path
             .extension()
             .is_some_and(|ext| ext == "html" || ext == "htm")
         {
-            let msg = format!("⟳ Procesando {}", path.display());
-            println!("   {}", msg.dimmed());
-            process_html_file_complete(&path, root_dir, base_url)?;
+            html_files.push(path);
         }
     }
     Ok(())
 }
 
 /// Process the downloaded page(s) to ensure all links are local and resources are downloaded (single page mode)
-fn process_downloaded_page(original_url: &str, target_dir: &PathBuf) -> Result<()> {
+fn process_downloaded_page(
+    original_url: &str,
+    target_dir: &PathBuf,
+    profile: &SiteProfile,
+    options: &PostProcessOptions,
+) -> Result<()> {
     let base_url = Url::parse(original_url)
         .with_context(|| format!("Invalid URL received: {}", original_url))?;
 
@@ -983,7 +1870,7 @@ fn process_downloaded_page(original_url: &str, target_dir: &PathBuf) -> Result<(
                 "   {}",
                 format!("⟳ Procesando archivo principal: {}", main_file.display()).dimmed()
             );
-            process_html_file_complete(&main_file, target_dir, &base_url)?;
+            process_html_file_with_options(&main_file, target_dir, &base_url, profile, options)?;
         } else {
             // Fallback: if we can't find the specific file, we might warn the user
             // but we explicitly DO NOT want to scan the whole directory to avoid touching other files
@@ -1011,106 +1898,286 @@ fn process_downloaded_page(original_url: &str, target_dir: &PathBuf) -> Result<(
     Ok(())
 }
 
-/// Calculate the local file path for a URL assuming a FLAT directory structure
-/// (used when wget is run with --no-directories)
-fn calculate_flat_local_path(url: &Url, base_dir: &Path) -> Option<PathBuf> {
-    let path = url.path();
-    let query = url.query(); // Get query parameters
-    let mut local_path = base_dir.to_path_buf();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn crawler(pattern: Option<&str>, base_path: Option<&str>) -> Crawler {
+        Crawler::new(
+            "https://example.com/docs/",
+            PathBuf::from("target"),
+            PathBuf::from("wget"),
+            pattern,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+        .with_base_path(base_path)
+    }
 
-    if path == "/" || path.is_empty() {
-        local_path.push("index.html");
-    } else {
-        // Remove leading slash
-        let rel_path = path.trim_start_matches('/');
+    /// Link graph with shared pages, downloaded by a fake `visit` that counts calls
+    fn crawl_fake_site(jobs: usize, limit: Option<usize>) -> (HashSet<String>, Vec<usize>) {
+        let (visited, calls) =
+            crawl_fake_site_from(Frontier::new(vec!["/".to_string()], limit), jobs);
+        (visited, calls.into_values().collect())
+    }
 
-        // In flat mode, we only care about the filename, not the directory structure
-        let mut file_name = if path.ends_with('/') {
-            "index.html".to_string()
-        } else {
-            rel_path
-                .split('/')
-                .next_back()
-                .unwrap_or("index.html")
-                .to_string()
-        };
+    fn crawl_fake_site_from(
+        frontier: Frontier,
+        jobs: usize,
+    ) -> (HashSet<String>, std::collections::HashMap<String, usize>) {
+        use std::collections::HashMap;
+
+        let site: HashMap<&str, Vec<&str>> = HashMap::from([
+            ("/", vec!["/a", "/b", "/c"]),
+            ("/a", vec!["/", "/b", "/d"]),
+            ("/b", vec!["/a", "/c", "/d", "/e"]),
+            ("/c", vec!["/e", "/missing"]),
+            ("/d", vec!["/"]),
+            ("/e", vec!["/a", "/d"]),
+        ]);
+        let calls: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
+
+        crawl_concurrently(&frontier, jobs, |url, _| {
+            *calls.lock().unwrap().entry(url.to_string()).or_default() += 1;
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            site.get(url)
+                .map(|links| links.iter().map(|l| l.to_string()).collect())
+        });
+
+        let (visited, _) = frontier.finish();
+        (visited, calls.into_inner().unwrap())
+    }
 
-        // If there are query parameters, wget converts them to @-notation
-        // Example: "page.php?id=123" becomes "page.php@id=123.html"
-        // Note: wget does NOT remove the original extension, it just appends @query.html
-        if let Some(query_str) = query {
-            // Append query with @ separator (keep the original extension)
-            file_name = format!("{}@{}", file_name, query_str);
+    #[test]
+    fn test_concurrent_crawl_visits_each_url_once() {
+        let expected: HashSet<String> = ["/", "/a", "/b", "/c", "/d", "/e"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        for jobs in [1, 4] {
+            let (visited, calls) = crawl_fake_site(jobs, None);
+            assert_eq!(visited, expected, "jobs = {}", jobs);
+            // 6 pages plus one failed download of /missing, each fetched once
+            assert_eq!(calls.len(), 7);
+            assert!(calls.iter().all(|&count| count == 1), "{:?}", calls);
         }
+    }
 
-        // Handle extension adjustments similar to wget
-        local_path.push(&file_name);
+    #[test]
+    fn test_concurrent_crawl_propagates_worker_panic() {
+        let frontier = Frontier::new(vec!["/".to_string()], None);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            crawl_concurrently(&frontier, 4, |url, _| match url {
+                "/" => Some(vec!["/a".to_string(), "/slow".to_string()]),
+                "/a" => panic!("worker failed"),
+                _ => {
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                    Some(vec!["/b".to_string()])
+                }
+            });
+        }));
+        assert!(result.is_err());
+    }
 
-        // wget's behavior with --adjust-extension:
-        // - If the file has query params, wget ALWAYS adds .html at the end
-        //   Example: "page.php?id=123" -> "page.php@id=123.html"
-        //   Example: "page.html?id=123" -> "page.html@id=123.html"
-        // - If no query params and no extension -> adds .html
-        // - If no query params and non-html extension (like .php) -> adds .html
+    #[test]
+    fn test_concurrent_crawl_respects_limit() {
+        let (visited, calls) = crawl_fake_site(4, Some(3));
+        assert_eq!(visited.len(), 3);
+        assert!(calls.iter().all(|&count| count == 1), "{:?}", calls);
+    }
 
-        if query.is_some() {
-            // Always add .html when there are query params
-            let name_str = local_path.file_name()?.to_string_lossy().to_string();
-            local_path.set_file_name(format!("{}.html", name_str));
-        } else {
-            // No query params - apply normal wget logic
-            let has_extension = file_name.contains('.');
-            if !has_extension {
-                local_path.set_extension("html");
-            } else if !file_name.ends_with(".html") && !file_name.ends_with(".htm") {
-                // Check if it's a known resource extension
-                let name_buf = PathBuf::from(&file_name);
-                let ext = name_buf.extension().and_then(|e| e.to_str()).unwrap_or("");
-                if ![
-                    "css", "js", "json", "xml", "txt", "pdf", "png", "jpg", "jpeg", "gif", "svg",
-                    "ico", "woff", "woff2", "ttf", "eot",
-                ]
-                .contains(&ext)
-                {
-                    // Not a known resource, likely HTML content - wget adds .html
-                    let name_str = local_path.file_name()?.to_string_lossy().to_string();
-                    local_path.set_file_name(format!("{}.html", name_str));
-                }
+    #[test]
+    fn test_crawl_report_counts_pages_and_links() {
+        let frontier = Frontier::new(vec!["/".to_string()], None);
+        crawl_concurrently(&frontier, 2, |url, _| match url {
+            "/" => Some(vec![
+                "/a".to_string(),
+                "/b".to_string(),
+                "/broken".to_string(),
+            ]),
+            "/a" => Some(vec!["/".to_string(), "/b".to_string(), "/c".to_string()]),
+            "/b" | "/c" => Some(Vec::new()),
+            _ => None,
+        });
+
+        assert_eq!(
+            frontier.report(),
+            CrawlReport {
+                pages_downloaded: 4,
+                pages_failed: 1,
+                links_discovered: 4,
+                ..CrawlReport::default()
             }
-        }
+        );
+    }
 
-        // Try to find the file - if it doesn't exist with query params, try without
-        if !local_path.exists() && query.is_some() {
-            // Try without query params
-            let simple_name = rel_path.split('/').next_back().unwrap_or("index.html");
-            let mut fallback_path = base_dir.to_path_buf();
-            fallback_path.push(simple_name);
-
-            let simple_has_extension = simple_name.contains('.');
-            if !simple_has_extension {
-                fallback_path.set_extension("html");
-            } else if !simple_name.ends_with(".html") && !simple_name.ends_with(".htm") {
-                let simple_buf = PathBuf::from(simple_name);
-                let ext = simple_buf
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("");
-                if ![
-                    "css", "js", "json", "xml", "txt", "pdf", "png", "jpg", "jpeg", "gif", "svg",
-                    "ico", "woff", "woff2", "ttf", "eot",
-                ]
-                .contains(&ext)
-                {
-                    let name_str = fallback_path.file_name()?.to_string_lossy().to_string();
-                    fallback_path.set_file_name(format!("{}.html", name_str));
-                }
-            }
+    #[test]
+    fn test_crawl_state_is_saved_and_resumed() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = CrawlState::path(temp_dir.path());
+        assert_eq!(CrawlState::load(&path).unwrap(), None);
+
+        let frontier = Frontier::new(vec!["/".to_string()], None).with_checkpoint(Checkpoint {
+            path: path.clone(),
+            start_url: "https://example.com/".to_string(),
+            every: 2,
+        });
+        crawl_fake_site_from(frontier, 1);
+
+        let saved = CrawlState::load(&path).unwrap().unwrap();
+        assert_eq!(saved.start_url, "https://example.com/");
+        assert_eq!(saved.downloaded_count, 6);
+        assert_eq!(saved.visited, ["/", "/a", "/b", "/c", "/d", "/e"]);
+
+        // Pages downloaded before the interruption are not fetched again
+        let interrupted = CrawlState {
+            start_url: saved.start_url,
+            visited: vec!["/".to_string(), "/a".to_string()],
+            queue: vec!["/b".to_string(), "/d".to_string()],
+            downloaded_count: 2,
+        };
+        let (visited, calls) = crawl_fake_site_from(Frontier::resume(interrupted, None), 4);
+        assert_eq!(visited.len(), 6);
+        assert!(
+            !calls.contains_key("/") && !calls.contains_key("/a"),
+            "{:?}",
+            calls
+        );
+        assert!(calls.values().all(|&count| count == 1), "{:?}", calls);
+    }
 
-            if fallback_path.exists() {
-                return Some(fallback_path);
-            }
-        }
+    #[test]
+    fn test_proxy_is_forwarded_to_wget() {
+        assert!(wget_proxy_args(&ProxySettings::default()).is_empty());
+
+        let proxy = HttpConfig::default()
+            .with_proxy(Some("http://proxy.corp:3128"))
+            .proxy;
+        let crawler = crawler(None, None).with_proxy(proxy);
+        let args: Vec<String> = crawler
+            .page_command("https://example.com/docs/")
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        let expected = [
+            "-e",
+            "use_proxy=yes",
+            "-e",
+            "http_proxy=http://proxy.corp:3128",
+            "-e",
+            "https_proxy=http://proxy.corp:3128",
+        ];
+        assert!(
+            args.windows(expected.len())
+                .any(|window| window == expected),
+            "{:?}",
+            args
+        );
+        assert_eq!(args.last().unwrap(), "https://example.com/docs/");
     }
 
-    Some(local_path)
+    #[test]
+    fn test_base_path_filters_urls_outside_prefix() {
+        let crawler = crawler(None, Some("/docs/"));
+
+        assert!(crawler.should_crawl_url("https://example.com/docs/"));
+        assert!(crawler.should_crawl_url("https://example.com/docs/guide/intro.html"));
+        assert!(!crawler.should_crawl_url("https://example.com/blog/post.html"));
+        assert!(!crawler.should_crawl_url("https://example.com/"));
+    }
+
+    #[test]
+    fn test_base_path_matches_whole_segments() {
+        let crawler = crawler(None, Some("/docs"));
+
+        assert!(crawler.should_crawl_url("https://example.com/docs"));
+        assert!(crawler.should_crawl_url("https://example.com/docs/guide.html"));
+        assert!(!crawler.should_crawl_url("https://example.com/docsify/index.html"));
+        assert!(!crawler.should_crawl_url("https://example.com/docs-old/page.html"));
+    }
+
+    #[test]
+    fn test_base_path_combines_with_pattern() {
+        // A missing leading slash is added
+        let crawler = crawler(Some(r"\.html$"), Some("docs"));
+
+        assert!(crawler.should_crawl_url("https://example.com/docs/page.html"));
+        assert!(!crawler.should_crawl_url("https://example.com/docs/image.png"));
+        assert!(!crawler.should_crawl_url("https://example.com/other/page.html"));
+    }
+
+    #[test]
+    fn test_extract_links_follows_meta_refresh() {
+        let temp_dir = TempDir::new().unwrap();
+        let page = temp_dir.path().join("index.html");
+        fs::write(
+            &page,
+            r#"<html><head><meta http-equiv="Refresh" content="0; URL='/new/home.html'"></head></html>"#,
+        )
+        .unwrap();
+
+        let base_url = Url::parse("https://example.com/old/index.html").unwrap();
+        let links = extract_links_from_html(&page, &base_url).unwrap();
+        assert_eq!(links, vec!["https://example.com/new/home.html".to_string()]);
+
+        // Off-domain redirects stay out of scope
+        fs::write(
+            &page,
+            r#"<meta http-equiv="refresh" content="0;url=https://other.test/">"#,
+        )
+        .unwrap();
+        assert!(extract_links_from_html(&page, &base_url)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_html_walk_survives_symlink_loop_and_respects_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        fs::create_dir_all(root.join("assets")).unwrap();
+        for page in [
+            "index.html",
+            "a/one.html",
+            "a/b/two.htm",
+            "a/b/c/three.html",
+        ] {
+            fs::write(root.join(page), "<html></html>").unwrap();
+        }
+        fs::write(root.join("assets/skip.html"), "").unwrap();
+        fs::write(root.join("a/style.css"), "").unwrap();
+        // a/b/loop -> a: following it would recurse forever
+        std::os::unix::fs::symlink(root.join("a"), root.join("a/b/loop")).unwrap();
+
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| f.strip_prefix(root).unwrap().to_string_lossy().to_string())
+                .collect()
+        };
+
+        let all = collect_html_files(root, DEFAULT_POST_PROCESS_DEPTH).unwrap();
+        assert_eq!(
+            relative(all),
+            [
+                "a/b/c/three.html",
+                "a/b/two.htm",
+                "a/one.html",
+                "index.html"
+            ]
+        );
+
+        let shallow = collect_html_files(root, 1).unwrap();
+        assert_eq!(relative(shallow), ["a/one.html", "index.html"]);
+
+        let root_only = collect_html_files(root, 0).unwrap();
+        assert_eq!(relative(root_only), ["index.html"]);
+    }
 }
@@ -1,11 +1,18 @@
+use crate::core::workspace::{parse_tags, Workspace};
 use crate::core::WorkspaceManager;
-use anyhow::Result;
+use crate::ui::glyphs::adapt;
+use crate::ui::{select_from_list, Cell, Table};
+use anyhow::{Context, Result};
 use colored::Colorize;
+use std::path::Path;
+use std::process::Command;
 
 pub fn execute(matches: &clap::ArgMatches) -> Result<()> {
     match matches.subcommand() {
         Some(("map", _)) => map_workspaces(),
-        Some(("list", _)) => list_workspaces(),
+        Some(("list", sub_matches)) => list_workspaces(sub_matches),
+        Some(("open", sub_matches)) => open_workspace(sub_matches),
+        Some(("tag", sub_matches)) => tag_workspace(sub_matches),
         _ => {
             println!("Use 'msc work --help' for more information.");
             Ok(())
@@ -48,11 +55,14 @@ fn map_workspaces() -> Result<()> {
     }
 }
 
-fn list_workspaces() -> Result<()> {
+fn list_workspaces(matches: &clap::ArgMatches) -> Result<()> {
     let manager = WorkspaceManager::new()?;
-    let workspaces = manager.list_workspaces();
+    let tags = matches
+        .get_one::<String>("tag")
+        .map(|value| parse_tags(value))
+        .unwrap_or_default();
 
-    if workspaces.is_empty() {
+    if manager.list_workspaces().is_empty() {
         println!(
             "{}",
             "No workspaces found. Use 'msc work map' to map your project folders.".yellow()
@@ -60,6 +70,15 @@ fn list_workspaces() -> Result<()> {
         return Ok(());
     }
 
+    let workspaces = manager.list_workspaces_tagged(&tags);
+    if workspaces.is_empty() {
+        println!(
+            "{}",
+            format!("No workspaces tagged {}.", tags.join(" or ")).yellow()
+        );
+        return Ok(());
+    }
+
     println!(
         "{} {}",
         "Workspaces:".white().bold(),
@@ -67,15 +86,187 @@ fn list_workspaces() -> Result<()> {
     );
     println!();
 
-    for (name, path) in workspaces {
-        let cleaned_path = path.strip_prefix("\\\\?\\").unwrap_or(&path);
-        println!(
-            "  {} {}",
-            "📂".to_string().blue().bold(),
-            name.cyan().bold()
-        );
-        println!("     {}", cleaned_path.dimmed());
+    let mut table = Table::new(&["Name", "Tags", "Path"])
+        .borderless()
+        .indent(2)
+        .min_width(2, 20)
+        .fit_terminal();
+    for workspace in workspaces {
+        let cleaned_path = workspace
+            .path
+            .strip_prefix("\\\\?\\")
+            .unwrap_or(&workspace.path);
+        table.add_row([
+            Cell::new(workspace.name.as_str()).styled(|text| text.cyan().bold().to_string()),
+            Cell::new(workspace.tags.join(", ")).styled(|text| text.dimmed().to_string()),
+            Cell::new(cleaned_path).styled(|text| text.dimmed().to_string()),
+        ]);
     }
+    table.print();
 
     Ok(())
 }
+
+fn tag_workspace(matches: &clap::ArgMatches) -> Result<()> {
+    let query = matches
+        .get_one::<String>("name")
+        .context("Workspace name is required")?;
+    let tags = parse_tags(
+        matches
+            .get_one::<String>("tags")
+            .context("Tags are required")?,
+    );
+    if tags.is_empty() {
+        return Err(anyhow::anyhow!("No tags given (use e.g. 'rust,cli')"));
+    }
+
+    let mut manager = WorkspaceManager::new()?;
+    let Some(workspace) = select_workspace(&manager, query)? else {
+        return Ok(());
+    };
+
+    let updated = manager.add_tags(&workspace.name, &tags)?;
+    manager.save()?;
+
+    println!(
+        "{} {} {}",
+        format!("{} Tagged", adapt("✓")).green(),
+        workspace.name.cyan().bold(),
+        format!("[{}]", updated.join(", ")).dimmed()
+    );
+    Ok(())
+}
+
+/// Resolve `query` to one workspace, asking the user when several match
+///
+/// Returns `None` when the user cancels the selection.
+fn select_workspace(manager: &WorkspaceManager, query: &str) -> Result<Option<Workspace>> {
+    let candidates = manager.find_workspaces(query);
+
+    match candidates.len() {
+        0 => Err(anyhow::anyhow!(
+            "No workspace matches '{}'. Use 'msc work list' to see registered workspaces.",
+            query
+        )),
+        1 => Ok(candidates.into_iter().next()),
+        _ => {
+            let names: Vec<String> = candidates.iter().map(|ws| ws.name.clone()).collect();
+            let title = format!("Several workspaces match '{}':", query);
+            match select_from_list(&title, &names)
+                .map_err(|e| anyhow::anyhow!("Selection failed: {}", e))?
+            {
+                Some(index) => Ok(candidates.into_iter().nth(index)),
+                None => {
+                    println!("{}", "Operation cancelled.".yellow());
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+fn open_workspace(matches: &clap::ArgMatches) -> Result<()> {
+    let query = matches
+        .get_one::<String>("name")
+        .context("Workspace name is required")?;
+    let manager = WorkspaceManager::new()?;
+    let Some(Workspace { name, path, .. }) = select_workspace(&manager, query)? else {
+        return Ok(());
+    };
+
+    let cleaned_path = path.strip_prefix("\\\\?\\").unwrap_or(&path);
+    if !Path::new(&path).is_dir() {
+        return Err(anyhow::anyhow!(
+            "Workspace '{}' points to {}, which no longer exists. Run 'msc work map' to refresh.",
+            name,
+            cleaned_path
+        ));
+    }
+
+    let editor = manager
+        .config()
+        .resolve_editor(std::env::var("EDITOR").ok());
+    println!(
+        "{} {} {}",
+        "Opening".cyan(),
+        name.cyan().bold(),
+        format!("with {}", editor).dimmed()
+    );
+
+    let status = editor_command(&editor, cleaned_path)?
+        .status()
+        .with_context(|| {
+            format!(
+                "Failed to launch editor '{}'. Set one with 'msc set editor <command>'.",
+                editor
+            )
+        })?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Editor '{}' exited with {}",
+            editor,
+            status
+        ));
+    }
+
+    Ok(())
+}
+
+/// Build the editor invocation; the editor may carry its own arguments (`code -n`)
+///
+/// The editor is started directly, never through a shell, so `path` reaches
+/// it as one argument whatever characters it contains.
+pub(crate) fn editor_command(editor: &str, path: &str) -> Result<Command> {
+    let words = split_editor(editor)?;
+    let (program, args) = words
+        .split_first()
+        .context("The editor command is empty. Set one with 'msc set editor <command>'.")?;
+
+    // Editors like `code` are .cmd shims on Windows: find them through PATH
+    // and PATHEXT, which Command::new alone does not search
+    #[cfg(windows)]
+    let program = which::which(program).unwrap_or_else(|_| program.into());
+
+    let mut command = Command::new(program);
+    command.args(args).arg(path);
+    Ok(command)
+}
+
+/// Split an editor setting into program and arguments, honoring quotes
+///
+/// A setting naming an existing file is taken whole, so an unquoted
+/// `C:\Program Files\...\code.exe` still works.
+fn split_editor(editor: &str) -> Result<Vec<String>> {
+    let editor = editor.trim();
+    if Path::new(editor).is_file() {
+        return Ok(vec![editor.to_string()]);
+    }
+
+    // Backslashes are path separators on Windows, not escapes
+    #[cfg(windows)]
+    let editor = editor.replace('\\', "\\\\");
+
+    shell_words::split(&editor).with_context(|| format!("Invalid editor command: {}", editor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_editor_honors_quotes() {
+        assert_eq!(split_editor("code -n").unwrap(), ["code", "-n"]);
+        assert_eq!(
+            split_editor("'/opt/My Editor/bin/edit' --wait").unwrap(),
+            ["/opt/My Editor/bin/edit", "--wait"]
+        );
+        assert!(split_editor("code 'unterminated").is_err());
+    }
+
+    #[test]
+    fn test_editor_command_passes_path_as_one_argument() {
+        let command = editor_command("code -n", "/work/a&b c|d").unwrap();
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["-n", "/work/a&b c|d"]);
+    }
+}
@@ -1,10 +1,18 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
-
-use super::alias_validator::validate_alias_command;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub use super::alias_schema::{
+    expand_command, parse_command_template, AliasEntry, AliasFile, AliasListing, AliasStats,
+    AliasUsage, CommandPart, ALIAS_SCHEMA_VERSION, STATS_FILE_NAME,
+};
+use super::alias_validator::{
+    normalize_alias_label, parse_env_assignment, validate_alias_command, validate_alias_cwd,
+    validate_unsafe_alias_command,
+};
 
 /// Represents a single alias
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +22,21 @@ pub struct Alias {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub created_at: String,
+    /// Directory the command runs in (validated to exist when set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Extra environment variables for the command
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Group used for organizing `msc alias list`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Free-form labels for filtering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Created with `--unsafe`: the command may use shell operators
+    #[serde(default, rename = "unsafe", skip_serializing_if = "std::ops::Not::not")]
+    pub unsafe_shell: bool,
 }
 
 impl Alias {
@@ -35,6 +58,11 @@ impl Alias {
             command,
             description: None,
             created_at: chrono::Utc::now().to_rfc3339(),
+            cwd: None,
+            env: BTreeMap::new(),
+            category: None,
+            tags: Vec::new(),
+            unsafe_shell: false,
         })
     }
 
@@ -55,14 +83,224 @@ impl Alias {
             command,
             description: Some(description),
             created_at: chrono::Utc::now().to_rfc3339(),
+            cwd: None,
+            env: BTreeMap::new(),
+            category: None,
+            tags: Vec::new(),
+            unsafe_shell: false,
         })
     }
+
+    /// Create an alias whose command may use shell operators (pipes,
+    /// redirections, substitutions)
+    ///
+    /// # Security
+    /// The command runs in the shell exactly as written. Only call this when
+    /// the user enabled `alias.allow_shell_operators` and asked for `--unsafe`;
+    /// the alias is marked so listings can flag it.
+    pub fn new_unsafe(name: String, command: String) -> Result<Self> {
+        validate_unsafe_alias_command(&command)?;
+
+        Ok(Self {
+            name,
+            command,
+            description: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            cwd: None,
+            env: BTreeMap::new(),
+            category: None,
+            tags: Vec::new(),
+            unsafe_shell: true,
+        })
+    }
+}
+
+impl From<&Alias> for AliasEntry {
+    fn from(alias: &Alias) -> Self {
+        Self {
+            name: alias.name.clone(),
+            command: alias.command.clone(),
+            description: alias.description.clone(),
+            cwd: alias.cwd.clone(),
+            env: alias.env.clone(),
+            category: alias.category.clone(),
+            tags: alias.tags.clone(),
+            unsafe_shell: alias.unsafe_shell,
+        }
+    }
+}
+
+impl Alias {
+    /// Replace the command, validating it like [`Alias::new`] (or
+    /// [`Alias::new_unsafe`] for unsafe aliases)
+    ///
+    /// Returns false if the command did not change.
+    pub fn set_command(&mut self, command: &str) -> Result<bool> {
+        let command = command.trim();
+        if self.unsafe_shell {
+            validate_unsafe_alias_command(command)?;
+        } else {
+            validate_alias_command(command)?;
+        }
+        if command == self.command {
+            return Ok(false);
+        }
+        self.command = command.to_string();
+        Ok(true)
+    }
+
+    /// Set the working directory, which must exist
+    ///
+    /// The path is stored in absolute form so the alias works from anywhere.
+    pub fn set_cwd(&mut self, cwd: &str) -> Result<()> {
+        let resolved = validate_alias_cwd(cwd)?;
+        self.cwd = Some(resolved.to_string_lossy().to_string());
+        Ok(())
+    }
+
+    /// Add an environment variable from a `KEY=VALUE` assignment
+    pub fn set_env(&mut self, assignment: &str) -> Result<()> {
+        let (key, value) = parse_env_assignment(assignment)?;
+        self.env.insert(key, value);
+        Ok(())
+    }
+
+    /// Set or clear the category
+    pub fn set_category(&mut self, category: Option<&str>) -> Result<()> {
+        self.category = category.map(normalize_alias_label).transpose()?;
+        Ok(())
+    }
+
+    /// Add a tag, returning false if it was already present
+    pub fn add_tag(&mut self, tag: &str) -> Result<bool> {
+        let tag = normalize_alias_label(tag)?;
+        if self.tags.contains(&tag) {
+            return Ok(false);
+        }
+        self.tags.push(tag);
+        self.tags.sort();
+        Ok(true)
+    }
+
+    /// Remove a tag, returning false if it was not present
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        let before = self.tags.len();
+        self.tags.retain(|t| *t != tag);
+        self.tags.len() != before
+    }
+
+    /// Build the invocation for this alias with `args` substituted or appended
+    ///
+    /// Arguments are expanded the same way `msc-shim` does.
+    pub fn invocation(&self, args: &[String]) -> AliasInvocation {
+        AliasInvocation {
+            command: expand_command(&self.command, args),
+            cwd: self.cwd.as_ref().map(PathBuf::from),
+            env: self.env.clone(),
+        }
+    }
+}
+
+/// A fully resolved alias command, ready to be spawned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasInvocation {
+    /// Command line passed to the platform shell
+    pub command: String,
+    pub cwd: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
+}
+
+/// Spawns alias invocations
+pub trait AliasRunner {
+    /// Run the invocation and return its exit code
+    fn run(&self, invocation: &AliasInvocation) -> Result<i32>;
+}
+
+/// Runner that executes through the platform shell, like `msc-shim`
+pub struct ShellRunner;
+
+impl AliasRunner for ShellRunner {
+    fn run(&self, invocation: &AliasInvocation) -> Result<i32> {
+        #[cfg(windows)]
+        let mut command = {
+            let mut command = Command::new("cmd");
+            command.args(["/C", &invocation.command]);
+            command
+        };
+
+        #[cfg(not(windows))]
+        let mut command = {
+            let mut command = Command::new("sh");
+            command.args(["-c", &invocation.command]);
+            command
+        };
+
+        if let Some(cwd) = &invocation.cwd {
+            command.current_dir(cwd);
+        }
+        command.envs(&invocation.env);
+
+        let status = command
+            .status()
+            .with_context(|| format!("Failed to execute '{}'", invocation.command))?;
+
+        Ok(status.code().unwrap_or(1))
+    }
+}
+
+/// Run an alias with extra arguments through `runner`
+///
+/// Fails before spawning if the configured working directory has been
+/// removed since the alias was created.
+pub fn run_alias(alias: &Alias, args: &[String], runner: &dyn AliasRunner) -> Result<i32> {
+    let invocation = alias.invocation(args);
+
+    if let Some(cwd) = &invocation.cwd {
+        if !Path::new(cwd).is_dir() {
+            anyhow::bail!(
+                "Working directory {:?} of alias '{}' no longer exists",
+                cwd,
+                alias.name
+            );
+        }
+    }
+
+    runner.run(&invocation)
+}
+
+/// Criteria for `msc alias list --category/--tag`
+#[derive(Debug, Clone, Default)]
+pub struct AliasFilter {
+    pub category: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl AliasFilter {
+    /// Whether an alias satisfies every criterion (case-insensitive)
+    pub fn matches(&self, alias: &Alias) -> bool {
+        let category_matches = self.category.as_ref().is_none_or(|category| {
+            alias
+                .category
+                .as_ref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(category))
+        });
+        let tag_matches = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| alias.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+
+        category_matches && tag_matches
+    }
 }
 
 /// Configuration for all aliases
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct AliasConfig {
     pub aliases: HashMap<String, Alias>,
+    /// Record alias executions in the stats file (opt-in)
+    #[serde(default)]
+    pub track_usage: bool,
 }
 
 impl AliasConfig {
@@ -128,6 +366,37 @@ impl AliasConfig {
         aliases
     }
 
+    /// Get the aliases matching a filter, sorted by name
+    pub fn filter_aliases(&self, filter: &AliasFilter) -> Vec<&Alias> {
+        self.list_aliases()
+            .into_iter()
+            .filter(|alias| filter.matches(alias))
+            .collect()
+    }
+
+    /// Group aliases by category, sorted by name; uncategorized ones come last
+    pub fn group_by_category<'a>(aliases: &[&'a Alias]) -> Vec<(Option<&'a str>, Vec<&'a Alias>)> {
+        let mut groups: BTreeMap<Option<&str>, Vec<&Alias>> = BTreeMap::new();
+        for alias in aliases {
+            groups
+                .entry(alias.category.as_deref())
+                .or_default()
+                .push(alias);
+        }
+
+        let uncategorized = groups.remove(&None);
+        let mut grouped: Vec<_> = groups.into_iter().collect();
+        if let Some(aliases) = uncategorized {
+            grouped.push((None, aliases));
+        }
+        grouped
+    }
+
+    /// Build the versioned listing emitted by `msc alias list --json`
+    pub fn to_listing(&self) -> AliasListing {
+        AliasListing::new(self.aliases.values().map(AliasEntry::from).collect())
+    }
+
     /// Check if an alias exists
     pub fn exists(&self, name: &str) -> bool {
         self.aliases.contains_key(name)
@@ -142,6 +411,103 @@ impl AliasConfig {
     }
 }
 
+/// An alias suggested for removal by `msc alias prune`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneCandidate {
+    pub name: String,
+    /// Last execution (Unix seconds), or `None` if never recorded
+    pub last_used: Option<u64>,
+}
+
+impl AliasStats {
+    /// Load statistics from disk (empty if tracking never ran)
+    pub fn load() -> Result<Self> {
+        let stats_path = Self::get_stats_path()?;
+
+        if !stats_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&stats_path)
+            .with_context(|| format!("Failed to read alias stats from {:?}", stats_path))?;
+
+        serde_json::from_str(&content).with_context(|| "Failed to parse alias stats")
+    }
+
+    /// Save statistics to disk
+    pub fn save(&self) -> Result<()> {
+        let stats_path = Self::get_stats_path()?;
+
+        if let Some(parent) = stats_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory: {:?}", parent))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize alias stats")?;
+
+        fs::write(&stats_path, content)
+            .with_context(|| format!("Failed to write alias stats to {:?}", stats_path))?;
+
+        Ok(())
+    }
+
+    /// Usage entries ordered by run count, most recently used first on ties
+    pub fn ranked(&self) -> Vec<(&str, &AliasUsage)> {
+        let mut ranked: Vec<(&str, &AliasUsage)> = self
+            .aliases
+            .iter()
+            .map(|(name, usage)| (name.as_str(), usage))
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.count
+                .cmp(&a.1.count)
+                .then(b.1.last_used.cmp(&a.1.last_used))
+                .then(a.0.cmp(b.0))
+        });
+        ranked
+    }
+
+    /// Get the path to the stats file (next to aliases.json)
+    fn get_stats_path() -> Result<PathBuf> {
+        Ok(AliasConfig::get_config_path()?.with_file_name(STATS_FILE_NAME))
+    }
+}
+
+/// Select aliases not used within the last `unused_for_days` days
+///
+/// Aliases that were never executed are judged by their creation date, or by
+/// when tracking was enabled if that is later, so freshly added aliases and
+/// aliases created before tracking started are not suggested. `now` is in
+/// Unix seconds.
+pub fn prune_candidates(
+    config: &AliasConfig,
+    stats: &AliasStats,
+    now: u64,
+    unused_for_days: u64,
+) -> Vec<PruneCandidate> {
+    let cutoff = now.saturating_sub(unused_for_days.saturating_mul(86_400));
+
+    config
+        .list_aliases()
+        .into_iter()
+        .filter_map(|alias| {
+            let last_used = stats.aliases.get(&alias.name).map(|usage| usage.last_used);
+            let reference = last_used.unwrap_or_else(|| {
+                let created_at = chrono::DateTime::parse_from_rfc3339(&alias.created_at)
+                    .map(|created| created.timestamp().max(0) as u64)
+                    .unwrap_or(0);
+                created_at.max(stats.tracking_since.unwrap_or(0))
+            });
+
+            (reference < cutoff).then(|| PruneCandidate {
+                name: alias.name.clone(),
+                last_used,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,4 +576,294 @@ mod tests {
         assert_eq!(list[1].name, "beta");
         assert_eq!(list[2].name, "zebra");
     }
+
+    #[test]
+    fn test_listing_matches_on_disk_schema() {
+        let mut config = AliasConfig::default();
+        config.add_alias(
+            Alias::with_description(
+                "gp".to_string(),
+                "git push".to_string(),
+                "Quick git push".to_string(),
+            )
+            .unwrap(),
+        );
+        config.add_alias(
+            Alias::new("pyh".to_string(), "python3 -m http.server".to_string()).unwrap(),
+        );
+
+        let listing = config.to_listing();
+        assert_eq!(listing.schema_version, ALIAS_SCHEMA_VERSION);
+        assert_eq!(listing.aliases[0].name, "gp");
+        assert_eq!(listing.aliases[1].description, None);
+
+        // The file the shim reads and the listing describe the same entries
+        let on_disk = serde_json::to_string(&config).unwrap();
+        let shim_view: AliasFile = serde_json::from_str(&on_disk).unwrap();
+        let listing_json = serde_json::to_string(&listing).unwrap();
+        let parsed: AliasListing = serde_json::from_str(&listing_json).unwrap();
+        let from_listing = parsed.into_file();
+
+        assert_eq!(shim_view.aliases, from_listing.aliases);
+    }
+
+    #[test]
+    fn test_stats_record_increments() {
+        let mut stats = AliasStats::default();
+        stats.record("gs", 100);
+        stats.record("gs", 250);
+        stats.record("pyh", 200);
+
+        assert_eq!(
+            stats.aliases["gs"],
+            AliasUsage {
+                count: 2,
+                last_used: 250
+            }
+        );
+        assert_eq!(stats.aliases["pyh"].count, 1);
+
+        let ranked: Vec<&str> = stats.ranked().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(ranked, ["gs", "pyh"]);
+    }
+
+    #[test]
+    fn test_prune_candidates() {
+        const DAY: u64 = 86_400;
+        let now = 100 * DAY;
+
+        let mut config = AliasConfig::default();
+        for name in ["fresh", "recent", "stale", "never"] {
+            let mut alias = Alias::new(name.to_string(), "echo hi".to_string()).unwrap();
+            alias.created_at = "1970-01-01T00:00:00Z".to_string();
+            config.add_alias(alias);
+        }
+        // Never run, but created yesterday
+        config.aliases.get_mut("fresh").unwrap().created_at =
+            chrono::DateTime::from_timestamp((now - DAY) as i64, 0)
+                .unwrap()
+                .to_rfc3339();
+
+        let mut stats = AliasStats::default();
+        stats.record("recent", now - 5 * DAY);
+        stats.record("stale", now - 60 * DAY);
+
+        let candidates = prune_candidates(&config, &stats, now, 30);
+        assert_eq!(
+            candidates,
+            vec![
+                PruneCandidate {
+                    name: "never".to_string(),
+                    last_used: None
+                },
+                PruneCandidate {
+                    name: "stale".to_string(),
+                    last_used: Some(now - 60 * DAY)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prune_candidates_waits_for_tracking_period() {
+        const DAY: u64 = 86_400;
+        let now = 100 * DAY;
+
+        let mut config = AliasConfig::default();
+        let mut alias = Alias::new("old".to_string(), "echo hi".to_string()).unwrap();
+        alias.created_at = "1970-01-01T00:00:00Z".to_string();
+        config.add_alias(alias);
+
+        // Tracking enabled five days ago: too soon to call "old" unused
+        let mut stats = AliasStats {
+            tracking_since: Some(now - 5 * DAY),
+            ..AliasStats::default()
+        };
+        assert!(prune_candidates(&config, &stats, now, 30).is_empty());
+
+        stats.tracking_since = Some(now - 40 * DAY);
+        let candidates = prune_candidates(&config, &stats, now, 30);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].name, "old");
+    }
+
+    /// Records invocations instead of spawning processes
+    #[derive(Default)]
+    struct MockRunner {
+        invocations: std::cell::RefCell<Vec<AliasInvocation>>,
+    }
+
+    impl AliasRunner for MockRunner {
+        fn run(&self, invocation: &AliasInvocation) -> Result<i32> {
+            self.invocations.borrow_mut().push(invocation.clone());
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test_run_alias_passes_cwd_and_env() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut alias = Alias::new("srv".to_string(), "npm run dev".to_string()).unwrap();
+        alias.set_cwd(temp_dir.path().to_str().unwrap()).unwrap();
+        alias.set_env("PORT=3000").unwrap();
+        alias.set_env("NODE_ENV=development").unwrap();
+
+        let runner = MockRunner::default();
+        let code = run_alias(&alias, &["--open".to_string()], &runner).unwrap();
+        assert_eq!(code, 0);
+
+        let invocations = runner.invocations.borrow();
+        assert_eq!(invocations.len(), 1);
+        let invocation = &invocations[0];
+        assert_eq!(invocation.command, "npm run dev --open");
+        assert_eq!(
+            invocation.cwd.as_deref(),
+            Some(temp_dir.path().canonicalize().unwrap().as_path())
+        );
+        assert_eq!(invocation.env["PORT"], "3000");
+        assert_eq!(invocation.env["NODE_ENV"], "development");
+    }
+
+    #[test]
+    fn test_set_command_validates() {
+        let mut alias = Alias::new("gs".to_string(), "git status".to_string()).unwrap();
+
+        assert!(alias.set_command("  git status -sb ").unwrap());
+        assert_eq!(alias.command, "git status -sb");
+        assert!(!alias.set_command("git status -sb").unwrap());
+
+        assert!(alias.set_command("git status; rm -rf /").is_err());
+        assert!(alias.set_command("   ").is_err());
+        assert_eq!(alias.command, "git status -sb");
+    }
+
+    #[test]
+    fn test_unsafe_alias_is_marked() {
+        let command = "git log --oneline | head -20".to_string();
+        assert!(Alias::new("gl".to_string(), command.clone()).is_err());
+
+        let mut alias = Alias::new_unsafe("gl".to_string(), command).unwrap();
+        assert!(alias.unsafe_shell);
+        assert!(alias.set_command("git log | less").unwrap());
+        assert!(AliasEntry::from(&alias).unsafe_shell);
+
+        // The marker is stored as "unsafe" and omitted for regular aliases
+        let json = serde_json::to_value(&alias).unwrap();
+        assert_eq!(json["unsafe"], true);
+        let safe = Alias::new("gs".to_string(), "git status".to_string()).unwrap();
+        assert!(serde_json::to_value(&safe).unwrap().get("unsafe").is_none());
+    }
+
+    #[test]
+    fn test_invocation_substitutes_placeholders() {
+        let args = |list: &[&str]| -> Vec<String> { list.iter().map(|a| a.to_string()).collect() };
+        let command = |template: &str, list: &[&str]| {
+            Alias::new("a".to_string(), template.to_string())
+                .unwrap()
+                .invocation(&args(list))
+                .command
+        };
+
+        assert_eq!(command("git checkout {1}", &["main"]), "git checkout main");
+        assert_eq!(command("cp {2} {1}", &["a", "b", "-v"]), "cp b a -v");
+        assert_eq!(command("echo {@} done", &["x", "y"]), "echo x y done");
+        // A missing argument is left empty
+        assert_eq!(command("git checkout {1}", &[]), "git checkout ");
+        // Without placeholders arguments are appended as before
+        assert_eq!(command("git status", &["-s"]), "git status -s");
+        assert_eq!(command("git status", &[]), "git status");
+    }
+
+    #[test]
+    fn test_run_alias_rejects_missing_cwd() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut alias = Alias::new("srv".to_string(), "ls".to_string()).unwrap();
+        alias.set_cwd(temp_dir.path().to_str().unwrap()).unwrap();
+        drop(temp_dir);
+
+        let runner = MockRunner::default();
+        assert!(run_alias(&alias, &[], &runner).is_err());
+        assert!(runner.invocations.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_cwd_and_env_survive_serialization() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut alias = Alias::new("srv".to_string(), "ls".to_string()).unwrap();
+        alias.set_cwd(temp_dir.path().to_str().unwrap()).unwrap();
+        alias.set_env("A=1").unwrap();
+
+        let json = serde_json::to_string(&alias).unwrap();
+        let shim_view: AliasEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(shim_view.cwd, alias.cwd);
+        assert_eq!(shim_view.env, alias.env);
+
+        // Aliases saved before these fields existed still load
+        let legacy = r#"{"name":"gs","command":"git status","created_at":"2024-01-01T00:00:00Z"}"#;
+        let legacy: Alias = serde_json::from_str(legacy).unwrap();
+        assert!(legacy.cwd.is_none() && legacy.env.is_empty());
+    }
+
+    #[test]
+    fn test_tag_assignment() {
+        let mut alias = Alias::new("gs".to_string(), "git status".to_string()).unwrap();
+        alias.set_category(Some("Git")).unwrap();
+        assert!(alias.add_tag("VCS").unwrap());
+        assert!(alias.add_tag("daily").unwrap());
+        assert!(!alias.add_tag("vcs").unwrap());
+
+        assert_eq!(alias.category.as_deref(), Some("git"));
+        assert_eq!(alias.tags, ["daily", "vcs"]);
+        assert!(alias.add_tag("has space").is_err());
+
+        assert!(alias.remove_tag("Daily"));
+        assert!(!alias.remove_tag("daily"));
+        alias.set_category(None).unwrap();
+        assert!(alias.category.is_none());
+    }
+
+    #[test]
+    fn test_filtered_listing() {
+        let mut config = AliasConfig::default();
+        let mut gs = Alias::new("gs".to_string(), "git status".to_string()).unwrap();
+        gs.set_category(Some("git")).unwrap();
+        gs.add_tag("daily").unwrap();
+        let mut gp = Alias::new("gp".to_string(), "git push".to_string()).unwrap();
+        gp.set_category(Some("git")).unwrap();
+        let mut dev = Alias::new("dev".to_string(), "npm run dev".to_string()).unwrap();
+        dev.add_tag("daily").unwrap();
+        config.add_alias(gs);
+        config.add_alias(gp);
+        config.add_alias(dev);
+        config.add_alias(Alias::new("ll".to_string(), "ls -la".to_string()).unwrap());
+
+        let names = |aliases: Vec<&Alias>| -> Vec<String> {
+            aliases.into_iter().map(|a| a.name.clone()).collect()
+        };
+
+        let by_category = AliasFilter {
+            category: Some("GIT".to_string()),
+            tag: None,
+        };
+        assert_eq!(names(config.filter_aliases(&by_category)), ["gp", "gs"]);
+
+        let by_tag = AliasFilter {
+            category: None,
+            tag: Some("daily".to_string()),
+        };
+        assert_eq!(names(config.filter_aliases(&by_tag)), ["dev", "gs"]);
+
+        let both = AliasFilter {
+            category: Some("git".to_string()),
+            tag: Some("daily".to_string()),
+        };
+        assert_eq!(names(config.filter_aliases(&both)), ["gs"]);
+
+        let all = config.filter_aliases(&AliasFilter::default());
+        assert_eq!(all.len(), 4);
+        let groups = AliasConfig::group_by_category(&all);
+        assert_eq!(groups[0].0, Some("git"));
+        assert_eq!(groups[1].0, None);
+        assert_eq!(names(groups[1].1.clone()), ["dev", "ll"]);
+    }
 }
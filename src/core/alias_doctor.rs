@@ -0,0 +1,183 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::alias::AliasConfig;
+use super::alias_generator::AliasGenerator;
+
+/// A problem found between `aliases.json` and the shims directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShimIssue {
+    /// A configured alias has no executable
+    Missing { alias: String },
+    /// The executable differs from what the current msc would generate
+    Stale { alias: String, path: PathBuf },
+    /// An executable exists without a matching alias
+    Orphan { alias: String, path: PathBuf },
+}
+
+impl ShimIssue {
+    pub fn alias_name(&self) -> &str {
+        match self {
+            ShimIssue::Missing { alias }
+            | ShimIssue::Stale { alias, .. }
+            | ShimIssue::Orphan { alias, .. } => alias,
+        }
+    }
+}
+
+/// Compare the configured aliases against the shims in `bin_dir`
+///
+/// Issues are sorted by alias name. A missing `bin_dir` reports every alias
+/// as missing.
+pub fn inspect_shims(
+    config: &AliasConfig,
+    bin_dir: &Path,
+    generator: &dyn AliasGenerator,
+) -> Result<Vec<ShimIssue>> {
+    let mut issues = Vec::new();
+
+    for alias in config.list_aliases() {
+        let path = bin_dir.join(generator.file_name(&alias.name));
+        if !path.exists() {
+            issues.push(ShimIssue::Missing {
+                alias: alias.name.clone(),
+            });
+            continue;
+        }
+
+        let actual = fs::read(&path).with_context(|| format!("Failed to read shim {:?}", path))?;
+        if actual != generator.expected_contents(alias)? {
+            issues.push(ShimIssue::Stale {
+                alias: alias.name.clone(),
+                path,
+            });
+        }
+    }
+
+    if bin_dir.is_dir() {
+        let entries = fs::read_dir(bin_dir)
+            .with_context(|| format!("Failed to read directory {:?}", bin_dir))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Some(alias) = generator.alias_name_from_file(&path) else {
+                continue;
+            };
+            if !config.exists(&alias) {
+                issues.push(ShimIssue::Orphan { alias, path });
+            }
+        }
+    }
+
+    issues.sort_by(|a, b| a.alias_name().cmp(b.alias_name()));
+    Ok(issues)
+}
+
+/// Regenerate missing/stale shims and remove orphans, returning how many were fixed
+pub fn fix_shim_issues(
+    issues: &[ShimIssue],
+    config: &AliasConfig,
+    bin_dir: &Path,
+    generator: &dyn AliasGenerator,
+) -> Result<usize> {
+    if issues.is_empty() {
+        return Ok(0);
+    }
+
+    fs::create_dir_all(bin_dir)
+        .with_context(|| format!("Failed to create bin directory: {:?}", bin_dir))?;
+
+    for issue in issues {
+        match issue {
+            ShimIssue::Missing { alias } | ShimIssue::Stale { alias, .. } => {
+                let alias = config
+                    .get_alias(alias)
+                    .with_context(|| format!("Alias '{}' disappeared from config", alias))?;
+                generator.generate(alias, bin_dir)?;
+            }
+            ShimIssue::Orphan { alias, .. } => generator.cleanup(alias, bin_dir)?,
+        }
+    }
+
+    Ok(issues.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::alias::Alias;
+    use crate::core::alias_generator::UnixScriptGenerator;
+    use tempfile::TempDir;
+
+    fn fixture() -> (TempDir, AliasConfig, UnixScriptGenerator) {
+        let temp_dir = TempDir::new().unwrap();
+        let generator = UnixScriptGenerator::new();
+        let mut config = AliasConfig::default();
+
+        for (name, command) in [("gs", "git status"), ("ll", "ls -la")] {
+            let alias = Alias::new(name.to_string(), command.to_string()).unwrap();
+            generator.generate(&alias, temp_dir.path()).unwrap();
+            config.add_alias(alias);
+        }
+
+        (temp_dir, config, generator)
+    }
+
+    #[test]
+    fn test_healthy_layout_has_no_issues() {
+        let (temp_dir, config, generator) = fixture();
+        let issues = inspect_shims(&config, temp_dir.path(), &generator).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_detects_missing_and_orphan_shims() {
+        let (temp_dir, mut config, generator) = fixture();
+        fs::remove_file(temp_dir.path().join("gs")).unwrap();
+        config.remove_alias("ll");
+        // Unrelated files are not mistaken for shims, even without an extension
+        fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("tool"), "#!/bin/sh\necho mine\n").unwrap();
+
+        let issues = inspect_shims(&config, temp_dir.path(), &generator).unwrap();
+        assert_eq!(
+            issues,
+            vec![
+                ShimIssue::Missing {
+                    alias: "gs".to_string()
+                },
+                ShimIssue::Orphan {
+                    alias: "ll".to_string(),
+                    path: temp_dir.path().join("ll"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detects_stale_shim_and_fixes_everything() {
+        let (temp_dir, mut config, generator) = fixture();
+        config.add_alias(Alias::new("gs".to_string(), "git status -sb".to_string()).unwrap());
+        fs::write(
+            temp_dir.path().join("old"),
+            "#!/bin/bash\n# Generated by msc - Alias: old\nexec true \"$@\"\n",
+        )
+        .unwrap();
+
+        let issues = inspect_shims(&config, temp_dir.path(), &generator).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert!(matches!(&issues[0], ShimIssue::Stale { alias, .. } if alias == "gs"));
+        assert!(matches!(&issues[1], ShimIssue::Orphan { alias, .. } if alias == "old"));
+
+        let fixed = fix_shim_issues(&issues, &config, temp_dir.path(), &generator).unwrap();
+        assert_eq!(fixed, 2);
+        assert!(inspect_shims(&config, temp_dir.path(), &generator)
+            .unwrap()
+            .is_empty());
+        assert!(!temp_dir.path().join("old").exists());
+    }
+}
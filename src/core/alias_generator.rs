@@ -1,8 +1,9 @@
 use anyhow::{Context, Result};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use super::alias::Alias;
+use super::alias::{parse_command_template, Alias, CommandPart};
 
 /// Trait for generating alias executables
 pub trait AliasGenerator {
@@ -11,6 +12,26 @@ pub trait AliasGenerator {
 
     /// Clean up an alias executable
     fn cleanup(&self, alias_name: &str, output_path: &Path) -> Result<()>;
+
+    /// File name of the executable generated for an alias
+    fn file_name(&self, alias_name: &str) -> String;
+
+    /// Alias name an executable file belongs to, if it looks like one of ours
+    fn alias_name_from_file(&self, path: &Path) -> Option<String>;
+
+    /// Exact contents `generate` writes for an alias, used to detect stale shims
+    fn expected_contents(&self, alias: &Alias) -> Result<Vec<u8>>;
+}
+
+/// Shim binary embedded at build time (see build.rs)
+fn embedded_shim_bytes() -> &'static [u8] {
+    #[cfg(target_os = "windows")]
+    let shim_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/msc-shim.exe"));
+
+    #[cfg(not(target_os = "windows"))]
+    let shim_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/msc-shim"));
+
+    shim_bytes
 }
 
 /// Windows executable shim generator
@@ -34,9 +55,10 @@ impl WindowsExeGenerator {
         Ok(config_dir.join("msc").join("aliases").join("msc-shim.exe"))
     }
 
-    /// Ensure the shim executable exists, extract it if needed
+    /// Ensure the shim executable exists and matches this msc build, extract it if needed
     fn ensure_shim_exists(shim_path: &Path) -> Result<()> {
-        if shim_path.exists() {
+        let shim_bytes = embedded_shim_bytes();
+        if fs::read(shim_path).is_ok_and(|existing| existing == shim_bytes) {
             return Ok(());
         }
 
@@ -46,14 +68,7 @@ impl WindowsExeGenerator {
                 .with_context(|| format!("Failed to create directory: {:?}", parent))?;
         }
 
-        // Extract embedded shim
-        // The build.rs script compiles msc-shim and embeds it
-        #[cfg(target_os = "windows")]
-        let shim_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/msc-shim.exe"));
-
-        #[cfg(not(target_os = "windows"))]
-        let shim_bytes = include_bytes!(concat!(env!("OUT_DIR"), "/msc-shim"));
-
+        // Extract embedded shim (also replaces one left by an older msc version)
         fs::write(shim_path, shim_bytes)
             .with_context(|| format!("Failed to write shim to {:?}", shim_path))?;
 
@@ -86,6 +101,22 @@ impl AliasGenerator for WindowsExeGenerator {
 
         Ok(())
     }
+
+    fn file_name(&self, alias_name: &str) -> String {
+        format!("{}.exe", alias_name)
+    }
+
+    fn alias_name_from_file(&self, path: &Path) -> Option<String> {
+        let file_name = path.file_name()?.to_str()?;
+        let (stem, extension) = file_name.rsplit_once('.')?;
+        extension
+            .eq_ignore_ascii_case("exe")
+            .then(|| stem.to_string())
+    }
+
+    fn expected_contents(&self, _alias: &Alias) -> Result<Vec<u8>> {
+        Ok(embedded_shim_bytes().to_vec())
+    }
 }
 
 /// Unix shell script generator
@@ -122,10 +153,7 @@ impl AliasGenerator for UnixScriptGenerator {
         }
 
         // Create shell script
-        let script = format!(
-            "#!/bin/bash\n# Generated by msc - Alias: {}\nexec {} \"$@\"\n",
-            alias.name, alias.command
-        );
+        let script = self.expected_contents(alias)?;
 
         fs::write(&target_path, script)
             .with_context(|| format!("Failed to write script to {:?}", target_path))?;
@@ -167,6 +195,90 @@ impl AliasGenerator for UnixScriptGenerator {
 
         Ok(())
     }
+
+    fn file_name(&self, alias_name: &str) -> String {
+        alias_name.to_string()
+    }
+
+    fn alias_name_from_file(&self, path: &Path) -> Option<String> {
+        // Scripts have no extension and carry the header `generate` writes;
+        // any other file in the directory is not ours
+        let file_name = path.file_name()?.to_str()?;
+        if file_name.contains('.') {
+            return None;
+        }
+
+        let mut head = Vec::new();
+        fs::File::open(path)
+            .ok()?
+            .take(SCRIPT_HEADER_PEEK_LEN)
+            .read_to_end(&mut head)
+            .ok()?;
+        String::from_utf8_lossy(&head)
+            .lines()
+            .nth(1)
+            .is_some_and(|line| line.starts_with(SCRIPT_HEADER))
+            .then(|| file_name.to_string())
+    }
+
+    fn expected_contents(&self, alias: &Alias) -> Result<Vec<u8>> {
+        let mut script = format!("#!/bin/bash\n{}{}\n", SCRIPT_HEADER, alias.name);
+        if let Some(cwd) = &alias.cwd {
+            script.push_str(&format!("cd {} || exit 1\n", shell_quote(cwd)));
+        }
+        for (key, value) in &alias.env {
+            script.push_str(&format!("export {}={}\n", key, shell_quote(value)));
+        }
+        script.push_str(&format!("exec {}\n", script_command(&alias.command)));
+
+        Ok(script.into_bytes())
+    }
+}
+
+/// Second line of every generated script, followed by the alias name
+const SCRIPT_HEADER: &str = "# Generated by msc - Alias: ";
+
+/// Bytes read when checking a file for [`SCRIPT_HEADER`]
+const SCRIPT_HEADER_PEEK_LEN: u64 = 512;
+
+/// The alias command for a bash `exec` line
+///
+/// `{N}` becomes `"${N}"` and `{@}` becomes `"$@"`; arguments no placeholder
+/// used are passed after the command, like `msc-shim` does.
+fn script_command(command: &str) -> String {
+    let parts = parse_command_template(command);
+    if parts
+        .iter()
+        .all(|part| matches!(part, CommandPart::Literal(_)))
+    {
+        return format!("{} \"$@\"", command);
+    }
+
+    let mut line = String::new();
+    let mut used = 0;
+    let mut uses_all = false;
+    for part in parts {
+        match part {
+            CommandPart::Literal(text) => line.push_str(text),
+            CommandPart::Arg(n) => {
+                line.push_str(&format!("\"${{{}}}\"", n));
+                used = used.max(n);
+            }
+            CommandPart::AllArgs => {
+                line.push_str("\"$@\"");
+                uses_all = true;
+            }
+        }
+    }
+    if !uses_all {
+        line.push_str(&format!(" \"${{@:{}}}\"", used + 1));
+    }
+    line
+}
+
+/// Quote a value for a POSIX shell script (single quotes, escaping embedded ones)
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
 }
 
 /// Get the appropriate generator for the current platform
@@ -218,4 +330,36 @@ mod tests {
         let script_path = temp_dir.path().join("test");
         assert!(!script_path.exists());
     }
+
+    #[test]
+    fn test_unix_script_sets_cwd_and_env() {
+        let generator = UnixScriptGenerator::new();
+        let temp_dir = tempdir().unwrap();
+
+        let mut alias = Alias::new("srv".to_string(), "npm start".to_string()).unwrap();
+        alias.set_cwd(temp_dir.path().to_str().unwrap()).unwrap();
+        alias.set_env("GREETING=it's here").unwrap();
+
+        generator.generate(&alias, temp_dir.path()).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("srv")).unwrap();
+        let cwd = alias.cwd.as_deref().unwrap();
+        assert!(content.contains(&format!("cd '{}' || exit 1", cwd)));
+        assert!(content.contains("export GREETING='it'\\''s here'"));
+        assert!(content.ends_with("exec npm start \"$@\"\n"));
+    }
+
+    #[test]
+    fn test_script_command_maps_placeholders() {
+        assert_eq!(script_command("git status"), "git status \"$@\"");
+        assert_eq!(
+            script_command("git checkout {1}"),
+            "git checkout \"${1}\" \"${@:2}\""
+        );
+        assert_eq!(
+            script_command("cp {2} {1}"),
+            "cp \"${2}\" \"${1}\" \"${@:3}\""
+        );
+        assert_eq!(script_command("echo {@} done"), "echo \"$@\" done");
+    }
 }
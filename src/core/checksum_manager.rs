@@ -1,10 +1,119 @@
-use anyhow::{ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
+use sha1::Sha1;
 use sha2::{Digest, Sha256};
+use std::fmt;
 use std::fs::File;
-use std::io;
+use std::io::{self, Read};
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use xxhash_rust::xxh3::Xxh3;
 
-/// Manages SHA256 checksums for downloaded binaries
+/// Size of the chunks read between progress callbacks
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Hash algorithm used by [`ChecksumManager`]
+///
+/// `Sha256` is the default and the only one used to verify downloads.
+/// `Blake3` and `Xxh3` are much faster and suited for comparing local files;
+/// `Xxh3` is not cryptographic and must not be used for verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    #[default]
+    Sha256,
+    Sha1,
+    Blake3,
+    Xxh3,
+}
+
+impl Algorithm {
+    /// All supported algorithms
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::Sha256,
+        Algorithm::Sha1,
+        Algorithm::Blake3,
+        Algorithm::Xxh3,
+    ];
+
+    /// Lowercase name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Blake3 => "blake3",
+            Algorithm::Xxh3 => "xxh3",
+        }
+    }
+
+    /// Whether the algorithm is safe against deliberate collisions
+    pub fn is_cryptographic(&self) -> bool {
+        matches!(self, Algorithm::Sha256 | Algorithm::Blake3)
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.to_lowercase().replace('-', "");
+        Algorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == normalized)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown hash algorithm '{}' (supported: sha256, sha1, blake3, xxh3)",
+                    s
+                )
+            })
+    }
+}
+
+/// Incremental hasher for any [`Algorithm`]
+enum Hasher {
+    Sha256(Sha256),
+    Sha1(Sha1),
+    Blake3(Box<blake3::Hasher>),
+    Xxh3(Box<Xxh3>),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Sha1 => Hasher::Sha1(Sha1::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Xxh3 => Hasher::Xxh3(Box::new(Xxh3::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Blake3(h) => {
+                h.update(data);
+            }
+            Hasher::Xxh3(h) => h.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Sha256(h) => format!("{:x}", h.finalize()),
+            Hasher::Sha1(h) => format!("{:x}", h.finalize()),
+            Hasher::Blake3(h) => h.finalize().to_hex().to_string(),
+            Hasher::Xxh3(h) => format!("{:016x}", h.digest()),
+        }
+    }
+}
+
+/// Manages checksums for downloaded binaries (SHA256 by default)
 ///
 /// Provides verification and lookup of known checksums for tools like yt-dlp, ffmpeg, and wget.
 /// Checksums are obtained from official release pages and should be updated regularly.
@@ -20,15 +129,32 @@ pub struct ChecksumManager;
 impl ChecksumManager {
     /// Verify file matches expected SHA256 hash
     pub fn verify_file(path: &Path, expected_hash: &str) -> Result<()> {
-        let mut file = File::open(path)?;
-        let mut hasher = Sha256::new();
+        Self::verify_file_with_progress(path, expected_hash, Algorithm::Sha256, |_, _| {}, None)
+    }
 
-        io::copy(&mut file, &mut hasher)?;
+    /// Verify file matches expected hash using the given algorithm
+    pub fn verify_file_with(path: &Path, expected_hash: &str, algorithm: Algorithm) -> Result<()> {
+        Self::verify_file_with_progress(path, expected_hash, algorithm, |_, _| {}, None)
+    }
 
-        let hash = format!("{:x}", hasher.finalize());
+    /// Verify file matches expected hash, reporting progress
+    ///
+    /// See [`ChecksumManager::calculate_hash_with_progress`] for the callback
+    /// and cancellation semantics.
+    pub fn verify_file_with_progress<F>(
+        path: &Path,
+        expected_hash: &str,
+        algorithm: Algorithm,
+        on_progress: F,
+        cancel_flag: Option<&AtomicBool>,
+    ) -> Result<()>
+    where
+        F: FnMut(u64, u64),
+    {
+        let hash = Self::calculate_hash_with_progress(path, algorithm, on_progress, cancel_flag)?;
 
         ensure!(
-            hash == expected_hash,
+            hash.eq_ignore_ascii_case(expected_hash),
             "Hash mismatch!\n  Expected: {}\n  Got:      {}",
             expected_hash,
             hash
@@ -106,13 +232,83 @@ impl ChecksumManager {
     /// Calculate SHA256 hash of a file
     /// Useful for generating hashes of newly downloaded files
     pub fn calculate_hash(path: &Path) -> Result<String> {
+        Self::calculate_hash_with_progress(path, Algorithm::Sha256, |_, _| {}, None)
+    }
+
+    /// Calculate the hash of a file using the given algorithm
+    pub fn calculate_hash_with(path: &Path, algorithm: Algorithm) -> Result<String> {
+        Self::calculate_hash_with_progress(path, algorithm, |_, _| {}, None)
+    }
+
+    /// Calculate the hash of in-memory data using the given algorithm
+    pub fn hash_bytes(data: &[u8], algorithm: Algorithm) -> String {
+        let mut hasher = Hasher::new(algorithm);
+        hasher.update(data);
+        hasher.finalize_hex()
+    }
+
+    /// Calculate the hash of a file, reporting progress
+    ///
+    /// # Arguments
+    /// * `algorithm` - Hash algorithm to use
+    /// * `on_progress` - Callback called with (bytes hashed, total bytes) after each chunk
+    /// * `cancel_flag` - When set to `true`, hashing stops and an error is returned
+    pub fn calculate_hash_with_progress<F>(
+        path: &Path,
+        algorithm: Algorithm,
+        mut on_progress: F,
+        cancel_flag: Option<&AtomicBool>,
+    ) -> Result<String>
+    where
+        F: FnMut(u64, u64),
+    {
         let mut file = File::open(path)?;
-        let mut hasher = Sha256::new();
+        let total = file.metadata()?.len();
+        let mut hasher = Hasher::new(algorithm);
+        let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+        let mut hashed: u64 = 0;
+
+        loop {
+            if cancel_flag.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+                bail!("Hashing cancelled: {}", path.display());
+            }
+
+            let read = match file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            hasher.update(&buffer[..read]);
+            hashed += read as u64;
+            on_progress(hashed, total.max(hashed));
+        }
+
+        Ok(hasher.finalize_hex())
+    }
+}
 
-        io::copy(&mut file, &mut hasher)?;
+/// Parse one `hash  filename` line of a checksum list (the sha256sum format)
+///
+/// The binary-mode marker (`hash *filename`) is accepted. Blank lines,
+/// `#` comments and lines that don't start with a hex hash return `None`.
+pub fn parse_checksum_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    if line.trim().is_empty() || line.trim_start().starts_with('#') {
+        return None;
+    }
 
-        Ok(format!("{:x}", hasher.finalize()))
+    let (hash, rest) = line.trim_start().split_once(' ')?;
+    if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
     }
+
+    let file = rest
+        .strip_prefix(' ')
+        .or_else(|| rest.strip_prefix('*'))
+        .unwrap_or(rest);
+    (!file.is_empty()).then_some((hash, file))
 }
 
 #[cfg(test)]
@@ -170,6 +366,133 @@ mod tests {
         assert_eq!(hash, expected, "Hash should match expected value");
     }
 
+    #[test]
+    fn test_progress_callback_is_monotonic() {
+        let temp = tempdir().unwrap();
+        let file_path = temp.path().join("large.bin");
+
+        let data = vec![0xABu8; HASH_CHUNK_SIZE * 3 + 123];
+        std::fs::write(&file_path, &data).unwrap();
+
+        let mut reports = Vec::new();
+        let hash = ChecksumManager::calculate_hash_with_progress(
+            &file_path,
+            Algorithm::Sha256,
+            |hashed, total| reports.push((hashed, total)),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(hash, ChecksumManager::calculate_hash(&file_path).unwrap());
+        assert!(reports.len() >= 4, "Should report once per chunk");
+        assert!(
+            reports.windows(2).all(|w| w[0].0 < w[1].0),
+            "Byte counts should increase monotonically"
+        );
+        assert_eq!(
+            reports.last().unwrap(),
+            &(data.len() as u64, data.len() as u64)
+        );
+    }
+
+    #[test]
+    fn test_cancel_flag_aborts_hashing() {
+        let temp = tempdir().unwrap();
+        let file_path = temp.path().join("large.bin");
+        std::fs::write(&file_path, vec![0u8; HASH_CHUNK_SIZE * 10]).unwrap();
+
+        let cancel = AtomicBool::new(false);
+        let mut calls = 0;
+        let result = ChecksumManager::calculate_hash_with_progress(
+            &file_path,
+            Algorithm::Sha256,
+            |_, _| {
+                calls += 1;
+                cancel.store(true, Ordering::Relaxed);
+            },
+            Some(&cancel),
+        );
+
+        assert!(result.is_err(), "Cancelled hashing should return an error");
+        assert_eq!(calls, 1, "Hashing should stop right after cancellation");
+    }
+
+    #[test]
+    fn test_known_hashes_for_each_algorithm() {
+        let cases = [
+            (
+                Algorithm::Sha256,
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (Algorithm::Sha1, "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            (
+                Algorithm::Blake3,
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (Algorithm::Xxh3, "2d06800538d394c2"),
+        ];
+
+        for (algorithm, expected) in cases {
+            assert_eq!(
+                ChecksumManager::hash_bytes(b"", algorithm),
+                expected,
+                "{}",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_hash_matches_bytes_hash() {
+        let temp = tempdir().unwrap();
+        let file_path = temp.path().join("test.bin");
+        std::fs::write(&file_path, b"test content").unwrap();
+
+        assert_eq!(
+            ChecksumManager::calculate_hash_with(&file_path, Algorithm::Sha1).unwrap(),
+            "1eebdf4fdc9fc7bf283031b93f9aef3338de9052"
+        );
+
+        for algorithm in Algorithm::ALL {
+            assert_eq!(
+                ChecksumManager::calculate_hash_with(&file_path, algorithm).unwrap(),
+                ChecksumManager::hash_bytes(b"test content", algorithm),
+                "{}",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_checksum_line() {
+        assert_eq!(
+            parse_checksum_line("abc123  file.iso"),
+            Some(("abc123", "file.iso"))
+        );
+        assert_eq!(
+            parse_checksum_line("ABC123 *bin/app.exe\r"),
+            Some(("ABC123", "bin/app.exe"))
+        );
+        assert_eq!(
+            parse_checksum_line("abc123  name with spaces.txt"),
+            Some(("abc123", "name with spaces.txt"))
+        );
+        assert_eq!(parse_checksum_line(""), None);
+        assert_eq!(parse_checksum_line("# comment"), None);
+        assert_eq!(parse_checksum_line("not-a-hash  file"), None);
+        assert_eq!(parse_checksum_line("abc123"), None);
+    }
+
+    #[test]
+    fn test_algorithm_from_str() {
+        assert_eq!("SHA256".parse::<Algorithm>().unwrap(), Algorithm::Sha256);
+        assert_eq!("sha-1".parse::<Algorithm>().unwrap(), Algorithm::Sha1);
+        assert_eq!("blake3".parse::<Algorithm>().unwrap(), Algorithm::Blake3);
+        assert_eq!("xxh3".parse::<Algorithm>().unwrap(), Algorithm::Xxh3);
+        assert!("md5".parse::<Algorithm>().is_err());
+        assert_eq!(Algorithm::default(), Algorithm::Sha256);
+    }
+
     #[test]
     fn test_get_expected_hash_yt_dlp() {
         // Test known yt-dlp version
@@ -0,0 +1,170 @@
+//! Privilege classification for clean paths
+//!
+//! `msc clean start` runs in two phases: user directories are cleaned first
+//! without elevation, then system directories are cleaned with administrator
+//! privileges. This module decides which phase a directory belongs to and
+//! records which rule produced that decision, so `msc clean explain` can show it.
+
+/// Cleanup phase a directory belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivilegeCategory {
+    /// Cleaned in phase 1, no elevation required
+    User,
+    /// Cleaned in phase 2, requires administrator privileges
+    System,
+}
+
+/// Rule that determined the category of a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrivilegeRule {
+    /// The path contains a known user-directory fragment
+    UserIndicator(String),
+    /// The path is inside the directory pointed to by a temp environment variable
+    TempEnvVar { var: String, value: String },
+    /// No user rule matched, so the path is treated as a system directory
+    NoUserRuleMatched,
+}
+
+/// Result of classifying a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivilegeExplanation {
+    pub category: PrivilegeCategory,
+    pub rule: PrivilegeRule,
+}
+
+/// Environment inputs used by the classification rules
+#[derive(Debug, Clone, Default)]
+pub struct PrivilegeEnv {
+    /// Current user name (from USERNAME or USER)
+    pub username: String,
+    /// Temp environment variables as (name, value), e.g. ("TEMP", "C:\\Users\\me\\AppData\\Local\\Temp")
+    pub temp_vars: Vec<(String, String)>,
+}
+
+impl PrivilegeEnv {
+    /// Read the classification inputs from the process environment
+    pub fn from_env() -> Self {
+        let username = std::env::var("USERNAME")
+            .ok()
+            .or_else(|| std::env::var("USER").ok())
+            .unwrap_or_default();
+
+        let temp_vars = ["TEMP", "TMP"]
+            .iter()
+            .filter_map(|var| {
+                std::env::var(var)
+                    .ok()
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (var.to_string(), value))
+            })
+            .collect();
+
+        Self {
+            username,
+            temp_vars,
+        }
+    }
+}
+
+/// Classify a path using the given environment, reporting the matched rule
+pub fn explain_with_env(path: &str, env: &PrivilegeEnv) -> PrivilegeExplanation {
+    let path_lower = path.to_lowercase();
+    let current_user = env.username.to_lowercase();
+
+    // User directories that don't require admin
+    let user_indicators = [
+        format!("\\users\\{}\\appdata", current_user),
+        format!("\\users\\{}\\temp", current_user),
+        format!("/home/{}/", current_user),
+        "\\appdata\\local\\temp".to_string(),
+        "\\appdata\\roaming\\temp".to_string(),
+    ];
+
+    for indicator in &user_indicators {
+        if path_lower.contains(indicator.as_str()) {
+            return PrivilegeExplanation {
+                category: PrivilegeCategory::User,
+                rule: PrivilegeRule::UserIndicator(indicator.clone()),
+            };
+        }
+    }
+
+    // Also check environment-based temp directories
+    for (var, value) in &env.temp_vars {
+        if path.starts_with(value.as_str()) {
+            return PrivilegeExplanation {
+                category: PrivilegeCategory::User,
+                rule: PrivilegeRule::TempEnvVar {
+                    var: var.clone(),
+                    value: value.clone(),
+                },
+            };
+        }
+    }
+
+    PrivilegeExplanation {
+        category: PrivilegeCategory::System,
+        rule: PrivilegeRule::NoUserRuleMatched,
+    }
+}
+
+/// Classify a path using the current process environment
+pub fn explain(path: &str) -> PrivilegeExplanation {
+    explain_with_env(path, &PrivilegeEnv::from_env())
+}
+
+/// Check if a directory is a user directory (doesn't require admin privileges)
+pub fn is_user_directory(path: &str) -> bool {
+    explain(path).category == PrivilegeCategory::User
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_env() -> PrivilegeEnv {
+        PrivilegeEnv {
+            username: "alice".to_string(),
+            temp_vars: vec![("TMP".to_string(), "/var/tmp/alice-tmp".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_known_user_path() {
+        let explanation = explain_with_env("/home/alice/.cache/thumbnails", &test_env());
+        assert_eq!(explanation.category, PrivilegeCategory::User);
+        assert_eq!(
+            explanation.rule,
+            PrivilegeRule::UserIndicator("/home/alice/".to_string())
+        );
+
+        let explanation = explain_with_env("C:\\Users\\Alice\\AppData\\Local\\Temp", &test_env());
+        assert_eq!(explanation.category, PrivilegeCategory::User);
+        assert_eq!(
+            explanation.rule,
+            PrivilegeRule::UserIndicator("\\users\\alice\\appdata".to_string())
+        );
+    }
+
+    #[test]
+    fn test_system_path() {
+        for path in ["C:\\Windows\\Temp", "/tmp", "/home/bob/.cache"] {
+            let explanation = explain_with_env(path, &test_env());
+            assert_eq!(explanation.category, PrivilegeCategory::System, "{}", path);
+            assert_eq!(explanation.rule, PrivilegeRule::NoUserRuleMatched);
+        }
+    }
+
+    #[test]
+    fn test_env_var_temp_path() {
+        let explanation = explain_with_env("/var/tmp/alice-tmp/build", &test_env());
+        assert_eq!(explanation.category, PrivilegeCategory::User);
+        assert_eq!(
+            explanation.rule,
+            PrivilegeRule::TempEnvVar {
+                var: "TMP".to_string(),
+                value: "/var/tmp/alice-tmp".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! JSON report of a `clean start` run (`--report`)
+//!
+//! Mirrors the two cleanup phases: user directories (no admin required) and
+//! system directories (admin required). Each phase lists the directories it
+//! covers with the files found under them, plus the phase's [`CleanupStats`].
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::cleaner::{CleanupStats, FileRecord};
+
+/// Report written by `msc clean start --report <PATH>`
+#[derive(Debug, Serialize)]
+pub struct CleanReport {
+    /// Whether files were only listed (`would_delete`) instead of deleted
+    pub dry_run: bool,
+    pub user: PhaseReport,
+    pub system: PhaseReport,
+}
+
+/// One cleanup phase
+#[derive(Debug, Serialize)]
+pub struct PhaseReport {
+    /// `false` when the phase did not run (declined, cancelled, no privileges)
+    pub executed: bool,
+    pub directories: Vec<DirectoryReport>,
+    pub totals: CleanupStats,
+}
+
+/// A configured clean path and the files found under it
+#[derive(Debug, Serialize)]
+pub struct DirectoryReport {
+    pub path: String,
+    pub accessible: bool,
+    pub files: Vec<FileRecord>,
+}
+
+impl CleanReport {
+    /// Report where neither phase has run yet
+    pub fn new(dry_run: bool, user_directories: &[String], system_directories: &[String]) -> Self {
+        Self {
+            dry_run,
+            user: PhaseReport::not_executed(user_directories),
+            system: PhaseReport::not_executed(system_directories),
+        }
+    }
+
+    /// Write the report as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("Failed to write report to {:?}", path))
+    }
+}
+
+impl PhaseReport {
+    /// Phase that did not run: its directories are listed without files
+    pub fn not_executed(directories: &[String]) -> Self {
+        Self {
+            executed: false,
+            directories: directories
+                .iter()
+                .map(|path| DirectoryReport {
+                    path: path.clone(),
+                    accessible: true,
+                    files: Vec::new(),
+                })
+                .collect(),
+            totals: CleanupStats::default(),
+        }
+    }
+
+    /// Phase that ran, grouping the recorded files by the directory they were found in
+    pub fn executed(directories: &[String], mut stats: CleanupStats) -> Self {
+        let mut by_directory: HashMap<String, Vec<FileRecord>> = HashMap::new();
+        for record in std::mem::take(&mut stats.files) {
+            by_directory
+                .entry(record.directory.clone())
+                .or_default()
+                .push(record);
+        }
+
+        Self {
+            executed: true,
+            directories: directories
+                .iter()
+                .map(|path| DirectoryReport {
+                    path: path.clone(),
+                    accessible: !stats.inaccessible_dirs.contains(path),
+                    files: by_directory.remove(path).unwrap_or_default(),
+                })
+                .collect(),
+            totals: stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cleaner::FileOutcome;
+
+    fn record(directory: &str, name: &str, outcome: FileOutcome) -> FileRecord {
+        FileRecord {
+            directory: directory.to_string(),
+            path: format!("{}/{}", directory, name),
+            size: 10,
+            age_secs: Some(90_000),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_report_separates_phases_and_groups_files() {
+        let user_dirs = vec!["/home/u/tmp".to_string(), "/home/u/gone".to_string()];
+        let system_dirs = vec!["/tmp".to_string()];
+        let mut report = CleanReport::new(false, &user_dirs, &system_dirs);
+
+        let stats = CleanupStats {
+            total_files: 2,
+            deleted_files: 1,
+            skipped_files: 1,
+            inaccessible_dirs: vec!["/home/u/gone".to_string()],
+            files: vec![
+                record("/home/u/tmp", "a.log", FileOutcome::Deleted),
+                record("/home/u/tmp", "b.log", FileOutcome::Skipped),
+            ],
+            ..CleanupStats::default()
+        };
+        report.user = PhaseReport::executed(&user_dirs, stats);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["dry_run"], false);
+
+        let user = &json["user"];
+        assert_eq!(user["executed"], true);
+        assert_eq!(user["totals"]["deleted_files"], 1);
+        assert!(user["totals"].get("files").is_none());
+        assert_eq!(user["directories"][0]["path"], "/home/u/tmp");
+        assert_eq!(
+            user["directories"][0]["files"][0]["path"],
+            "/home/u/tmp/a.log"
+        );
+        assert_eq!(user["directories"][0]["files"][0]["outcome"], "deleted");
+        assert_eq!(user["directories"][0]["files"][1]["outcome"], "skipped");
+        assert_eq!(user["directories"][0]["files"][1]["age_secs"], 90_000);
+        assert_eq!(user["directories"][1]["accessible"], false);
+
+        let system = &json["system"];
+        assert_eq!(system["executed"], false);
+        assert_eq!(system["directories"][0]["path"], "/tmp");
+        assert!(system["directories"][0]["files"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+}
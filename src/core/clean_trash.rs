@@ -0,0 +1,358 @@
+//! Trash sessions for `clean start --trash`
+//!
+//! Instead of deleting, cleanup moves files into
+//! `~/.msc-trash/<session-id>/files/`, keeping their original directory
+//! structure, and writes a `manifest.json` mapping every trashed path back to
+//! its original location. `msc clean restore <session-id>` reads the manifest
+//! and moves everything back.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Name of the trash directory inside the home directory
+pub const TRASH_DIR_NAME: &str = ".msc-trash";
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// A path moved to the trash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub original: PathBuf,
+    pub trashed: PathBuf,
+    pub size: u64,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashManifest {
+    pub id: String,
+    /// RFC 3339 creation time
+    pub created_at: String,
+    pub entries: Vec<TrashEntry>,
+}
+
+impl TrashManifest {
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+}
+
+/// Result of [`restore_session`]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RestoreStats {
+    pub restored: usize,
+    /// Entries whose original location is occupied again (left in the trash)
+    pub conflicts: usize,
+    pub failed: usize,
+}
+
+/// One cleanup run moving files into the trash
+///
+/// Shared by both cleanup phases; entries are recorded as files are moved and
+/// the manifest is written by [`TrashSession::save`].
+pub struct TrashSession {
+    id: String,
+    dir: PathBuf,
+    created_at: String,
+    entries: Mutex<Vec<TrashEntry>>,
+}
+
+impl TrashSession {
+    /// Start a session under `~/.msc-trash`
+    pub fn create() -> Result<Self> {
+        Self::create_in(&default_trash_root()?)
+    }
+
+    /// Start a session under `trash_root`, named after the current time
+    pub fn create_in(trash_root: &Path) -> Result<Self> {
+        let now = chrono::Local::now();
+        let base_id = now.format("%Y%m%d-%H%M%S").to_string();
+
+        // Two runs within the same second get a numeric suffix
+        let mut id = base_id.clone();
+        let mut suffix = 2;
+        while trash_root.join(&id).exists() {
+            id = format!("{}-{}", base_id, suffix);
+            suffix += 1;
+        }
+
+        let dir = trash_root.join(&id);
+        fs::create_dir_all(dir.join("files"))
+            .with_context(|| format!("Failed to create trash directory {:?}", dir))?;
+
+        Ok(Self {
+            id,
+            dir,
+            created_at: now.to_rfc3339(),
+            entries: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of paths moved so far
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move a file or directory into the session, keeping its original path below `files/`
+    pub fn move_in(&self, path: &Path, size: u64) -> io::Result<()> {
+        let trashed = self.dir.join("files").join(relative_trash_path(path));
+        move_path(path, &trashed)?;
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(TrashEntry {
+                original: path.to_path_buf(),
+                trashed,
+                size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Write `manifest.json`; a session that trashed nothing is removed instead
+    pub fn save(&self) -> Result<()> {
+        let entries = self
+            .entries
+            .lock()
+            .map(|entries| entries.clone())
+            .unwrap_or_default();
+
+        if entries.is_empty() {
+            let _ = fs::remove_dir_all(&self.dir);
+            return Ok(());
+        }
+
+        let manifest = TrashManifest {
+            id: self.id.clone(),
+            created_at: self.created_at.clone(),
+            entries,
+        };
+        let path = self.dir.join(MANIFEST_FILE);
+        fs::write(&path, serde_json::to_string_pretty(&manifest)?)
+            .with_context(|| format!("Failed to write trash manifest {:?}", path))
+    }
+}
+
+/// `~/.msc-trash`
+pub fn default_trash_root() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    Ok(home.join(TRASH_DIR_NAME))
+}
+
+/// Manifests of the sessions under `trash_root`, newest first
+pub fn list_sessions(trash_root: &Path) -> Vec<TrashManifest> {
+    let Ok(entries) = fs::read_dir(trash_root) else {
+        return Vec::new();
+    };
+
+    let mut sessions: Vec<TrashManifest> = entries
+        .flatten()
+        .filter_map(|entry| read_manifest(&entry.path()).ok())
+        .collect();
+    sessions.sort_by(|a, b| b.id.cmp(&a.id));
+    sessions
+}
+
+/// Move every entry of a session back to its original location
+///
+/// Entries whose original path exists again are left in the trash and
+/// counted as conflicts. The session directory is removed once it is empty.
+pub fn restore_session(trash_root: &Path, session_id: &str) -> Result<RestoreStats> {
+    if session_id.is_empty() || session_id.contains(['/', '\\']) || session_id.contains("..") {
+        anyhow::bail!("Invalid trash session id: {}", session_id);
+    }
+
+    let session_dir = trash_root.join(session_id);
+    let manifest = read_manifest(&session_dir)
+        .with_context(|| format!("Trash session not found: {}", session_id))?;
+
+    let mut stats = RestoreStats::default();
+    let mut remaining = Vec::new();
+
+    for entry in &manifest.entries {
+        if entry.original.exists() {
+            stats.conflicts += 1;
+            remaining.push(entry.clone());
+            continue;
+        }
+
+        match move_path(&entry.trashed, &entry.original) {
+            Ok(()) => stats.restored += 1,
+            Err(e) => {
+                log::warn!("Failed to restore {:?}: {}", entry.original, e);
+                stats.failed += 1;
+                remaining.push(entry.clone());
+            }
+        }
+    }
+
+    if remaining.is_empty() {
+        fs::remove_dir_all(&session_dir)
+            .with_context(|| format!("Failed to remove trash session {:?}", session_dir))?;
+    } else {
+        let manifest = TrashManifest {
+            entries: remaining,
+            ..manifest
+        };
+        fs::write(
+            session_dir.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+    }
+
+    Ok(stats)
+}
+
+fn read_manifest(session_dir: &Path) -> Result<TrashManifest> {
+    let content = fs::read_to_string(session_dir.join(MANIFEST_FILE))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// `/tmp/a/b.txt` -> `tmp/a/b.txt`, `C:\Temp\a` -> `C/Temp/a`
+fn relative_trash_path(path: &Path) -> PathBuf {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => {
+                let prefix = prefix.as_os_str().to_string_lossy();
+                let cleaned: String = prefix.chars().filter(|c| c.is_alphanumeric()).collect();
+                if !cleaned.is_empty() {
+                    relative.push(cleaned);
+                }
+            }
+            Component::RootDir | Component::CurDir | Component::ParentDir => {}
+            Component::Normal(part) => relative.push(part),
+        }
+    }
+    relative
+}
+
+/// Rename `from` to `to`, copying and deleting when they are on different filesystems
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    // rename fails across filesystems (e.g. tmpfs /tmp -> home)
+    let metadata = fs::symlink_metadata(from)?;
+    if metadata.is_dir() {
+        copy_dir_all(from, to)?;
+        fs::remove_dir_all(from)
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    }
+}
+
+fn copy_dir_all(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_trash_path_drops_root() {
+        let path = Path::new("/tmp/cache/a.tmp");
+        assert_eq!(
+            relative_trash_path(path),
+            Path::new("tmp").join("cache").join("a.tmp")
+        );
+    }
+
+    #[test]
+    fn test_trash_and_restore_roundtrip() {
+        let work = tempfile::tempdir().unwrap();
+        let trash_root = work.path().join("trash");
+        let nested = work.path().join("temp").join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        let top = work.path().join("temp").join("a.tmp");
+        let deep = nested.join("b.log");
+        fs::write(&top, "aaa").unwrap();
+        fs::write(&deep, "bb").unwrap();
+
+        let session = TrashSession::create_in(&trash_root).unwrap();
+        session.move_in(&top, 3).unwrap();
+        session.move_in(&deep, 2).unwrap();
+        session.save().unwrap();
+        assert!(!top.exists());
+        assert!(!deep.exists());
+
+        let sessions = list_sessions(&trash_root);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, session.id());
+        assert_eq!(sessions[0].total_size(), 5);
+        assert!(sessions[0].entries[1]
+            .trashed
+            .ends_with(Path::new("nested").join("b.log")));
+
+        // The original location is occupied again: that entry stays trashed
+        fs::write(&top, "new").unwrap();
+        let stats = restore_session(&trash_root, session.id()).unwrap();
+        assert_eq!(
+            stats,
+            RestoreStats {
+                restored: 1,
+                conflicts: 1,
+                failed: 0
+            }
+        );
+        assert_eq!(fs::read_to_string(&deep).unwrap(), "bb");
+        assert_eq!(list_sessions(&trash_root)[0].entries.len(), 1);
+
+        fs::remove_file(&top).unwrap();
+        restore_session(&trash_root, session.id()).unwrap();
+        assert_eq!(fs::read_to_string(&top).unwrap(), "aaa");
+        assert!(!session.dir().exists(), "Empty sessions are removed");
+    }
+
+    #[test]
+    fn test_restore_rejects_unknown_or_unsafe_ids() {
+        let work = tempfile::tempdir().unwrap();
+        assert!(restore_session(work.path(), "20240101-000000").is_err());
+        assert!(restore_session(work.path(), "../etc").is_err());
+    }
+
+    #[test]
+    fn test_empty_session_leaves_nothing_behind() {
+        let work = tempfile::tempdir().unwrap();
+        let session = TrashSession::create_in(work.path()).unwrap();
+        assert!(session.is_empty());
+        session.save().unwrap();
+        assert!(!session.dir().exists());
+        assert!(list_sessions(work.path()).is_empty());
+    }
+}
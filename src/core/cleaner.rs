@@ -3,6 +3,12 @@
 //! This module provides functionality to scan and clean temporary files
 //! from system temporary directories.
 //!
+//! A `.mscignore` file at the root of a clean path (gitignore syntax) protects
+//! the files it matches; they are reported as skipped and never deleted.
+//!
+//! With a [`TrashSession`] set in [`TempCleaner::trash`], files are moved into
+//! the msc trash instead of being deleted and can be restored later.
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -21,8 +27,12 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use crate::core::clean_trash::TrashSession;
 use crate::core::Config;
+use crate::git::{is_gitignored, load_mscignore, MSCIGNORE_FILE};
 use anyhow::Result;
+use ignore::gitignore::Gitignore;
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -38,16 +48,22 @@ pub struct TempCleaner {
     pub directories: Vec<String>,
     pub min_age: Option<Duration>,
     pub max_age: Option<Duration>,
+    /// Only delete files with one of these extensions (case-insensitive, no dot)
+    pub extensions: Option<Vec<String>>,
+    /// Move files into this trash session instead of deleting them
+    pub trash: Option<Arc<TrashSession>>,
     pub cancel_flag: Arc<AtomicBool>,
 }
 
 /// Statistics from cleanup operations
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct CleanupStats {
     pub total_files: usize,
     pub total_size: u64,
     pub deleted_files: usize,
     pub deleted_size: u64,
+    /// Of `deleted_files`, how many were moved to the trash instead of deleted
+    pub trashed_files: usize,
     pub failed_files: usize,
     pub inaccessible_dirs: Vec<String>,
     // Error tracking fields
@@ -56,6 +72,37 @@ pub struct CleanupStats {
     pub other_errors: usize,
     // Cancellation tracking
     pub was_cancelled: bool,
+    /// Files visited by [`TempCleaner::clean_with_records`] (empty otherwise)
+    #[serde(skip)]
+    pub files: Vec<FileRecord>,
+}
+
+/// What cleanup did with a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileOutcome {
+    Deleted,
+    /// Moved to the msc trash (restorable)
+    Trashed,
+    /// Dry run: the file would have been deleted
+    WouldDelete,
+    /// Kept because it does not match the age filter
+    Skipped,
+    /// Deletion failed (permissions, file in use)
+    Failed,
+}
+
+/// A file visited during cleanup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileRecord {
+    /// Configured clean path the file was found under
+    #[serde(skip)]
+    pub directory: String,
+    pub path: String,
+    pub size: u64,
+    /// Seconds since the last modification (`None` if unknown)
+    pub age_secs: Option<u64>,
+    pub outcome: FileOutcome,
 }
 
 impl TempCleaner {
@@ -71,6 +118,8 @@ impl TempCleaner {
             directories,
             min_age: Some(Duration::from_secs(24 * 3600)), // Default: 24 hours
             max_age: None,
+            extensions: None,
+            trash: None,
             cancel_flag: Arc::new(AtomicBool::new(false)),
         })
     }
@@ -110,7 +159,15 @@ impl TempCleaner {
                 continue;
             }
 
-            count_files_recursive(path, &mut stats, self.min_age, self.max_age);
+            let mscignore = load_mscignore(path);
+            count_files_recursive(
+                path,
+                &mut stats,
+                &mscignore,
+                self.min_age,
+                self.max_age,
+                self.extensions.as_deref(),
+            );
         }
 
         stats
@@ -121,7 +178,27 @@ impl TempCleaner {
     /// # Arguments
     /// * `dry_run` - If true, only simulate deletion without actually deleting
     /// * `on_progress` - Callback function called with (processed, total) for each file
-    pub fn clean<F>(&self, dry_run: bool, mut on_progress: F) -> Result<CleanupStats>
+    pub fn clean<F>(&self, dry_run: bool, on_progress: F) -> Result<CleanupStats>
+    where
+        F: FnMut(usize, usize),
+    {
+        self.clean_impl(dry_run, false, on_progress)
+    }
+
+    /// Same as [`TempCleaner::clean`], also listing every visited file in [`CleanupStats::files`]
+    pub fn clean_with_records<F>(&self, dry_run: bool, on_progress: F) -> Result<CleanupStats>
+    where
+        F: FnMut(usize, usize),
+    {
+        self.clean_impl(dry_run, true, on_progress)
+    }
+
+    fn clean_impl<F>(
+        &self,
+        dry_run: bool,
+        record_files: bool,
+        mut on_progress: F,
+    ) -> Result<CleanupStats>
     where
         F: FnMut(usize, usize),
     {
@@ -132,15 +209,20 @@ impl TempCleaner {
             total_files: stats.total_files,
             deleted_files: 0,
             deleted_size: 0,
+            trashed_files: 0,
             failed_files: 0,
             skipped_files: 0,
             permission_errors: 0,
             other_errors: 0,
             dry_run,
+            trash: self.trash.as_deref(),
             on_progress: &mut on_progress,
             last_check: Instant::now(),
             check_interval: Duration::from_millis(100),
             throttle_delay: None,
+            record_files,
+            directory: String::new(),
+            files: Vec::new(),
         };
 
         for temp_dir in &self.directories {
@@ -156,11 +238,15 @@ impl TempCleaner {
                 continue;
             }
 
+            ctx.directory = temp_dir.clone();
+            let mscignore = load_mscignore(path);
             delete_files_recursive_with_tracking(
                 path,
                 &mut ctx,
+                &mscignore,
                 self.min_age,
                 self.max_age,
+                self.extensions.as_deref(),
                 &self.cancel_flag,
             );
         }
@@ -170,12 +256,14 @@ impl TempCleaner {
             total_size: stats.total_size,
             deleted_files: ctx.deleted_files,
             deleted_size: ctx.deleted_size,
+            trashed_files: ctx.trashed_files,
             failed_files: ctx.failed_files,
             inaccessible_dirs: stats.inaccessible_dirs,
             skipped_files: ctx.skipped_files,
             permission_errors: ctx.permission_errors,
             other_errors: ctx.other_errors,
             was_cancelled: self.cancel_flag.load(Ordering::Relaxed),
+            files: ctx.files,
         })
     }
 }
@@ -186,16 +274,9 @@ fn should_delete_file(
     min_age: Option<Duration>,
     max_age: Option<Duration>,
 ) -> bool {
-    // Get file modification time
-    let modified_time = match metadata.modified() {
-        Ok(time) => time,
-        Err(_) => return false, // Don't delete if we can't verify age
-    };
-
-    let now = SystemTime::now();
-    let age = match now.duration_since(modified_time) {
-        Ok(dur) => dur,
-        Err(_) => return false, // File from future? Don't delete
+    // Don't delete if we can't verify age (or the file is from the future)
+    let Some(age) = file_age(metadata) else {
+        return false;
     };
 
     // Check minimum age
@@ -215,11 +296,42 @@ fn should_delete_file(
     true
 }
 
+/// Check if a file passes the extension filter (every file does without one)
+fn matches_extension(path: &Path, extensions: Option<&[String]>) -> bool {
+    let Some(extensions) = extensions else {
+        return true;
+    };
+
+    let Some(ext) = path.extension().map(|e| e.to_string_lossy()) else {
+        return false;
+    };
+    extensions
+        .iter()
+        .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(&ext))
+}
+
+/// Check if `.mscignore` protects a path (the ignore file itself always is)
+fn is_protected(mscignore: &Option<Gitignore>, path: &Path, is_dir: bool) -> bool {
+    if mscignore.is_none() {
+        return false;
+    }
+    (!is_dir && path.file_name().is_some_and(|name| name == MSCIGNORE_FILE))
+        || is_gitignored(mscignore, path, is_dir)
+}
+
+/// Time since the file was last modified
+fn file_age(metadata: &fs::Metadata) -> Option<Duration> {
+    let modified_time = metadata.modified().ok()?;
+    SystemTime::now().duration_since(modified_time).ok()
+}
+
 fn count_files_recursive(
     dir: &Path,
     stats: &mut CleanupStats,
+    mscignore: &Option<Gitignore>,
     min_age: Option<Duration>,
     max_age: Option<Duration>,
+    extensions: Option<&[String]>,
 ) {
     match fs::read_dir(dir) {
         Ok(entries) => {
@@ -228,16 +340,29 @@ fn count_files_recursive(
                     Ok(entry) => {
                         match entry.metadata() {
                             Ok(metadata) => {
-                                if metadata.is_file() {
-                                    // Only count files that would be deleted based on age
-                                    if should_delete_file(&metadata, min_age, max_age) {
+                                if is_protected(mscignore, &entry.path(), metadata.is_dir()) {
+                                    if metadata.is_file() {
+                                        stats.skipped_files += 1;
+                                    }
+                                } else if metadata.is_file() {
+                                    // Only count files that would be deleted based on age and extension
+                                    if should_delete_file(&metadata, min_age, max_age)
+                                        && matches_extension(&entry.path(), extensions)
+                                    {
                                         stats.total_files += 1;
                                         stats.total_size += metadata.len();
                                     } else {
                                         stats.skipped_files += 1;
                                     }
                                 } else if metadata.is_dir() {
-                                    count_files_recursive(&entry.path(), stats, min_age, max_age);
+                                    count_files_recursive(
+                                        &entry.path(),
+                                        stats,
+                                        mscignore,
+                                        min_age,
+                                        max_age,
+                                        extensions,
+                                    );
                                 }
                             }
                             Err(e) => {
@@ -287,22 +412,50 @@ where
     total_files: usize,
     deleted_files: usize,
     deleted_size: u64,
+    trashed_files: usize,
     failed_files: usize,
     skipped_files: usize,
     permission_errors: usize,
     other_errors: usize,
     dry_run: bool,
+    /// Move files here instead of deleting them
+    trash: Option<&'a TrashSession>,
     on_progress: &'a mut F,
     last_check: Instant,
     check_interval: Duration,
     throttle_delay: Option<Duration>,
+    record_files: bool,
+    /// Clean path currently being processed
+    directory: String,
+    files: Vec<FileRecord>,
+}
+
+impl<F> DeleteContext<'_, F>
+where
+    F: FnMut(usize, usize),
+{
+    fn record(&mut self, path: &Path, metadata: &fs::Metadata, outcome: FileOutcome) {
+        if !self.record_files {
+            return;
+        }
+
+        self.files.push(FileRecord {
+            directory: self.directory.clone(),
+            path: path.to_string_lossy().to_string(),
+            size: metadata.len(),
+            age_secs: file_age(metadata).map(|age| age.as_secs()),
+            outcome,
+        });
+    }
 }
 
 fn delete_files_recursive_with_tracking<F>(
     dir: &Path,
     ctx: &mut DeleteContext<F>,
+    mscignore: &Option<Gitignore>,
     min_age: Option<Duration>,
     max_age: Option<Duration>,
+    extensions: Option<&[String]>,
     cancel_flag: &Arc<AtomicBool>,
 ) where
     F: FnMut(usize, usize),
@@ -329,30 +482,57 @@ fn delete_files_recursive_with_tracking<F>(
                     Ok(entry) => {
                         match entry.metadata() {
                             Ok(metadata) => {
-                                if metadata.is_file() {
+                                if is_protected(mscignore, &entry.path(), metadata.is_dir()) {
+                                    if metadata.is_file() {
+                                        ctx.skipped_files += 1;
+                                        ctx.record(&entry.path(), &metadata, FileOutcome::Skipped);
+                                    }
+                                } else if metadata.is_file() {
                                     ctx.processed += 1;
                                     let file_path = entry.path();
                                     let file_size = metadata.len();
 
                                     (ctx.on_progress)(ctx.processed, ctx.total_files);
 
-                                    // Check if file should be deleted based on age
-                                    if !should_delete_file(&metadata, min_age, max_age) {
+                                    // Check if file should be deleted based on age and extension
+                                    if !should_delete_file(&metadata, min_age, max_age)
+                                        || !matches_extension(&file_path, extensions)
+                                    {
                                         ctx.skipped_files += 1;
+                                        ctx.record(&file_path, &metadata, FileOutcome::Skipped);
                                         continue;
                                     }
 
                                     if ctx.dry_run {
                                         ctx.deleted_files += 1;
                                         ctx.deleted_size += file_size;
+                                        ctx.record(&file_path, &metadata, FileOutcome::WouldDelete);
                                     } else {
-                                        match fs::remove_file(&file_path) {
+                                        let (result, outcome) = match ctx.trash {
+                                            Some(trash) => (
+                                                trash.move_in(&file_path, file_size),
+                                                FileOutcome::Trashed,
+                                            ),
+                                            None => {
+                                                (fs::remove_file(&file_path), FileOutcome::Deleted)
+                                            }
+                                        };
+                                        match result {
                                             Ok(_) => {
                                                 ctx.deleted_files += 1;
                                                 ctx.deleted_size += file_size;
+                                                if outcome == FileOutcome::Trashed {
+                                                    ctx.trashed_files += 1;
+                                                }
+                                                ctx.record(&file_path, &metadata, outcome);
                                             }
                                             Err(e) => {
                                                 ctx.failed_files += 1;
+                                                ctx.record(
+                                                    &file_path,
+                                                    &metadata,
+                                                    FileOutcome::Failed,
+                                                );
                                                 if e.kind() == std::io::ErrorKind::PermissionDenied
                                                 {
                                                     ctx.permission_errors += 1;
@@ -375,8 +555,10 @@ fn delete_files_recursive_with_tracking<F>(
                                     delete_files_recursive_with_tracking(
                                         &entry.path(),
                                         ctx,
+                                        mscignore,
                                         min_age,
                                         max_age,
+                                        extensions,
                                         cancel_flag,
                                     );
                                 }
@@ -611,6 +793,147 @@ mod tests {
         assert_eq!(stats.deleted_files, 2, "Stats should show 2 files deleted");
     }
 
+    #[test]
+    fn test_clean_with_records_lists_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_path = temp_dir.path();
+        std::fs::write(test_path.join("old.txt"), "old").unwrap();
+
+        let mut cleaner = TempCleaner::new().unwrap();
+        cleaner.directories = vec![test_path.to_string_lossy().to_string()];
+        cleaner.min_age = None;
+
+        // Plain clean() does not collect records
+        assert!(cleaner.clean(true, |_, _| {}).unwrap().files.is_empty());
+
+        let stats = cleaner.clean_with_records(true, |_, _| {}).unwrap();
+        assert_eq!(stats.files.len(), 1);
+        let record = &stats.files[0];
+        assert_eq!(record.directory, cleaner.directories[0]);
+        assert!(record.path.ends_with("old.txt"));
+        assert_eq!(record.size, 3);
+        assert!(record.age_secs.is_some());
+        assert_eq!(record.outcome, FileOutcome::WouldDelete);
+
+        // Recent files are kept by the age filter and reported as skipped
+        cleaner.min_age = Some(Duration::from_secs(3600));
+        let stats = cleaner.clean_with_records(false, |_, _| {}).unwrap();
+        assert_eq!(stats.files[0].outcome, FileOutcome::Skipped);
+        assert!(test_path.join("old.txt").exists());
+
+        cleaner.min_age = None;
+        let stats = cleaner.clean_with_records(false, |_, _| {}).unwrap();
+        assert_eq!(stats.files[0].outcome, FileOutcome::Deleted);
+    }
+
+    #[test]
+    fn test_extension_filter_only_deletes_matching_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_path = temp_dir.path();
+        let nested = test_path.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let tmp = test_path.join("cache.TMP");
+        let log = nested.join("run.log");
+        let keep = test_path.join("notes.txt");
+        let no_ext = nested.join("README");
+        for file in [&tmp, &log, &keep, &no_ext] {
+            std::fs::write(file, "x").unwrap();
+        }
+
+        let mut cleaner = TempCleaner::new().unwrap();
+        cleaner.directories = vec![test_path.to_string_lossy().to_string()];
+        cleaner.min_age = None;
+        cleaner.extensions = Some(vec!["tmp".to_string(), ".log".to_string()]);
+
+        let scan = cleaner.scan();
+        assert_eq!(scan.total_files, 2);
+        assert_eq!(scan.skipped_files, 2);
+
+        let stats = cleaner.clean(false, |_, _| {}).unwrap();
+        assert_eq!(stats.deleted_files, 2);
+        assert_eq!(stats.skipped_files, 2);
+        assert!(!tmp.exists(), "Matching files at the top level are deleted");
+        assert!(!log.exists(), "Matching files are found at any depth");
+        assert!(keep.exists());
+        assert!(no_ext.exists());
+    }
+
+    #[test]
+    fn test_mscignore_protects_matching_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_path = temp_dir.path();
+        let session = test_path.join("session");
+        std::fs::create_dir_all(&session).unwrap();
+
+        let stale = test_path.join("stale.tmp");
+        let pinned = test_path.join("pinned.tmp");
+        let report = test_path.join("report.pdf");
+        let session_file = session.join("state.db");
+        for file in [&stale, &pinned, &report, &session_file] {
+            std::fs::write(file, "x").unwrap();
+        }
+        std::fs::write(
+            test_path.join(MSCIGNORE_FILE),
+            "# keep these\n*.pdf\n*.tmp\n!stale.tmp\nsession/\n",
+        )
+        .unwrap();
+
+        let mut cleaner = TempCleaner::new().unwrap();
+        cleaner.directories = vec![test_path.to_string_lossy().to_string()];
+        cleaner.min_age = None;
+
+        let scan = cleaner.scan();
+        assert_eq!(scan.total_files, 1);
+        // pinned.tmp, report.pdf and the .mscignore file itself
+        assert_eq!(scan.skipped_files, 3);
+
+        let stats = cleaner.clean(false, |_, _| {}).unwrap();
+        assert_eq!(stats.deleted_files, 1);
+        assert_eq!(stats.skipped_files, 3);
+        assert!(!stale.exists(), "Negated patterns are cleaned");
+        assert!(pinned.exists());
+        assert!(report.exists());
+        assert!(session_file.exists(), "Ignored directories are not entered");
+        assert!(test_path.join(MSCIGNORE_FILE).exists());
+    }
+
+    #[test]
+    fn test_trash_mode_moves_files_and_counts_them() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_path = temp_dir.path().join("temp");
+        std::fs::create_dir_all(test_path.join("sub")).unwrap();
+        let top = test_path.join("a.tmp");
+        let nested = test_path.join("sub").join("b.tmp");
+        std::fs::write(&top, "aaa").unwrap();
+        std::fs::write(&nested, "bb").unwrap();
+
+        let trash = Arc::new(TrashSession::create_in(&temp_dir.path().join("trash")).unwrap());
+        let mut cleaner = TempCleaner::new().unwrap();
+        cleaner.directories = vec![test_path.to_string_lossy().to_string()];
+        cleaner.min_age = None;
+        cleaner.trash = Some(trash.clone());
+
+        let stats = cleaner.clean_with_records(false, |_, _| {}).unwrap();
+        assert_eq!(stats.deleted_files, 2);
+        assert_eq!(stats.trashed_files, 2);
+        assert_eq!(stats.deleted_size, 5);
+        assert!(stats
+            .files
+            .iter()
+            .all(|file| file.outcome == FileOutcome::Trashed));
+        assert!(!top.exists());
+        assert!(!nested.exists());
+        assert_eq!(trash.len(), 2);
+
+        trash.save().unwrap();
+        let restored =
+            crate::core::clean_trash::restore_session(&temp_dir.path().join("trash"), trash.id())
+                .unwrap();
+        assert_eq!(restored.restored, 2);
+        assert_eq!(std::fs::read_to_string(&nested).unwrap(), "bb");
+    }
+
     #[test]
     fn test_cleanup_tracks_failed_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,9 +1,10 @@
+use crate::core::wget::{normalize_extension, DEFAULT_REJECTED_EXTENSIONS};
 use anyhow::{Context, Result};
 use log::warn;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use wincode_derive::{SchemaRead, SchemaWrite};
 
 #[derive(Debug, Default, Serialize, Deserialize, SchemaWrite, SchemaRead)]
@@ -39,6 +40,101 @@ pub struct Config {
     pub ignored_work_folders: Vec<String>,
     #[serde(default)]
     pub installation_method: Option<String>,
+    /// Extensions wget never downloads; `None` means the built-in defaults
+    #[serde(default)]
+    pub rejected_extensions: Option<Vec<String>>,
+    /// Folder names purged by work cache cleanup; `None` means the built-in defaults
+    #[serde(default)]
+    pub work_cache_folders: Option<Vec<String>>,
+    /// Command used by `msc work open`; `None` falls back to `$EDITOR` or `code`
+    #[serde(default)]
+    pub editor: Option<String>,
+    /// Tags per workspace name; kept when `work map` rebuilds the workspace list
+    #[serde(default)]
+    pub workspace_tags: HashMap<String, Vec<String>>,
+    /// `alias.allow_shell_operators`: lets `msc alias add --unsafe` store
+    /// commands with pipes, redirections and other shell operators
+    #[serde(default)]
+    pub alias_allow_shell_operators: bool,
+}
+
+/// Field layout of config files written before `rejected_extensions`
+///
+/// wincode stores fields in order without names, so a file in this layout
+/// does not decode as [`Config`] and is read through this struct instead.
+#[derive(Debug, Default, SchemaWrite, SchemaRead)]
+struct LegacyConfig {
+    work_path: Option<String>,
+    video_path: Option<String>,
+    yt_dlp_path: Option<String>,
+    yt_dlp_installed_by_msc: bool,
+    web_path: Option<String>,
+    ffmpeg_path: Option<String>,
+    ffmpeg_installed_by_msc: bool,
+    wget_path: Option<String>,
+    wget_installed_by_msc: bool,
+    workspaces: HashMap<String, String>,
+    default_paths: Vec<String>,
+    custom_paths: Vec<String>,
+    excluded_default_paths: Vec<String>,
+    ignored_work_folders: Vec<String>,
+    installation_method: Option<String>,
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Config {
+            work_path: legacy.work_path,
+            video_path: legacy.video_path,
+            yt_dlp_path: legacy.yt_dlp_path,
+            yt_dlp_installed_by_msc: legacy.yt_dlp_installed_by_msc,
+            web_path: legacy.web_path,
+            ffmpeg_path: legacy.ffmpeg_path,
+            ffmpeg_installed_by_msc: legacy.ffmpeg_installed_by_msc,
+            wget_path: legacy.wget_path,
+            wget_installed_by_msc: legacy.wget_installed_by_msc,
+            workspaces: legacy.workspaces,
+            default_paths: legacy.default_paths,
+            custom_paths: legacy.custom_paths,
+            excluded_default_paths: legacy.excluded_default_paths,
+            ignored_work_folders: legacy.ignored_work_folders,
+            installation_method: legacy.installation_method,
+            ..Config::default()
+        }
+    }
+}
+
+/// Setting name of [`Config::alias_allow_shell_operators`]
+pub const ALLOW_SHELL_OPERATORS: &str = "alias.allow_shell_operators";
+
+/// Editor launched when neither the config nor `$EDITOR` names one
+pub const DEFAULT_EDITOR: &str = "code";
+
+/// Cache folders cleaned in each work project until the user edits the list
+pub const DEFAULT_WORK_CACHE_FOLDERS: [&str; 3] = ["target", "dist", "node_modules"];
+
+/// Changes that resetting the clean paths would apply
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResetPreview {
+    /// Custom paths that will be removed
+    pub removed_custom_paths: Vec<String>,
+    /// Default paths that are currently excluded and will be restored
+    pub restored_default_paths: Vec<String>,
+}
+
+/// How `msc config import` combines an imported config with the current one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Union lists and maps; imported single values win when set
+    Merge,
+    /// Drop the current config and use the imported one as is
+    Replace,
+}
+
+impl ResetPreview {
+    pub fn is_empty(&self) -> bool {
+        self.removed_custom_paths.is_empty() && self.restored_default_paths.is_empty()
+    }
 }
 
 impl Config {
@@ -55,14 +151,21 @@ impl Config {
                 warn!("Config file is empty, using default configuration");
                 Config::default()
             } else {
-                match wincode::deserialize::<Config>(&data) {
+                match Self::decode(&data) {
                     Ok(config) => config,
                     Err(e) => {
+                        // Keep the unreadable file: the next save() would overwrite it
+                        let backup_path = config_path.with_extension("bin.bak");
+                        fs::copy(&config_path, &backup_path).with_context(|| {
+                            format!(
+                                "Failed to back up unreadable config file: {:?}",
+                                config_path
+                            )
+                        })?;
                         warn!(
                             "Failed to deserialize config file with wincode ({}). \
-                             Using default configuration. \
-                             This often happens if the Config struct changed.",
-                            e
+                             Using default configuration; the old file was saved to {:?}.",
+                            e, backup_path
                         );
                         Config::default()
                     }
@@ -75,6 +178,17 @@ impl Config {
         Ok(config)
     }
 
+    /// Decode a wincode config file, upgrading files written before the
+    /// fields after `installation_method` were added
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        match wincode::deserialize::<Config>(data) {
+            Ok(config) => Ok(config),
+            Err(e) => wincode::deserialize::<LegacyConfig>(data)
+                .map(Config::from)
+                .map_err(|_| anyhow::anyhow!("{}", e)),
+        }
+    }
+
     pub fn sync_default_paths(&mut self) {
         use crate::platform::get_default_temp_directories;
         self.default_paths = get_default_temp_directories();
@@ -97,6 +211,148 @@ impl Config {
         Ok(())
     }
 
+    /// Whole config as pretty JSON, for `msc config export`
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize config to JSON")
+    }
+
+    /// Parse a config written by [`Config::to_json`]
+    pub fn from_json(data: &str) -> Result<Self> {
+        let mut config: Config = serde_json::from_str(data).context("Invalid config JSON")?;
+        config.sync_default_paths();
+        Ok(config)
+    }
+
+    /// Apply an imported config on top of this one
+    ///
+    /// Returns the imported settings that were not applied. Neither mode
+    /// changes `alias.allow_shell_operators`: that stays a local decision.
+    pub fn import(&mut self, imported: Config, mode: ImportMode) -> Vec<String> {
+        if mode == ImportMode::Replace {
+            let mut not_applied = Vec::new();
+            if imported.alias_allow_shell_operators && !self.alias_allow_shell_operators {
+                not_applied.push(ALLOW_SHELL_OPERATORS.to_string());
+            }
+            let allow_shell_operators = self.alias_allow_shell_operators;
+            *self = imported;
+            self.alias_allow_shell_operators = allow_shell_operators;
+            self.sync_default_paths();
+            return not_applied;
+        }
+
+        fn union(current: &mut Vec<String>, imported: Vec<String>) {
+            for item in imported {
+                if !current.contains(&item) {
+                    current.push(item);
+                }
+            }
+        }
+
+        let Config {
+            work_path,
+            video_path,
+            yt_dlp_path,
+            yt_dlp_installed_by_msc,
+            web_path,
+            ffmpeg_path,
+            ffmpeg_installed_by_msc,
+            wget_path,
+            wget_installed_by_msc,
+            workspaces,
+            default_paths: _,
+            custom_paths,
+            excluded_default_paths,
+            ignored_work_folders,
+            installation_method,
+            rejected_extensions,
+            work_cache_folders,
+            editor,
+            workspace_tags,
+            alias_allow_shell_operators,
+        } = imported;
+
+        // Tool paths and their "installed by msc" flags travel together
+        if yt_dlp_path.is_some() {
+            self.yt_dlp_path = yt_dlp_path;
+            self.yt_dlp_installed_by_msc = yt_dlp_installed_by_msc;
+        }
+        if ffmpeg_path.is_some() {
+            self.ffmpeg_path = ffmpeg_path;
+            self.ffmpeg_installed_by_msc = ffmpeg_installed_by_msc;
+        }
+        if wget_path.is_some() {
+            self.wget_path = wget_path;
+            self.wget_installed_by_msc = wget_installed_by_msc;
+        }
+
+        self.work_path = work_path.or(self.work_path.take());
+        self.video_path = video_path.or(self.video_path.take());
+        self.web_path = web_path.or(self.web_path.take());
+        self.installation_method = installation_method.or(self.installation_method.take());
+        self.editor = editor.or(self.editor.take());
+
+        let mut not_applied = Vec::new();
+        if alias_allow_shell_operators && !self.alias_allow_shell_operators {
+            not_applied.push(ALLOW_SHELL_OPERATORS.to_string());
+        }
+
+        for path in custom_paths {
+            self.add_clean_path(path);
+        }
+        union(&mut self.excluded_default_paths, excluded_default_paths);
+        union(&mut self.ignored_work_folders, ignored_work_folders);
+        if let Some(extensions) = rejected_extensions {
+            union(
+                self.rejected_extensions.get_or_insert_with(Vec::new),
+                extensions,
+            );
+        }
+        if let Some(folders) = work_cache_folders {
+            union(
+                self.work_cache_folders.get_or_insert_with(Vec::new),
+                folders,
+            );
+        }
+
+        self.workspaces.extend(workspaces);
+        for (name, tags) in workspace_tags {
+            union(self.workspace_tags.entry(name).or_default(), tags);
+        }
+
+        not_applied
+    }
+
+    /// Configured directories and tools that don't exist on this machine, as (setting, path)
+    pub fn missing_paths(&self) -> Vec<(String, String)> {
+        let mut referenced: Vec<(String, &String)> = Vec::new();
+        for (name, path) in [
+            ("work path", &self.work_path),
+            ("video path", &self.video_path),
+            ("web path", &self.web_path),
+            ("yt-dlp", &self.yt_dlp_path),
+            ("ffmpeg", &self.ffmpeg_path),
+            ("wget", &self.wget_path),
+        ] {
+            if let Some(path) = path {
+                referenced.push((name.to_string(), path));
+            }
+        }
+        for path in &self.custom_paths {
+            referenced.push(("clean path".to_string(), path));
+        }
+        let mut workspaces: Vec<_> = self.workspaces.iter().collect();
+        workspaces.sort();
+        for (name, path) in workspaces {
+            referenced.push((format!("workspace '{}'", name), path));
+        }
+
+        referenced
+            .into_iter()
+            .filter(|(_, path)| !Path::new(path.as_str()).exists())
+            .map(|(name, path)| (name, path.clone()))
+            .collect()
+    }
+
     fn get_config_path() -> Result<PathBuf> {
         let config_dir =
             dirs::config_dir().with_context(|| "Could not determine config directory")?;
@@ -112,6 +368,11 @@ impl Config {
         self.work_path.as_ref()
     }
 
+    /// Remove the configured work path, returning the previous value
+    pub fn clear_work_path(&mut self) -> Option<String> {
+        self.work_path.take()
+    }
+
     pub fn set_video_path(&mut self, path: String) {
         self.video_path = Some(path);
     }
@@ -120,6 +381,11 @@ impl Config {
         self.video_path.as_ref()
     }
 
+    /// Remove the configured video path, returning the previous value
+    pub fn clear_video_path(&mut self) -> Option<String> {
+        self.video_path.take()
+    }
+
     pub fn set_web_path(&mut self, path: String) {
         self.web_path = Some(path);
     }
@@ -128,6 +394,11 @@ impl Config {
         self.web_path.as_ref()
     }
 
+    /// Remove the configured web path, returning the previous value
+    pub fn clear_web_path(&mut self) -> Option<String> {
+        self.web_path.take()
+    }
+
     pub fn set_yt_dlp_path(&mut self, path: String) {
         self.yt_dlp_path = Some(path);
     }
@@ -188,6 +459,55 @@ impl Config {
         self.workspaces.clear();
     }
 
+    pub fn get_workspace_tags(&self, name: &str) -> &[String] {
+        self.workspace_tags
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn set_workspace_tags(&mut self, name: String, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.workspace_tags.remove(&name);
+        } else {
+            self.workspace_tags.insert(name, tags);
+        }
+    }
+
+    // Editor management
+
+    pub fn set_editor(&mut self, command: String) {
+        self.editor = Some(command);
+    }
+
+    pub fn get_editor(&self) -> Option<&String> {
+        self.editor.as_ref()
+    }
+
+    pub fn clear_editor(&mut self) -> Option<String> {
+        self.editor.take()
+    }
+
+    /// Editor to launch: the configured one, then `env_editor` (`$EDITOR`), then `code`
+    pub fn resolve_editor(&self, env_editor: Option<String>) -> String {
+        self.editor
+            .clone()
+            .or(env_editor)
+            .map(|editor| editor.trim().to_string())
+            .filter(|editor| !editor.is_empty())
+            .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+    }
+
+    // Alias settings
+
+    pub fn set_alias_allow_shell_operators(&mut self, allow: bool) {
+        self.alias_allow_shell_operators = allow;
+    }
+
+    pub fn alias_allow_shell_operators(&self) -> bool {
+        self.alias_allow_shell_operators
+    }
+
     // Clean paths management
 
     /// Get all active clean paths (default + custom, excluding removed defaults)
@@ -260,6 +580,19 @@ impl Config {
         false
     }
 
+    /// Preview what `reset_to_defaults` would change without applying it
+    pub fn reset_preview(&self) -> ResetPreview {
+        ResetPreview {
+            removed_custom_paths: self
+                .custom_paths
+                .iter()
+                .filter(|p| !self.default_paths.contains(p))
+                .cloned()
+                .collect(),
+            restored_default_paths: self.excluded_default_paths.clone(),
+        }
+    }
+
     /// Reset to only system defaults (clears all custom paths and exclusions)
     pub fn reset_to_defaults(&mut self) {
         self.custom_paths.clear();
@@ -314,6 +647,91 @@ impl Config {
         &self.ignored_work_folders
     }
 
+    // Work cache folder management
+
+    /// Cache folder names cleaned inside each work project (the defaults until edited)
+    pub fn get_work_cache_folders(&self) -> Vec<String> {
+        match self.work_cache_folders {
+            Some(ref folders) => folders.clone(),
+            None => DEFAULT_WORK_CACHE_FOLDERS
+                .iter()
+                .map(|folder| folder.to_string())
+                .collect(),
+        }
+    }
+
+    /// Add a folder name to the work cache cleanup list
+    pub fn add_work_cache_folder(&mut self, folder_name: String) -> bool {
+        let folders = self
+            .work_cache_folders
+            .get_or_insert_with(|| DEFAULT_WORK_CACHE_FOLDERS.map(String::from).to_vec());
+
+        if folders.contains(&folder_name) {
+            return false;
+        }
+
+        folders.push(folder_name);
+        true
+    }
+
+    /// Remove a folder name from the work cache cleanup list (including a default one)
+    pub fn remove_work_cache_folder(&mut self, folder_name: &str) -> bool {
+        let folders = self
+            .work_cache_folders
+            .get_or_insert_with(|| DEFAULT_WORK_CACHE_FOLDERS.map(String::from).to_vec());
+
+        if let Some(pos) = folders.iter().position(|f| f == folder_name) {
+            folders.remove(pos);
+            return true;
+        }
+        false
+    }
+
+    // Wget extension policy management
+
+    /// Extensions rejected for wget downloads (the defaults until the list is edited)
+    pub fn get_rejected_extensions(&self) -> Vec<String> {
+        match self.rejected_extensions {
+            Some(ref extensions) => extensions.clone(),
+            None => DEFAULT_REJECTED_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+        }
+    }
+
+    /// Add an extension to the reject list
+    pub fn add_rejected_extension(&mut self, ext: &str) -> bool {
+        let ext = normalize_extension(ext);
+        if ext.is_empty() {
+            return false;
+        }
+
+        let extensions = self
+            .rejected_extensions
+            .get_or_insert_with(|| DEFAULT_REJECTED_EXTENSIONS.map(String::from).to_vec());
+        if extensions.contains(&ext) {
+            return false;
+        }
+
+        extensions.push(ext);
+        true
+    }
+
+    /// Remove an extension from the reject list (including a default one)
+    pub fn remove_rejected_extension(&mut self, ext: &str) -> bool {
+        let ext = normalize_extension(ext);
+        let extensions = self
+            .rejected_extensions
+            .get_or_insert_with(|| DEFAULT_REJECTED_EXTENSIONS.map(String::from).to_vec());
+
+        if let Some(pos) = extensions.iter().position(|e| *e == ext) {
+            extensions.remove(pos);
+            return true;
+        }
+        false
+    }
+
     // Installation method management
 
     /// Set the installation method (Manual, Winget, Chocolatey, Homebrew, Cargo)
@@ -326,3 +744,143 @@ impl Config {
         self.installation_method.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(custom: &[&str], work: Option<&str>) -> Config {
+        Config {
+            custom_paths: custom.iter().map(|p| p.to_string()).collect(),
+            work_path: work.map(String::from),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_legacy_layout() {
+        let mut workspaces = HashMap::new();
+        workspaces.insert("msc".to_string(), "/home/me/projects/msc".to_string());
+        let legacy = LegacyConfig {
+            work_path: Some("/home/me/projects".to_string()),
+            wget_path: Some("/usr/bin/wget".to_string()),
+            workspaces,
+            custom_paths: vec!["/tmp/cache".to_string()],
+            ..LegacyConfig::default()
+        };
+        let data = wincode::serialize(&legacy).unwrap();
+
+        let config = Config::decode(&data).unwrap();
+
+        assert_eq!(config.work_path.as_deref(), Some("/home/me/projects"));
+        assert_eq!(config.wget_path.as_deref(), Some("/usr/bin/wget"));
+        assert_eq!(config.workspaces.len(), 1);
+        assert_eq!(config.custom_paths, vec!["/tmp/cache".to_string()]);
+        assert_eq!(config.rejected_extensions, None);
+        assert_eq!(config.editor, None);
+    }
+
+    #[test]
+    fn test_decode_current_layout() {
+        let mut config = sample(&["/tmp/cache"], Some("/home/me/projects"));
+        config.set_editor("vim".to_string());
+        let data = wincode::serialize(&config).unwrap();
+
+        let decoded = Config::decode(&data).unwrap();
+
+        assert_eq!(decoded.work_path, config.work_path);
+        assert_eq!(decoded.editor.as_deref(), Some("vim"));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut config = sample(&["/tmp/cache"], Some("/home/me/projects"));
+        config.add_workspace("msc".to_string(), "/home/me/projects/msc".to_string());
+        config.set_workspace_tags("msc".to_string(), vec!["rust".to_string()]);
+
+        let restored = Config::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(restored.custom_paths, config.custom_paths);
+        assert_eq!(restored.work_path, config.work_path);
+        assert_eq!(restored.get_workspaces(), config.get_workspaces());
+        assert_eq!(restored.get_workspace_tags("msc"), ["rust"]);
+        assert!(Config::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_import_merge_unions_clean_paths() {
+        let mut config = sample(&["/a", "/b"], Some("/old/work"));
+        config.set_editor("vim".to_string());
+
+        config.import(sample(&["/b", "/c"], None), ImportMode::Merge);
+        assert_eq!(config.custom_paths, ["/a", "/b", "/c"]);
+        // Unset imported values keep the current ones
+        assert_eq!(config.get_work_path().unwrap(), "/old/work");
+        assert_eq!(config.get_editor().unwrap(), "vim");
+
+        config.import(sample(&[], Some("/new/work")), ImportMode::Merge);
+        assert_eq!(config.get_work_path().unwrap(), "/new/work");
+    }
+
+    #[test]
+    fn test_import_merge_keeps_local_shell_operator_setting() {
+        let imported = || {
+            let mut imported = sample(&[], None);
+            imported.set_alias_allow_shell_operators(true);
+            imported
+        };
+        let mut config = sample(&[], None);
+
+        let not_applied = config.import(imported(), ImportMode::Merge);
+        assert!(!config.alias_allow_shell_operators());
+        assert_eq!(not_applied, ["alias.allow_shell_operators"]);
+
+        // Already allowed locally: nothing to report
+        config.set_alias_allow_shell_operators(true);
+        assert!(config.import(imported(), ImportMode::Merge).is_empty());
+        assert!(config.alias_allow_shell_operators());
+
+        // Replace takes everything else but keeps the local setting
+        let mut config = sample(&["/a"], None);
+        let not_applied = config.import(imported(), ImportMode::Replace);
+        assert!(!config.alias_allow_shell_operators());
+        assert_eq!(not_applied, ["alias.allow_shell_operators"]);
+        assert!(config.custom_paths.is_empty());
+
+        let mut config = sample(&[], None);
+        config.set_alias_allow_shell_operators(true);
+        assert!(config
+            .import(sample(&[], None), ImportMode::Replace)
+            .is_empty());
+        assert!(config.alias_allow_shell_operators());
+    }
+
+    #[test]
+    fn test_import_replace_overwrites() {
+        let mut config = sample(&["/a", "/b"], Some("/old/work"));
+        config.import(sample(&["/c"], None), ImportMode::Replace);
+        assert_eq!(config.custom_paths, ["/c"]);
+        assert_eq!(config.get_work_path(), None);
+    }
+
+    #[test]
+    fn test_missing_paths_reports_only_absent_entries() {
+        let existing = tempfile::TempDir::new().unwrap();
+        let existing = existing.path().to_string_lossy().to_string();
+        let mut config = sample(
+            &[existing.as_str(), "/definitely/not/here"],
+            Some(existing.as_str()),
+        );
+        config.add_workspace("gone".to_string(), "/no/such/project".to_string());
+
+        assert_eq!(
+            config.missing_paths(),
+            [
+                ("clean path".to_string(), "/definitely/not/here".to_string()),
+                (
+                    "workspace 'gone'".to_string(),
+                    "/no/such/project".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Disk usage totals for `msc du`
+//!
+//! A file can be measured two ways:
+//!
+//! - **Allocated** (the default): the disk blocks the file occupies. Sparse
+//!   files count only their written blocks, and small files are rounded up
+//!   to a whole block, so this is what the files actually cost on disk.
+//! - **Apparent**: the logical length of the file, as shown by `ls -l` or
+//!   `msc list -l`.
+//!
+//! Allocated sizes come from the block count on Unix. Other platforms don't
+//! expose it through the standard library, so both modes report the logical
+//! length there.
+
+use crate::core::FileScanner;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+/// Block size used by `st_blocks`, independent of the file system block size
+#[cfg(unix)]
+const STAT_BLOCK_SIZE: u64 = 512;
+
+/// How file sizes are measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeMode {
+    /// Disk space allocated to the file
+    Allocated,
+    /// Logical file length
+    Apparent,
+}
+
+impl SizeMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SizeMode::Allocated => "allocated",
+            SizeMode::Apparent => "apparent",
+        }
+    }
+}
+
+/// Size of a single file according to `mode`
+pub fn file_size(metadata: &Metadata, mode: SizeMode) -> u64 {
+    match mode {
+        SizeMode::Apparent => metadata.len(),
+        SizeMode::Allocated => allocated_size(metadata),
+    }
+}
+
+#[cfg(unix)]
+fn allocated_size(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * STAT_BLOCK_SIZE
+}
+
+#[cfg(not(unix))]
+fn allocated_size(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Whether `metadata` is another link to a file already counted
+#[cfg(unix)]
+fn is_repeated_hard_link(metadata: &Metadata, seen: &mut HashSet<(u64, u64)>) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1 && !seen.insert((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn is_repeated_hard_link(_metadata: &Metadata, _seen: &mut HashSet<(u64, u64)>) -> bool {
+    false
+}
+
+/// Total size of one direct child of the measured directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// Size of the file, or of every file below the directory
+    pub size: u64,
+}
+
+/// Measure every direct child of `path`, largest first
+///
+/// Directory sizes include everything below them. Symlinks count as the link
+/// itself and are never followed. Like `du`, a file with several hard links
+/// is counted once, under the first link scanned (Unix only).
+pub fn disk_usage(path: &Path, show_hidden: bool, mode: SizeMode) -> Result<Vec<UsageEntry>> {
+    let scanner = FileScanner::new(path)?;
+    let entries = scanner.scan_recursive(show_hidden, false)?;
+
+    let mut usage: HashMap<PathBuf, UsageEntry> = HashMap::new();
+    let mut seen_links = HashSet::new();
+    for entry in &entries {
+        let Ok(relative) = entry.path.strip_prefix(path) else {
+            continue;
+        };
+        let Some(top) = relative.components().next() else {
+            continue;
+        };
+        let top_path = path.join(top);
+
+        let total = usage.entry(top_path.clone()).or_insert_with(|| UsageEntry {
+            name: top.as_os_str().to_string_lossy().to_string(),
+            is_dir: relative.components().count() > 1 || entry.is_dir,
+            path: top_path,
+            size: 0,
+        });
+        if !entry.is_dir && !is_repeated_hard_link(&entry.metadata, &mut seen_links) {
+            total.size += file_size(&entry.metadata, mode);
+        }
+    }
+
+    let mut usage: Vec<UsageEntry> = usage.into_values().collect();
+    usage.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_directories_sum_their_contents() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        fs::write(dir.path().join("src/a.rs"), vec![b'a'; 300]).unwrap();
+        fs::write(dir.path().join("src/nested/b.rs"), vec![b'b'; 200]).unwrap();
+        fs::write(dir.path().join("README.md"), vec![b'r'; 100]).unwrap();
+
+        let usage = disk_usage(dir.path(), false, SizeMode::Apparent).unwrap();
+        let summary: Vec<(&str, bool, u64)> = usage
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.is_dir, entry.size))
+            .collect();
+        assert_eq!(summary, [("src", true, 500), ("README.md", false, 100)]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_allocated_size_differs_from_apparent() {
+        let dir = TempDir::new().unwrap();
+
+        // A sparse file has a large length but (almost) no blocks
+        let sparse = dir.path().join("sparse.img");
+        fs::File::create(&sparse)
+            .unwrap()
+            .set_len(64 * 1024 * 1024)
+            .unwrap();
+        let metadata = fs::metadata(&sparse).unwrap();
+        assert_eq!(file_size(&metadata, SizeMode::Apparent), 64 * 1024 * 1024);
+        assert!(file_size(&metadata, SizeMode::Allocated) < 64 * 1024 * 1024);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_hard_links_are_counted_once() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("data")).unwrap();
+        fs::write(dir.path().join("data/a.bin"), vec![b'a'; 300]).unwrap();
+        fs::hard_link(dir.path().join("data/a.bin"), dir.path().join("data/b.bin")).unwrap();
+
+        let usage = disk_usage(dir.path(), false, SizeMode::Apparent).unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].size, 300);
+    }
+}
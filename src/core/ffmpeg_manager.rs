@@ -6,7 +6,7 @@ use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::core::Config;
+use crate::core::{http, Config};
 
 #[derive(Default)]
 pub struct FFmpegManager {
@@ -52,13 +52,8 @@ impl FFmpegManager {
     pub fn get_latest_version() -> Result<String> {
         println!("{}", "Consultando última versión de ffmpeg...".cyan());
 
-        let client = reqwest::blocking::Client::builder()
-            .user_agent("msc-cli")
-            .build()?;
-
-        let response = client
-            .get("https://api.github.com/repos/BtbN/FFmpeg-Builds/releases/latest")
-            .send()?;
+        let response =
+            http::get("https://api.github.com/repos/BtbN/FFmpeg-Builds/releases/latest")?;
 
         if !response.status().is_success() {
             return Err(anyhow!(
@@ -95,8 +90,7 @@ impl FFmpegManager {
         println!("{}", "Descargando ffmpeg...".cyan());
         println!("{} {}", "URL:".dimmed(), download_url.dimmed());
 
-        let response =
-            reqwest::blocking::get(&download_url).context("Error al descargar ffmpeg")?;
+        let response = http::get(&download_url).context("Error al descargar ffmpeg")?;
 
         if !response.status().is_success() {
             return Err(anyhow!(
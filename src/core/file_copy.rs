@@ -0,0 +1,244 @@
+//! Resumable file copies for `msc cp` and `msc mv`
+//!
+//! Data is written to `<destination>.part` and renamed onto the destination
+//! only once it is complete, so an interrupted copy never leaves a truncated
+//! file under the final name. Copying again picks up where the `.part` file
+//! ended. Resuming assumes the source did not change in between; pass a
+//! checksum algorithm to [`verify_copy`] to make sure.
+
+use crate::core::checksum_manager::Algorithm;
+use crate::core::ChecksumManager;
+use anyhow::{bail, Context, Result};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Result of [`copy_file`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyStats {
+    /// Size of the copied file
+    pub total: u64,
+    /// Bytes already present in the `.part` file when the copy started
+    pub resumed_from: u64,
+}
+
+/// Where the data is written until the copy completes
+pub fn part_path(destination: &Path) -> PathBuf {
+    let mut name = destination
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_default();
+    name.push(".part");
+    destination.with_file_name(name)
+}
+
+/// `destination`, or `destination/<source name>` when it is an existing directory
+pub fn resolve_destination(source: &Path, destination: &Path) -> Result<PathBuf> {
+    if !destination.is_dir() {
+        return Ok(destination.to_path_buf());
+    }
+    let name = source
+        .file_name()
+        .with_context(|| format!("'{}' has no file name", source.display()))?;
+    Ok(destination.join(name))
+}
+
+/// Copy `source` to `destination` through a `.part` file, resuming a previous attempt
+///
+/// `on_progress` receives (bytes present, total bytes) after each chunk.
+/// When `cancel_flag` is set the copy stops with an error and the `.part`
+/// file is kept for the next attempt.
+pub fn copy_file<F>(
+    source: &Path,
+    destination: &Path,
+    mut on_progress: F,
+    cancel_flag: Option<&AtomicBool>,
+) -> Result<CopyStats>
+where
+    F: FnMut(u64, u64),
+{
+    if !source.is_file() {
+        bail!("'{}' is not a file", source.display());
+    }
+    let mut input =
+        File::open(source).with_context(|| format!("Failed to open '{}'", source.display()))?;
+    let total = input.metadata()?.len();
+
+    let part = part_path(destination);
+    let existing = part.metadata().map(|m| m.len()).unwrap_or(0);
+    // A part file longer than the source can't be a prefix of it
+    let resumed_from = if existing <= total { existing } else { 0 };
+
+    let mut output = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(resumed_from == 0)
+        .open(&part)
+        .with_context(|| format!("Failed to open '{}'", part.display()))?;
+    output.seek(SeekFrom::Start(resumed_from))?;
+    output.set_len(resumed_from)?;
+    input.seek(SeekFrom::Start(resumed_from))?;
+
+    let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+    let mut copied = resumed_from;
+    on_progress(copied, total);
+
+    loop {
+        if cancel_flag.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+            output.flush()?;
+            bail!(
+                "Copy cancelled after {} of {} bytes; run it again to resume",
+                copied,
+                total
+            );
+        }
+
+        let read = match input.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        output
+            .write_all(&buffer[..read])
+            .with_context(|| format!("Failed to write '{}'", part.display()))?;
+        copied += read as u64;
+        on_progress(copied, total.max(copied));
+    }
+
+    output.sync_all()?;
+    drop(output);
+    fs::rename(&part, destination).with_context(|| {
+        format!(
+            "Failed to move '{}' to '{}'",
+            part.display(),
+            destination.display()
+        )
+    })?;
+
+    Ok(CopyStats {
+        total: copied,
+        resumed_from,
+    })
+}
+
+/// Check that `copy` has the same content as `original`
+pub fn verify_copy(original: &Path, copy: &Path, algorithm: Algorithm) -> Result<()> {
+    let expected = ChecksumManager::calculate_hash_with(original, algorithm)
+        .with_context(|| format!("Failed to hash '{}'", original.display()))?;
+    let actual = ChecksumManager::calculate_hash_with(copy, algorithm)
+        .with_context(|| format!("Failed to hash '{}'", copy.display()))?;
+
+    if expected != actual {
+        bail!(
+            "Checksum mismatch for '{}' ({}): expected {}, got {}",
+            copy.display(),
+            algorithm,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_copy_with_verify() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("movie.mkv");
+        let destination = dir.path().join("copy.mkv");
+        fs::write(&source, sample_data(3 * COPY_CHUNK_SIZE + 17)).unwrap();
+
+        let mut last = (0, 0);
+        let stats = copy_file(
+            &source,
+            &destination,
+            |done, total| last = (done, total),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(stats.resumed_from, 0);
+        assert_eq!(stats.total, 3 * COPY_CHUNK_SIZE as u64 + 17);
+        assert_eq!(last, (stats.total, stats.total));
+        assert!(!part_path(&destination).exists());
+        verify_copy(&source, &destination, Algorithm::Sha256).unwrap();
+
+        // A corrupted copy fails verification
+        fs::write(&destination, b"corrupted").unwrap();
+        assert!(verify_copy(&source, &destination, Algorithm::Sha256).is_err());
+    }
+
+    #[test]
+    fn test_interrupted_copy_resumes_from_part_file() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("movie.mkv");
+        let destination = dir.path().join("copy.mkv");
+        let data = sample_data(4 * COPY_CHUNK_SIZE);
+        fs::write(&source, &data).unwrap();
+
+        // Cancel once two chunks are written
+        let cancel = AtomicBool::new(false);
+        let result = copy_file(
+            &source,
+            &destination,
+            |done, _| {
+                if done >= 2 * COPY_CHUNK_SIZE as u64 {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+            },
+            Some(&cancel),
+        );
+        assert!(result.is_err());
+        assert!(!destination.exists());
+        let part = part_path(&destination);
+        assert_eq!(
+            fs::metadata(&part).unwrap().len(),
+            2 * COPY_CHUNK_SIZE as u64
+        );
+
+        let stats = copy_file(&source, &destination, |_, _| {}, None).unwrap();
+        assert_eq!(stats.resumed_from, 2 * COPY_CHUNK_SIZE as u64);
+        assert!(!part.exists());
+        assert_eq!(fs::read(&destination).unwrap(), data);
+    }
+
+    #[test]
+    fn test_oversized_part_file_restarts_copy() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("small.txt");
+        let destination = dir.path().join("out.txt");
+        fs::write(&source, b"fresh").unwrap();
+        fs::write(part_path(&destination), b"stale and much longer").unwrap();
+
+        let stats = copy_file(&source, &destination, |_, _| {}, None).unwrap();
+        assert_eq!(stats.resumed_from, 0);
+        assert_eq!(fs::read(&destination).unwrap(), b"fresh");
+    }
+
+    #[test]
+    fn test_resolve_destination_into_directory() {
+        let dir = TempDir::new().unwrap();
+        let source = Path::new("/videos/movie.mkv");
+        assert_eq!(
+            resolve_destination(source, dir.path()).unwrap(),
+            dir.path().join("movie.mkv")
+        );
+        let file = dir.path().join("renamed.mkv");
+        assert_eq!(resolve_destination(source, &file).unwrap(), file);
+        assert_eq!(part_path(&file), dir.path().join("renamed.mkv.part"));
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! This module provides a `FileScanner` that can scan directories and
 //! return detailed information about files, including Git status and
-//! respecting .gitignore patterns.
+//! respecting .gitignore patterns. Entries matched by a `.mscignore` file
+//! in the scanned root are always left out. [`find_duplicates`] groups
+//! scanned files with identical content.
 //!
 //! # Examples
 //!
@@ -19,12 +21,17 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
-use crate::git::{load_git_status, load_gitignore, GitStatus};
-use crate::platform::is_hidden;
+use crate::core::checksum_manager::{Algorithm, ChecksumManager};
+use crate::git::{
+    is_gitignored, load_git_status, load_gitignore, load_mscignore, GitStatus, MSCIGNORE_FILE,
+};
+use crate::platform::{file_identity, is_hidden};
 use anyhow::Result;
 use ignore::gitignore::Gitignore;
-use std::collections::HashMap;
+use ignore::WalkBuilder;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 /// Scanner for listing files with detailed information
@@ -35,6 +42,7 @@ pub struct FileScanner {
     path: PathBuf,
     git_status: Option<HashMap<String, GitStatus>>,
     gitignore: Option<Gitignore>,
+    mscignore: Option<Gitignore>,
 }
 
 /// Entry from a directory scan
@@ -43,21 +51,83 @@ pub struct ScanEntry {
     pub name: String,
     pub path: PathBuf,
     pub is_dir: bool,
+    pub is_symlink: bool,
     pub size: u64,
     pub metadata: fs::Metadata,
 }
 
+/// Aggregated totals for a directory tree
+#[derive(Debug, Default, Clone)]
+pub struct DirectorySummary {
+    pub files: u64,
+    pub directories: u64,
+    pub symlinks: u64,
+    pub total_size: u64,
+    /// Extension (lowercase, without dot) -> (file count, total size)
+    pub extensions: HashMap<String, (u64, u64)>,
+}
+
+/// Key used in `DirectorySummary::extensions` for files without an extension
+pub const NO_EXTENSION: &str = "(none)";
+
+impl DirectorySummary {
+    /// Build a summary from scanned entries
+    pub fn from_entries(entries: &[ScanEntry]) -> Self {
+        let mut summary = Self::default();
+
+        for entry in entries {
+            if entry.is_symlink {
+                summary.symlinks += 1;
+            } else if entry.is_dir {
+                summary.directories += 1;
+            } else {
+                summary.files += 1;
+                summary.total_size += entry.size;
+
+                let ext = Path::new(&entry.name)
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_lowercase())
+                    .unwrap_or_else(|| NO_EXTENSION.to_string());
+                let stats = summary.extensions.entry(ext).or_insert((0, 0));
+                stats.0 += 1;
+                stats.1 += entry.size;
+            }
+        }
+
+        summary
+    }
+
+    /// Most common extensions by file count (ties broken by size, then name)
+    pub fn top_extensions(&self, limit: usize) -> Vec<(String, u64, u64)> {
+        let mut extensions: Vec<(String, u64, u64)> = self
+            .extensions
+            .iter()
+            .map(|(ext, (count, size))| (ext.clone(), *count, *size))
+            .collect();
+
+        extensions.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| b.2.cmp(&a.2))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        extensions.truncate(limit);
+        extensions
+    }
+}
+
 impl FileScanner {
     pub fn new(path: &Path) -> Result<Self> {
         log::debug!("Creating FileScanner for path: {:?}", path);
 
         let git_status = load_git_status(path);
         let gitignore = load_gitignore(path);
+        let mscignore = load_mscignore(path);
 
         Ok(Self {
             path: path.to_path_buf(),
             git_status,
             gitignore,
+            mscignore,
         })
     }
 
@@ -90,12 +160,17 @@ impl FileScanner {
 
             if let Ok(metadata) = entry.metadata() {
                 let is_dir = metadata.is_dir();
+                if is_gitignored(&self.mscignore, &entry.path(), is_dir) {
+                    continue;
+                }
+                let is_symlink = entry.file_type().map(|t| t.is_symlink()).unwrap_or(false);
                 let size = if is_dir { 0 } else { metadata.len() };
 
                 entries.push(ScanEntry {
                     name: file_name,
                     path: entry.path(),
                     is_dir,
+                    is_symlink,
                     size,
                     metadata,
                 });
@@ -111,6 +186,194 @@ impl FileScanner {
 
         Ok(entries)
     }
+
+    /// Recursively scan the directory tree (the root itself is not included)
+    ///
+    /// Symlinks are reported but not followed. `.mscignore` files are honored
+    /// in every scanned directory.
+    ///
+    /// # Arguments
+    /// * `show_hidden` - If true, include hidden files in results
+    /// * `respect_gitignore` - If true, skip entries matched by .gitignore files
+    pub fn scan_recursive(
+        &self,
+        show_hidden: bool,
+        respect_gitignore: bool,
+    ) -> Result<Vec<ScanEntry>> {
+        self.scan_recursive_with_depth(show_hidden, respect_gitignore, None)
+    }
+
+    /// Recursively scan the directory tree up to `max_depth` levels
+    ///
+    /// A depth of 1 returns only the direct children of the scanned directory.
+    pub fn scan_recursive_with_depth(
+        &self,
+        show_hidden: bool,
+        respect_gitignore: bool,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<ScanEntry>> {
+        let walker = WalkBuilder::new(&self.path)
+            .max_depth(max_depth)
+            .hidden(!show_hidden)
+            .git_ignore(respect_gitignore)
+            .git_exclude(respect_gitignore)
+            .git_global(false)
+            .parents(respect_gitignore)
+            .ignore(false)
+            .add_custom_ignore_filename(MSCIGNORE_FILE)
+            .require_git(false)
+            .follow_links(false)
+            .build();
+
+        let mut entries = Vec::new();
+
+        for result in walker {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::debug!("Skipping unreadable entry: {}", e);
+                    continue;
+                }
+            };
+
+            if entry.depth() == 0 {
+                continue;
+            }
+
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+            let is_symlink = entry.path_is_symlink();
+            let is_dir = !is_symlink && metadata.is_dir();
+            let size = if is_dir { 0 } else { metadata.len() };
+
+            entries.push(ScanEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: entry.path().to_path_buf(),
+                is_dir,
+                is_symlink,
+                size,
+                metadata,
+            });
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Files with identical content, found by [`find_duplicates`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// Size of each copy in bytes
+    pub size: u64,
+    /// SHA-256 of the shared content
+    pub hash: String,
+    /// Sorted paths; the first one is the copy kept by `--delete-extra`
+    pub paths: Vec<PathBuf>,
+}
+
+impl DuplicateGroup {
+    /// Bytes that would be freed by keeping a single copy
+    pub fn wasted_size(&self) -> u64 {
+        self.size * (self.paths.len() as u64).saturating_sub(1)
+    }
+}
+
+/// Bytes read per file by the cheap first pass of [`find_duplicates`]
+const DUPLICATE_PREFIX_LEN: u64 = 64 * 1024;
+
+/// Group scanned files with identical content
+///
+/// Files are bucketed by size first, so only same-size candidates are read.
+/// Hard links to the same file count once, under the first path scanned.
+/// Larger candidates are then grouped by an xxh3 hash of their first
+/// [`DUPLICATE_PREFIX_LEN`] bytes, and only files sharing a prefix are read
+/// in full to confirm the group with SHA-256. Directories, symlinks, empty
+/// and unreadable files are ignored. Groups are returned largest waste first.
+pub fn find_duplicates(entries: &[ScanEntry]) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<&ScanEntry>> = HashMap::new();
+    for entry in entries {
+        if !entry.is_dir && !entry.is_symlink && entry.size > 0 {
+            by_size.entry(entry.size).or_default().push(entry);
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (size, same_size) in by_size {
+        if same_size.len() < 2 {
+            continue;
+        }
+
+        // Hard links share one copy of the data: keep a single path per file
+        let mut identities = HashSet::new();
+        let candidates: Vec<&Path> = same_size
+            .into_iter()
+            .filter(|entry| {
+                file_identity(&entry.path, &entry.metadata)
+                    .is_none_or(|identity| identities.insert(identity))
+            })
+            .map(|entry| entry.path.as_path())
+            .collect();
+        if candidates.len() < 2 {
+            continue;
+        }
+
+        let prefix_groups = if size > DUPLICATE_PREFIX_LEN {
+            group_by_hash(&candidates, hash_prefix)
+        } else {
+            // The prefix would be the whole file: hash it once
+            vec![(String::new(), candidates)]
+        };
+
+        for (_, prefix_group) in prefix_groups {
+            for (hash, mut paths) in group_by_hash(&prefix_group, |path| {
+                ChecksumManager::calculate_hash_with(path, Algorithm::Sha256)
+            }) {
+                paths.sort();
+                groups.push(DuplicateGroup {
+                    size,
+                    hash,
+                    paths: paths.into_iter().map(Path::to_path_buf).collect(),
+                });
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| {
+        b.wasted_size()
+            .cmp(&a.wasted_size())
+            .then_with(|| a.paths.cmp(&b.paths))
+    });
+    groups
+}
+
+/// xxh3 hash of the first [`DUPLICATE_PREFIX_LEN`] bytes of a file
+fn hash_prefix(path: &Path) -> Result<String> {
+    let mut prefix = Vec::new();
+    fs::File::open(path)?
+        .take(DUPLICATE_PREFIX_LEN)
+        .read_to_end(&mut prefix)?;
+    Ok(ChecksumManager::hash_bytes(&prefix, Algorithm::Xxh3))
+}
+
+/// Hash every path and return the groups of two or more with the same hash
+fn group_by_hash<'a>(
+    paths: &[&'a Path],
+    hash: impl Fn(&Path) -> Result<String>,
+) -> Vec<(String, Vec<&'a Path>)> {
+    let mut by_hash: HashMap<String, Vec<&'a Path>> = HashMap::new();
+    for &path in paths {
+        match hash(path) {
+            Ok(hash) => by_hash.entry(hash).or_default().push(path),
+            Err(e) => log::debug!("Skipping unreadable file {:?}: {}", path, e),
+        }
+    }
+
+    by_hash
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .collect()
 }
 
 #[cfg(test)]
@@ -130,4 +393,106 @@ mod tests {
         // Should be able to scan current directory
         assert!(entries.is_ok());
     }
+
+    #[test]
+    fn test_scan_skips_mscignore_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("build").join("out")).unwrap();
+        for file in ["a.log", "keep.log", "main.rs", "build/out/app.bin"] {
+            fs::write(root.join(file), "x").unwrap();
+        }
+        fs::write(root.join(MSCIGNORE_FILE), "*.log\n!keep.log\nbuild/\n").unwrap();
+
+        let scanner = FileScanner::new(root).unwrap();
+        let names = |entries: Vec<ScanEntry>| -> Vec<String> {
+            let mut names: Vec<String> = entries.into_iter().map(|e| e.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(scanner.scan(false).unwrap()), ["keep.log", "main.rs"]);
+        assert_eq!(
+            names(scanner.scan_recursive(false, false).unwrap()),
+            ["keep.log", "main.rs"]
+        );
+    }
+    #[test]
+    fn test_find_duplicates_confirms_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("a.txt"), "same content").unwrap();
+        fs::write(root.join("nested").join("b.txt"), "same content").unwrap();
+        fs::write(root.join("c.txt"), "same content").unwrap();
+        // Same size, different content
+        fs::write(root.join("d.txt"), "diff content").unwrap();
+        fs::write(root.join("unique.txt"), "unique").unwrap();
+        fs::write(root.join("empty1"), "").unwrap();
+        fs::write(root.join("empty2"), "").unwrap();
+
+        let scanner = FileScanner::new(root).unwrap();
+        let entries = scanner.scan_recursive(false, false).unwrap();
+        let groups = find_duplicates(&entries);
+
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.size, 12);
+        assert_eq!(
+            group.paths,
+            vec![
+                root.join("a.txt"),
+                root.join("c.txt"),
+                root.join("nested").join("b.txt")
+            ]
+        );
+        assert_eq!(group.wasted_size(), 24);
+        assert_eq!(
+            group.hash,
+            ChecksumManager::hash_bytes(b"same content", Algorithm::Sha256)
+        );
+    }
+
+    #[test]
+    fn test_find_duplicates_large_files_share_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let mut content = vec![b'x'; DUPLICATE_PREFIX_LEN as usize + 16];
+        fs::write(root.join("a.bin"), &content).unwrap();
+        fs::write(root.join("b.bin"), &content).unwrap();
+        // Same size and prefix, different tail
+        *content.last_mut().unwrap() = b'y';
+        fs::write(root.join("c.bin"), &content).unwrap();
+
+        let scanner = FileScanner::new(root).unwrap();
+        let entries = scanner.scan_recursive(false, false).unwrap();
+        let groups = find_duplicates(&entries);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].paths,
+            vec![root.join("a.bin"), root.join("b.bin")]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_find_duplicates_ignores_hard_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a.txt"), "same content").unwrap();
+        fs::hard_link(root.join("a.txt"), root.join("b.txt")).unwrap();
+
+        let scanner = FileScanner::new(root).unwrap();
+        let entries = scanner.scan_recursive(false, false).unwrap();
+        assert!(find_duplicates(&entries).is_empty());
+
+        // A real copy is still reported once, next to one of the links
+        fs::write(root.join("c.txt"), "same content").unwrap();
+        let entries = scanner.scan_recursive(false, false).unwrap();
+        let groups = find_duplicates(&entries);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths.len(), 2);
+        assert!(groups[0].paths.contains(&root.join("c.txt")));
+    }
 }
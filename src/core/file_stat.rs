@@ -0,0 +1,152 @@
+//! Detailed metadata for a single file or directory
+//!
+//! This module gathers everything `msc list --stat` displays: size,
+//! permissions, timestamps, inode/hardlink information (Unix only),
+//! a MIME type guess and the Git status when inside a repository.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use msc::core::file_stat::FileStat;
+//! use std::path::Path;
+//!
+//! let stat = FileStat::from_path(Path::new("Cargo.toml"))?;
+//! println!("{} bytes, {}", stat.size, stat.mime_type);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::git::{get_git_status_for_path, GitStatus};
+use crate::utils::mime::guess_mime_type;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Metadata snapshot of a single filesystem entry
+#[derive(Debug, Clone)]
+pub struct FileStat {
+    pub path: PathBuf,
+    pub size: u64,
+    pub is_dir: bool,
+    pub is_symlink: bool,
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    /// Inode number (Unix only)
+    pub inode: Option<u64>,
+    /// Number of hard links (Unix only)
+    pub hard_links: Option<u64>,
+    pub mime_type: &'static str,
+    /// Git status, or None when the path is not inside a repository
+    pub git_status: Option<GitStatus>,
+    pub metadata: fs::Metadata,
+}
+
+impl FileStat {
+    /// Collect metadata for the given path
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let symlink_metadata = fs::symlink_metadata(path)
+            .with_context(|| format!("Cannot read metadata for '{}'", path.display()))?;
+        let is_symlink = symlink_metadata.file_type().is_symlink();
+        let metadata = fs::metadata(path).unwrap_or(symlink_metadata);
+
+        let is_dir = metadata.is_dir();
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mime_type = if is_dir {
+            "inode/directory"
+        } else {
+            guess_mime_type(&file_name)
+        };
+
+        let (inode, hard_links) = inode_info(&metadata);
+        let size = if is_dir { 0 } else { metadata.len() };
+        let created = metadata.created().ok();
+        let modified = metadata.modified().ok();
+        let accessed = metadata.accessed().ok();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            size,
+            is_dir,
+            is_symlink,
+            created,
+            modified,
+            accessed,
+            inode,
+            hard_links,
+            mime_type,
+            git_status: get_git_status_for_path(path),
+            metadata,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn inode_info(metadata: &fs::Metadata) -> (Option<u64>, Option<u64>) {
+    use std::os::unix::fs::MetadataExt;
+    (Some(metadata.ino()), Some(metadata.nlink()))
+}
+
+#[cfg(not(unix))]
+fn inode_info(_metadata: &fs::Metadata) -> (Option<u64>, Option<u64>) {
+    (None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::format_permissions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stat_reports_size_and_permissions() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("fixture.json");
+        fs::write(&file_path, b"{\"hello\": \"world\"}").unwrap();
+
+        let stat = FileStat::from_path(&file_path).unwrap();
+        let metadata = fs::metadata(&file_path).unwrap();
+
+        assert_eq!(stat.size, 18);
+        assert!(!stat.is_dir);
+        assert_eq!(
+            format_permissions(&stat.metadata),
+            format_permissions(&metadata)
+        );
+        assert_eq!(stat.mime_type, "application/json");
+        assert!(stat.modified.is_some());
+    }
+
+    #[test]
+    fn test_stat_directory() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let stat = FileStat::from_path(temp_dir.path()).unwrap();
+
+        assert!(stat.is_dir);
+        assert_eq!(stat.size, 0);
+        assert_eq!(stat.mime_type, "inode/directory");
+    }
+
+    #[test]
+    fn test_stat_missing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(FileStat::from_path(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stat_hard_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("a.txt");
+        fs::write(&file_path, b"data").unwrap();
+        fs::hard_link(&file_path, temp_dir.path().join("b.txt")).unwrap();
+
+        let stat = FileStat::from_path(&file_path).unwrap();
+        assert_eq!(stat.hard_links, Some(2));
+        assert!(stat.inode.is_some());
+    }
+}
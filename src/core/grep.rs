@@ -0,0 +1,168 @@
+//! Content search for `msc grep`
+//!
+//! Files are searched in parallel, line by line, with a regex. Binary files
+//! (see [`classify_file`]) are skipped.
+
+use crate::utils::mime::{classify_file, ContentClass};
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A line matching the search pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+    pub path: PathBuf,
+    /// 1-based line number
+    pub line_number: usize,
+    pub line: String,
+    /// Byte ranges of every match within `line`
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Search one file; binary files yield no matches
+pub fn search_file(path: &Path, regex: &Regex) -> io::Result<Vec<GrepMatch>> {
+    if classify_file(path)? == ContentClass::Binary {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read(path)?;
+
+    let text = String::from_utf8_lossy(&content);
+    let matches = text
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let ranges: Vec<(usize, usize)> = regex
+                .find_iter(line)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect();
+            (!ranges.is_empty()).then(|| GrepMatch {
+                path: path.to_path_buf(),
+                line_number: i + 1,
+                line: line.to_string(),
+                ranges,
+            })
+        })
+        .collect();
+
+    Ok(matches)
+}
+
+/// Search `paths` on `workers` threads
+///
+/// Unreadable files are skipped. Results are ordered by path, then line.
+pub fn search_files(paths: &[PathBuf], regex: &Regex, workers: usize) -> Vec<GrepMatch> {
+    let workers = workers.max(1).min(paths.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+
+                match search_file(path, regex) {
+                    Ok(matches) if !matches.is_empty() => results
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .extend(matches),
+                    Ok(_) => {}
+                    Err(e) => log::debug!("Skipping unreadable file {:?}: {}", path, e),
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then_with(|| a.line_number.cmp(&b.line_number))
+    });
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::RegexBuilder;
+    use tempfile::TempDir;
+
+    fn fixture() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src").join("main.rs"),
+            "fn main() {\n    // TODO: parse args\n    run();\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("notes.md"),
+            "todo list\nnothing here\nTODO twice TODO\n",
+        )
+        .unwrap();
+        // Binary by extension, and by content with an unknown extension
+        fs::write(root.join("logo.png"), b"\x89PNG TODO\x00\x01").unwrap();
+        fs::write(root.join("blob.dat"), b"TODO\x00\x00binary").unwrap();
+        dir
+    }
+
+    fn all_files(root: &Path) -> Vec<PathBuf> {
+        ["src/main.rs", "notes.md", "logo.png", "blob.dat"]
+            .iter()
+            .map(|file| root.join(file))
+            .collect()
+    }
+
+    #[test]
+    fn test_search_files_finds_pattern_across_fixture() {
+        let dir = fixture();
+        let regex = Regex::new("TODO").unwrap();
+        let matches = search_files(&all_files(dir.path()), &regex, 4);
+
+        let found: Vec<(String, usize)> = matches
+            .iter()
+            .map(|m| {
+                let relative = m.path.strip_prefix(dir.path()).unwrap();
+                (relative.to_string_lossy().replace('\\', "/"), m.line_number)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [("notes.md".to_string(), 3), ("src/main.rs".to_string(), 2)]
+        );
+        assert_eq!(matches[0].ranges, [(0, 4), (11, 15)]);
+    }
+
+    #[test]
+    fn test_search_skips_binary_files() {
+        let dir = fixture();
+        let regex = Regex::new("TODO").unwrap();
+        assert!(search_file(&dir.path().join("logo.png"), &regex)
+            .unwrap()
+            .is_empty());
+        assert!(search_file(&dir.path().join("blob.dat"), &regex)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_ignore_case() {
+        let dir = fixture();
+        let regex = RegexBuilder::new("todo")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        let matches = search_file(&dir.path().join("notes.md"), &regex).unwrap();
+        let lines: Vec<usize> = matches.iter().map(|m| m.line_number).collect();
+        assert_eq!(lines, [1, 3]);
+    }
+}
@@ -0,0 +1,488 @@
+//! Shared HTTP client configuration
+//!
+//! Every outgoing request (resource downloads, HEAD checks, update checks,
+//! tool installers) builds its client from [`HttpConfig`] so they agree on
+//! the User-Agent, timeouts, retries and proxy settings. Proxies come from
+//! `HTTP_PROXY`/`HTTPS_PROXY` (upper or lower case), with hosts listed in
+//! `NO_PROXY` bypassing them.
+//!
+//! [`RateLimiter`] caps the combined download speed of several transfers
+//! (`--rate-limit`).
+
+use anyhow::{Context, Result};
+use reqwest::blocking::{Client, ClientBuilder, RequestBuilder, Response};
+use std::io::{self, Read};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// User-Agent sent by msc's own requests (GitHub API, installers)
+pub const DEFAULT_USER_AGENT: &str = concat!("msc-cli/", env!("CARGO_PKG_VERSION"));
+
+/// User-Agent for requests that must look like a browser (web page assets)
+pub const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// Total request timeout, same as reqwest's blocking default
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Extra attempts after a connection error, timeout, 429 or 5xx
+const DEFAULT_RETRIES: u32 = 2;
+/// Wait before the first retry; doubled on each further attempt
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Proxy settings read from the environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxySettings {
+    pub http: Option<String>,
+    pub https: Option<String>,
+    /// Comma-separated hosts that bypass the proxy
+    pub no_proxy: Option<String>,
+}
+
+impl ProxySettings {
+    /// Read the proxy variables through `lookup` (upper case wins over lower case)
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name: &str| {
+            lookup(name)
+                .or_else(|| lookup(&name.to_lowercase()))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        Self {
+            http: var("HTTP_PROXY"),
+            https: var("HTTPS_PROXY"),
+            no_proxy: var("NO_PROXY"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.http.is_none() && self.https.is_none()
+    }
+
+    /// Single proxy for tools that take only one (HTTPS preferred)
+    pub fn preferred(&self) -> Option<&str> {
+        self.https.as_deref().or(self.http.as_deref())
+    }
+}
+
+/// Settings used to build HTTP clients
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub user_agent: String,
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    pub retries: u32,
+    pub proxy: ProxySettings,
+}
+
+impl Default for HttpConfig {
+    /// Default settings without any proxy
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            retries: DEFAULT_RETRIES,
+            proxy: ProxySettings::default(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Default settings with the proxies from the process environment
+    pub fn from_env() -> Self {
+        Self {
+            proxy: ProxySettings::from_lookup(|name| std::env::var(name).ok()),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Route HTTP and HTTPS through `proxy` (e.g. from `--proxy`)
+    ///
+    /// `None` keeps the current settings; `NO_PROXY` still applies either way.
+    pub fn with_proxy(mut self, proxy: Option<&str>) -> Self {
+        if let Some(url) = proxy.map(str::trim).filter(|url| !url.is_empty()) {
+            self.proxy.http = Some(url.to_string());
+            self.proxy.https = Some(url.to_string());
+        }
+        self
+    }
+
+    /// Client builder with these settings applied
+    ///
+    /// Callers can still adjust it (redirect policy, decompression) before
+    /// calling `build()`. Only the proxies in [`HttpConfig::proxy`] are used;
+    /// reqwest's own environment lookup is disabled so there is a single
+    /// source of truth.
+    pub fn builder(&self) -> Result<ClientBuilder> {
+        let mut builder = Client::builder()
+            .user_agent(&self.user_agent)
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout);
+
+        if self.proxy.is_empty() {
+            return Ok(builder.no_proxy());
+        }
+
+        let no_proxy = self
+            .proxy
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+        if let Some(url) = &self.proxy.http {
+            let proxy = reqwest::Proxy::http(url)
+                .with_context(|| format!("Invalid HTTP_PROXY: {}", url))?
+                .no_proxy(no_proxy.clone());
+            builder = builder.proxy(proxy);
+        }
+        if let Some(url) = &self.proxy.https {
+            let proxy = reqwest::Proxy::https(url)
+                .with_context(|| format!("Invalid HTTPS_PROXY: {}", url))?
+                .no_proxy(no_proxy);
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(builder)
+    }
+
+    pub fn client(&self) -> Result<Client> {
+        Ok(self.builder()?.build()?)
+    }
+
+    /// Send a request, retrying transient failures up to [`HttpConfig::retries`] times
+    ///
+    /// Connection errors, timeouts, `429` and `5xx` responses are retried
+    /// with exponential backoff; the last response or error is returned.
+    /// Only use it for idempotent requests (GET/HEAD).
+    pub fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut backoff = RETRY_BACKOFF;
+
+        for _ in 0..self.retries {
+            let Some(attempt) = request.try_clone() else {
+                break;
+            };
+
+            match attempt.send() {
+                Ok(response) if !is_retryable_status(response.status()) => return Ok(response),
+                Err(e) if !(e.is_connect() || e.is_timeout()) => return Err(e.into()),
+                _ => {}
+            }
+
+            std::thread::sleep(backoff);
+            backoff *= 2;
+        }
+
+        Ok(request.send()?)
+    }
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Time source for [`RateLimiter`], replaceable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Token bucket shared by concurrent downloads to cap their combined speed
+///
+/// The bucket holds up to one second of traffic. Reads take tokens as they
+/// arrive; when the bucket runs dry the reader sleeps until the debt is
+/// refilled, so the total rate converges to `bytes_per_sec`.
+pub struct RateLimiter {
+    bytes_per_sec: u64,
+    bucket: Mutex<Bucket>,
+    clock: Box<dyn Clock>,
+}
+
+struct Bucket {
+    /// Available bytes; negative while readers wait for a refill
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_sec: u64) -> Self {
+        Self::with_clock(bytes_per_sec, Box::new(SystemClock))
+    }
+
+    pub fn with_clock(bytes_per_sec: u64, clock: Box<dyn Clock>) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        let bucket = Bucket {
+            tokens: bytes_per_sec as f64,
+            last_refill: clock.now(),
+        };
+
+        Self {
+            bytes_per_sec,
+            bucket: Mutex::new(bucket),
+            clock,
+        }
+    }
+
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Account for `bytes` transferred, sleeping if the rate was exceeded
+    pub fn acquire(&self, bytes: u64) {
+        let rate = self.bytes_per_sec as f64;
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = self.clock.now();
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            bucket.last_refill = now;
+            bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(rate);
+            bucket.tokens -= bytes as f64;
+
+            if bucket.tokens < 0.0 {
+                Duration::from_secs_f64(-bucket.tokens / rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        if !wait.is_zero() {
+            self.clock.sleep(wait);
+        }
+    }
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("bytes_per_sec", &self.bytes_per_sec)
+            .finish()
+    }
+}
+
+/// Reader that charges every read to a [`RateLimiter`]
+pub struct ThrottledReader<'a, R> {
+    inner: R,
+    limiter: &'a RateLimiter,
+}
+
+impl<'a, R: Read> ThrottledReader<'a, R> {
+    pub fn new(inner: R, limiter: &'a RateLimiter) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<R: Read> Read for ThrottledReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.limiter.acquire(read as u64);
+        Ok(read)
+    }
+}
+
+/// GET a URL with the default settings, retrying transient failures
+pub fn get(url: &str) -> Result<Response> {
+    let config = HttpConfig::from_env();
+    config.send(config.client()?.get(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_proxy_env_vars_are_honored() {
+        let proxy = ProxySettings::from_lookup(lookup(&[
+            ("HTTP_PROXY", "http://proxy.local:3128"),
+            ("https_proxy", "http://secure.local:8080"),
+            ("NO_PROXY", "localhost,127.0.0.1"),
+        ]));
+
+        assert_eq!(proxy.http.as_deref(), Some("http://proxy.local:3128"));
+        assert_eq!(proxy.https.as_deref(), Some("http://secure.local:8080"));
+        assert_eq!(proxy.no_proxy.as_deref(), Some("localhost,127.0.0.1"));
+
+        let config = HttpConfig {
+            proxy,
+            ..HttpConfig::default()
+        };
+        assert!(config.client().is_ok());
+    }
+
+    #[test]
+    fn test_upper_case_proxy_wins_and_blank_is_ignored() {
+        let proxy = ProxySettings::from_lookup(lookup(&[
+            ("HTTPS_PROXY", "http://upper.local:1"),
+            ("https_proxy", "http://lower.local:2"),
+            ("HTTP_PROXY", "  "),
+        ]));
+
+        assert_eq!(proxy.https.as_deref(), Some("http://upper.local:1"));
+        assert_eq!(proxy.http, None);
+        assert!(ProxySettings::from_lookup(lookup(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_explicit_proxy_overrides_env_and_is_used() {
+        let config = HttpConfig {
+            proxy: ProxySettings::from_lookup(lookup(&[
+                ("HTTP_PROXY", "http://env.local:1"),
+                ("NO_PROXY", "intranet.local"),
+            ])),
+            ..HttpConfig::default()
+        };
+        assert_eq!(config.clone().with_proxy(None).proxy, config.proxy);
+        assert_eq!(config.proxy.preferred(), Some("http://env.local:1"));
+
+        // The proxy receives the absolute URL of the target
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 2048];
+            let n = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let config = config.with_proxy(Some(&proxy_url));
+        assert_eq!(config.proxy.https.as_deref(), Some(proxy_url.as_str()));
+        assert_eq!(config.proxy.no_proxy.as_deref(), Some("intranet.local"));
+
+        let request = config.client().unwrap().get("http://example.invalid/page");
+        let response = config.send(request).unwrap();
+        assert_eq!(response.status().as_u16(), 204);
+
+        let request = server.join().unwrap();
+        assert!(
+            request.starts_with("GET http://example.invalid/page HTTP/1.1"),
+            "{}",
+            request
+        );
+    }
+
+    /// Clock that advances only when slept on, recording the total wait
+    struct MockClock {
+        start: Instant,
+        elapsed: Arc<Mutex<Duration>>,
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.start + *self.elapsed.lock().unwrap()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.elapsed.lock().unwrap() += duration;
+        }
+    }
+
+    fn mock_limiter(bytes_per_sec: u64) -> (RateLimiter, Arc<Mutex<Duration>>) {
+        let elapsed = Arc::new(Mutex::new(Duration::ZERO));
+        let clock = MockClock {
+            start: Instant::now(),
+            elapsed: elapsed.clone(),
+        };
+        (
+            RateLimiter::with_clock(bytes_per_sec, Box::new(clock)),
+            elapsed,
+        )
+    }
+
+    #[test]
+    fn test_rate_limiter_delays_after_burst() {
+        let (limiter, elapsed) = mock_limiter(1000);
+
+        // The first second of traffic is allowed as a burst
+        limiter.acquire(1000);
+        assert_eq!(*elapsed.lock().unwrap(), Duration::ZERO);
+
+        // 3000 more bytes at 1000 B/s take 3 seconds, however they are split
+        for _ in 0..6 {
+            limiter.acquire(500);
+        }
+        let waited = elapsed.lock().unwrap().as_secs_f64();
+        assert!((waited - 3.0).abs() < 1e-6, "waited {}s", waited);
+    }
+
+    #[test]
+    fn test_throttled_reader_charges_limiter() {
+        let (limiter, elapsed) = mock_limiter(1024);
+        let data = vec![7u8; 4096];
+
+        let mut out = Vec::new();
+        ThrottledReader::new(data.as_slice(), &limiter)
+            .read_to_end(&mut out)
+            .unwrap();
+
+        assert_eq!(out, data);
+        let waited = elapsed.lock().unwrap().as_secs_f64();
+        assert!((waited - 3.0).abs() < 1e-6, "waited {}s", waited);
+    }
+
+    #[test]
+    fn test_default_user_agent_is_sent() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 2048];
+            let n = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let config = HttpConfig::default();
+        assert_eq!(config.user_agent, DEFAULT_USER_AGENT);
+        let response = config.send(config.client().unwrap().get(&url)).unwrap();
+        assert_eq!(response.status().as_u16(), 204);
+
+        let request = server.join().unwrap();
+        assert!(
+            request.contains(&format!(
+                "user-agent: {}",
+                DEFAULT_USER_AGENT.to_lowercase()
+            )),
+            "{}",
+            request
+        );
+    }
+}
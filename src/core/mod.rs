@@ -1,16 +1,29 @@
 // Core business logic module
 
 pub mod alias;
+pub mod alias_doctor;
 pub mod alias_generator;
+pub mod alias_schema;
 pub mod alias_validator;
+pub mod batch_exec;
+pub mod batch_rename;
 pub mod blacklist;
 pub mod checksum_manager;
+pub mod clean_privilege;
+pub mod clean_report;
+pub mod clean_trash;
 pub mod cleaner;
 pub mod config;
+pub mod disk_usage;
 pub mod ffmpeg_manager;
+pub mod file_copy;
 pub mod file_scanner;
+pub mod file_stat;
+pub mod grep;
+pub mod http;
 pub mod path_manager;
 pub mod path_validator;
+pub mod remove;
 pub mod system_info;
 pub mod system_monitor;
 pub mod update;
@@ -18,18 +31,20 @@ pub mod validation;
 pub mod wget;
 pub mod workspace;
 pub mod yt_dlp_manager;
+pub mod yt_dlp_progress;
 
 // Re-export commonly used items
-pub use alias::{Alias, AliasConfig};
+pub use alias::{Alias, AliasConfig, AliasEntry, AliasListing};
 pub use alias_generator::{get_generator, AliasGenerator};
 pub use blacklist::Blacklist;
 pub use checksum_manager::ChecksumManager;
 pub use cleaner::{CleanupStats, TempCleaner};
-pub use config::Config;
+pub use config::{Config, ImportMode};
 pub use ffmpeg_manager::FFmpegManager;
-pub use file_scanner::{FileScanner, ScanEntry};
+pub use file_scanner::{find_duplicates, DirectorySummary, DuplicateGroup, FileScanner, ScanEntry};
+pub use file_stat::FileStat;
 pub use path_manager::PathManager;
-pub use path_validator::{PathValidator, ValidationResult};
+pub use path_validator::{BatchValidation, PathValidator, ValidationResult};
 pub use wget::WgetManager;
 pub use workspace::WorkspaceManager;
 pub use yt_dlp_manager::YtDlpManager;
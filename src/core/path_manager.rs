@@ -1,13 +1,26 @@
 use anyhow::{Context, Result};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(unix)]
 use std::io::Write;
 
 pub struct PathManager;
 
+/// A modification `add_to_path` would make, shown by `alias init --dry-run`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathChange {
+    /// Append `content` to the shell rc file `file`
+    AppendToFile { file: PathBuf, content: String },
+    /// Set the registry value `name` under `key` to `value`
+    SetRegistryValue {
+        key: String,
+        name: String,
+        value: String,
+    },
+}
+
 impl PathManager {
     /// Get the aliases bin directory path
     pub fn get_aliases_bin_dir() -> Result<PathBuf> {
@@ -47,9 +60,19 @@ impl PathManager {
             .any(|p| p.trim() == bin_dir_str.as_ref()))
     }
 
-    /// Add bin directory to PATH (Windows implementation)
-    #[cfg(windows)]
+    /// Add bin directory to PATH
     pub fn add_to_path() -> Result<()> {
+        match Self::plan_add_to_path()? {
+            Some(change) => Self::apply(&change),
+            None => Ok(()),
+        }
+    }
+
+    /// What `add_to_path` would change, or `None` if PATH is already set up
+    ///
+    /// Reads the current state but writes nothing.
+    #[cfg(windows)]
+    pub fn plan_add_to_path() -> Result<Option<PathChange>> {
         use winreg::enums::*;
         use winreg::RegKey;
 
@@ -58,7 +81,7 @@ impl PathManager {
 
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
         let env = hkcu
-            .open_subkey_with_flags("Environment", KEY_READ | KEY_WRITE)
+            .open_subkey_with_flags("Environment", KEY_READ)
             .with_context(|| "Failed to open registry key")?;
 
         let current_path: String = env
@@ -67,7 +90,7 @@ impl PathManager {
 
         // Check if already in PATH
         if current_path.split(';').any(|p| p.trim() == bin_dir_str) {
-            return Ok(());
+            return Ok(None);
         }
 
         // Add to PATH
@@ -77,7 +100,29 @@ impl PathManager {
             format!("{};{}", current_path, bin_dir_str)
         };
 
-        env.set_value("Path", &new_path)
+        Ok(Some(PathChange::SetRegistryValue {
+            key: "HKEY_CURRENT_USER\\Environment".to_string(),
+            name: "Path".to_string(),
+            value: new_path,
+        }))
+    }
+
+    /// Write a planned change (Windows implementation)
+    #[cfg(windows)]
+    fn apply(change: &PathChange) -> Result<()> {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let PathChange::SetRegistryValue { name, value, .. } = change else {
+            anyhow::bail!("Unsupported PATH change on Windows: {:?}", change);
+        };
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let env = hkcu
+            .open_subkey_with_flags("Environment", KEY_READ | KEY_WRITE)
+            .with_context(|| "Failed to open registry key")?;
+
+        env.set_value(name, value)
             .with_context(|| "Failed to write PATH to registry")?;
 
         // Broadcast environment change
@@ -126,9 +171,11 @@ impl PathManager {
         Ok(())
     }
 
-    /// Add bin directory to PATH (Unix implementation)
+    /// What `add_to_path` would change, or `None` if PATH is already set up
+    ///
+    /// Reads the current state but writes nothing.
     #[cfg(unix)]
-    pub fn add_to_path() -> Result<()> {
+    pub fn plan_add_to_path() -> Result<Option<PathChange>> {
         let bin_dir = Self::get_aliases_bin_dir()?;
         let bin_dir_str = bin_dir.to_string_lossy();
 
@@ -137,37 +184,41 @@ impl PathManager {
 
         let home = dirs::home_dir().with_context(|| "Could not determine home directory")?;
 
-        let (rc_file, path_line) = if shell.contains("zsh") {
-            let rc = home.join(".zshrc");
-            let line = format!("\n# MSC aliases\nexport PATH=\"{}:$PATH\"\n", bin_dir_str);
-            (rc, line)
-        } else if shell.contains("fish") {
-            let rc = home.join(".config/fish/config.fish");
-            let line = format!("\n# MSC aliases\nset -gx PATH {} $PATH\n", bin_dir_str);
-            (rc, line)
-        } else {
-            // Default to bash
-            let rc = home.join(".bashrc");
-            let line = format!("\n# MSC aliases\nexport PATH=\"{}:$PATH\"\n", bin_dir_str);
-            (rc, line)
-        };
+        let (rc_file, path_line) = shell_rc_entry(&shell, &home, &bin_dir);
 
         // Check if already in rc file
         if rc_file.exists() {
             let content = fs::read_to_string(&rc_file)?;
             if content.contains(&bin_dir_str.to_string()) {
-                return Ok(());
+                return Ok(None);
             }
         }
 
+        Ok(Some(PathChange::AppendToFile {
+            file: rc_file,
+            content: path_line,
+        }))
+    }
+
+    /// Write a planned change (Unix implementation)
+    #[cfg(unix)]
+    fn apply(change: &PathChange) -> Result<()> {
+        let PathChange::AppendToFile {
+            file: rc_file,
+            content,
+        } = change
+        else {
+            anyhow::bail!("Unsupported PATH change on Unix: {:?}", change);
+        };
+
         // Append to rc file
         let mut file = fs::OpenOptions::new()
             .create(true)
             .append(true)
-            .open(&rc_file)
+            .open(rc_file)
             .with_context(|| format!("Failed to open {:?}", rc_file))?;
 
-        file.write_all(path_line.as_bytes())
+        file.write_all(content.as_bytes())
             .with_context(|| format!("Failed to write to {:?}", rc_file))?;
 
         log::info!("Added to PATH in {:?}", rc_file);
@@ -256,10 +307,53 @@ impl PathManager {
     }
 }
 
+/// The rc file for `shell` and the lines that put `bin_dir` on its PATH
+///
+/// Unknown shells get the bash setup.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn shell_rc_entry(shell: &str, home: &Path, bin_dir: &Path) -> (PathBuf, String) {
+    let bin_dir_str = bin_dir.to_string_lossy();
+
+    if shell.contains("zsh") {
+        let rc = home.join(".zshrc");
+        let line = format!("\n# MSC aliases\nexport PATH=\"{}:$PATH\"\n", bin_dir_str);
+        (rc, line)
+    } else if shell.contains("fish") {
+        let rc = home.join(".config/fish/config.fish");
+        let line = format!("\n# MSC aliases\nset -gx PATH {} $PATH\n", bin_dir_str);
+        (rc, line)
+    } else {
+        // Default to bash
+        let rc = home.join(".bashrc");
+        let line = format!("\n# MSC aliases\nexport PATH=\"{}:$PATH\"\n", bin_dir_str);
+        (rc, line)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shell_rc_entry() {
+        let home = Path::new("/home/user");
+        let bin = Path::new("/home/user/.config/msc/aliases/bin");
+
+        let (rc, line) = shell_rc_entry("/usr/bin/zsh", home, bin);
+        assert_eq!(rc, home.join(".zshrc"));
+        assert_eq!(
+            line,
+            "\n# MSC aliases\nexport PATH=\"/home/user/.config/msc/aliases/bin:$PATH\"\n"
+        );
+
+        let (rc, line) = shell_rc_entry("/usr/bin/fish", home, bin);
+        assert_eq!(rc, home.join(".config/fish/config.fish"));
+        assert!(line.contains("set -gx PATH /home/user/.config/msc/aliases/bin $PATH"));
+
+        let (rc, _) = shell_rc_entry("/bin/sh", home, bin);
+        assert_eq!(rc, home.join(".bashrc"));
+    }
+
     #[test]
     fn test_get_aliases_bin_dir() {
         let bin_dir = PathManager::get_aliases_bin_dir().unwrap();
@@ -1,3 +1,4 @@
+use crate::utils::path::expand_path;
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
@@ -172,6 +173,78 @@ impl PathValidator {
         Ok(ValidationResult::Safe(canonical))
     }
 
+    /// Validate a batch of paths (e.g. read from a file) without prompting
+    ///
+    /// `~` and environment variables are expanded first. Forbidden paths and
+    /// errors are always skipped; paths with warnings are only accepted when
+    /// `allow_warnings` is true.
+    pub fn validate_batch(&self, paths: &[String], allow_warnings: bool) -> BatchValidation {
+        let mut batch = BatchValidation::default();
+
+        for raw in paths {
+            let expanded = match expand_path(raw) {
+                Ok(expanded) => expanded,
+                Err(e) => {
+                    batch.skipped.push((raw.clone(), e.to_string()));
+                    continue;
+                }
+            };
+
+            match self.validate_path(Path::new(&expanded)) {
+                Ok(ValidationResult::Safe(canonical)) => batch.accepted.push(canonical),
+                Ok(ValidationResult::Warning(msg, canonical)) => {
+                    if allow_warnings {
+                        batch.accepted.push(canonical);
+                    } else {
+                        batch.skipped.push((raw.clone(), msg));
+                    }
+                }
+                Ok(ValidationResult::Forbidden(msg)) | Ok(ValidationResult::Error(msg)) => {
+                    batch.skipped.push((raw.clone(), msg))
+                }
+                Err(e) => batch.skipped.push((raw.clone(), e.to_string())),
+            }
+        }
+
+        batch
+    }
+
+    /// Why `path` must not be removed by `msc rm`, or `None` if it may be
+    ///
+    /// System directories and everything inside them are protected. The
+    /// directories above the home directory (like `/` or `C:\Users`) and the
+    /// home directory itself are only protected as a whole, so the user's own
+    /// files below them can still be removed. A symlink is judged by its own
+    /// location, not by its target.
+    pub fn check_removal(&self, path: &Path) -> Option<String> {
+        let resolved = match resolve_link_location(path) {
+            Ok(resolved) => resolved,
+            Err(e) => return Some(format!("Cannot resolve path: {}", e)),
+        };
+        let target = normalize_for_compare(&resolved.to_string_lossy());
+        let home = dirs::home_dir().map(|home| normalize_for_compare(&home.to_string_lossy()));
+
+        for forbidden in &self.forbidden_paths {
+            let forbidden = normalize_for_compare(forbidden);
+            if target == forbidden {
+                return Some(format!("{} is a protected location", resolved.display()));
+            }
+
+            let holds_home = home
+                .as_deref()
+                .is_some_and(|home| is_within(home, &forbidden));
+            if !holds_home && is_within(&target, &forbidden) {
+                return Some(format!(
+                    "{} is inside the protected system directory {}",
+                    resolved.display(),
+                    forbidden
+                ));
+            }
+        }
+
+        None
+    }
+
     fn is_safe_subdirectory(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy().to_lowercase();
 
@@ -229,6 +302,58 @@ pub enum ValidationResult {
     Error(String),
 }
 
+/// Result of validating a batch of paths
+#[derive(Debug, Default)]
+pub struct BatchValidation {
+    /// Canonical paths that passed validation
+    pub accepted: Vec<PathBuf>,
+    /// Rejected inputs with the reason they were skipped
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Canonical location of `path`; for a symlink, the link itself rather than its target
+fn resolve_link_location(path: &Path) -> std::io::Result<PathBuf> {
+    if !path.symlink_metadata()?.file_type().is_symlink() {
+        return path.canonicalize();
+    }
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.canonicalize()?,
+        _ => std::env::current_dir()?,
+    };
+    Ok(match path.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    })
+}
+
+/// Lowercased, backslash-separated, without the `\\?\` prefix or a trailing separator
+fn normalize_for_compare(path: &str) -> String {
+    path.to_lowercase()
+        .replace('/', "\\")
+        .replace("\\\\?\\", "")
+        .trim_end_matches('\\')
+        .to_string()
+}
+
+/// Whether normalized `path` is `dir` or lies below it
+fn is_within(path: &str, dir: &str) -> bool {
+    path == dir
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('\\'))
+}
+
+/// Parse a path list: one path per line, blank lines and `#` comments ignored
+pub fn parse_path_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -598,4 +723,39 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_check_removal_protects_system_directories() {
+        let validator = PathValidator::new();
+
+        assert!(validator.check_removal(Path::new("/")).is_some());
+        assert!(validator.check_removal(Path::new("/etc")).is_some());
+        assert!(validator.check_removal(Path::new("/usr/bin")).is_some());
+        if let Some(home) = dirs::home_dir() {
+            assert!(validator.check_removal(&home).is_some());
+        }
+    }
+
+    #[test]
+    fn test_check_removal_allows_regular_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("notes.txt");
+        std::fs::write(&file, "x").unwrap();
+
+        let validator = PathValidator::new();
+        assert_eq!(validator.check_removal(&file), None);
+        assert_eq!(validator.check_removal(temp_dir.path()), None);
+        assert!(validator
+            .check_removal(&temp_dir.path().join("missing"))
+            .is_some());
+    }
+
+    #[test]
+    fn test_is_within_respects_component_boundaries() {
+        assert!(is_within("\\usr\\bin", "\\usr"));
+        assert!(is_within("\\usr", "\\usr"));
+        assert!(!is_within("\\usr2\\bin", "\\usr"));
+        assert!(is_within("c:\\temp", "c:"));
+    }
 }
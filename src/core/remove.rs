@@ -0,0 +1,203 @@
+//! Safe removal for `msc rm`
+//!
+//! Paths go to the OS trash (recycle bin) unless permanent deletion is
+//! requested. Protected locations (see [`PathValidator::check_removal`]) are
+//! refused either way.
+
+use crate::core::PathValidator;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How `msc rm` disposes of a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoveMode {
+    /// Move to the OS trash so it can be restored
+    Trash,
+    /// Delete from disk
+    Permanent,
+}
+
+/// Performs the actual removal
+pub trait Remover {
+    /// Move `path` to the OS trash
+    fn trash(&self, path: &Path) -> Result<()>;
+    /// Delete `path` (recursively for directories)
+    fn delete(&self, path: &Path) -> Result<()>;
+}
+
+/// Remover backed by the OS trash and the filesystem
+pub struct SystemRemover;
+
+impl Remover for SystemRemover {
+    fn trash(&self, path: &Path) -> Result<()> {
+        trash::delete(path).map_err(|e| anyhow!("Failed to move to trash: {}", e))
+    }
+
+    fn delete(&self, path: &Path) -> Result<()> {
+        let is_dir = path
+            .symlink_metadata()
+            .map(|m| m.file_type().is_dir())
+            .unwrap_or(false);
+        let result = if is_dir {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        };
+        result.map_err(|e| anyhow!("Failed to delete: {}", e))
+    }
+}
+
+/// Outcome of [`remove_paths`]
+#[derive(Debug, Default)]
+pub struct RemoveSummary {
+    pub removed: Vec<PathBuf>,
+    /// Protected or missing paths that were not touched, with the reason
+    pub refused: Vec<(PathBuf, String)>,
+    /// Paths whose removal failed, with the error description
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl RemoveSummary {
+    pub fn is_success(&self) -> bool {
+        self.refused.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// Paths in `paths` that `validator` refuses, with the reason
+pub fn protected_paths(paths: &[PathBuf], validator: &PathValidator) -> Vec<(PathBuf, String)> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            validator
+                .check_removal(path)
+                .map(|reason| (path.clone(), reason))
+        })
+        .collect()
+}
+
+/// Remove every path that `validator` allows, using `remover`
+pub fn remove_paths(
+    paths: &[PathBuf],
+    mode: RemoveMode,
+    validator: &PathValidator,
+    remover: &dyn Remover,
+) -> RemoveSummary {
+    let mut summary = RemoveSummary::default();
+
+    for path in paths {
+        if let Some(reason) = validator.check_removal(path) {
+            summary.refused.push((path.clone(), reason));
+            continue;
+        }
+
+        let result = match mode {
+            RemoveMode::Trash => remover.trash(path),
+            RemoveMode::Permanent => remover.delete(path),
+        };
+        match result {
+            Ok(()) => summary.removed.push(path.clone()),
+            Err(e) => summary.failed.push((path.clone(), e.to_string())),
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    /// Records calls instead of touching the filesystem
+    #[derive(Default)]
+    struct RecordingRemover {
+        calls: RefCell<Vec<(&'static str, PathBuf)>>,
+    }
+
+    impl Remover for RecordingRemover {
+        fn trash(&self, path: &Path) -> Result<()> {
+            self.calls.borrow_mut().push(("trash", path.to_path_buf()));
+            Ok(())
+        }
+
+        fn delete(&self, path: &Path) -> Result<()> {
+            self.calls.borrow_mut().push(("delete", path.to_path_buf()));
+            Ok(())
+        }
+    }
+
+    fn fixture() -> (TempDir, Vec<PathBuf>) {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("notes.txt");
+        let sub = dir.path().join("build");
+        fs::write(&file, "x").unwrap();
+        fs::create_dir(&sub).unwrap();
+        (dir, vec![file, sub])
+    }
+
+    #[test]
+    fn test_default_mode_moves_to_trash() {
+        let (_dir, paths) = fixture();
+        let remover = RecordingRemover::default();
+
+        let summary = remove_paths(&paths, RemoveMode::Trash, &PathValidator::new(), &remover);
+
+        assert!(summary.is_success());
+        assert_eq!(summary.removed, paths);
+        let calls = remover.calls.into_inner();
+        assert!(calls.iter().all(|(kind, _)| *kind == "trash"));
+        assert_eq!(calls.len(), 2);
+        // Nothing was actually removed by the recording remover
+        assert!(paths.iter().all(|path| path.exists()));
+    }
+
+    #[test]
+    fn test_permanent_mode_deletes() {
+        let (_dir, paths) = fixture();
+        let remover = RecordingRemover::default();
+
+        remove_paths(
+            &paths[..1],
+            RemoveMode::Permanent,
+            &PathValidator::new(),
+            &remover,
+        );
+        assert_eq!(remover.calls.into_inner(), [("delete", paths[0].clone())]);
+    }
+
+    #[test]
+    fn test_system_remover_deletes_files_and_directories() {
+        let (_dir, paths) = fixture();
+        fs::write(paths[1].join("out.o"), "x").unwrap();
+
+        for path in &paths {
+            SystemRemover.delete(path).unwrap();
+            assert!(!path.exists());
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_protected_paths_are_refused() {
+        let (_dir, mut paths) = fixture();
+        paths.push(PathBuf::from("/etc"));
+        paths.push(PathBuf::from("/"));
+        let remover = RecordingRemover::default();
+        let validator = PathValidator::new();
+
+        assert_eq!(protected_paths(&paths, &validator).len(), 2);
+
+        let summary = remove_paths(&paths, RemoveMode::Trash, &validator, &remover);
+        let refused: Vec<&PathBuf> = summary.refused.iter().map(|(path, _)| path).collect();
+        assert_eq!(refused, [&PathBuf::from("/etc"), &PathBuf::from("/")]);
+        assert_eq!(summary.removed.len(), 2);
+        assert!(!summary.is_success());
+        assert!(remover
+            .calls
+            .into_inner()
+            .iter()
+            .all(|(_, path)| !path.starts_with("/etc") && path != Path::new("/")));
+    }
+}
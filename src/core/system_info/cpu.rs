@@ -1,4 +1,4 @@
-use crate::core::system_info::types::{CpuInfo, CpuInstructionSets};
+use crate::core::system_info::types::{CpuCacheDetails, CpuInfo, CpuInstructionSets};
 use crate::error::Result;
 use sysinfo::{CpuRefreshKind, RefreshKind, System};
 
@@ -32,6 +32,15 @@ pub fn collect() -> Result<CpuInfo> {
         None
     };
 
+    let cache_details = cache_details();
+
+    let hybrid_cores = detect_hybrid_cores(
+        first_cpu.brand(),
+        physical_cores,
+        cpus.len(),
+        efficiency_threads(),
+    );
+
     // Get platform-specific details
     #[cfg(windows)]
     let (
@@ -82,6 +91,10 @@ pub fn collect() -> Result<CpuInfo> {
         l1_cache_kb: None, // L1 cache not readily available via WMI
         l2_cache_kb,
         l3_cache_kb,
+        cache_line_bytes: cache_details
+            .as_ref()
+            .and_then(|caches| caches.iter().find_map(|cache| cache.line_size_bytes)),
+        cache_details,
 
         // Power and frequency
         tdp_watts: None, // TDP not available via standard WMI
@@ -108,6 +121,8 @@ pub fn collect() -> Result<CpuInfo> {
         ccd_count: None,
         #[cfg(not(windows))]
         ccx_count: None,
+        performance_cores: hybrid_cores.map(|cores| cores.performance),
+        efficiency_cores: hybrid_cores.map(|cores| cores.efficiency),
     })
 }
 
@@ -124,6 +139,8 @@ pub fn get_fallback() -> CpuInfo {
         l1_cache_kb: None,
         l2_cache_kb: None,
         l3_cache_kb: None,
+        cache_line_bytes: None,
+        cache_details: None,
         tdp_watts: None,
         max_tdp_watts: None,
         turbo_frequency_mhz: None,
@@ -133,5 +150,328 @@ pub fn get_fallback() -> CpuInfo {
         chiplet_count: None,
         ccd_count: None,
         ccx_count: None,
+        performance_cores: None,
+        efficiency_cores: None,
+    }
+}
+
+/// Performance/efficiency core split of a hybrid CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HybridCores {
+    pub performance: usize,
+    pub efficiency: usize,
+}
+
+/// Intel generations that mix P-cores and E-cores
+const HYBRID_MICROARCHITECTURES: &[&str] =
+    &["Alder Lake", "Raptor Lake", "Meteor Lake", "Arrow Lake"];
+
+/// P/E split for hybrid CPUs; `None` for every other CPU or when it can't be determined
+///
+/// `efficiency_threads` is the number of logical CPUs the OS reports as
+/// E-cores, when it does; otherwise the split is derived from the counts.
+pub fn detect_hybrid_cores(
+    model: &str,
+    physical: usize,
+    logical: usize,
+    efficiency_threads: Option<usize>,
+) -> Option<HybridCores> {
+    let microarch = detect_microarchitecture(model)?;
+    if !HYBRID_MICROARCHITECTURES.contains(&microarch.as_str()) {
+        return None;
+    }
+
+    match efficiency_threads {
+        // E-cores have one thread each
+        Some(efficiency) => hybrid_split(physical.checked_sub(efficiency)?, efficiency),
+        None => derive_hybrid_cores(physical, logical),
+    }
+}
+
+/// Derive the split from core and thread counts
+///
+/// On hybrid Intel CPUs only P-cores have Hyper-Threading, so
+/// `logical = 2P + E` and `physical = P + E`. Returns `None` when the counts
+/// show no E-cores (e.g. a P-core-only i5-12400) or no Hyper-Threading
+/// (e.g. Arrow Lake), where the split can't be told from counts alone.
+pub fn derive_hybrid_cores(physical: usize, logical: usize) -> Option<HybridCores> {
+    let performance = logical.checked_sub(physical)?;
+    hybrid_split(performance, physical.checked_sub(performance)?)
+}
+
+fn hybrid_split(performance: usize, efficiency: usize) -> Option<HybridCores> {
+    (performance > 0 && efficiency > 0).then_some(HybridCores {
+        performance,
+        efficiency,
+    })
+}
+
+/// Logical CPUs Linux lists as E-cores in `/sys/devices/cpu_atom/cpus`
+#[cfg(target_os = "linux")]
+fn efficiency_threads() -> Option<usize> {
+    count_cpu_list(&std::fs::read_to_string("/sys/devices/cpu_atom/cpus").ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn efficiency_threads() -> Option<usize> {
+    None
+}
+
+/// Cache geometry Linux exposes in `/sys/devices/system/cpu/cpu0/cache/index*`
+#[cfg(target_os = "linux")]
+fn cache_details() -> Option<Vec<CpuCacheDetails>> {
+    use crate::core::system_info::types::CacheType;
+
+    let cache_dir = std::path::Path::new("/sys/devices/system/cpu/cpu0/cache");
+    let read = |index: usize, file: &str| {
+        std::fs::read_to_string(cache_dir.join(format!("index{}", index)).join(file))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+
+    let mut caches = Vec::new();
+    for index in 0.. {
+        let Some(level) = read(index, "level").and_then(|level| level.parse().ok()) else {
+            break;
+        };
+        let cache_type = match read(index, "type").as_deref() {
+            Some("Data") => CacheType::Data,
+            Some("Instruction") => CacheType::Instruction,
+            _ => CacheType::Unified,
+        };
+        caches.push(CpuCacheDetails {
+            level,
+            cache_type,
+            size_kb: read(index, "size").and_then(|size| parse_cache_size(&size)),
+            // 0 means the kernel doesn't know
+            ways: read(index, "ways_of_associativity")
+                .and_then(|ways| ways.parse().ok())
+                .filter(|ways| *ways > 0),
+            line_size_bytes: read(index, "coherency_line_size")
+                .and_then(|size| size.parse().ok())
+                .filter(|size| *size > 0),
+            shared_by_cpus: read(index, "shared_cpu_list")
+                .and_then(|list| count_cpu_list(&list))
+                .and_then(|count| u32::try_from(count).ok()),
+        });
+    }
+
+    (!caches.is_empty()).then_some(caches)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cache_details() -> Option<Vec<CpuCacheDetails>> {
+    None
+}
+
+/// Cache size in KB from sysfs notation (`48K`, `2048K`, `32M`)
+pub fn parse_cache_size(size: &str) -> Option<u32> {
+    let size = size.trim();
+    let (number, multiplier) = match size.chars().last()? {
+        'K' | 'k' => (&size[..size.len() - 1], 1),
+        'M' | 'm' => (&size[..size.len() - 1], 1024),
+        'G' | 'g' => (&size[..size.len() - 1], 1024 * 1024),
+        _ => (size, 1),
+    };
+    number.parse::<u32>().ok()?.checked_mul(multiplier)
+}
+
+/// Number of CPUs in a kernel CPU list such as `0-15,20,22-23`
+pub fn count_cpu_list(list: &str) -> Option<usize> {
+    let list = list.trim();
+    if list.is_empty() {
+        return Some(0);
+    }
+    list.split(',')
+        .map(|range| match range.split_once('-') {
+            Some((start, end)) => {
+                let start: usize = start.trim().parse().ok()?;
+                let end: usize = end.trim().parse().ok()?;
+                end.checked_sub(start).map(|span| span + 1)
+            }
+            None => range.trim().parse::<usize>().ok().map(|_| 1),
+        })
+        .sum()
+}
+
+/// Microarchitecture name guessed from the CPU model string
+pub fn detect_microarchitecture(model: &str) -> Option<String> {
+    let model_lower = model.to_lowercase();
+
+    // AMD microarchitectures
+    if model_lower.contains("ryzen") {
+        // Ryzen AI 300 series (Zen 5)
+        if model_lower.contains("ai 9")
+            || model_lower.contains("ai 7")
+            || model_lower.contains("ai 3")
+        {
+            return Some("Zen 5".to_string());
+        }
+        // Ryzen 9000 series (Zen 5)
+        if model_lower.contains("9950")
+            || model_lower.contains("9900")
+            || model_lower.contains("9700")
+            || model_lower.contains("9600")
+        {
+            return Some("Zen 5".to_string());
+        }
+        // Ryzen 7000 series (Zen 4)
+        if model_lower.contains("7950")
+            || model_lower.contains("7900")
+            || model_lower.contains("7700")
+            || model_lower.contains("7600")
+            || model_lower.contains("7000")
+        {
+            return Some("Zen 4".to_string());
+        }
+        // Ryzen 5000 series (Zen 3)
+        if model_lower.contains("5950")
+            || model_lower.contains("5900")
+            || model_lower.contains("5800")
+            || model_lower.contains("5700")
+            || model_lower.contains("5600")
+            || model_lower.contains("5000")
+        {
+            return Some("Zen 3".to_string());
+        }
+        // Ryzen 3000 series (Zen 2)
+        if model_lower.contains("3950")
+            || model_lower.contains("3900")
+            || model_lower.contains("3800")
+            || model_lower.contains("3700")
+            || model_lower.contains("3600")
+            || model_lower.contains("3000")
+        {
+            return Some("Zen 2".to_string());
+        }
+        // Fallback for any Ryzen
+        return Some("Zen".to_string());
+    }
+
+    // Intel microarchitectures
+    if model_lower.contains("intel") {
+        // Core Ultra (Arrow Lake / Lunar Lake)
+        if model_lower.contains("core ultra")
+            || model_lower.contains("ultra 9")
+            || model_lower.contains("ultra 7")
+            || model_lower.contains("ultra 5")
+        {
+            if model_lower.contains("200") {
+                return Some("Arrow Lake".to_string());
+            }
+            return Some("Meteor Lake".to_string());
+        }
+        // 14th gen (Raptor Lake Refresh)
+        if model_lower.contains("14900")
+            || model_lower.contains("14700")
+            || model_lower.contains("14600")
+            || model_lower.contains("14400")
+        {
+            return Some("Raptor Lake".to_string());
+        }
+        // 13th gen (Raptor Lake)
+        if model_lower.contains("13900")
+            || model_lower.contains("13700")
+            || model_lower.contains("13600")
+            || model_lower.contains("13400")
+        {
+            return Some("Raptor Lake".to_string());
+        }
+        // 12th gen (Alder Lake)
+        if model_lower.contains("12900")
+            || model_lower.contains("12700")
+            || model_lower.contains("12600")
+            || model_lower.contains("12400")
+            || model_lower.contains("12100")
+        {
+            return Some("Alder Lake".to_string());
+        }
+        // 11th gen (Rocket Lake)
+        if model_lower.contains("11900")
+            || model_lower.contains("11700")
+            || model_lower.contains("11600")
+            || model_lower.contains("11400")
+        {
+            return Some("Rocket Lake".to_string());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hybrid_split_from_core_counts() {
+        // i9-13900K: 8P (16 threads) + 16E = 24 cores / 32 threads
+        assert_eq!(
+            derive_hybrid_cores(24, 32),
+            Some(HybridCores {
+                performance: 8,
+                efficiency: 16
+            })
+        );
+        // i5-12600K: 6P + 4E = 10 cores / 16 threads
+        assert_eq!(
+            derive_hybrid_cores(10, 16),
+            Some(HybridCores {
+                performance: 6,
+                efficiency: 4
+            })
+        );
+        // i5-12400 has no E-cores; without Hyper-Threading the split is unknown
+        assert_eq!(derive_hybrid_cores(6, 12), None);
+        assert_eq!(derive_hybrid_cores(24, 24), None);
+    }
+
+    #[test]
+    fn test_hybrid_detection_only_for_hybrid_models() {
+        assert_eq!(
+            detect_hybrid_cores("13th Gen Intel(R) Core(TM) i5-13600K", 14, 20, None),
+            Some(HybridCores {
+                performance: 6,
+                efficiency: 8
+            })
+        );
+        // Core Ultra 9 285K has no Hyper-Threading: needs the OS's E-core list
+        let arrow_lake = "Intel(R) Core(TM) Ultra 9 285K";
+        assert_eq!(detect_hybrid_cores(arrow_lake, 24, 24, None), None);
+        assert_eq!(
+            detect_hybrid_cores(arrow_lake, 24, 24, Some(16)),
+            Some(HybridCores {
+                performance: 8,
+                efficiency: 16
+            })
+        );
+
+        assert_eq!(
+            detect_hybrid_cores("AMD Ryzen 9 7950X 16-Core Processor", 16, 32, None),
+            None
+        );
+        assert_eq!(
+            detect_hybrid_cores("11th Gen Intel(R) Core(TM) i7-11700K", 8, 16, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_cache_size() {
+        assert_eq!(parse_cache_size("48K"), Some(48));
+        assert_eq!(parse_cache_size("2048K\n"), Some(2048));
+        assert_eq!(parse_cache_size("32M"), Some(32 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size(""), None);
+        assert_eq!(parse_cache_size("big"), None);
+    }
+
+    #[test]
+    fn test_count_cpu_list() {
+        assert_eq!(count_cpu_list("16-31\n"), Some(16));
+        assert_eq!(count_cpu_list("0-7,12,14-15"), Some(11));
+        assert_eq!(count_cpu_list(""), Some(0));
+        assert_eq!(count_cpu_list("7-3"), None);
+        assert_eq!(count_cpu_list("a-b"), None);
     }
 }
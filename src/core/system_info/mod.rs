@@ -8,6 +8,8 @@ pub mod motherboard;
 pub mod network;
 pub mod os;
 pub mod power;
+pub mod public_ip;
+pub mod redact;
 pub mod storage;
 pub mod types;
 
@@ -22,5 +22,6 @@ pub fn get_fallback() -> NetworkInfo {
         wifi_adapters: vec![],
         ethernet_adapters: vec![],
         bluetooth_adapters: vec![],
+        public_ip: None,
     }
 }
@@ -0,0 +1,235 @@
+//! Public IP lookup (opt-in, `msc sys info --public-ip`)
+//!
+//! Unlike the rest of the inventory this needs network access and tells a
+//! third-party service the machine's address, so it only runs when asked
+//! for. Results are cached for a few minutes to avoid hitting the services
+//! on every invocation.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::types::PublicIpInfo;
+use crate::core::http::HttpConfig;
+
+/// How long a cached lookup is reused
+pub const CACHE_TTL_SECS: i64 = 5 * 60;
+
+/// Keep a missing IPv6 route from stalling `sys info`
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Services queried by [`lookup`]
+#[derive(Debug, Clone)]
+pub struct PublicIpEndpoints {
+    /// Returns `{"ip": "..."}` over IPv4
+    pub ipv4: String,
+    /// Returns `{"ip": "..."}` over IPv6
+    pub ipv6: String,
+    /// ISP and location; `{ip}` is replaced with the address
+    pub geo: String,
+}
+
+impl Default for PublicIpEndpoints {
+    fn default() -> Self {
+        Self {
+            ipv4: "https://api.ipify.org?format=json".to_string(),
+            ipv6: "https://api6.ipify.org?format=json".to_string(),
+            geo: "https://ipinfo.io/{ip}/json".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IpResponse {
+    ip: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeoResponse {
+    city: Option<String>,
+    country: Option<String>,
+    /// `AS15169 Google LLC`
+    org: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedLookup {
+    fetched_at: i64,
+    info: PublicIpInfo,
+}
+
+/// Look up the public addresses, reusing a recent result from the cache file
+pub fn lookup_cached(http: &HttpConfig) -> Result<PublicIpInfo> {
+    let cache = cache_path();
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(info) = cache.as_deref().and_then(|path| read_cache(path, now)) {
+        return Ok(info);
+    }
+
+    let info = lookup(http, &PublicIpEndpoints::default())?;
+    if let Some(path) = cache {
+        // A cache that can't be written only costs a repeated lookup
+        let _ = write_cache(&path, now, &info);
+    }
+    Ok(info)
+}
+
+/// Query the IP echo services; fails only when neither address is found
+pub fn lookup(http: &HttpConfig, endpoints: &PublicIpEndpoints) -> Result<PublicIpInfo> {
+    let http = HttpConfig {
+        retries: 0,
+        ..http.clone().with_timeout(LOOKUP_TIMEOUT)
+    };
+    let client = http.client()?;
+    let fetch = |url: &str| -> Result<String> {
+        let response = http.send(client.get(url))?.error_for_status()?;
+        Ok(response.text()?)
+    };
+
+    let ipv4 = fetch(&endpoints.ipv4)
+        .ok()
+        .and_then(|body| parse_ip_response(&body));
+    let ipv6 = fetch(&endpoints.ipv6)
+        .ok()
+        .and_then(|body| parse_ip_response(&body));
+    let ip = ipv4
+        .as_ref()
+        .or(ipv6.as_ref())
+        .context("Could not determine the public IP address (no network access?)")?;
+
+    let geo = fetch(&endpoints.geo.replace("{ip}", ip))
+        .ok()
+        .and_then(|body| serde_json::from_str::<GeoResponse>(&body).ok());
+
+    Ok(PublicIpInfo {
+        ipv4,
+        ipv6,
+        isp: geo
+            .as_ref()
+            .and_then(|geo| geo.org.as_deref().map(strip_asn)),
+        city: geo.as_ref().and_then(|geo| geo.city.clone()),
+        country: geo.and_then(|geo| geo.country),
+    })
+}
+
+/// Address from an `{"ip": "..."}` body, ignoring anything that isn't one
+pub fn parse_ip_response(body: &str) -> Option<String> {
+    let response: IpResponse = serde_json::from_str(body).ok()?;
+    let ip: IpAddr = response.ip.trim().parse().ok()?;
+    Some(ip.to_string())
+}
+
+/// `AS15169 Google LLC` -> `Google LLC`
+fn strip_asn(org: &str) -> String {
+    match org.split_once(' ') {
+        Some((asn, name)) if asn.starts_with("AS") => name.to_string(),
+        _ => org.to_string(),
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("msc").join("public_ip.json"))
+}
+
+fn read_cache(path: &Path, now: i64) -> Option<PublicIpInfo> {
+    let cached: CachedLookup = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let age = now - cached.fetched_at;
+    (0..CACHE_TTL_SECS).contains(&age).then_some(cached.info)
+}
+
+fn write_cache(path: &Path, now: i64, info: &PublicIpInfo) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let cached = CachedLookup {
+        fetched_at: now,
+        info: info.clone(),
+    };
+    fs::write(path, serde_json::to_string(&cached)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve `count` requests, answering by path: `/v4`, `/geo/<ip>`, anything else 404
+    fn mock_server(count: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 2048];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+
+                let (status, body) = match path {
+                    "/v4" => ("200 OK", r#"{"ip":"203.0.113.7"}"#),
+                    "/geo/203.0.113.7" => (
+                        "200 OK",
+                        r#"{"ip":"203.0.113.7","city":"Lima","country":"PE","org":"AS6147 Telefonica del Peru"}"#,
+                    ),
+                    _ => ("404 Not Found", ""),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn test_lookup_parses_mock_endpoint() {
+        let base = mock_server(3);
+        let endpoints = PublicIpEndpoints {
+            ipv4: format!("{}/v4", base),
+            ipv6: format!("{}/v6", base),
+            geo: format!("{}/geo/{{ip}}", base),
+        };
+
+        let info = lookup(&HttpConfig::default(), &endpoints).unwrap();
+        assert_eq!(info.ipv4.as_deref(), Some("203.0.113.7"));
+        assert_eq!(info.ipv6, None);
+        assert_eq!(info.isp.as_deref(), Some("Telefonica del Peru"));
+        assert_eq!(info.city.as_deref(), Some("Lima"));
+        assert_eq!(info.country.as_deref(), Some("PE"));
+    }
+
+    #[test]
+    fn test_parse_ip_response_rejects_non_addresses() {
+        assert_eq!(
+            parse_ip_response(r#"{"ip":"2001:db8::1"}"#).as_deref(),
+            Some("2001:db8::1")
+        );
+        assert_eq!(parse_ip_response(r#"{"ip":"<html>"}"#), None);
+        assert_eq!(parse_ip_response("not json"), None);
+    }
+
+    #[test]
+    fn test_cache_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("msc").join("public_ip.json");
+        let info = PublicIpInfo {
+            ipv4: Some("203.0.113.7".to_string()),
+            ..Default::default()
+        };
+
+        assert!(read_cache(&path, 1_000).is_none());
+        write_cache(&path, 1_000, &info).unwrap();
+        assert_eq!(read_cache(&path, 1_060), Some(info));
+        assert!(read_cache(&path, 1_000 + CACHE_TTL_SECS).is_none());
+    }
+}
@@ -0,0 +1,179 @@
+//! Masking of identifying details before system information is shared
+//!
+//! Serial numbers, device UUIDs, SSIDs, MAC/IP addresses and hostnames are replaced
+//! with [`REDACTED`]; models, capacities and everything else used to
+//! diagnose a problem are left untouched. Values that were never detected
+//! stay `None` so a redacted report still shows what was unavailable.
+
+use super::types::SystemInfo;
+use crate::core::system_monitor::SystemMetrics;
+
+/// Placeholder written in place of a masked value
+pub const REDACTED: &str = "[redacted]";
+
+/// Mask identifying fields of `info` in place
+pub fn redact_system_info(info: &mut SystemInfo) {
+    let disks = info.storage.iter_mut().map(|disk| &mut disk.serial_number);
+    let battery = info
+        .battery
+        .iter_mut()
+        .map(|battery| &mut battery.serial_number);
+    let gpus = info
+        .gpu
+        .iter_mut()
+        .filter_map(|gpu| gpu.nvidia_metrics.as_mut())
+        .map(|nvidia| &mut nvidia.uuid);
+    let wifi = info.network.wifi_adapters.iter_mut().flat_map(|wifi| {
+        [
+            &mut wifi.ssid,
+            &mut wifi.ipv4_address,
+            &mut wifi.ipv6_address,
+        ]
+    });
+    let ethernet = info.network.ethernet_adapters.iter_mut().flat_map(|eth| {
+        [
+            &mut eth.mac_address,
+            &mut eth.ipv4_address,
+            &mut eth.ipv6_address,
+        ]
+    });
+    let public_ip = info
+        .network
+        .public_ip
+        .iter_mut()
+        .flat_map(|public| [&mut public.ipv4, &mut public.ipv6, &mut public.city]);
+
+    for value in disks
+        .chain(battery)
+        .chain(gpus)
+        .chain(wifi)
+        .chain(ethernet)
+        .chain(public_ip)
+    {
+        if value.is_some() {
+            *value = Some(REDACTED.to_string());
+        }
+    }
+}
+
+/// Mask the hostname in a metrics snapshot
+pub fn redact_metrics(metrics: &mut SystemMetrics) {
+    if !metrics.global.hostname.is_empty() {
+        metrics.global.hostname = REDACTED.to_string();
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::system_info::types::*;
+    use crate::core::system_info::{cpu, memory, os};
+
+    pub(crate) const DISK_SERIAL: &str = "S6B2NS0T123456A";
+    pub(crate) const BATTERY_SERIAL: &str = "BAT-98765";
+    const MAC: &str = "a4:bb:6d:12:34:56";
+
+    /// System with a serial-numbered disk and battery and a wired adapter
+    pub(crate) fn sample_info() -> SystemInfo {
+        SystemInfo {
+            cpu: cpu::get_fallback(),
+            memory: memory::get_fallback(),
+            gpu: Vec::new(),
+            motherboard: None,
+            network: NetworkInfo {
+                wifi_adapters: Vec::new(),
+                ethernet_adapters: vec![EthernetAdapter {
+                    name: "eth0".to_string(),
+                    speed_mbps: Some(1000),
+                    mac_address: Some(MAC.to_string()),
+                    ipv4_address: Some("192.168.1.20".to_string()),
+                    ipv6_address: None,
+                    gateway_latency_ms: Some(1),
+                }],
+                bluetooth_adapters: Vec::new(),
+                public_ip: None,
+            },
+            storage: vec![StorageInfo {
+                name: "nvme0n1".to_string(),
+                mount_point: "/".to_string(),
+                total_bytes: 512 * 1024 * 1024 * 1024,
+                available_bytes: 256 * 1024 * 1024 * 1024,
+                used_bytes: 256 * 1024 * 1024 * 1024,
+                usage_percent: 50.0,
+                disk_type: DiskType::NVMe,
+                file_system: "ext4".to_string(),
+                manufacturer: Some("Samsung".to_string()),
+                model: Some("980 PRO".to_string()),
+                serial_number: Some(DISK_SERIAL.to_string()),
+                firmware_version: Some("5B2QGXA7".to_string()),
+                bus_type: None,
+                interface_speed: None,
+                smart_status: None,
+                temperature_celsius: None,
+                power_on_hours: None,
+                total_bytes_read: None,
+                total_bytes_written: None,
+            }],
+            os: os::get_fallback(),
+            npu: None,
+            battery: Some(BatteryInfo {
+                is_present: true,
+                state: BatteryState::Full,
+                percentage: Some(100),
+                time_remaining_secs: None,
+                time_to_full_secs: None,
+                design_capacity_mwh: Some(57_000),
+                full_charge_capacity_mwh: Some(52_000),
+                health_percentage: Some(91),
+                cycle_count: Some(120),
+                technology: None,
+                voltage_mv: None,
+                design_voltage_mv: None,
+                discharge_rate_mw: None,
+                manufacturer: Some("SMP".to_string()),
+                serial_number: Some(BATTERY_SERIAL.to_string()),
+                manufacture_date: None,
+            }),
+            power_plan: None,
+        }
+    }
+
+    #[test]
+    fn test_redaction_masks_identifiers_only() {
+        let mut info = sample_info();
+        redact_system_info(&mut info);
+
+        let disk = &info.storage[0];
+        assert_eq!(disk.serial_number.as_deref(), Some(REDACTED));
+        assert_eq!(disk.model.as_deref(), Some("980 PRO"));
+        assert_eq!(disk.firmware_version.as_deref(), Some("5B2QGXA7"));
+        assert_eq!(disk.total_bytes, 512 * 1024 * 1024 * 1024);
+
+        let battery = info.battery.as_ref().unwrap();
+        assert_eq!(battery.serial_number.as_deref(), Some(REDACTED));
+        assert_eq!(battery.design_capacity_mwh, Some(57_000));
+        assert_eq!(battery.manufacturer.as_deref(), Some("SMP"));
+
+        let eth = &info.network.ethernet_adapters[0];
+        assert_eq!(eth.mac_address.as_deref(), Some(REDACTED));
+        assert_eq!(eth.ipv4_address.as_deref(), Some(REDACTED));
+        assert_eq!(eth.ipv6_address, None);
+        assert_eq!(eth.speed_mbps, Some(1000));
+
+        let json = serde_json::to_string(&info).unwrap();
+        for secret in [DISK_SERIAL, BATTERY_SERIAL, MAC] {
+            assert!(!json.contains(secret), "{} was not masked", secret);
+        }
+    }
+
+    #[test]
+    fn test_metrics_hostname_is_masked() {
+        let mut metrics = SystemMetrics::default();
+        metrics.global.hostname = "alice-laptop".to_string();
+        metrics.cpu.brand = "AMD Ryzen 7 7840U".to_string();
+
+        redact_metrics(&mut metrics);
+        assert_eq!(metrics.global.hostname, REDACTED);
+        assert_eq!(metrics.cpu.brand, "AMD Ryzen 7 7840U");
+    }
+}
@@ -31,6 +31,8 @@ pub struct CpuInfo {
     pub l1_cache_kb: Option<u32>,
     pub l2_cache_kb: Option<u32>,
     pub l3_cache_kb: Option<u32>,
+    pub cache_line_bytes: Option<u32>,
+    pub cache_details: Option<Vec<CpuCacheDetails>>, // Per-level geometry (Linux sysfs)
 
     // Power and frequency
     pub tdp_watts: Option<u32>,
@@ -48,6 +50,28 @@ pub struct CpuInfo {
     pub chiplet_count: Option<u32>,
     pub ccd_count: Option<u32>, // Core Complex Dies (Zen)
     pub ccx_count: Option<u32>, // Core Complexes (Zen)
+
+    // Topology (Intel hybrid, Alder Lake+)
+    pub performance_cores: Option<usize>, // P-cores
+    pub efficiency_cores: Option<usize>,  // E-cores
+}
+
+/// Geometry of one CPU cache (as seen from the first logical CPU)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CpuCacheDetails {
+    pub level: u8,
+    pub cache_type: CacheType,
+    pub size_kb: Option<u32>,
+    pub ways: Option<u32>, // Associativity
+    pub line_size_bytes: Option<u32>,
+    pub shared_by_cpus: Option<u32>, // Logical CPUs sharing this cache
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CacheType {
+    Data,
+    Instruction,
+    Unified,
 }
 
 /// CPU Instruction Set Support
@@ -254,6 +278,19 @@ pub struct NetworkInfo {
     pub wifi_adapters: Vec<WifiAdapter>,
     pub ethernet_adapters: Vec<EthernetAdapter>,
     pub bluetooth_adapters: Vec<BluetoothAdapter>,
+    /// Only looked up with `--public-ip`, since it needs network access
+    #[serde(default)]
+    pub public_ip: Option<PublicIpInfo>,
+}
+
+/// Public address as seen by an external service
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PublicIpInfo {
+    pub ipv4: Option<String>,
+    pub ipv6: Option<String>,
+    pub isp: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>, // ISO 3166 code, e.g. "PE"
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -267,6 +304,9 @@ pub struct WifiAdapter {
     pub ipv4_address: Option<String>,
     pub ipv6_address: Option<String>,
     pub gateway_latency_ms: Option<u32>,
+    pub ssid: Option<String>,               // None when not connected
+    pub signal_quality_percent: Option<u8>, // 0-100, as reported by the driver
+    pub rssi_dbm: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -6,18 +6,24 @@ use super::metrics::SystemMetrics;
 use serde::{Deserialize, Serialize};
 
 /// Alert configuration with thresholds
+///
+/// Missing fields deserialize to their defaults, so saved configurations keep
+/// working when new thresholds are added.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AlertConfig {
-    pub cpu_warning: f32,     // Warning threshold (%)
-    pub cpu_critical: f32,    // Critical threshold (%)
-    pub memory_warning: f32,  // Warning threshold (%)
-    pub memory_critical: f32, // Critical threshold (%)
-    pub gpu_warning: f32,     // Warning threshold (%)
-    pub gpu_critical: f32,    // Critical threshold (%)
-    pub disk_warning: f32,    // Warning threshold (%)
-    pub disk_critical: f32,   // Critical threshold (%)
-    pub temp_warning: f32,    // Warning threshold (°C)
-    pub temp_critical: f32,   // Critical threshold (°C)
+    pub cpu_warning: f32,       // Warning threshold (%)
+    pub cpu_critical: f32,      // Critical threshold (%)
+    pub memory_warning: f32,    // Warning threshold (%)
+    pub memory_critical: f32,   // Critical threshold (%)
+    pub gpu_warning: f32,       // Warning threshold (%)
+    pub gpu_critical: f32,      // Critical threshold (%)
+    pub disk_warning: f32,      // Warning threshold (%)
+    pub disk_critical: f32,     // Critical threshold (%)
+    pub temp_warning: f32,      // Warning threshold (°C)
+    pub temp_critical: f32,     // Critical threshold (°C)
+    pub gpu_temp_warning: f32,  // Warning threshold (°C)
+    pub gpu_temp_critical: f32, // Critical threshold (°C)
 }
 
 impl Default for AlertConfig {
@@ -33,6 +39,8 @@ impl Default for AlertConfig {
             disk_critical: 95.0,
             temp_warning: 75.0,
             temp_critical: 90.0,
+            gpu_temp_warning: 80.0,
+            gpu_temp_critical: 90.0,
         }
     }
 }
@@ -171,6 +179,34 @@ pub fn evaluate_alerts(metrics: &SystemMetrics, config: &AlertConfig) -> Vec<Ale
                 threshold: config.gpu_critical,
             });
         }
+
+        // GPU temperature alerts
+        if let Some(temp) = gpu.temperature_celsius {
+            let temp = temp as f32;
+            if temp >= config.gpu_temp_critical {
+                alerts.push(Alert {
+                    severity: AlertSeverity::Critical,
+                    category: AlertCategory::GPU,
+                    message: format!(
+                        "GPU temperature at {:.0}°C (critical threshold: {:.0}°C)",
+                        temp, config.gpu_temp_critical
+                    ),
+                    value: temp,
+                    threshold: config.gpu_temp_critical,
+                });
+            } else if temp >= config.gpu_temp_warning {
+                alerts.push(Alert {
+                    severity: AlertSeverity::Warning,
+                    category: AlertCategory::GPU,
+                    message: format!(
+                        "GPU temperature at {:.0}°C (warning threshold: {:.0}°C)",
+                        temp, config.gpu_temp_warning
+                    ),
+                    value: temp,
+                    threshold: config.gpu_temp_warning,
+                });
+            }
+        }
     }
 
     // Disk alerts
@@ -251,7 +287,7 @@ pub fn evaluate_alerts(metrics: &SystemMetrics, config: &AlertConfig) -> Vec<Ale
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::system_monitor::{CpuMetrics, MemoryMetrics};
+    use crate::core::system_monitor::{CpuMetrics, GpuMetrics, GpuVendor, MemoryMetrics};
 
     #[test]
     fn test_cpu_alert() {
@@ -285,6 +321,47 @@ mod tests {
         assert_eq!(alerts[0].severity, AlertSeverity::Warning);
     }
 
+    #[test]
+    fn test_gpu_temperature_alerts() {
+        let config = AlertConfig::default();
+        let gpu_at = |temperature: Option<u32>| SystemMetrics {
+            gpu: Some(GpuMetrics {
+                vendor: GpuVendor::Nvidia,
+                name: "Test GPU".to_string(),
+                utilization_percent: 10,
+                memory_used_bytes: 1 << 30,
+                memory_total_bytes: 8 << 30,
+                memory_percent: 12.5,
+                temperature_celsius: temperature,
+                fan_speed_percent: None,
+                power_draw_watts: None,
+                power_limit_watts: None,
+                clock_graphics_mhz: None,
+                clock_memory_mhz: None,
+                pcie_generation: None,
+                pcie_lanes: None,
+                max_pcie_generation: None,
+                max_pcie_lanes: None,
+            }),
+            ..Default::default()
+        };
+
+        for (temperature, expected) in [
+            (Some(60), None),
+            (Some(80), Some((AlertSeverity::Warning, 80.0))),
+            (Some(85), Some((AlertSeverity::Warning, 80.0))),
+            (Some(93), Some((AlertSeverity::Critical, 90.0))),
+            (None, None),
+        ] {
+            let alerts = evaluate_alerts(&gpu_at(temperature), &config);
+            let found = alerts
+                .iter()
+                .find(|a| a.category == AlertCategory::GPU && a.message.contains("temperature"))
+                .map(|a| (a.severity, a.threshold));
+            assert_eq!(found, expected, "{:?}°C", temperature);
+        }
+    }
+
     #[test]
     fn test_no_alerts() {
         let config = AlertConfig::default();
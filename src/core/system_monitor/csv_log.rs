@@ -0,0 +1,205 @@
+//! CSV logging of metrics snapshots.
+//!
+//! Each row holds the timestamp, CPU usage, memory, GPU temperature/usage,
+//! total network throughput and per-core usage. Rows are appended and
+//! flushed one at a time, so a killed process still leaves a valid file.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use super::metrics::SystemMetrics;
+use crate::error::Result;
+
+/// Columns before the per-core `core_N` columns
+const FIXED_COLUMNS: &[&str] = &[
+    "timestamp",
+    "cpu_percent",
+    "memory_used_bytes",
+    "memory_total_bytes",
+    "gpu_temp_celsius",
+    "gpu_usage_percent",
+    "net_rx_bytes_per_sec",
+    "net_tx_bytes_per_sec",
+];
+
+/// Appends one row per metrics snapshot to a CSV file
+pub struct CsvLogger {
+    file: File,
+    /// Number of `core_N` columns; `None` until the header is written
+    core_columns: Option<usize>,
+    last_timestamp: Option<i64>,
+}
+
+impl CsvLogger {
+    /// Open `path` for appending, keeping the header of an existing file
+    pub fn open(path: &Path) -> Result<Self> {
+        let core_columns = match File::open(path) {
+            Ok(existing) => {
+                let mut header = String::new();
+                BufReader::new(existing).read_line(&mut header)?;
+                (!header.trim().is_empty()).then(|| {
+                    header
+                        .trim()
+                        .split(',')
+                        .filter(|column| column.starts_with("core_"))
+                        .count()
+                })
+            }
+            Err(_) => None,
+        };
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file,
+            core_columns,
+            last_timestamp: None,
+        })
+    }
+
+    /// Append a row for `metrics`; returns whether one was written
+    ///
+    /// Snapshots without CPU data yet (right after startup) and repeated
+    /// snapshots within the same second are skipped, so the runtime's partial
+    /// updates produce at most one row per second.
+    pub fn append(&mut self, metrics: &SystemMetrics) -> Result<bool> {
+        if metrics.cpu.core_count == 0 || self.last_timestamp == Some(metrics.timestamp) {
+            return Ok(false);
+        }
+
+        let mut text = String::new();
+        let core_columns = match self.core_columns {
+            Some(columns) => columns,
+            None => {
+                let columns = metrics.cpu.per_core_usage.len();
+                text.push_str(&header(columns));
+                columns
+            }
+        };
+        text.push_str(&row(metrics, core_columns));
+
+        // One write per row so a partial write can't split a line
+        self.file.write_all(text.as_bytes())?;
+        self.file.flush()?;
+
+        self.core_columns = Some(core_columns);
+        self.last_timestamp = Some(metrics.timestamp);
+        Ok(true)
+    }
+}
+
+fn header(core_columns: usize) -> String {
+    let cores = (0..core_columns).map(|core| format!("core_{}", core));
+    let columns: Vec<String> = FIXED_COLUMNS
+        .iter()
+        .map(|column| column.to_string())
+        .chain(cores)
+        .collect();
+    format!("{}\n", columns.join(","))
+}
+
+/// Values for one row; missing GPU values are empty, extra cores are dropped
+fn row(metrics: &SystemMetrics, core_columns: usize) -> String {
+    let timestamp = chrono::DateTime::from_timestamp(metrics.timestamp, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| metrics.timestamp.to_string());
+    let gpu = metrics.gpu.as_ref();
+    let optional = |value: Option<String>| value.unwrap_or_default();
+
+    let mut values = vec![
+        timestamp,
+        format!("{:.1}", metrics.cpu.global_usage),
+        metrics.memory.used_bytes.to_string(),
+        metrics.memory.total_bytes.to_string(),
+        optional(gpu.and_then(|gpu| gpu.temperature_celsius.map(|temp| temp.to_string()))),
+        optional(gpu.map(|gpu| gpu.utilization_percent.to_string())),
+        metrics
+            .network
+            .iter()
+            .map(|net| net.rx_bytes_per_sec)
+            .sum::<u64>()
+            .to_string(),
+        metrics
+            .network
+            .iter()
+            .map(|net| net.tx_bytes_per_sec)
+            .sum::<u64>()
+            .to_string(),
+    ];
+    values.extend((0..core_columns).map(|core| {
+        optional(
+            metrics
+                .cpu
+                .per_core_usage
+                .get(core)
+                .map(|usage| format!("{:.1}", usage)),
+        )
+    }));
+    format!("{}\n", values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::system_monitor::NetworkMetrics;
+
+    fn snapshot(timestamp: i64, cores: &[f32]) -> SystemMetrics {
+        let mut metrics = SystemMetrics {
+            timestamp,
+            ..Default::default()
+        };
+        metrics.cpu.core_count = cores.len();
+        metrics.cpu.per_core_usage = cores.to_vec();
+        metrics.cpu.global_usage = 12.5;
+        metrics.memory.used_bytes = 4096;
+        metrics.memory.total_bytes = 8192;
+        metrics.network = vec![
+            NetworkMetrics {
+                rx_bytes_per_sec: 100,
+                tx_bytes_per_sec: 10,
+                ..Default::default()
+            },
+            NetworkMetrics {
+                rx_bytes_per_sec: 50,
+                ..Default::default()
+            },
+        ];
+        metrics
+    }
+
+    #[test]
+    fn test_header_written_once_and_rows_appended() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.csv");
+
+        let mut logger = CsvLogger::open(&path).unwrap();
+        assert!(!logger.append(&SystemMetrics::default()).unwrap());
+        assert!(logger
+            .append(&snapshot(1_700_000_000, &[10.0, 20.0]))
+            .unwrap());
+        assert!(!logger
+            .append(&snapshot(1_700_000_000, &[10.0, 20.0]))
+            .unwrap());
+        drop(logger);
+
+        // Reopening keeps the existing header and its core columns
+        let mut logger = CsvLogger::open(&path).unwrap();
+        assert!(logger
+            .append(&snapshot(1_700_000_001, &[30.0, 40.0, 50.0]))
+            .unwrap());
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "timestamp,cpu_percent,memory_used_bytes,memory_total_bytes,gpu_temp_celsius,\
+             gpu_usage_percent,net_rx_bytes_per_sec,net_tx_bytes_per_sec,core_0,core_1"
+        );
+        assert_eq!(
+            lines[1],
+            "2023-11-14T22:13:20+00:00,12.5,4096,8192,,,150,10,10.0,20.0"
+        );
+        assert!(lines[2].ends_with(",30.0,40.0"));
+    }
+}
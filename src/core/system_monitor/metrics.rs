@@ -69,6 +69,11 @@ pub struct GpuMetrics {
     pub power_limit_watts: Option<u32>,
     pub clock_graphics_mhz: Option<u32>,
     pub clock_memory_mhz: Option<u32>,
+    /// Negotiated PCIe link, which drops at idle to save power
+    pub pcie_generation: Option<u32>,
+    pub pcie_lanes: Option<u32>,
+    pub max_pcie_generation: Option<u32>,
+    pub max_pcie_lanes: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
@@ -5,11 +5,13 @@
 
 pub mod alerts;
 mod collector;
+pub mod csv_log;
 pub mod disk_enrichment;
 mod gpu;
 mod history;
 mod metrics;
 pub mod process_tree;
+pub mod prometheus;
 pub mod runtime;
 pub mod tasks;
 
@@ -18,6 +20,7 @@ pub use collector::{
     collect_battery_info, collect_cpu, collect_disks, collect_memory, collect_temperatures,
     sort_and_truncate_processes, CollectorConfig, MetricsCollector,
 };
+pub use csv_log::CsvLogger;
 pub use gpu::GpuProvider;
 pub use history::MetricsHistory;
 pub use metrics::{
@@ -26,7 +29,8 @@ pub use metrics::{
     SystemMetrics, TemperatureReading,
 };
 pub use process_tree::{
-    build_process_tree, flatten_tree, format_tree_indent, FlattenedProcess, ProcessTreeNode,
+    build_process_tree, filter_process_tree, flatten_tree, format_tree_indent, sort_process_tree,
+    sort_processes, to_dot, FlattenedProcess, ProcessSortKey, ProcessTreeFilter, ProcessTreeNode,
 };
-pub use runtime::{MetricsRuntime, UiState};
-pub use tasks::SubsystemUpdate;
+pub use runtime::{CompleteSnapshot, MetricsRuntime, SubsystemStatus, UiState};
+pub use tasks::{Subsystem, SubsystemUpdate};
@@ -3,8 +3,63 @@
 //! Builds hierarchical process trees from flat process lists.
 
 use super::metrics::ProcessMetrics;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// Column the process table is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessSortKey {
+    #[default]
+    Cpu,
+    Memory,
+    Pid,
+    Name,
+}
+
+impl ProcessSortKey {
+    /// Next key in column order, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            ProcessSortKey::Cpu => ProcessSortKey::Memory,
+            ProcessSortKey::Memory => ProcessSortKey::Pid,
+            ProcessSortKey::Pid => ProcessSortKey::Name,
+            ProcessSortKey::Name => ProcessSortKey::Cpu,
+        }
+    }
+
+    /// Usage columns list the heaviest processes first; PID and name ascend
+    pub fn is_descending(self) -> bool {
+        matches!(self, ProcessSortKey::Cpu | ProcessSortKey::Memory)
+    }
+
+    fn compare(self, a: &ProcessMetrics, b: &ProcessMetrics) -> Ordering {
+        let ordering = match self {
+            ProcessSortKey::Cpu => b
+                .cpu_usage_percent
+                .partial_cmp(&a.cpu_usage_percent)
+                .unwrap_or(Ordering::Equal),
+            ProcessSortKey::Memory => b.memory_bytes.cmp(&a.memory_bytes),
+            ProcessSortKey::Pid => Ordering::Equal,
+            ProcessSortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        };
+        // PID breaks ties so equal rows don't swap places between refreshes
+        ordering.then(a.pid.cmp(&b.pid))
+    }
+}
+
+/// Sort a flat process list by `key`
+pub fn sort_processes(processes: &mut [ProcessMetrics], key: ProcessSortKey) {
+    processes.sort_by(|a, b| key.compare(a, b));
+}
+
+/// Sort every level of a process tree by `key`, keeping children under their parent
+pub fn sort_process_tree(tree: &mut [ProcessTreeNode], key: ProcessSortKey) {
+    tree.sort_by(|a, b| key.compare(&a.process, &b.process));
+    for node in tree.iter_mut() {
+        sort_process_tree(&mut node.children, key);
+    }
+}
+
 /// A node in the process tree
 #[derive(Debug, Clone)]
 pub struct ProcessTreeNode {
@@ -83,6 +138,86 @@ fn build_node(
     }
 }
 
+/// Criteria for pruning a process tree; every criterion that is set must match
+#[derive(Debug, Clone, Default)]
+pub struct ProcessTreeFilter {
+    /// Case-insensitive substring of the process name
+    pub name: Option<String>,
+    /// Only keep the subtree rooted at this PID
+    pub pid: Option<u32>,
+    /// Minimum CPU usage in percent
+    pub min_cpu: Option<f32>,
+}
+
+impl ProcessTreeFilter {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.pid.is_none() && self.min_cpu.is_none()
+    }
+
+    fn matches(&self, process: &ProcessMetrics) -> bool {
+        let name_matches = self
+            .name
+            .as_ref()
+            .is_none_or(|name| process.name.to_lowercase().contains(&name.to_lowercase()));
+        let cpu_matches = self
+            .min_cpu
+            .is_none_or(|min| process.cpu_usage_percent >= min);
+        name_matches && cpu_matches
+    }
+}
+
+/// Prune a process tree with `filter`
+///
+/// With a PID only that subtree is kept, re-rooted at depth 0. Non-matching
+/// nodes are dropped unless a descendant matches, so matches keep their
+/// ancestors for context.
+pub fn filter_process_tree(
+    tree: &[ProcessTreeNode],
+    filter: &ProcessTreeFilter,
+) -> Vec<ProcessTreeNode> {
+    let roots = match filter.pid {
+        Some(pid) => find_subtree(tree, pid)
+            .map(|node| {
+                let mut node = node.clone();
+                set_depth(&mut node, 0);
+                vec![node]
+            })
+            .unwrap_or_default(),
+        None => tree.to_vec(),
+    };
+
+    roots
+        .into_iter()
+        .filter_map(|node| prune_node(node, filter))
+        .collect()
+}
+
+fn find_subtree(tree: &[ProcessTreeNode], pid: u32) -> Option<&ProcessTreeNode> {
+    tree.iter().find_map(|node| {
+        if node.process.pid == pid {
+            Some(node)
+        } else {
+            find_subtree(&node.children, pid)
+        }
+    })
+}
+
+fn set_depth(node: &mut ProcessTreeNode, depth: usize) {
+    node.depth = depth;
+    for child in &mut node.children {
+        set_depth(child, depth + 1);
+    }
+}
+
+fn prune_node(mut node: ProcessTreeNode, filter: &ProcessTreeFilter) -> Option<ProcessTreeNode> {
+    node.children = std::mem::take(&mut node.children)
+        .into_iter()
+        .filter_map(|child| prune_node(child, filter))
+        .collect();
+
+    (filter.matches(&node.process) || !node.children.is_empty()).then_some(node)
+}
+
 /// Flatten a process tree into a linear list with depth information
 pub fn flatten_tree(tree: &[ProcessTreeNode]) -> Vec<FlattenedProcess> {
     let mut result = Vec::new();
@@ -125,6 +260,44 @@ fn flatten_node(
     }
 }
 
+/// Render a process tree as a Graphviz DOT digraph
+///
+/// Nodes are labeled with PID, name and CPU usage; edges point from parent to
+/// child. Render with e.g. `dot -Tsvg processes.dot -o processes.svg`.
+pub fn to_dot(tree: &[ProcessTreeNode]) -> String {
+    let mut dot = String::from(
+        "digraph processes {\n    rankdir=LR;\n    node [shape=box, fontname=\"monospace\"];\n",
+    );
+    for node in tree {
+        write_dot_node(node, &mut dot);
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn write_dot_node(node: &ProcessTreeNode, dot: &mut String) {
+    let process = &node.process;
+    dot.push_str(&format!(
+        "    p{} [label=\"{}\\n{}\\n{:.1}% CPU\"];\n",
+        process.pid,
+        process.pid,
+        escape_dot(&process.name),
+        process.cpu_usage_percent
+    ));
+    for child in &node.children {
+        dot.push_str(&format!(
+            "    p{} -> p{};\n",
+            process.pid, child.process.pid
+        ));
+        write_dot_node(child, dot);
+    }
+}
+
+/// Escape a value for a double-quoted DOT string
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 /// Generate tree indentation string (like htop)
 pub fn format_tree_indent(flattened: &FlattenedProcess) -> String {
     let mut indent = String::new();
@@ -230,4 +403,149 @@ mod tests {
         assert_eq!(flat[0].depth, 0);
         assert_eq!(flat[1].depth, 1);
     }
+
+    fn process(
+        pid: u32,
+        parent_pid: Option<u32>,
+        name: &str,
+        cpu: f32,
+        memory: u64,
+    ) -> ProcessMetrics {
+        ProcessMetrics {
+            pid,
+            parent_pid,
+            name: name.to_string(),
+            cpu_usage_percent: cpu,
+            memory_bytes: memory,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_processes_by_key() {
+        let mut processes = vec![
+            process(30, None, "bash", 1.0, 5000),
+            process(10, None, "Xorg", 9.0, 1000),
+            process(20, None, "chrome", 4.0, 9000),
+        ];
+        let pids = |processes: &[ProcessMetrics]| -> Vec<u32> {
+            processes.iter().map(|p| p.pid).collect()
+        };
+
+        sort_processes(&mut processes, ProcessSortKey::Cpu);
+        assert_eq!(pids(&processes), [10, 20, 30]);
+        sort_processes(&mut processes, ProcessSortKey::Memory);
+        assert_eq!(pids(&processes), [20, 30, 10]);
+        sort_processes(&mut processes, ProcessSortKey::Pid);
+        assert_eq!(pids(&processes), [10, 20, 30]);
+        sort_processes(&mut processes, ProcessSortKey::Name);
+        assert_eq!(pids(&processes), [30, 20, 10]);
+    }
+
+    #[test]
+    fn test_sort_process_tree_keeps_hierarchy() {
+        let processes = vec![
+            process(1, None, "init", 0.1, 100),
+            process(3, Some(1), "b-child", 1.0, 900),
+            process(2, Some(1), "a-child", 5.0, 200),
+            process(9, None, "kthreadd", 0.0, 50),
+        ];
+
+        let mut tree = build_process_tree(&processes);
+        sort_process_tree(&mut tree, ProcessSortKey::Memory);
+        let order: Vec<u32> = flatten_tree(&tree).iter().map(|f| f.process.pid).collect();
+        assert_eq!(order, [1, 3, 2, 9]);
+
+        sort_process_tree(&mut tree, ProcessSortKey::Name);
+        let order: Vec<u32> = flatten_tree(&tree).iter().map(|f| f.process.pid).collect();
+        assert_eq!(order, [1, 2, 3, 9]);
+    }
+
+    #[test]
+    fn test_to_dot_nodes_and_edges() {
+        let processes = vec![
+            process(1, None, "init", 0.1, 100),
+            process(2, Some(1), "sshd", 0.0, 200),
+            process(3, Some(2), "bash \"login\"", 2.5, 300),
+            process(9, None, "kthreadd", 0.0, 50),
+        ];
+
+        let dot = to_dot(&build_process_tree(&processes));
+        assert!(dot.starts_with("digraph processes {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.matches("[label=").count(), 4);
+        assert_eq!(dot.matches(" -> ").count(), 2);
+        assert!(dot.contains("p1 -> p2;"));
+        assert!(dot.contains("p2 -> p3;"));
+        assert!(dot.contains(r#"p3 [label="3\nbash \"login\"\n2.5% CPU"];"#));
+    }
+
+    fn filtered_pids(tree: &[ProcessTreeNode], filter: &ProcessTreeFilter) -> Vec<u32> {
+        flatten_tree(&filter_process_tree(tree, filter))
+            .iter()
+            .map(|f| f.process.pid)
+            .collect()
+    }
+
+    fn sample_tree() -> Vec<ProcessTreeNode> {
+        let processes = vec![
+            process(1, None, "init", 0.1, 100),
+            process(2, Some(1), "sshd", 0.0, 200),
+            process(3, Some(2), "bash", 2.5, 300),
+            process(4, Some(3), "cargo", 80.0, 400),
+            process(5, Some(1), "cron", 0.0, 50),
+            process(9, None, "kthreadd", 0.0, 50),
+        ];
+        let mut tree = build_process_tree(&processes);
+        sort_process_tree(&mut tree, ProcessSortKey::Pid);
+        tree
+    }
+
+    #[test]
+    fn test_filter_by_pid_keeps_subtree() {
+        let tree = sample_tree();
+        let filter = ProcessTreeFilter {
+            pid: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(filtered_pids(&tree, &filter), [2, 3, 4]);
+        let filtered = filter_process_tree(&tree, &filter);
+        assert_eq!(filtered[0].depth, 0, "The subtree is re-rooted");
+        assert_eq!(format_tree_indent(&flatten_tree(&filtered)[0]), "");
+
+        let missing = ProcessTreeFilter {
+            pid: Some(42),
+            ..Default::default()
+        };
+        assert!(filter_process_tree(&tree, &missing).is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_name_keeps_ancestors() {
+        let tree = sample_tree();
+        let by_name = ProcessTreeFilter {
+            name: Some("BASH".to_string()),
+            ..Default::default()
+        };
+        // cargo is a child of bash but does not match
+        assert_eq!(filtered_pids(&tree, &by_name), [1, 2, 3]);
+
+        let by_cpu = ProcessTreeFilter {
+            min_cpu: Some(50.0),
+            ..Default::default()
+        };
+        assert_eq!(filtered_pids(&tree, &by_cpu), [1, 2, 3, 4]);
+
+        let combined = ProcessTreeFilter {
+            name: Some("cron".to_string()),
+            min_cpu: Some(50.0),
+            ..Default::default()
+        };
+        assert!(filter_process_tree(&tree, &combined).is_empty());
+        assert_eq!(
+            filtered_pids(&tree, &ProcessTreeFilter::default()),
+            [1, 2, 3, 4, 5, 9]
+        );
+    }
 }
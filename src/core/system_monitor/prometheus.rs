@@ -0,0 +1,511 @@
+//! Prometheus text exposition of system metrics.
+//!
+//! Every field of a [`SystemMetrics`] snapshot maps to a named `msc_*`
+//! family. Per-core, per-disk, per-interface, per-GPU and per-sensor series
+//! are told apart by labels, so one scrape covers the whole machine.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use super::metrics::{DiskMetrics, GpuMetrics, NetworkMetrics, SystemMetrics};
+
+/// `Content-Type` of the text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+type Labels = Vec<(&'static str, String)>;
+
+/// Accumulates metric families in exposition format
+#[derive(Default)]
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    /// Write one family; families without samples are omitted entirely
+    fn family(
+        &mut self,
+        name: &str,
+        kind: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels, f64)>,
+    ) {
+        let mut samples = samples.into_iter().peekable();
+        if samples.peek().is_none() {
+            return;
+        }
+
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = writeln!(self.out, "{}{} {}", name, format_labels(&labels), value);
+        }
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (Labels, f64)>) {
+        self.family(name, "gauge", help, samples);
+    }
+
+    fn counter(
+        &mut self,
+        name: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels, f64)>,
+    ) {
+        self.family(name, "counter", help, samples);
+    }
+}
+
+/// `{key="value",...}`, or nothing for an unlabelled series
+fn format_labels(labels: &Labels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+/// Escape a label value as the exposition format requires
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Single unlabelled sample
+fn single(value: f64) -> Option<(Labels, f64)> {
+    Some((Vec::new(), value))
+}
+
+/// Render a snapshot in Prometheus text format
+pub fn render(metrics: &SystemMetrics) -> String {
+    let mut exp = Exposition::default();
+
+    // Global
+    exp.gauge(
+        "msc_uptime_seconds",
+        "Seconds since boot",
+        single(metrics.global.uptime_secs as f64),
+    );
+    exp.gauge(
+        "msc_boot_time_seconds",
+        "Boot time as a Unix timestamp",
+        single(metrics.global.boot_time as f64),
+    );
+    exp.gauge(
+        "msc_battery_percent",
+        "Battery charge",
+        metrics
+            .global
+            .battery_percent
+            .and_then(|percent| single(percent as f64)),
+    );
+    exp.gauge(
+        "msc_battery_time_remaining_seconds",
+        "Estimated battery time remaining",
+        metrics
+            .global
+            .battery_time_remaining_secs
+            .and_then(|secs| single(secs as f64)),
+    );
+
+    // CPU
+    let cpu = &metrics.cpu;
+    exp.gauge(
+        "msc_cpu_usage_percent",
+        "Overall CPU usage",
+        single(cpu.global_usage as f64),
+    );
+    exp.gauge(
+        "msc_cpu_core_usage_percent",
+        "Usage of each logical CPU",
+        cpu.per_core_usage
+            .iter()
+            .enumerate()
+            .map(|(core, usage)| (vec![("core", core.to_string())], *usage as f64)),
+    );
+    exp.gauge(
+        "msc_cpu_frequency_mhz",
+        "Current frequency of each logical CPU",
+        cpu.frequencies_mhz
+            .iter()
+            .enumerate()
+            .map(|(core, mhz)| (vec![("core", core.to_string())], *mhz as f64)),
+    );
+    exp.gauge(
+        "msc_cpu_cores",
+        "Number of logical CPUs",
+        single(cpu.core_count as f64),
+    );
+    let (one, five, fifteen) = cpu.load_average;
+    exp.gauge(
+        "msc_load_average",
+        "System load average",
+        [("1m", one), ("5m", five), ("15m", fifteen)]
+            .map(|(period, load)| (vec![("period", period.to_string())], load)),
+    );
+
+    // Memory
+    let memory = &metrics.memory;
+    exp.gauge(
+        "msc_memory_total_bytes",
+        "Installed memory",
+        single(memory.total_bytes as f64),
+    );
+    exp.gauge(
+        "msc_memory_used_bytes",
+        "Memory in use, excluding cache and buffers",
+        single(memory.used_bytes as f64),
+    );
+    exp.gauge(
+        "msc_memory_cache_buffers_bytes",
+        "Memory used for cache and buffers",
+        single(memory.cache_buffers_bytes as f64),
+    );
+    exp.gauge(
+        "msc_memory_available_bytes",
+        "Memory available for new allocations",
+        single(memory.available_bytes as f64),
+    );
+    exp.gauge(
+        "msc_memory_usage_percent",
+        "Memory usage",
+        single(memory.usage_percent as f64),
+    );
+    exp.gauge(
+        "msc_swap_total_bytes",
+        "Swap size",
+        single(memory.swap_total_bytes as f64),
+    );
+    exp.gauge(
+        "msc_swap_used_bytes",
+        "Swap in use",
+        single(memory.swap_used_bytes as f64),
+    );
+
+    // GPU: one series per GPU, labelled by index and name
+    let gpus: Vec<(Labels, &GpuMetrics)> = metrics
+        .gpu
+        .iter()
+        .enumerate()
+        .map(|(index, gpu)| {
+            (
+                vec![("index", index.to_string()), ("name", gpu.name.clone())],
+                gpu,
+            )
+        })
+        .collect();
+    let gpu_series = |value: fn(&GpuMetrics) -> Option<f64>| {
+        gpus.iter()
+            .filter_map(move |(labels, gpu)| value(gpu).map(|value| (labels.clone(), value)))
+            .collect::<Vec<_>>()
+    };
+    exp.gauge(
+        "msc_gpu_usage_percent",
+        "GPU utilization",
+        gpu_series(|gpu| Some(gpu.utilization_percent as f64)),
+    );
+    exp.gauge(
+        "msc_gpu_memory_used_bytes",
+        "GPU memory in use",
+        gpu_series(|gpu| Some(gpu.memory_used_bytes as f64)),
+    );
+    exp.gauge(
+        "msc_gpu_memory_total_bytes",
+        "GPU memory size",
+        gpu_series(|gpu| Some(gpu.memory_total_bytes as f64)),
+    );
+    exp.gauge(
+        "msc_gpu_temp_celsius",
+        "GPU temperature",
+        gpu_series(|gpu| gpu.temperature_celsius.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_fan_speed_percent",
+        "GPU fan speed",
+        gpu_series(|gpu| gpu.fan_speed_percent.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_power_watts",
+        "GPU power draw",
+        gpu_series(|gpu| gpu.power_draw_watts.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_power_limit_watts",
+        "GPU power limit",
+        gpu_series(|gpu| gpu.power_limit_watts.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_clock_graphics_mhz",
+        "GPU graphics clock",
+        gpu_series(|gpu| gpu.clock_graphics_mhz.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_clock_memory_mhz",
+        "GPU memory clock",
+        gpu_series(|gpu| gpu.clock_memory_mhz.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_pcie_generation",
+        "Negotiated PCIe generation",
+        gpu_series(|gpu| gpu.pcie_generation.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_pcie_lanes",
+        "Negotiated PCIe link width",
+        gpu_series(|gpu| gpu.pcie_lanes.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_pcie_max_generation",
+        "Maximum PCIe generation",
+        gpu_series(|gpu| gpu.max_pcie_generation.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_gpu_pcie_max_lanes",
+        "Maximum PCIe link width",
+        gpu_series(|gpu| gpu.max_pcie_lanes.map(f64::from)),
+    );
+
+    // Disks: labelled by mount point and device
+    let disk_labels = |disk: &DiskMetrics| -> Labels {
+        vec![
+            ("mount", disk.mount_point.clone()),
+            ("device", disk.name.clone()),
+        ]
+    };
+    let disk_series = |value: fn(&DiskMetrics) -> Option<f64>| {
+        metrics
+            .disks
+            .iter()
+            .filter_map(move |disk| value(disk).map(|value| (disk_labels(disk), value)))
+            .collect::<Vec<_>>()
+    };
+    exp.gauge(
+        "msc_disk_total_bytes",
+        "Filesystem size",
+        disk_series(|disk| Some(disk.total_bytes as f64)),
+    );
+    exp.gauge(
+        "msc_disk_available_bytes",
+        "Filesystem free space",
+        disk_series(|disk| Some(disk.available_bytes as f64)),
+    );
+    exp.gauge(
+        "msc_disk_usage_percent",
+        "Filesystem usage",
+        disk_series(|disk| Some(disk.usage_percent as f64)),
+    );
+    exp.gauge(
+        "msc_disk_read_bytes_per_second",
+        "Disk read throughput",
+        disk_series(|disk| disk.read_bytes_per_sec.map(|bytes| bytes as f64)),
+    );
+    exp.gauge(
+        "msc_disk_write_bytes_per_second",
+        "Disk write throughput",
+        disk_series(|disk| disk.write_bytes_per_sec.map(|bytes| bytes as f64)),
+    );
+    exp.gauge(
+        "msc_disk_temp_celsius",
+        "Disk temperature",
+        disk_series(|disk| disk.temperature_celsius.map(f64::from)),
+    );
+    exp.gauge(
+        "msc_disk_power_on_hours",
+        "Disk power-on time",
+        disk_series(|disk| disk.power_on_hours.map(|hours| hours as f64)),
+    );
+
+    // Network: labelled by interface
+    let net_series = |value: fn(&NetworkMetrics) -> u64| {
+        metrics
+            .network
+            .iter()
+            .map(move |net| {
+                (
+                    vec![("interface", net.interface.clone())],
+                    value(net) as f64,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    exp.counter(
+        "msc_network_receive_bytes_total",
+        "Bytes received",
+        net_series(|net| net.rx_bytes_total),
+    );
+    exp.counter(
+        "msc_network_transmit_bytes_total",
+        "Bytes sent",
+        net_series(|net| net.tx_bytes_total),
+    );
+    exp.gauge(
+        "msc_network_receive_bytes_per_second",
+        "Receive throughput",
+        net_series(|net| net.rx_bytes_per_sec),
+    );
+    exp.gauge(
+        "msc_network_transmit_bytes_per_second",
+        "Transmit throughput",
+        net_series(|net| net.tx_bytes_per_sec),
+    );
+    exp.counter(
+        "msc_network_receive_packets_total",
+        "Packets received",
+        net_series(|net| net.rx_packets),
+    );
+    exp.counter(
+        "msc_network_transmit_packets_total",
+        "Packets sent",
+        net_series(|net| net.tx_packets),
+    );
+    exp.counter(
+        "msc_network_receive_errors_total",
+        "Receive errors",
+        net_series(|net| net.rx_errors),
+    );
+    exp.counter(
+        "msc_network_transmit_errors_total",
+        "Transmit errors",
+        net_series(|net| net.tx_errors),
+    );
+    exp.counter(
+        "msc_network_receive_drops_total",
+        "Dropped incoming packets",
+        net_series(|net| net.rx_drops),
+    );
+    exp.counter(
+        "msc_network_transmit_drops_total",
+        "Dropped outgoing packets",
+        net_series(|net| net.tx_drops),
+    );
+
+    // Temperature sensors
+    exp.gauge(
+        "msc_temperature_celsius",
+        "Sensor temperature",
+        metrics.temperatures.iter().map(|reading| {
+            (
+                vec![("sensor", reading.label.clone())],
+                reading.current_celsius as f64,
+            )
+        }),
+    );
+    exp.gauge(
+        "msc_temperature_critical_celsius",
+        "Sensor critical threshold",
+        metrics.temperatures.iter().filter_map(|reading| {
+            reading
+                .critical_celsius
+                .map(|critical| (vec![("sensor", reading.label.clone())], critical as f64))
+        }),
+    );
+
+    exp.out
+}
+
+/// Answer one HTTP request: `GET /metrics` gets `body`, anything else a 404
+pub fn respond(stream: &mut TcpStream, body: impl FnOnce() -> String) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&*stream).read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", CONTENT_TYPE, body()),
+        _ => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "Metrics are served at /metrics\n".to_string(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::system_monitor::GpuVendor;
+
+    fn sample_metrics() -> SystemMetrics {
+        let mut metrics = SystemMetrics::default();
+        metrics.cpu.global_usage = 42.5;
+        metrics.cpu.per_core_usage = vec![40.0, 45.0];
+        metrics.memory.used_bytes = 8_589_934_592;
+        metrics.gpu = Some(GpuMetrics {
+            vendor: GpuVendor::Nvidia,
+            name: "RTX 4070".to_string(),
+            utilization_percent: 87,
+            memory_used_bytes: 4_294_967_296,
+            memory_total_bytes: 12_884_901_888,
+            memory_percent: 33.3,
+            temperature_celsius: Some(71),
+            fan_speed_percent: None,
+            power_draw_watts: Some(180),
+            power_limit_watts: Some(200),
+            clock_graphics_mhz: None,
+            clock_memory_mhz: None,
+            pcie_generation: Some(1),
+            pcie_lanes: Some(16),
+            max_pcie_generation: Some(4),
+            max_pcie_lanes: Some(16),
+        });
+        metrics.disks = vec![DiskMetrics {
+            name: "C:\\".to_string(),
+            mount_point: "C:\\".to_string(),
+            total_bytes: 1000,
+            available_bytes: 250,
+            usage_percent: 75.0,
+            ..Default::default()
+        }];
+        metrics.network = vec![NetworkMetrics {
+            interface: "eth0".to_string(),
+            rx_bytes_total: 1234,
+            ..Default::default()
+        }];
+        metrics
+    }
+
+    #[test]
+    fn test_render_maps_fields_to_labelled_series() {
+        let text = render(&sample_metrics());
+
+        assert!(text.contains("# TYPE msc_cpu_usage_percent gauge\nmsc_cpu_usage_percent 42.5\n"));
+        assert!(text.contains("msc_cpu_core_usage_percent{core=\"1\"} 45\n"));
+        assert!(text.contains("msc_memory_used_bytes 8589934592\n"));
+        assert!(text.contains("msc_gpu_temp_celsius{index=\"0\",name=\"RTX 4070\"} 71\n"));
+        assert!(text.contains("msc_disk_usage_percent{mount=\"C:\\\\\",device=\"C:\\\\\"} 75\n"));
+        assert!(text.contains("# TYPE msc_network_receive_bytes_total counter\n"));
+        assert!(text.contains("msc_network_receive_bytes_total{interface=\"eth0\"} 1234\n"));
+
+        // Values the GPU doesn't report are left out instead of exported as 0
+        assert!(!text.contains("msc_gpu_fan_speed_percent"));
+        assert!(!text.contains("msc_battery_percent"));
+    }
+
+    #[test]
+    fn test_every_sample_has_help_and_type() {
+        let text = render(&sample_metrics());
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(
+                text.contains(&format!("# TYPE {} ", name)),
+                "{} has no TYPE line",
+                name
+            );
+            assert!(name.starts_with("msc_"));
+        }
+    }
+}
@@ -2,15 +2,28 @@
 //!
 //! This module provides the async runtime that coordinates all metrics collection tasks.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc, watch};
 
 use super::metrics::SystemMetrics;
 use super::tasks::{
     battery_task, cpu_memory_process_task, disks_task, global_metrics_task, gpu_task, network_task,
-    temperatures_task, SubsystemUpdate,
+    temperatures_task, Subsystem, SubsystemUpdate,
 };
 
+/// Health of a subsystem as last reported by its task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubsystemStatus {
+    Ok,
+    /// The task could not collect metrics; the reason is shown instead of blank data
+    Unavailable(String),
+}
+
+/// Latest status of every subsystem that has reported at least once
+pub type SubsystemStatuses = HashMap<Subsystem, SubsystemStatus>;
+
 /// Wrapper around the Tokio runtime for metrics collection.
 ///
 /// This provides a clean interface for managing the background metrics collection.
@@ -21,6 +34,9 @@ pub struct MetricsRuntime {
     /// Sender for UI state changes (for adaptive scheduling - future)
     pub ui_events_tx: watch::Sender<UiState>,
 
+    /// Latest status of each subsystem merged into the snapshot so far
+    status_rx: watch::Receiver<SubsystemStatuses>,
+
     /// Shutdown signal sender
     shutdown_tx: broadcast::Sender<()>,
 
@@ -28,6 +44,54 @@ pub struct MetricsRuntime {
     _runtime_handle: tokio::runtime::Runtime,
 }
 
+/// A snapshot together with the subsystems that never reported in time
+#[derive(Debug, Clone)]
+pub struct CompleteSnapshot {
+    pub metrics: Arc<SystemMetrics>,
+    pub missing: Vec<Subsystem>,
+    /// Subsystems that reported an error instead of metrics, with the reason
+    pub unavailable: Vec<(Subsystem, String)>,
+}
+
+impl CompleteSnapshot {
+    /// The snapshot as JSON
+    ///
+    /// Fields of missing and unavailable subsystems are null; the reasons are
+    /// listed under `"unavailable"` (e.g. `{"gpu": "NVML not found"}`).
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        let mut value = serde_json::to_value(&*self.metrics)?;
+        let unavailable = self.unavailable.iter().map(|(subsystem, _)| subsystem);
+        for subsystem in self.missing.iter().chain(unavailable) {
+            for field in subsystem.json_fields() {
+                if let Some(slot) = value.pointer_mut(field) {
+                    *slot = serde_json::Value::Null;
+                }
+            }
+        }
+
+        let reasons: serde_json::Map<String, serde_json::Value> = self
+            .unavailable
+            .iter()
+            .map(|(subsystem, reason)| (subsystem.name().to_string(), reason.clone().into()))
+            .collect();
+        if let Some(object) = value.as_object_mut() {
+            object.insert("unavailable".to_string(), reasons.into());
+        }
+        Ok(value)
+    }
+}
+
+/// Subsystems currently reporting an error, in [`Subsystem::ALL`] order
+pub fn unavailable_subsystems(statuses: &SubsystemStatuses) -> Vec<(Subsystem, String)> {
+    Subsystem::ALL
+        .into_iter()
+        .filter_map(|subsystem| match statuses.get(&subsystem) {
+            Some(SubsystemStatus::Unavailable(reason)) => Some((subsystem, reason.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
 /// UI state that tasks can react to (for adaptive scheduling).
 #[derive(Debug, Clone)]
 pub struct UiState {
@@ -53,12 +117,19 @@ impl MetricsRuntime {
             selected_tab: 0,
             should_collect_processes: true,
         });
+        let (status_tx, status_rx) = watch::channel(SubsystemStatuses::new());
         let (shutdown_tx, _) = broadcast::channel::<()>(1);
 
         // Spawn all tasks on the runtime
         let shutdown_for_spawn = shutdown_tx.clone();
         runtime.spawn(async move {
-            spawn_all_tasks(snapshot_tx, ui_events_rx, shutdown_for_spawn.subscribe()).await
+            spawn_all_tasks(
+                snapshot_tx,
+                status_tx,
+                ui_events_rx,
+                shutdown_for_spawn.subscribe(),
+            )
+            .await
         });
 
         // log::info!("MetricsRuntime initialized successfully");
@@ -66,11 +137,60 @@ impl MetricsRuntime {
         Ok(Self {
             snapshot_rx,
             ui_events_tx,
+            status_rx,
             shutdown_tx,
             _runtime_handle: runtime,
         })
     }
 
+    /// Latest merged snapshot.
+    pub fn latest_snapshot(&self) -> Arc<SystemMetrics> {
+        self.snapshot_rx.borrow().clone()
+    }
+
+    /// Block until the first complete snapshot is available, or `timeout` elapses.
+    ///
+    /// Subsystems report independently, so right after startup the snapshot
+    /// is still mostly defaults; this waits for CPU/memory and global metrics.
+    pub fn wait_for_snapshot(&self, timeout: Duration) -> Arc<SystemMetrics> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let snapshot = self.latest_snapshot();
+            let ready = snapshot.cpu.core_count > 0 && !snapshot.global.hostname.is_empty();
+            if ready || Instant::now() >= deadline {
+                return snapshot;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Block until every subsystem has reported at least once, or `timeout` elapses.
+    ///
+    /// Unlike [`Self::wait_for_snapshot`] this also waits for slow subsystems
+    /// (disks, temperatures, GPU); the ones that never report are listed in
+    /// [`CompleteSnapshot::missing`].
+    pub fn wait_for_all_subsystems(&self, timeout: Duration) -> CompleteSnapshot {
+        let missing = wait_for_reports(&self.status_rx, timeout);
+        CompleteSnapshot {
+            metrics: self.latest_snapshot(),
+            missing,
+            unavailable: unavailable_subsystems(&self.subsystem_statuses()),
+        }
+    }
+
+    /// Latest status of every subsystem that has reported
+    pub fn subsystem_statuses(&self) -> SubsystemStatuses {
+        self.status_rx.borrow().clone()
+    }
+
+    /// Why `subsystem` has no data, if its task reported an error
+    pub fn unavailable_reason(&self, subsystem: Subsystem) -> Option<String> {
+        match self.status_rx.borrow().get(&subsystem) {
+            Some(SubsystemStatus::Unavailable(reason)) => Some(reason.clone()),
+            _ => None,
+        }
+    }
+
     /// Shutdown the runtime gracefully.
     pub fn shutdown(self) {
         // log::info!("Shutting down MetricsRuntime");
@@ -84,6 +204,7 @@ impl MetricsRuntime {
 /// This function creates the orchestrator and all subsystem tasks.
 pub async fn spawn_all_tasks(
     snapshot_tx: watch::Sender<Arc<SystemMetrics>>,
+    status_tx: watch::Sender<SubsystemStatuses>,
     _ui_events_rx: watch::Receiver<UiState>,
     shutdown: broadcast::Receiver<()>,
 ) {
@@ -96,6 +217,7 @@ pub async fn spawn_all_tasks(
     tokio::spawn(orchestrator_task(
         update_rx,
         snapshot_tx,
+        status_tx,
         shutdown.resubscribe(),
     ));
 
@@ -131,6 +253,7 @@ pub async fn spawn_all_tasks(
 async fn orchestrator_task(
     mut update_rx: mpsc::Receiver<SubsystemUpdate>,
     snapshot_tx: watch::Sender<Arc<SystemMetrics>>,
+    status_tx: watch::Sender<SubsystemStatuses>,
     mut shutdown: broadcast::Receiver<()>,
 ) {
     // log::info!("Orchestrator task started");
@@ -140,6 +263,14 @@ async fn orchestrator_task(
     loop {
         tokio::select! {
             Some(update) = update_rx.recv() => {
+                let subsystem = update.subsystem();
+                let status = match &update {
+                    SubsystemUpdate::Unavailable { reason, .. } => {
+                        SubsystemStatus::Unavailable(reason.clone())
+                    }
+                    _ => SubsystemStatus::Ok,
+                };
+
                 // Merge update into current snapshot
                 match update {
                     SubsystemUpdate::CpuMemoryProcess { cpu, memory, processes } => {
@@ -167,6 +298,15 @@ async fn orchestrator_task(
                     SubsystemUpdate::Global(global) => {
                         current_snapshot.global = global;
                     }
+                    SubsystemUpdate::Unavailable { subsystem, reason } => {
+                        log::warn!("{} unavailable: {}", subsystem.label(), reason);
+                        // Drop stale data so it is not shown as current
+                        match subsystem {
+                            Subsystem::Gpu => current_snapshot.gpu = None,
+                            Subsystem::Temperatures => current_snapshot.temperatures.clear(),
+                            _ => {}
+                        }
+                    }
                 }
 
                 // Update timestamp
@@ -175,6 +315,10 @@ async fn orchestrator_task(
                 // Send updated snapshot
                 // watch::send() only fails if there are no receivers (which is fine)
                 let _ = snapshot_tx.send(Arc::new(current_snapshot.clone()));
+                // Marked after the snapshot is sent, so waiters never see a report without its data
+                status_tx.send_if_modified(|statuses| {
+                    statuses.insert(subsystem, status.clone()).as_ref() != Some(&status)
+                });
 
                 // log::trace!("Snapshot updated and sent to UI");
             }
@@ -185,3 +329,152 @@ async fn orchestrator_task(
         }
     }
 }
+
+/// Poll until every subsystem is in `reported` or `timeout` elapses; returns the missing ones
+fn wait_for_reports(
+    reported: &watch::Receiver<SubsystemStatuses>,
+    timeout: Duration,
+) -> Vec<Subsystem> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let missing: Vec<Subsystem> = {
+            let reported = reported.borrow();
+            Subsystem::ALL
+                .into_iter()
+                .filter(|subsystem| !reported.contains_key(subsystem))
+                .collect()
+        };
+        let now = Instant::now();
+        if missing.is_empty() || now >= deadline {
+            return missing;
+        }
+        std::thread::sleep(Duration::from_millis(20).min(deadline - now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_for_reports_returns_when_all_reported() {
+        let (status_tx, status_rx) = watch::channel(SubsystemStatuses::new());
+        let reporter = std::thread::spawn(move || {
+            for subsystem in Subsystem::ALL {
+                std::thread::sleep(Duration::from_millis(5));
+                status_tx.send_modify(|statuses| {
+                    statuses.insert(subsystem, SubsystemStatus::Ok);
+                });
+            }
+        });
+
+        let started = Instant::now();
+        let missing = wait_for_reports(&status_rx, Duration::from_secs(10));
+        reporter.join().unwrap();
+
+        assert!(missing.is_empty());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_wait_for_reports_times_out_with_missing() {
+        let reported: SubsystemStatuses = Subsystem::ALL
+            .into_iter()
+            .filter(|subsystem| *subsystem != Subsystem::Gpu)
+            .map(|subsystem| (subsystem, SubsystemStatus::Ok))
+            .collect();
+        let (_status_tx, status_rx) = watch::channel(reported);
+
+        let started = Instant::now();
+        let missing = wait_for_reports(&status_rx, Duration::from_millis(100));
+
+        assert_eq!(missing, [Subsystem::Gpu]);
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_complete_snapshot_json_nulls_missing_fields() {
+        let mut metrics = SystemMetrics::default();
+        metrics.global.hostname = "box".to_string();
+        metrics.global.battery_percent = Some(50.0);
+        let snapshot = CompleteSnapshot {
+            metrics: Arc::new(metrics),
+            missing: vec![Subsystem::Battery, Subsystem::Disks],
+            unavailable: vec![(Subsystem::Gpu, "NVML not found".to_string())],
+        };
+
+        let json = snapshot.to_json().unwrap();
+        assert!(json["disks"].is_null());
+        assert!(json["global"]["battery_percent"].is_null());
+        assert!(json["global"]["power_source"].is_null());
+        assert_eq!(json["global"]["hostname"], "box");
+        assert!(json["cpu"].is_object());
+        assert!(json["network"].is_array());
+        assert!(json["gpu"].is_null());
+        assert_eq!(json["unavailable"]["gpu"], "NVML not found");
+    }
+
+    #[tokio::test]
+    async fn test_failing_collector_surfaces_reason_in_status() {
+        let (update_tx, update_rx) = mpsc::channel(8);
+        let (snapshot_tx, snapshot_rx) = watch::channel(Arc::new(SystemMetrics::default()));
+        let (status_tx, mut status_rx) = watch::channel(SubsystemStatuses::new());
+        let (shutdown_tx, _) = broadcast::channel::<()>(1);
+        let orchestrator = tokio::spawn(orchestrator_task(
+            update_rx,
+            snapshot_tx,
+            status_tx,
+            shutdown_tx.subscribe(),
+        ));
+
+        let updates = [
+            SubsystemUpdate::Temperatures(vec![Default::default()]),
+            SubsystemUpdate::Unavailable {
+                subsystem: Subsystem::Temperatures,
+                reason: "no temperature sensors found".to_string(),
+            },
+            SubsystemUpdate::Unavailable {
+                subsystem: Subsystem::Gpu,
+                reason: "NVML library not found".to_string(),
+            },
+            SubsystemUpdate::Disks(Vec::new()),
+        ];
+        for update in updates {
+            update_tx.send(update).await.unwrap();
+        }
+
+        let statuses = tokio::time::timeout(
+            Duration::from_secs(5),
+            status_rx.wait_for(|statuses| statuses.len() == 3),
+        )
+        .await
+        .expect("orchestrator should report every subsystem")
+        .unwrap()
+        .clone();
+
+        assert_eq!(
+            statuses.get(&Subsystem::Gpu),
+            Some(&SubsystemStatus::Unavailable(
+                "NVML library not found".to_string()
+            ))
+        );
+        assert_eq!(statuses.get(&Subsystem::Disks), Some(&SubsystemStatus::Ok));
+        assert_eq!(
+            unavailable_subsystems(&statuses),
+            [
+                (Subsystem::Gpu, "NVML library not found".to_string()),
+                (
+                    Subsystem::Temperatures,
+                    "no temperature sensors found".to_string()
+                ),
+            ]
+        );
+        assert!(
+            snapshot_rx.borrow().temperatures.is_empty(),
+            "Stale readings are dropped"
+        );
+
+        let _ = shutdown_tx.send(());
+        orchestrator.await.unwrap();
+    }
+}
@@ -3,7 +3,7 @@
 use tokio::sync::{broadcast, mpsc};
 use tokio::time::{interval, Duration, MissedTickBehavior};
 
-use super::SubsystemUpdate;
+use super::{Subsystem, SubsystemUpdate};
 use crate::platform::gpu::get_gpu_provider;
 
 /// Task that monitors GPU metrics.
@@ -18,8 +18,13 @@ pub async fn gpu_task(
         Ok(provider) => provider,
         Err(e) => {
             log::warn!("GPU initialization failed: {:?}", e);
-            // Send None and terminate task (no GPU available)
-            let _ = update_tx.send(SubsystemUpdate::Gpu(None)).await;
+            // Report why and terminate task (no GPU available)
+            let _ = update_tx
+                .send(SubsystemUpdate::Unavailable {
+                    subsystem: Subsystem::Gpu,
+                    reason: e.to_string(),
+                })
+                .await;
             return;
         }
     };
@@ -53,7 +58,10 @@ pub async fn gpu_task(
                         consecutive_failures += 1;
                         if consecutive_failures >= MAX_FAILURES {
                             log::error!("GPU monitoring disabled after {} consecutive failures", MAX_FAILURES);
-                            let _ = update_tx.send(SubsystemUpdate::Gpu(None)).await;
+                            let _ = update_tx.send(SubsystemUpdate::Unavailable {
+                                subsystem: Subsystem::Gpu,
+                                reason: format!("collection failed {} times in a row: {}", MAX_FAILURES, e),
+                            }).await;
                             break;
                         }
 
@@ -54,4 +54,93 @@ pub enum SubsystemUpdate {
 
     /// Global system metrics (uptime, hostname, etc.)
     Global(GlobalMetrics),
+
+    /// A subsystem could not collect its metrics (e.g. no GPU driver)
+    Unavailable {
+        subsystem: Subsystem,
+        reason: String,
+    },
+}
+
+impl SubsystemUpdate {
+    /// Task that produced this update
+    pub fn subsystem(&self) -> Subsystem {
+        match self {
+            Self::CpuMemoryProcess { .. } => Subsystem::CpuMemoryProcess,
+            Self::Gpu(_) => Subsystem::Gpu,
+            Self::Disks(_) => Subsystem::Disks,
+            Self::Network(_) => Subsystem::Network,
+            Self::Temperatures(_) => Subsystem::Temperatures,
+            Self::Battery { .. } => Subsystem::Battery,
+            Self::Global(_) => Subsystem::Global,
+            Self::Unavailable { subsystem, .. } => *subsystem,
+        }
+    }
+}
+
+/// Subsystem tasks feeding the orchestrator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    CpuMemoryProcess,
+    Gpu,
+    Disks,
+    Network,
+    Temperatures,
+    Battery,
+    Global,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 7] = [
+        Subsystem::CpuMemoryProcess,
+        Subsystem::Gpu,
+        Subsystem::Disks,
+        Subsystem::Network,
+        Subsystem::Temperatures,
+        Subsystem::Battery,
+        Subsystem::Global,
+    ];
+
+    /// Identifier used in JSON output
+    pub fn name(self) -> &'static str {
+        match self {
+            Subsystem::CpuMemoryProcess => "cpu_memory_process",
+            Subsystem::Gpu => "gpu",
+            Subsystem::Disks => "disks",
+            Subsystem::Network => "network",
+            Subsystem::Temperatures => "temperatures",
+            Subsystem::Battery => "battery",
+            Subsystem::Global => "global",
+        }
+    }
+
+    /// Name shown to users
+    pub fn label(self) -> &'static str {
+        match self {
+            Subsystem::CpuMemoryProcess => "CPU/Memory",
+            Subsystem::Gpu => "GPU",
+            Subsystem::Disks => "Disks",
+            Subsystem::Network => "Network",
+            Subsystem::Temperatures => "Temperatures",
+            Subsystem::Battery => "Battery",
+            Subsystem::Global => "System",
+        }
+    }
+
+    /// JSON pointers of the `SystemMetrics` fields this subsystem fills in
+    pub fn json_fields(self) -> &'static [&'static str] {
+        match self {
+            Subsystem::CpuMemoryProcess => &["/cpu", "/memory", "/top_processes"],
+            Subsystem::Gpu => &["/gpu"],
+            Subsystem::Disks => &["/disks"],
+            Subsystem::Network => &["/network"],
+            Subsystem::Temperatures => &["/temperatures"],
+            Subsystem::Battery => &[
+                "/global/power_source",
+                "/global/battery_percent",
+                "/global/battery_time_remaining_secs",
+            ],
+            Subsystem::Global => &["/global"],
+        }
+    }
 }
@@ -4,7 +4,7 @@ use sysinfo::Components;
 use tokio::sync::{broadcast, mpsc};
 use tokio::time::{interval, Duration, MissedTickBehavior};
 
-use super::SubsystemUpdate;
+use super::{Subsystem, SubsystemUpdate};
 use crate::core::system_monitor::collect_temperatures;
 
 /// Task that monitors temperature sensors.
@@ -31,7 +31,17 @@ pub async fn temperatures_task(
                 // Collect metrics using pure function
                 let temp_metrics = collect_temperatures(&components);
 
-                if let Err(e) = update_tx.send(SubsystemUpdate::Temperatures(temp_metrics)).await {
+                // An empty list would look like a blank panel; say why instead
+                let update = if temp_metrics.is_empty() {
+                    SubsystemUpdate::Unavailable {
+                        subsystem: Subsystem::Temperatures,
+                        reason: "no temperature sensors found".to_string(),
+                    }
+                } else {
+                    SubsystemUpdate::Temperatures(temp_metrics)
+                };
+
+                if let Err(e) = update_tx.send(update).await {
                     log::error!("Failed to send temperatures update: {}", e);
                     break;
                 }
@@ -1,12 +1,15 @@
 use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
-use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use super::platform_installer;
 use super::release_info::{fetch_latest_release, get_platform_assets, ReleaseInfo};
-use crate::core::Config;
+use crate::core::checksum_manager::Algorithm;
+use crate::core::http;
+use crate::core::{ChecksumManager, Config};
+use crate::ui::progress::read_with_progress;
+use crate::ui::{clear_line, show_progress_bar};
 
 pub struct UpdateManager {
     config: Config,
@@ -55,9 +58,10 @@ impl UpdateManager {
         println!("{} {}", "Downloading:".cyan(), binary_asset.name.yellow());
 
         // Descargar el binario
-        let binary_data = reqwest::blocking::get(&binary_asset.browser_download_url)
-            .context("Failed to download update")?
-            .bytes()
+        let response =
+            http::get(&binary_asset.browser_download_url).context("Failed to download update")?;
+        let total = response.content_length().unwrap_or(0);
+        let binary_data = read_with_progress(response, total, "Downloading:")
             .context("Failed to read update data")?;
 
         println!(
@@ -68,7 +72,7 @@ impl UpdateManager {
 
         // Descargar el checksum
         println!("{}", "Downloading checksum...".dimmed());
-        let checksum_text = reqwest::blocking::get(&checksum_asset.browser_download_url)
+        let checksum_text = http::get(&checksum_asset.browser_download_url)
             .context("Failed to download checksum")?
             .text()
             .context("Failed to read checksum")?;
@@ -93,12 +97,20 @@ impl UpdateManager {
     pub fn verify_checksum(&self, file_path: &Path, expected_hash: &str) -> Result<()> {
         println!("{}", "Verifying checksum...".cyan());
 
-        let file_data =
-            fs::read(file_path).context("Failed to read downloaded file for verification")?;
-
-        let mut hasher = Sha256::new();
-        hasher.update(&file_data);
-        let calculated_hash = format!("{:x}", hasher.finalize());
+        let calculated_hash = ChecksumManager::calculate_hash_with_progress(
+            file_path,
+            Algorithm::Sha256,
+            |hashed, total| {
+                show_progress_bar(
+                    (hashed / 1024) as usize,
+                    (total / 1024) as usize,
+                    "Hashing (KB):",
+                )
+            },
+            None,
+        )
+        .context("Failed to read downloaded file for verification")?;
+        clear_line();
 
         if calculated_hash.to_lowercase() != expected_hash.to_lowercase() {
             return Err(anyhow!(
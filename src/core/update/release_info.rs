@@ -1,6 +1,8 @@
 use anyhow::{anyhow, Context, Result};
 use serde::Deserialize;
 
+use crate::core::http;
+
 #[derive(Debug, Deserialize)]
 pub struct ReleaseInfo {
     pub tag_name: String,
@@ -31,11 +33,7 @@ impl ReleaseInfo {
 pub fn fetch_latest_release(repo: &str) -> Result<ReleaseInfo> {
     let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
 
-    let response = reqwest::blocking::Client::new()
-        .get(&url)
-        .header("User-Agent", "MSC-CLI")
-        .send()
-        .context("Failed to fetch latest release from GitHub")?;
+    let response = http::get(&url).context("Failed to fetch latest release from GitHub")?;
 
     if !response.status().is_success() {
         return Err(anyhow!(
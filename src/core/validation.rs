@@ -228,6 +228,33 @@ pub fn validate_directory_path(path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Resolve a directory path before storing it in the configuration
+///
+/// The path must be a directory. When it does not exist, `confirm_create` is
+/// asked whether to create it; declining is an error. The returned path is
+/// canonicalized.
+pub fn resolve_config_directory<F>(path: &str, confirm_create: F) -> Result<PathBuf>
+where
+    F: FnOnce(&Path) -> Result<bool>,
+{
+    validate_directory_path(path)?;
+
+    let path_buf = Path::new(path.trim());
+    if !path_buf.exists() {
+        ensure!(
+            confirm_create(path_buf)?,
+            "Directory does not exist: {}",
+            path
+        );
+        std::fs::create_dir_all(path_buf)
+            .with_context(|| format!("Failed to create directory: {}", path))?;
+    }
+
+    path_buf
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path: {}", path))
+}
+
 pub fn validate_workspace_name(name: &str) -> Result<()> {
     if name.trim().is_empty() {
         return Err(anyhow!("Workspace name cannot be empty"));
@@ -679,6 +706,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_config_directory_existing() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().to_string_lossy().to_string();
+
+        let resolved = resolve_config_directory(&path, |_| {
+            panic!("Should not ask to create an existing directory")
+        })
+        .unwrap();
+
+        assert_eq!(resolved, temp.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_config_directory_creates_missing() {
+        let temp = tempfile::tempdir().unwrap();
+        let missing = temp.path().join("new").join("videos");
+        let path = missing.to_string_lossy().to_string();
+
+        // Declined: error and nothing created
+        assert!(resolve_config_directory(&path, |_| Ok(false)).is_err());
+        assert!(!missing.exists());
+
+        // Accepted: created and canonicalized
+        let resolved = resolve_config_directory(&path, |_| Ok(true)).unwrap();
+        assert!(missing.is_dir());
+        assert_eq!(resolved, missing.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_config_directory_rejects_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("notes");
+        std::fs::write(&file, "not a directory").unwrap();
+
+        let result = resolve_config_directory(&file.to_string_lossy(), |_| Ok(true));
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_redact_url_credentials() {
         // URLs with credentials should be redacted
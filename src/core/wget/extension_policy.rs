@@ -0,0 +1,113 @@
+//! File extensions the crawler refuses to download
+//!
+//! The persisted list lives in the config (`msc wget reject-types`) and
+//! defaults to [`DEFAULT_REJECTED_EXTENSIONS`]. Each invocation can extend it
+//! with `--reject` or lift entries with `--accept`; `--accept` wins when an
+//! extension appears in both.
+
+use std::collections::BTreeSet;
+use url::Url;
+
+/// Extensions rejected until the user edits the list
+pub const DEFAULT_REJECTED_EXTENSIONS: [&str; 8] =
+    ["exe", "msi", "iso", "dmg", "img", "apk", "scr", "bat"];
+
+/// Normalize a user-supplied extension: trimmed, lowercase, no leading dot
+pub fn normalize_extension(ext: &str) -> String {
+    ext.trim().trim_start_matches('.').to_lowercase()
+}
+
+/// Which resource extensions may be downloaded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtensionPolicy {
+    rejected: BTreeSet<String>,
+}
+
+impl ExtensionPolicy {
+    /// Policy rejecting the given extensions
+    pub fn new<I, S>(rejected: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            rejected: rejected
+                .into_iter()
+                .map(|ext| normalize_extension(ext.as_ref()))
+                .filter(|ext| !ext.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Apply per-invocation `--reject` additions and `--accept` exceptions
+    pub fn with_overrides(mut self, accept: &[String], reject: &[String]) -> Self {
+        self.rejected.extend(
+            reject
+                .iter()
+                .map(|ext| normalize_extension(ext))
+                .filter(|ext| !ext.is_empty()),
+        );
+        for ext in accept {
+            self.rejected.remove(&normalize_extension(ext));
+        }
+        self
+    }
+
+    /// Rejected extensions, sorted
+    pub fn rejected(&self) -> impl Iterator<Item = &str> {
+        self.rejected.iter().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    /// Whether the resource at `url` may be downloaded
+    ///
+    /// Only the extension of the URL path is checked, so query strings and
+    /// fragments never cause a rejection. URLs without an extension are allowed.
+    pub fn allows(&self, url: &str) -> bool {
+        if self.rejected.is_empty() {
+            return true;
+        }
+
+        let path = match Url::parse(url) {
+            Ok(parsed) => parsed.path().to_string(),
+            Err(_) => url.split(['?', '#']).next().unwrap_or_default().to_string(),
+        };
+        let file_name = path.rsplit('/').next().unwrap_or_default();
+
+        match file_name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => !self.rejected.contains(&ext.to_lowercase()),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_by_path_extension() {
+        let policy = ExtensionPolicy::new(["EXE", ".iso"]);
+
+        assert!(!policy.allows("https://example.com/setup.exe"));
+        assert!(!policy.allows("https://example.com/dl/Ubuntu.ISO?mirror=1"));
+        assert!(policy.allows("https://example.com/page.html?file=setup.exe"));
+        assert!(policy.allows("https://example.com/downloads/"));
+        assert!(policy.allows("https://example.com/.exe"));
+        assert!(policy.allows("/img/logo.png"));
+    }
+
+    #[test]
+    fn test_overrides_extend_and_lift_rejections() {
+        let policy = ExtensionPolicy::new(DEFAULT_REJECTED_EXTENSIONS)
+            .with_overrides(&["exe".to_string()], &[".zip".to_string()]);
+
+        assert!(policy.allows("https://example.com/setup.exe"));
+        assert!(!policy.allows("https://example.com/archive.zip"));
+        assert!(!policy.allows("https://example.com/disk.iso"));
+        assert!(!policy.rejected().any(|ext| ext == "exe"));
+    }
+}
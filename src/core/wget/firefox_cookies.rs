@@ -0,0 +1,263 @@
+// Firefox (and LibreWolf) cookie extraction
+//
+// Firefox keeps unencrypted cookies in `<profile>/cookies.sqlite` (`moz_cookies`
+// table). The profile in use is listed in `profiles.ini` next to the profiles.
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::Connection;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::wget_cookies::Cookie;
+
+/// Seconds between the Windows epoch (1601, used by [`Cookie::expires`]) and 1970
+const WINDOWS_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// Expiry values above this are milliseconds (Firefox 110+), not seconds
+const MILLISECOND_EXPIRY_THRESHOLD: i64 = 100_000_000_000;
+
+/// Whether `browser` stores cookies the Firefox way
+pub fn is_firefox_browser(browser: &str) -> bool {
+    matches!(browser.to_lowercase().as_str(), "firefox" | "librewolf")
+}
+
+/// Directory holding `profiles.ini` for a Firefox-based browser
+pub fn firefox_data_dir(browser: &str) -> Result<PathBuf> {
+    let librewolf = browser.eq_ignore_ascii_case("librewolf");
+
+    if cfg!(windows) {
+        let roaming = env::var("APPDATA").context("APPDATA no está configurado")?;
+        let dir = if librewolf {
+            "librewolf"
+        } else {
+            "Mozilla\\Firefox"
+        };
+        Ok(PathBuf::from(roaming).join(dir))
+    } else {
+        let home = PathBuf::from(env::var("HOME").context("HOME no está configurado")?);
+        let dir = match (cfg!(target_os = "macos"), librewolf) {
+            (true, true) => "Library/Application Support/librewolf",
+            (true, false) => "Library/Application Support/Firefox",
+            (false, true) => ".librewolf",
+            (false, false) => ".mozilla/firefox",
+        };
+        Ok(home.join(dir))
+    }
+}
+
+/// Default profile directory declared in `profiles.ini`
+///
+/// The `[Install…]` section (current Firefox) wins over a `[Profile…]`
+/// marked `Default=1`; if neither exists the first profile is used.
+pub fn default_profile_from_ini(content: &str, data_dir: &Path) -> Option<PathBuf> {
+    let mut install_default = None;
+    let mut marked_default = None;
+    let mut first_profile = None;
+
+    // (section, path, is_relative, default flag) of the section being read
+    let mut section = String::new();
+    let mut path: Option<String> = None;
+    let mut is_relative = true;
+    let mut is_default = false;
+
+    let mut finish_section =
+        |section: &str, path: Option<String>, is_relative: bool, is_default: bool| {
+            let Some(path) = path else {
+                return;
+            };
+            let resolved = if is_relative {
+                data_dir.join(path)
+            } else {
+                PathBuf::from(path)
+            };
+
+            if section.starts_with("Install") {
+                install_default.get_or_insert(resolved);
+            } else if section.starts_with("Profile") {
+                if is_default {
+                    marked_default.get_or_insert(resolved.clone());
+                }
+                first_profile.get_or_insert(resolved);
+            }
+        };
+
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            finish_section(&section, path.take(), is_relative, is_default);
+            section = name.to_string();
+            is_relative = true;
+            is_default = false;
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match (key.trim(), value.trim()) {
+            // Install sections always use relative paths in `Default=`
+            ("Default", value) if section.starts_with("Install") => {
+                path = Some(value.to_string());
+            }
+            ("Default", value) => is_default = value == "1",
+            ("Path", value) => path = Some(value.to_string()),
+            ("IsRelative", value) => is_relative = value != "0",
+            _ => {}
+        }
+    }
+    finish_section(&section, path, is_relative, is_default);
+
+    install_default.or(marked_default).or(first_profile)
+}
+
+/// `cookies.sqlite` of the default profile under `data_dir`
+pub fn find_profile_cookie_db(data_dir: &Path) -> Result<PathBuf> {
+    let ini_path = data_dir.join("profiles.ini");
+    let content = fs::read_to_string(&ini_path).with_context(|| {
+        format!(
+            "No se pudo leer la lista de perfiles: {}",
+            ini_path.display()
+        )
+    })?;
+
+    let profile = default_profile_from_ini(&content, data_dir)
+        .ok_or_else(|| anyhow!("No hay ningún perfil declarado en: {}", ini_path.display()))?;
+
+    let cookie_file = profile.join("cookies.sqlite");
+    if !cookie_file.exists() {
+        return Err(anyhow!(
+            "El perfil por defecto no tiene 'cookies.sqlite': {}",
+            profile.display()
+        ));
+    }
+    Ok(cookie_file)
+}
+
+/// Convert a `moz_cookies.expiry` value to the Chrome timestamp used by [`Cookie`]
+pub fn firefox_expiry_to_chrome(expiry: i64) -> i64 {
+    if expiry <= 0 {
+        return 0; // Session cookie
+    }
+
+    let secs = if expiry > MILLISECOND_EXPIRY_THRESHOLD {
+        expiry / 1000
+    } else {
+        expiry
+    };
+    (secs + WINDOWS_EPOCH_OFFSET_SECS) * 1_000_000
+}
+
+/// Cookies for `domain` (also `.domain` and `www.domain`) from a `moz_cookies` table
+pub fn read_moz_cookies(conn: &Connection, domain: &str) -> Result<Vec<Cookie>> {
+    let clean_domain = domain.strip_prefix("www.").unwrap_or(domain);
+    let hosts = [
+        format!(".{}", clean_domain),
+        clean_domain.to_string(),
+        format!("www.{}", clean_domain),
+    ];
+
+    let mut stmt = conn.prepare(
+        "SELECT name, value, host, path, expiry, isSecure, COALESCE(isHttpOnly, 0), \
+         COALESCE(sameSite, -1) FROM moz_cookies WHERE host = ?1 OR host = ?2 OR host = ?3",
+    )?;
+
+    let cookies = stmt
+        .query_map(hosts, |row| {
+            let same_site = match row.get::<_, i64>(7)? {
+                0 => "None",
+                1 => "Lax",
+                2 => "Strict",
+                _ => "Unspecified",
+            };
+
+            Ok(Cookie {
+                name: row.get(0)?,
+                value: row.get(1)?,
+                domain: row.get(2)?,
+                path: row.get(3)?,
+                expires: firefox_expiry_to_chrome(row.get(4)?),
+                secure: row.get::<_, i64>(5)? != 0,
+                http_only: row.get::<_, i64>(6)? != 0,
+                same_site: same_site.to_string(),
+            })
+        })?
+        .flatten()
+        .collect();
+
+    Ok(cookies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::wget::chrome_time_to_unix;
+
+    #[test]
+    fn test_install_section_wins_over_default_flag() {
+        let ini = "[Profile1]\nName=old\nIsRelative=1\nPath=Profiles/old.default\nDefault=1\n\n\
+                   [Profile0]\nName=main\nIsRelative=1\nPath=Profiles/abc.default-release\n\n\
+                   [Install4F96D1932A9F858E]\nDefault=Profiles/abc.default-release\nLocked=1\n";
+        let dir = Path::new("/ff");
+
+        assert_eq!(
+            default_profile_from_ini(ini, dir),
+            Some(dir.join("Profiles/abc.default-release"))
+        );
+
+        // Without an install section, the profile marked Default=1 is used
+        let legacy = ini.split("[Install").next().unwrap();
+        assert_eq!(
+            default_profile_from_ini(legacy, dir),
+            Some(dir.join("Profiles/old.default"))
+        );
+    }
+
+    #[test]
+    fn test_absolute_and_first_profile_paths() {
+        let ini = "[General]\nStartWithLastProfile=1\n\n\
+                   [Profile0]\nName=work\nIsRelative=0\nPath=/data/ff-work\n";
+        assert_eq!(
+            default_profile_from_ini(ini, Path::new("/ff")),
+            Some(PathBuf::from("/data/ff-work"))
+        );
+        assert_eq!(
+            default_profile_from_ini("[General]\n", Path::new("/ff")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expiry_converts_seconds_and_milliseconds() {
+        // 2024-01-01 00:00:00 UTC
+        let chrome = 13_348_540_800_000_000;
+        assert_eq!(firefox_expiry_to_chrome(1_704_067_200), chrome);
+        assert_eq!(firefox_expiry_to_chrome(1_704_067_200_000), chrome);
+        assert_eq!(firefox_expiry_to_chrome(0), 0);
+    }
+
+    #[test]
+    fn test_read_moz_cookies_maps_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE moz_cookies (name TEXT, value TEXT, host TEXT, path TEXT, \
+             expiry INTEGER, isSecure INTEGER, isHttpOnly INTEGER, sameSite INTEGER);
+             INSERT INTO moz_cookies VALUES ('sid', 'abc', '.example.com', '/', 1704067200, 1, 1, 1);
+             INSERT INTO moz_cookies VALUES ('age', '18', 'www.example.com', '/', 0, 0, 0, 0);
+             INSERT INTO moz_cookies VALUES ('other', 'x', '.other.test', '/', 0, 0, 0, 0);",
+        )
+        .unwrap();
+
+        let mut cookies = read_moz_cookies(&conn, "www.example.com").unwrap();
+        cookies.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name, "age");
+        assert_eq!(cookies[0].expires, 0);
+        assert_eq!(cookies[0].same_site, "None");
+        assert_eq!(cookies[1].value, "abc");
+        assert_eq!(cookies[1].domain, ".example.com");
+        assert!(cookies[1].secure && cookies[1].http_only);
+        assert_eq!(cookies[1].same_site, "Lax");
+        assert_eq!(chrome_time_to_unix(cookies[1].expires), 1_704_067_200);
+    }
+}
@@ -0,0 +1,115 @@
+//! Character encoding of saved HTML pages
+//!
+//! Post-processing works on UTF-8 text. Pages saved in another encoding
+//! (e.g. ISO-8859-1) are decoded with the charset their byte-order mark or
+//! `<meta>` tags declare, processed as UTF-8 and written back as UTF-8 with
+//! the declaration updated by [`declare_utf8`].
+
+use encoding_rs::{Encoding, UTF_8};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Bytes searched for a `<meta>` charset declaration, as browsers do
+const CHARSET_SNIFF_LIMIT: usize = 1024;
+
+/// `charset=` inside `<meta charset>` or `<meta http-equiv="Content-Type" content="...">`
+fn charset_regex() -> &'static Regex {
+    static CHARSET_RE: OnceLock<Regex> = OnceLock::new();
+    CHARSET_RE.get_or_init(|| {
+        Regex::new(r#"(?i)(<meta\b[^>]*?\bcharset\s*=\s*["']?\s*)([a-z0-9_.:\-]+)"#)
+            .expect("Hardcoded regex must be valid")
+    })
+}
+
+/// HTML page decoded to UTF-8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedHtml {
+    pub text: String,
+    /// Encoding the file was read with
+    pub encoding: &'static Encoding,
+    /// Some bytes were invalid in that encoding and were replaced with U+FFFD
+    pub lossy: bool,
+}
+
+impl DecodedHtml {
+    /// Whether the file was not UTF-8 and has to be written back converted
+    pub fn converted(&self) -> bool {
+        self.encoding != UTF_8
+    }
+}
+
+/// Charset label declared in the first bytes of `bytes`, if any
+pub fn declared_charset(bytes: &[u8]) -> Option<String> {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(CHARSET_SNIFF_LIMIT)]);
+    charset_regex()
+        .captures(&head)
+        .map(|caps| caps[2].to_string())
+}
+
+/// Decode an HTML file using its byte-order mark or declared charset, defaulting to UTF-8
+///
+/// Bytes that are invalid in the chosen encoding are replaced and reported
+/// through [`DecodedHtml::lossy`] instead of failing.
+pub fn decode_html(bytes: &[u8]) -> DecodedHtml {
+    let declared = declared_charset(bytes)
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        // A page cannot really be UTF-16 if its <meta> was readable as ASCII
+        .map(Encoding::output_encoding)
+        .unwrap_or(UTF_8);
+
+    // decode() lets a byte-order mark override the declared encoding
+    let (text, encoding, lossy) = declared.decode(bytes);
+    DecodedHtml {
+        text: text.into_owned(),
+        encoding,
+        lossy,
+    }
+}
+
+/// Point every `<meta>` charset declaration at UTF-8
+pub fn declare_utf8(html: &str) -> String {
+    charset_regex().replace_all(html, "${1}utf-8").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_charset_forms() {
+        assert_eq!(
+            declared_charset(br#"<head><meta charset="ISO-8859-1"></head>"#).as_deref(),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(
+            declared_charset(
+                br#"<meta http-equiv="Content-Type" content="text/html; charset=windows-1252">"#
+            )
+            .as_deref(),
+            Some("windows-1252")
+        );
+        assert_eq!(
+            declared_charset(b"<html><body>sin meta</body></html>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_decode_and_redeclare() {
+        let latin1 = b"<meta charset='iso-8859-1'><p>Espa\xf1a</p>";
+        let decoded = decode_html(latin1);
+        assert!(decoded.converted());
+        assert!(!decoded.lossy);
+        assert_eq!(decoded.text, "<meta charset='iso-8859-1'><p>España</p>");
+        assert_eq!(
+            declare_utf8(&decoded.text),
+            "<meta charset='utf-8'><p>España</p>"
+        );
+
+        // Undeclared invalid UTF-8 is decoded lossily instead of failing
+        let broken = decode_html(b"<p>Espa\xf1a</p>");
+        assert!(!broken.converted());
+        assert!(broken.lossy);
+        assert_eq!(broken.text, "<p>Espa\u{fffd}a</p>");
+    }
+}
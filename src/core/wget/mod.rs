@@ -6,6 +6,10 @@ pub mod chrome_launcher;
 pub mod chrome_manager;
 pub mod cookie_formats;
 pub mod dpapi;
+pub mod extension_policy;
+pub mod firefox_cookies;
+pub mod html_charset;
+pub mod site_profile;
 pub mod wget_cookies;
 pub mod wget_manager;
 pub mod wget_utils;
@@ -23,13 +27,23 @@ pub use cookie_formats::{
     format_wget,
 };
 pub use dpapi::decrypt_dpapi;
+pub use extension_policy::{normalize_extension, ExtensionPolicy, DEFAULT_REJECTED_EXTENSIONS};
+pub use firefox_cookies::is_firefox_browser;
+pub use html_charset::{decode_html, DecodedHtml};
+pub use site_profile::{SiteProfile, SiteProfiles, DEFAULT_PROFILE};
 pub use wget_cookies::{
     create_cookie_file, debug_database_info, extract_cookies_from_db, extract_cookies_with_cdp,
     find_browser_cookie_db, format_cookies, resolve_cookie_path, Cookie,
 };
 pub use wget_manager::WgetManager;
 pub use wget_utils::{
-    calculate_local_path_for_url, calculate_possible_local_paths, download_resource,
-    extract_filename_from_url, is_local_path, is_placeholder_image,
+    asset_file_name, calculate_flat_local_path, calculate_local_path_for_url,
+    calculate_possible_local_paths, corrected_asset_path, document_base_url, download_resource,
+    download_resource_via, download_resource_with_progress, extract_filename_from_url, head_check,
+    head_check_via, is_compressed_format, is_local_path, is_placeholder_image, meta_refresh_target,
+    HeadCheckReport,
+};
+pub use wgetpostprocessing::{
+    process_html_file_complete, process_html_file_with_options, DownloadStats, PostProcessLog,
+    PostProcessOptions, SavedAssets,
 };
-pub use wgetpostprocessing::process_html_file_complete;
@@ -0,0 +1,279 @@
+//! Site profiles for wget post-processing
+//!
+//! A profile selects which post-processing phases run on downloaded HTML.
+//! Built-in profiles cover the site types the post-processor was written
+//! for; users can add or override profiles in
+//! `<config_dir>/msc/wget_profiles.json`:
+//!
+//! ```json
+//! {
+//!   "profiles": {
+//!     "blog": { "cdn_downloads": false, "removal_rules": ["tracking", "dns-prefetch"] }
+//!   }
+//! }
+//! ```
+//!
+//! Fields omitted in a user profile keep the `default` profile's value.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Profile used when `--profile` is not given
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Element and script removal rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RemovalRule {
+    /// Redirect, cookie-banner, analytics, CAPTCHA, bot-detection and geo scripts
+    Scripts,
+    /// `<link rel="dns-prefetch">` elements
+    DnsPrefetch,
+    /// Google Tag Manager links and iframes
+    Tracking,
+    /// "Create account" forms
+    AccountForms,
+    /// Cookie banner menu entries
+    CookieBanners,
+    /// adBlock alert `if` blocks
+    AdblockAlerts,
+    /// Holiday promo `if/else` blocks
+    HolidayPromos,
+}
+
+impl RemovalRule {
+    pub const ALL: [RemovalRule; 7] = [
+        RemovalRule::Scripts,
+        RemovalRule::DnsPrefetch,
+        RemovalRule::Tracking,
+        RemovalRule::AccountForms,
+        RemovalRule::CookieBanners,
+        RemovalRule::AdblockAlerts,
+        RemovalRule::HolidayPromos,
+    ];
+}
+
+/// Post-processing phases enabled for a site
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteProfile {
+    pub description: String,
+    /// Hydrate `data-src`-style lazy images and download `srcset` variants
+    pub lazy_images: bool,
+    /// Rewrite `ts_reader.run(...)` chapter data (manga/manhwa readers)
+    pub reader_scripts: bool,
+    /// Download CDN URLs found in JS variables and markup
+    pub cdn_downloads: bool,
+    /// Fix autoplay flags, broken `embedCode` JSON and unquoted `rel` attributes
+    pub markup_fixes: bool,
+    pub removal_rules: Vec<RemovalRule>,
+}
+
+impl Default for SiteProfile {
+    /// Every phase enabled (the behavior before profiles existed)
+    fn default() -> Self {
+        Self {
+            description: "Todas las fases de post-procesamiento".to_string(),
+            lazy_images: true,
+            reader_scripts: true,
+            cdn_downloads: true,
+            markup_fixes: true,
+            removal_rules: RemovalRule::ALL.to_vec(),
+        }
+    }
+}
+
+impl SiteProfile {
+    /// Whether a removal rule is enabled
+    pub fn removes(&self, rule: RemovalRule) -> bool {
+        self.removal_rules.contains(&rule)
+    }
+}
+
+/// Profiles shipped with msc
+pub fn builtin_profiles() -> Vec<(&'static str, SiteProfile)> {
+    vec![
+        (DEFAULT_PROFILE, SiteProfile::default()),
+        (
+            "manhwa",
+            SiteProfile {
+                description: "Lectores de manhwa/manga: imágenes diferidas, lector y CDN"
+                    .to_string(),
+                lazy_images: true,
+                reader_scripts: true,
+                cdn_downloads: true,
+                markup_fixes: false,
+                removal_rules: vec![
+                    RemovalRule::Scripts,
+                    RemovalRule::DnsPrefetch,
+                    RemovalRule::Tracking,
+                    RemovalRule::CookieBanners,
+                    RemovalRule::AdblockAlerts,
+                ],
+            },
+        ),
+        (
+            "wordpress",
+            SiteProfile {
+                description: "Blogs WordPress: imágenes diferidas y limpieza de tracking"
+                    .to_string(),
+                lazy_images: true,
+                reader_scripts: false,
+                cdn_downloads: true,
+                markup_fixes: false,
+                removal_rules: vec![
+                    RemovalRule::DnsPrefetch,
+                    RemovalRule::Tracking,
+                    RemovalRule::CookieBanners,
+                ],
+            },
+        ),
+        (
+            "docs",
+            SiteProfile {
+                description: "Sitios de documentación: solo limpieza de tracking".to_string(),
+                lazy_images: false,
+                reader_scripts: false,
+                cdn_downloads: false,
+                markup_fixes: false,
+                removal_rules: vec![RemovalRule::DnsPrefetch, RemovalRule::Tracking],
+            },
+        ),
+    ]
+}
+
+/// Layout of `wget_profiles.json`
+#[derive(Debug, Default, Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, SiteProfile>,
+}
+
+/// Built-in profiles merged with the user's profiles
+#[derive(Debug, Clone)]
+pub struct SiteProfiles {
+    profiles: BTreeMap<String, SiteProfile>,
+}
+
+impl SiteProfiles {
+    /// Built-ins overridden/extended by the given user profiles
+    pub fn with_user_profiles(user: BTreeMap<String, SiteProfile>) -> Self {
+        let mut profiles: BTreeMap<String, SiteProfile> = builtin_profiles()
+            .into_iter()
+            .map(|(name, profile)| (name.to_string(), profile))
+            .collect();
+        profiles.extend(
+            user.into_iter()
+                .map(|(name, profile)| (name.to_lowercase(), profile)),
+        );
+        Self { profiles }
+    }
+
+    /// Load built-ins plus `<config_dir>/msc/wget_profiles.json` if present
+    pub fn load() -> Result<Self> {
+        let path = Self::get_config_path()?;
+        if !path.exists() {
+            return Ok(Self::with_user_profiles(BTreeMap::new()));
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read wget profiles from {:?}", path))?;
+        Self::from_json(&content).with_context(|| format!("Invalid wget profiles in {:?}", path))
+    }
+
+    /// Parse user profiles from JSON and merge them with the built-ins
+    pub fn from_json(content: &str) -> Result<Self> {
+        let file: ProfilesFile = serde_json::from_str(content)?;
+        Ok(Self::with_user_profiles(file.profiles))
+    }
+
+    /// Look up a profile by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Result<&SiteProfile> {
+        let name = name.to_lowercase();
+        self.profiles.get(&name).ok_or_else(|| {
+            anyhow!(
+                "Perfil '{}' no encontrado. Disponibles: {}",
+                name,
+                self.names().join(", ")
+            )
+        })
+    }
+
+    /// Names of all available profiles, sorted
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
+    }
+
+    fn get_config_path() -> Result<PathBuf> {
+        let config_dir =
+            dirs::config_dir().with_context(|| "Could not determine config directory")?;
+
+        Ok(config_dir.join("msc").join("wget_profiles.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_profile_enables_everything() {
+        let profiles = SiteProfiles::with_user_profiles(BTreeMap::new());
+        let profile = profiles.get(DEFAULT_PROFILE).unwrap();
+
+        assert!(profile.lazy_images && profile.reader_scripts);
+        assert!(profile.cdn_downloads && profile.markup_fixes);
+        for rule in RemovalRule::ALL {
+            assert!(profile.removes(rule), "{:?} should be enabled", rule);
+        }
+    }
+
+    #[test]
+    fn test_builtin_profiles_select_phases() {
+        let profiles = SiteProfiles::with_user_profiles(BTreeMap::new());
+
+        let manhwa = profiles.get("Manhwa").unwrap();
+        assert!(manhwa.lazy_images && manhwa.reader_scripts && manhwa.cdn_downloads);
+        assert!(manhwa.removes(RemovalRule::Scripts));
+        assert!(!manhwa.removes(RemovalRule::HolidayPromos));
+
+        let docs = profiles.get("docs").unwrap();
+        assert!(!docs.lazy_images && !docs.reader_scripts && !docs.cdn_downloads);
+        assert!(docs.removes(RemovalRule::Tracking));
+        assert!(!docs.removes(RemovalRule::Scripts));
+
+        assert!(profiles.get("missing").is_err());
+    }
+
+    #[test]
+    fn test_user_profiles_override_builtins() {
+        let json = r#"{
+            "profiles": {
+                "docs": { "cdn_downloads": true, "removal_rules": [] },
+                "blog": { "reader_scripts": false, "removal_rules": ["tracking", "cookie-banners"] }
+            }
+        }"#;
+        let profiles = SiteProfiles::from_json(json).unwrap();
+
+        let docs = profiles.get("docs").unwrap();
+        assert!(docs.cdn_downloads);
+        assert!(docs.removal_rules.is_empty());
+        // Omitted fields fall back to the default profile
+        assert!(docs.lazy_images);
+
+        let blog = profiles.get("blog").unwrap();
+        assert!(!blog.reader_scripts);
+        assert_eq!(
+            blog.removal_rules,
+            [RemovalRule::Tracking, RemovalRule::CookieBanners]
+        );
+
+        assert!(profiles.names().contains(&"manhwa"));
+        assert!(
+            SiteProfiles::from_json(r#"{"profiles":{"x":{"removal_rules":["bogus"]}}}"#).is_err()
+        );
+    }
+}
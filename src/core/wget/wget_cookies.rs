@@ -9,6 +9,7 @@ use url::Url;
 
 use super::chrome_decrypt::ChromeDecryptor;
 use super::chrome_manager::ChromeManager;
+use super::firefox_cookies::{find_profile_cookie_db, firefox_data_dir, read_moz_cookies};
 // use super::cdp_cookies;
 // use super::chrome_launcher::ChromeInstance;
 
@@ -123,36 +124,21 @@ pub fn find_browser_cookie_db(browser: &str) -> Result<PathBuf> {
                 PathBuf::from(app_data).join("microsoft-edge/Default/Cookies")
             }
         }
-        "firefox" => {
-            // Firefox uses a different structure with profiles
-            if cfg!(windows) {
-                let roaming = env::var("APPDATA").context("APPDATA no está configurado")?;
-                let profiles_dir = PathBuf::from(roaming).join("Mozilla\\Firefox\\Profiles");
-                find_firefox_cookie_db(&profiles_dir)?
-            } else if cfg!(target_os = "macos") {
-                let home = env::var("HOME").context("HOME no está configurado")?;
-                let profiles_dir =
-                    PathBuf::from(home).join("Library/Application Support/Firefox/Profiles");
-                find_firefox_cookie_db(&profiles_dir)?
-            } else {
-                let profiles_dir = PathBuf::from(app_data).join("firefox/Profiles");
-                find_firefox_cookie_db(&profiles_dir)?
-            }
-        }
-        "librewolf" => {
-            // LibreWolf is a Firefox fork with different directory structure
-            if cfg!(windows) {
-                let roaming = env::var("APPDATA").context("APPDATA no está configurado")?;
-                let profiles_dir = PathBuf::from(roaming).join("librewolf\\Profiles");
-                find_firefox_cookie_db(&profiles_dir)?
-            } else if cfg!(target_os = "macos") {
-                let home = env::var("HOME").context("HOME no está configurado")?;
-                let profiles_dir =
-                    PathBuf::from(home).join("Library/Application Support/librewolf/Profiles");
-                find_firefox_cookie_db(&profiles_dir)?
-            } else {
-                let profiles_dir = PathBuf::from(app_data).join("librewolf/Profiles");
-                find_firefox_cookie_db(&profiles_dir)?
+        "firefox" | "librewolf" => {
+            // Firefox-based browsers keep one cookies.sqlite per profile;
+            // profiles.ini says which profile is the default
+            let data_dir = firefox_data_dir(browser)?;
+            match find_profile_cookie_db(&data_dir) {
+                Ok(path) => path,
+                Err(e) => {
+                    log::debug!("profiles.ini lookup failed: {}", e);
+                    let profiles_dir = data_dir.join("Profiles");
+                    if profiles_dir.is_dir() {
+                        find_firefox_cookie_db(&profiles_dir)?
+                    } else {
+                        find_firefox_cookie_db(&data_dir)?
+                    }
+                }
             }
         }
         "brave" => {
@@ -379,78 +365,38 @@ pub fn extract_cookies_from_db(db_path: &Path, domain: &str) -> Result<Vec<Cooki
                 same_site: same_site_from_int(same_site_int).to_string(),
             });
         }
-    } else {
-        // Try Firefox schema - match .domain.com, domain.com, or www.domain.com
-        let firefox_query = "SELECT name, value, host, path, expiry, isSecure, COALESCE(isHttpOnly, 0), COALESCE(sameSite, -1) FROM moz_cookies WHERE host = ?1 OR host = ?2 OR host = ?3";
-
-        if let Ok(mut stmt) = conn.prepare(firefox_query) {
-            let cookie_iter = stmt.query_map(
-                [
-                    &dot_domain_pattern,
-                    &exact_domain_pattern,
-                    &www_domain_pattern,
-                ],
-                |row| {
-                    let http_only = row.get::<_, i64>(6)? != 0;
-                    let same_site_int = row.get::<_, i64>(7)?;
-
-                    let same_site = match same_site_int {
-                        0 => "None",
-                        1 => "Lax",
-                        2 => "Strict",
-                        -1 => "Unspecified", // COALESCE default
-                        _ => "Unspecified",
-                    }
-                    .to_string();
-
-                    Ok(Cookie {
-                        name: row.get(0)?,
-                        value: row.get(1)?,
-                        domain: row.get(2)?,
-                        path: row.get(3)?,
-                        expires: row.get(4)?,
-                        secure: row.get::<_, i64>(5)? != 0,
-                        http_only,
-                        same_site,
-                    })
-                },
-            )?;
-
-            for cookie in cookie_iter.flatten() {
-                cookies.push(cookie);
-            }
-
-            // If no cookies found, try to debug by showing what hosts exist
-            if cookies.is_empty() {
-                eprintln!();
-                eprintln!(
-                    "{}",
-                    "🔍 Debug: No se encontraron cookies con el patrón de búsqueda.".yellow()
-                );
-                eprintln!(
-                    "{}",
-                    format!(
-                        "   Buscando: '{}' o '{}'",
-                        exact_domain_pattern, dot_domain_pattern
-                    )
-                    .dimmed()
-                );
-                eprintln!();
-                eprintln!(
-                    "{}",
-                    "   Mostrando todos los hosts en la base de datos:".dimmed()
-                );
+    } else if let Ok(found) = read_moz_cookies(&conn, domain) {
+        // Firefox schema - match .domain.com, domain.com, or www.domain.com
+        cookies = found;
+
+        // If no cookies found, try to debug by showing what hosts exist
+        if cookies.is_empty() {
+            eprintln!();
+            eprintln!(
+                "{}",
+                "🔍 Debug: No se encontraron cookies con el patrón de búsqueda.".yellow()
+            );
+            eprintln!(
+                "{}",
+                format!(
+                    "   Buscando: '{}' o '{}'",
+                    exact_domain_pattern, dot_domain_pattern
+                )
+                .dimmed()
+            );
+            eprintln!();
+            eprintln!(
+                "{}",
+                "   Mostrando todos los hosts en la base de datos:".dimmed()
+            );
 
-                if let Ok(mut stmt) = conn.prepare("SELECT DISTINCT host FROM moz_cookies LIMIT 20")
-                {
-                    let hosts: Vec<String> =
-                        stmt.query_map([], |row| row.get(0))?.flatten().collect();
-                    for host in hosts {
-                        eprintln!("     • {}", host.cyan());
-                    }
+            if let Ok(mut stmt) = conn.prepare("SELECT DISTINCT host FROM moz_cookies LIMIT 20") {
+                let hosts: Vec<String> = stmt.query_map([], |row| row.get(0))?.flatten().collect();
+                for host in hosts {
+                    eprintln!("     • {}", host.cyan());
                 }
-                eprintln!();
             }
+            eprintln!();
         }
     }
 
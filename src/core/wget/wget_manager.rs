@@ -6,7 +6,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::core::Config;
+use crate::core::{http, Config};
 
 #[derive(Default)]
 pub struct WgetManager {
@@ -56,7 +56,7 @@ impl WgetManager {
         println!("{}", "Descargando wget...".cyan());
         println!("{} {}", "URL:".dimmed(), download_url.dimmed());
 
-        let response = reqwest::blocking::get(download_url).context("Error al descargar wget")?;
+        let response = http::get(download_url).context("Error al descargar wget")?;
 
         if !response.status().is_success() {
             return Err(anyhow!(
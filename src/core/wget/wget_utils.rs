@@ -1,138 +1,153 @@
 use anyhow::{anyhow, Result};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use url::Url;
 
-/// Calculate the local file path where wget would save a given URL
-/// This mirrors wget's behavior with --adjust-extension and directory structure
-pub fn calculate_local_path_for_url(url: &Url, base_dir: &Path) -> Option<PathBuf> {
-    let domain = url.domain()?;
-    let path = url.path();
-    let query = url.query(); // Get query parameters
+use crate::core::http::{HttpConfig, RateLimiter, ThrottledReader, BROWSER_USER_AGENT};
+use crate::ui::progress::read_with_progress;
 
-    let mut local_path = base_dir.join(domain);
+/// Resolution base for relative URLs in a document
+///
+/// A `<base href>` overrides the document URL (it may itself be relative to
+/// it); without one, `base_url` is returned unchanged.
+pub fn document_base_url(document: &scraper::Html, base_url: &Url) -> Url {
+    let Ok(selector) = scraper::Selector::parse("base[href]") else {
+        return base_url.clone();
+    };
 
-    if path == "/" || path.is_empty() {
-        local_path.push("index.html");
-    } else {
-        // Remove leading slash and decode percent encoding
-        let rel_path = path.trim_start_matches('/');
+    document
+        .select(&selector)
+        .next()
+        .and_then(|element| element.value().attr("href"))
+        .map(str::trim)
+        .filter(|href| !href.is_empty())
+        .and_then(|href| base_url.join(href).ok())
+        .unwrap_or_else(|| base_url.clone())
+}
 
-        if path.ends_with('/') {
-            // Directory path - wget adds index.html
-            local_path.push(rel_path);
-            local_path.push("index.html");
-        } else {
-            // File path - need to handle query params
-            let path_parts: Vec<&str> = rel_path.split('/').collect();
-            let mut file_name = path_parts.last().unwrap_or(&"index.html").to_string();
-
-            // If there are query parameters, wget converts them to @-notation
-            // Example: "page.php?id=123" becomes "page.php@id=123.html"
-            // Note: wget does NOT remove the original extension, it just appends @query.html
-            if let Some(query_str) = query {
-                // Append query with @ separator (keep the original extension)
-                file_name = format!("{}@{}", file_name, query_str);
-            }
+/// Target of a `<meta http-equiv="refresh" content="N;url=...">` redirect, unresolved
+pub fn meta_refresh_target(document: &scraper::Html) -> Option<String> {
+    let selector = scraper::Selector::parse("meta[http-equiv][content]").ok()?;
+
+    document
+        .select(&selector)
+        .filter(|element| {
+            element
+                .value()
+                .attr("http-equiv")
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("refresh"))
+        })
+        .find_map(|element| parse_refresh_content(element.value().attr("content")?))
+}
 
-            // Push the directory structure (excluding the filename)
-            if path_parts.len() > 1 {
-                for part in &path_parts[..path_parts.len() - 1] {
-                    local_path.push(part);
-                }
-            }
+/// Extract the URL from a refresh `content` value (`"0;url=/next"`, `"5; URL='next'"`)
+fn parse_refresh_content(content: &str) -> Option<String> {
+    let (_, target) = content.split_once([';', ','])?;
+    let target = target.trim_start();
 
-            // Push the filename (with query params if any)
-            local_path.push(&file_name);
+    // "url=" is optional in practice; browsers accept a bare URL too
+    let target = match target.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => {
+            target[3..].trim_start().strip_prefix('=')?.trim_start()
+        }
+        _ => target,
+    };
+    let target = target.trim_matches(|c| c == '"' || c == '\'').trim();
 
-            // wget's behavior with --adjust-extension:
-            // - If the file has query params, wget ALWAYS adds .html at the end
-            //   Example: "page.php?id=123" -> "page.php@id=123.html"
-            //   Example: "page.html?id=123" -> "page.html@id=123.html"
-            // - If no query params and no extension -> adds .html
-            // - If no query params and non-html extension (like .php) -> adds .html
+    (!target.is_empty()).then(|| target.to_string())
+}
 
-            if query.is_some() {
-                // Always add .html when there are query params
-                let new_name = format!("{}.html", local_path.file_name()?.to_string_lossy());
-                local_path.set_file_name(new_name);
-            } else {
-                // No query params - apply normal wget logic
-                let has_extension = file_name.contains('.');
-                if !has_extension {
-                    // No extension - wget will add .html
-                    local_path.set_extension("html");
-                } else if !file_name.ends_with(".html") && !file_name.ends_with(".htm") {
-                    // Check if it looks like a HTML page (no other file extension)
-                    let ext = local_path
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("");
-                    if ext != "html"
-                        && ext != "htm"
-                        && ![
-                            "css", "js", "json", "xml", "txt", "pdf", "png", "jpg", "jpeg", "gif",
-                            "svg", "ico", "woff", "woff2", "ttf", "eot",
-                        ]
-                        .contains(&ext)
-                    {
-                        // Likely an HTML page with unusual extension - wget adds .html
-                        let new_name =
-                            format!("{}.html", local_path.file_name()?.to_string_lossy());
-                        local_path.set_file_name(new_name);
-                    }
-                }
-            }
+// URL -> local path mapping
+//
+// These rules mirror where wget saves a page when run with
+// `--adjust-extension` (structured layout) or `--no-directories` (flat):
+//
+// | URL path     | Structured (under `<domain>/`) | Flat                  |
+// |--------------|--------------------------------|-----------------------|
+// | `/` or empty | `index.html`                   | `index.html`          |
+// | `/a/`        | `a/index.html`                 | `index.html`          |
+// | `/a`         | `a.html`                       | `a.html`              |
+// | `/a.html`    | `a.html` (same for `.htm`)     | `a.html`              |
+// | `/a.css`     | `a.css` (known resource)       | `a.css`               |
+// | `/a.php`     | `a.php.html` (unknown ext)     | `a.php.html`          |
+// | `/a.php?x=1` | `a.php@x=1.html`               | `a.php@x=1.html`      |
+// | `/a/?x=1`    | `a/index.html@x=1.html`        | `index.html@x=1.html` |
+//
+// A query string is appended with `@` and always gets `.html`. When that
+// file does not exist on disk, the query-less name is used if it exists.
+// The flat name is always the last component of the structured path.
+
+/// Extensions that wget keeps as-is instead of appending `.html`
+const RESOURCE_EXTENSIONS: &[&str] = &[
+    "html", "htm", "css", "js", "json", "xml", "txt", "pdf", "png", "jpg", "jpeg", "gif", "svg",
+    "ico", "woff", "woff2", "ttf", "eot",
+];
+
+/// File name wget gives to the last path segment (`name`), with an optional query
+fn wget_file_name(name: &str, query: Option<&str>) -> String {
+    if let Some(query) = query {
+        return format!("{}@{}.html", name, query);
+    }
 
-            // Try to find the file - if it doesn't exist with query params, try without
-            if !local_path.exists() && query.is_some() {
-                // Reconstruct path without query params
-                let mut fallback_path = base_dir.join(domain);
+    let extension = Path::new(name).extension().and_then(|e| e.to_str());
+    match extension {
+        Some(ext) if RESOURCE_EXTENSIONS.contains(&ext) => name.to_string(),
+        _ => format!("{}.html", name),
+    }
+}
 
-                // Push directory structure
-                if path_parts.len() > 1 {
-                    for part in &path_parts[..path_parts.len() - 1] {
-                        fallback_path.push(part);
-                    }
-                }
+/// Path of a URL relative to its domain directory (structured layout)
+///
+/// Pass `with_query = false` to get the query-less fallback name.
+pub fn wget_relative_path(url: &Url, with_query: bool) -> PathBuf {
+    let path = url.path().trim_start_matches('/');
+    let (dirs, name) = path.rsplit_once('/').unwrap_or(("", path));
+    // Directory URLs ("/", "/a/") are saved as their index page
+    let name = if name.is_empty() { "index.html" } else { name };
+
+    let mut relative = PathBuf::new();
+    relative.extend(dirs.split('/').filter(|part| !part.is_empty()));
+    relative.push(wget_file_name(name, url.query().filter(|_| with_query)));
+    relative
+}
 
-                // Push original filename without query
-                let original_file_name = path_parts.last().unwrap_or(&"index.html");
-                fallback_path.push(original_file_name);
-
-                // Handle extension
-                let orig_has_extension = original_file_name.contains('.');
-                if !orig_has_extension {
-                    fallback_path.set_extension("html");
-                } else if !original_file_name.ends_with(".html")
-                    && !original_file_name.ends_with(".htm")
-                {
-                    let ext = fallback_path
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("");
-                    if ext != "html"
-                        && ext != "htm"
-                        && ![
-                            "css", "js", "json", "xml", "txt", "pdf", "png", "jpg", "jpeg", "gif",
-                            "svg", "ico", "woff", "woff2", "ttf", "eot",
-                        ]
-                        .contains(&ext)
-                    {
-                        let new_name =
-                            format!("{}.html", fallback_path.file_name()?.to_string_lossy());
-                        fallback_path.set_file_name(new_name);
-                    }
-                }
+/// The query-aware path under `root`, or the query-less one when only that exists
+fn existing_or_query_fallback(url: &Url, root: &Path, flat: bool) -> PathBuf {
+    let build = |with_query: bool| {
+        let relative = wget_relative_path(url, with_query);
+        if flat {
+            root.join(relative.file_name().unwrap_or_default())
+        } else {
+            root.join(relative)
+        }
+    };
 
-                if fallback_path.exists() {
-                    return Some(fallback_path);
-                }
-            }
+    let local_path = build(true);
+    if !local_path.exists() && url.query().is_some() {
+        let fallback_path = build(false);
+        if fallback_path.exists() {
+            return fallback_path;
         }
     }
+    local_path
+}
 
-    Some(local_path)
+/// Calculate the local file path where wget would save a given URL
+/// This mirrors wget's behavior with --adjust-extension and directory structure
+pub fn calculate_local_path_for_url(url: &Url, base_dir: &Path) -> Option<PathBuf> {
+    let domain = url.domain()?;
+    Some(existing_or_query_fallback(
+        url,
+        &base_dir.join(domain),
+        false,
+    ))
+}
+
+/// Calculate the local file path for a URL assuming a FLAT directory structure
+/// (used when wget is run with --no-directories)
+pub fn calculate_flat_local_path(url: &Url, base_dir: &Path) -> Option<PathBuf> {
+    Some(existing_or_query_fallback(url, base_dir, true))
 }
 
 /// Check if a path is already a local relative path (not a remote URL)
@@ -159,84 +174,115 @@ pub fn calculate_possible_local_paths(url: &Url, base_dir: &Path) -> Vec<PathBuf
         return paths;
     };
 
-    let url_path = url.path();
-
-    // Build the relative path from URL
-    let mut rel_path_parts = Vec::new();
-
-    if url_path == "/" || url_path.is_empty() {
-        rel_path_parts.push("index.html".to_string());
-    } else {
-        let rel_path = url_path.trim_start_matches('/');
-
-        if url_path.ends_with('/') {
-            rel_path_parts.push(rel_path.to_string());
-            rel_path_parts.push("index.html".to_string());
-        } else {
-            rel_path_parts.push(rel_path.to_string());
-
-            // Add .html extension if needed
-            let has_extension = rel_path.contains('.')
-                && rel_path
-                    .split('/')
-                    .next_back()
-                    .map(|f| f.contains('.'))
-                    .unwrap_or(false);
-
-            if !has_extension {
-                // Modify last part to add .html
-                if let Some(last) = rel_path_parts.last_mut() {
-                    *last = format!("{}.html", last);
-                }
-            } else if !url_path.ends_with(".html") && !url_path.ends_with(".htm") {
-                let ext = rel_path.split('.').next_back().unwrap_or("");
-                if ![
-                    "html", "htm", "css", "js", "json", "xml", "txt", "pdf", "png", "jpg", "jpeg",
-                    "gif", "svg", "ico", "woff", "woff2", "ttf", "eot",
-                ]
-                .contains(&ext)
-                {
-                    if let Some(last) = rel_path_parts.last_mut() {
-                        *last = format!("{}.html", last);
-                    }
-                }
-            }
-        }
+    // Query-aware name first, then the query-less fallback
+    let mut relative_paths = vec![wget_relative_path(url, true)];
+    if url.query().is_some() {
+        relative_paths.push(wget_relative_path(url, false));
     }
 
-    // Build final path string
-    let final_rel_path = rel_path_parts.join("");
-
     // Check if base_dir ends with the domain name
     // This works for both relative (./manhwa-espanol.com) and absolute paths
     // (C:\Users\...\manhwa-espanol.com)
     let base_dir_name = base_dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-    if base_dir_name == domain {
-        // base_dir already includes domain, so try both options:
-        // Option 1: base_dir/path (without domain duplication - CORRECT)
-        let path_without_domain = base_dir.join(&final_rel_path);
-        paths.push(path_without_domain);
-
-        // Option 2: base_dir/domain/path (with domain duplication - legacy/alternative)
-        let path_with_domain = base_dir.join(domain).join(&final_rel_path);
-        paths.push(path_with_domain);
-    } else {
-        // base_dir doesn't include domain, so only try with domain
-        let path_with_domain = base_dir.join(domain).join(&final_rel_path);
-        paths.push(path_with_domain);
+    for relative in &relative_paths {
+        if base_dir_name == domain {
+            // base_dir already includes domain, so try both options:
+            // Option 1: base_dir/path (without domain duplication - CORRECT)
+            paths.push(base_dir.join(relative));
+        }
+        // base_dir/domain/path (with domain duplication when base_dir is the domain)
+        paths.push(base_dir.join(domain).join(relative));
     }
 
     paths
 }
 
+/// Longest file name produced for a URL, in bytes
+///
+/// Below the usual 255-byte limit to leave room for the suffixes added
+/// later (a corrected extension, wget's `@query.html`).
+const MAX_FILE_NAME_BYTES: usize = 200;
+
+/// Device names Windows reserves regardless of extension
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Percent-decode a URL path segment (`%E6%BC%AB` -> `漫`)
+fn decode_segment(segment: &str) -> String {
+    percent_encoding::percent_decode_str(segment)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Make a decoded file name valid on every platform
+///
+/// Keeps letters and digits of any script plus `-`, `_` and `.`; everything
+/// else (Windows reserved characters, whitespace, controls) becomes `_`.
+/// Leading/trailing dots are dropped, reserved device names get a `_`
+/// prefix and overlong names are shortened (see [`truncate_file_name`]).
+fn sanitize_file_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let mut clean = replaced.trim_matches('.').to_string();
+    let stem = clean.split('.').next().unwrap_or_default();
+    if WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        clean.insert(0, '_');
+    }
+
+    truncate_file_name(clean)
+}
+
+/// Hash used in file names; xxh3 output is fixed, unlike `DefaultHasher`'s,
+/// so names stay the same across Rust versions and existing downloads match
+fn stable_hash(text: &str) -> u64 {
+    xxhash_rust::xxh3::xxh3_64(text.as_bytes())
+}
+
+/// Shorten a name to [`MAX_FILE_NAME_BYTES`], keeping its extension
+///
+/// A hash of the full name is appended so different long names that share
+/// a prefix stay distinct; the result is stable, so every reference to the
+/// same URL maps to the same file.
+fn truncate_file_name(name: String) -> String {
+    if name.len() <= MAX_FILE_NAME_BYTES {
+        return name;
+    }
+
+    let (stem, extension) = match name.rfind('.') {
+        Some(pos) if name.len() - pos <= 16 => name.split_at(pos),
+        _ => (name.as_str(), ""),
+    };
+
+    let suffix = format!("_{:x}{}", stable_hash(&name), extension);
+
+    let mut end = (MAX_FILE_NAME_BYTES - suffix.len()).min(stem.len());
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &stem[..end], suffix)
+}
+
 /// Extract a safe filename from a URL, handling complex CDN URLs with path segments after extensions
+///
+/// Path segments are percent-decoded and sanitized with `sanitize_file_name`.
 /// Example: "https://cdn.com/video.png/plain/rs:fit:323:182?query=123" -> "video_hash.png"
 /// Example: "https://upload.wikimedia.org/wikipedia/en/thumb/1/1f/Reddit_logo_2023.svg/330px-Reddit_logo_2023.svg.png" -> "330px-Reddit_logo_2023.svg.png"
 pub fn extract_filename_from_url(url: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-
     // List of known file extensions
     let known_extensions = [
         ".jpg", ".jpeg", ".png", ".gif", ".svg", ".webp", ".bmp", ".ico", ".mp4", ".webm", ".ogv",
@@ -255,16 +301,14 @@ pub fn extract_filename_from_url(url: &str) -> String {
     // This handles cases like: /path/to/file.png or /path/to/330px-image.svg.png
     if let Some(last_segment) = url_without_fragment.split('/').next_back() {
         // Check if this segment has a known extension
-        let segment_lower = last_segment.to_lowercase();
+        let segment = decode_segment(last_segment);
+        let segment_lower = segment.to_lowercase();
 
         for ext in &known_extensions {
             if segment_lower.ends_with(ext) {
                 // This looks like a valid filename with extension
                 // Clean it to make it filesystem-safe but preserve dots in filename
-                let clean_name: String = last_segment
-                    .chars()
-                    .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == '.')
-                    .collect();
+                let clean_name = sanitize_file_name(&segment);
 
                 if !clean_name.is_empty() && clean_name.contains('.') {
                     return clean_name;
@@ -297,20 +341,15 @@ pub fn extract_filename_from_url(url: &str) -> String {
     // If we found an extension, create a safe filename
     if let (Some(name), Some(ext)) = (name_before_extension, found_extension) {
         // Clean the name to make it filesystem-safe
-        let clean_name: String = name
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
-            .collect();
+        let clean_name: String = decode_segment(name).replace('.', "_");
 
         if !clean_name.is_empty() {
-            return format!("{}{}", clean_name, ext);
+            return sanitize_file_name(&format!("{}{}", clean_name, ext));
         }
     }
 
     // Final fallback: create a hash-based filename
-    let mut hasher = DefaultHasher::new();
-    url.hash(&mut hasher);
-    let hash = hasher.finish();
+    let hash = stable_hash(url);
 
     // Try to detect extension from content-type later, for now use a generic one
     // If there was an extension found, use it, otherwise use .bin
@@ -321,25 +360,371 @@ pub fn extract_filename_from_url(url: &str) -> String {
     }
 }
 
-pub fn download_resource(url: &str, path: &PathBuf) -> Result<()> {
+/// File name for a downloaded asset
+///
+/// Same as [`extract_filename_from_url`], except that with
+/// `keep_query_strings` a hash of the query string is added before the
+/// extension (`img.jpg?v=2` -> `img_1a2b3c4d.jpg`), so versions of a file
+/// that differ only by query don't overwrite each other.
+pub fn asset_file_name(url: &str, keep_query_strings: bool) -> String {
+    let name = extract_filename_from_url(url);
+    if !keep_query_strings {
+        return name;
+    }
+
+    let without_fragment = url.split('#').next().unwrap_or(url);
+    let Some((_, query)) = without_fragment
+        .split_once('?')
+        .filter(|(_, query)| !query.is_empty())
+    else {
+        return name;
+    };
+
+    let tag = stable_hash(query) as u32;
+
+    match name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}_{:08x}.{}", stem, tag, extension),
+        None => format!("{}_{:08x}", name, tag),
+    }
+}
+
+/// Download a resource to `path`, returning where it was actually stored
+///
+/// When the content (magic bytes, or `Content-Type` for extension-less names)
+/// shows a different file type than the extension of `path`, the file is
+/// saved with the corrected extension instead; callers must reference the
+/// returned path.
+pub fn download_resource(url: &str, path: &Path) -> Result<PathBuf> {
+    download_resource_via(url, path, None, None, None)
+}
+
+/// Same as [`download_resource`], showing a progress bar labelled `progress` if given
+pub fn download_resource_with_progress(
+    url: &str,
+    path: &Path,
+    progress: Option<&str>,
+) -> Result<PathBuf> {
+    download_resource_via(url, path, None, progress, None)
+}
+
+/// Same as [`download_resource_with_progress`], going through `proxy` if given
+/// (otherwise through the `HTTP(S)_PROXY` environment variables)
+///
+/// With a `limiter`, the body is read no faster than its rate, which is
+/// shared with every other download using the same limiter.
+/// The body size is checked against `Content-Length` when the server sends it.
+pub fn download_resource_via(
+    url: &str,
+    path: &Path,
+    proxy: Option<&str>,
+    progress: Option<&str>,
+    limiter: Option<&RateLimiter>,
+) -> Result<PathBuf> {
     if path.exists() {
-        return Ok(());
+        return Ok(path.to_path_buf());
     }
 
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .timeout(std::time::Duration::from_secs(30))
+    // The client negotiates gzip/deflate/br and decodes `Content-Encoding`
+    // transparently, except for files that are meant to be stored compressed
+    let keep_encoded = is_compressed_format(url);
+    let config = HttpConfig::from_env()
+        .with_user_agent(BROWSER_USER_AGENT)
+        .with_proxy(proxy);
+    let client = config
+        .builder()?
+        .gzip(!keep_encoded)
+        .deflate(!keep_encoded)
+        .brotli(!keep_encoded)
         .build()?;
 
-    let response = client.get(url).send()?;
+    let response = config.send(client.get(url))?;
 
     if !response.status().is_success() {
         return Err(anyhow!("Status: {}", response.status()));
     }
 
-    let bytes = response.bytes()?;
-    fs::write(path, bytes)?;
-    Ok(())
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    // Unknown for decoded (gzip/deflate/br) bodies
+    let expected_size = response.content_length();
+
+    let mut body: Box<dyn Read + '_> = match limiter {
+        Some(limiter) => Box::new(ThrottledReader::new(response, limiter)),
+        None => Box::new(response),
+    };
+    let bytes = match progress {
+        Some(prefix) => read_with_progress(body, expected_size.unwrap_or(0), prefix)?,
+        None => {
+            let mut bytes = Vec::new();
+            body.read_to_end(&mut bytes)?;
+            bytes
+        }
+    };
+
+    if let Some(expected) = expected_size {
+        if bytes.len() as u64 != expected {
+            return Err(anyhow!(
+                "Descarga incompleta: {} de {} bytes",
+                bytes.len(),
+                expected
+            ));
+        }
+    }
+
+    let final_path = corrected_asset_path(path, content_type.as_deref(), &bytes);
+    fs::write(&final_path, bytes)?;
+    Ok(final_path)
+}
+
+/// Extensions treated as equivalent when checking a detected type
+const EQUIVALENT_EXTENSIONS: &[(&str, &str)] = &[("jpg", "jpeg"), ("htm", "html")];
+
+/// Path with its extension fixed to match the downloaded content
+///
+/// Magic bytes are trusted for binary formats (images, fonts, PDF). The
+/// `Content-Type` header is only used when the name has no usable extension
+/// (none, or the `.bin` placeholder), since CDNs often mislabel text assets.
+pub fn corrected_asset_path(path: &Path, content_type: Option<&str>, bytes: &[u8]) -> PathBuf {
+    let current = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    let placeholder = current.as_deref().is_none_or(|ext| ext == "bin");
+
+    let detected = sniff_extension(bytes).or_else(|| {
+        placeholder
+            .then(|| content_type.and_then(extension_for_mime))
+            .flatten()
+    });
+
+    match (detected, current.as_deref()) {
+        (Some(detected), Some(current)) if same_extension(detected, current) => path.to_path_buf(),
+        (Some(detected), _) => path.with_extension(detected),
+        (None, _) => path.to_path_buf(),
+    }
+}
+
+fn same_extension(a: &str, b: &str) -> bool {
+    a == b
+        || EQUIVALENT_EXTENSIONS
+            .iter()
+            .any(|&(x, y)| (a == x && b == y) || (a == y && b == x))
+}
+
+/// File extension for well-known binary signatures
+fn sniff_extension(bytes: &[u8]) -> Option<&'static str> {
+    let ext = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "png"
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "jpg"
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        "gif"
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "webp"
+    } else if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" && &bytes[8..12] == b"avif" {
+        "avif"
+    } else if bytes.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
+        "ico"
+    } else if bytes.starts_with(b"wOF2") {
+        "woff2"
+    } else if bytes.starts_with(b"wOFF") {
+        "woff"
+    } else if bytes.starts_with(b"%PDF-") {
+        "pdf"
+    } else {
+        return None;
+    };
+    Some(ext)
+}
+
+/// File extension for a `Content-Type` value (parameters are ignored)
+fn extension_for_mime(content_type: &str) -> Option<&'static str> {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+
+    let ext = match mime.as_str() {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "image/bmp" => "bmp",
+        "text/css" => "css",
+        "text/javascript" | "application/javascript" | "application/x-javascript" => "js",
+        "application/json" => "json",
+        "text/html" => "html",
+        "font/woff" => "woff",
+        "font/woff2" => "woff2",
+        "font/ttf" => "ttf",
+        "font/otf" => "otf",
+        "video/mp4" => "mp4",
+        "video/webm" => "webm",
+        "audio/mpeg" => "mp3",
+        "application/pdf" => "pdf",
+        _ => return None,
+    };
+    Some(ext)
+}
+
+/// Extensions of files that are compressed on disk and must not be decoded
+const COMPRESSED_EXTENSIONS: &[&str] = &[
+    "gz", "tgz", "svgz", "br", "zst", "bz2", "xz", "zip", "7z", "rar",
+];
+
+/// True if the URL points to an already-compressed file (e.g. `.svgz`, `.tar.gz`)
+///
+/// Servers often label these with `Content-Encoding: gzip`; decoding them
+/// would store content that no longer matches the file extension.
+pub fn is_compressed_format(url: &str) -> bool {
+    let path = Url::parse(url)
+        .map(|u| u.path().to_string())
+        .unwrap_or_else(|_| url.split(['?', '#']).next().unwrap_or(url).to_string());
+
+    Path::new(&path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            COMPRESSED_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Maximum number of same-host redirects followed by `head_check`
+const HEAD_CHECK_MAX_REDIRECTS: usize = 5;
+
+/// Result of a pre-download HEAD request against a wget target
+#[derive(Debug, Clone)]
+pub struct HeadCheckReport {
+    /// HTTP status of the last response received
+    pub status: u16,
+    /// URL of the last response received (after same-host redirects)
+    pub final_url: String,
+    /// Value of the Content-Type header, if present
+    pub content_type: Option<String>,
+    /// Target of a redirect that leaves the original host (not followed)
+    pub cross_domain_redirect: Option<String>,
+}
+
+impl HeadCheckReport {
+    /// True if the server reported an HTML document
+    pub fn is_html(&self) -> bool {
+        self.content_type.as_deref().is_some_and(|ct| {
+            let ct = ct.to_lowercase();
+            ct.starts_with("text/html") || ct.starts_with("application/xhtml+xml")
+        })
+    }
+
+    /// True if the last response was a 2xx status
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Human-readable warnings for anything that looks wrong with the target
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Some(target) = &self.cross_domain_redirect {
+            warnings.push(format!("La URL redirige a otro dominio: {}", target));
+        } else if !self.is_success() {
+            warnings.push(format!(
+                "El servidor respondió con estado HTTP {}",
+                self.status
+            ));
+        }
+
+        if self.cross_domain_redirect.is_none() && self.is_success() && !self.is_html() {
+            warnings.push(format!(
+                "El contenido no es HTML ({})",
+                self.content_type.as_deref().unwrap_or("sin Content-Type")
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// Issue a HEAD request to confirm a URL is reachable and serves HTML
+///
+/// Redirects are followed manually while they stay on the same host; a redirect
+/// to a different host is recorded in the report instead of being followed, since
+/// it usually indicates a login wall or a typo in the domain.
+/// Servers that reject HEAD (405/501) are retried with GET without reading the body.
+pub fn head_check(url: &str) -> Result<HeadCheckReport> {
+    head_check_via(url, None)
+}
+
+/// Same as [`head_check`], going through `proxy` if given
+pub fn head_check_via(url: &str, proxy: Option<&str>) -> Result<HeadCheckReport> {
+    let config = HttpConfig::from_env()
+        .with_user_agent(BROWSER_USER_AGENT)
+        .with_proxy(proxy)
+        .with_timeout(std::time::Duration::from_secs(15));
+    let client = config
+        .builder()?
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+
+    let original = Url::parse(url)?;
+    let mut current = original.clone();
+
+    for _ in 0..=HEAD_CHECK_MAX_REDIRECTS {
+        let mut response = config.send(client.head(current.as_str()))?;
+        let code = response.status().as_u16();
+        if code == 405 || code == 501 {
+            response = config.send(client.get(current.as_str()))?;
+        }
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+
+        if status.is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| anyhow!("Redirect without Location header"))?;
+            let next = current.join(location)?;
+
+            if next.host_str() != original.host_str() {
+                return Ok(HeadCheckReport {
+                    status: status.as_u16(),
+                    final_url: current.to_string(),
+                    content_type,
+                    cross_domain_redirect: Some(next.to_string()),
+                });
+            }
+
+            current = next;
+            continue;
+        }
+
+        return Ok(HeadCheckReport {
+            status: status.as_u16(),
+            final_url: current.to_string(),
+            content_type,
+            cross_domain_redirect: None,
+        });
+    }
+
+    Err(anyhow!(
+        "Too many redirects (more than {})",
+        HEAD_CHECK_MAX_REDIRECTS
+    ))
 }
 
 pub fn is_placeholder_image(url: &str) -> bool {
@@ -364,3 +749,237 @@ pub fn is_placeholder_image(url: &str) -> bool {
 
     valid_names.iter().any(|name| url_lower.contains(name))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    fn relative(s: &str) -> String {
+        wget_relative_path(&url(s), true)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    #[test]
+    fn test_relative_path_spec() {
+        let cases = [
+            ("https://example.com", "index.html"),
+            ("https://example.com/", "index.html"),
+            ("https://example.com/a/", "a/index.html"),
+            ("https://example.com/a/b/", "a/b/index.html"),
+            ("https://example.com/a", "a.html"),
+            ("https://example.com/a/b", "a/b.html"),
+            ("https://example.com/a.html", "a.html"),
+            ("https://example.com/a.htm", "a.htm"),
+            ("https://example.com/css/a.css", "css/a.css"),
+            ("https://example.com/a.php", "a.php.html"),
+            ("https://example.com/v1.2/page", "v1.2/page.html"),
+            ("https://example.com/a.php?x=1", "a.php@x=1.html"),
+            ("https://example.com/a.html?x=1&y=2", "a.html@x=1&y=2.html"),
+            ("https://example.com/a/?x=1", "a/index.html@x=1.html"),
+            ("https://example.com/a.html#section", "a.html"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(relative(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_meta_refresh_target() {
+        let target = |html: &str| meta_refresh_target(&scraper::Html::parse_document(html));
+
+        assert_eq!(
+            target(r#"<meta http-equiv="refresh" content="0;url=/next.html">"#),
+            Some("/next.html".to_string())
+        );
+        assert_eq!(
+            target(r#"<meta http-equiv="Refresh" content="5; URL='https://example.com/a'">"#),
+            Some("https://example.com/a".to_string())
+        );
+        assert_eq!(
+            target(r#"<meta http-equiv="refresh" content="3, other.html">"#),
+            Some("other.html".to_string())
+        );
+        // A refresh without a target only reloads the page
+        assert_eq!(target(r#"<meta http-equiv="refresh" content="30">"#), None);
+        assert_eq!(target(r#"<meta name="refresh" content="0;url=/x">"#), None);
+    }
+
+    #[test]
+    fn test_query_less_relative_path() {
+        let path = wget_relative_path(&url("https://example.com/a.php?x=1"), false);
+        assert_eq!(path, PathBuf::from("a.php.html"));
+    }
+
+    #[test]
+    fn test_structured_path_is_under_domain() {
+        let base = Path::new("out");
+        assert_eq!(
+            calculate_local_path_for_url(&url("https://example.com/a/"), base),
+            Some(base.join("example.com").join("a").join("index.html"))
+        );
+        assert_eq!(
+            calculate_local_path_for_url(&url("http://127.0.0.1/a"), base),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scheme_relative_urls_resolve_to_their_host() {
+        let page = url("https://example.com/posts/1");
+        let cdn = page.join("//cdn.example.net/lib/app.js").unwrap();
+
+        assert_eq!(
+            calculate_local_path_for_url(&cdn, Path::new("out")),
+            Some(
+                Path::new("out")
+                    .join("cdn.example.net")
+                    .join("lib")
+                    .join("app.js")
+            )
+        );
+    }
+
+    #[test]
+    fn test_flat_name_matches_structured_file_name() {
+        let base = Path::new("out");
+        for input in [
+            "https://example.com/",
+            "https://example.com/a/",
+            "https://example.com/a/b",
+            "https://example.com/docs/a.php?x=1",
+            "https://example.com/a/?x=1",
+            "https://example.com/style.css",
+        ] {
+            let u = url(input);
+            let structured = calculate_local_path_for_url(&u, base).unwrap();
+            let flat = calculate_flat_local_path(&u, base).unwrap();
+            assert_eq!(flat.parent(), Some(base), "{}", input);
+            assert_eq!(flat.file_name(), structured.file_name(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_query_fallback_only_when_file_exists() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        let u = url("https://example.com/a.php?x=1");
+
+        // Nothing on disk: the query-aware name is expected
+        assert_eq!(
+            calculate_flat_local_path(&u, base),
+            Some(base.join("a.php@x=1.html"))
+        );
+
+        // Only the query-less file exists: use it
+        fs::write(base.join("a.php.html"), "").unwrap();
+        assert_eq!(
+            calculate_flat_local_path(&u, base),
+            Some(base.join("a.php.html"))
+        );
+
+        // Both exist: the query-aware one wins
+        fs::write(base.join("a.php@x=1.html"), "").unwrap();
+        assert_eq!(
+            calculate_flat_local_path(&u, base),
+            Some(base.join("a.php@x=1.html"))
+        );
+    }
+
+    #[test]
+    fn test_possible_paths_agree_with_structured_path() {
+        let u = url("https://example.com/a.php?x=1");
+
+        let outer = Path::new("sites");
+        let paths = calculate_possible_local_paths(&u, outer);
+        assert_eq!(
+            paths,
+            vec![
+                calculate_local_path_for_url(&u, outer).unwrap(),
+                outer.join("example.com").join("a.php.html"),
+            ]
+        );
+
+        // base_dir already named after the domain: the non-duplicated path comes first
+        let inner = Path::new("sites").join("example.com");
+        let paths = calculate_possible_local_paths(&url("https://example.com/a/"), &inner);
+        assert_eq!(paths[0], inner.join("a").join("index.html"));
+        assert_eq!(
+            paths[1],
+            inner.join("example.com").join("a").join("index.html")
+        );
+    }
+
+    #[test]
+    fn test_filename_decodes_percent_encoded_cjk() {
+        assert_eq!(
+            extract_filename_from_url("https://cdn.example.com/img/%E6%BC%AB%E7%94%BB.jpg"),
+            "漫画.jpg"
+        );
+        // Already-decoded unicode is kept as-is
+        assert_eq!(
+            extract_filename_from_url("https://cdn.example.com/img/漫画-01.png?w=300"),
+            "漫画-01.png"
+        );
+    }
+
+    #[test]
+    fn test_filename_replaces_reserved_characters() {
+        assert_eq!(
+            extract_filename_from_url("https://example.com/a%3Fb%3Ac%20d.png"),
+            "a_b_c_d.png"
+        );
+        assert_eq!(
+            extract_filename_from_url("https://example.com/%2E%2E%2Fsecret.txt"),
+            "_secret.txt"
+        );
+        assert_eq!(sanitize_file_name("con.png"), "_con.png");
+        assert_eq!(sanitize_file_name("console.png"), "console.png");
+    }
+
+    #[test]
+    fn test_overlong_filename_is_truncated_safely() {
+        let long_name = "漫".repeat(150);
+        let url = format!("https://example.com/{}.jpg", long_name);
+
+        let name = extract_filename_from_url(&url);
+        assert!(name.len() <= MAX_FILE_NAME_BYTES, "{} bytes", name.len());
+        assert!(name.starts_with("漫漫"));
+        assert!(name.ends_with(".jpg"));
+        // Stable for the same URL, distinct for a different one
+        assert_eq!(extract_filename_from_url(&url), name);
+        let other = format!("https://example.com/{}x.jpg", long_name);
+        assert_ne!(extract_filename_from_url(&other), name);
+    }
+
+    #[test]
+    fn test_asset_file_name_keeps_query_versions_apart() {
+        let v1 = "https://example.com/img.jpg?v=1";
+        let v2 = "https://example.com/img.jpg?v=2#top";
+
+        // Default: the query is ignored, as before
+        assert_eq!(asset_file_name(v1, false), "img.jpg");
+        assert_eq!(asset_file_name(v2, false), "img.jpg");
+
+        let name1 = asset_file_name(v1, true);
+        let name2 = asset_file_name(v2, true);
+        assert_ne!(name1, name2);
+        assert!(
+            name1.starts_with("img_") && name1.ends_with(".jpg"),
+            "{}",
+            name1
+        );
+        assert_eq!(asset_file_name(v1, true), name1);
+        // No query, nothing to add
+        assert_eq!(
+            asset_file_name("https://example.com/img.jpg?", true),
+            "img.jpg"
+        );
+    }
+}
@@ -1,47 +1,430 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use url::Url;
 
+use super::extension_policy::ExtensionPolicy;
+use super::html_charset::{declare_utf8, decode_html};
+use super::site_profile::{RemovalRule, SiteProfile};
 use super::wget_utils::{
-    calculate_local_path_for_url, calculate_possible_local_paths, download_resource,
-    extract_filename_from_url, is_local_path, is_placeholder_image,
+    asset_file_name, calculate_local_path_for_url, calculate_possible_local_paths,
+    document_base_url, download_resource_via, is_local_path, is_placeholder_image,
 };
+use crate::core::http::RateLimiter;
 use crate::core::validation::{load_default_blacklist, validate_url_not_blacklisted};
+use crate::core::Blacklist;
+use crate::ui::glyphs::adapt;
+use crate::ui::verbosity::{self, Verbosity};
+
+/// Resources downloaded at the same time when `--concurrency` is not given
+pub const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// Options for how post-processing saves downloaded assets
+#[derive(Debug, Clone)]
+pub struct PostProcessOptions {
+    /// Add a hash of the query string to asset file names, so `img.jpg?v=1`
+    /// and `img.jpg?v=2` are saved as separate files
+    pub keep_query_strings: bool,
+    /// Maximum number of resources downloaded in parallel
+    pub concurrency: usize,
+    /// Proxy for resource downloads (`--proxy`); `None` uses `HTTP(S)_PROXY`
+    pub proxy: Option<String>,
+    /// Combined speed cap shared by all resource downloads (`--rate-limit`)
+    pub rate_limit: Option<Arc<RateLimiter>>,
+    /// Extensions that are never downloaded (`reject-types`, `--accept`, `--reject`)
+    pub extension_policy: ExtensionPolicy,
+    /// Resources saved so far, shared by every file processed with these options
+    pub download_stats: Arc<DownloadStats>,
+    /// Resources saved under a corrected extension, so later pages reuse them
+    pub saved_assets: Arc<SavedAssets>,
+    /// Destination of progress lines, filtered by verbosity (`-v`, `--quiet`)
+    pub log: Arc<PostProcessLog>,
+}
+
+impl Default for PostProcessOptions {
+    fn default() -> Self {
+        Self {
+            keep_query_strings: false,
+            concurrency: DEFAULT_DOWNLOAD_CONCURRENCY,
+            proxy: None,
+            rate_limit: None,
+            extension_policy: ExtensionPolicy::default(),
+            download_stats: Arc::default(),
+            saved_assets: Arc::default(),
+            log: Arc::default(),
+        }
+    }
+}
+
+/// Progress lines printed while post-processing
+///
+/// Each page gets a one-line summary by default; per-resource lines are
+/// printed at `-v` (failures, rejections, blocked URLs) or `-vv` (everything).
+/// [`PostProcessLog::capturing`] keeps the lines instead of printing them.
+#[derive(Debug)]
+pub struct PostProcessLog {
+    level: Verbosity,
+    captured: Option<Mutex<Vec<String>>>,
+}
+
+impl Default for PostProcessLog {
+    /// Print at the level chosen with the global flags
+    fn default() -> Self {
+        Self {
+            level: verbosity::level(),
+            captured: None,
+        }
+    }
+}
+
+impl PostProcessLog {
+    /// Keep lines allowed at `level` in memory, see [`PostProcessLog::lines`]
+    pub fn capturing(level: Verbosity) -> Self {
+        Self {
+            level,
+            captured: Some(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Lines kept by a capturing log
+    pub fn lines(&self) -> Vec<String> {
+        self.captured
+            .as_ref()
+            .map(|lines| lines.lock().unwrap_or_else(|e| e.into_inner()).clone())
+            .unwrap_or_default()
+    }
+
+    /// Page summaries and warnings
+    pub fn info(&self, line: String) {
+        self.emit(Verbosity::Normal, line);
+    }
+
+    /// Resources that failed, were rejected or blocked
+    pub fn verbose(&self, line: String) {
+        self.emit(Verbosity::Verbose, line);
+    }
+
+    /// Every resource and cleanup step
+    pub fn debug(&self, line: String) {
+        self.emit(Verbosity::Debug, line);
+    }
+
+    fn emit(&self, required: Verbosity, line: String) {
+        if self.level < required {
+            return;
+        }
+        match &self.captured {
+            Some(lines) => lines.lock().unwrap_or_else(|e| e.into_inner()).push(line),
+            None => println!("{}", line),
+        }
+    }
+}
+
+/// What happened to the resources of one page, printed as its summary line
+#[derive(Debug, Default)]
+struct PageTally {
+    downloaded: AtomicUsize,
+    failed: AtomicUsize,
+    rejected: AtomicUsize,
+    blocked: AtomicUsize,
+}
+
+impl PageTally {
+    fn summary(&self, file_path: &Path) -> String {
+        let mut parts = vec![format!(
+            "{} recursos descargados",
+            self.downloaded.load(Ordering::Relaxed)
+        )];
+        for (count, label) in [
+            (&self.failed, "con error"),
+            (&self.rejected, "rechazados"),
+            (&self.blocked, "bloqueados"),
+        ] {
+            let count = count.load(Ordering::Relaxed);
+            if count > 0 {
+                parts.push(format!("{} {}", count, label));
+            }
+        }
+
+        format!(
+            "   {} {}",
+            adapt(&format!(
+                "📄 {}:",
+                file_path.file_name().unwrap_or_default().to_string_lossy()
+            ))
+            .dimmed(),
+            parts.join(", ").dimmed()
+        )
+    }
+}
+
+/// Running count of the resources post-processing downloaded
+#[derive(Debug, Default)]
+pub struct DownloadStats {
+    files: AtomicUsize,
+    bytes: AtomicU64,
+}
+
+impl DownloadStats {
+    /// Count one saved resource of `bytes` bytes
+    pub fn record(&self, bytes: u64) {
+        self.files.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn files(&self) -> usize {
+        self.files.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+}
+
+/// Resources whose saved file name differs from the requested one
+///
+/// `download_resource` fixes the extension from the content (`.bin` saved as
+/// `.png`), so checking the requested path alone would fetch them again.
+#[derive(Debug, Default)]
+pub struct SavedAssets {
+    by_requested_path: Mutex<HashMap<PathBuf, PathBuf>>,
+}
+
+impl SavedAssets {
+    /// Remember that a resource requested at `requested` was saved to `saved`
+    pub fn record(&self, requested: &Path, saved: &Path) {
+        if requested != saved {
+            self.by_requested_path
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(requested.to_path_buf(), saved.to_path_buf());
+        }
+    }
+
+    /// File already on disk for a resource requested at `requested`, if any
+    pub fn existing(&self, requested: &Path) -> Option<PathBuf> {
+        if requested.exists() {
+            return Some(requested.to_path_buf());
+        }
+        self.by_requested_path
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(requested)
+            .filter(|saved| saved.exists())
+            .cloned()
+    }
+}
+
+/// Resources waiting to be downloaded by one post-processing phase
+///
+/// Phases queue `(url, final_path)` pairs while scanning the page, download
+/// them in parallel with [`AssetDownloads::download_all`] and only then apply
+/// their string replacements, using the index returned by `queue`.
+#[derive(Debug, Default)]
+struct AssetDownloads {
+    pending: Vec<(String, PathBuf)>,
+    by_path: HashMap<PathBuf, usize>,
+}
+
+impl AssetDownloads {
+    /// Queue a download, returning its index; URLs saved to the same path are fetched once
+    fn queue(&mut self, url: &str, final_path: &Path) -> usize {
+        if let Some(&index) = self.by_path.get(final_path) {
+            return index;
+        }
+        self.pending
+            .push((url.to_string(), final_path.to_path_buf()));
+        self.by_path
+            .insert(final_path.to_path_buf(), self.pending.len() - 1);
+        self.pending.len() - 1
+    }
+
+    /// Download everything with up to `options.concurrency` workers
+    ///
+    /// Returns, per queued index, the path the resource was saved to, or
+    /// `None` if the download failed.
+    fn download_all(
+        &self,
+        options: &PostProcessOptions,
+        tally: &PageTally,
+    ) -> Vec<Option<PathBuf>> {
+        let workers = options.concurrency.max(1).min(self.pending.len());
+        let next = AtomicUsize::new(0);
+        let saved = Mutex::new(vec![None; self.pending.len()]);
+
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some((url, final_path)) = self.pending.get(index) else {
+                        break;
+                    };
+
+                    if !options.extension_policy.allows(url) {
+                        tally.rejected.fetch_add(1, Ordering::Relaxed);
+                        options.log.verbose(format!(
+                            "   {} {}",
+                            adapt("🚫 Tipo rechazado:").yellow().dimmed(),
+                            url.dimmed()
+                        ));
+                        continue;
+                    }
+
+                    let result = match download_resource_via(
+                        url,
+                        final_path,
+                        options.proxy.as_deref(),
+                        None,
+                        options.rate_limit.as_deref(),
+                    ) {
+                        Ok(saved_path) => {
+                            tally.downloaded.fetch_add(1, Ordering::Relaxed);
+                            options.saved_assets.record(final_path, &saved_path);
+                            options
+                                .download_stats
+                                .record(fs::metadata(&saved_path).map(|m| m.len()).unwrap_or(0));
+                            options.log.debug(format!(
+                                "   {} {} -> {}",
+                                adapt("✓ Descargado:").green().dimmed(),
+                                url.dimmed(),
+                                saved_path
+                                    .file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy()
+                                    .cyan()
+                            ));
+                            Some(saved_path)
+                        }
+                        Err(e) => {
+                            tally.failed.fetch_add(1, Ordering::Relaxed);
+                            options.log.verbose(format!(
+                                "   {} {} -> Error: {}",
+                                adapt("✗ Error descargando:").red().dimmed(),
+                                url.dimmed(),
+                                e.to_string().yellow()
+                            ));
+                            log::warn!("Failed to download {}: {}", url, e);
+                            None
+                        }
+                    };
+                    saved.lock().unwrap_or_else(|e| e.into_inner())[index] = result;
+                });
+            }
+        });
+
+        saved.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Check a resource URL against the blacklist, reporting it when blocked
+fn is_blocked(
+    url: &str,
+    blacklist: &Blacklist,
+    label: &str,
+    log: &PostProcessLog,
+    tally: &PageTally,
+) -> bool {
+    match validate_url_not_blacklisted(url, blacklist) {
+        Ok(()) => false,
+        Err(e) => {
+            tally.blocked.fetch_add(1, Ordering::Relaxed);
+            log.verbose(format!(
+                "   {} {} - {}",
+                adapt(label).red().dimmed(),
+                url.dimmed(),
+                e.to_string().yellow()
+            ));
+            true
+        }
+    }
+}
 
 /// Process HTML file completely: download resources, rewrite resource URLs, and rewrite hrefs to local files
+///
+/// Resource downloading and link rewriting always run; the site-specific
+/// phases (lazy images, reader scripts, removals, CDN, markup fixes) are
+/// selected by `profile`.
 pub fn process_html_file_complete(
     file_path: &PathBuf,
     base_dir: &PathBuf,
     base_url: &Url,
+    profile: &SiteProfile,
 ) -> Result<()> {
-    let content = fs::read_to_string(file_path)?;
+    process_html_file_with_options(
+        file_path,
+        base_dir,
+        base_url,
+        profile,
+        &PostProcessOptions::default(),
+    )
+}
+
+/// Same as [`process_html_file_complete`], with explicit asset options
+pub fn process_html_file_with_options(
+    file_path: &PathBuf,
+    base_dir: &PathBuf,
+    base_url: &Url,
+    profile: &SiteProfile,
+    options: &PostProcessOptions,
+) -> Result<()> {
+    let decoded = decode_html(&fs::read(file_path)?);
+    if decoded.lossy {
+        options.log.info(format!(
+            "   {} {}",
+            adapt("⚠️  Caracteres inválidos reemplazados en:").yellow(),
+            file_path.display()
+        ));
+    }
+    // The page is processed and written back as UTF-8, so it must declare it
+    let content = if decoded.converted() {
+        options.log.debug(format!(
+            "   {}",
+            adapt(&format!(
+                "🔤 Convirtiendo de {} a UTF-8...",
+                decoded.encoding.name()
+            ))
+            .cyan()
+            .dimmed()
+        ));
+        declare_utf8(&decoded.text)
+    } else {
+        decoded.text
+    };
     let document = scraper::Html::parse_document(&content);
 
+    // Relative URLs resolve against <base href> when the page declares one
+    let document_base = document_base_url(&document, base_url);
+
     let mut new_content = content.clone();
-    let mut replacements = Vec::new();
+    // (target, reference, is_image, queued download the reference depends on)
+    let mut replacements: Vec<(String, String, bool, Option<usize>)> = Vec::new();
+    let mut downloads = AssetDownloads::default();
+    let tally = PageTally::default();
 
     // Load blacklist for URL filtering
     let blacklist = match load_default_blacklist() {
         Ok(bl) => {
             if !bl.is_empty() {
-                println!(
+                options.log.debug(format!(
                     "   {} {} dominios bloqueados",
-                    "🛡️  Blacklist cargada:".dimmed(),
+                    adapt("🛡️  Blacklist cargada:").dimmed(),
                     bl.len().to_string().cyan()
-                );
+                ));
             }
             bl
         }
         Err(e) => {
-            println!(
+            options.log.info(format!(
                 "   {} {}",
-                "⚠️  No se pudo cargar blacklist:".yellow().dimmed(),
+                adapt("⚠️  No se pudo cargar blacklist:").yellow().dimmed(),
                 e.to_string().dimmed()
-            );
+            ));
             // Continue without blacklist
-            crate::core::Blacklist::new()
+            Blacklist::new()
         }
     };
 
@@ -67,7 +450,7 @@ pub fn process_html_file_complete(
         let mut srcset_urls: Vec<String> = Vec::new();
 
         // Handle srcset attribute for images (multiple URLs with different sizes)
-        if tag_name == "img" {
+        if tag_name == "img" && profile.lazy_images {
             if let Some(srcset_val) = element.value().attr("srcset") {
                 if !srcset_val.is_empty() {
                     // Parse srcset: "url1 size1, url2 size2, ..."
@@ -115,7 +498,7 @@ pub fn process_html_file_complete(
             // Handle <a> links - Replace with local paths
             if tag_name == "a" {
                 // Resolve URL
-                if let Ok(resolved_url) = base_url.join(url_str) {
+                if let Ok(resolved_url) = document_base.join(url_str) {
                     // Check if it's in scope (same domain)
                     if resolved_url.domain() == base_url.domain() {
                         // Calculate the local path where this file should be
@@ -136,6 +519,7 @@ pub fn process_html_file_complete(
                                         url_str.to_string(),
                                         relative_link_str,
                                         false,
+                                        None,
                                     ));
                                 }
                             }
@@ -157,28 +541,28 @@ pub fn process_html_file_complete(
                 };
 
                 // Check blacklist before downloading
-                if let Err(e) = validate_url_not_blacklisted(&full_url, &blacklist) {
-                    println!(
-                        "   {} {} - {}",
-                        "🚫 URL bloqueada:".red().dimmed(),
-                        full_url.dimmed(),
-                        e.to_string().yellow()
-                    );
+                if is_blocked(
+                    &full_url,
+                    &blacklist,
+                    "🚫 URL bloqueada:",
+                    &options.log,
+                    &tally,
+                ) {
                     continue; // Skip this resource
                 }
 
                 // Log external resource detection for images
                 if tag_name == "img" {
-                    println!(
+                    options.log.debug(format!(
                         "   {} {} ({})",
-                        "🔍 Detectada imagen externa:".blue().dimmed(),
+                        adapt("🔍 Detectada imagen externa:").blue().dimmed(),
                         full_url.dimmed(),
                         tag_name.yellow()
-                    );
+                    ));
                 }
 
                 // Extract safe filename from URL
-                let file_name = extract_filename_from_url(&full_url);
+                let file_name = asset_file_name(&full_url, options.keep_query_strings);
 
                 // Try to find or download the resource
                 // Priority: 1) local assets/ folder, 2) global assets/ folder
@@ -207,29 +591,13 @@ pub fn process_html_file_complete(
                     (local_path, format!("{}{}", assets_rel_path, file_name))
                 };
 
-                // Download if needed
-                if !final_path.exists() {
-                    match download_resource(&full_url, &final_path) {
-                        Ok(_) => {
-                            println!(
-                                "   {} {} -> {}",
-                                "✓ Descargado:".green().dimmed(),
-                                full_url.dimmed(),
-                                file_name.cyan()
-                            );
-                        }
-                        Err(e) => {
-                            println!(
-                                "   {} {} -> Error: {}",
-                                "✗ Error descargando:".red().dimmed(),
-                                full_url.dimmed(),
-                                e.to_string().yellow()
-                            );
-                            log::warn!("Failed to download {}: {}", full_url, e);
-                            continue;
-                        }
+                // Download if needed (after the scan, see AssetDownloads)
+                let (relative_path, download) = match options.saved_assets.existing(&final_path) {
+                    Some(saved_path) => {
+                        (retarget_asset_reference(&relative_path, &saved_path), None)
                     }
-                }
+                    None => (relative_path, Some(downloads.queue(&full_url, &final_path))),
+                };
 
                 // Record replacement
                 if tag_name == "img" {
@@ -240,12 +608,13 @@ pub fn process_html_file_complete(
                                 original_src.to_string(),
                                 relative_path.clone(),
                                 true,
+                                download,
                             ));
                         }
                     }
-                    replacements.push((url_str.to_string(), relative_path, true));
+                    replacements.push((url_str.to_string(), relative_path, true, download));
                 } else {
-                    replacements.push((url_str.to_string(), relative_path, false));
+                    replacements.push((url_str.to_string(), relative_path, false, download));
                 }
             }
         }
@@ -274,24 +643,24 @@ pub fn process_html_file_complete(
                 };
 
                 // Check blacklist before downloading srcset images
-                if let Err(e) = validate_url_not_blacklisted(&full_url, &blacklist) {
-                    println!(
-                        "   {} {} - {}",
-                        "🚫 URL bloqueada (srcset):".red().dimmed(),
-                        full_url.dimmed(),
-                        e.to_string().yellow()
-                    );
+                if is_blocked(
+                    &full_url,
+                    &blacklist,
+                    "🚫 URL bloqueada (srcset):",
+                    &options.log,
+                    &tally,
+                ) {
                     continue; // Skip this srcset resource
                 }
 
-                println!(
+                options.log.debug(format!(
                     "   {} {} (srcset)",
-                    "🔍 Detectada imagen en srcset:".blue().dimmed(),
+                    adapt("🔍 Detectada imagen en srcset:").blue().dimmed(),
                     full_url.dimmed()
-                );
+                ));
 
                 // Extract safe filename from URL
-                let file_name = extract_filename_from_url(&full_url);
+                let file_name = asset_file_name(&full_url, options.keep_query_strings);
 
                 // Try to find or download the resource
                 let local_assets_dir = parent.join("assets");
@@ -316,36 +685,37 @@ pub fn process_html_file_complete(
                     (local_path, format!("{}{}", assets_rel_path, file_name))
                 };
 
-                // Download if needed
-                if !final_path.exists() {
-                    match download_resource(&full_url, &final_path) {
-                        Ok(_) => {
-                            println!(
-                                "   {} {} -> {}",
-                                "✓ Descargado (srcset):".green().dimmed(),
-                                full_url.dimmed(),
-                                file_name.cyan()
-                            );
-                        }
-                        Err(e) => {
-                            println!(
-                                "   {} {} -> Error: {}",
-                                "✗ Error descargando (srcset):".red().dimmed(),
-                                full_url.dimmed(),
-                                e.to_string().yellow()
-                            );
-                            log::warn!("Failed to download srcset image {}: {}", full_url, e);
-                            continue;
-                        }
+                // Download if needed (after the scan, see AssetDownloads)
+                let (relative_path, download) = match options.saved_assets.existing(&final_path) {
+                    Some(saved_path) => {
+                        (retarget_asset_reference(&relative_path, &saved_path), None)
                     }
-                }
+                    None => (relative_path, Some(downloads.queue(&full_url, &final_path))),
+                };
 
                 // Record replacement for srcset URL
-                replacements.push((srcset_url.clone(), relative_path, true));
+                replacements.push((srcset_url.clone(), relative_path, true, download));
             }
         }
     }
 
+    // Download the queued resources in parallel, then point each reference
+    // at the saved file (dropping references whose download failed)
+    let saved_paths = downloads.download_all(options, &tally);
+    let replacements: Vec<(String, String, bool)> = replacements
+        .into_iter()
+        .filter_map(|(target, reference, is_image, download)| match download {
+            None => Some((target, reference, is_image)),
+            Some(index) => saved_paths[index].as_ref().map(|saved_path| {
+                (
+                    target,
+                    retarget_asset_reference(&reference, saved_path),
+                    is_image,
+                )
+            }),
+        })
+        .collect();
+
     // --- Fix absolute paths that start with "/" ---
     // These paths are meant to be relative to the website root, but when viewing locally
     // they need to be converted to proper relative paths from the current file's location
@@ -407,15 +777,15 @@ pub fn process_html_file_complete(
     }
 
     if !absolute_path_replacements.is_empty() {
-        println!(
+        options.log.debug(format!(
             "   {}",
-            format!(
+            adapt(&format!(
                 "🔧 Corrigiendo {} rutas absolutas a rutas relativas...",
                 absolute_path_replacements.len()
-            )
+            ))
             .cyan()
             .dimmed()
-        );
+        ));
         for (old_path, new_path) in absolute_path_replacements {
             new_content = new_content.replace(&old_path, &new_path);
         }
@@ -480,777 +850,988 @@ pub fn process_html_file_complete(
     }
 
     // --- Script-based Image Extraction (ts_reader) ---
-    let script_regex =
-        regex::Regex::new(r#"ts_reader\.run\((.*)\);"#).context("Failed to create script regex")?;
-    let mut script_replacements = Vec::new();
-
-    for cap in script_regex.captures_iter(&new_content) {
-        if let Some(json_match) = cap.get(1) {
-            let json_str = json_match.as_str();
-            if let Ok(mut json_data) = serde_json::from_str::<serde_json::Value>(json_str) {
-                let mut modified = false;
-
-                if let Some(obj) = json_data.as_object_mut() {
-                    obj.insert("lazyload".to_string(), serde_json::Value::Bool(false));
-                    modified = true;
-
-                    // Replace nextUrl with local path if it exists
-                    if let Some(next_url_val) = obj.get("nextUrl") {
-                        if let Some(next_url_str) = next_url_val.as_str() {
-                            // Parse and resolve the nextUrl
-                            let resolved_url = if next_url_str.starts_with("http://")
-                                || next_url_str.starts_with("https://")
-                            {
-                                Url::parse(next_url_str).ok()
-                            } else {
-                                base_url.join(next_url_str).ok()
-                            };
-
-                            if let Some(resolved_url) = resolved_url {
-                                // Check if it's in the same domain
-                                if resolved_url.domain() == base_url.domain() {
-                                    // Calculate possible local paths for this URL
-                                    let possible_paths =
-                                        calculate_possible_local_paths(&resolved_url, base_dir);
-
-                                    // Try each possible path until we find one that exists
-                                    let mut found_path: Option<PathBuf> = None;
-                                    for path in possible_paths.iter() {
-                                        if path.exists() {
-                                            found_path = Some(path.clone());
-                                            break;
+    if profile.reader_scripts {
+        let script_regex = regex::Regex::new(r#"ts_reader\.run\((.*)\);"#)
+            .context("Failed to create script regex")?;
+        let mut script_replacements = Vec::new();
+        // (original JSON, edited JSON, image references waiting on a download)
+        let mut scripts: Vec<(String, serde_json::Value, Vec<ReaderImage>)> = Vec::new();
+        let mut downloads = AssetDownloads::default();
+
+        for cap in script_regex.captures_iter(&new_content) {
+            if let Some(json_match) = cap.get(1) {
+                let json_str = json_match.as_str();
+                if let Ok(mut json_data) = serde_json::from_str::<serde_json::Value>(json_str) {
+                    let mut modified = false;
+
+                    if let Some(obj) = json_data.as_object_mut() {
+                        obj.insert("lazyload".to_string(), serde_json::Value::Bool(false));
+                        modified = true;
+
+                        // Replace nextUrl with local path if it exists
+                        if let Some(next_url_val) = obj.get("nextUrl") {
+                            if let Some(next_url_str) = next_url_val.as_str() {
+                                // Parse and resolve the nextUrl
+                                let resolved_url = if next_url_str.starts_with("http://")
+                                    || next_url_str.starts_with("https://")
+                                {
+                                    Url::parse(next_url_str).ok()
+                                } else {
+                                    document_base.join(next_url_str).ok()
+                                };
+
+                                if let Some(resolved_url) = resolved_url {
+                                    // Check if it's in the same domain
+                                    if resolved_url.domain() == base_url.domain() {
+                                        // Calculate possible local paths for this URL
+                                        let possible_paths =
+                                            calculate_possible_local_paths(&resolved_url, base_dir);
+
+                                        // Try each possible path until we find one that exists
+                                        let mut found_path: Option<PathBuf> = None;
+                                        for path in possible_paths.iter() {
+                                            if path.exists() {
+                                                found_path = Some(path.clone());
+                                                break;
+                                            }
                                         }
-                                    }
-
-                                    if let Some(local_next_path) = found_path {
-                                        // Calculate relative path from current file to next file
-                                        let parent_file = file_path.parent().unwrap_or(base_dir);
-                                        if let Some(relative_next) =
-                                            pathdiff::diff_paths(&local_next_path, parent_file)
-                                        {
-                                            let relative_next_str =
-                                                relative_next.to_string_lossy().replace('\\', "/");
 
-                                            obj.insert(
-                                                "nextUrl".to_string(),
-                                                serde_json::Value::String(relative_next_str),
-                                            );
-                                            modified = true;
+                                        if let Some(local_next_path) = found_path {
+                                            // Calculate relative path from current file to next file
+                                            let parent_file =
+                                                file_path.parent().unwrap_or(base_dir);
+                                            if let Some(relative_next) =
+                                                pathdiff::diff_paths(&local_next_path, parent_file)
+                                            {
+                                                let relative_next_str = relative_next
+                                                    .to_string_lossy()
+                                                    .replace('\\', "/");
+
+                                                obj.insert(
+                                                    "nextUrl".to_string(),
+                                                    serde_json::Value::String(relative_next_str),
+                                                );
+                                                modified = true;
+                                            }
                                         }
                                     }
                                 }
                             }
                         }
-                    }
 
-                    // Similarly handle prevUrl if it exists
-                    if let Some(prev_url_val) = obj.get("prevUrl") {
-                        if let Some(prev_url_str) = prev_url_val.as_str() {
-                            let resolved_url = if prev_url_str.starts_with("http://")
-                                || prev_url_str.starts_with("https://")
-                            {
-                                Url::parse(prev_url_str).ok()
-                            } else {
-                                base_url.join(prev_url_str).ok()
-                            };
-
-                            if let Some(resolved_url) = resolved_url {
-                                if resolved_url.domain() == base_url.domain() {
-                                    let possible_paths =
-                                        calculate_possible_local_paths(&resolved_url, base_dir);
-
-                                    let mut found_path: Option<PathBuf> = None;
-                                    for path in possible_paths.iter() {
-                                        if path.exists() {
-                                            found_path = Some(path.clone());
-                                            break;
+                        // Similarly handle prevUrl if it exists
+                        if let Some(prev_url_val) = obj.get("prevUrl") {
+                            if let Some(prev_url_str) = prev_url_val.as_str() {
+                                let resolved_url = if prev_url_str.starts_with("http://")
+                                    || prev_url_str.starts_with("https://")
+                                {
+                                    Url::parse(prev_url_str).ok()
+                                } else {
+                                    document_base.join(prev_url_str).ok()
+                                };
+
+                                if let Some(resolved_url) = resolved_url {
+                                    if resolved_url.domain() == base_url.domain() {
+                                        let possible_paths =
+                                            calculate_possible_local_paths(&resolved_url, base_dir);
+
+                                        let mut found_path: Option<PathBuf> = None;
+                                        for path in possible_paths.iter() {
+                                            if path.exists() {
+                                                found_path = Some(path.clone());
+                                                break;
+                                            }
                                         }
-                                    }
 
-                                    if let Some(local_prev_path) = found_path {
-                                        let parent_file = file_path.parent().unwrap_or(base_dir);
-                                        if let Some(relative_prev) =
-                                            pathdiff::diff_paths(&local_prev_path, parent_file)
-                                        {
-                                            let relative_prev_str =
-                                                relative_prev.to_string_lossy().replace('\\', "/");
-
-                                            obj.insert(
-                                                "prevUrl".to_string(),
-                                                serde_json::Value::String(relative_prev_str),
-                                            );
-                                            modified = true;
+                                        if let Some(local_prev_path) = found_path {
+                                            let parent_file =
+                                                file_path.parent().unwrap_or(base_dir);
+                                            if let Some(relative_prev) =
+                                                pathdiff::diff_paths(&local_prev_path, parent_file)
+                                            {
+                                                let relative_prev_str = relative_prev
+                                                    .to_string_lossy()
+                                                    .replace('\\', "/");
+
+                                                obj.insert(
+                                                    "prevUrl".to_string(),
+                                                    serde_json::Value::String(relative_prev_str),
+                                                );
+                                                modified = true;
+                                            }
                                         }
                                     }
                                 }
                             }
                         }
                     }
-                }
 
-                if let Some(sources) = json_data.get_mut("sources").and_then(|s| s.as_array_mut()) {
-                    for source in sources {
-                        if let Some(images) =
-                            source.get_mut("images").and_then(|i| i.as_array_mut())
-                        {
-                            for image_val in images {
-                                if let Some(img_url) = image_val.as_str() {
-                                    // Skip if already a local relative path
-                                    if is_local_path(img_url) {
-                                        continue; // Already processed, skip
-                                    }
+                    let mut pending_images = Vec::new();
 
-                                    let full_url = if img_url.starts_with("//") {
-                                        format!("https:{}", img_url)
-                                    } else if img_url.starts_with("http://")
-                                        || img_url.starts_with("https://")
-                                    {
-                                        img_url.to_string()
-                                    } else {
-                                        // Relative URL that needs to be resolved against base_url
-                                        match base_url.join(img_url) {
-                                            Ok(resolved) => resolved.to_string(),
-                                            Err(_) => {
-                                                // Can't resolve, skip it
-                                                continue;
-                                            }
-                                        }
-                                    };
-
-                                    let file_name = extract_filename_from_url(&full_url);
-
-                                    // Try to find or download the resource
-                                    // Priority: 1) local assets/ folder, 2) global assets/ folder
-                                    let local_assets_dir = parent.join("assets");
-                                    let global_assets_dir = base_dir.join("assets");
-
-                                    let local_path = local_assets_dir.join(&file_name);
-                                    let global_path = global_assets_dir.join(&file_name);
-
-                                    let (final_path, replacement_path) = if local_path.exists() {
-                                        // Already exists locally
-                                        (local_path, format!("{}{}", assets_rel_path, file_name))
-                                    } else if global_path.exists() {
-                                        // Exists in global, calculate relative path
-                                        let parent_to_base = pathdiff::diff_paths(base_dir, parent)
-                                            .unwrap_or_default();
-                                        let mut rel_to_global = String::new();
-                                        for _ in 0..parent_to_base.components().count() {
-                                            rel_to_global.push_str("../");
+                    if let Some(sources) =
+                        json_data.get_mut("sources").and_then(|s| s.as_array_mut())
+                    {
+                        for (source_index, source) in sources.iter_mut().enumerate() {
+                            if let Some(images) =
+                                source.get_mut("images").and_then(|i| i.as_array_mut())
+                            {
+                                for (image_index, image_val) in images.iter_mut().enumerate() {
+                                    if let Some(img_url) = image_val.as_str() {
+                                        // Skip if already a local relative path
+                                        if is_local_path(img_url) {
+                                            continue; // Already processed, skip
                                         }
-                                        rel_to_global.push_str("assets/");
-                                        rel_to_global.push_str(&file_name);
-                                        (global_path, rel_to_global)
-                                    } else {
-                                        // Need to download - prefer local
-                                        if fs::create_dir_all(&local_assets_dir).is_ok() {
+
+                                        let full_url = if img_url.starts_with("//") {
+                                            format!("https:{}", img_url)
+                                        } else if img_url.starts_with("http://")
+                                            || img_url.starts_with("https://")
+                                        {
+                                            img_url.to_string()
+                                        } else {
+                                            // Relative URL that needs to be resolved against base_url
+                                            match document_base.join(img_url) {
+                                                Ok(resolved) => resolved.to_string(),
+                                                Err(_) => {
+                                                    // Can't resolve, skip it
+                                                    continue;
+                                                }
+                                            }
+                                        };
+
+                                        let file_name =
+                                            asset_file_name(&full_url, options.keep_query_strings);
+
+                                        // Try to find or download the resource
+                                        // Priority: 1) local assets/ folder, 2) global assets/ folder
+                                        let local_assets_dir = parent.join("assets");
+                                        let global_assets_dir = base_dir.join("assets");
+
+                                        let local_path = local_assets_dir.join(&file_name);
+                                        let global_path = global_assets_dir.join(&file_name);
+
+                                        let (final_path, replacement_path) = if local_path.exists()
+                                        {
+                                            // Already exists locally
                                             (
                                                 local_path,
                                                 format!("{}{}", assets_rel_path, file_name),
                                             )
+                                        } else if global_path.exists() {
+                                            // Exists in global, calculate relative path
+                                            let parent_to_base =
+                                                pathdiff::diff_paths(base_dir, parent)
+                                                    .unwrap_or_default();
+                                            let mut rel_to_global = String::new();
+                                            for _ in 0..parent_to_base.components().count() {
+                                                rel_to_global.push_str("../");
+                                            }
+                                            rel_to_global.push_str("assets/");
+                                            rel_to_global.push_str(&file_name);
+                                            (global_path, rel_to_global)
                                         } else {
-                                            continue;
-                                        }
-                                    };
-
-                                    // Download if needed
-                                    if !final_path.exists() {
-                                        match download_resource(&full_url, &final_path) {
-                                            Ok(_) => {
-                                                *image_val =
-                                                    serde_json::Value::String(replacement_path);
-                                                modified = true;
+                                            // Need to download - prefer local
+                                            if fs::create_dir_all(&local_assets_dir).is_ok() {
+                                                (
+                                                    local_path,
+                                                    format!("{}{}", assets_rel_path, file_name),
+                                                )
+                                            } else {
+                                                continue;
                                             }
-                                            Err(e) => {
-                                                log::warn!(
-                                                    "Failed to download script image {}: {}",
-                                                    full_url,
-                                                    e
-                                                );
+                                        };
+
+                                        // Download if needed (after the scan, see AssetDownloads)
+                                        if let Some(saved_path) =
+                                            options.saved_assets.existing(&final_path)
+                                        {
+                                            // File already exists, just update the reference
+                                            *image_val = serde_json::Value::String(
+                                                retarget_asset_reference(
+                                                    &replacement_path,
+                                                    &saved_path,
+                                                ),
+                                            );
+                                            modified = true;
+                                        } else {
+                                            if is_blocked(
+                                                &full_url,
+                                                &blacklist,
+                                                "🚫 URL bloqueada (lector):",
+                                                &options.log,
+                                                &tally,
+                                            ) {
+                                                continue;
                                             }
+                                            pending_images.push(ReaderImage {
+                                                pointer: format!(
+                                                    "/sources/{}/images/{}",
+                                                    source_index, image_index
+                                                ),
+                                                reference: replacement_path,
+                                                download: downloads.queue(&full_url, &final_path),
+                                            });
                                         }
-                                    } else {
-                                        // File already exists, just update the reference
-                                        *image_val = serde_json::Value::String(replacement_path);
-                                        modified = true;
                                     }
                                 }
                             }
                         }
                     }
-                }
 
-                if modified {
-                    if let Ok(new_json_str) = serde_json::to_string(&json_data) {
-                        script_replacements.push((json_str.to_string(), new_json_str));
+                    if modified {
+                        scripts.push((json_str.to_string(), json_data, pending_images));
                     }
                 }
             }
         }
-    }
 
-    for (old_json, new_json) in script_replacements {
-        new_content = new_content.replace(&old_json, &new_json);
+        // Images whose download failed keep their original URL
+        let saved_paths = downloads.download_all(options, &tally);
+        for (json_str, mut json_data, pending_images) in scripts {
+            for image in pending_images {
+                if let (Some(saved_path), Some(image_val)) = (
+                    &saved_paths[image.download],
+                    json_data.pointer_mut(&image.pointer),
+                ) {
+                    *image_val = serde_json::Value::String(retarget_asset_reference(
+                        &image.reference,
+                        saved_path,
+                    ));
+                }
+            }
+
+            if let Ok(new_json_str) = serde_json::to_string(&json_data) {
+                script_replacements.push((json_str, new_json_str));
+            }
+        }
+
+        for (old_json, new_json) in script_replacements {
+            new_content = new_content.replace(&old_json, &new_json);
+        }
     }
 
     // --- Script Sanitization (Remove Redirects) ---
     // Remove scripts containing "redirectUrl =" or "createAccountUrl ="
-    let script_block_regex = regex::Regex::new(r#"(?s)<script[^>]*>(.*?)</script>"#)
-        .context("Failed to create script block regex")?;
-
-    let mut script_removals = Vec::new();
-
-    for cap in script_block_regex.captures_iter(&new_content) {
-        if let (Some(full_match), Some(content_match)) = (cap.get(0), cap.get(1)) {
-            let script_content = content_match.as_str();
-            let full_script = full_match.as_str();
-
-            // Verificar si es un script externo que carga cookie_banner.js
-            let is_cookie_banner_src = full_script.contains("src=")
-                && (full_script.contains("cookie_banner.js")
-                    || full_script.contains("cookie_banner.min.js"));
-
-            // Verificar si es un script de redirección o compra
-            let is_redirect_script = script_content.contains("redirectUrl =")
-                || script_content.contains("createAccountUrl =");
-            // script_content.contains("createAccountUrl =") ||
-            // script_content.contains("var PURCHASE_PHX =");
-
-            // Verificar si es el script del banner de cookies (inline)
-            let is_cookie_banner_script = script_content
-                .contains("typeof CookieHelper != 'undefined'")
-                || script_content.contains("typeof CookieHelper !== 'undefined'")
-                || script_content.contains("var essentialCookiesListAll =")
-                || script_content.contains("var customizeCookiesTemplate =");
-
-            // Verificar si es un script de publicidad
-            let is_ads_script = false;
-
-            // Verificar si es un script de Google Tag Manager o analytics
-            let is_analytics_script = full_script
-                .contains("https://www.googletagmanager.com/gtm.js")
-                || full_script.contains("googletagmanager.com/gtm.js");
-
-            // Verificar si es un script de CAPTCHA
-            let is_captcha_script = script_content.contains("captchaType")
-                || script_content.contains("captchaToken")
-                || full_script.contains("www.google.com/recaptcha");
-
-            // Verificar si es un script con funciones de cookies/mensajes de usuario
-            let is_cookie_message_script = full_script
-                .contains(r#"<a onclick="hideUserMessage();"#)
-                || script_content.contains("setCookieAdvanced(");
-
-            // Verificar si es un script relacionado con Google/bots/whitelist
-            let is_bot_whitelist_script = script_content.contains("'google'")
-                || script_content.contains("'googlebot'")
-                || script_content.contains("isInWhitelist");
-
-            // Verificar si es un script de geo-localización
-            let is_geo_localization_script = script_content.contains("geo-localization")
-                || script_content.contains("geolocalization")
-                || script_content.contains("geo_localization");
-
-            if is_redirect_script
-                || is_cookie_banner_script
-                || is_cookie_banner_src
-                || is_ads_script
-                || is_analytics_script
-                || is_captcha_script
-                || is_cookie_message_script
-                || is_bot_whitelist_script
-                || is_geo_localization_script
-            {
-                script_removals.push(full_script.to_string());
-
-                // Mostrar mensaje específico según el tipo de script
-                if is_cookie_banner_script || is_cookie_banner_src {
-                    println!(
-                        "   {} {}",
-                        "🍪 Detectado script de banner de cookies".yellow().dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
-                } else if is_ads_script {
-                    println!(
-                        "   {} {}",
-                        "📢 Detectado script de publicidad".yellow().dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
-                } else if is_analytics_script {
-                    println!(
-                        "   {} {}",
-                        "📊 Detectado script de analytics/tracking"
-                            .yellow()
-                            .dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
-                } else if is_captcha_script {
-                    println!(
-                        "   {} {}",
-                        "🤖 Detectado script de CAPTCHA/reCAPTCHA".yellow().dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
-                } else if is_cookie_message_script {
-                    println!(
-                        "   {} {}",
-                        "💬 Detectado script de mensajes/cookies de usuario"
-                            .yellow()
-                            .dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
-                } else if is_bot_whitelist_script {
-                    println!(
-                        "   {} {}",
-                        "🔍 Detectado script de bot/whitelist detection"
-                            .yellow()
-                            .dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
-                } else if is_geo_localization_script {
-                    println!(
-                        "   {} {}",
-                        "🌍 Detectado script de geo-localización".yellow().dimmed(),
-                        format!("({} bytes)", full_script.len()).dimmed()
-                    );
+    if profile.removes(RemovalRule::Scripts) {
+        let script_block_regex = regex::Regex::new(r#"(?s)<script[^>]*>(.*?)</script>"#)
+            .context("Failed to create script block regex")?;
+
+        let mut script_removals = Vec::new();
+
+        for cap in script_block_regex.captures_iter(&new_content) {
+            if let (Some(full_match), Some(content_match)) = (cap.get(0), cap.get(1)) {
+                let script_content = content_match.as_str();
+                let full_script = full_match.as_str();
+
+                // Verificar si es un script externo que carga cookie_banner.js
+                let is_cookie_banner_src = full_script.contains("src=")
+                    && (full_script.contains("cookie_banner.js")
+                        || full_script.contains("cookie_banner.min.js"));
+
+                // Verificar si es un script de redirección o compra
+                let is_redirect_script = script_content.contains("redirectUrl =")
+                    || script_content.contains("createAccountUrl =");
+                // script_content.contains("createAccountUrl =") ||
+                // script_content.contains("var PURCHASE_PHX =");
+
+                // Verificar si es el script del banner de cookies (inline)
+                let is_cookie_banner_script = script_content
+                    .contains("typeof CookieHelper != 'undefined'")
+                    || script_content.contains("typeof CookieHelper !== 'undefined'")
+                    || script_content.contains("var essentialCookiesListAll =")
+                    || script_content.contains("var customizeCookiesTemplate =");
+
+                // Verificar si es un script de publicidad
+                let is_ads_script = false;
+
+                // Verificar si es un script de Google Tag Manager o analytics
+                let is_analytics_script = full_script
+                    .contains("https://www.googletagmanager.com/gtm.js")
+                    || full_script.contains("googletagmanager.com/gtm.js");
+
+                // Verificar si es un script de CAPTCHA
+                let is_captcha_script = script_content.contains("captchaType")
+                    || script_content.contains("captchaToken")
+                    || full_script.contains("www.google.com/recaptcha");
+
+                // Verificar si es un script con funciones de cookies/mensajes de usuario
+                let is_cookie_message_script = full_script
+                    .contains(r#"<a onclick="hideUserMessage();"#)
+                    || script_content.contains("setCookieAdvanced(");
+
+                // Verificar si es un script relacionado con Google/bots/whitelist
+                let is_bot_whitelist_script = script_content.contains("'google'")
+                    || script_content.contains("'googlebot'")
+                    || script_content.contains("isInWhitelist");
+
+                // Verificar si es un script de geo-localización
+                let is_geo_localization_script = script_content.contains("geo-localization")
+                    || script_content.contains("geolocalization")
+                    || script_content.contains("geo_localization");
+
+                if is_redirect_script
+                    || is_cookie_banner_script
+                    || is_cookie_banner_src
+                    || is_ads_script
+                    || is_analytics_script
+                    || is_captcha_script
+                    || is_cookie_message_script
+                    || is_bot_whitelist_script
+                    || is_geo_localization_script
+                {
+                    script_removals.push(full_script.to_string());
+
+                    // Mostrar mensaje específico según el tipo de script
+                    if is_cookie_banner_script || is_cookie_banner_src {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("🍪 Detectado script de banner de cookies")
+                                .yellow()
+                                .dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    } else if is_ads_script {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("📢 Detectado script de publicidad").yellow().dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    } else if is_analytics_script {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("📊 Detectado script de analytics/tracking")
+                                .yellow()
+                                .dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    } else if is_captcha_script {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("🤖 Detectado script de CAPTCHA/reCAPTCHA")
+                                .yellow()
+                                .dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    } else if is_cookie_message_script {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("💬 Detectado script de mensajes/cookies de usuario")
+                                .yellow()
+                                .dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    } else if is_bot_whitelist_script {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("🔍 Detectado script de bot/whitelist detection")
+                                .yellow()
+                                .dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    } else if is_geo_localization_script {
+                        options.log.debug(format!(
+                            "   {} {}",
+                            adapt("🌍 Detectado script de geo-localización")
+                                .yellow()
+                                .dimmed(),
+                            format!("({} bytes)", full_script.len()).dimmed()
+                        ));
+                    }
                 }
             }
         }
+
+        if !script_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🛡️  Eliminando {} scripts de redirección...",
+                    script_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for script in script_removals {
+                new_content = new_content.replace(&script, "");
+            }
+        }
     }
 
-    if !script_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "🛡️  Eliminando {} scripts de redirección...",
-                script_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for script in script_removals {
-            new_content = new_content.replace(&script, "");
+    // --- Remove <base> elements ---
+    // Links were rewritten to local relative paths, which a remote base would break
+    if document_base != *base_url {
+        let base_tag_regex =
+            regex::Regex::new(r#"<base\b[^>]*>"#).context("Failed to create base regex")?;
+
+        if base_tag_regex.is_match(&new_content) {
+            options.log.debug(format!(
+                "   {}",
+                adapt("🔗 Eliminando elemento <base>...").yellow().dimmed()
+            ));
+            new_content = base_tag_regex.replace_all(&new_content, "").into_owned();
         }
     }
 
+    // --- Remove meta-refresh redirects ---
+    // Offline copies must not bounce to the (possibly remote) redirect target
+    let meta_refresh_regex =
+        regex::Regex::new(r#"(?i)<meta\b[^>]*http-equiv\s*=\s*["']?refresh["']?[^>]*>"#)
+            .context("Failed to create meta-refresh regex")?;
+
+    if meta_refresh_regex.is_match(&new_content) {
+        options.log.debug(format!(
+            "   {}",
+            adapt("🔗 Eliminando redirección <meta http-equiv=\"refresh\">...")
+                .yellow()
+                .dimmed()
+        ));
+        new_content = meta_refresh_regex
+            .replace_all(&new_content, "")
+            .into_owned();
+    }
+
     // --- Remove dns-prefetch link elements ---
     // Match both <link .../> and <link ...></link> formats
-    let dns_prefetch_regex =
-        regex::Regex::new(r#"<link[^>]*rel\s*=\s*["']dns-prefetch["'][^>]*/?>"#)
-            .context("Failed to create dns-prefetch regex")?;
+    if profile.removes(RemovalRule::DnsPrefetch) {
+        let dns_prefetch_regex =
+            regex::Regex::new(r#"<link[^>]*rel\s*=\s*["']dns-prefetch["'][^>]*/?>"#)
+                .context("Failed to create dns-prefetch regex")?;
 
-    let mut link_removals = Vec::new();
+        let mut link_removals = Vec::new();
 
-    for cap in dns_prefetch_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            link_removals.push(full_match.as_str().to_string());
+        for cap in dns_prefetch_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                link_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !link_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "🔗 Eliminando {} elementos <link> dns-prefetch...",
-                link_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for link in link_removals {
-            new_content = new_content.replace(&link, "");
+        if !link_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🔗 Eliminando {} elementos <link> dns-prefetch...",
+                    link_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for link in link_removals {
+                new_content = new_content.replace(&link, "");
+            }
         }
     }
 
     // --- Remove Google Tag Manager link elements ---
     // Match <link> elements with href containing googletagmanager.com
     // This includes preconnect, dns-prefetch, and any other link types to GTM
-    let gtm_link_regex = regex::Regex::new(
-        r#"<link[^>]*href\s*=\s*["']https?://[^"']*googletagmanager\.com[^"']*["'][^>]*/?>"#,
-    )
-    .context("Failed to create Google Tag Manager link regex")?;
+    if profile.removes(RemovalRule::Tracking) {
+        let gtm_link_regex = regex::Regex::new(
+            r#"<link[^>]*href\s*=\s*["']https?://[^"']*googletagmanager\.com[^"']*["'][^>]*/?>"#,
+        )
+        .context("Failed to create Google Tag Manager link regex")?;
 
-    let mut gtm_link_removals = Vec::new();
+        let mut gtm_link_removals = Vec::new();
 
-    for cap in gtm_link_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            gtm_link_removals.push(full_match.as_str().to_string());
+        for cap in gtm_link_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                gtm_link_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !gtm_link_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "📊 Eliminando {} elementos <link> de Google Tag Manager...",
-                gtm_link_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for link in gtm_link_removals {
-            new_content = new_content.replace(&link, "");
+        if !gtm_link_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "📊 Eliminando {} elementos <link> de Google Tag Manager...",
+                    gtm_link_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for link in gtm_link_removals {
+                new_content = new_content.replace(&link, "");
+            }
         }
     }
 
     // --- Remove create account form elements ---
-    let create_account_regex =
-        regex::Regex::new(r#"<v-create-account-form[^>]*>.*?</v-create-account-form>"#)
-            .context("Failed to create v-create-account-form regex")?;
+    if profile.removes(RemovalRule::AccountForms) {
+        let create_account_regex =
+            regex::Regex::new(r#"<v-create-account-form[^>]*>.*?</v-create-account-form>"#)
+                .context("Failed to create v-create-account-form regex")?;
 
-    let mut form_removals = Vec::new();
+        let mut form_removals = Vec::new();
 
-    for cap in create_account_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            form_removals.push(full_match.as_str().to_string());
+        for cap in create_account_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                form_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !form_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "📝 Eliminando {} elementos <v-create-account-form>...",
-                form_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for form in form_removals {
-            new_content = new_content.replace(&form, "");
+        if !form_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "📝 Eliminando {} elementos <v-create-account-form>...",
+                    form_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for form in form_removals {
+                new_content = new_content.replace(&form, "");
+            }
         }
     }
 
     // --- Remove cookie banner menu items ---
     // Match <li> elements containing <a> with onclick="showFullCookieBanner();"
-    let cookie_banner_li_regex = regex::Regex::new(r#"<li[^>]*>.*?<a[^>]*onclick\s*=\s*["']showFullCookieBanner\(\);["'][^>]*>.*?</a>.*?</li>"#)
-        .context("Failed to create cookie banner li regex")?;
+    if profile.removes(RemovalRule::CookieBanners) {
+        let cookie_banner_li_regex = regex::Regex::new(r#"<li[^>]*>.*?<a[^>]*onclick\s*=\s*["']showFullCookieBanner\(\);["'][^>]*>.*?</a>.*?</li>"#)
+            .context("Failed to create cookie banner li regex")?;
 
-    let mut li_removals = Vec::new();
+        let mut li_removals = Vec::new();
 
-    for cap in cookie_banner_li_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            li_removals.push(full_match.as_str().to_string());
+        for cap in cookie_banner_li_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                li_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !li_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "🍪 Eliminando {} elementos <li> con showFullCookieBanner...",
-                li_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for li in li_removals {
-            new_content = new_content.replace(&li, "");
+        if !li_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🍪 Eliminando {} elementos <li> con showFullCookieBanner...",
+                    li_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for li in li_removals {
+                new_content = new_content.replace(&li, "");
+            }
         }
     }
 
     // --- Remove adBlock alert if blocks ---
     // Match: if (!getCookieAdvanced('adBlockAlertHidden')) { ... }
-    let adblock_if_regex = regex::Regex::new(
-        r#"if\s*\(\s*!getCookieAdvanced\s*\(\s*['"]adBlockAlertHidden['"]\s*\)\s*\)\s*\{[^{}]*\}"#,
-    )
-    .context("Failed to create adblock if regex")?;
+    if profile.removes(RemovalRule::AdblockAlerts) {
+        let adblock_if_regex = regex::Regex::new(
+            r#"if\s*\(\s*!getCookieAdvanced\s*\(\s*['"]adBlockAlertHidden['"]\s*\)\s*\)\s*\{[^{}]*\}"#,
+        )
+        .context("Failed to create adblock if regex")?;
 
-    let mut if_removals = Vec::new();
+        let mut if_removals = Vec::new();
 
-    for cap in adblock_if_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            if_removals.push(full_match.as_str().to_string());
+        for cap in adblock_if_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                if_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !if_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "🚫 Eliminando {} bloques if de adBlock alert...",
-                if_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for if_block in if_removals {
-            new_content = new_content.replace(&if_block, "");
+        if !if_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🚫 Eliminando {} bloques if de adBlock alert...",
+                    if_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for if_block in if_removals {
+                new_content = new_content.replace(&if_block, "");
+            }
         }
     }
 
     // --- Remove holiday promo if-else blocks ---
     // Match: if (typeof page_params.holiday_promo_prem === 'undefined') { ... } else { ... }
-    let holiday_promo_regex = regex::Regex::new(
-        r#"if\s*\(\s*typeof\s+page_params\.holiday_promo_prem\s*===\s*['"]undefined['"]\s*\)\s*\{[^{}]*\}\s*else\s*\{[^{}]*\}"#
-    ).context("Failed to create holiday promo regex")?;
+    if profile.removes(RemovalRule::HolidayPromos) {
+        let holiday_promo_regex = regex::Regex::new(
+            r#"if\s*\(\s*typeof\s+page_params\.holiday_promo_prem\s*===\s*['"]undefined['"]\s*\)\s*\{[^{}]*\}\s*else\s*\{[^{}]*\}"#
+        ).context("Failed to create holiday promo regex")?;
 
-    let mut promo_removals = Vec::new();
+        let mut promo_removals = Vec::new();
 
-    for cap in holiday_promo_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            promo_removals.push(full_match.as_str().to_string());
+        for cap in holiday_promo_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                promo_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !promo_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "🎁 Eliminando {} bloques if-else de holiday_promo_prem...",
-                promo_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for promo_block in promo_removals {
-            new_content = new_content.replace(&promo_block, "");
+        if !promo_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🎁 Eliminando {} bloques if-else de holiday_promo_prem...",
+                    promo_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for promo_block in promo_removals {
+                new_content = new_content.replace(&promo_block, "");
+            }
         }
     }
 
     // --- Download CDN Resources from JS Variables ---
     // Detect patterns like: playlistJs = "https://cdn.example.com/file.m3u8"
-    let js_var_cdn_regex = regex::Regex::new(r#"(\w+)\s*=\s*["']((https?://[^"']*cdn[^"']*))["']"#)
-        .context("Failed to create JS variable CDN regex")?;
+    if profile.cdn_downloads {
+        let js_var_cdn_regex =
+            regex::Regex::new(r#"(\w+)\s*=\s*["']((https?://[^"']*cdn[^"']*))["']"#)
+                .context("Failed to create JS variable CDN regex")?;
+
+        // (matched text, variable name, reference, queued download)
+        let mut cdn_replacements: Vec<(String, String, String, Option<usize>)> = Vec::new();
+        let mut downloads = AssetDownloads::default();
+
+        for cap in js_var_cdn_regex.captures_iter(&new_content) {
+            if let (Some(full_match), Some(var_name), Some(cdn_url)) =
+                (cap.get(0), cap.get(1), cap.get(2))
+            {
+                let var_name_str = var_name.as_str();
+                let cdn_url_str = cdn_url.as_str();
+                let full_match_str = full_match.as_str();
 
-    let mut cdn_replacements = Vec::new();
+                // Extract safe filename from URL
+                let file_name = asset_file_name(cdn_url_str, options.keep_query_strings);
 
-    for cap in js_var_cdn_regex.captures_iter(&new_content) {
-        if let (Some(full_match), Some(var_name), Some(cdn_url)) =
-            (cap.get(0), cap.get(1), cap.get(2))
-        {
-            let var_name_str = var_name.as_str();
-            let cdn_url_str = cdn_url.as_str();
-            let full_match_str = full_match.as_str();
-
-            // Extract safe filename from URL
-            let file_name = extract_filename_from_url(cdn_url_str);
-
-            // Try to find or download the resource
-            let local_assets_dir = parent.join("assets");
-            fs::create_dir_all(&local_assets_dir)?;
-
-            let local_path = local_assets_dir.join(&file_name);
-            let relative_path = format!("{}{}", assets_rel_path, file_name);
-
-            // Download if needed
-            if !local_path.exists() {
-                println!(
-                    "   {} {} -> {}",
-                    "📦 Descargando recurso CDN:".cyan().dimmed(),
-                    var_name_str.yellow(),
-                    file_name.dimmed()
-                );
-
-                match download_resource(cdn_url_str, &local_path) {
-                    Ok(_) => {
-                        // Create replacement with local path
-                        let replacement = format!(r#"{} = "{}""#, var_name_str, relative_path);
-                        cdn_replacements.push((full_match_str.to_string(), replacement));
-                    }
-                    Err(e) => {
-                        log::warn!("Failed to download CDN resource {}: {}", cdn_url_str, e);
-                        println!(
-                            "   {} {}",
-                            "⚠️  Error descargando:".yellow(),
-                            e.to_string().dimmed()
-                        );
+                // Try to find or download the resource
+                let local_assets_dir = parent.join("assets");
+                fs::create_dir_all(&local_assets_dir)?;
+
+                let local_path = local_assets_dir.join(&file_name);
+                let mut relative_path = format!("{}{}", assets_rel_path, file_name);
+
+                // Download if needed (after the scan, see AssetDownloads)
+                let download = if let Some(saved_path) = options.saved_assets.existing(&local_path)
+                {
+                    // File already exists, just update the reference
+                    relative_path = retarget_asset_reference(&relative_path, &saved_path);
+                    None
+                } else {
+                    if is_blocked(
+                        cdn_url_str,
+                        &blacklist,
+                        "🚫 URL bloqueada (CDN):",
+                        &options.log,
+                        &tally,
+                    ) {
+                        continue;
                     }
-                }
-            } else {
-                // File already exists, just update the reference
-                let replacement = format!(r#"{} = "{}""#, var_name_str, relative_path);
-                cdn_replacements.push((full_match_str.to_string(), replacement));
+                    options.log.debug(format!(
+                        "   {} {} -> {}",
+                        adapt("📦 Descargando recurso CDN:").cyan().dimmed(),
+                        var_name_str.yellow(),
+                        file_name.dimmed()
+                    ));
+                    Some(downloads.queue(cdn_url_str, &local_path))
+                };
+
+                cdn_replacements.push((
+                    full_match_str.to_string(),
+                    var_name_str.to_string(),
+                    relative_path,
+                    download,
+                ));
             }
         }
-    }
 
-    // Apply CDN replacements
-    for (old_str, new_str) in cdn_replacements {
-        new_content = new_content.replace(&old_str, &new_str);
-    }
+        // Apply CDN replacements (with local paths) once the downloads finished
+        let saved_paths = downloads.download_all(options, &tally);
+        for (old_str, var_name, relative_path, download) in cdn_replacements {
+            let relative_path = match download {
+                None => relative_path,
+                Some(index) => match &saved_paths[index] {
+                    Some(saved_path) => retarget_asset_reference(&relative_path, saved_path),
+                    None => continue,
+                },
+            };
+            let new_str = format!(r#"{} = "{}""#, var_name, relative_path);
+            new_content = new_content.replace(&old_str, &new_str);
+        }
 
-    // --- Download Any CDN URLs in HTML (Generic CDN Detection) ---
-    // Detect any https:// URL that contains "cdn" in the domain
-    // Pattern matches: "https://anything-with-cdn-in-domain/path"
-    let generic_cdn_regex = regex::Regex::new(r#"["'](https://[^/"']*cdn[^/"']*[^"']*)["']"#)
-        .context("Failed to create generic CDN regex")?;
+        // --- Download Any CDN URLs in HTML (Generic CDN Detection) ---
+        // Detect any https:// URL that contains "cdn" in the domain
+        // Pattern matches: "https://anything-with-cdn-in-domain/path"
+        let generic_cdn_regex = regex::Regex::new(r#"["'](https://[^/"']*cdn[^/"']*[^"']*)["']"#)
+            .context("Failed to create generic CDN regex")?;
 
-    let mut generic_cdn_replacements = Vec::new();
+        let mut generic_cdn_replacements: Vec<(String, String, Option<usize>)> = Vec::new();
+        let mut downloads = AssetDownloads::default();
 
-    for cap in generic_cdn_regex.captures_iter(&new_content) {
-        if let Some(cdn_url_match) = cap.get(1) {
-            let cdn_url_str = cdn_url_match.as_str();
+        for cap in generic_cdn_regex.captures_iter(&new_content) {
+            if let Some(cdn_url_match) = cap.get(1) {
+                let cdn_url_str = cdn_url_match.as_str();
 
-            // Skip if this is a Google Fonts URL (already filtered)
-            if cdn_url_str.contains("fonts.googleapis.com")
-                || cdn_url_str.contains("fonts.gstatic.com")
-            {
-                continue;
-            }
+                // Skip if this is a Google Fonts URL (already filtered)
+                if cdn_url_str.contains("fonts.googleapis.com")
+                    || cdn_url_str.contains("fonts.gstatic.com")
+                {
+                    continue;
+                }
 
-            // Extract safe filename from URL
-            let file_name = extract_filename_from_url(cdn_url_str);
-
-            // Try to find or download the resource
-            let local_assets_dir = parent.join("assets");
-            fs::create_dir_all(&local_assets_dir)?;
-
-            let local_path = local_assets_dir.join(&file_name);
-            let relative_path = format!("{}{}", assets_rel_path, file_name);
-
-            // Download if needed
-            if !local_path.exists() {
-                println!(
-                    "   {} {}",
-                    "🌐 Descargando recurso CDN genérico:".cyan().dimmed(),
-                    file_name.dimmed()
-                );
-
-                match download_resource(cdn_url_str, &local_path) {
-                    Ok(_) => {
-                        // Create replacement with local path
-                        generic_cdn_replacements
-                            .push((cdn_url_str.to_string(), relative_path.clone()));
-                    }
-                    Err(e) => {
-                        log::warn!(
-                            "Failed to download generic CDN resource {}: {}",
-                            cdn_url_str,
-                            e
-                        );
-                        println!(
-                            "   {} {}",
-                            "⚠️  Error descargando:".yellow(),
-                            e.to_string().dimmed()
-                        );
+                // Extract safe filename from URL
+                let file_name = asset_file_name(cdn_url_str, options.keep_query_strings);
+
+                // Try to find or download the resource
+                let local_assets_dir = parent.join("assets");
+                fs::create_dir_all(&local_assets_dir)?;
+
+                let local_path = local_assets_dir.join(&file_name);
+                let mut relative_path = format!("{}{}", assets_rel_path, file_name);
+
+                // Download if needed (after the scan, see AssetDownloads)
+                let download = if let Some(saved_path) = options.saved_assets.existing(&local_path)
+                {
+                    // File already exists, just update the reference
+                    relative_path = retarget_asset_reference(&relative_path, &saved_path);
+                    None
+                } else {
+                    if is_blocked(
+                        cdn_url_str,
+                        &blacklist,
+                        "🚫 URL bloqueada (CDN):",
+                        &options.log,
+                        &tally,
+                    ) {
+                        continue;
                     }
-                }
-            } else {
-                // File already exists, just update the reference
-                generic_cdn_replacements.push((cdn_url_str.to_string(), relative_path));
+                    options.log.debug(format!(
+                        "   {} {}",
+                        adapt("🌐 Descargando recurso CDN genérico:")
+                            .cyan()
+                            .dimmed(),
+                        file_name.dimmed()
+                    ));
+                    Some(downloads.queue(cdn_url_str, &local_path))
+                };
+
+                generic_cdn_replacements.push((cdn_url_str.to_string(), relative_path, download));
             }
         }
-    }
 
-    // Apply generic CDN replacements
-    for (old_url, new_path) in generic_cdn_replacements {
-        new_content = new_content.replace(&old_url, &new_path);
+        // Apply generic CDN replacements once the downloads finished
+        let saved_paths = downloads.download_all(options, &tally);
+        for (old_url, new_path, download) in generic_cdn_replacements {
+            let new_path = match download {
+                None => new_path,
+                Some(index) => match &saved_paths[index] {
+                    Some(saved_path) => retarget_asset_reference(&new_path, saved_path),
+                    None => continue,
+                },
+            };
+            new_content = new_content.replace(&old_url, &new_path);
+        }
     }
 
     // --- Fix preventAutoplayForAVModal variable ---
     // Replace "preventAutoplayForAVModal = <anything>;" with "preventAutoplayForAVModal = true;"
-    let autoplay_regex = regex::Regex::new(r"preventAutoplayForAVModal\s*=\s*[^;]+;")
-        .context("Failed to create preventAutoplayForAVModal regex")?;
+    if profile.markup_fixes {
+        let autoplay_regex = regex::Regex::new(r"preventAutoplayForAVModal\s*=\s*[^;]+;")
+            .context("Failed to create preventAutoplayForAVModal regex")?;
+
+        if autoplay_regex.is_match(&new_content) {
+            options.log.debug(format!(
+                "   {}",
+                adapt("🎬 Ajustando preventAutoplayForAVModal = true...")
+                    .cyan()
+                    .dimmed()
+            ));
+            new_content = autoplay_regex
+                .replace_all(&new_content, "preventAutoplayForAVModal = true;")
+                .to_string();
+        }
 
-    if autoplay_regex.is_match(&new_content) {
-        println!(
-            "   {}",
-            "🎬 Ajustando preventAutoplayForAVModal = true..."
+        // --- Fix embedCode with improperly escaped quotes in iframes ---
+        // Pattern: "embedCode":"<iframe src="..." ...>"
+        // Should be: "embedCode":"<iframe src=\"...\" ...>"
+        let embed_code_regex =
+            regex::Regex::new(r#""embedCode"\s*:\s*"(<iframe[^>]*>(?:</iframe>)?)"#)
+                .context("Failed to create embedCode regex")?;
+
+        let mut embed_replacements = Vec::new();
+
+        for cap in embed_code_regex.captures_iter(&new_content) {
+            if let (Some(full_match), Some(iframe_content)) = (cap.get(0), cap.get(1)) {
+                let original_iframe = iframe_content.as_str();
+                let full_match_str = full_match.as_str();
+
+                // Escape all quotes inside the iframe HTML
+                // Replace " with \" and / with \/ for proper JSON escaping
+                let escaped_iframe = original_iframe
+                    .replace('\\', r"\\") // Escape backslashes first
+                    .replace('"', r#"\""#) // Escape double quotes
+                    .replace('/', r"\/"); // Escape forward slashes for JSON
+
+                // Create the corrected embedCode entry
+                let corrected = format!(r#""embedCode":"{}""#, escaped_iframe);
+
+                // Only add if it's different (to avoid unnecessary replacements)
+                if full_match_str != corrected {
+                    embed_replacements.push((full_match_str.to_string(), corrected));
+                }
+            }
+        }
+
+        if !embed_replacements.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🔧 Corrigiendo {} embedCode con comillas mal escapadas...",
+                    embed_replacements.len()
+                ))
                 .cyan()
                 .dimmed()
-        );
-        new_content = autoplay_regex
-            .replace_all(&new_content, "preventAutoplayForAVModal = true;")
-            .to_string();
-    }
-
-    // --- Fix embedCode with improperly escaped quotes in iframes ---
-    // Pattern: "embedCode":"<iframe src="..." ...>"
-    // Should be: "embedCode":"<iframe src=\"...\" ...>"
-    let embed_code_regex = regex::Regex::new(r#""embedCode"\s*:\s*"(<iframe[^>]*>(?:</iframe>)?)"#)
-        .context("Failed to create embedCode regex")?;
-
-    let mut embed_replacements = Vec::new();
-
-    for cap in embed_code_regex.captures_iter(&new_content) {
-        if let (Some(full_match), Some(iframe_content)) = (cap.get(0), cap.get(1)) {
-            let original_iframe = iframe_content.as_str();
-            let full_match_str = full_match.as_str();
-
-            // Escape all quotes inside the iframe HTML
-            // Replace " with \" and / with \/ for proper JSON escaping
-            let escaped_iframe = original_iframe
-                .replace('\\', r"\\") // Escape backslashes first
-                .replace('"', r#"\""#) // Escape double quotes
-                .replace('/', r"\/"); // Escape forward slashes for JSON
-
-            // Create the corrected embedCode entry
-            let corrected = format!(r#""embedCode":"{}""#, escaped_iframe);
-
-            // Only add if it's different (to avoid unnecessary replacements)
-            if full_match_str != corrected {
-                embed_replacements.push((full_match_str.to_string(), corrected));
+            ));
+            for (old, new) in embed_replacements {
+                new_content = new_content.replace(&old, &new);
             }
         }
-    }
 
-    if !embed_replacements.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "🔧 Corrigiendo {} embedCode con comillas mal escapadas...",
-                embed_replacements.len()
-            )
-            .cyan()
-            .dimmed()
-        );
-        for (old, new) in embed_replacements {
-            new_content = new_content.replace(&old, &new);
+        // --- Fix rel=nofollow without quotes ---
+        // Pattern: rel=nofollow (without quotes)
+        // Should be: rel="nofollow"
+        let rel_nofollow_regex = regex::Regex::new(r#"\brel=nofollow\b"#)
+            .context("Failed to create rel=nofollow regex")?;
+
+        if rel_nofollow_regex.is_match(&new_content) {
+            let count = rel_nofollow_regex.find_iter(&new_content).count();
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "🔗 Corrigiendo {} atributos rel=nofollow sin comillas...",
+                    count
+                ))
+                .cyan()
+                .dimmed()
+            ));
+            new_content = rel_nofollow_regex
+                .replace_all(&new_content, r#"rel="nofollow""#)
+                .to_string();
         }
     }
 
-    // --- Fix rel=nofollow without quotes ---
-    // Pattern: rel=nofollow (without quotes)
-    // Should be: rel="nofollow"
-    let rel_nofollow_regex =
-        regex::Regex::new(r#"\brel=nofollow\b"#).context("Failed to create rel=nofollow regex")?;
-
-    if rel_nofollow_regex.is_match(&new_content) {
-        let count = rel_nofollow_regex.find_iter(&new_content).count();
-        println!(
-            "   {}",
-            format!(
-                "🔗 Corrigiendo {} atributos rel=nofollow sin comillas...",
-                count
-            )
-            .cyan()
-            .dimmed()
-        );
-        new_content = rel_nofollow_regex
-            .replace_all(&new_content, r#"rel="nofollow""#)
-            .to_string();
-    }
-
     // --- Remove Google Tag Manager iframes ---
     // Pattern: <iframe src="https://www.googletagmanager.com/..." ...></iframe>
     // These are tracking iframes that are not needed for offline viewing
-    let gtm_iframe_regex = regex::Regex::new(r#"<iframe[^>]*src\s*=\s*["']https?://www\.googletagmanager\.com/[^"']*["'][^>]*>.*?</iframe>"#)
-        .context("Failed to create Google Tag Manager iframe regex")?;
+    if profile.removes(RemovalRule::Tracking) {
+        let gtm_iframe_regex = regex::Regex::new(r#"<iframe[^>]*src\s*=\s*["']https?://www\.googletagmanager\.com/[^"']*["'][^>]*>.*?</iframe>"#)
+            .context("Failed to create Google Tag Manager iframe regex")?;
 
-    let mut gtm_iframe_removals = Vec::new();
+        let mut gtm_iframe_removals = Vec::new();
 
-    for cap in gtm_iframe_regex.captures_iter(&new_content) {
-        if let Some(full_match) = cap.get(0) {
-            gtm_iframe_removals.push(full_match.as_str().to_string());
+        for cap in gtm_iframe_regex.captures_iter(&new_content) {
+            if let Some(full_match) = cap.get(0) {
+                gtm_iframe_removals.push(full_match.as_str().to_string());
+            }
         }
-    }
 
-    if !gtm_iframe_removals.is_empty() {
-        println!(
-            "   {}",
-            format!(
-                "📊 Eliminando {} iframes de Google Tag Manager...",
-                gtm_iframe_removals.len()
-            )
-            .yellow()
-            .dimmed()
-        );
-        for iframe in gtm_iframe_removals {
-            new_content = new_content.replace(&iframe, "");
+        if !gtm_iframe_removals.is_empty() {
+            options.log.debug(format!(
+                "   {}",
+                adapt(&format!(
+                    "📊 Eliminando {} iframes de Google Tag Manager...",
+                    gtm_iframe_removals.len()
+                ))
+                .yellow()
+                .dimmed()
+            ));
+            for iframe in gtm_iframe_removals {
+                new_content = new_content.replace(&iframe, "");
+            }
         }
     }
 
     fs::write(file_path, new_content)?;
+    options.log.info(tally.summary(file_path));
 
     Ok(())
 }
+
+/// A `ts_reader` image whose reference is set once its download finishes
+struct ReaderImage {
+    /// JSON pointer to the image inside the reader data
+    pointer: String,
+    reference: String,
+    download: usize,
+}
+
+/// Point an asset reference at the file actually saved by `download_resource`
+///
+/// The saved name can differ from the one derived from the URL when the
+/// extension was corrected from the downloaded content.
+fn retarget_asset_reference(reference: &str, saved_path: &Path) -> String {
+    let Some(saved_name) = saved_path.file_name().and_then(|name| name.to_str()) else {
+        return reference.to_string();
+    };
+
+    match reference.rfind('/') {
+        Some(pos) => format!("{}{}", &reference[..=pos], saved_name),
+        None => saved_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::wget::site_profile::{SiteProfiles, DEFAULT_PROFILE};
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    const PAGE: &str = r#"<html><head><title>Guide</title></head><body>
+<p>Content</p>
+<script>var redirectUrl = "https://example.com/buy";</script>
+</body></html>"#;
+
+    /// Post-process `PAGE` with the named built-in profile and return the result
+    fn process_with_profile(name: &str) -> String {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("index.html");
+        fs::write(&file, PAGE).unwrap();
+
+        let profiles = SiteProfiles::with_user_profiles(BTreeMap::new());
+        process_html_file_complete(
+            &file,
+            &dir.path().to_path_buf(),
+            &Url::parse("https://example.com/").unwrap(),
+            profiles.get(name).unwrap(),
+        )
+        .unwrap();
+        fs::read_to_string(&file).unwrap()
+    }
+
+    #[test]
+    fn test_profile_selects_script_removal() {
+        // The default profile strips redirect scripts
+        let default = process_with_profile(DEFAULT_PROFILE);
+        assert!(!default.contains("redirectUrl"));
+        assert!(default.contains("<p>Content</p>"));
+
+        // The docs profile only removes tracking and leaves scripts alone
+        let docs = process_with_profile("docs");
+        assert!(docs.contains("redirectUrl"));
+        assert!(docs.contains("<p>Content</p>"));
+    }
+
+    #[test]
+    fn test_saved_assets_finds_corrected_path() {
+        let dir = TempDir::new().unwrap();
+        let requested = dir.path().join("image.bin");
+        let saved = dir.path().join("image.png");
+        let assets = SavedAssets::default();
+
+        assert_eq!(assets.existing(&requested), None);
+
+        fs::write(&saved, b"\x89PNG\r\n\x1a\n").unwrap();
+        assets.record(&requested, &saved);
+        assert_eq!(assets.existing(&requested), Some(saved));
+
+        // A file at the requested path wins
+        fs::write(&requested, b"data").unwrap();
+        assert_eq!(assets.existing(&requested), Some(requested.clone()));
+    }
+}
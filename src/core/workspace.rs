@@ -1,7 +1,8 @@
 //! Workspace management module
 //!
 //! This module provides functionality for managing project workspaces,
-//! including mapping directories and listing registered workspaces.
+//! including mapping directories, listing registered workspaces and
+//! tagging them.
 //!
 //! # Examples
 //!
@@ -13,8 +14,8 @@
 //! println!("Mapped {} workspaces", count);
 //!
 //! let workspaces = manager.list_workspaces();
-//! for (name, path) in workspaces {
-//!     println!("{}: {}", name, path);
+//! for workspace in workspaces {
+//!     println!("{}: {}", workspace.name, workspace.path);
 //! }
 //! # Ok::<(), anyhow::Error>(())
 //! ```
@@ -25,11 +26,38 @@ use log::warn;
 use std::fs;
 use std::path::Path;
 
+/// A registered project folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    pub name: String,
+    pub path: String,
+    /// Lowercase tags, sorted
+    pub tags: Vec<String>,
+}
+
+impl Workspace {
+    /// Whether the workspace carries any of `tags` (case-insensitive)
+    pub fn has_any_tag(&self, tags: &[String]) -> bool {
+        tags.iter()
+            .any(|tag| self.tags.contains(&tag.trim().to_lowercase()))
+    }
+}
+
+/// Split a comma-separated tag list, normalized to trimmed lowercase
+pub fn parse_tags(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
 /// Workspace manager for handling project workspaces
 ///
 /// This struct manages the configuration of workspaces, allowing you to:
 /// - Map all directories in a work path as workspaces
-/// - List all registered workspaces
+/// - List all registered workspaces, optionally filtered by tag
+/// - Tag workspaces
 /// - Access the underlying configuration
 pub struct WorkspaceManager {
     config: Config,
@@ -95,20 +123,92 @@ impl WorkspaceManager {
         Ok(count)
     }
 
-    /// List all registered workspaces, sorted alphabetically
+    /// Find workspaces whose name matches `query`, sorted alphabetically
     ///
-    /// Returns a vector of (name, path) tuples
-    pub fn list_workspaces(&self) -> Vec<(String, String)> {
+    /// Matching is case-insensitive. An exact name match is returned alone;
+    /// otherwise names containing `query` win over names that only contain
+    /// its characters in order (`mpr` matches `my-project`).
+    pub fn find_workspaces(&self, query: &str) -> Vec<Workspace> {
+        let query = query.to_lowercase();
+        let workspaces = self.list_workspaces();
+
+        let exact: Vec<_> = workspaces
+            .iter()
+            .filter(|ws| ws.name.to_lowercase() == query)
+            .cloned()
+            .collect();
+        if !exact.is_empty() {
+            return exact;
+        }
+
+        let containing: Vec<_> = workspaces
+            .iter()
+            .filter(|ws| ws.name.to_lowercase().contains(&query))
+            .cloned()
+            .collect();
+        if !containing.is_empty() {
+            return containing;
+        }
+
+        workspaces
+            .into_iter()
+            .filter(|ws| is_subsequence(&query, &ws.name.to_lowercase()))
+            .collect()
+    }
+
+    /// List all registered workspaces with their tags, sorted alphabetically
+    pub fn list_workspaces(&self) -> Vec<Workspace> {
         let mut workspaces: Vec<_> = self
             .config
             .get_workspaces()
             .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
+            .map(|(name, path)| Workspace {
+                name: name.clone(),
+                path: path.clone(),
+                tags: self.config.get_workspace_tags(name).to_vec(),
+            })
             .collect();
 
-        workspaces.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
+        workspaces.sort_by_key(|ws| ws.name.to_lowercase());
         workspaces
     }
+
+    /// Workspaces carrying at least one of `tags`; all of them when `tags` is empty
+    pub fn list_workspaces_tagged(&self, tags: &[String]) -> Vec<Workspace> {
+        self.list_workspaces()
+            .into_iter()
+            .filter(|ws| tags.is_empty() || ws.has_any_tag(tags))
+            .collect()
+    }
+
+    /// Add `tags` to the workspace called `name`, returning its updated tags
+    ///
+    /// Call [`WorkspaceManager::save`] to persist the change.
+    pub fn add_tags(&mut self, name: &str, tags: &[String]) -> Result<Vec<String>> {
+        if !self.config.get_workspaces().contains_key(name) {
+            return Err(anyhow::anyhow!("Workspace '{}' not found", name));
+        }
+
+        let mut updated = self.config.get_workspace_tags(name).to_vec();
+        updated.extend(tags.iter().map(|tag| tag.trim().to_lowercase()));
+        updated.retain(|tag| !tag.is_empty());
+        updated.sort();
+        updated.dedup();
+
+        self.config
+            .set_workspace_tags(name.to_string(), updated.clone());
+        Ok(updated)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.config.save()
+    }
+}
+
+/// Whether every character of `needle` appears in `haystack`, in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
 }
 
 #[cfg(test)]
@@ -127,4 +227,54 @@ mod tests {
         let manager = WorkspaceManager::with_config(config);
         assert!(manager.list_workspaces().is_empty());
     }
+
+    fn manager_with(names: &[&str]) -> WorkspaceManager {
+        let mut config = Config::default();
+        for name in names {
+            config.add_workspace(name.to_string(), format!("/work/{}", name));
+        }
+        WorkspaceManager::with_config(config)
+    }
+
+    fn names(workspaces: Vec<Workspace>) -> Vec<String> {
+        workspaces.into_iter().map(|ws| ws.name).collect()
+    }
+
+    #[test]
+    fn test_find_workspaces_ranks_matches() {
+        let manager = manager_with(&["api", "api-gateway", "my-project", "web-app"]);
+        let found = |query: &str| names(manager.find_workspaces(query));
+
+        assert_eq!(found("API"), ["api"]);
+        assert_eq!(found("ap"), ["api", "api-gateway", "web-app"]);
+        assert_eq!(found("mpr"), ["my-project"]);
+        assert!(found("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_tags_are_normalized_and_filtered_with_or() {
+        let mut manager = manager_with(&["api", "cli-tool", "web-app"]);
+
+        assert_eq!(parse_tags(" Rust, CLI,,"), ["rust", "cli"]);
+        assert_eq!(
+            manager
+                .add_tags("cli-tool", &parse_tags("rust,cli"))
+                .unwrap(),
+            ["cli", "rust"]
+        );
+        assert_eq!(
+            manager
+                .add_tags("cli-tool", &parse_tags("RUST,tools"))
+                .unwrap(),
+            ["cli", "rust", "tools"]
+        );
+        manager.add_tags("web-app", &parse_tags("ts")).unwrap();
+        assert!(manager.add_tags("missing", &parse_tags("rust")).is_err());
+
+        let tagged = |tags: &str| names(manager.list_workspaces_tagged(&parse_tags(tags)));
+        assert_eq!(tagged("rust"), ["cli-tool"]);
+        assert_eq!(tagged("Rust,ts"), ["cli-tool", "web-app"]);
+        assert_eq!(tagged(""), ["api", "cli-tool", "web-app"]);
+        assert!(tagged("go").is_empty());
+    }
 }
@@ -4,7 +4,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::core::http::{self, HttpConfig};
 use crate::core::Config;
+use crate::ui::progress::read_with_progress;
 
 pub struct YtDlpManager {
     config: Config,
@@ -36,13 +38,14 @@ impl YtDlpManager {
     pub fn get_latest_version() -> Result<String> {
         println!("{}", "Consultando última versión de yt-dlp...".cyan());
 
-        let client = reqwest::blocking::Client::builder()
+        let config = HttpConfig::from_env();
+        let client = config
+            .builder()?
             .redirect(reqwest::redirect::Policy::none())
             .build()?;
 
-        let response = client
-            .get("https://github.com/yt-dlp/yt-dlp/releases/latest")
-            .send()?;
+        let response =
+            config.send(client.get("https://github.com/yt-dlp/yt-dlp/releases/latest"))?;
 
         // Extraer Location header
         if let Some(location) = response.headers().get("Location") {
@@ -73,8 +76,7 @@ impl YtDlpManager {
         println!("{}", "Descargando yt-dlp...".cyan());
         println!("{} {}", "URL:".dimmed(), download_url.dimmed());
 
-        let response =
-            reqwest::blocking::get(&download_url).context("Error al descargar yt-dlp")?;
+        let response = http::get(&download_url).context("Error al descargar yt-dlp")?;
 
         if !response.status().is_success() {
             return Err(anyhow!(
@@ -83,7 +85,8 @@ impl YtDlpManager {
             ));
         }
 
-        let bytes = response.bytes()?.to_vec();
+        let total = response.content_length().unwrap_or(0);
+        let bytes = read_with_progress(response, total, "Descargando:")?;
 
         println!("{} {} bytes", "Descargado:".green(), bytes.len());
 
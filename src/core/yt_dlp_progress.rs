@@ -0,0 +1,112 @@
+//! Progreso de descarga reportado por yt-dlp
+//!
+//! yt-dlp se ejecuta con `--newline --progress-template` usando
+//! [`PROGRESS_TEMPLATE`], de modo que cada actualización es una línea propia
+//! con campos separados por `|`. [`parse_progress_line`] las convierte en
+//! [`YtDlpProgress`]; cualquier otra línea se muestra tal cual.
+
+/// Marca que distingue nuestras líneas de progreso del resto de la salida
+const PROGRESS_MARKER: &str = "[msc-progress]";
+
+/// Valor de `--progress-template` (los campos que faltan salen como `NA`)
+pub const PROGRESS_TEMPLATE: &str = "download:[msc-progress] \
+    %(progress.downloaded_bytes)s|%(progress.total_bytes)s|\
+    %(progress.total_bytes_estimate)s|%(progress.speed)s|%(progress.eta)s|\
+    %(info.playlist_index)s|%(info.n_entries)s|%(info.id)s|%(info.format_id)s";
+
+/// Una actualización de progreso de yt-dlp
+#[derive(Debug, Clone, PartialEq)]
+pub struct YtDlpProgress {
+    pub downloaded: u64,
+    /// Tamaño total, exacto o estimado; `None` si yt-dlp no lo conoce
+    pub total: Option<u64>,
+    /// Bytes por segundo
+    pub speed: Option<f64>,
+    /// Segundos restantes
+    pub eta: Option<u64>,
+    /// Posición en la playlist y número de entradas
+    pub playlist_index: Option<usize>,
+    pub playlist_count: Option<usize>,
+    /// Video y formato que se descargan; cambia entre entradas de una playlist
+    /// y entre las pistas de video y audio de un mismo video
+    pub item: String,
+}
+
+impl YtDlpProgress {
+    /// Porcentaje completado en `0..=100` (0 si el total es desconocido)
+    pub fn percentage(&self) -> usize {
+        match self.total {
+            Some(total) if total > 0 => {
+                ((self.downloaded as f64 / total as f64) * 100.0).min(100.0) as usize
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Interpreta una línea de progreso; `None` si la línea es otra salida de yt-dlp
+/// o no tiene el formato esperado
+pub fn parse_progress_line(line: &str) -> Option<YtDlpProgress> {
+    let fields: Vec<&str> = line
+        .trim()
+        .strip_prefix(PROGRESS_MARKER)?
+        .trim()
+        .split('|')
+        .collect();
+    let [downloaded, total, estimate, speed, eta, index, count, id, format_id] = fields[..] else {
+        return None;
+    };
+
+    Some(YtDlpProgress {
+        downloaded: parse_number(downloaded)? as u64,
+        total: parse_number(total)
+            .or_else(|| parse_number(estimate))
+            .map(|bytes| bytes as u64),
+        speed: parse_number(speed),
+        eta: parse_number(eta).map(|secs| secs as u64),
+        playlist_index: index.parse().ok(),
+        playlist_count: count.parse().ok(),
+        item: format!("{}/{}", id, format_id),
+    })
+}
+
+/// Número de un campo de la plantilla (`NA` o `None` si no está disponible)
+fn parse_number(field: &str) -> Option<f64> {
+    field
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress_line() {
+        let progress = parse_progress_line(
+            "[msc-progress] 5242880|NA|20971520.5|1048576.0|15|2|10|dQw4w9WgXcQ|137",
+        )
+        .unwrap();
+
+        assert_eq!(progress.downloaded, 5_242_880);
+        assert_eq!(progress.total, Some(20_971_520));
+        assert_eq!(progress.percentage(), 25);
+        assert_eq!(progress.speed, Some(1_048_576.0));
+        assert_eq!(progress.eta, Some(15));
+        assert_eq!(progress.playlist_index, Some(2));
+        assert_eq!(progress.playlist_count, Some(10));
+        assert_eq!(progress.item, "dQw4w9WgXcQ/137");
+    }
+
+    #[test]
+    fn test_unknown_fields_and_other_output() {
+        let progress = parse_progress_line("[msc-progress] 1024|NA|NA|NA|NA|NA|NA|abc|18").unwrap();
+        assert_eq!(progress.total, None);
+        assert_eq!(progress.percentage(), 0);
+        assert_eq!(progress.playlist_index, None);
+
+        assert!(parse_progress_line("[download] Destination: video.mp4").is_none());
+        assert!(parse_progress_line("[msc-progress] NA|NA|NA").is_none());
+    }
+}
@@ -47,6 +47,34 @@ pub enum MscError {
     Tui(String),
 }
 
+/// A command failed after reporting the details itself
+///
+/// Handlers return this instead of calling `std::process::exit`, which would
+/// also end `msc shell`. `main` exits with `code` without printing anything
+/// more; the shell prints `message` and keeps reading commands.
+#[derive(Error, Debug)]
+#[error("{message}")]
+pub struct CommandFailed {
+    pub code: i32,
+    pub message: String,
+}
+
+impl CommandFailed {
+    pub fn new<S: Into<String>>(code: i32, message: S) -> Self {
+        CommandFailed {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Exit code requested by `error`, if it is a [`CommandFailed`]
+    pub fn exit_code(error: &anyhow::Error) -> Option<i32> {
+        error
+            .downcast_ref::<CommandFailed>()
+            .map(|failed| failed.code)
+    }
+}
+
 /// Result type alias for MSC application
 pub type Result<T> = std::result::Result<T, MscError>;
 
@@ -0,0 +1,147 @@
+// Branch and upstream tracking information
+use git2::{Branch, ErrorCode, Repository};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchInfo {
+    /// Branch name, or the short commit id when HEAD is detached
+    pub name: String,
+    pub detached: bool,
+    /// Upstream branch (e.g. `origin/main`), if one is configured
+    pub upstream: Option<String>,
+    /// Commits on the branch that are not on the upstream
+    pub ahead: usize,
+    /// Commits on the upstream that are not on the branch
+    pub behind: usize,
+}
+
+/// Current branch of the work tree containing `dir_path`, or None outside a work tree
+pub fn load_branch_info(dir_path: &Path) -> Option<BranchInfo> {
+    let repo = Repository::discover(dir_path).ok()?;
+    repo.workdir()?;
+
+    let head = match repo.head() {
+        Ok(head) => head,
+        // No commits yet: HEAD still names the branch to be created
+        Err(e) if e.code() == ErrorCode::UnbornBranch => {
+            let name = repo
+                .find_reference("HEAD")
+                .ok()?
+                .symbolic_target()?
+                .trim_start_matches("refs/heads/")
+                .to_string();
+            return Some(BranchInfo {
+                name,
+                detached: false,
+                upstream: None,
+                ahead: 0,
+                behind: 0,
+            });
+        }
+        Err(_) => return None,
+    };
+
+    if !head.is_branch() {
+        let oid = head.target()?.to_string();
+        return Some(BranchInfo {
+            name: oid[..7.min(oid.len())].to_string(),
+            detached: true,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+        });
+    }
+
+    let name = head.shorthand()?.to_string();
+    let local = head.target();
+    let upstream = Branch::wrap(head).upstream().ok();
+
+    let (upstream_name, ahead, behind) = match upstream {
+        Some(upstream) => {
+            let upstream_name = upstream.name().ok().flatten().map(str::to_string);
+            let (ahead, behind) = match (local, upstream.get().target()) {
+                (Some(local), Some(remote)) => {
+                    repo.graph_ahead_behind(local, remote).unwrap_or((0, 0))
+                }
+                _ => (0, 0),
+            };
+            (upstream_name, ahead, behind)
+        }
+        None => (None, 0, 0),
+    };
+
+    Some(BranchInfo {
+        name,
+        detached: false,
+        upstream: upstream_name,
+        ahead,
+        behind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Oid, Signature};
+
+    fn commit(repo: &Repository, message: &str) -> Oid {
+        let signature = Signature::now("msc", "msc@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_branch_info_outside_repo_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        // tempdir may sit inside a repository on some machines
+        if Repository::discover(dir.path()).is_err() {
+            assert_eq!(load_branch_info(dir.path()), None);
+        }
+    }
+
+    #[test]
+    fn test_branch_info_ahead_of_upstream() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let unborn = load_branch_info(dir.path()).unwrap();
+        assert_eq!(unborn.name, "main");
+        assert_eq!(unborn.upstream, None);
+
+        let base = commit(&repo, "base");
+        repo.remote("origin", "https://example.invalid/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", base, true, "fetch")
+            .unwrap();
+        repo.find_branch("main", git2::BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/main"))
+            .unwrap();
+        commit(&repo, "first");
+        commit(&repo, "second");
+
+        let info = load_branch_info(dir.path()).unwrap();
+        assert_eq!(info.name, "main");
+        assert!(!info.detached);
+        assert_eq!(info.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((info.ahead, info.behind), (2, 0));
+
+        repo.set_head_detached(base).unwrap();
+        let detached = load_branch_info(dir.path()).unwrap();
+        assert!(detached.detached);
+        assert_eq!(detached.name, base.to_string()[..7]);
+    }
+}
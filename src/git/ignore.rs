@@ -30,3 +30,23 @@ pub fn is_gitignored(gitignore: &Option<Gitignore>, path: &Path, is_dir: bool) -
         false
     }
 }
+
+/// Name of the msc-specific ignore file, read from the root of a scanned directory
+pub const MSCIGNORE_FILE: &str = ".mscignore";
+
+/// Load `.mscignore` from `root` (gitignore syntax, including `!` negation)
+///
+/// Patterns are relative to `root`. Returns None when the file is missing or unreadable.
+pub fn load_mscignore(root: &Path) -> Option<Gitignore> {
+    let mscignore_path = root.join(MSCIGNORE_FILE);
+    if !mscignore_path.is_file() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(root);
+    if let Some(e) = builder.add(&mscignore_path) {
+        log::warn!("Error reading {:?}: {}", mscignore_path, e);
+    }
+
+    builder.build().ok()
+}
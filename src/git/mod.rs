@@ -1,10 +1,12 @@
 // Git integration module
 
+pub mod branch;
 pub mod colors;
 pub mod ignore;
 pub mod status;
 
 // Re-export commonly used items
+pub use branch::{load_branch_info, BranchInfo};
 pub use colors::apply_git_colors;
-pub use ignore::{is_gitignored, load_gitignore};
-pub use status::{get_git_status_for_file, load_git_status, GitStatus};
+pub use ignore::{is_gitignored, load_gitignore, load_mscignore, MSCIGNORE_FILE};
+pub use status::{get_git_status_for_file, get_git_status_for_path, load_git_status, GitStatus};
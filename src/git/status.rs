@@ -13,6 +13,20 @@ pub enum GitStatus {
     Clean,     // No changes
 }
 
+impl GitStatus {
+    /// Lowercase name used in machine-readable output
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitStatus::Untracked => "untracked",
+            GitStatus::Modified => "modified",
+            GitStatus::Added => "added",
+            GitStatus::Deleted => "deleted",
+            GitStatus::Renamed => "renamed",
+            GitStatus::Clean => "clean",
+        }
+    }
+}
+
 pub fn load_git_status(dir_path: &Path) -> Option<HashMap<String, GitStatus>> {
     let repo = Repository::discover(dir_path).ok()?;
     let mut status_map = HashMap::new();
@@ -23,30 +37,44 @@ pub fn load_git_status(dir_path: &Path) -> Option<HashMap<String, GitStatus>> {
         let path = entry.path()?;
         let flags = entry.status();
 
-        let git_status = if flags.contains(Status::WT_DELETED)
-            || flags.contains(Status::INDEX_DELETED)
-        {
-            GitStatus::Deleted
-        } else if flags.contains(Status::WT_NEW) || flags.contains(Status::INDEX_NEW) {
-            if flags.contains(Status::INDEX_NEW) {
-                GitStatus::Added
-            } else {
-                GitStatus::Untracked
-            }
-        } else if flags.contains(Status::WT_MODIFIED) || flags.contains(Status::INDEX_MODIFIED) {
-            GitStatus::Modified
-        } else if flags.contains(Status::WT_RENAMED) || flags.contains(Status::INDEX_RENAMED) {
-            GitStatus::Renamed
-        } else {
-            GitStatus::Clean
-        };
-
-        status_map.insert(path.to_string(), git_status);
+        status_map.insert(path.to_string(), status_from_flags(flags));
     }
 
     Some(status_map)
 }
 
+fn status_from_flags(flags: Status) -> GitStatus {
+    if flags.contains(Status::WT_DELETED) || flags.contains(Status::INDEX_DELETED) {
+        GitStatus::Deleted
+    } else if flags.contains(Status::WT_NEW) || flags.contains(Status::INDEX_NEW) {
+        if flags.contains(Status::INDEX_NEW) {
+            GitStatus::Added
+        } else {
+            GitStatus::Untracked
+        }
+    } else if flags.contains(Status::WT_MODIFIED) || flags.contains(Status::INDEX_MODIFIED) {
+        GitStatus::Modified
+    } else if flags.contains(Status::WT_RENAMED) || flags.contains(Status::INDEX_RENAMED) {
+        GitStatus::Renamed
+    } else {
+        GitStatus::Clean
+    }
+}
+
+/// Get the git status of a single file, or None if it is not inside a repository
+pub fn get_git_status_for_path(file_path: &Path) -> Option<GitStatus> {
+    let absolute = file_path.canonicalize().ok()?;
+    let repo = Repository::discover(absolute.parent().unwrap_or(&absolute)).ok()?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let relative = absolute.strip_prefix(&workdir).ok()?;
+
+    match repo.status_file(relative) {
+        Ok(flags) => Some(status_from_flags(flags)),
+        // Directories and files unknown to the index are reported as clean
+        Err(_) => Some(GitStatus::Clean),
+    }
+}
+
 pub fn get_git_status_for_file(
     git_status_map: &Option<HashMap<String, GitStatus>>,
     file_path: &Path,
@@ -5,6 +5,7 @@ pub mod error;
 pub use error::{MscError, Result};
 
 // Module declarations
+pub mod cli;
 pub mod commands;
 pub mod core;
 pub mod git;
@@ -17,6 +18,9 @@ pub use core::config::Config;
 
 // Initialize logging
 pub fn init_logging() {
+    // Make sure log output containing glyphs is not garbled on Windows consoles
+    platform::enable_utf8_output();
+
     env_logger::Builder::from_default_env()
         .filter_level(log::LevelFilter::Info)
         .init();
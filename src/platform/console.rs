@@ -0,0 +1,23 @@
+// Platform-specific console setup
+
+/// UTF-8 code page identifier (`CP_UTF8`)
+#[cfg(windows)]
+const CP_UTF8: u32 = 65001;
+
+/// Switch the Windows console output code page to UTF-8
+///
+/// Without this, legacy consoles interpret the UTF-8 bytes of glyphs such as
+/// `✓` or `°` using the OEM code page and show mojibake (`âœ“`, `Â°C`).
+/// Returns `false` if the code page could not be changed (for example when
+/// no console is attached), so callers can fall back to ASCII output.
+#[cfg(windows)]
+pub fn enable_utf8_output() -> bool {
+    // SAFETY: SetConsoleOutputCP has no memory-safety preconditions
+    unsafe { winapi::um::wincon::SetConsoleOutputCP(CP_UTF8) != 0 }
+}
+
+/// Terminals on other platforms take their encoding from the locale
+#[cfg(not(windows))]
+pub fn enable_utf8_output() -> bool {
+    true
+}
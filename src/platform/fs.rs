@@ -17,3 +17,70 @@ pub fn is_hidden(entry: &std::fs::DirEntry) -> bool {
 pub fn is_hidden(_entry: &std::fs::DirEntry) -> bool {
     false
 }
+
+/// Resolve a Unix user id to its user name, falling back to the numeric id
+#[cfg(unix)]
+pub fn owner_name(uid: u32) -> String {
+    // SAFETY: getpwuid returns a pointer into static storage or null
+    unsafe {
+        let passwd = libc::getpwuid(uid);
+        if !passwd.is_null() && !(*passwd).pw_name.is_null() {
+            if let Ok(name) = std::ffi::CStr::from_ptr((*passwd).pw_name).to_str() {
+                return name.to_string();
+            }
+        }
+    }
+    uid.to_string()
+}
+
+/// Resolve a Unix group id to its group name, falling back to the numeric id
+#[cfg(unix)]
+pub fn group_name(gid: u32) -> String {
+    // SAFETY: getgrgid returns a pointer into static storage or null
+    unsafe {
+        let group = libc::getgrgid(gid);
+        if !group.is_null() && !(*group).gr_name.is_null() {
+            if let Ok(name) = std::ffi::CStr::from_ptr((*group).gr_name).to_str() {
+                return name.to_string();
+            }
+        }
+    }
+    gid.to_string()
+}
+
+/// Identity of the file behind `path`: (device, inode) on Unix, (volume
+/// serial, file index) on Windows
+///
+/// Hard links to one file share an identity. `None` when it can't be read.
+#[cfg(unix)]
+pub fn file_identity(_path: &std::path::Path, metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(windows)]
+pub fn file_identity(path: &std::path::Path, _metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION,
+    };
+
+    let file = std::fs::File::open(path).ok()?;
+    // SAFETY: `info` is plain data, the handle stays open while `file` lives
+    let (ok, info) = unsafe {
+        let mut info: BY_HANDLE_FILE_INFORMATION = std::mem::zeroed();
+        let ok = GetFileInformationByHandle(file.as_raw_handle() as _, &mut info);
+        (ok, info)
+    };
+    (ok != 0).then(|| {
+        (
+            u64::from(info.dwVolumeSerialNumber),
+            (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow),
+        )
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn file_identity(_path: &std::path::Path, _metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
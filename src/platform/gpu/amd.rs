@@ -153,6 +153,10 @@ impl GpuProvider for AmdGpuProvider {
                 power_limit_watts: power_limit,
                 clock_graphics_mhz: clock_graphics,
                 clock_memory_mhz: clock_memory,
+                pcie_generation: None,
+                pcie_lanes: None,
+                max_pcie_generation: None,
+                max_pcie_lanes: None,
             })
         }
         #[cfg(not(all(unix, feature = "rocm")))]
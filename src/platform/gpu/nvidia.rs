@@ -109,6 +109,11 @@ impl GpuProvider for NvidiaGpuProvider {
             let clock_graphics = device.clock_info(Clock::Graphics).ok();
             let clock_memory = device.clock_info(Clock::Memory).ok();
 
+            let pcie_generation = device.current_pcie_link_gen().ok();
+            let pcie_lanes = device.current_pcie_link_width().ok();
+            let max_pcie_generation = device.max_pcie_link_gen().ok();
+            let max_pcie_lanes = device.max_pcie_link_width().ok();
+
             Ok(GpuMetrics {
                 vendor: GpuVendor::Nvidia,
                 name,
@@ -122,6 +127,10 @@ impl GpuProvider for NvidiaGpuProvider {
                 power_limit_watts: power_limit,
                 clock_graphics_mhz: clock_graphics,
                 clock_memory_mhz: clock_memory,
+                pcie_generation,
+                pcie_lanes,
+                max_pcie_generation,
+                max_pcie_lanes,
             })
         }
         #[cfg(not(feature = "nvml"))]
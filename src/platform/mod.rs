@@ -1,8 +1,10 @@
 // Platform-specific code module
 
+pub mod console;
 pub mod elevation;
 pub mod fs;
 pub mod gpu;
+pub mod process;
 pub mod system;
 pub mod temp_dirs;
 
@@ -13,6 +15,10 @@ pub mod system_info_windows;
 pub mod nvidia_nvml;
 
 // Re-exports para imports limpios
+pub use console::enable_utf8_output;
 pub use elevation::{elevate_and_rerun, ensure_elevated, is_elevated, simulate_elevation_command};
-pub use fs::is_hidden;
+pub use fs::{file_identity, is_hidden};
+#[cfg(unix)]
+pub use fs::{group_name, owner_name};
+pub use process::kill_process;
 pub use temp_dirs::{get_default_temp_directories, get_recycle_bin_directory};
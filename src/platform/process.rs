@@ -0,0 +1,70 @@
+// Process control by PID
+
+use std::io;
+
+/// Ask a process to exit (`SIGTERM`), or kill it immediately (`SIGKILL`) when `force`
+///
+/// Windows has no graceful equivalent for arbitrary processes, so both modes
+/// use `TerminateProcess`. Errors keep the OS error kind, so callers can tell
+/// `PermissionDenied` apart from a process that already exited.
+#[cfg(unix)]
+pub fn kill_process(pid: u32, force: bool) -> io::Result<()> {
+    // 0 and negative values would signal whole process groups
+    let pid = libc::pid_t::try_from(pid)
+        .ok()
+        .filter(|pid| *pid > 0)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid PID"))?;
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+
+    // SAFETY: kill() only sends a signal; pid was checked to be a single process
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(windows)]
+pub fn kill_process(pid: u32, _force: bool) -> io::Result<()> {
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{OpenProcess, TerminateProcess};
+    use winapi::um::winnt::PROCESS_TERMINATE;
+
+    // SAFETY: the handle is checked for null and closed before returning
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let result = if TerminateProcess(handle, 1) == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        };
+        CloseHandle(handle);
+        result
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Command;
+
+    #[test]
+    fn test_kill_process_sends_requested_signal() {
+        for (force, signal) in [(false, libc::SIGTERM), (true, libc::SIGKILL)] {
+            let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+            kill_process(child.id(), force).unwrap();
+            assert_eq!(child.wait().unwrap().signal(), Some(signal));
+        }
+    }
+
+    #[test]
+    fn test_kill_process_rejects_group_pids() {
+        let err = kill_process(0, false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(kill_process(u32::MAX, false).is_err());
+    }
+}
@@ -33,6 +33,7 @@ pub fn get_network_info() -> Result<NetworkInfo> {
 
     let mut wifi_adapters = Vec::new();
     let mut ethernet_adapters = Vec::new();
+    let wlan_interfaces = get_wlan_interfaces();
 
     for adapter_json in adapter_array.iter() {
         let name = adapter_json["Name"].as_str().unwrap_or("").to_string();
@@ -62,6 +63,15 @@ pub fn get_network_info() -> Result<NetworkInfo> {
             // Get IP addresses and gateway latency
             let (ipv4, ipv6, gateway_latency) = get_adapter_network_details(&name);
 
+            // Connected network and signal, matched by adapter description
+            let connection = wlan_interfaces
+                .iter()
+                .find(|wlan| wlan.description.eq_ignore_ascii_case(&description));
+            let ssid = connection.and_then(|wlan| wlan.ssid.clone());
+            let signal_quality = connection
+                .filter(|wlan| wlan.ssid.is_some())
+                .and_then(|wlan| wlan.signal_quality);
+
             wifi_adapters.push(WifiAdapter {
                 name: description.clone(),
                 wifi_standard,
@@ -72,6 +82,9 @@ pub fn get_network_info() -> Result<NetworkInfo> {
                 ipv4_address: ipv4,
                 ipv6_address: ipv6,
                 gateway_latency_ms: gateway_latency,
+                ssid,
+                signal_quality_percent: signal_quality,
+                rssi_dbm: signal_quality.map(quality_to_rssi),
             });
         }
         // Detect Ethernet adapters
@@ -103,6 +116,7 @@ pub fn get_network_info() -> Result<NetworkInfo> {
         wifi_adapters,
         ethernet_adapters,
         bluetooth_adapters,
+        public_ip: None,
     })
 }
 
@@ -127,6 +141,65 @@ fn parse_link_speed(link_speed: Option<&str>) -> Option<u32> {
     })
 }
 
+/// Connection state of a wireless interface as reported by `netsh wlan`
+#[derive(Debug, Default, PartialEq)]
+struct WlanInterface {
+    description: String,
+    ssid: Option<String>,
+    signal_quality: Option<u8>,
+}
+
+/// Query SSID and signal quality of every wireless interface
+fn get_wlan_interfaces() -> Vec<WlanInterface> {
+    use std::process::Command;
+
+    Command::new("netsh")
+        .args(["wlan", "show", "interfaces"])
+        .output()
+        .map(|output| parse_wlan_interfaces(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse `netsh wlan show interfaces` output (English and Spanish labels)
+///
+/// Each interface block starts with its `Name` line; `SSID` is only present
+/// while the interface is connected.
+fn parse_wlan_interfaces(output: &str) -> Vec<WlanInterface> {
+    let mut interfaces: Vec<WlanInterface> = Vec::new();
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+
+        match key.as_str() {
+            "name" | "nombre" => interfaces.push(WlanInterface::default()),
+            _ => {
+                let Some(current) = interfaces.last_mut() else {
+                    continue;
+                };
+                match key.as_str() {
+                    "description" | "descripción" => current.description = value.to_string(),
+                    "ssid" if !value.is_empty() => current.ssid = Some(value.to_string()),
+                    "signal" | "señal" => {
+                        current.signal_quality = value.trim_end_matches('%').trim().parse().ok()
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    interfaces
+}
+
+/// Approximate RSSI from Windows signal quality, which maps -100..-50 dBm to 0..100%
+fn quality_to_rssi(quality: u8) -> i32 {
+    i32::from(quality.min(100)) / 2 - 100
+}
+
 /// Detect chipset manufacturer from adapter description
 fn detect_chipset_manufacturer(description: &str) -> Option<String> {
     let desc_lower = description.to_lowercase();
@@ -1,6 +1,11 @@
 use chrono::{DateTime, Local};
+use colored::{Color, Colorize};
 use std::fs;
+use std::io::IsTerminal;
 use std::time::SystemTime;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::glyphs::adapt;
 
 /// Format file size in human-readable format (B, KB, MB, GB)
 pub fn format_size(size: u64) -> String {
@@ -15,6 +20,35 @@ pub fn format_size(size: u64) -> String {
     }
 }
 
+/// Parse a human-readable size such as `500`, `64K`, `1.5MB` or `2GiB` into bytes
+///
+/// Units are case-insensitive and 1024-based, like [`format_size`]; a bare
+/// number is bytes.
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let value: f64 = number.parse().map_err(|_| {
+        format!(
+            "Invalid size '{}': expected a number like 512K or 2MB",
+            input
+        )
+    })?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        other => return Err(format!("Invalid size unit '{}' (use B, K, M or G)", other)),
+    };
+
+    Ok((value * multiplier as f64) as u64)
+}
+
 /// Format timestamp in human-readable format (YYYY-MM-DD HH:MM)
 pub fn format_time(time: SystemTime) -> String {
     let datetime: DateTime<Local> = time.into();
@@ -63,7 +97,12 @@ pub fn format_permissions(metadata: &fs::Metadata) -> String {
 pub fn format_permissions(metadata: &fs::Metadata) -> String {
     use std::os::unix::fs::PermissionsExt;
 
-    let mode = metadata.permissions().mode();
+    format_mode(metadata.permissions().mode())
+}
+
+/// Format Unix mode bits as an `rwxrwxrwx` string
+#[cfg(unix)]
+pub fn format_mode(mode: u32) -> String {
     let mut perms = String::new();
 
     // Owner permissions
@@ -83,3 +122,476 @@ pub fn format_permissions(metadata: &fs::Metadata) -> String {
 
     perms
 }
+
+/// Color for a single permission character (read=yellow, write=red, execute=green)
+///
+/// Returns None for characters that should be dimmed (e.g. '-').
+pub fn permission_char_color(c: char) -> Option<Color> {
+    match c {
+        'r' | 'R' => Some(Color::Yellow),
+        'w' => Some(Color::Red),
+        'x' => Some(Color::Green),
+        '-' => None,
+        // Windows attribute flags (H, S, D, A)
+        _ => Some(Color::Magenta),
+    }
+}
+
+/// Color-code a permission string produced by `format_permissions`
+pub fn colorize_permissions(perms: &str) -> String {
+    perms
+        .chars()
+        .map(|c| match permission_char_color(c) {
+            Some(color) => c.to_string().color(color).to_string(),
+            None => c.to_string().dimmed().to_string(),
+        })
+        .collect()
+}
+
+/// Horizontal alignment of a [`Table`] column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+/// One table cell: its plain text plus optional styling
+///
+/// Widths are measured on the plain text, so styling never shifts columns.
+pub struct Cell {
+    text: String,
+    style: CellStyle,
+}
+
+enum CellStyle {
+    /// Use the column color, if any
+    Inherit,
+    Color(Color),
+    Custom(Box<dyn Fn(&str) -> String>),
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: CellStyle::Inherit,
+        }
+    }
+
+    /// Override the column color for this cell
+    pub fn color(mut self, color: Color) -> Self {
+        self.style = CellStyle::Color(color);
+        self
+    }
+
+    /// Style the (possibly truncated) text with a custom function
+    pub fn styled<F>(mut self, style: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.style = CellStyle::Custom(Box::new(style));
+        self
+    }
+
+    fn paint(&self, text: &str, column_color: Option<Color>) -> String {
+        match &self.style {
+            CellStyle::Custom(style) => style(text),
+            CellStyle::Color(color) => text.color(*color).to_string(),
+            CellStyle::Inherit => match column_color {
+                Some(color) => text.color(color).to_string(),
+                None => text.to_string(),
+            },
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::new(text)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Cell::new(text)
+    }
+}
+
+struct Column {
+    header: String,
+    align: Align,
+    color: Option<Color>,
+    /// Never shrink below this width when fitting the table
+    min_width: usize,
+    max_width: Option<usize>,
+}
+
+/// Column-aligned table with width negotiation
+///
+/// Columns take the width of their widest cell. When the table is wider
+/// than [`Table::max_width`], the widest shrinkable column gives up one
+/// character at a time until it fits; cells that no longer fit are
+/// truncated with `…`.
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Cell>>,
+    max_width: Option<usize>,
+    indent: usize,
+    /// Bordered tables use ` │ ` between columns and a rule under the header
+    bordered: bool,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            columns: headers
+                .iter()
+                .map(|header| Column {
+                    header: header.to_string(),
+                    align: Align::Left,
+                    color: None,
+                    min_width: header.width(),
+                    max_width: None,
+                })
+                .collect(),
+            rows: Vec::new(),
+            max_width: None,
+            indent: 0,
+            bordered: true,
+        }
+    }
+
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        self.columns[column].align = align;
+        self
+    }
+
+    /// Default color for the cells of a column
+    pub fn color(mut self, column: usize, color: Color) -> Self {
+        self.columns[column].color = Some(color);
+        self
+    }
+
+    /// Lowest width a column may be shrunk to (defaults to the header width)
+    pub fn min_width(mut self, column: usize, width: usize) -> Self {
+        self.columns[column].min_width = width;
+        self
+    }
+
+    /// Cap a column's width regardless of the available space
+    pub fn max_column_width(mut self, column: usize, width: usize) -> Self {
+        self.columns[column].max_width = Some(width);
+        self
+    }
+
+    /// Total width the rendered table must fit in
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Fit the terminal width when stdout is a terminal (piped output is never truncated)
+    pub fn fit_terminal(self) -> Self {
+        if std::io::stdout().is_terminal() {
+            self.max_width(super::terminal::width())
+        } else {
+            self
+        }
+    }
+
+    /// Spaces before every line
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Separate columns with two spaces and omit the header rule
+    pub fn borderless(mut self) -> Self {
+        self.bordered = false;
+        self
+    }
+
+    /// Append a row; missing trailing cells are left empty
+    pub fn add_row<I, C>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cell>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    fn separator(&self) -> &'static str {
+        if self.bordered {
+            " │ "
+        } else {
+            "  "
+        }
+    }
+
+    /// Final width of every column after fitting [`Table::max_width`]
+    pub fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let natural = self
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.text.width())
+                    .fold(column.header.width(), usize::max);
+                match column.max_width {
+                    Some(max) => natural.min(max.max(column.min_width)),
+                    None => natural,
+                }
+            })
+            .collect();
+
+        let Some(limit) = self.max_width else {
+            return widths;
+        };
+
+        let chrome = self.indent + self.separator().width() * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + chrome > limit {
+            let widest = widths
+                .iter()
+                .enumerate()
+                .filter(|(i, width)| **width > self.columns[*i].min_width)
+                .max_by_key(|(i, width)| (**width, std::cmp::Reverse(*i)))
+                .map(|(i, _)| i);
+            match widest {
+                Some(i) => widths[i] -= 1,
+                None => break,
+            }
+        }
+
+        widths
+    }
+
+    /// Render the header and all rows, one line each
+    pub fn render(&self) -> String {
+        let widths = self.column_widths();
+        let indent = " ".repeat(self.indent);
+        let separator = adapt(self.separator());
+        let mut output = String::new();
+
+        let headers: Vec<String> = self
+            .columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| {
+                let text = truncate_to_width(&column.header, *width);
+                pad_cell(
+                    &text.white().bold().to_string(),
+                    text.width(),
+                    *width,
+                    column.align,
+                )
+            })
+            .collect();
+        output.push_str(&indent);
+        output.push_str(headers.join(separator.as_ref()).trim_end());
+        output.push('\n');
+
+        if self.bordered {
+            let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+            output.push_str(&indent);
+            output.push_str(&adapt(&rule.join("─┼─")));
+            output.push('\n');
+        }
+
+        for row in &self.rows {
+            let cells: Vec<String> = self
+                .columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (column, width))| match row.get(i) {
+                    Some(cell) => {
+                        let text = truncate_to_width(&cell.text, *width);
+                        let painted = cell.paint(&text, column.color);
+                        pad_cell(&painted, text.width(), *width, column.align)
+                    }
+                    None => " ".repeat(*width),
+                })
+                .collect();
+            output.push_str(&indent);
+            output.push_str(cells.join(separator.as_ref()).trim_end());
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Print the rendered table to stdout
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+fn pad_cell(painted: &str, text_width: usize, width: usize, align: Align) -> String {
+    let padding = " ".repeat(width.saturating_sub(text_width));
+    match align {
+        Align::Left => format!("{}{}", painted, padding),
+        Align::Right => format!("{}{}", padding, painted),
+    }
+}
+
+/// Cut `text` to at most `width` display columns, ending in `…` when shortened
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > width - 1 {
+            break;
+        }
+        out.push(ch);
+        used += ch_width;
+    }
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_format_mode() {
+        assert_eq!(format_mode(0o755), "rwxr-xr-x");
+        assert_eq!(format_mode(0o644), "rw-r--r--");
+        assert_eq!(format_mode(0o600), "rw-------");
+        assert_eq!(format_mode(0o000), "---------");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_colors_for_modes() {
+        let colors = |mode: u32| -> Vec<Option<Color>> {
+            format_mode(mode)
+                .chars()
+                .map(permission_char_color)
+                .collect()
+        };
+
+        let y = Some(Color::Yellow);
+        let r = Some(Color::Red);
+        let g = Some(Color::Green);
+
+        assert_eq!(colors(0o755), vec![y, r, g, y, None, g, y, None, g]);
+        assert_eq!(
+            colors(0o640),
+            vec![y, r, None, y, None, None, None, None, None]
+        );
+        assert_eq!(
+            colors(0o111),
+            vec![None, None, g, None, None, g, None, None, g]
+        );
+    }
+
+    #[test]
+    fn test_parse_size_units() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("64K"), Ok(64 * 1024));
+        assert_eq!(parse_size("1.5mb"), Ok(1536 * 1024));
+        assert_eq!(parse_size(" 2 GiB "), Ok(2 * 1024 * 1024 * 1024));
+        assert!(parse_size("fast").is_err());
+        assert!(parse_size("10 parsecs").is_err());
+    }
+
+    #[test]
+    fn test_colorize_permissions_preserves_text() {
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let colored = colorize_permissions("rwxr-xr--");
+        assert_eq!(ansi.replace_all(&colored, ""), "rwxr-xr--");
+    }
+
+    fn plain_lines(table: &Table) -> Vec<String> {
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        ansi.replace_all(&table.render(), "")
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn sample_table() -> Table {
+        let mut table = Table::new(&["Name", "Size"])
+            .align(1, Align::Right)
+            .borderless();
+        table.add_row(["Cargo.toml", "2.1KB"]);
+        table.add_row(["src", "-"]);
+        table.add_row(["a-rather-long-file-name.txt", "120.0MB"]);
+        table
+    }
+
+    #[test]
+    fn test_table_columns_take_widest_cell() {
+        assert_eq!(sample_table().column_widths(), vec![27, 7]);
+    }
+
+    #[test]
+    fn test_table_left_and_right_alignment() {
+        let lines = plain_lines(&sample_table());
+        assert_eq!(lines[0], format!("{:<27}  {:>7}", "Name", "Size"));
+        assert_eq!(lines[1], format!("{:<27}  {:>7}", "Cargo.toml", "2.1KB"));
+        assert_eq!(lines[2], format!("{:<27}  {:>7}", "src", "-"));
+        assert_eq!(lines[3], "a-rather-long-file-name.txt  120.0MB");
+    }
+
+    #[test]
+    fn test_table_shrinks_widest_column_to_fit() {
+        let table = sample_table().max_width(24);
+        // 24 - separator(2) - Size(7) leaves 15 for Name
+        assert_eq!(table.column_widths(), vec![15, 7]);
+        let lines = plain_lines(&table);
+        assert_eq!(lines[3], "a-rather-long-…  120.0MB");
+        assert!(lines.iter().all(|line| line.width() <= 24));
+    }
+
+    #[test]
+    fn test_table_respects_min_and_max_widths() {
+        // Name can't go below 20, so the table overflows instead of truncating further
+        let table = sample_table().min_width(0, 20).max_width(10);
+        assert_eq!(table.column_widths(), vec![20, 4]);
+
+        let capped = sample_table().max_column_width(0, 12);
+        assert_eq!(capped.column_widths(), vec![12, 7]);
+    }
+
+    #[test]
+    fn test_table_bordered_rule_and_missing_cells() {
+        let mut table = Table::new(&["A", "Bee"]);
+        table.add_row(["x"]);
+        let lines = plain_lines(&table);
+        assert_eq!(lines[0], "A │ Bee");
+        assert_eq!(lines[1], "──┼────");
+        assert_eq!(lines[2], "x │");
+    }
+
+    #[test]
+    fn test_truncate_to_width_counts_display_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("abcdef", 4), "abc…");
+        assert_eq!(truncate_to_width("日本語テキスト", 5), "日本…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+}
@@ -0,0 +1,176 @@
+//! Decorative glyphs with ASCII fallbacks
+//!
+//! Emoji, check marks and box-drawing characters render as mojibake on
+//! legacy consoles. Decorative text is passed through [`adapt`], which
+//! leaves it untouched normally and substitutes the ASCII equivalents from
+//! [`GLYPH_TABLE`] in ASCII mode (`--ascii`, or auto-detected through
+//! [`super::terminal`]).
+//!
+//! Only decorative literals should be adapted; user data such as file
+//! names is printed as-is.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::terminal;
+
+/// Unicode glyphs and their ASCII replacements
+///
+/// Pictographs not listed here (most emoji) are dropped in ASCII mode, since
+/// the text that follows them already carries the meaning.
+pub const GLYPH_TABLE: &[(char, &str)] = &[
+    // Status marks
+    ('✓', "[OK]"),
+    ('✔', "[OK]"),
+    ('✗', "[X]"),
+    ('✘', "[X]"),
+    ('⚠', "[!]"),
+    ('🚫', "[X]"),
+    ('❌', "[X]"),
+    ('✅', "[OK]"),
+    ('ℹ', "[i]"),
+    // Arrows and bullets
+    ('→', "->"),
+    ('←', "<-"),
+    ('↑', "Up"),
+    ('↓', "Down"),
+    ('•', "*"),
+    ('●', "*"),
+    ('○', "o"),
+    ('⚡', "~"),
+    // Units
+    ('°', ""),
+    // Box drawing and bars
+    ('━', "="),
+    ('═', "="),
+    ('─', "-"),
+    ('│', "|"),
+    ('┃', "|"),
+    ('║', "|"),
+    ('┌', "+"),
+    ('┐', "+"),
+    ('└', "+"),
+    ('┘', "+"),
+    ('├', "+"),
+    ('┤', "+"),
+    ('┬', "+"),
+    ('┴', "+"),
+    ('┼', "+"),
+    ('█', "#"),
+    ('▓', "#"),
+    ('▒', "."),
+    ('░', "."),
+];
+
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enable ASCII mode when forced (`--ascii`) or when the terminal lacks Unicode support
+pub fn init(force_ascii: bool) {
+    set_ascii_mode(force_ascii || !terminal::capabilities().unicode);
+}
+
+/// Explicitly enable or disable ASCII mode
+pub fn set_ascii_mode(enabled: bool) {
+    ASCII_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether decorative glyphs are currently replaced with ASCII
+pub fn ascii_mode() -> bool {
+    ASCII_MODE.load(Ordering::Relaxed)
+}
+
+/// Adapt decorative text to the current output mode
+pub fn adapt(text: &str) -> Cow<'_, str> {
+    if ascii_mode() {
+        asciify(text)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Replace decorative glyphs with their ASCII equivalents
+///
+/// Non-decorative characters (including accented letters) are kept.
+pub fn asciify(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_decorative) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if let Some((_, replacement)) = GLYPH_TABLE.iter().find(|(glyph, _)| *glyph == c) {
+            output.push_str(replacement);
+        } else if is_joiner(c) {
+            // Variation selectors and zero-width joiners are dropped silently
+        } else if is_decorative(c) {
+            // Unknown pictograph: drop it together with its padding
+            while chars.peek().is_some_and(|&next| is_joiner(next)) {
+                chars.next();
+            }
+            while chars.peek() == Some(&' ') {
+                chars.next();
+            }
+        } else {
+            output.push(c);
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+fn is_joiner(c: char) -> bool {
+    matches!(c, '\u{FE0E}' | '\u{FE0F}' | '\u{200D}')
+}
+
+/// Characters that are decoration rather than text
+fn is_decorative(c: char) -> bool {
+    matches!(c,
+        '°' | '•' | 'ℹ'
+        // Arrows, symbols, box drawing, block elements, dingbats
+        | '\u{2190}'..='\u{2BFF}'
+        // Emoji and pictographs
+        | '\u{1F000}'..='\u{1FAFF}')
+        || is_joiner(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_glyphs_are_replaced() {
+        assert_eq!(asciify("✓ Done"), "[OK] Done");
+        assert_eq!(asciify("✗ Failed"), "[X] Failed");
+        assert_eq!(asciify("⚠️  Warning: x"), "[!]  Warning: x");
+        assert_eq!(asciify("Use ↑/↓ arrows"), "Use Up/Down arrows");
+        assert_eq!(asciify("45°C"), "45C");
+        assert_eq!(asciify("███░░"), "###..");
+    }
+
+    #[test]
+    fn test_unknown_emoji_are_dropped() {
+        assert_eq!(asciify("🛡️  Blacklist cargada:"), "Blacklist cargada:");
+        assert_eq!(asciify("   🍪 Detectado script"), "   Detectado script");
+    }
+
+    #[test]
+    fn test_text_is_preserved() {
+        assert_eq!(asciify("plain text"), "plain text");
+        assert!(matches!(asciify("plain text"), Cow::Borrowed(_)));
+        // Accented letters are text, not decoration
+        assert_eq!(
+            asciify("🔧 Corrigiendo redirección"),
+            "Corrigiendo redirección"
+        );
+    }
+
+    #[test]
+    fn test_table_replacements_are_ascii() {
+        for (glyph, replacement) in GLYPH_TABLE {
+            assert!(replacement.is_ascii(), "{}", glyph);
+            assert!(is_decorative(*glyph), "{} should be decorative", glyph);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Ctrl+C handling for long-running commands
+//!
+//! `ctrlc` accepts only one handler per process, but `msc shell` runs many
+//! commands in the same process. A single handler is installed on first use
+//! and sets a shared cancel flag; every command that wants to stop on Ctrl+C
+//! takes the flag through [`cancel_flag`], which resets it first.
+
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+type CancelHook = Box<dyn Fn() + Send>;
+
+static HANDLER: OnceLock<std::result::Result<Arc<AtomicBool>, String>> = OnceLock::new();
+static ON_CANCEL: Mutex<Option<CancelHook>> = Mutex::new(None);
+
+/// Cancel flag for the command about to run, set when Ctrl+C is pressed
+pub fn cancel_flag() -> Result<Arc<AtomicBool>> {
+    install(None)
+}
+
+/// Like [`cancel_flag`], also running `on_cancel` when Ctrl+C is pressed
+///
+/// The hook replaces the one from the previous command.
+pub fn cancel_flag_with(on_cancel: impl Fn() + Send + 'static) -> Result<Arc<AtomicBool>> {
+    install(Some(Box::new(on_cancel)))
+}
+
+fn install(on_cancel: Option<CancelHook>) -> Result<Arc<AtomicBool>> {
+    let flag = HANDLER
+        .get_or_init(|| {
+            let flag = Arc::new(AtomicBool::new(false));
+            let handler_flag = Arc::clone(&flag);
+            ctrlc::set_handler(move || {
+                if let Some(hook) = ON_CANCEL.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+                    hook();
+                }
+                handler_flag.store(true, Ordering::Relaxed);
+            })
+            .map(|_| flag)
+            .map_err(|e| e.to_string())
+        })
+        .as_ref()
+        .map_err(|e| anyhow!("Failed to set Ctrl+C handler: {}", e))?;
+
+    *ON_CANCEL.lock().unwrap_or_else(|e| e.into_inner()) = on_cancel;
+    flag.store(false, Ordering::Relaxed);
+    Ok(Arc::clone(flag))
+}
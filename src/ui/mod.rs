@@ -1,15 +1,26 @@
 // UI and formatting module
 
 pub mod formatters;
+pub mod glyphs;
+pub mod interrupt;
 pub mod monitor_tui;
+pub mod output;
 pub mod progress;
 pub mod prompts;
 pub mod system_formatters;
+pub mod terminal;
+pub mod verbosity;
 
 // Re-export commonly used items for cleaner imports
-pub use formatters::{format_permissions, format_size, format_time};
-pub use progress::{clear_line, show_progress_bar};
+pub use formatters::{
+    colorize_permissions, format_permissions, format_size, format_time, parse_size, Align, Cell,
+    Table,
+};
+pub use progress::{
+    clear_line, show_progress_bar, CliProgressBar, ProgressState, ProgressTracker, ProgressUnit,
+};
 pub use prompts::{
-    bold, confirm, dimmed, error, info, read_confirmation, read_exact_confirmation,
-    select_from_list, success, warn,
+    bold, confirm, dimmed, error, info, read_confirmation, read_confirmation_cancellable,
+    read_exact_confirmation, select_from_list, success, warn, Confirmation,
 };
+pub use verbosity::Verbosity;
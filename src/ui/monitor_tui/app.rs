@@ -1,4 +1,5 @@
 use std::io;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -11,11 +12,15 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 use crate::core::system_monitor::{
-    evaluate_alerts, Alert, AlertConfig, MetricsHistory, MetricsRuntime, SystemMetrics,
+    build_process_tree, evaluate_alerts, flatten_tree, sort_process_tree, sort_processes, Alert,
+    AlertConfig, CsvLogger, FlattenedProcess, MetricsHistory, MetricsRuntime, ProcessMetrics,
+    ProcessSortKey, SystemMetrics,
 };
+use crate::platform::kill_process;
 
 use super::event_handler::MonitorEvent;
 use super::render::render_ui;
+use crate::ui::terminal::capabilities;
 
 /// Monitor application state
 pub struct MonitorApp {
@@ -25,7 +30,8 @@ pub struct MonitorApp {
     pub should_quit: bool,
     pub show_help: bool,
     pub selected_tab: usize,
-    pub process_sort_by_memory: bool,
+    /// Kept across refreshes; snapshots always arrive sorted by CPU
+    pub sort_key: ProcessSortKey,
     pub interval_ms: u64,
     pub show_process_tree: bool,
     pub selected_process_index: usize,
@@ -36,10 +42,56 @@ pub struct MonitorApp {
     pub smoothed_memory_usage: f32,
     pub smoothed_gpu_usage: f32,
     pub smoothed_per_core: Vec<f32>,
+    /// Appends every new snapshot when `--log-csv` is given
+    pub csv_log: Option<CsvLogger>,
+    /// Kill waiting for y/n confirmation
+    pub pending_kill: Option<PendingKill>,
+    /// Result of the last action, shown in the footer for a few seconds
+    pub status: Option<StatusMessage>,
+}
+
+/// Process the user asked to kill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingKill {
+    pub pid: u32,
+    pub name: String,
+    pub force: bool,
+}
+
+/// Footer message reporting the outcome of an action
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub is_error: bool,
+    pub shown_at: Instant,
+}
+
+impl StatusMessage {
+    const DURATION: Duration = Duration::from_secs(5);
+
+    fn new(text: String, is_error: bool) -> Self {
+        Self {
+            text,
+            is_error,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.shown_at.elapsed() >= Self::DURATION
+    }
 }
 
 impl MonitorApp {
     pub fn new(config: MonitorAppConfig) -> Result<Self> {
+        let csv_log = config
+            .log_csv
+            .as_deref()
+            .map(|path| {
+                CsvLogger::open(path)
+                    .with_context(|| format!("Failed to open CSV log {}", path.display()))
+            })
+            .transpose()?;
         let runtime = MetricsRuntime::new()?;
 
         Ok(Self {
@@ -49,7 +101,7 @@ impl MonitorApp {
             should_quit: false,
             show_help: false,
             selected_tab: 0,
-            process_sort_by_memory: false,
+            sort_key: ProcessSortKey::default(),
             interval_ms: config.interval_ms,
             show_process_tree: true, // Default to tree view
             selected_process_index: 0,
@@ -59,6 +111,9 @@ impl MonitorApp {
             smoothed_memory_usage: 0.0,
             smoothed_gpu_usage: 0.0,
             smoothed_per_core: Vec::new(),
+            csv_log,
+            pending_kill: None,
+            status: None,
         })
     }
 
@@ -118,11 +173,81 @@ impl MonitorApp {
             // Evaluate alerts
             self.alerts = evaluate_alerts(&self.metrics, &self.alert_config);
 
+            if let Some(logger) = self.csv_log.as_mut() {
+                if let Err(e) = logger.append(&self.metrics) {
+                    log::warn!("Stopping CSV logging: {}", e);
+                    self.csv_log = None;
+                }
+            }
+
             return true;
         }
         false
     }
 
+    /// Top processes ordered by the selected column
+    pub fn sorted_processes(&self) -> Vec<ProcessMetrics> {
+        let mut processes = self.metrics.top_processes.clone();
+        sort_processes(&mut processes, self.sort_key);
+        processes
+    }
+
+    /// Top processes as a tree, siblings ordered by the selected column
+    pub fn sorted_process_tree(&self) -> Vec<FlattenedProcess> {
+        let mut tree = build_process_tree(&self.metrics.top_processes);
+        sort_process_tree(&mut tree, self.sort_key);
+        flatten_tree(&tree)
+    }
+
+    /// PIDs in the order the process table shows them
+    pub fn displayed_pids(&self) -> Vec<u32> {
+        if self.show_process_tree {
+            self.sorted_process_tree()
+                .iter()
+                .map(|flat| flat.process.pid)
+                .collect()
+        } else {
+            self.sorted_processes().iter().map(|p| p.pid).collect()
+        }
+    }
+
+    /// PID of the highlighted process row
+    pub fn selected_pid(&self) -> Option<u32> {
+        self.displayed_pids()
+            .get(self.selected_process_index)
+            .copied()
+    }
+
+    /// Send the confirmed signal and report the outcome in the footer
+    fn kill_pending(&mut self) {
+        let Some(kill) = self.pending_kill.take() else {
+            return;
+        };
+        let action = if kill.force {
+            "Killed"
+        } else {
+            "Sent SIGTERM to"
+        };
+
+        self.status = Some(match kill_process(kill.pid, kill.force) {
+            Ok(()) => StatusMessage::new(
+                format!("{} {} (PID {})", action, kill.name, kill.pid),
+                false,
+            ),
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => StatusMessage::new(
+                format!(
+                    "Permission denied: cannot kill {} (PID {}); run as administrator/root",
+                    kill.name, kill.pid
+                ),
+                true,
+            ),
+            Err(e) => StatusMessage::new(
+                format!("Failed to kill {} (PID {}): {}", kill.name, kill.pid, e),
+                true,
+            ),
+        });
+    }
+
     /// Smooth a value using Exponential Moving Average
     /// Alpha controls smoothing: 0.0 = no change, 1.0 = instant change
     /// Lower alpha = smoother but slower response
@@ -149,9 +274,8 @@ impl MonitorApp {
                     self.selected_tab - 1
                 };
             }
-            MonitorEvent::ToggleProcessSort => {
-                self.process_sort_by_memory = !self.process_sort_by_memory;
-            }
+            MonitorEvent::ToggleProcessSort => self.sort_key = self.sort_key.next(),
+            MonitorEvent::SortProcesses(key) => self.sort_key = key,
             MonitorEvent::ToggleProcessTree => {
                 self.show_process_tree = !self.show_process_tree;
                 self.selected_process_index = 0; // Reset selection
@@ -167,6 +291,25 @@ impl MonitorApp {
                     self.selected_process_index += 1;
                 }
             }
+            MonitorEvent::KillProcess { pid, force } => {
+                if pid == std::process::id() {
+                    self.status = Some(StatusMessage::new(
+                        "Refusing to kill the monitor itself".to_string(),
+                        true,
+                    ));
+                    return;
+                }
+                let name = self
+                    .metrics
+                    .top_processes
+                    .iter()
+                    .find(|p| p.pid == pid)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| "process".to_string());
+                self.pending_kill = Some(PendingKill { pid, name, force });
+            }
+            MonitorEvent::ConfirmKill => self.kill_pending(),
+            MonitorEvent::CancelKill => self.pending_kill = None,
             MonitorEvent::None => {}
         }
     }
@@ -184,6 +327,8 @@ pub struct MonitorAppConfig {
     pub show_temperatures: bool,
     pub show_processes: bool,
     pub top_processes: usize,
+    /// CSV file that receives one row per snapshot
+    pub log_csv: Option<PathBuf>,
 }
 
 impl Default for MonitorAppConfig {
@@ -198,6 +343,7 @@ impl Default for MonitorAppConfig {
             show_temperatures: true,
             show_processes: true,
             top_processes: 10,
+            log_csv: None,
         }
     }
 }
@@ -205,6 +351,11 @@ impl Default for MonitorAppConfig {
 /// Run the monitor TUI application
 pub fn run_monitor_app(config: MonitorAppConfig) -> Result<()> {
     // Setup terminal
+    if !capabilities().is_interactive() {
+        anyhow::bail!(
+            "The monitor requires an interactive terminal (stdin and stdout must not be redirected)"
+        );
+    }
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
@@ -232,16 +383,38 @@ pub fn run_monitor_app(config: MonitorAppConfig) -> Result<()> {
         if event::poll(Duration::from_millis(1)).context("Event poll failed")? {
             if let Event::Key(key) = event::read().context("Event read failed")? {
                 if key.kind == KeyEventKind::Press {
-                    let monitor_event = match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => MonitorEvent::Quit,
-                        KeyCode::Char('?') | KeyCode::Char('h') => MonitorEvent::ToggleHelp,
-                        KeyCode::Tab => MonitorEvent::NextTab,
-                        KeyCode::BackTab => MonitorEvent::PrevTab,
-                        KeyCode::Char('s') => MonitorEvent::ToggleProcessSort,
-                        KeyCode::Char('t') => MonitorEvent::ToggleProcessTree,
-                        KeyCode::Up | KeyCode::Char('k') => MonitorEvent::ProcessUp,
-                        KeyCode::Down | KeyCode::Char('j') => MonitorEvent::ProcessDown,
-                        _ => MonitorEvent::None,
+                    let monitor_event = if app.pending_kill.is_some() {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                MonitorEvent::ConfirmKill
+                            }
+                            _ => MonitorEvent::CancelKill,
+                        }
+                    } else {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => MonitorEvent::Quit,
+                            KeyCode::Char('?') | KeyCode::Char('h') => MonitorEvent::ToggleHelp,
+                            KeyCode::Tab => MonitorEvent::NextTab,
+                            KeyCode::BackTab => MonitorEvent::PrevTab,
+                            KeyCode::Char('s') => MonitorEvent::ToggleProcessSort,
+                            KeyCode::Char('t') => MonitorEvent::ToggleProcessTree,
+                            KeyCode::Char('c') => MonitorEvent::SortProcesses(ProcessSortKey::Cpu),
+                            KeyCode::Char('m') => {
+                                MonitorEvent::SortProcesses(ProcessSortKey::Memory)
+                            }
+                            KeyCode::Char('p') => MonitorEvent::SortProcesses(ProcessSortKey::Pid),
+                            KeyCode::Char('n') => MonitorEvent::SortProcesses(ProcessSortKey::Name),
+                            KeyCode::Up => MonitorEvent::ProcessUp,
+                            KeyCode::Down | KeyCode::Char('j') => MonitorEvent::ProcessDown,
+                            KeyCode::Char(c @ ('k' | 'K')) => app
+                                .selected_pid()
+                                .map(|pid| MonitorEvent::KillProcess {
+                                    pid,
+                                    force: c == 'K',
+                                })
+                                .unwrap_or(MonitorEvent::None),
+                            _ => MonitorEvent::None,
+                        }
                     };
                     app.handle_event(monitor_event);
                 }
@@ -1,3 +1,5 @@
+use crate::core::system_monitor::ProcessSortKey;
+
 /// Events that can occur in the monitor TUI
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MonitorEvent {
@@ -9,14 +11,22 @@ pub enum MonitorEvent {
     NextTab,
     /// Switch to previous tab/section
     PrevTab,
-    /// Toggle process sort mode
+    /// Cycle through the process sort columns
     ToggleProcessSort,
+    /// Sort the process table by a specific column
+    SortProcesses(ProcessSortKey),
     /// Toggle process tree view
     ToggleProcessTree,
     /// Navigate process list up
     ProcessUp,
     /// Navigate process list down
     ProcessDown,
+    /// Ask to kill the selected process (`force`: SIGKILL instead of SIGTERM)
+    KillProcess { pid: u32, force: bool },
+    /// Confirm the pending kill
+    ConfirmKill,
+    /// Dismiss the pending kill
+    CancelKill,
     /// No action
     None,
 }
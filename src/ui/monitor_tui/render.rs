@@ -22,7 +22,8 @@ fn format_size(bytes: u64) -> String {
 
 use super::app::MonitorApp;
 use super::widgets::{colored_gauge, temp_color};
-use crate::core::system_monitor::{DiskType, SmartStatus};
+use crate::core::system_monitor::{DiskType, SmartStatus, Subsystem};
+use crate::ui::system_formatters::format_pcie_link;
 
 /// Main render function
 pub fn render_ui(frame: &mut Frame, app: &MonitorApp) {
@@ -75,7 +76,7 @@ pub fn render_ui(frame: &mut Frame, app: &MonitorApp) {
         render_network_disk_section(frame, chunks[4], app);
         render_processes_section(frame, chunks[5], app);
         render_temperatures_section(frame, chunks[6], app);
-        render_footer(frame, chunks[7]);
+        render_footer(frame, chunks[7], app);
     } else {
         render_global_dashboard(frame, chunks[0], app);
         render_cpu_section(frame, chunks[1], app);
@@ -83,7 +84,7 @@ pub fn render_ui(frame: &mut Frame, app: &MonitorApp) {
         render_network_disk_section(frame, chunks[3], app);
         render_processes_section(frame, chunks[4], app);
         render_temperatures_section(frame, chunks[5], app);
-        render_footer(frame, chunks[6]);
+        render_footer(frame, chunks[6], app);
     }
 
     // Render help overlay if active
@@ -483,7 +484,16 @@ fn render_memory_gpu_section(frame: &mut Frame, area: Rect, app: &MonitorApp) {
                 ])
                 .split(gpu_inner);
 
-            let name = Paragraph::new(gpu.name.clone()).style(Style::default().fg(Color::Cyan));
+            // A negotiated link below the maximum flags an idle card or a slow slot
+            let pcie_link = format_pcie_link(
+                gpu.max_pcie_generation.zip(gpu.max_pcie_lanes),
+                gpu.pcie_generation.zip(gpu.pcie_lanes),
+            );
+            let name_text = match pcie_link {
+                Some(link) => format!("{} │ PCIe {}", gpu.name, link),
+                None => gpu.name.clone(),
+            };
+            let name = Paragraph::new(name_text).style(Style::default().fg(Color::Cyan));
             frame.render_widget(name, gpu_layout[0]);
 
             // Use smoothed GPU usage if available
@@ -516,11 +526,22 @@ fn render_memory_gpu_section(frame: &mut Frame, area: Rect, app: &MonitorApp) {
                     .map(|p| p.to_string())
                     .unwrap_or("N/A".into()),
             );
-            let details_para = Paragraph::new(details).style(Style::default().fg(Color::White));
+            // Highlight the temperature with the same thresholds as the GPU alerts
+            let details_color = match gpu.temperature_celsius.map(|t| t as f32) {
+                Some(t) if t >= app.alert_config.gpu_temp_critical => Color::Red,
+                Some(t) if t >= app.alert_config.gpu_temp_warning => Color::Yellow,
+                _ => Color::White,
+            };
+            let details_para = Paragraph::new(details).style(Style::default().fg(details_color));
             frame.render_widget(details_para, gpu_layout[2]);
         }
     } else {
-        let no_gpu = Paragraph::new("No GPU detected").style(Style::default().fg(Color::DarkGray));
+        // Say why when the GPU task reported an error
+        let text = match app.runtime.unavailable_reason(Subsystem::Gpu) {
+            Some(reason) => format!("GPU: unavailable ({})", reason),
+            None => "No GPU detected".to_string(),
+        };
+        let no_gpu = Paragraph::new(text).style(Style::default().fg(Color::DarkGray));
         frame.render_widget(no_gpu, gpu_inner);
     }
 }
@@ -748,7 +769,7 @@ fn render_network_disk_section(frame: &mut Frame, area: Rect, app: &MonitorApp)
 }
 
 fn render_processes_section(frame: &mut Frame, area: Rect, app: &MonitorApp) {
-    use crate::core::system_monitor::{build_process_tree, flatten_tree, format_tree_indent};
+    use crate::core::system_monitor::{format_tree_indent, ProcessSortKey};
 
     let mode_str = if app.show_process_tree {
         "Tree"
@@ -766,7 +787,7 @@ fn render_processes_section(frame: &mut Frame, area: Rect, app: &MonitorApp) {
 
     let block = Block::default()
         .title(format!(
-            " Processes ({}) [t:toggle s:sort ↑↓:nav] ",
+            " Processes ({}) [t:toggle c/m/p/n:sort k/K:kill ↑↓:nav] ",
             mode_str
         ))
         .borders(Borders::ALL)
@@ -779,22 +800,35 @@ fn render_processes_section(frame: &mut Frame, area: Rect, app: &MonitorApp) {
         return; // Not enough space for header + at least one row
     }
 
+    // Arrow on the active sort column
+    let arrow = if app.sort_key.is_descending() {
+        " ▼"
+    } else {
+        " ▲"
+    };
+    let header_cell = |title: &str, key: ProcessSortKey| {
+        if app.sort_key == key {
+            Cell::from(format!("{}{}", title, arrow)).style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Cell::from(title.to_string()).style(Style::default().add_modifier(Modifier::BOLD))
+        }
+    };
     let header = Row::new(vec![
-        Cell::from("PID").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Name").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("CPU %").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Memory").style(Style::default().add_modifier(Modifier::BOLD)),
+        header_cell("PID", ProcessSortKey::Pid),
+        header_cell("Name", ProcessSortKey::Name),
+        header_cell("CPU %", ProcessSortKey::Cpu),
+        header_cell("Memory", ProcessSortKey::Memory),
     ])
     .height(1);
 
     let rows: Vec<Row> = if app.metrics.top_processes.is_empty() {
         vec![] // No processes to show
     } else if app.show_process_tree {
-        // Build and flatten tree
-        let tree = build_process_tree(&app.metrics.top_processes);
-        let flattened = flatten_tree(&tree);
-
-        flattened
+        app.sorted_process_tree()
             .iter()
             .enumerate()
             .map(|(i, flat_proc)| {
@@ -821,8 +855,7 @@ fn render_processes_section(frame: &mut Frame, area: Rect, app: &MonitorApp) {
             .collect()
     } else {
         // Flat list view
-        app.metrics
-            .top_processes
+        app.sorted_processes()
             .iter()
             .enumerate()
             .map(|(i, proc)| {
@@ -865,7 +898,10 @@ fn render_temperatures_section(frame: &mut Frame, area: Rect, app: &MonitorApp)
     }
 
     let temps: String = if app.metrics.temperatures.is_empty() {
-        "No temperature sensors detected".to_string()
+        match app.runtime.unavailable_reason(Subsystem::Temperatures) {
+            Some(reason) => format!("unavailable ({})", reason),
+            None => "No temperature sensors detected".to_string(),
+        }
     } else {
         app.metrics
             .temperatures
@@ -889,9 +925,30 @@ fn render_temperatures_section(frame: &mut Frame, area: Rect, app: &MonitorApp)
     frame.render_widget(para, area);
 }
 
-fn render_footer(frame: &mut Frame, area: Rect) {
-    let help = " q: Quit │ ?: Help │ Tab: Switch section │ s: Sort processes ";
-    let para = Paragraph::new(help).style(Style::default().fg(Color::DarkGray));
+fn render_footer(frame: &mut Frame, area: Rect, app: &MonitorApp) {
+    let para = if let Some(kill) = &app.pending_kill {
+        let signal = if kill.force { "SIGKILL" } else { "SIGTERM" };
+        Paragraph::new(format!(
+            " Send {} to {} (PID {})? [y/N] ",
+            signal, kill.name, kill.pid
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if let Some(status) = app.status.as_ref().filter(|s| !s.is_expired()) {
+        let color = if status.is_error {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        Paragraph::new(format!(" {} ", status.text)).style(Style::default().fg(color))
+    } else {
+        let help =
+            " q: Quit │ ?: Help │ Tab: Switch section │ c/m/p/n: Sort processes │ k/K: Kill ";
+        Paragraph::new(help).style(Style::default().fg(Color::DarkGray))
+    };
     frame.render_widget(para, area);
 }
 
@@ -905,7 +962,12 @@ fn render_help_overlay(frame: &mut Frame, area: Rect) {
     ? / h       Toggle this help screen
     Tab         Next section
     Shift+Tab   Previous section
-    s           Toggle process sort (CPU/Memory)
+    s           Cycle process sort column
+    c/m/p/n     Sort processes by CPU/Memory/PID/Name
+    t           Toggle process tree view
+    ↑ / ↓ / j   Select process
+    k           Terminate selected process (SIGTERM)
+    K           Force kill selected process (SIGKILL)
 
     Press any key to close this help
     "#;
@@ -0,0 +1,103 @@
+//! Stdout handling for piped usage
+//!
+//! When msc output is piped into another tool (`msc list | head`), the
+//! downstream process may close the pipe early. Rust ignores SIGPIPE, so
+//! writes then fail with `ErrorKind::BrokenPipe` and `println!` panics.
+//! This module turns that situation into a clean exit and provides a
+//! buffered stdout writer for commands that print many lines.
+
+use std::io::{self, BufWriter, IsTerminal, LineWriter, Write};
+
+/// Exit code used when the downstream side of a pipe is closed
+pub const BROKEN_PIPE_EXIT_CODE: i32 = 0;
+
+/// Check whether an error (or any error in its chain) is a broken pipe
+pub fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<io::Error>()
+            .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+    })
+}
+
+/// Check whether a panic message was caused by writing to a closed stdout
+pub fn is_broken_pipe_message(message: &str) -> bool {
+    message.contains("failed printing to stdout")
+        && (message.contains("Broken pipe")
+            || message.contains("os error 32")
+            // Windows: ERROR_NO_DATA "The pipe is being closed"
+            || message.contains("os error 232"))
+}
+
+/// Install a panic hook that exits cleanly when stdout is a closed pipe
+///
+/// All other panics are forwarded to the previously installed hook. Exiting
+/// from the hook also works with `panic = "abort"` release builds.
+pub fn install_broken_pipe_handler() {
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<String>()
+            .map(|s| s.as_str())
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or("");
+
+        if is_broken_pipe_message(message) {
+            std::process::exit(BROKEN_PIPE_EXIT_CODE);
+        }
+
+        previous(info);
+    }));
+}
+
+/// Buffered stdout writer
+///
+/// Line-buffered when attached to a terminal (so output appears as it is
+/// produced), block-buffered when piped (fewer syscalls for large listings).
+pub fn stdout_writer() -> Box<dyn Write> {
+    let stdout = io::stdout();
+    if stdout.is_terminal() {
+        Box::new(LineWriter::new(stdout))
+    } else {
+        Box::new(BufWriter::new(stdout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_broken_pipe_detects_io_error() {
+        let err = anyhow::Error::new(io::Error::from(io::ErrorKind::BrokenPipe));
+        assert!(is_broken_pipe(&err));
+    }
+
+    #[test]
+    fn test_is_broken_pipe_through_context() {
+        let err = anyhow::Error::new(io::Error::from(io::ErrorKind::BrokenPipe))
+            .context("Failed to write listing");
+        assert!(is_broken_pipe(&err));
+    }
+
+    #[test]
+    fn test_is_broken_pipe_ignores_other_errors() {
+        let err = anyhow::Error::new(io::Error::from(io::ErrorKind::NotFound));
+        assert!(!is_broken_pipe(&err));
+        assert!(!is_broken_pipe(&anyhow::anyhow!("Broken pipe")));
+    }
+
+    #[test]
+    fn test_is_broken_pipe_message() {
+        assert!(is_broken_pipe_message(
+            "failed printing to stdout: Broken pipe (os error 32)"
+        ));
+        assert!(is_broken_pipe_message(
+            "failed printing to stdout: The pipe is being closed. (os error 232)"
+        ));
+        assert!(!is_broken_pipe_message("failed printing to stdout: other"));
+        assert!(!is_broken_pipe_message("index out of bounds"));
+    }
+}
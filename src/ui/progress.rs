@@ -1,8 +1,204 @@
 // Progress bar and progress indicators module
-// This is a placeholder for future progress bar functionality
+//
+// Long operations report progress through a `ProgressTracker`, which turns
+// processed/total counts into `ProgressState` snapshots (fraction, rate, ETA)
+// and forwards them to a `ProgressSink`. `CliProgressBar` renders them as a
+// terminal bar; `SharedProgress` stores the latest snapshot for the TUI.
 
+use super::formatters::format_size;
+use super::terminal;
 use colored::Colorize;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Unit of the quantity being processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressUnit {
+    /// Discrete items (files, pages, ...)
+    #[default]
+    Items,
+    /// Bytes (downloads, hashing, ...)
+    Bytes,
+}
+
+/// Snapshot of a long-running operation's progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressState {
+    pub processed: u64,
+    pub total: u64,
+    pub elapsed: Duration,
+    pub unit: ProgressUnit,
+}
+
+impl ProgressState {
+    pub fn new(processed: u64, total: u64, elapsed: Duration, unit: ProgressUnit) -> Self {
+        Self {
+            processed,
+            total,
+            elapsed,
+            unit,
+        }
+    }
+
+    /// Completed fraction in `0.0..=1.0` (0 when the total is unknown)
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.processed as f64 / self.total as f64).min(1.0)
+    }
+
+    /// Completed percentage in `0..=100`
+    pub fn percentage(&self) -> usize {
+        (self.fraction() * 100.0) as usize
+    }
+
+    /// Whether all work is done
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.processed >= self.total
+    }
+
+    /// Units processed per second, or `None` before any measurable progress
+    pub fn rate(&self) -> Option<f64> {
+        let secs = self.elapsed.as_secs_f64();
+        if self.processed == 0 || secs <= 0.0 {
+            return None;
+        }
+        Some(self.processed as f64 / secs)
+    }
+
+    /// Estimated time remaining, extrapolated from the average rate so far
+    ///
+    /// Returns `None` when nothing has been processed yet or the total is
+    /// unknown, and zero once the operation is complete.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.is_complete() {
+            return Some(Duration::ZERO);
+        }
+        if self.total == 0 || self.processed == 0 {
+            return None;
+        }
+        let remaining = (self.total - self.processed) as f64;
+        let per_unit = self.elapsed.as_secs_f64() / self.processed as f64;
+        Some(Duration::from_secs_f64(remaining * per_unit))
+    }
+}
+
+/// Backend that displays progress updates
+pub trait ProgressSink {
+    /// Called on every update
+    fn update(&mut self, state: &ProgressState);
+
+    /// Called once when the operation ends
+    fn finish(&mut self, _state: &ProgressState) {}
+}
+
+/// Tracks processed/total counts and timing, forwarding snapshots to a sink
+pub struct ProgressTracker<S: ProgressSink> {
+    sink: S,
+    total: u64,
+    processed: u64,
+    unit: ProgressUnit,
+    started: Instant,
+}
+
+impl<S: ProgressSink> ProgressTracker<S> {
+    pub fn new(total: u64, unit: ProgressUnit, sink: S) -> Self {
+        Self {
+            sink,
+            total,
+            processed: 0,
+            unit,
+            started: Instant::now(),
+        }
+    }
+
+    /// Current snapshot
+    pub fn state(&self) -> ProgressState {
+        ProgressState::new(
+            self.processed,
+            self.total,
+            self.started.elapsed(),
+            self.unit,
+        )
+    }
+
+    /// Set absolute progress (and total, which may be discovered late)
+    pub fn set(&mut self, processed: u64, total: u64) {
+        self.processed = processed;
+        self.total = total;
+        let state = self.state();
+        self.sink.update(&state);
+    }
+
+    /// Advance progress by `amount`
+    pub fn advance(&mut self, amount: u64) {
+        self.set(self.processed + amount, self.total);
+    }
+
+    /// Notify the sink that the operation has ended
+    pub fn finish(&mut self) {
+        let state = self.state();
+        self.sink.finish(&state);
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+}
+
+/// Terminal progress bar backend
+pub struct CliProgressBar {
+    prefix: String,
+}
+
+impl CliProgressBar {
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+impl ProgressSink for CliProgressBar {
+    fn update(&mut self, state: &ProgressState) {
+        show_progress(state, &self.prefix);
+    }
+
+    fn finish(&mut self, _state: &ProgressState) {
+        println!();
+    }
+}
+
+/// Backend that keeps the latest snapshot for another thread (e.g. the TUI) to render
+#[derive(Debug, Clone, Default)]
+pub struct SharedProgress {
+    latest: Arc<Mutex<Option<ProgressState>>>,
+}
+
+impl SharedProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest reported snapshot, if any
+    pub fn latest(&self) -> Option<ProgressState> {
+        self.latest.lock().ok().and_then(|guard| *guard)
+    }
+}
+
+impl ProgressSink for SharedProgress {
+    fn update(&mut self, state: &ProgressState) {
+        if let Ok(mut guard) = self.latest.lock() {
+            *guard = Some(*state);
+        }
+    }
+
+    fn finish(&mut self, state: &ProgressState) {
+        self.update(state);
+    }
+}
 
 /// Display a simple progress bar
 ///
@@ -38,8 +234,247 @@ pub fn show_progress_bar(processed: usize, total: usize, prefix: &str) {
     io::stdout().flush().ok();
 }
 
+/// Display a progress bar with elapsed time, ETA and throughput
+pub fn show_progress(state: &ProgressState, prefix: &str) {
+    let bar_length: usize = 30;
+    let filled = (state.fraction() * bar_length as f64) as usize;
+    let empty = bar_length.saturating_sub(filled);
+
+    let counts = match state.unit {
+        ProgressUnit::Items => format!("{}/{}", state.processed, state.total),
+        ProgressUnit::Bytes => format!(
+            "{}/{}",
+            format_size(state.processed),
+            format_size(state.total)
+        ),
+    };
+
+    print!(
+        "\r{} [{}{}] {}% ({}) {} ",
+        prefix.white(),
+        "=".repeat(filled).green(),
+        " ".repeat(empty),
+        state.percentage(),
+        counts,
+        progress_stats(state).dimmed()
+    );
+
+    io::stdout().flush().ok();
+}
+
+/// Elapsed time, ETA and throughput, e.g. `12s, ETA 30s, 4.2 files/s`
+///
+/// Before any progress the ETA is shown as `--`; once complete the ETA is
+/// omitted.
+pub fn progress_stats(state: &ProgressState) -> String {
+    let elapsed = format_duration_short(state.elapsed);
+    let rate = state
+        .rate()
+        .map(|rate| format_rate(rate, state.unit))
+        .unwrap_or_else(|| "--".to_string());
+
+    if state.is_complete() {
+        return format!("{}, {}", elapsed, rate);
+    }
+
+    let eta = state
+        .eta()
+        .map(format_duration_short)
+        .unwrap_or_else(|| "--".to_string());
+
+    format!("{}, ETA {}, {}", elapsed, eta, rate)
+}
+
+/// Format a duration compactly: `45s`, `3m 05s`, `1h 02m`
+pub fn format_duration_short(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Format a throughput value for the given unit
+fn format_rate(rate: f64, unit: ProgressUnit) -> String {
+    match unit {
+        ProgressUnit::Items => format!("{:.1} items/s", rate),
+        ProgressUnit::Bytes => format!("{}/s", format_size(rate as u64)),
+    }
+}
+
+/// Most bytes [`read_with_progress`] reserves before reading; the buffer grows past it
+const MAX_PREALLOCATION: u64 = 8 * 1024 * 1024;
+
+/// Read a stream to the end while showing a progress bar
+///
+/// `total` is the expected size in bytes (e.g. from `Content-Length`); when
+/// unknown (0) the bar only shows the transferred size and rate.
+pub fn read_with_progress<R: Read>(mut reader: R, total: u64, prefix: &str) -> io::Result<Vec<u8>> {
+    let mut progress =
+        ProgressTracker::new(total, ProgressUnit::Bytes, CliProgressBar::new(prefix));
+    // `total` comes from the server: trust it only up to a modest reservation
+    let mut data = Vec::with_capacity(total.min(MAX_PREALLOCATION) as usize);
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        data.extend_from_slice(&buffer[..read]);
+        progress.advance(read as u64);
+    }
+
+    progress.finish();
+    Ok(data)
+}
+
 /// Clear the current line (useful for progress bars)
 pub fn clear_line() {
-    print!("\r{}\r", " ".repeat(80));
+    print!("\r{}\r", " ".repeat(terminal::width().saturating_sub(1)));
     io::stdout().flush().ok();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sink that records every snapshot it receives
+    #[derive(Default)]
+    struct RecordingSink {
+        updates: Vec<ProgressState>,
+        finished: bool,
+    }
+
+    impl ProgressSink for RecordingSink {
+        fn update(&mut self, state: &ProgressState) {
+            self.updates.push(*state);
+        }
+
+        fn finish(&mut self, _state: &ProgressState) {
+            self.finished = true;
+        }
+    }
+
+    fn state(processed: u64, total: u64, elapsed_secs: u64) -> ProgressState {
+        ProgressState::new(
+            processed,
+            total,
+            Duration::from_secs(elapsed_secs),
+            ProgressUnit::Items,
+        )
+    }
+
+    #[test]
+    fn test_fraction_and_eta() {
+        let sequence = [(0, 100, 0), (25, 100, 10), (50, 100, 20), (100, 100, 40)];
+        let expected = [
+            (0.0, None),
+            (0.25, Some(30)),
+            (0.5, Some(20)),
+            (1.0, Some(0)),
+        ];
+
+        for ((processed, total, elapsed), (fraction, eta)) in sequence.into_iter().zip(expected) {
+            let s = state(processed, total, elapsed);
+            assert_eq!(s.fraction(), fraction);
+            assert_eq!(s.eta().map(|d| d.as_secs()), eta);
+        }
+    }
+
+    #[test]
+    fn test_unknown_total() {
+        let s = state(10, 0, 5);
+        assert_eq!(s.fraction(), 0.0);
+        assert_eq!(s.eta(), None);
+        assert_eq!(s.rate(), Some(2.0));
+    }
+
+    #[test]
+    fn test_eta_before_any_progress() {
+        // Nothing processed yet: no rate, no ETA, even after some time
+        let s = state(0, 100, 5);
+        assert_eq!(s.eta(), None);
+        assert_eq!(s.rate(), None);
+        assert_eq!(progress_stats(&s), "5s, ETA --, --");
+    }
+
+    #[test]
+    fn test_eta_from_elapsed() {
+        // 10 of 40 in 5s -> 0.5s per item -> 15s remaining at 2 items/s
+        let s = state(10, 40, 5);
+        assert_eq!(s.eta(), Some(Duration::from_secs(15)));
+        assert_eq!(s.rate(), Some(2.0));
+        assert_eq!(progress_stats(&s), "5s, ETA 15s, 2.0 items/s");
+
+        // Slow progress gives long ETAs
+        let s = state(1, 100, 60);
+        assert_eq!(s.eta(), Some(Duration::from_secs(99 * 60)));
+        assert_eq!(format_duration_short(s.eta().unwrap()), "1h 39m");
+    }
+
+    #[test]
+    fn test_stats_on_completion() {
+        let s = state(40, 40, 20);
+        assert_eq!(s.eta(), Some(Duration::ZERO));
+        assert_eq!(progress_stats(&s), "20s, 2.0 items/s");
+    }
+
+    #[test]
+    fn test_byte_throughput() {
+        let s = ProgressState::new(
+            4 * 1024 * 1024,
+            8 * 1024 * 1024,
+            Duration::from_secs(2),
+            ProgressUnit::Bytes,
+        );
+        assert_eq!(progress_stats(&s), "2s, ETA 2s, 2.0MB/s");
+    }
+
+    #[test]
+    fn test_format_duration_short() {
+        assert_eq!(format_duration_short(Duration::from_secs(0)), "0s");
+        assert_eq!(format_duration_short(Duration::from_secs(59)), "59s");
+        assert_eq!(format_duration_short(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_duration_short(Duration::from_secs(3720)), "1h 02m");
+    }
+
+    #[test]
+    fn test_read_with_progress_returns_all_bytes() {
+        let data = vec![7u8; 200 * 1024];
+        let read = read_with_progress(&data[..], data.len() as u64, "Test:").unwrap();
+        assert_eq!(read, data);
+    }
+
+    #[test]
+    fn test_tracker_forwards_to_any_backend() {
+        let mut recording = ProgressTracker::new(4, ProgressUnit::Items, RecordingSink::default());
+        let shared = SharedProgress::new();
+        let mut tui = ProgressTracker::new(4, ProgressUnit::Items, shared.clone());
+
+        for _ in 0..4 {
+            recording.advance(1);
+            tui.advance(1);
+        }
+        recording.finish();
+        tui.finish();
+
+        let fractions: Vec<f64> = recording
+            .sink()
+            .updates
+            .iter()
+            .map(|s| s.fraction())
+            .collect();
+        assert_eq!(fractions, vec![0.25, 0.5, 0.75, 1.0]);
+        assert!(recording.sink().finished);
+
+        let latest = shared.latest().unwrap();
+        assert_eq!(latest.fraction(), 1.0);
+        assert_eq!(latest.eta(), Some(Duration::ZERO));
+    }
+}
@@ -7,7 +7,13 @@ use crossterm::{
     execute,
     terminal::{self, ClearType},
 };
-use std::io::{self, Write};
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use super::glyphs::adapt;
+use super::terminal::capabilities as terminal_caps;
 
 /// Ask user for yes/no confirmation
 pub fn confirm(message: &str) -> io::Result<bool> {
@@ -84,9 +90,86 @@ pub fn read_exact_confirmation(prompt: &str, expected: &str) -> anyhow::Result<b
     }
 }
 
+/// How often a cancellable prompt checks the cancel flag
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Outcome of a cancellable confirmation prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confirmation {
+    /// User answered y/yes
+    Confirmed,
+    /// User answered anything else (or closed stdin)
+    Declined,
+    /// The cancel flag was raised (e.g. Ctrl+C) while waiting for input
+    Cancelled,
+}
+
+/// Ask user for confirmation, aborting as soon as `cancel_flag` is set
+///
+/// Unlike [`read_confirmation`], this does not block the caller on stdin: a
+/// Ctrl+C handler that only sets the flag is honored immediately instead of
+/// after the next Enter.
+pub fn read_confirmation_cancellable(
+    prompt: &str,
+    cancel_flag: &AtomicBool,
+) -> anyhow::Result<Confirmation> {
+    print!("{}", prompt.white().bold());
+    io::stdout().flush()?;
+
+    let result = read_confirmation_from(io::BufReader::new(io::stdin()), cancel_flag);
+    if result == Confirmation::Cancelled {
+        println!();
+    }
+    Ok(result)
+}
+
+/// Read a y/n answer from `reader` on a background thread, polling `cancel_flag`
+///
+/// If cancelled, the reader thread is left blocked until its next line; it
+/// owns the reader, so this is only safe when the caller aborts afterwards.
+pub fn read_confirmation_from<R>(reader: R, cancel_flag: &AtomicBool) -> Confirmation
+where
+    R: BufRead + Send + 'static,
+{
+    if cancel_flag.load(Ordering::SeqCst) {
+        return Confirmation::Cancelled;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut reader = reader;
+        let mut input = String::new();
+        let _ = tx.send(reader.read_line(&mut input).map(|_| input));
+    });
+
+    loop {
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Confirmation::Cancelled;
+        }
+
+        match rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+            Ok(Ok(input)) => {
+                let response = input.trim().to_lowercase();
+                return if response == "y" || response == "yes" {
+                    Confirmation::Confirmed
+                } else {
+                    Confirmation::Declined
+                };
+            }
+            Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => return Confirmation::Declined,
+            Err(RecvTimeoutError::Timeout) => continue,
+        }
+    }
+}
+
 /// Display a warning message
 pub fn warn(message: &str) {
-    println!("{}", format!("⚠️  Warning: {}", message).yellow().bold());
+    println!(
+        "{}",
+        format!("{} {}", adapt("⚠️  Warning:"), message)
+            .yellow()
+            .bold()
+    );
 }
 
 /// Display an info message
@@ -121,6 +204,12 @@ pub fn select_from_list(title: &str, items: &[String]) -> io::Result<Option<usiz
         return Ok(None);
     }
 
+    if !terminal_caps().is_interactive() {
+        return Err(io::Error::other(
+            "Interactive selection requires a terminal (stdin and stdout must not be redirected)",
+        ));
+    }
+
     let mut selected_index = 0;
     let mut stdout = io::stdout();
 
@@ -174,7 +263,7 @@ fn run_selection_loop(
         println!("\r");
         println!(
             "{}\r",
-            "Use ↑/↓ arrows to navigate, Enter to select, Esc to cancel".dimmed()
+            adapt("Use ↑/↓ arrows to navigate, Enter to select, Esc to cancel").dimmed()
         );
         println!("\r");
 
@@ -185,7 +274,11 @@ fn run_selection_loop(
 
             if index == *selected_index {
                 // Highlight selected item
-                println!("  {} {}\r", "→".green().bold(), cleaned_path.green().bold());
+                println!(
+                    "  {} {}\r",
+                    adapt("→").green().bold(),
+                    cleaned_path.green().bold()
+                );
             } else {
                 println!("    {}\r", cleaned_path.dimmed());
             }
@@ -252,3 +345,69 @@ fn run_selection_loop(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    /// Reader that blocks until its sender is dropped, like an idle terminal
+    struct BlockingReader(mpsc::Receiver<()>);
+
+    impl Read for BlockingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            let _ = self.0.recv();
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test_confirmation_from_reader() {
+        let flag = AtomicBool::new(false);
+        assert_eq!(
+            read_confirmation_from(Cursor::new("yes\n"), &flag),
+            Confirmation::Confirmed
+        );
+        assert_eq!(
+            read_confirmation_from(Cursor::new("n\n"), &flag),
+            Confirmation::Declined
+        );
+        assert_eq!(
+            read_confirmation_from(Cursor::new(""), &flag),
+            Confirmation::Declined
+        );
+    }
+
+    #[test]
+    fn test_cancel_during_prompt_returns_promptly() {
+        let (_keep_blocked, rx) = mpsc::channel::<()>();
+        let reader = io::BufReader::new(BlockingReader(rx));
+        let flag = Arc::new(AtomicBool::new(false));
+
+        let trigger = Arc::clone(&flag);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            trigger.store(true, Ordering::SeqCst);
+        });
+
+        let start = Instant::now();
+        let result = read_confirmation_from(reader, &flag);
+        assert_eq!(result, Confirmation::Cancelled);
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "Cancellation took {:?}",
+            start.elapsed()
+        );
+    }
+
+    #[test]
+    fn test_already_cancelled_skips_reading() {
+        let flag = AtomicBool::new(true);
+        assert_eq!(
+            read_confirmation_from(Cursor::new("y\n"), &flag),
+            Confirmation::Cancelled
+        );
+    }
+}
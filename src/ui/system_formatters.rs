@@ -1,5 +1,10 @@
+use super::glyphs::adapt;
+use super::progress::format_duration_short;
+use crate::core::system_info::cpu;
 use crate::core::system_info::types::*;
+use crate::core::system_monitor::{Subsystem, SystemMetrics};
 use colored::*;
+use std::time::Duration;
 
 /// Filter for controlling which system information sections to display
 #[derive(Debug, Clone)]
@@ -30,6 +35,38 @@ impl DisplayFilter {
             energy: true,
         }
     }
+
+    /// Whether a top-level `SystemInfo` field belongs to a shown section
+    pub fn shows_field(&self, field: &str) -> bool {
+        match field {
+            "cpu" => self.cpu,
+            "gpu" => self.gpu,
+            "memory" => self.memory,
+            "motherboard" => self.motherboard,
+            "network" => self.network,
+            "storage" => self.storage,
+            "os" => self.os,
+            "npu" => self.npu,
+            "battery" | "power_plan" => self.energy,
+            _ => true,
+        }
+    }
+}
+
+/// Serializable view of the sections selected by `filter`
+///
+/// Hidden sections are left out entirely; absent optional values inside the
+/// shown sections (e.g. `nvidia_metrics`) are kept as `null` so consumers get
+/// a stable schema.
+pub fn system_info_value(
+    info: &SystemInfo,
+    filter: &DisplayFilter,
+) -> serde_json::Result<serde_json::Value> {
+    let mut value = serde_json::to_value(info)?;
+    if let serde_json::Value::Object(fields) = &mut value {
+        fields.retain(|field, _| filter.shows_field(field));
+    }
+    Ok(value)
 }
 
 pub fn format_system_info(info: &SystemInfo, filter: &DisplayFilter) {
@@ -86,6 +123,240 @@ pub fn format_system_info(info: &SystemInfo, filter: &DisplayFilter) {
     println!();
 }
 
+/// Print one live metrics snapshot (`sys monitor --once`/`--count`)
+pub fn format_metrics_snapshot(metrics: &SystemMetrics) {
+    let taken_at = chrono::DateTime::from_timestamp(metrics.timestamp, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default();
+    println!(
+        "\n{} {}",
+        "SYSTEM METRICS".bold().bright_cyan(),
+        taken_at.dimmed()
+    );
+    println!("{}", "=".repeat(80));
+    if !metrics.global.hostname.is_empty() {
+        println!(
+            "  Host: {} (up {})",
+            metrics.global.hostname,
+            format_duration_short(Duration::from_secs(metrics.global.uptime_secs))
+        );
+    }
+
+    print_section_header("CPU");
+    println!(
+        "  Usage: {} {:.1}%",
+        create_usage_bar(metrics.cpu.global_usage, 30),
+        metrics.cpu.global_usage
+    );
+    let (one, five, fifteen) = metrics.cpu.load_average;
+    if one > 0.0 || five > 0.0 || fifteen > 0.0 {
+        println!("  Load Average: {:.2} {:.2} {:.2}", one, five, fifteen);
+    }
+
+    print_section_header("Memory");
+    println!(
+        "  Used: {} {} of {} ({:.1}%)",
+        create_usage_bar(metrics.memory.usage_percent, 30),
+        format_bytes(metrics.memory.used_bytes),
+        format_bytes(metrics.memory.total_bytes),
+        metrics.memory.usage_percent
+    );
+    if metrics.memory.swap_total_bytes > 0 {
+        println!(
+            "  Swap: {} of {} ({:.1}%)",
+            format_bytes(metrics.memory.swap_used_bytes),
+            format_bytes(metrics.memory.swap_total_bytes),
+            metrics.memory.swap_percent
+        );
+    }
+
+    if let Some(gpu) = &metrics.gpu {
+        print_section_header("GPU");
+        println!("  {}", gpu.name.bright_white());
+        println!(
+            "  Usage: {} {}%",
+            create_usage_bar(gpu.utilization_percent as f32, 30),
+            gpu.utilization_percent
+        );
+        println!(
+            "  VRAM: {} of {} ({:.1}%)",
+            format_bytes(gpu.memory_used_bytes),
+            format_bytes(gpu.memory_total_bytes),
+            gpu.memory_percent
+        );
+        if let Some(temp) = gpu.temperature_celsius {
+            println!("  Temperature: {}", format_celsius(temp));
+        }
+        if let Some(power) = gpu.power_draw_watts {
+            println!("  Power Draw: {} W", power);
+        }
+    }
+
+    if !metrics.disks.is_empty() {
+        print_section_header("Disks");
+        for disk in &metrics.disks {
+            println!(
+                "  {} {} {:.1}% ({} free)",
+                disk.mount_point,
+                create_usage_bar(disk.usage_percent, 20),
+                disk.usage_percent,
+                format_bytes(disk.available_bytes)
+            );
+        }
+    }
+
+    if !metrics.network.is_empty() {
+        print_section_header("Network");
+        for net in &metrics.network {
+            println!(
+                "  {}: {}/s down, {}/s up",
+                net.interface,
+                format_bytes(net.rx_bytes_per_sec),
+                format_bytes(net.tx_bytes_per_sec)
+            );
+        }
+    }
+
+    if !metrics.temperatures.is_empty() {
+        print_section_header("Temperatures");
+        for reading in &metrics.temperatures {
+            println!(
+                "  {}: {}",
+                reading.label,
+                format_celsius(format!("{:.0}", reading.current_celsius))
+            );
+        }
+    }
+
+    if !metrics.top_processes.is_empty() {
+        print_section_header("Top Processes");
+        for process in metrics.top_processes.iter().take(5) {
+            println!(
+                "  {:>7}  {:<24} {:>5.1}% CPU  {}",
+                process.pid,
+                process.name,
+                process.cpu_usage_percent,
+                format_bytes(process.memory_bytes)
+            );
+        }
+    }
+
+    println!();
+}
+
+/// Subsystems that reported an error instead of data (printed after a snapshot)
+pub fn format_unavailable_subsystems(unavailable: &[(Subsystem, String)]) {
+    if unavailable.is_empty() {
+        return;
+    }
+    print_section_header("Unavailable");
+    for (subsystem, reason) in unavailable {
+        println!(
+            "  {}: {}",
+            subsystem.label(),
+            format!("unavailable ({})", reason).yellow()
+        );
+    }
+    println!();
+}
+
+/// Plain-text overview of the whole system, without colors, for reports
+pub fn system_info_summary(info: &SystemInfo) -> String {
+    let mut lines = vec![
+        "SYSTEM INFORMATION".to_string(),
+        "=".repeat(80),
+        format!(
+            "OS:          {} {} ({})",
+            info.os.name, info.os.version, info.os.architecture
+        ),
+        format!(
+            "CPU:         {} ({} cores / {} threads, {} MHz)",
+            info.cpu.model, info.cpu.physical_cores, info.cpu.logical_cores, info.cpu.frequency_mhz
+        ),
+        format!(
+            "Memory:      {} total, {} available{}",
+            format_bytes(info.memory.total_bytes),
+            format_bytes(info.memory.available_bytes),
+            info.memory
+                .ddr_type
+                .map(|ddr| format!(" ({})", ddr))
+                .unwrap_or_default()
+        ),
+    ];
+
+    for gpu in &info.gpu {
+        let vram = gpu
+            .vram_bytes
+            .map(|bytes| format!(", {} VRAM", format_bytes(bytes)))
+            .unwrap_or_default();
+        let driver = gpu
+            .driver_version
+            .as_deref()
+            .map(|version| format!(", driver {}", version))
+            .unwrap_or_default();
+        lines.push(format!("GPU:         {}{}{}", gpu.name, vram, driver));
+    }
+
+    if let Some(mb) = &info.motherboard {
+        let board: Vec<&str> = [mb.manufacturer.as_deref(), mb.product.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        let bios = mb
+            .bios_version
+            .as_deref()
+            .map(|version| format!(" (BIOS {})", version))
+            .unwrap_or_default();
+        lines.push(format!("Motherboard: {}{}", board.join(" "), bios));
+    }
+
+    for disk in &info.storage {
+        lines.push(format!(
+            "Storage:     {} {} [{}] {} used of {} ({:.1}%)",
+            disk.mount_point,
+            disk.model.as_deref().unwrap_or(&disk.name),
+            disk.disk_type,
+            format_bytes(disk.used_bytes),
+            format_bytes(disk.total_bytes),
+            disk.usage_percent
+        ));
+    }
+
+    for adapter in &info.network.wifi_adapters {
+        lines.push(format!("WiFi:        {}", adapter.name));
+    }
+    for adapter in &info.network.ethernet_adapters {
+        lines.push(format!("Ethernet:    {}", adapter.name));
+    }
+
+    if let Some(npu) = &info.npu {
+        lines.push(format!("NPU:         {}", npu.name));
+    }
+
+    if let Some(battery) = info.battery.as_ref().filter(|battery| battery.is_present) {
+        let health = battery
+            .health_percentage
+            .map(|health| format!(", {}% health", health))
+            .unwrap_or_default();
+        let cycles = battery
+            .cycle_count
+            .map(|cycles| format!(", {} cycles", cycles))
+            .unwrap_or_default();
+        lines.push(format!(
+            "Battery:     {}{}{}",
+            battery.state, health, cycles
+        ));
+    }
+
+    let mut summary = lines.join("\n");
+    summary.push('\n');
+    summary
+}
+
 fn print_section_header(title: &str) {
     println!("\n{}", title.bold().green());
     println!("{}", "-".repeat(title.len()));
@@ -100,9 +371,15 @@ fn print_cpu_info(cpu: &CpuInfo) {
         "  Cores: {} physical, {} logical",
         cpu.physical_cores, cpu.logical_cores
     );
+    if let (Some(p_cores), Some(e_cores)) = (cpu.performance_cores, cpu.efficiency_cores) {
+        println!(
+            "  Hybrid Cores: {}",
+            format!("{}P + {}E cores", p_cores, e_cores).cyan()
+        );
+    }
 
     // Detect microarchitecture
-    let microarch = detect_cpu_microarchitecture(&cpu.model);
+    let microarch = cpu::detect_microarchitecture(&cpu.model);
     let arch_str = if let Some(ref ma) = microarch {
         format!("{} ({})", cpu.architecture, ma)
     } else {
@@ -146,6 +423,9 @@ fn print_cpu_info(cpu: &CpuInfo) {
     if !cache_parts.is_empty() {
         println!("  Cache: {}", cache_parts.join(", "));
     }
+    for line in format_cache_details(cpu) {
+        println!("  {}", line);
+    }
 
     // TDP information
     if let Some(tdp) = cpu.tdp_watts {
@@ -222,6 +502,53 @@ fn print_cpu_info(cpu: &CpuInfo) {
     }
 }
 
+/// Cache line size and per-level geometry, when the platform reports them
+fn format_cache_details(cpu: &CpuInfo) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(line_size) = cpu.cache_line_bytes {
+        lines.push(format!("Cache Line: {} bytes", line_size));
+    }
+
+    let threads_per_core = match cpu.physical_cores {
+        0 => 1,
+        physical => cpu.logical_cores.div_ceil(physical).max(1) as u32,
+    };
+    for cache in cpu.cache_details.iter().flatten() {
+        let suffix = match cache.cache_type {
+            CacheType::Data => "d",
+            CacheType::Instruction => "i",
+            CacheType::Unified => "",
+        };
+        let mut parts = Vec::new();
+        if let Some(size_kb) = cache.size_kb {
+            parts.push(if size_kb >= 1024 && size_kb.is_multiple_of(1024) {
+                format!("{} MB", size_kb / 1024)
+            } else {
+                format!("{} KB", size_kb)
+            });
+        }
+        if let Some(ways) = cache.ways {
+            parts.push(format!("{}-way", ways));
+        }
+        if let Some(shared_by) = cache.shared_by_cpus {
+            parts.push(if shared_by <= threads_per_core {
+                "per core".to_string()
+            } else {
+                format!("shared by {} threads", shared_by)
+            });
+        }
+        if !parts.is_empty() {
+            lines.push(format!(
+                "  L{}{}: {}",
+                cache.level,
+                suffix,
+                parts.join(", ")
+            ));
+        }
+    }
+    lines
+}
+
 fn print_memory_info(mem: &MemoryInfo) {
     print_section_header("Memory (RAM)");
 
@@ -291,9 +618,9 @@ fn print_memory_prediction(prediction: &MemoryPrediction) {
 
     if let Some(smbios) = prediction.smbios_reported_bytes {
         let validation_mark = if prediction.is_smbios_validated {
-            "✓".green()
+            adapt("✓").green()
         } else {
-            "✗".red()
+            adapt("✗").red()
         };
         println!(
             "    SMBIOS Reported: {} {}",
@@ -360,11 +687,11 @@ fn print_gpu_info(gpus: &[GpuInfo]) {
         // Real-time metrics
         if let Some(temp) = gpu.temperature_celsius {
             let temp_str = if temp > 80 {
-                format!("{}°C", temp).red()
+                format_celsius(temp).red()
             } else if temp > 70 {
-                format!("{}°C", temp).yellow()
+                format_celsius(temp).yellow()
             } else {
-                format!("{}°C", temp).green()
+                format_celsius(temp).green()
             };
             println!("  Temperature: {}", temp_str);
         }
@@ -418,8 +745,12 @@ fn print_gpu_info(gpus: &[GpuInfo]) {
                 println!("    RT Cores: {}", rt);
             }
 
-            if let (Some(gen), Some(lanes)) = (nvidia.max_pcie_generation, nvidia.max_pcie_lanes) {
-                println!("    PCIe: Gen {} x{}", gen, lanes);
+            let max_link = nvidia.max_pcie_generation.zip(nvidia.max_pcie_lanes);
+            let current_link = nvidia
+                .current_pcie_generation
+                .zip(nvidia.current_pcie_lanes);
+            if let Some(link) = format_pcie_link(max_link, current_link) {
+                println!("    PCIe: {}", link);
             }
 
             if let Some(fan_rpm) = nvidia.fan_rpm {
@@ -560,6 +891,18 @@ fn print_network_info(net: &NetworkInfo) {
                 println!("    Bands: {}", bands_str);
             }
 
+            // Connected network and signal strength
+            let connection = format_wifi_connection(wifi);
+            match wifi.signal_quality_percent.filter(|_| wifi.ssid.is_some()) {
+                Some(quality) => println!(
+                    "    Network: {} {}",
+                    connection.color(signal_quality_color(quality)),
+                    signal_bars(quality).color(signal_quality_color(quality))
+                ),
+                None if wifi.ssid.is_some() => println!("    Network: {}", connection),
+                None => println!("    Network: {}", connection.dimmed()),
+            }
+
             // Current link speed
             if let Some(speed) = wifi.current_link_speed_mbps {
                 let speed_str = if speed >= 1000 {
@@ -647,7 +990,7 @@ fn print_network_info(net: &NetworkInfo) {
         }
 
         for bt in &net.bluetooth_adapters {
-            let status_icon = if bt.is_enabled { "✓" } else { "✗" };
+            let status_icon = adapt(if bt.is_enabled { "✓" } else { "✗" });
             let status_color = if bt.is_enabled {
                 status_icon.green()
             } else {
@@ -669,6 +1012,32 @@ fn print_network_info(net: &NetworkInfo) {
     {
         println!("  No network adapters detected");
     }
+
+    if let Some(public_ip) = &net.public_ip {
+        println!();
+        println!("  {} {}", "Public IP".bold(), "(looked up online)".dimmed());
+        for line in format_public_ip(public_ip) {
+            println!("    {}", line);
+        }
+    }
+}
+
+/// Lines for the public IP block, e.g. `IPv4: 203.0.113.7`
+pub fn format_public_ip(public_ip: &PublicIpInfo) -> Vec<String> {
+    let location = match (&public_ip.city, &public_ip.country) {
+        (Some(city), Some(country)) => Some(format!("{}, {}", city, country)),
+        (city, country) => city.clone().or_else(|| country.clone()),
+    };
+
+    [
+        ("IPv4", public_ip.ipv4.clone()),
+        ("IPv6", public_ip.ipv6.clone()),
+        ("ISP", public_ip.isp.clone()),
+        ("Location", location),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.map(|value| format!("{}: {}", label, value)))
+    .collect()
 }
 
 fn print_storage_info(storage: &[StorageInfo]) {
@@ -731,13 +1100,13 @@ fn print_storage_info(storage: &[StorageInfo]) {
         // Temperature (if available)
         if let Some(temp) = disk.temperature_celsius {
             let temp_color = if temp >= 60 {
-                temp.to_string().red()
+                format_celsius(temp).red()
             } else if temp >= 50 {
-                temp.to_string().yellow()
+                format_celsius(temp).yellow()
             } else {
-                temp.to_string().green()
+                format_celsius(temp).green()
             };
-            println!("    Temperature: {}°C", temp_color);
+            println!("    Temperature: {}", temp_color);
         }
 
         // Health status (if available)
@@ -1116,15 +1485,72 @@ fn format_timeout(secs: u32) -> String {
     }
 }
 
-/// Create a usage bar with ASCII characters
+/// PCIe link as `Gen 4 x16`, adding the negotiated link when it differs
+/// (e.g. `Gen 4 x16 (current: Gen 1 x16)` for a card idling or in a slow slot)
+pub fn format_pcie_link(max: Option<(u32, u32)>, current: Option<(u32, u32)>) -> Option<String> {
+    let link = |(gen, lanes): (u32, u32)| format!("Gen {} x{}", gen, lanes);
+    match (max, current) {
+        (Some(max), Some(current)) if max != current => {
+            Some(format!("{} (current: {})", link(max), link(current)))
+        }
+        (Some(max), _) => Some(link(max)),
+        (None, Some(current)) => Some(link(current)),
+        (None, None) => None,
+    }
+}
+
+/// Connected network as `HomeNet (82%, -59 dBm)`, or `Disconnected`
+pub fn format_wifi_connection(wifi: &WifiAdapter) -> String {
+    let Some(ssid) = &wifi.ssid else {
+        return "Disconnected".to_string();
+    };
+
+    let signal: Vec<String> = wifi
+        .signal_quality_percent
+        .map(|quality| format!("{}%", quality))
+        .into_iter()
+        .chain(wifi.rssi_dbm.map(|rssi| format!("{} dBm", rssi)))
+        .collect();
+    if signal.is_empty() {
+        ssid.clone()
+    } else {
+        format!("{} ({})", ssid, signal.join(", "))
+    }
+}
+
+/// Color for a WiFi signal quality: green when strong, red when unusable
+pub fn signal_quality_color(quality: u8) -> Color {
+    match quality {
+        70.. => Color::Green,
+        40..=69 => Color::Yellow,
+        _ => Color::Red,
+    }
+}
+
+/// Four-cell signal strength indicator
+fn signal_bars(quality: u8) -> String {
+    let filled = usize::from(quality.min(100)).div_ceil(25);
+    format!(
+        "{}{}",
+        adapt("█").repeat(filled),
+        adapt("░").repeat(4 - filled)
+    )
+}
+
+/// Format a temperature in degrees Celsius (`°C`, or `C` in ASCII mode)
+pub fn format_celsius<T: std::fmt::Display>(temp: T) -> String {
+    format!("{}{}", temp, adapt("°C"))
+}
+
+/// Create a usage bar with block characters (ASCII in ASCII mode)
 fn create_usage_bar(usage_percent: f32, width: usize) -> String {
     use colored::Colorize;
 
     let filled = ((usage_percent / 100.0) * width as f32) as usize;
     let empty = width.saturating_sub(filled);
 
-    let filled_char = "█";
-    let empty_char = "░";
+    let filled_char = adapt("█");
+    let empty_char = adapt("░");
 
     let bar = format!("{}{}", filled_char.repeat(filled), empty_char.repeat(empty));
 
@@ -1159,106 +1585,196 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
-fn detect_cpu_microarchitecture(model: &str) -> Option<String> {
-    let model_lower = model.to_lowercase();
-
-    // AMD microarchitectures
-    if model_lower.contains("ryzen") {
-        // Ryzen AI 300 series (Zen 5)
-        if model_lower.contains("ai 9")
-            || model_lower.contains("ai 7")
-            || model_lower.contains("ai 3")
-        {
-            return Some("Zen 5".to_string());
-        }
-        // Ryzen 9000 series (Zen 5)
-        if model_lower.contains("9950")
-            || model_lower.contains("9900")
-            || model_lower.contains("9700")
-            || model_lower.contains("9600")
-        {
-            return Some("Zen 5".to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wifi_adapter(ssid: Option<&str>, quality: Option<u8>) -> WifiAdapter {
+        WifiAdapter {
+            name: "Intel(R) Wi-Fi 6 AX201 160MHz".to_string(),
+            wifi_standard: WifiStandard::WiFi6,
+            bands: vec![WifiBand::Band2_4GHz, WifiBand::Band5GHz],
+            max_speed_mbps: None,
+            current_link_speed_mbps: Some(866),
+            chipset_manufacturer: Some("Intel".to_string()),
+            ipv4_address: None,
+            ipv6_address: None,
+            gateway_latency_ms: None,
+            ssid: ssid.map(str::to_string),
+            signal_quality_percent: quality,
+            rssi_dbm: quality.map(|quality| i32::from(quality) / 2 - 100),
         }
-        // Ryzen 7000 series (Zen 4)
-        if model_lower.contains("7950")
-            || model_lower.contains("7900")
-            || model_lower.contains("7700")
-            || model_lower.contains("7600")
-            || model_lower.contains("7000")
-        {
-            return Some("Zen 4".to_string());
-        }
-        // Ryzen 5000 series (Zen 3)
-        if model_lower.contains("5950")
-            || model_lower.contains("5900")
-            || model_lower.contains("5800")
-            || model_lower.contains("5700")
-            || model_lower.contains("5600")
-            || model_lower.contains("5000")
-        {
-            return Some("Zen 3".to_string());
-        }
-        // Ryzen 3000 series (Zen 2)
-        if model_lower.contains("3950")
-            || model_lower.contains("3900")
-            || model_lower.contains("3800")
-            || model_lower.contains("3700")
-            || model_lower.contains("3600")
-            || model_lower.contains("3000")
-        {
-            return Some("Zen 2".to_string());
-        }
-        // Fallback for any Ryzen
-        return Some("Zen".to_string());
     }
 
-    // Intel microarchitectures
-    if model_lower.contains("intel") {
-        // Core Ultra (Arrow Lake / Lunar Lake)
-        if model_lower.contains("core ultra")
-            || model_lower.contains("ultra 9")
-            || model_lower.contains("ultra 7")
-            || model_lower.contains("ultra 5")
-        {
-            if model_lower.contains("200") {
-                return Some("Arrow Lake".to_string());
-            }
-            return Some("Meteor Lake".to_string());
-        }
-        // 14th gen (Raptor Lake Refresh)
-        if model_lower.contains("14900")
-            || model_lower.contains("14700")
-            || model_lower.contains("14600")
-            || model_lower.contains("14400")
-        {
-            return Some("Raptor Lake".to_string());
-        }
-        // 13th gen (Raptor Lake)
-        if model_lower.contains("13900")
-            || model_lower.contains("13700")
-            || model_lower.contains("13600")
-            || model_lower.contains("13400")
-        {
-            return Some("Raptor Lake".to_string());
-        }
-        // 12th gen (Alder Lake)
-        if model_lower.contains("12900")
-            || model_lower.contains("12700")
-            || model_lower.contains("12600")
-            || model_lower.contains("12400")
-            || model_lower.contains("12100")
-        {
-            return Some("Alder Lake".to_string());
-        }
-        // 11th gen (Rocket Lake)
-        if model_lower.contains("11900")
-            || model_lower.contains("11700")
-            || model_lower.contains("11600")
-            || model_lower.contains("11400")
-        {
-            return Some("Rocket Lake".to_string());
-        }
+    #[test]
+    fn test_public_ip_formatting() {
+        let public_ip = PublicIpInfo {
+            ipv4: Some("203.0.113.7".to_string()),
+            ipv6: None,
+            isp: Some("Telefonica del Peru".to_string()),
+            city: Some("Lima".to_string()),
+            country: Some("PE".to_string()),
+        };
+        assert_eq!(
+            format_public_ip(&public_ip),
+            [
+                "IPv4: 203.0.113.7",
+                "ISP: Telefonica del Peru",
+                "Location: Lima, PE"
+            ]
+        );
+
+        let country_only = PublicIpInfo {
+            ipv6: Some("2001:db8::1".to_string()),
+            country: Some("PE".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_public_ip(&country_only),
+            ["IPv6: 2001:db8::1", "Location: PE"]
+        );
+    }
+
+    #[test]
+    fn test_signal_quality_color() {
+        assert_eq!(signal_quality_color(100), Color::Green);
+        assert_eq!(signal_quality_color(70), Color::Green);
+        assert_eq!(signal_quality_color(69), Color::Yellow);
+        assert_eq!(signal_quality_color(40), Color::Yellow);
+        assert_eq!(signal_quality_color(39), Color::Red);
+        assert_eq!(signal_quality_color(0), Color::Red);
+    }
+
+    #[test]
+    fn test_wifi_connection_formatting() {
+        let connected = wifi_adapter(Some("HomeNet"), Some(82));
+        assert_eq!(format_wifi_connection(&connected), "HomeNet (82%, -59 dBm)");
+
+        let no_signal = wifi_adapter(Some("HomeNet"), None);
+        assert_eq!(format_wifi_connection(&no_signal), "HomeNet");
+
+        // A stale signal reading without an SSID still reads as disconnected
+        let disconnected = wifi_adapter(None, Some(82));
+        assert_eq!(format_wifi_connection(&disconnected), "Disconnected");
+    }
+
+    #[test]
+    fn test_cache_details_formatting() {
+        let mut cpu = cpu::get_fallback();
+        cpu.physical_cores = 8;
+        cpu.logical_cores = 16;
+        assert!(format_cache_details(&cpu).is_empty());
+
+        cpu.cache_line_bytes = Some(64);
+        cpu.cache_details = Some(vec![
+            CpuCacheDetails {
+                level: 1,
+                cache_type: CacheType::Data,
+                size_kb: Some(48),
+                ways: Some(12),
+                line_size_bytes: Some(64),
+                shared_by_cpus: Some(2),
+            },
+            CpuCacheDetails {
+                level: 3,
+                cache_type: CacheType::Unified,
+                size_kb: Some(32 * 1024),
+                ways: None,
+                line_size_bytes: Some(64),
+                shared_by_cpus: Some(16),
+            },
+            CpuCacheDetails {
+                level: 2,
+                cache_type: CacheType::Unified,
+                size_kb: None,
+                ways: None,
+                line_size_bytes: None,
+                shared_by_cpus: None,
+            },
+        ]);
+
+        assert_eq!(
+            format_cache_details(&cpu),
+            [
+                "Cache Line: 64 bytes",
+                "  L1d: 48 KB, 12-way, per core",
+                "  L3: 32 MB, shared by 16 threads",
+            ]
+        );
     }
 
-    None
+    #[test]
+    fn test_pcie_link_shows_current_only_when_different() {
+        assert_eq!(
+            format_pcie_link(Some((4, 16)), Some((1, 16))).as_deref(),
+            Some("Gen 4 x16 (current: Gen 1 x16)")
+        );
+        assert_eq!(
+            format_pcie_link(Some((4, 16)), Some((4, 16))).as_deref(),
+            Some("Gen 4 x16")
+        );
+        assert_eq!(
+            format_pcie_link(Some((4, 16)), None).as_deref(),
+            Some("Gen 4 x16")
+        );
+        assert_eq!(
+            format_pcie_link(None, Some((3, 8))).as_deref(),
+            Some("Gen 3 x8")
+        );
+        assert_eq!(format_pcie_link(None, None), None);
+    }
+
+    #[test]
+    fn test_filter_selects_top_level_fields() {
+        let filter = DisplayFilter {
+            gpu: false,
+            memory: false,
+            motherboard: false,
+            network: false,
+            storage: false,
+            os: false,
+            npu: false,
+            ..DisplayFilter::all()
+        };
+
+        assert!(filter.shows_field("cpu"));
+        assert!(filter.shows_field("battery") && filter.shows_field("power_plan"));
+        assert!(!filter.shows_field("gpu") && !filter.shows_field("storage"));
+    }
+
+    #[test]
+    fn test_absent_gpu_metrics_serialize_as_null() {
+        let gpu = GpuInfo {
+            name: "Test GPU".to_string(),
+            vendor: "Test".to_string(),
+            vram_bytes: None,
+            memory_type: None,
+            is_integrated: true,
+            driver_version: None,
+            core_clock_mhz: None,
+            memory_clock_mhz: None,
+            temperature_celsius: None,
+            power_draw_watts: None,
+            fan_speed_percent: None,
+            nvidia_metrics: None,
+            amd_metrics: None,
+        };
+
+        let value = serde_json::to_value(&gpu).unwrap();
+        assert!(value["nvidia_metrics"].is_null());
+        assert!(value["amd_metrics"].is_null());
+        assert!(value.as_object().unwrap().contains_key("nvidia_metrics"));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_temperature_output_is_utf8() {
+        let bytes = format_celsius(45).into_bytes();
+
+        let text = std::str::from_utf8(&bytes).expect("Output should be valid UTF-8");
+        assert_eq!(text, "45°C");
+        // Degree sign encoded as UTF-8 (C2 B0), not double-encoded mojibake ("Â°" = C3 82 C2 B0)
+        assert!(bytes.windows(2).any(|w| w == [0xC2, 0xB0]));
+        assert!(!text.contains("Â°"));
+    }
 }
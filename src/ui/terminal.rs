@@ -0,0 +1,249 @@
+//! Terminal capability detection
+//!
+//! Colors, icons, box-drawing characters and interactive prompts all depend
+//! on what the attached terminal supports. This module inspects the
+//! environment once and exposes the result, so the rest of the UI code can
+//! degrade gracefully in pipes, CI logs, `TERM=dumb` and legacy consoles.
+
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+
+/// Fallback size when the terminal size cannot be queried
+pub const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Environment variables relevant to capability detection
+#[derive(Debug, Clone, Default)]
+pub struct TerminalEnv {
+    /// `NO_COLOR` (any non-empty value disables color)
+    pub no_color: Option<String>,
+    /// `CLICOLOR_FORCE` (any value other than "0" forces color)
+    pub clicolor_force: Option<String>,
+    /// `TERM`
+    pub term: Option<String>,
+    /// First non-empty of `LC_ALL`, `LC_CTYPE`, `LANG`
+    pub locale: Option<String>,
+    /// Set when running in a Windows terminal known to render Unicode
+    /// (`WT_SESSION`, `TERM_PROGRAM`, `ConEmuANSI=ON`)
+    pub modern_windows_terminal: bool,
+}
+
+impl TerminalEnv {
+    /// Read the detection inputs from the process environment
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the detection inputs using a custom variable lookup
+    pub fn from_lookup<F>(lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let non_empty = |name: &str| lookup(name).filter(|value| !value.is_empty());
+
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|name| non_empty(name));
+
+        let modern_windows_terminal = non_empty("WT_SESSION").is_some()
+            || non_empty("TERM_PROGRAM").is_some()
+            || lookup("ConEmuANSI").is_some_and(|v| v.eq_ignore_ascii_case("on"));
+
+        Self {
+            no_color: non_empty("NO_COLOR"),
+            clicolor_force: non_empty("CLICOLOR_FORCE"),
+            term: non_empty("TERM"),
+            locale,
+            modern_windows_terminal,
+        }
+    }
+
+    /// Whether `TERM` is set to `dumb`
+    pub fn is_dumb(&self) -> bool {
+        self.term.as_deref() == Some("dumb")
+    }
+}
+
+/// Detected capabilities of the attached terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    /// stdout is attached to a terminal
+    pub stdout_is_tty: bool,
+    /// stdin is attached to a terminal (interactive prompts are possible)
+    pub stdin_is_tty: bool,
+    /// ANSI colors should be emitted
+    pub color: bool,
+    /// Unicode glyphs (emoji, box drawing) render correctly
+    pub unicode: bool,
+}
+
+impl TerminalCapabilities {
+    /// Compute capabilities from explicit inputs
+    pub fn detect_with(
+        env: &TerminalEnv,
+        stdout_is_tty: bool,
+        stdin_is_tty: bool,
+        is_windows: bool,
+    ) -> Self {
+        Self {
+            stdout_is_tty,
+            stdin_is_tty,
+            color: supports_color(env, stdout_is_tty),
+            unicode: supports_unicode(env, is_windows),
+        }
+    }
+
+    /// Whether full-screen or raw-mode interaction (TUI, arrow-key menus) is possible
+    pub fn is_interactive(&self) -> bool {
+        self.stdout_is_tty && self.stdin_is_tty
+    }
+}
+
+/// Decide whether to emit colors
+///
+/// `NO_COLOR` always wins, then `CLICOLOR_FORCE`; otherwise colors are used
+/// only on a terminal that is not `TERM=dumb`.
+pub fn supports_color(env: &TerminalEnv, stdout_is_tty: bool) -> bool {
+    if env.no_color.is_some() {
+        return false;
+    }
+    if env.clicolor_force.as_deref().is_some_and(|v| v != "0") {
+        return true;
+    }
+    stdout_is_tty && !env.is_dumb()
+}
+
+/// Decide whether Unicode glyphs can be rendered
+pub fn supports_unicode(env: &TerminalEnv, is_windows: bool) -> bool {
+    if env.is_dumb() {
+        return false;
+    }
+
+    if is_windows {
+        // Legacy conhost renders emoji and box drawing as mojibake; modern
+        // terminals and MSYS/mintty (which set TERM) are fine.
+        return env.modern_windows_terminal || env.term.is_some();
+    }
+
+    env.locale.as_deref().is_some_and(|locale| {
+        let locale = locale.to_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+    })
+}
+
+/// Capabilities of the current process, detected once
+pub fn capabilities() -> &'static TerminalCapabilities {
+    static CAPABILITIES: OnceLock<TerminalCapabilities> = OnceLock::new();
+    CAPABILITIES.get_or_init(|| {
+        TerminalCapabilities::detect_with(
+            &TerminalEnv::from_env(),
+            io::stdout().is_terminal(),
+            io::stdin().is_terminal(),
+            cfg!(windows),
+        )
+    })
+}
+
+/// Current terminal size as (columns, rows), or [`DEFAULT_SIZE`] when unknown
+pub fn size() -> (u16, u16) {
+    match crossterm::terminal::size() {
+        Ok((cols, rows)) if cols > 0 && rows > 0 => (cols, rows),
+        _ => DEFAULT_SIZE,
+    }
+}
+
+/// Current terminal width in columns
+pub fn width() -> usize {
+    size().0 as usize
+}
+
+/// Apply the detected color support to the `colored` crate
+///
+/// Call once at startup so every formatter and prompt honors `NO_COLOR`,
+/// `TERM=dumb` and piped output without checking individually.
+pub fn init() {
+    if !capabilities().color {
+        colored::control::set_override(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> TerminalEnv {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        TerminalEnv::from_lookup(|name| {
+            vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+        })
+    }
+
+    #[test]
+    fn test_no_color_disables_color() {
+        let env = env(&[("NO_COLOR", "1"), ("TERM", "xterm-256color")]);
+        assert!(!supports_color(&env, true));
+
+        // NO_COLOR wins over CLICOLOR_FORCE
+        let env_forced = TerminalEnv {
+            clicolor_force: Some("1".to_string()),
+            ..env
+        };
+        assert!(!supports_color(&env_forced, true));
+    }
+
+    #[test]
+    fn test_empty_no_color_is_ignored() {
+        let env = env(&[("NO_COLOR", ""), ("TERM", "xterm-256color")]);
+        assert!(supports_color(&env, true));
+    }
+
+    #[test]
+    fn test_dumb_terminal() {
+        let env = env(&[("TERM", "dumb"), ("LANG", "en_US.UTF-8")]);
+        assert!(!supports_color(&env, true));
+        assert!(!supports_unicode(&env, false));
+        assert!(!supports_unicode(&env, true));
+    }
+
+    #[test]
+    fn test_color_requires_tty_unless_forced() {
+        let plain = env(&[("TERM", "xterm")]);
+        assert!(supports_color(&plain, true));
+        assert!(!supports_color(&plain, false));
+
+        let forced = env(&[("TERM", "xterm"), ("CLICOLOR_FORCE", "1")]);
+        assert!(supports_color(&forced, false));
+
+        let not_forced = env(&[("TERM", "xterm"), ("CLICOLOR_FORCE", "0")]);
+        assert!(!supports_color(&not_forced, false));
+    }
+
+    #[test]
+    fn test_unicode_from_locale() {
+        assert!(supports_unicode(&env(&[("LANG", "en_US.UTF-8")]), false));
+        assert!(supports_unicode(&env(&[("LC_ALL", "C.utf8")]), false));
+        assert!(!supports_unicode(&env(&[("LANG", "C")]), false));
+        assert!(!supports_unicode(&env(&[]), false));
+
+        // LC_ALL takes precedence over LANG
+        let overridden = env(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")]);
+        assert!(!supports_unicode(&overridden, false));
+    }
+
+    #[test]
+    fn test_unicode_on_windows() {
+        assert!(!supports_unicode(&env(&[]), true));
+        assert!(supports_unicode(&env(&[("WT_SESSION", "abc")]), true));
+        assert!(supports_unicode(&env(&[("ConEmuANSI", "ON")]), true));
+    }
+
+    #[test]
+    fn test_interactive_requires_both_streams() {
+        let env = env(&[("TERM", "xterm")]);
+        assert!(TerminalCapabilities::detect_with(&env, true, true, false).is_interactive());
+        assert!(!TerminalCapabilities::detect_with(&env, true, false, false).is_interactive());
+        assert!(!TerminalCapabilities::detect_with(&env, false, true, false).is_interactive());
+    }
+}
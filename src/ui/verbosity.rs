@@ -0,0 +1,86 @@
+//! Output verbosity selected with the global `-v`/`--quiet` flags
+//!
+//! Commands that print per-item progress check [`level`] to decide how much
+//! to show: `--quiet` keeps only errors, the default prints summaries, `-v`
+//! adds problems with individual items and `-vv` prints every step.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much progress output commands print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// `--quiet`: errors only
+    Quiet,
+    /// Summaries (default)
+    #[default]
+    Normal,
+    /// `-v`: summaries plus failed, rejected or blocked items
+    Verbose,
+    /// `-vv`: every step
+    Debug,
+}
+
+impl Verbosity {
+    /// Level for `verbose` occurrences of `-v`, or [`Verbosity::Quiet`] when `quiet`
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Verbosity::Quiet,
+            1 => Verbosity::Normal,
+            2 => Verbosity::Verbose,
+            _ => Verbosity::Debug,
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Set the level from the global flags
+pub fn init(verbose: u8, quiet: bool) {
+    set(Verbosity::from_flags(verbose, quiet));
+}
+
+/// Explicitly set the output level
+pub fn set(level: Verbosity) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Current output level
+pub fn level() -> Verbosity {
+    Verbosity::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Whether output at `required` should be printed
+pub fn enabled(required: Verbosity) -> bool {
+    level() >= required
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_map_to_levels() {
+        assert_eq!(Verbosity::from_flags(0, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(1, false), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(3, false), Verbosity::Debug);
+        assert_eq!(Verbosity::from_flags(2, true), Verbosity::Quiet);
+
+        for level in [
+            Verbosity::Quiet,
+            Verbosity::Normal,
+            Verbosity::Verbose,
+            Verbosity::Debug,
+        ] {
+            assert_eq!(Verbosity::from_u8(level as u8), level);
+        }
+    }
+}
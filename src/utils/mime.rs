@@ -0,0 +1,231 @@
+//! MIME type utilities
+//!
+//! This module provides a lightweight, extension-based MIME type guess
+//! for displaying file metadata, and a text/binary classification that
+//! combines it with a look at the first bytes of a file.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// How many leading bytes [`classify_file`] inspects
+pub const CLASSIFY_PREFIX_LEN: usize = 8 * 1024;
+
+/// Whether a file holds text or binary data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentClass {
+    Text,
+    Binary,
+}
+
+/// Returns the MIME type for a given filename based on its extension
+///
+/// # Arguments
+///
+/// * `filename` - The name of the file (with or without extension)
+///
+/// # Returns
+///
+/// The MIME type, or `application/octet-stream` if the extension is unknown
+///
+/// # Examples
+///
+/// ```
+/// use msc::utils::mime::guess_mime_type;
+///
+/// assert_eq!(guess_mime_type("index.html"), "text/html");
+/// assert_eq!(guess_mime_type("photo.JPG"), "image/jpeg");
+/// assert_eq!(guess_mime_type("unknown"), "application/octet-stream");
+/// ```
+pub fn guess_mime_type(filename: &str) -> &'static str {
+    let path = Path::new(filename);
+
+    let ext = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => ext.to_lowercase(),
+        None => return "application/octet-stream",
+    };
+
+    match ext.as_str() {
+        // Text and source code
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "js" | "mjs" | "cjs" => "text/javascript",
+        "ts" | "tsx" => "text/typescript",
+        "rs" => "text/x-rust",
+        "py" => "text/x-python",
+        "c" | "h" => "text/x-c",
+        "cpp" | "hpp" | "cc" => "text/x-c++",
+        "java" => "text/x-java",
+        "go" => "text/x-go",
+        "sh" | "bash" => "application/x-sh",
+        "ps1" => "text/x-powershell",
+
+        // Data formats
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+
+        // Images
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "bmp" => "image/bmp",
+
+        // Audio / Video
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "avi" => "video/x-msvideo",
+        "mov" => "video/quicktime",
+
+        // Documents
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+
+        // Archives
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" => "application/gzip",
+        "7z" => "application/x-7z-compressed",
+        "rar" => "application/vnd.rar",
+
+        // Fonts
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+
+        // Binaries
+        "wasm" => "application/wasm",
+        "exe" | "dll" | "msi" => "application/vnd.microsoft.portable-executable",
+
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether a MIME type from [`guess_mime_type`] is known to hold binary data
+///
+/// `application/octet-stream` (unknown extension) is not considered binary:
+/// callers that need certainty should inspect the contents.
+pub fn is_binary_mime(mime: &str) -> bool {
+    if mime.starts_with("text/") || mime == "application/octet-stream" {
+        return false;
+    }
+    !matches!(
+        mime,
+        "application/json"
+            | "application/xml"
+            | "application/yaml"
+            | "application/toml"
+            | "application/x-sh"
+            | "image/svg+xml"
+    )
+}
+
+/// Classify a file from its name and leading bytes
+///
+/// Empty files are text. Extensions with a binary MIME type are binary;
+/// anything else is binary only when the prefix contains a NUL byte.
+pub fn classify(filename: &str, prefix: &[u8]) -> ContentClass {
+    if prefix.is_empty() {
+        return ContentClass::Text;
+    }
+    if is_binary_mime(guess_mime_type(filename)) {
+        return ContentClass::Binary;
+    }
+
+    let sniffed = &prefix[..prefix.len().min(CLASSIFY_PREFIX_LEN)];
+    if sniffed.contains(&0) {
+        ContentClass::Binary
+    } else {
+        ContentClass::Text
+    }
+}
+
+/// Classify a file, reading at most [`CLASSIFY_PREFIX_LEN`] bytes of it
+pub fn classify_file(path: &Path) -> io::Result<ContentClass> {
+    let mut prefix = Vec::with_capacity(CLASSIFY_PREFIX_LEN);
+    File::open(path)?
+        .take(CLASSIFY_PREFIX_LEN as u64)
+        .read_to_end(&mut prefix)?;
+
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    Ok(classify(&name, &prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_extensions() {
+        assert_eq!(guess_mime_type("main.rs"), "text/x-rust");
+        assert_eq!(guess_mime_type("config.json"), "application/json");
+        assert_eq!(guess_mime_type("archive.zip"), "application/zip");
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(guess_mime_type("IMAGE.PNG"), "image/png");
+    }
+
+    #[test]
+    fn test_unknown_extension() {
+        assert_eq!(
+            guess_mime_type("file.unknownext"),
+            "application/octet-stream"
+        );
+        assert_eq!(guess_mime_type("Makefile"), "application/octet-stream");
+    }
+    #[test]
+    fn test_is_binary_mime() {
+        assert!(is_binary_mime(guess_mime_type("photo.png")));
+        assert!(is_binary_mime(guess_mime_type("app.exe")));
+        assert!(!is_binary_mime(guess_mime_type("main.rs")));
+        assert!(!is_binary_mime(guess_mime_type("icon.svg")));
+        assert!(!is_binary_mime(guess_mime_type("Cargo.toml")));
+        assert!(!is_binary_mime(guess_mime_type("Makefile")));
+    }
+
+    #[test]
+    fn test_classify_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let png = dir.path().join("logo.png");
+        let source = dir.path().join("main.rs");
+        let empty = dir.path().join("empty.png");
+        let blob = dir.path().join("data");
+        std::fs::write(&png, b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR").unwrap();
+        std::fs::write(&source, "fn main() {}\n").unwrap();
+        std::fs::write(&empty, "").unwrap();
+        std::fs::write(&blob, b"head\x00tail").unwrap();
+
+        assert_eq!(classify_file(&png).unwrap(), ContentClass::Binary);
+        assert_eq!(classify_file(&source).unwrap(), ContentClass::Text);
+        assert_eq!(classify_file(&empty).unwrap(), ContentClass::Text);
+        assert_eq!(classify_file(&blob).unwrap(), ContentClass::Binary);
+    }
+
+    #[test]
+    fn test_classify_only_sniffs_prefix() {
+        let mut late_nul = vec![b'a'; CLASSIFY_PREFIX_LEN];
+        late_nul.push(0);
+        assert_eq!(classify("notes", &late_nul), ContentClass::Text);
+    }
+}
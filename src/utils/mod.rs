@@ -1,4 +1,5 @@
 // Utilities module
 
 pub mod icons;
-// pub mod path;
+pub mod mime;
+pub mod path;
@@ -0,0 +1,330 @@
+//! Path expansion and display utilities
+//!
+//! This module expands `~`, Unix-style (`$VAR`, `${VAR}`) and Windows-style
+//! (`%VAR%`) environment variable references so users can write portable
+//! paths that resolve per-machine, and renders entry paths for listings.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Expand `~` and environment variable references in a path
+///
+/// Unknown variables are reported as errors instead of being left in place,
+/// so a typo never silently produces a different (possibly dangerous) path.
+///
+/// # Examples
+///
+/// ```
+/// use msc::utils::path::expand_path;
+///
+/// std::env::set_var("MSC_DOC_EXAMPLE", "/tmp/example");
+/// assert_eq!(expand_path("$MSC_DOC_EXAMPLE/cache").unwrap(), "/tmp/example/cache");
+/// assert_eq!(expand_path("%MSC_DOC_EXAMPLE%/cache").unwrap(), "/tmp/example/cache");
+/// ```
+pub fn expand_path(input: &str) -> Result<String> {
+    expand_path_with(input, dirs::home_dir(), |name| std::env::var(name).ok())
+}
+
+/// Expand a path using an explicit home directory and variable lookup
+pub fn expand_path_with<F>(input: &str, home: Option<PathBuf>, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let lookup_var = |name: &str| {
+        lookup(name).ok_or_else(|| anyhow!("Environment variable '{}' is not set", name))
+    };
+
+    // 1. Tilde expansion (only at the start: "~", "~/..." or "~\...")
+    let mut rest = input;
+    let mut output = String::new();
+    if input == "~" || input.starts_with("~/") || input.starts_with("~\\") {
+        let home = home.ok_or_else(|| anyhow!("Could not determine home directory"))?;
+        output.push_str(&home.to_string_lossy());
+        rest = &input[1..];
+    }
+
+    // 2. Variable expansion
+    let chars: Vec<char> = rest.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '$' if i + 1 < chars.len() && chars[i + 1] == '{' => {
+                let end = chars[i + 2..]
+                    .iter()
+                    .position(|&c| c == '}')
+                    .ok_or_else(|| anyhow!("Unterminated '${{' in path: {}", input))?;
+                let name: String = chars[i + 2..i + 2 + end].iter().collect();
+                output.push_str(&lookup_var(&name)?);
+                i += end + 3;
+            }
+            '$' => {
+                let name: String = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                    .collect();
+                if name.is_empty() {
+                    output.push('$');
+                    i += 1;
+                } else {
+                    output.push_str(&lookup_var(&name)?);
+                    i += name.chars().count() + 1;
+                }
+            }
+            '%' => {
+                let end = chars[i + 1..].iter().position(|&c| c == '%');
+                match end {
+                    Some(end) if end > 0 => {
+                        let name: String = chars[i + 1..i + 1 + end].iter().collect();
+                        output.push_str(&lookup_var(&name)?);
+                        i += end + 2;
+                    }
+                    _ => {
+                        output.push('%');
+                        i += 1;
+                    }
+                }
+            }
+            c => {
+                output.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Check whether a path contains anything `expand_path` would change
+pub fn needs_expansion(input: &str) -> bool {
+    input.starts_with('~') || input.contains('$') || input.matches('%').count() >= 2
+}
+
+/// How listing commands print entry paths
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PathStyle {
+    /// Only the file name (default)
+    #[default]
+    Name,
+    /// Canonical absolute path
+    Absolute,
+    /// Path relative to a canonical base directory
+    RelativeTo(PathBuf),
+}
+
+impl PathStyle {
+    /// Build a relative style, canonicalizing the base directory
+    pub fn relative_to(base: &str) -> Result<Self> {
+        let base = Path::new(base)
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve base directory: {}", base))?;
+        Ok(PathStyle::RelativeTo(base))
+    }
+
+    /// Render an entry given its file name and full path
+    pub fn render(&self, name: &str, full_path: &Path) -> String {
+        match self {
+            PathStyle::Name => name.to_string(),
+            PathStyle::Absolute => display_clean(&canonical_or_original(full_path)),
+            PathStyle::RelativeTo(base) => {
+                let absolute = canonical_or_original(full_path);
+                match pathdiff::diff_paths(&absolute, base) {
+                    Some(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+                    Some(relative) => relative.to_string_lossy().to_string(),
+                    None => display_clean(&absolute),
+                }
+            }
+        }
+    }
+
+    /// Render an entry as a path, keeping names that are not valid UTF-8 intact
+    pub fn render_path(&self, full_path: &Path) -> PathBuf {
+        match self {
+            PathStyle::Name => full_path.file_name().map(PathBuf::from).unwrap_or_default(),
+            PathStyle::Absolute => strip_verbatim(canonical_or_original(full_path)),
+            PathStyle::RelativeTo(base) => {
+                let absolute = canonical_or_original(full_path);
+                match pathdiff::diff_paths(&absolute, base) {
+                    Some(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+                    Some(relative) => relative,
+                    None => strip_verbatim(absolute),
+                }
+            }
+        }
+    }
+}
+
+fn canonical_or_original(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Display a path without the Windows verbatim prefix (`\\?\`)
+fn display_clean(path: &Path) -> String {
+    let display = path.to_string_lossy();
+    display
+        .strip_prefix("\\\\?\\")
+        .unwrap_or(&display)
+        .to_string()
+}
+
+/// Path without the Windows verbatim prefix (`\\?\`)
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    match path.to_str().and_then(|p| p.strip_prefix("\\\\?\\")) {
+        Some(stripped) => PathBuf::from(stripped),
+        None => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/alice".to_string()),
+            "TEMP" => Some("C:\\Users\\alice\\AppData\\Local\\Temp".to_string()),
+            _ => None,
+        }
+    }
+
+    fn home() -> Option<PathBuf> {
+        Some(PathBuf::from("/home/alice"))
+    }
+
+    #[test]
+    fn test_tilde_expansion() {
+        assert_eq!(
+            expand_path_with("~", home(), lookup).unwrap(),
+            "/home/alice"
+        );
+        assert_eq!(
+            expand_path_with("~/.cache", home(), lookup).unwrap(),
+            "/home/alice/.cache"
+        );
+        // Tilde in the middle is left alone
+        assert_eq!(
+            expand_path_with("/tmp/~backup", home(), lookup).unwrap(),
+            "/tmp/~backup"
+        );
+    }
+
+    #[test]
+    fn test_unix_variable_expansion() {
+        assert_eq!(
+            expand_path_with("$HOME/.cache", home(), lookup).unwrap(),
+            "/home/alice/.cache"
+        );
+        assert_eq!(
+            expand_path_with("${HOME}/tmp", home(), lookup).unwrap(),
+            "/home/alice/tmp"
+        );
+    }
+
+    #[test]
+    fn test_windows_variable_expansion() {
+        assert_eq!(
+            expand_path_with("%TEMP%\\msc", home(), lookup).unwrap(),
+            "C:\\Users\\alice\\AppData\\Local\\Temp\\msc"
+        );
+    }
+
+    #[test]
+    fn test_unknown_variable_is_error() {
+        assert!(expand_path_with("$NOPE/x", home(), lookup).is_err());
+        assert!(expand_path_with("%NOPE%\\x", home(), lookup).is_err());
+        assert!(expand_path_with("${HOME", home(), lookup).is_err());
+    }
+
+    #[test]
+    fn test_literal_characters_preserved() {
+        assert_eq!(
+            expand_path_with("/tmp/100%", home(), lookup).unwrap(),
+            "/tmp/100%"
+        );
+        assert_eq!(
+            expand_path_with("/tmp/$", home(), lookup).unwrap(),
+            "/tmp/$"
+        );
+    }
+
+    #[test]
+    fn test_needs_expansion() {
+        assert!(needs_expansion("~/x"));
+        assert!(needs_expansion("$HOME"));
+        assert!(needs_expansion("%TEMP%"));
+        assert!(!needs_expansion("/tmp/plain"));
+    }
+
+    #[test]
+    fn test_path_style_absolute_is_canonical() {
+        let temp = tempfile::tempdir().unwrap();
+        let nested = temp.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("file.txt"), "x").unwrap();
+
+        // Non-canonical input with a `..` component
+        let indirect = temp
+            .path()
+            .join("a")
+            .join("..")
+            .join("a")
+            .join("b")
+            .join("file.txt");
+        let rendered = PathStyle::Absolute.render("file.txt", &indirect);
+
+        let expected = nested.join("file.txt").canonicalize().unwrap();
+        assert_eq!(
+            PathBuf::from(&rendered),
+            PathBuf::from(display_clean(&expected))
+        );
+        assert!(!rendered.contains(".."));
+    }
+
+    #[test]
+    fn test_path_style_relative_to_base() {
+        let temp = tempfile::tempdir().unwrap();
+        let nested = temp.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        let file = nested.join("file.txt");
+        std::fs::write(&file, "x").unwrap();
+
+        let base = temp.path().join("a").to_string_lossy().to_string();
+        let style = PathStyle::relative_to(&base).unwrap();
+
+        assert_eq!(
+            PathBuf::from(style.render("file.txt", &file)),
+            Path::new("b").join("file.txt")
+        );
+        assert_eq!(style.render("a", &temp.path().join("a")), ".");
+        assert_eq!(
+            PathBuf::from(style.render("tmp", temp.path())),
+            PathBuf::from("..")
+        );
+    }
+
+    #[test]
+    fn test_path_style_name() {
+        assert_eq!(
+            PathStyle::Name.render("file.txt", Path::new("/x/file.txt")),
+            "file.txt"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_render_path_keeps_non_utf8_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let name = OsStr::from_bytes(b"caf\xe9.txt");
+        let full_path = dir.path().join(name);
+        std::fs::write(&full_path, b"").unwrap();
+        let base = dir.path().canonicalize().unwrap();
+
+        assert_eq!(PathStyle::Name.render_path(&full_path), Path::new(name));
+        assert_eq!(
+            PathStyle::RelativeTo(base).render_path(&full_path),
+            Path::new(name)
+        );
+    }
+}
@@ -6,6 +6,7 @@ mod integration {
     mod commands_test;
     mod config_test;
     mod ffmpeg_test;
+    mod output_test;
     mod set_command_test;
     mod vget_security_test;
     mod wget_test;
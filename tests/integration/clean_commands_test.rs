@@ -450,3 +450,97 @@ fn test_integration_full_workflow() {
     assert_eq!(config.get_clean_paths().len(), initial_count);
     assert!(!config.get_clean_paths().contains(&custom2));
 }
+
+#[test]
+fn test_expanded_clean_path_goes_through_validation() {
+    use msc::core::{PathValidator, ValidationResult};
+    use msc::utils::path::expand_path_with;
+    use std::path::{Path, PathBuf};
+
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+    fs::create_dir_all(&cache_dir).unwrap();
+    let temp_str = temp_dir.path().to_string_lossy().to_string();
+
+    let lookup = |name: &str| match name {
+        "MSC_TEST_CACHE" => Some(temp_str.clone()),
+        #[cfg(unix)]
+        "MSC_TEST_SYSTEM" => Some("/usr".to_string()),
+        #[cfg(windows)]
+        "MSC_TEST_SYSTEM" => Some("C:\\Windows".to_string()),
+        _ => None,
+    };
+    let validator = PathValidator::new();
+
+    // ~ expansion resolves to a safe temp location
+    let expanded =
+        expand_path_with("~/cache", Some(temp_dir.path().to_path_buf()), lookup).unwrap();
+    assert_eq!(PathBuf::from(&expanded), cache_dir);
+    let result = validator.validate_path(Path::new(&expanded)).unwrap();
+    assert!(matches!(result, ValidationResult::Safe(_)));
+
+    // $VAR and %VAR% expansion resolve to the same safe location
+    for input in ["$MSC_TEST_CACHE/cache", "%MSC_TEST_CACHE%/cache"] {
+        let expanded = expand_path_with(input, None, lookup).unwrap();
+        assert_eq!(PathBuf::from(&expanded), cache_dir, "{}", input);
+        let result = validator.validate_path(Path::new(&expanded)).unwrap();
+        assert!(matches!(result, ValidationResult::Safe(_)), "{}", input);
+    }
+
+    // A variable pointing at a protected directory is still rejected
+    let expanded = expand_path_with("$MSC_TEST_SYSTEM", None, lookup).unwrap();
+    if Path::new(&expanded).exists() {
+        let result = validator.validate_path(Path::new(&expanded)).unwrap();
+        assert!(
+            matches!(result, ValidationResult::Forbidden(_)),
+            "Expanded system path must stay forbidden: {:?}",
+            result
+        );
+    }
+}
+
+#[test]
+fn test_import_paths_from_file_partitions_safe_and_forbidden() {
+    use msc::core::path_validator::parse_path_list;
+    use msc::core::PathValidator;
+
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+    fs::create_dir_all(&cache_dir).unwrap();
+    let missing_dir = temp_dir.path().join("does-not-exist");
+
+    #[cfg(unix)]
+    let forbidden = "/usr/bin";
+    #[cfg(windows)]
+    let forbidden = "C:\\Windows";
+
+    let list_file = temp_dir.path().join("paths.txt");
+    fs::write(
+        &list_file,
+        format!(
+            "# exported clean paths\n\n{}\n{}\n  {}  \n",
+            cache_dir.display(),
+            forbidden,
+            missing_dir.display()
+        ),
+    )
+    .unwrap();
+
+    let content = fs::read_to_string(&list_file).unwrap();
+    let paths = parse_path_list(&content);
+    assert_eq!(paths.len(), 3, "Comments and blank lines should be ignored");
+
+    let batch = PathValidator::new().validate_batch(&paths, false);
+
+    assert_eq!(batch.accepted.len(), 1);
+    assert_eq!(
+        batch.accepted[0].canonicalize().unwrap(),
+        cache_dir.canonicalize().unwrap()
+    );
+
+    let skipped: Vec<&str> = batch.skipped.iter().map(|(p, _)| p.as_str()).collect();
+    assert_eq!(skipped.len(), 2);
+    assert!(skipped.contains(&forbidden));
+    assert!(skipped.contains(&missing_dir.to_string_lossy().as_ref()));
+    assert!(batch.skipped.iter().all(|(_, reason)| !reason.is_empty()));
+}
@@ -145,3 +145,28 @@ fn test_remove_custom_vs_default_paths() {
         assert!(!config.get_clean_paths().contains(&default_path));
     }
 }
+
+#[test]
+fn test_reset_preview_lists_custom_paths() {
+    let mut config = Config::default();
+    config.sync_default_paths();
+
+    let custom_a = "/custom/preview/a".to_string();
+    let custom_b = "/custom/preview/b".to_string();
+    assert!(config.add_clean_path(custom_a.clone()));
+    assert!(config.add_clean_path(custom_b.clone()));
+
+    let excluded_default = config.get_default_paths()[0].clone();
+    assert!(config.remove_clean_path(&excluded_default));
+
+    let preview = config.reset_preview();
+    assert_eq!(preview.removed_custom_paths, vec![custom_a, custom_b]);
+    assert_eq!(preview.restored_default_paths, vec![excluded_default]);
+    assert!(!preview.is_empty());
+
+    // The preview must not modify the configuration
+    assert_eq!(config.get_custom_paths().len(), 2);
+
+    config.reset_to_defaults();
+    assert!(config.reset_preview().is_empty());
+}
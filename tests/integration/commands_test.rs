@@ -48,6 +48,11 @@ fn test_workspace_list() {
         excluded_default_paths: Vec::new(),
         ignored_work_folders: Vec::new(),
         installation_method: None,
+        rejected_extensions: None,
+        work_cache_folders: None,
+        editor: None,
+        workspace_tags: HashMap::new(),
+        alias_allow_shell_operators: false,
     };
 
     let manager = WorkspaceManager::with_config(config);
@@ -71,6 +76,8 @@ fn test_temp_cleaner_creation() {
             directories,
             min_age: Some(std::time::Duration::from_secs(24 * 3600)),
             max_age: None,
+            extensions: None,
+            trash: None,
             cancel_flag: Arc::new(AtomicBool::new(false)),
         };
 
@@ -94,6 +101,8 @@ fn test_temp_cleaner_scan() {
             directories: config.get_clean_paths(),
             min_age: Some(std::time::Duration::from_secs(24 * 3600)),
             max_age: None,
+            extensions: None,
+            trash: None,
             cancel_flag: Arc::new(AtomicBool::new(false)),
         }
     };
@@ -118,6 +127,8 @@ fn test_temp_cleaner_dry_run() {
             directories: config.get_clean_paths(),
             min_age: Some(std::time::Duration::from_secs(24 * 3600)),
             max_age: None,
+            extensions: None,
+            trash: None,
             cancel_flag: Arc::new(AtomicBool::new(false)),
         }
     };
@@ -188,3 +199,52 @@ fn test_file_scanner_sorts_directories_first() {
     assert!(!entries[1].is_dir);
     assert_eq!(entries[1].name, "afile.txt");
 }
+
+#[test]
+fn test_file_scanner_recursive_summary() {
+    use msc::core::DirectorySummary;
+
+    let temp_dir = TempDir::new().unwrap();
+    let test_path = temp_dir.path();
+
+    fs::create_dir_all(test_path.join("src/nested")).unwrap();
+    fs::write(test_path.join("README.md"), "# Readme").unwrap();
+    fs::write(test_path.join("src/main.rs"), "fn main() {}").unwrap();
+    fs::write(test_path.join("src/lib.rs"), "// lib").unwrap();
+    fs::write(test_path.join("src/nested/util.rs"), "// util").unwrap();
+    fs::write(test_path.join("Makefile"), "all:").unwrap();
+
+    let scanner = FileScanner::new(test_path).unwrap();
+    let entries = scanner.scan_recursive(false, false).unwrap();
+    let summary = DirectorySummary::from_entries(&entries);
+
+    assert_eq!(summary.files, 5);
+    assert_eq!(summary.directories, 2);
+    assert_eq!(summary.symlinks, 0);
+    assert_eq!(summary.total_size, 8 + 12 + 6 + 7 + 4);
+
+    let top = summary.top_extensions(2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0], ("rs".to_string(), 3, 25));
+    assert_eq!(top[1], ("md".to_string(), 1, 8));
+}
+
+#[test]
+fn test_file_scanner_recursive_respects_gitignore() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_path = temp_dir.path();
+
+    fs::create_dir_all(test_path.join("target")).unwrap();
+    fs::write(test_path.join(".gitignore"), "target/\n").unwrap();
+    fs::write(test_path.join("main.rs"), "fn main() {}").unwrap();
+    fs::write(test_path.join("target/out.bin"), "binary").unwrap();
+
+    let scanner = FileScanner::new(test_path).unwrap();
+
+    let all = scanner.scan_recursive(false, false).unwrap();
+    assert!(all.iter().any(|e| e.name == "out.bin"));
+
+    let filtered = scanner.scan_recursive(false, true).unwrap();
+    assert!(!filtered.iter().any(|e| e.name == "out.bin"));
+    assert!(filtered.iter().any(|e| e.name == "main.rs"));
+}
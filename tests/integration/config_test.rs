@@ -1,4 +1,5 @@
 use msc::core::config::Config;
+use msc::core::wget::ExtensionPolicy;
 use tempfile::TempDir;
 
 #[test]
@@ -59,6 +60,11 @@ fn test_config_workspaces() {
         excluded_default_paths: Vec::new(),
         ignored_work_folders: Vec::new(),
         installation_method: None,
+        rejected_extensions: None,
+        work_cache_folders: None,
+        editor: None,
+        workspace_tags: HashMap::new(),
+        alias_allow_shell_operators: false,
     };
 
     assert_eq!(config.workspaces.len(), 2);
@@ -67,3 +73,79 @@ fn test_config_workspaces() {
         Some(&"/path/to/project1".to_string())
     );
 }
+
+#[test]
+fn test_config_clear_path_leaves_other_keys() {
+    let mut config = Config::default();
+    config.set_work_path("/tmp/work".to_string());
+    config.set_video_path("/tmp/video".to_string());
+    config.set_web_path("/tmp/web".to_string());
+
+    assert_eq!(config.clear_web_path(), Some("/tmp/web".to_string()));
+    assert!(config.get_web_path().is_none());
+    assert_eq!(config.get_work_path(), Some(&"/tmp/work".to_string()));
+    assert_eq!(config.get_video_path(), Some(&"/tmp/video".to_string()));
+
+    // Clearing an unset key is a no-op
+    assert_eq!(config.clear_web_path(), None);
+
+    assert_eq!(config.clear_work_path(), Some("/tmp/work".to_string()));
+    assert!(config.get_work_path().is_none());
+    assert_eq!(config.get_video_path(), Some(&"/tmp/video".to_string()));
+
+    assert_eq!(config.clear_video_path(), Some("/tmp/video".to_string()));
+    assert!(config.get_video_path().is_none());
+}
+
+#[test]
+fn test_config_rejected_extensions() {
+    let mut config = Config::default();
+    assert!(config
+        .get_rejected_extensions()
+        .contains(&"exe".to_string()));
+
+    // Editing the list keeps the defaults that weren't removed
+    assert!(config.add_rejected_extension(".ZIP"));
+    assert!(!config.add_rejected_extension("zip"));
+    assert!(config.remove_rejected_extension("iso"));
+    assert!(!config.remove_rejected_extension("iso"));
+
+    let rejected = config.get_rejected_extensions();
+    assert!(rejected.contains(&"zip".to_string()));
+    assert!(rejected.contains(&"exe".to_string()));
+    assert!(!rejected.contains(&"iso".to_string()));
+}
+
+#[test]
+fn test_config_rejection_can_be_accepted_per_run() {
+    let mut config = Config::default();
+    config.add_rejected_extension("zip");
+
+    let policy = ExtensionPolicy::new(config.get_rejected_extensions());
+    assert!(!policy.allows("https://example.com/files/setup.exe"));
+    assert!(!policy.allows("https://example.com/files/archive.zip"));
+    assert!(policy.allows("https://example.com/files/logo.png"));
+
+    let policy = policy.with_overrides(&["exe".to_string()], &[]);
+    assert!(policy.allows("https://example.com/files/setup.exe"));
+    assert!(!policy.allows("https://example.com/files/archive.zip"));
+}
+
+#[test]
+fn test_config_work_cache_folders() {
+    let mut config = Config::default();
+    assert_eq!(
+        config.get_work_cache_folders(),
+        vec!["target", "dist", "node_modules"]
+    );
+
+    assert!(config.add_work_cache_folder(".next".to_string()));
+    assert!(!config.add_work_cache_folder(".next".to_string()));
+    assert!(config.remove_work_cache_folder("dist"));
+    assert!(!config.remove_work_cache_folder("dist"));
+
+    assert_eq!(
+        config.get_work_cache_folders(),
+        vec!["target", "node_modules", ".next"]
+    );
+}
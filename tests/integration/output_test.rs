@@ -0,0 +1,282 @@
+use std::fs;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+/// Create enough entries that the listing exceeds any OS pipe buffer
+fn create_large_fixture() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    for i in 0..3000 {
+        fs::write(
+            temp_dir
+                .path()
+                .join(format!("file_with_a_fairly_long_name_{:05}.txt", i)),
+            "",
+        )
+        .unwrap();
+    }
+    temp_dir
+}
+
+fn run_with_closed_stdout(args: &[&str]) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Simulate a downstream consumer that exits immediately (like `| head -0`)
+    drop(child.stdout.take());
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_list_closed_pipe_exits_cleanly() {
+    let fixture = create_large_fixture();
+    let path = fixture.path().to_str().unwrap();
+
+    let output = run_with_closed_stdout(&["list", path]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!stderr.contains("panicked"), "Should not panic: {}", stderr);
+    assert!(
+        output.status.success(),
+        "Should exit cleanly, got {:?}: {}",
+        output.status,
+        stderr
+    );
+}
+
+#[test]
+fn test_list_deep_closed_pipe_exits_cleanly() {
+    let fixture = create_large_fixture();
+    let path = fixture.path().to_str().unwrap();
+
+    let output = run_with_closed_stdout(&["list", path, "--deep"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!stderr.contains("panicked"), "Should not panic: {}", stderr);
+    assert!(output.status.success(), "Should exit cleanly: {}", stderr);
+}
+
+#[test]
+fn test_list_long_closed_pipe_exits_cleanly() {
+    // The long format still uses println!, which is covered by the panic hook
+    let fixture = create_large_fixture();
+    let path = fixture.path().to_str().unwrap();
+
+    let output = run_with_closed_stdout(&["list", path, "--long"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!stderr.contains("panicked"), "Should not panic: {}", stderr);
+    assert!(output.status.success(), "Should exit cleanly: {}", stderr);
+}
+
+#[test]
+fn test_list_print0_separates_entries_with_nul() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("with space.txt"), "").unwrap();
+    fs::write(temp_dir.path().join("plain.txt"), "").unwrap();
+    fs::create_dir(temp_dir.path().join("sub")).unwrap();
+    fs::write(temp_dir.path().join("sub").join("nested.txt"), "").unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["list", path, "--deep", "--print0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // 4 entries: plain.txt, sub, sub/nested.txt, with space.txt
+    let nul_count = output.stdout.iter().filter(|&&b| b == 0).count();
+    assert_eq!(nul_count, 4, "Expected one NUL per entry");
+    assert_eq!(output.stdout.last(), Some(&0));
+    assert!(!output.stdout.contains(&b'\n'), "No newlines expected");
+
+    let entries: Vec<String> = output
+        .stdout
+        .split(|&b| b == 0)
+        .filter(|e| !e.is_empty())
+        .map(|e| String::from_utf8_lossy(e).to_string())
+        .collect();
+    assert!(entries.iter().any(|e| e.ends_with("with space.txt")));
+    assert!(entries.iter().any(|e| e.ends_with("nested.txt")));
+}
+
+#[test]
+fn test_list_json_emits_flat_entries_with_depth() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("b.txt"), "hello").unwrap();
+    fs::create_dir(temp_dir.path().join("A")).unwrap();
+    fs::write(temp_dir.path().join("A").join("nested.txt"), "").unwrap();
+    fs::write(temp_dir.path().join(".hidden"), "").unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["list", path, "--deep", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains('\u{1b}'), "No color codes expected");
+
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&stdout).unwrap();
+    let paths: Vec<&str> = entries
+        .iter()
+        .map(|e| e["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["A", "A/nested.txt", "b.txt"]);
+
+    assert_eq!(entries[0]["is_dir"], true);
+    assert_eq!(entries[0]["depth"], 0);
+    assert_eq!(entries[1]["depth"], 1);
+    assert_eq!(entries[1]["name"], "nested.txt");
+    assert_eq!(entries[2]["size"], 5);
+
+    // ISO-8601, e.g. 2025-01-31T12:00:00+01:00
+    let modified = entries[2]["modified"].as_str().unwrap();
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(modified).is_ok(),
+        "{}",
+        modified
+    );
+}
+
+#[test]
+fn test_list_json_conflicts_with_long() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["list", ".", "--json", "--long"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_list_path_style_conflicts_with_summary_modes() {
+    for mode in ["--count", "--stat", "--duplicates"] {
+        for style in [&["--absolute"][..], &["--relative-to", "."][..]] {
+            let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+                .args(["list", "."])
+                .arg(mode)
+                .args(style)
+                .output()
+                .unwrap();
+            assert!(!output.status.success(), "{} {:?}", mode, style);
+        }
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["list", ".", "--absolute", "--exec", "echo {}"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_list_exec_failure_sets_exit_code() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.txt"), "").unwrap();
+    let path = dir.path().to_str().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["list", path, "--exec", "true {}"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["list", path, "--exec", "false {}"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_sys_info_json_only_includes_filtered_sections() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["sys", "info", "--format", "json", "--cpu"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // stdout must be pure JSON (no "Collecting..." banner)
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let sections: Vec<&String> = info.as_object().unwrap().keys().collect();
+    assert_eq!(sections, ["cpu"]);
+    assert!(info["cpu"]["model"].is_string());
+    assert!(info["cpu"].as_object().unwrap().contains_key("l3_cache_kb"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_ascii_mode_output_has_no_emoji() {
+    // Redirect the config directory so the real config is untouched
+    let home = TempDir::new().unwrap();
+    let target = home.path().join("site");
+    fs::create_dir(&target).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["--ascii", "set", "web", target.to_str().unwrap()])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(
+        stdout.contains("[OK]"),
+        "Expected ASCII check mark: {}",
+        stdout
+    );
+    assert!(
+        output.stdout.is_ascii(),
+        "Output should not contain multibyte glyphs: {}",
+        stdout
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn test_alias_list_json_matches_shim_schema() {
+    use msc::core::alias::{AliasFile, AliasListing, ALIAS_SCHEMA_VERSION};
+    use msc::core::{Alias, AliasConfig};
+
+    let home = TempDir::new().unwrap();
+    let config_home = home.path().join(".config");
+    let alias_dir = config_home.join("msc").join("aliases");
+    fs::create_dir_all(&alias_dir).unwrap();
+
+    let mut config = AliasConfig::default();
+    config.add_alias(Alias::new("gs".to_string(), "git status".to_string()).unwrap());
+    config.add_alias(
+        Alias::with_description(
+            "pyh".to_string(),
+            "python3 -m http.server".to_string(),
+            "Serve cwd".to_string(),
+        )
+        .unwrap(),
+    );
+    let on_disk = serde_json::to_string_pretty(&config).unwrap();
+    fs::write(alias_dir.join("aliases.json"), &on_disk).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args(["alias", "list", "--json"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", &config_home)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let listing: AliasListing = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(listing.schema_version, ALIAS_SCHEMA_VERSION);
+    let names: Vec<&str> = listing.aliases.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, ["gs", "pyh"]);
+
+    // Same entries as the shim sees when reading aliases.json
+    let shim_view: AliasFile = serde_json::from_str(&on_disk).unwrap();
+    assert_eq!(listing.into_file().aliases, shim_view.aliases);
+}
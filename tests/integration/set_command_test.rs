@@ -4,6 +4,7 @@
 
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 use tempfile::TempDir;
 
 /// Helper function to check if a path is a valid directory
@@ -20,14 +21,28 @@ fn is_file_not_directory(path: &str) -> bool {
 
 #[test]
 fn test_set_work_with_nonexistent_path() {
-    // Test that setting a non-existent path shows warning but is accepted
-    // (Current behavior: accepts but shows warning)
-    let nonexistent_path = "/this/path/does/not/exist/at/all";
-
-    assert!(!Path::new(nonexistent_path).exists());
-
-    // The current implementation accepts non-existent paths with a warning
-    // This test documents that behavior
+    // A missing directory is refused when creating it is declined
+    let temp_dir = TempDir::new().unwrap();
+    let nonexistent_path = temp_dir.path().join("does").join("not").join("exist");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+        .args([
+            "set",
+            "work",
+            nonexistent_path.to_str().unwrap(),
+            "--no-create",
+        ])
+        // Keep any config write away from the real user config
+        .env("HOME", temp_dir.path())
+        .env("XDG_CONFIG_HOME", temp_dir.path().join("config"))
+        .env("APPDATA", temp_dir.path().join("config"))
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Directory does not exist"));
+    assert!(!nonexistent_path.exists());
+    assert!(!temp_dir.path().join("config").exists());
 }
 
 #[test]
@@ -111,4 +111,520 @@ mod wget_tests {
             );
         }
     }
+
+    /// Spawn a one-shot HTTP server that answers every request with `response`
+    fn spawn_mock_server(response: &'static str) -> String {
+        spawn_mock_server_bytes(response.as_bytes().to_vec())
+    }
+
+    /// Same as `spawn_mock_server`, for binary (e.g. compressed) responses
+    fn spawn_mock_server_bytes(response: Vec<u8>) -> String {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let mut stream = match stream {
+                    Ok(s) => s,
+                    Err(_) => break,
+                };
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(&response);
+            }
+        });
+
+        format!("http://{}/", addr)
+    }
+
+    #[test]
+    fn test_download_resource_decodes_gzip_css() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use msc::core::wget::download_resource;
+        use std::io::Write;
+
+        let css = "body { color: #333; }\n";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(css.as_bytes()).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/css\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            gzipped.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&gzipped);
+        let url = spawn_mock_server_bytes(response);
+
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("style.css");
+        let saved = download_resource(&format!("{}style.css", url), &path).unwrap();
+
+        assert_eq!(saved, path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), css);
+    }
+
+    #[test]
+    fn test_extensionless_png_gets_png_extension_and_reference() {
+        use msc::core::wget::{process_html_file_complete, SiteProfile};
+        use url::Url;
+
+        let png: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            png.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(png);
+        let server = spawn_mock_server_bytes(response);
+        let image_url = format!("{}img?id=5", server);
+
+        let temp = tempdir().unwrap();
+        let base_dir = temp.path().to_path_buf();
+        let page_path = base_dir.join("example.com").join("index.html");
+        fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+        fs::write(
+            &page_path,
+            format!(r#"<html><body><img src="{}"></body></html>"#, image_url),
+        )
+        .unwrap();
+
+        let page_url = Url::parse("https://example.com/").unwrap();
+        process_html_file_complete(&page_path, &base_dir, &page_url, &SiteProfile::default())
+            .unwrap();
+
+        let assets: Vec<String> = fs::read_dir(page_path.parent().unwrap().join("assets"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(assets.len(), 1, "{:?}", assets);
+        assert!(assets[0].ends_with(".png"), "{:?}", assets);
+
+        let processed = fs::read_to_string(&page_path).unwrap();
+        assert!(
+            processed.contains(&format!(r#"src="assets/{}""#, assets[0])),
+            "{}",
+            processed
+        );
+    }
+
+    #[test]
+    fn test_keep_query_strings_saves_each_version() {
+        use msc::core::wget::{process_html_file_with_options, PostProcessOptions, SiteProfile};
+        use url::Url;
+
+        let png: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            png.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(png);
+        let server = spawn_mock_server_bytes(response);
+
+        let temp = tempdir().unwrap();
+        let base_dir = temp.path().to_path_buf();
+        let page_path = base_dir.join("example.com").join("index.html");
+        fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+        fs::write(
+            &page_path,
+            format!(
+                r#"<html><body><img src="{0}logo.png?v=1"><img src="{0}logo.png?v=2"></body></html>"#,
+                server
+            ),
+        )
+        .unwrap();
+
+        let page_url = Url::parse("https://example.com/").unwrap();
+        let options = PostProcessOptions {
+            keep_query_strings: true,
+            ..PostProcessOptions::default()
+        };
+        process_html_file_with_options(
+            &page_path,
+            &base_dir,
+            &page_url,
+            &SiteProfile::default(),
+            &options,
+        )
+        .unwrap();
+
+        let mut assets: Vec<String> = fs::read_dir(page_path.parent().unwrap().join("assets"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assets.sort();
+        assert_eq!(assets.len(), 2, "{:?}", assets);
+
+        let processed = fs::read_to_string(&page_path).unwrap();
+        for asset in &assets {
+            assert!(asset.starts_with("logo_") && asset.ends_with(".png"));
+            assert!(
+                processed.contains(&format!(r#"src="assets/{}""#, asset)),
+                "{}",
+                processed
+            );
+        }
+    }
+
+    #[test]
+    fn test_post_process_log_summarizes_unless_debug() {
+        use msc::core::wget::{
+            process_html_file_with_options, ExtensionPolicy, PostProcessLog, PostProcessOptions,
+            SiteProfile,
+        };
+        use msc::ui::Verbosity;
+        use std::sync::Arc;
+        use url::Url;
+
+        let png: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            png.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(png);
+        let server = spawn_mock_server_bytes(response);
+
+        let process_at = |level: Verbosity| -> Vec<String> {
+            let temp = tempdir().unwrap();
+            let base_dir = temp.path().to_path_buf();
+            let page_path = base_dir.join("example.com").join("index.html");
+            fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+            fs::write(
+                &page_path,
+                format!(
+                    r#"<html><body><img src="{0}logo.png"><img src="{0}setup.exe"></body></html>"#,
+                    server
+                ),
+            )
+            .unwrap();
+
+            let options = PostProcessOptions {
+                extension_policy: ExtensionPolicy::new(["exe"]),
+                log: Arc::new(PostProcessLog::capturing(level)),
+                ..PostProcessOptions::default()
+            };
+            process_html_file_with_options(
+                &page_path,
+                &base_dir,
+                &Url::parse("https://example.com/").unwrap(),
+                &SiteProfile::default(),
+                &options,
+            )
+            .unwrap();
+            options.log.lines()
+        };
+
+        // Default verbosity: the page summary, no per-resource lines
+        let lines = process_at(Verbosity::Normal);
+        let summary = lines.last().unwrap();
+        assert!(summary.contains("index.html:"), "{:?}", lines);
+        assert!(summary.contains("1 recursos descargados"), "{:?}", lines);
+        assert!(summary.contains("1 rechazados"), "{:?}", lines);
+        assert!(
+            !lines
+                .iter()
+                .any(|l| l.contains("Descargado:") || l.contains("Tipo rechazado:")),
+            "{:?}",
+            lines
+        );
+
+        // -vv: every resource, then the same summary
+        let lines = process_at(Verbosity::Debug);
+        assert!(
+            lines.iter().any(|l| l.contains("Descargado:")),
+            "{:?}",
+            lines
+        );
+        assert!(
+            lines.iter().any(|l| l.contains("Tipo rechazado:")),
+            "{:?}",
+            lines
+        );
+        assert!(
+            lines.last().unwrap().contains("1 recursos descargados"),
+            "{:?}",
+            lines
+        );
+    }
+
+    #[test]
+    fn test_parallel_downloads_fetch_shared_assets_once() {
+        use msc::core::wget::{process_html_file_with_options, PostProcessOptions, SiteProfile};
+        use url::Url;
+
+        let png: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            png.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(png);
+        // The mock server answers two requests: one per distinct asset
+        let server = spawn_mock_server_bytes(response);
+
+        let temp = tempdir().unwrap();
+        let base_dir = temp.path().to_path_buf();
+        let page_path = base_dir.join("example.com").join("index.html");
+        fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+        fs::write(
+            &page_path,
+            format!(
+                r#"<html><body><img src="{0}a.png"><img src="{0}b.png"><img src="{0}a.png"></body></html>"#,
+                server
+            ),
+        )
+        .unwrap();
+
+        let page_url = Url::parse("https://example.com/").unwrap();
+        let options = PostProcessOptions {
+            concurrency: 4,
+            ..PostProcessOptions::default()
+        };
+        process_html_file_with_options(
+            &page_path,
+            &base_dir,
+            &page_url,
+            &SiteProfile::default(),
+            &options,
+        )
+        .unwrap();
+
+        let assets_dir = page_path.parent().unwrap().join("assets");
+        assert!(assets_dir.join("a.png").exists());
+        assert!(assets_dir.join("b.png").exists());
+
+        let processed = fs::read_to_string(&page_path).unwrap();
+        assert_eq!(
+            processed.matches(r#"src="assets/a.png""#).count(),
+            2,
+            "{}",
+            processed
+        );
+        assert_eq!(
+            processed.matches(r#"src="assets/b.png""#).count(),
+            1,
+            "{}",
+            processed
+        );
+    }
+
+    #[test]
+    fn test_corrected_asset_path_keeps_trusted_extensions() {
+        use msc::core::wget::corrected_asset_path;
+        use std::path::Path;
+
+        let png = b"\x89PNG\r\n\x1a\n";
+        // Wrong extension is fixed from magic bytes
+        assert_eq!(
+            corrected_asset_path(Path::new("a/photo.jpg"), None, png),
+            Path::new("a/photo.png")
+        );
+        // Content-Type only fills in placeholder extensions
+        assert_eq!(
+            corrected_asset_path(
+                Path::new("a/resource_1.bin"),
+                Some("text/css; charset=utf-8"),
+                b"body{}"
+            ),
+            Path::new("a/resource_1.css")
+        );
+        assert_eq!(
+            corrected_asset_path(Path::new("a/app.js"), Some("text/plain"), b"var x;"),
+            Path::new("a/app.js")
+        );
+        assert_eq!(
+            corrected_asset_path(Path::new("a/pic.jpeg"), None, &[0xFF, 0xD8, 0xFF, 0xE0]),
+            Path::new("a/pic.jpeg")
+        );
+    }
+
+    #[test]
+    fn test_wget_asset_saves_file_with_derived_name() {
+        use std::process::Command;
+
+        let css = "h1 { font-weight: bold; }\n";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/css\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            css.len(),
+            css
+        );
+        let server = spawn_mock_server_bytes(response.into_bytes());
+
+        let home = tempdir().unwrap();
+        let dest = tempdir().unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_msc"))
+            .args([
+                "wget",
+                "asset",
+                &format!("{}static/site.css", server),
+                dest.path().to_str().unwrap(),
+            ])
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join(".config"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let saved = dest.path().join("site.css");
+        assert_eq!(fs::read_to_string(&saved).unwrap(), css);
+    }
+
+    #[test]
+    fn test_latin1_page_is_rewritten_as_utf8() {
+        use msc::core::wget::{process_html_file_complete, SiteProfile};
+        use url::Url;
+
+        let temp = tempdir().unwrap();
+        let base_dir = temp.path().to_path_buf();
+        let page_path = base_dir.join("example.com").join("index.html");
+        fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+
+        // "Año de publicación" in ISO-8859-1, declared through http-equiv
+        let latin1: &[u8] = b"<html><head>\
+            <meta http-equiv=\"Content-Type\" content=\"text/html; charset=ISO-8859-1\">\
+            </head><body><h1>A\xf1o de publicaci\xf3n</h1></body></html>";
+        fs::write(&page_path, latin1).unwrap();
+
+        let page_url = Url::parse("https://example.com/").unwrap();
+        process_html_file_complete(&page_path, &base_dir, &page_url, &SiteProfile::default())
+            .unwrap();
+
+        let processed = fs::read_to_string(&page_path).expect("page should now be UTF-8");
+        assert!(processed.contains("Año de publicación"), "{}", processed);
+        assert!(processed.contains("charset=utf-8"), "{}", processed);
+        assert!(!processed.contains("ISO-8859-1"), "{}", processed);
+    }
+
+    #[test]
+    fn test_compressed_formats_are_not_decoded() {
+        use msc::core::wget::is_compressed_format;
+
+        assert!(is_compressed_format("https://cdn.test/icons/logo.svgz"));
+        assert!(is_compressed_format("https://cdn.test/dist/app.tar.GZ?v=2"));
+        assert!(!is_compressed_format("https://cdn.test/css/style.css"));
+        assert!(!is_compressed_format("https://cdn.test/gz/"));
+    }
+
+    #[test]
+    fn test_head_check_html_content_type() {
+        use msc::core::wget::head_check;
+
+        let url = spawn_mock_server(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+
+        let report = head_check(&url).unwrap();
+        assert_eq!(report.status, 200);
+        assert!(report.is_html());
+        assert!(report.cross_domain_redirect.is_none());
+        assert!(report.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_head_check_warns_on_binary_content() {
+        use msc::core::wget::head_check;
+
+        let url = spawn_mock_server(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+
+        let report = head_check(&url).unwrap();
+        assert!(!report.is_html());
+        let warnings = report.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("application/octet-stream"));
+    }
+
+    #[test]
+    fn test_head_check_warns_on_cross_domain_redirect() {
+        use msc::core::wget::head_check;
+
+        let url = spawn_mock_server(
+            "HTTP/1.1 302 Found\r\nLocation: http://login.other-domain.test/auth\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+
+        let report = head_check(&url).unwrap();
+        assert_eq!(report.status, 302);
+        assert_eq!(
+            report.cross_domain_redirect.as_deref(),
+            Some("http://login.other-domain.test/auth")
+        );
+        let warnings = report.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("other-domain.test"));
+    }
+
+    #[test]
+    fn test_postprocessing_resolves_links_against_base_href() {
+        use msc::core::wget::SiteProfile;
+        use msc::core::wget::{calculate_local_path_for_url, process_html_file_complete};
+        use url::Url;
+
+        let temp = tempdir().unwrap();
+        let base_dir = temp.path().to_path_buf();
+
+        // Target of the relative link, resolved against <base href>
+        let guide_url = Url::parse("https://example.com/docs/guide.html").unwrap();
+        let guide_path = calculate_local_path_for_url(&guide_url, &base_dir).unwrap();
+        fs::create_dir_all(guide_path.parent().unwrap()).unwrap();
+        fs::write(&guide_path, "<html></html>").unwrap();
+
+        let page_url = Url::parse("https://example.com/other/page.html").unwrap();
+        let page_path = calculate_local_path_for_url(&page_url, &base_dir).unwrap();
+        fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+        fs::write(
+            &page_path,
+            r#"<html><head><base href="https://example.com/docs/"></head><body><a href="guide.html">Guide</a></body></html>"#,
+        )
+        .unwrap();
+
+        process_html_file_complete(&page_path, &base_dir, &page_url, &SiteProfile::default())
+            .unwrap();
+
+        let processed = fs::read_to_string(&page_path).unwrap();
+        assert!(
+            processed.contains(r#"href="../docs/guide.html""#),
+            "{}",
+            processed
+        );
+        assert!(!processed.contains("<base"), "{}", processed);
+    }
+
+    #[test]
+    fn test_postprocessing_removes_meta_refresh() {
+        use msc::core::wget::{process_html_file_complete, SiteProfile};
+        use url::Url;
+
+        let temp = tempdir().unwrap();
+        let base_dir = temp.path().to_path_buf();
+        let page_path = base_dir.join("example.com").join("index.html");
+        fs::create_dir_all(page_path.parent().unwrap()).unwrap();
+        fs::write(
+            &page_path,
+            r#"<html><head><META HTTP-EQUIV="refresh" CONTENT="0;url=https://example.com/next"><title>t</title></head></html>"#,
+        )
+        .unwrap();
+
+        let page_url = Url::parse("https://example.com/").unwrap();
+        process_html_file_complete(&page_path, &base_dir, &page_url, &SiteProfile::default())
+            .unwrap();
+
+        let processed = fs::read_to_string(&page_path).unwrap();
+        assert!(
+            !processed.to_lowercase().contains("refresh"),
+            "{}",
+            processed
+        );
+        assert!(processed.contains("<title>t</title>"));
+    }
 }
@@ -41,9 +41,13 @@ fn test_workspace_list_with_workspaces() {
     let workspaces = manager.list_workspaces();
 
     assert_eq!(workspaces.len(), 3);
-    assert!(workspaces.contains(&("project1".to_string(), "/path/to/project1".to_string())));
-    assert!(workspaces.contains(&("project2".to_string(), "/path/to/project2".to_string())));
-    assert!(workspaces.contains(&("website".to_string(), "/path/to/website".to_string())));
+    let pairs: Vec<(&str, &str)> = workspaces
+        .iter()
+        .map(|ws| (ws.name.as_str(), ws.path.as_str()))
+        .collect();
+    assert!(pairs.contains(&("project1", "/path/to/project1")));
+    assert!(pairs.contains(&("project2", "/path/to/project2")));
+    assert!(pairs.contains(&("website", "/path/to/website")));
 }
 
 #[test]
@@ -92,7 +96,7 @@ fn test_workspace_with_empty_name() {
     let workspaces = manager.list_workspaces();
 
     // Empty names should be handled (ideally rejected)
-    assert!(workspaces.iter().any(|(name, _)| name.is_empty()));
+    assert!(workspaces.iter().any(|ws| ws.name.is_empty()));
 }
 
 #[test]
@@ -133,9 +137,8 @@ fn test_workspace_duplicate_names() {
     assert_eq!(workspaces.len(), 1);
 
     // Should have the second path (overwritten)
-    let (name, path) = &workspaces[0];
-    assert_eq!(name, "project");
-    assert_eq!(path, "/path/to/project2");
+    assert_eq!(workspaces[0].name, "project");
+    assert_eq!(workspaces[0].path, "/path/to/project2");
 }
 
 #[test]
@@ -154,7 +157,7 @@ fn test_workspace_with_very_long_name() {
     let workspaces = manager.list_workspaces();
 
     assert_eq!(workspaces.len(), 1);
-    assert_eq!(workspaces[0].0, long_name);
+    assert_eq!(workspaces[0].name, long_name);
 }
 
 #[test]
@@ -260,7 +263,7 @@ fn test_workspace_map_ignores_hidden_directories() {
         let workspaces = manager.list_workspaces();
 
         // Should not include hidden directories
-        let hidden_found = workspaces.iter().any(|(name, _)| name.starts_with('.'));
+        let hidden_found = workspaces.iter().any(|ws| ws.name.starts_with('.'));
 
         assert!(!hidden_found, "Hidden directories should not be mapped");
     }
@@ -297,7 +300,7 @@ fn test_workspace_map_ignores_files() {
         );
 
         // Verify "project" is in the list
-        let has_project = workspaces.iter().any(|(name, _)| name == "project");
+        let has_project = workspaces.iter().any(|ws| ws.name == "project");
         assert!(has_project, "Expected to find 'project' directory");
     }
     // If mapping fails, test still passes (implementation may vary)
@@ -337,7 +340,7 @@ fn test_workspace_map_with_nested_directories() {
 
         // Should include the top-level 'project' directory
         assert!(
-            workspaces.iter().any(|(name, _)| name == "project"),
+            workspaces.iter().any(|ws| ws.name == "project"),
             "Expected to find 'project' directory"
         );
     }